@@ -20,6 +20,11 @@ impl Plugin for JackdawCameraPlugin {
 /// - Scroll wheel: move forward/back along view direction
 /// - Right-click + scroll: adjust camera speed
 /// - Shift (held): run speed multiplier
+///
+/// With `orbit` on, Alt turns the mouse buttons into turntable controls around `pivot`:
+/// - Alt + left-drag: orbit
+/// - Alt + middle-drag: pan (moves the pivot along)
+/// - Alt + right-drag: dolly toward or away from the pivot
 #[derive(Component)]
 pub struct JackdawCameraSettings {
     /// Mouse look sensitivity (radians per pixel).
@@ -32,6 +37,18 @@ pub struct JackdawCameraSettings {
     pub enabled: bool,
     /// Scroll movement speed (units per scroll line).
     pub scroll_speed: f32,
+    /// Whether Alt + mouse buttons orbit, pan and dolly around `pivot`.
+    pub orbit: bool,
+    /// Point the orbit controls turn around; the editor keeps it on the selection.
+    pub pivot: Vec3,
+}
+
+impl JackdawCameraSettings {
+    /// Whether Alt is held with orbit controls on, so mouse buttons navigate instead of
+    /// selecting or dragging.
+    pub fn orbit_modifier_held(&self, keyboard: &ButtonInput<KeyCode>) -> bool {
+        self.orbit && keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight])
+    }
 }
 
 impl Default for JackdawCameraSettings {
@@ -42,6 +59,8 @@ impl Default for JackdawCameraSettings {
             run_multiplier: 2.0,
             enabled: true,
             scroll_speed: 1.0,
+            orbit: false,
+            pivot: Vec3::ZERO,
         }
     }
 }
@@ -66,6 +85,17 @@ fn camera_system(
         let alt = keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
         let right_held = mouse.pressed(MouseButton::Right);
 
+        if settings.orbit_modifier_held(&keyboard)
+            && mouse.any_pressed([MouseButton::Left, MouseButton::Middle, MouseButton::Right])
+        {
+            let mouse_delta: Vec2 = mouse_motion.read().map(|motion| motion.delta).sum();
+            scroll_events.read().count();
+            if mouse_delta != Vec2::ZERO {
+                orbit_navigation(&mut settings, &mut transform, &mouse, mouse_delta);
+            }
+            continue;
+        }
+
         // Mouse look (only while right-click held)
         if right_held {
             let mut mouse_delta = Vec2::ZERO;
@@ -137,3 +167,39 @@ fn camera_system(
         }
     }
 }
+
+/// Alt + drag: orbit with the left button, pan with the middle, dolly with the right.
+fn orbit_navigation(
+    settings: &mut JackdawCameraSettings,
+    transform: &mut Transform,
+    mouse: &ButtonInput<MouseButton>,
+    delta: Vec2,
+) {
+    let offset = transform.translation - settings.pivot;
+    let distance = offset.length().max(0.1);
+
+    if mouse.pressed(MouseButton::Left) {
+        let yaw = Quat::from_rotation_y(-delta.x * settings.sensitivity);
+        let pitch =
+            Quat::from_axis_angle(transform.right().as_vec3(), -delta.y * settings.sensitivity);
+        // Stop short of looking straight up or down so the turntable doesn't flip
+        let pitched = pitch * transform.rotation;
+        let rotation = if (pitched * Vec3::NEG_Z).y.abs() < 0.99 {
+            yaw * pitch
+        } else {
+            yaw
+        };
+        transform.translation = settings.pivot + rotation * offset;
+        transform.rotation = (rotation * transform.rotation).normalize();
+    } else if mouse.pressed(MouseButton::Middle) {
+        // Scaled by distance so the pivot tracks the cursor at any zoom
+        let scale = distance * 0.0015;
+        let pan =
+            (transform.right().as_vec3() * -delta.x + transform.up().as_vec3() * delta.y) * scale;
+        transform.translation += pan;
+        settings.pivot += pan;
+    } else if mouse.pressed(MouseButton::Right) {
+        let new_distance = (distance * (1.0 + delta.y * 0.005)).max(0.1);
+        transform.translation = settings.pivot + offset.normalize_or_zero() * new_distance;
+    }
+}
@@ -1,34 +1,192 @@
-use bevy::prelude::*;
+use std::any::Any;
 
-pub trait EditorCommand: Send + Sync + 'static {
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+
+pub trait EditorCommand: Any + Send + Sync {
     fn execute(&self, world: &mut World);
     fn undo(&self, world: &mut World);
     fn description(&self) -> &str;
+
+    /// Whether `next` can be folded into this command with [`merge`](Self::merge).
+    /// Only consulted while a merge is open, see [`CommandHistory::begin_merge`].
+    fn can_merge(&self, _next: &dyn EditorCommand) -> bool {
+        false
+    }
+
+    /// Fold `next` (already executed) into this command so both undo as one step.
+    /// Only called after [`can_merge`](Self::can_merge) accepted `next`.
+    fn merge(&mut self, _next: &dyn EditorCommand) {}
+
+    /// Fold `next` into this command if [`can_merge`](Self::can_merge) accepts it.
+    /// Returns `false`, leaving this command untouched, if it doesn't.
+    fn try_merge(&mut self, next: &dyn EditorCommand) -> bool {
+        if !self.can_merge(next) {
+            return false;
+        }
+        self.merge(next);
+        true
+    }
+
+    /// Approximate heap and inline size of this command in bytes, used to bound
+    /// the history. Commands holding snapshots or large buffers should override it.
+    fn memory_size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+
+    /// Rewrite the entity ids this command holds after entities were respawned under
+    /// new ids, e.g. by undoing a delete. `map` takes old ids to new ones.
+    fn remap_entities(&mut self, _map: &EntityHashMap<Entity>) {}
 }
 
-#[derive(Resource, Default)]
+/// Replace `entity` with its new id if `map` has one.
+pub fn remap_entity(entity: &mut Entity, map: &EntityHashMap<Entity>) {
+    if let Some(&new) = map.get(entity) {
+        *entity = new;
+    }
+}
+
+/// Default for [`CommandHistory::max_depth`].
+pub const DEFAULT_HISTORY_DEPTH: usize = 200;
+/// Default for [`CommandHistory::max_memory`], in bytes.
+pub const DEFAULT_HISTORY_MEMORY: usize = 256 * 1024 * 1024;
+
+#[derive(Resource)]
 pub struct CommandHistory {
     pub undo_stack: Vec<Box<dyn EditorCommand>>,
     pub redo_stack: Vec<Box<dyn EditorCommand>>,
+    /// Most undo steps kept; the oldest are dropped beyond this.
+    pub max_depth: usize,
+    /// Approximate byte budget for both stacks; the oldest undo steps, then the furthest
+    /// redo steps, are dropped beyond this.
+    pub max_memory: usize,
+    /// Undo stack length when the open merge began.
+    merge_base: Option<usize>,
+    /// Undo and redo steps applied so far.
+    replays: u64,
+    /// New undo steps recorded so far.
+    pushes: u64,
+}
+
+impl Default for CommandHistory {
+    fn default() -> Self {
+        Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            max_depth: DEFAULT_HISTORY_DEPTH,
+            max_memory: DEFAULT_HISTORY_MEMORY,
+            merge_base: None,
+            replays: 0,
+            pushes: 0,
+        }
+    }
 }
 
 impl CommandHistory {
     pub fn execute(&mut self, command: Box<dyn EditorCommand>, world: &mut World) {
         command.execute(world);
-        self.undo_stack.push(command);
+        self.push_executed(command);
+    }
+
+    /// Record a command that has already been applied to the world. While a merge
+    /// is open, it is folded into the previous entry if that entry accepts it.
+    pub fn push_executed(&mut self, command: Box<dyn EditorCommand>) {
         self.redo_stack.clear();
+        if let Some(base) = self.merge_base
+            && self.undo_stack.len() > base
+            && let Some(last) = self.undo_stack.last_mut()
+            && last.try_merge(&*command)
+        {
+            return;
+        }
+        self.undo_stack.push(command);
+        self.pushes += 1;
+        self.trim();
+    }
+
+    /// Approximate bytes held by the undo and redo stacks.
+    pub fn memory_usage(&self) -> usize {
+        self.undo_stack
+            .iter()
+            .chain(&self.redo_stack)
+            .map(|cmd| cmd.memory_size())
+            .sum()
+    }
+
+    /// Drop the oldest undo steps until the history fits [`max_depth`](Self::max_depth)
+    /// and [`max_memory`](Self::max_memory). The most recent step is always kept; if
+    /// the history is still over budget, the furthest redo steps go too.
+    /// Returns the number of steps dropped.
+    pub fn trim(&mut self) -> usize {
+        let len = self.undo_stack.len();
+        let mut memory = self.memory_usage();
+        let mut count = 0;
+        while len - count > 1 && (len - count > self.max_depth || memory > self.max_memory) {
+            memory = memory.saturating_sub(self.undo_stack[count].memory_size());
+            count += 1;
+        }
+        if count > 0 {
+            self.undo_stack.drain(..count);
+            self.merge_base = self.merge_base.map(|base| base.saturating_sub(count));
+        }
+
+        // The redo stack's furthest step sits at the front
+        let mut redo_count = 0;
+        while redo_count < self.redo_stack.len() && memory > self.max_memory {
+            memory = memory.saturating_sub(self.redo_stack[redo_count].memory_size());
+            redo_count += 1;
+        }
+        self.redo_stack.drain(..redo_count);
+        count + redo_count
+    }
+
+    /// Start coalescing: commands pushed until [`end_merge`](Self::end_merge) fold
+    /// into one undo step where they can. Used for continuous edits like drags.
+    /// Does nothing if a merge is already open.
+    pub fn begin_merge(&mut self) {
+        if self.merge_base.is_none() {
+            self.merge_base = Some(self.undo_stack.len());
+        }
+    }
+
+    /// Finish the open merge; the next command starts a new undo step.
+    pub fn end_merge(&mut self) {
+        self.merge_base = None;
+    }
+
+    /// Point every recorded step at the new ids of respawned entities.
+    pub fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        for command in self.undo_stack.iter_mut().chain(&mut self.redo_stack) {
+            command.remap_entities(map);
+        }
+    }
+
+    /// Number of undo and redo steps applied so far. Lets systems watching the world
+    /// tell changes replayed from history apart from new edits.
+    pub fn replays(&self) -> u64 {
+        self.replays
+    }
+
+    /// Number of new undo steps recorded through [`push_executed`](Self::push_executed)
+    /// so far. Merges into the last step don't count. Lets systems tell whether an edit
+    /// was recorded since they last looked.
+    pub fn pushes(&self) -> u64 {
+        self.pushes
     }
 
     pub fn undo(&mut self, world: &mut World) {
+        self.end_merge();
         if let Some(command) = self.undo_stack.pop() {
             command.undo(world);
+            self.replays += 1;
             self.redo_stack.push(command);
         }
     }
 
     pub fn redo(&mut self, world: &mut World) {
+        self.end_merge();
         if let Some(command) = self.redo_stack.pop() {
             command.execute(world);
+            self.replays += 1;
             self.undo_stack.push(command);
         }
     }
@@ -55,4 +213,43 @@ impl EditorCommand for CommandGroup {
     fn description(&self) -> &str {
         &self.label
     }
+
+    /// Groups produced by the same edit merge pairwise, and only if every pair does.
+    fn can_merge(&self, next: &dyn EditorCommand) -> bool {
+        let Some(next) = (next as &dyn Any).downcast_ref::<CommandGroup>() else {
+            return false;
+        };
+        next.label == self.label
+            && next.commands.len() == self.commands.len()
+            && self
+                .commands
+                .iter()
+                .zip(&next.commands)
+                .all(|(cmd, next)| cmd.can_merge(&**next))
+    }
+
+    fn merge(&mut self, next: &dyn EditorCommand) {
+        let Some(next) = (next as &dyn Any).downcast_ref::<CommandGroup>() else {
+            return;
+        };
+        for (cmd, next) in self.commands.iter_mut().zip(&next.commands) {
+            cmd.merge(&**next);
+        }
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        for cmd in &mut self.commands {
+            cmd.remap_entities(map);
+        }
+    }
+
+    fn memory_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.label.len()
+            + self
+                .commands
+                .iter()
+                .map(|cmd| cmd.memory_size())
+                .sum::<usize>()
+    }
 }
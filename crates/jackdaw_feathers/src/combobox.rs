@@ -16,8 +16,10 @@ pub fn plugin(app: &mut App) {
             (
                 setup_combobox,
                 handle_combobox_popover_closed,
+                apply_external_selection,
                 sync_combobox_selection,
-            ),
+            )
+                .chain(),
         );
 }
 
@@ -433,6 +435,19 @@ fn handle_option_click(
     }
 }
 
+fn apply_external_selection(
+    mut combos: Query<
+        (&ComboBoxSelectedIndex, &mut ComboBoxConfig),
+        Changed<ComboBoxSelectedIndex>,
+    >,
+) {
+    for (index, mut config) in &mut combos {
+        if index.0 < config.options.len() {
+            config.selected = index.0;
+        }
+    }
+}
+
 fn sync_combobox_selection(
     mut combos: Query<(Entity, &ComboBoxConfig, &mut ComboBoxState)>,
     triggers: Query<(&ComboBoxTrigger, &Children)>,
@@ -13,13 +13,14 @@ pub struct HoverCursor(pub SystemCursorIcon);
 #[derive(Component)]
 pub struct ActiveCursor(pub SystemCursorIcon);
 
+/// The window the icon was last set on, and the icon.
 #[derive(Resource, Default)]
-struct ManagedCursor(Option<SystemCursorIcon>);
+struct ManagedCursor(Option<(Entity, SystemCursorIcon)>);
 
 fn update_cursors(
     active_cursors: Query<&ActiveCursor>,
     hover_cursors: Query<(&HoverCursor, &Hovered, Option<&ZIndex>)>,
-    window: Single<Entity, With<Window>>,
+    windows: Query<(Entity, &Window)>,
     mut commands: Commands,
     mut managed: ResMut<ManagedCursor>,
 ) {
@@ -33,17 +34,22 @@ fn update_cursors(
             .map(|(hover, _, _)| hover.0)
     };
 
+    // Panels can live in secondary windows; only the one under the pointer gets the icon
+    let hovered_window = windows
+        .iter()
+        .find(|(_, window)| window.cursor_position().is_some())
+        .map(|(entity, _)| entity);
+    let desired = hovered_window.zip(desired);
+
     if managed.0 == desired {
         return;
     }
 
-    match desired {
-        Some(icon) => {
-            commands.entity(*window).insert(CursorIcon::from(icon));
-        }
-        None => {
-            commands.entity(*window).remove::<CursorIcon>();
-        }
+    if let Some((window, _)) = managed.0 {
+        commands.entity(window).try_remove::<CursorIcon>();
+    }
+    if let Some((window, icon)) = desired {
+        commands.entity(window).insert(CursorIcon::from(icon));
     }
 
     managed.0 = desired;
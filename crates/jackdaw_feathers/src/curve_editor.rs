@@ -0,0 +1,556 @@
+use bevy::picking::events::{Click, Drag, DragEnd, DragStart};
+use bevy::prelude::*;
+use bevy::ui::UiGlobalTransform;
+use jackdaw_jsn::{FloatCurve, FloatCurveKey};
+
+use crate::combobox::{ComboBoxChangeEvent, combobox};
+use crate::tokens;
+
+const GRAPH_HEIGHT: f32 = 120.0;
+/// Points the curve is plotted with.
+const PLOT_SAMPLES: usize = 64;
+const PLOT_DOT_SIZE: f32 = 2.0;
+const KEY_SIZE: f32 = 8.0;
+const TANGENT_SIZE: f32 = 6.0;
+/// Horizontal distance, as a fraction of the graph width, of tangent handles from their key.
+const TANGENT_REACH: f32 = 0.08;
+
+pub fn plugin(app: &mut App) {
+    app.add_observer(on_handle_drag_start)
+        .add_observer(on_handle_drag)
+        .add_observer(on_handle_drag_end)
+        .add_observer(on_graph_click)
+        .add_systems(Update, (setup_curve_editor, update_curve_visuals).chain());
+}
+
+/// Starting shapes offered by the preset dropdown, over time and value 0..1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurvePreset {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    Constant,
+    Falloff,
+}
+
+impl CurvePreset {
+    pub const ALL: [Self; 6] = [
+        Self::Linear,
+        Self::EaseIn,
+        Self::EaseOut,
+        Self::EaseInOut,
+        Self::Constant,
+        Self::Falloff,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Linear => "Linear",
+            Self::EaseIn => "Ease In",
+            Self::EaseOut => "Ease Out",
+            Self::EaseInOut => "Ease In/Out",
+            Self::Constant => "Constant",
+            Self::Falloff => "Falloff",
+        }
+    }
+
+    /// The preset curve, scaled to the value range `min..max`.
+    pub fn curve(self, min: f32, max: f32) -> FloatCurve {
+        let range = max - min;
+        let key = |time: f32, value: f32, in_tangent: f32, out_tangent: f32| FloatCurveKey {
+            time,
+            value: min + value * range,
+            in_tangent: in_tangent * range,
+            out_tangent: out_tangent * range,
+        };
+        let keys = match self {
+            Self::Linear => vec![key(0.0, 0.0, 1.0, 1.0), key(1.0, 1.0, 1.0, 1.0)],
+            Self::EaseIn => vec![key(0.0, 0.0, 0.0, 0.0), key(1.0, 1.0, 2.0, 2.0)],
+            Self::EaseOut => vec![key(0.0, 0.0, 2.0, 2.0), key(1.0, 1.0, 0.0, 0.0)],
+            Self::EaseInOut => vec![key(0.0, 0.0, 0.0, 0.0), key(1.0, 1.0, 0.0, 0.0)],
+            Self::Constant => vec![key(0.0, 1.0, 0.0, 0.0), key(1.0, 1.0, 0.0, 0.0)],
+            Self::Falloff => vec![key(0.0, 1.0, 0.0, 0.0), key(1.0, 0.0, -2.0, -2.0)],
+        };
+        FloatCurve { keys }
+    }
+}
+
+/// Fired on the editor while keys are dragged.
+#[derive(EntityEvent)]
+pub struct CurveEditorChangeEvent {
+    pub entity: Entity,
+    pub curve: FloatCurve,
+}
+
+/// Fired on the editor when an edit is finished: a drag released, a key added or
+/// removed, or a preset picked.
+#[derive(EntityEvent)]
+pub struct CurveEditorCommitEvent {
+    pub entity: Entity,
+    pub curve: FloatCurve,
+}
+
+#[derive(Component)]
+pub struct EditorCurveEditor;
+
+/// The curve being edited, with keys sorted by time.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct CurveEditorState {
+    pub curve: FloatCurve,
+}
+
+pub struct CurveEditorProps {
+    pub curve: FloatCurve,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl Default for CurveEditorProps {
+    fn default() -> Self {
+        Self {
+            curve: CurvePreset::Linear.curve(0.0, 1.0),
+            min: 0.0,
+            max: 1.0,
+        }
+    }
+}
+
+impl CurveEditorProps {
+    pub fn with_curve(mut self, curve: FloatCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Value range shown by the graph; time always spans 0..1.
+    pub fn with_range(mut self, min: f32, max: f32) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+}
+
+/// A graph of a float over time 0..1. Drag keys and their tangent handles, click the
+/// graph to add a key, right-click a key to remove it, or start over from a preset.
+pub fn curve_editor(props: CurveEditorProps) -> impl Bundle {
+    let CurveEditorProps {
+        mut curve,
+        min,
+        max,
+    } = props;
+    curve.keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+    (
+        EditorCurveEditor,
+        CurveEditorState { curve },
+        CurveEditorConfig {
+            min,
+            max,
+            initialized: false,
+        },
+        Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: px(tokens::SPACING_SM),
+            width: percent(100),
+            ..default()
+        },
+    )
+}
+
+#[derive(Component)]
+struct CurveEditorConfig {
+    min: f32,
+    max: f32,
+    initialized: bool,
+}
+
+impl CurveEditorConfig {
+    fn to_graph(&self, time: f32, value: f32) -> Vec2 {
+        let range = (self.max - self.min).max(f32::EPSILON);
+        Vec2::new(time, (value - self.min) / range)
+    }
+
+    fn graph_to_curve(&self, point: Vec2) -> (f32, f32) {
+        (point.x, self.min + point.y * (self.max - self.min))
+    }
+}
+
+#[derive(Component)]
+struct CurveGraph(Entity);
+
+#[derive(Component)]
+struct CurvePlotDot {
+    index: usize,
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum HandleKind {
+    Key,
+    InTangent,
+    OutTangent,
+}
+
+#[derive(Component)]
+struct CurveHandle {
+    editor: Entity,
+    graph: Entity,
+    index: usize,
+    kind: HandleKind,
+}
+
+#[derive(Component)]
+struct DraggingHandle;
+
+fn setup_curve_editor(
+    mut commands: Commands,
+    mut editors: Query<(Entity, &mut CurveEditorConfig)>,
+) {
+    for (editor, mut config) in &mut editors {
+        if config.initialized {
+            continue;
+        }
+        config.initialized = true;
+
+        let graph = commands
+            .spawn((
+                CurveGraph(editor),
+                Node {
+                    width: percent(100),
+                    height: px(GRAPH_HEIGHT),
+                    border: UiRect::all(px(1)),
+                    border_radius: BorderRadius::all(px(tokens::BORDER_RADIUS_SM)),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                BackgroundColor(tokens::INPUT_BG),
+                BorderColor::all(tokens::BORDER_SUBTLE),
+                ChildOf(editor),
+            ))
+            .id();
+        // Midlines for reference
+        for horizontal in [true, false] {
+            commands.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: if horizontal { px(0) } else { percent(50) },
+                    top: if horizontal { percent(50) } else { px(0) },
+                    width: if horizontal { percent(100) } else { px(1) },
+                    height: if horizontal { px(1) } else { percent(100) },
+                    ..default()
+                },
+                BackgroundColor(tokens::BORDER_SUBTLE),
+                Pickable::IGNORE,
+                ChildOf(graph),
+            ));
+        }
+        for index in 0..PLOT_SAMPLES {
+            commands.spawn((
+                CurvePlotDot { index },
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: px(PLOT_DOT_SIZE),
+                    height: px(PLOT_DOT_SIZE),
+                    margin: UiRect::all(px(-PLOT_DOT_SIZE / 2.0)),
+                    ..default()
+                },
+                BackgroundColor(tokens::TEXT_ACCENT),
+                Pickable::IGNORE,
+                ChildOf(graph),
+            ));
+        }
+
+        let options: Vec<String> = std::iter::once("Preset...".to_string())
+            .chain(CurvePreset::ALL.iter().map(|p| p.label().to_string()))
+            .collect();
+        commands
+            .spawn((combobox(options), ChildOf(editor)))
+            .observe(
+            move |event: On<ComboBoxChangeEvent>,
+                  mut commands: Commands,
+                  mut editors: Query<(&CurveEditorConfig, &mut CurveEditorState)>| {
+                let Some(preset) = event
+                    .selected
+                    .checked_sub(1)
+                    .and_then(|i| CurvePreset::ALL.get(i))
+                else {
+                    return;
+                };
+                let Ok((config, mut state)) = editors.get_mut(editor) else {
+                    return;
+                };
+                state.curve = preset.curve(config.min, config.max);
+                commands.trigger(CurveEditorCommitEvent {
+                    entity: editor,
+                    curve: state.curve.clone(),
+                });
+            },
+        );
+    }
+}
+
+fn handle_node(kind: HandleKind) -> (Node, BackgroundColor, BorderColor) {
+    let size = match kind {
+        HandleKind::Key => KEY_SIZE,
+        _ => TANGENT_SIZE,
+    };
+    let radius = match kind {
+        HandleKind::Key => px(1),
+        _ => percent(50),
+    };
+    (
+        Node {
+            position_type: PositionType::Absolute,
+            width: px(size),
+            height: px(size),
+            margin: UiRect::all(px(-size / 2.0)),
+            border: UiRect::all(px(1)),
+            border_radius: BorderRadius::all(radius),
+            ..default()
+        },
+        BackgroundColor(match kind {
+            HandleKind::Key => tokens::TEXT_PRIMARY,
+            _ => tokens::PANEL_BG,
+        }),
+        BorderColor::all(tokens::TEXT_ACCENT),
+    )
+}
+
+/// Where a handle sits in graph space (0..1 both ways, y up).
+fn handle_position(config: &CurveEditorConfig, key: &FloatCurveKey, kind: HandleKind) -> Vec2 {
+    let at = config.to_graph(key.time, key.value);
+    let range = (config.max - config.min).max(f32::EPSILON);
+    match kind {
+        HandleKind::Key => at,
+        HandleKind::InTangent => {
+            at - Vec2::new(TANGENT_REACH, key.in_tangent / range * TANGENT_REACH)
+        }
+        HandleKind::OutTangent => {
+            at + Vec2::new(TANGENT_REACH, key.out_tangent / range * TANGENT_REACH)
+        }
+    }
+}
+
+fn place(node: &mut Node, point: Vec2) {
+    node.left = percent(point.x * 100.0);
+    node.top = percent((1.0 - point.y) * 100.0);
+}
+
+fn update_curve_visuals(
+    mut commands: Commands,
+    editors: Query<
+        (Entity, &CurveEditorConfig, &CurveEditorState, &Children),
+        Or<(Changed<CurveEditorState>, Changed<CurveEditorConfig>)>,
+    >,
+    graphs: Query<(Entity, &Children), With<CurveGraph>>,
+    mut dots: Query<(&CurvePlotDot, &mut Node), Without<CurveHandle>>,
+    mut handles: Query<(Entity, &CurveHandle, &mut Node), Without<CurvePlotDot>>,
+) {
+    for (editor, config, state, children) in &editors {
+        let Some((graph, graph_children)) = children.iter().find_map(|c| graphs.get(c).ok()) else {
+            continue;
+        };
+
+        for child in graph_children.iter() {
+            let Ok((dot, mut node)) = dots.get_mut(child) else {
+                continue;
+            };
+            let time = dot.index as f32 / (PLOT_SAMPLES - 1) as f32;
+            let value = state.curve.sample(time);
+            place(
+                &mut node,
+                config.to_graph(time, value).clamp(Vec2::ZERO, Vec2::ONE),
+            );
+        }
+
+        // Handles are kept while the key count stays the same, so a drag isn't cut short
+        let existing: Vec<Entity> = graph_children
+            .iter()
+            .filter(|&c| handles.contains(c))
+            .collect();
+        if existing.len() != state.curve.keys.len() * 3 {
+            for handle in existing {
+                commands.entity(handle).despawn();
+            }
+            for (index, key) in state.curve.keys.iter().enumerate() {
+                for kind in [
+                    HandleKind::InTangent,
+                    HandleKind::OutTangent,
+                    HandleKind::Key,
+                ] {
+                    let (mut node, background, border) = handle_node(kind);
+                    place(&mut node, handle_position(config, key, kind));
+                    commands.spawn((
+                        CurveHandle {
+                            editor,
+                            graph,
+                            index,
+                            kind,
+                        },
+                        node,
+                        background,
+                        border,
+                        ChildOf(graph),
+                    ));
+                }
+            }
+            continue;
+        }
+        for handle in existing {
+            let Ok((_, curve_handle, mut node)) = handles.get_mut(handle) else {
+                continue;
+            };
+            if let Some(key) = state.curve.keys.get(curve_handle.index) {
+                place(&mut node, handle_position(config, key, curve_handle.kind));
+            }
+        }
+    }
+}
+
+/// Pointer position in graph space, from a pointer event's window position.
+fn graph_point(
+    graphs: &Query<(&ComputedNode, &UiGlobalTransform), With<CurveGraph>>,
+    graph: Entity,
+    position: Vec2,
+) -> Option<Vec2> {
+    let (computed, transform) = graphs.get(graph).ok()?;
+    let normalized =
+        computed.normalize_point(*transform, position / computed.inverse_scale_factor)?;
+    Some(Vec2::new(normalized.x + 0.5, 0.5 - normalized.y))
+}
+
+fn on_handle_drag_start(
+    event: On<Pointer<DragStart>>,
+    mut commands: Commands,
+    handles: Query<(), With<CurveHandle>>,
+) {
+    if handles.contains(event.event_target()) {
+        commands.entity(event.event_target()).insert(DraggingHandle);
+    }
+}
+
+fn on_handle_drag(
+    mut event: On<Pointer<Drag>>,
+    mut commands: Commands,
+    handles: Query<&CurveHandle, With<DraggingHandle>>,
+    graphs: Query<(&ComputedNode, &UiGlobalTransform), With<CurveGraph>>,
+    mut editors: Query<(&CurveEditorConfig, &mut CurveEditorState)>,
+) {
+    let Ok(handle) = handles.get(event.event_target()) else {
+        return;
+    };
+    event.propagate(false);
+    let Some(point) = graph_point(&graphs, handle.graph, event.pointer_location.position) else {
+        return;
+    };
+    let Ok((config, mut state)) = editors.get_mut(handle.editor) else {
+        return;
+    };
+    let index = handle.index;
+    let previous = index
+        .checked_sub(1)
+        .and_then(|i| state.curve.keys.get(i))
+        .map(|k| k.time);
+    let next = state.curve.keys.get(index + 1).map(|k| k.time);
+    let Some(key) = state.curve.keys.get_mut(index) else {
+        return;
+    };
+    let (time, value) = config.graph_to_curve(point.clamp(Vec2::ZERO, Vec2::ONE));
+    match handle.kind {
+        HandleKind::Key => {
+            // Keys stay in order: each is held between its neighbours
+            key.time = time.clamp(previous.unwrap_or(0.0), next.unwrap_or(1.0));
+            key.value = value;
+        }
+        HandleKind::InTangent | HandleKind::OutTangent => {
+            let (_, unclamped) = config.graph_to_curve(point);
+            let dt = (time - key.time).abs().max(0.01);
+            let slope = (unclamped - key.value) / dt;
+            let slope = if handle.kind == HandleKind::InTangent {
+                -slope
+            } else {
+                slope
+            };
+            key.in_tangent = slope;
+            key.out_tangent = slope;
+        }
+    }
+    commands.trigger(CurveEditorChangeEvent {
+        entity: handle.editor,
+        curve: state.curve.clone(),
+    });
+}
+
+fn on_handle_drag_end(
+    event: On<Pointer<DragEnd>>,
+    mut commands: Commands,
+    handles: Query<&CurveHandle, With<DraggingHandle>>,
+    editors: Query<&CurveEditorState>,
+) {
+    let Ok(handle) = handles.get(event.event_target()) else {
+        return;
+    };
+    commands
+        .entity(event.event_target())
+        .remove::<DraggingHandle>();
+    if let Ok(state) = editors.get(handle.editor) {
+        commands.trigger(CurveEditorCommitEvent {
+            entity: handle.editor,
+            curve: state.curve.clone(),
+        });
+    }
+}
+
+/// Left-click on the graph adds a key on the curve; right-click on a key removes it.
+fn on_graph_click(
+    mut event: On<Pointer<Click>>,
+    mut commands: Commands,
+    graph_markers: Query<&CurveGraph>,
+    handles: Query<&CurveHandle>,
+    graphs: Query<(&ComputedNode, &UiGlobalTransform), With<CurveGraph>>,
+    mut editors: Query<(&CurveEditorConfig, &mut CurveEditorState)>,
+) {
+    let target = event.event_target();
+    let editor = if let Ok(handle) = handles.get(target) {
+        event.propagate(false);
+        if event.button != PointerButton::Secondary || handle.kind != HandleKind::Key {
+            return;
+        }
+        let Ok((_, mut state)) = editors.get_mut(handle.editor) else {
+            return;
+        };
+        // A curve keeps at least one key
+        if state.curve.keys.len() <= 1 || handle.index >= state.curve.keys.len() {
+            return;
+        }
+        state.curve.keys.remove(handle.index);
+        handle.editor
+    } else if let Ok(graph) = graph_markers.get(target) {
+        if event.button != PointerButton::Primary {
+            return;
+        }
+        let Some(point) = graph_point(&graphs, target, event.pointer_location.position) else {
+            return;
+        };
+        let Ok((config, mut state)) = editors.get_mut(graph.0) else {
+            return;
+        };
+        let (time, value) = config.graph_to_curve(point.clamp(Vec2::ZERO, Vec2::ONE));
+        let index = state.curve.keys.partition_point(|k| k.time < time);
+        state.curve.keys.insert(
+            index,
+            FloatCurveKey {
+                time,
+                value,
+                ..default()
+            },
+        );
+        graph.0
+    } else {
+        return;
+    };
+    if let Ok((_, state)) = editors.get(editor) {
+        commands.trigger(CurveEditorCommitEvent {
+            entity: editor,
+            curve: state.curve.clone(),
+        });
+    }
+}
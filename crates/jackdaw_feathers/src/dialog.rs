@@ -16,9 +16,11 @@ pub fn plugin(app: &mut App) {
     app.add_observer(on_open_dialog)
         .add_observer(on_open_confirmation_dialog)
         .add_observer(on_action_button_click)
+        .add_observer(on_secondary_button_click)
         .add_observer(on_cancel_button_click)
         .add_observer(on_close_button_click)
         .add_observer(on_close_dialog)
+        .add_observer(on_dialog_removed)
         .add_systems(
             Update,
             (
@@ -47,9 +49,17 @@ struct DialogCancelButton;
 #[derive(Component)]
 struct DialogActionButton;
 
+#[derive(Component)]
+struct DialogSecondaryButton;
+
 #[derive(Component)]
 pub struct DialogChildrenSlot;
 
+/// Names the feature a dialog belongs to. Set on the dialog and its children slot, so a
+/// feature fills and answers only its own dialog.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DialogId(pub &'static str);
+
 #[derive(Component, Default, Clone, Copy)]
 pub enum DialogVariant {
     #[default]
@@ -75,6 +85,21 @@ struct DialogConfig {
 #[derive(EntityEvent)]
 pub struct DialogActionEvent {
     pub entity: Entity,
+    pub id: Option<&'static str>,
+}
+
+/// Fired when the optional secondary button (between Cancel and the action) is clicked.
+#[derive(EntityEvent)]
+pub struct DialogSecondaryActionEvent {
+    pub entity: Entity,
+    pub id: Option<&'static str>,
+}
+
+/// Fired when a dialog goes away, whether through its action, Cancel, Esc, a backdrop
+/// click, or [`CloseDialogEvent`].
+#[derive(Event)]
+pub struct DialogClosedEvent {
+    pub id: Option<&'static str>,
 }
 
 #[derive(Event)]
@@ -82,9 +107,12 @@ pub struct CloseDialogEvent;
 
 #[derive(Event)]
 pub struct OpenDialogEvent {
+    pub id: Option<&'static str>,
     pub title: Option<String>,
     pub description: Option<String>,
     pub action: Option<String>,
+    /// Extra footer button firing [`DialogSecondaryActionEvent`], e.g. "Discard".
+    pub secondary: Option<String>,
     pub cancel: Option<String>,
     pub variant: DialogVariant,
     pub has_close_button: bool,
@@ -92,14 +120,17 @@ pub struct OpenDialogEvent {
     pub close_on_esc: bool,
     pub max_width: Option<Val>,
     pub content_padding: UiRect,
+    pub backdrop_opacity: f32,
 }
 
 impl OpenDialogEvent {
     pub fn new(title: impl Into<String>, action: impl Into<String>) -> Self {
         Self {
+            id: None,
             title: Some(title.into()),
             description: None,
             action: Some(action.into()),
+            secondary: None,
             cancel: Some("Cancel".into()),
             variant: DialogVariant::Default,
             has_close_button: true,
@@ -107,14 +138,25 @@ impl OpenDialogEvent {
             close_on_esc: true,
             max_width: None,
             content_padding: UiRect::all(px(24)),
+            backdrop_opacity: BACKDROP_OPACITY,
         }
     }
 
+    pub fn with_id(mut self, id: &'static str) -> Self {
+        self.id = Some(id);
+        self
+    }
+
     pub fn without_cancel(mut self) -> Self {
         self.cancel = None;
         self
     }
 
+    pub fn with_secondary(mut self, secondary: impl Into<String>) -> Self {
+        self.secondary = Some(secondary.into());
+        self
+    }
+
     pub fn with_variant(mut self, variant: DialogVariant) -> Self {
         self.variant = variant;
         self
@@ -139,10 +181,17 @@ impl OpenDialogEvent {
         self.content_padding = UiRect::ZERO;
         self
     }
+
+    /// Lighter backdrop for dialogs that preview their effect in the viewport behind them.
+    pub fn with_backdrop_opacity(mut self, backdrop_opacity: f32) -> Self {
+        self.backdrop_opacity = backdrop_opacity;
+        self
+    }
 }
 
 #[derive(Event)]
 pub struct OpenConfirmationDialogEvent {
+    pub id: Option<&'static str>,
     pub title: String,
     pub description: Option<String>,
     pub action: String,
@@ -151,12 +200,18 @@ pub struct OpenConfirmationDialogEvent {
 impl OpenConfirmationDialogEvent {
     pub fn new(title: impl Into<String>, action: impl Into<String>) -> Self {
         Self {
+            id: None,
             title: title.into(),
             description: None,
             action: action.into(),
         }
     }
 
+    pub fn with_id(mut self, id: &'static str) -> Self {
+        self.id = Some(id);
+        self
+    }
+
     pub fn with_description(mut self, description: impl Into<String>) -> Self {
         self.description = Some(description.into());
         self
@@ -169,6 +224,7 @@ impl From<&OpenConfirmationDialogEvent> for OpenDialogEvent {
             .with_variant(DialogVariant::Destructive)
             .with_close_button(false)
             .with_close_on_click_outside(false);
+        dialog.id = event.id;
         dialog.description = event.description.clone();
         dialog
     }
@@ -221,12 +277,12 @@ fn spawn_dialog(
                 align_items: AlignItems::Center,
                 ..default()
             },
-            BackgroundColor(Color::BLACK.with_alpha(BACKDROP_OPACITY)),
+            BackgroundColor(Color::BLACK.with_alpha(event.backdrop_opacity)),
         ))
         .id();
 
     let has_header = event.title.is_some() || event.description.is_some();
-    let has_footer = event.action.is_some() || event.cancel.is_some();
+    let has_footer = event.action.is_some() || event.secondary.is_some() || event.cancel.is_some();
 
     let header_id = if has_header {
         let mut header = commands.spawn((
@@ -282,6 +338,10 @@ fn spawn_dialog(
             footer.with_child((DialogCancelButton, button(ButtonProps::new(cancel))));
         }
 
+        if let Some(secondary) = &event.secondary {
+            footer.with_child((DialogSecondaryButton, button(ButtonProps::new(secondary))));
+        }
+
         if let Some(action) = &event.action {
             footer.with_child((
                 DialogActionButton,
@@ -315,18 +375,22 @@ fn spawn_dialog(
         panel.add_child(header_id);
     }
 
-    panel.with_child((
-        DialogChildrenSlot,
-        Node {
-            display: Display::None,
-            padding: event.content_padding,
-            border: UiRect::bottom(px(1)),
-            flex_direction: FlexDirection::Column,
-            row_gap: px(12),
-            ..default()
-        },
-        BorderColor::all(BORDER_COLOR),
-    ));
+    let slot_id = panel
+        .commands()
+        .spawn((
+            DialogChildrenSlot,
+            Node {
+                display: Display::None,
+                padding: event.content_padding,
+                border: UiRect::bottom(px(1)),
+                flex_direction: FlexDirection::Column,
+                row_gap: px(12),
+                ..default()
+            },
+            BorderColor::all(BORDER_COLOR),
+        ))
+        .id();
+    panel.add_child(slot_id);
 
     if let Some(footer_id) = footer_id {
         panel.add_child(footer_id);
@@ -353,24 +417,27 @@ fn spawn_dialog(
     let panel_id = panel.id();
     commands.entity(backdrop_id).add_child(panel_id);
 
-    commands
-        .spawn((
-            EditorDialog,
-            event.variant,
-            DialogConfig {
-                close_on_click_outside: event.close_on_click_outside,
-                close_on_esc: event.close_on_esc,
-            },
-            Node {
-                width: percent(100),
-                height: percent(100),
-                position_type: PositionType::Absolute,
-                ..default()
-            },
-            GlobalZIndex(200),
-            Pickable::IGNORE,
-        ))
-        .add_child(backdrop_id);
+    let mut dialog = commands.spawn((
+        EditorDialog,
+        event.variant,
+        DialogConfig {
+            close_on_click_outside: event.close_on_click_outside,
+            close_on_esc: event.close_on_esc,
+        },
+        Node {
+            width: percent(100),
+            height: percent(100),
+            position_type: PositionType::Absolute,
+            ..default()
+        },
+        GlobalZIndex(200),
+        Pickable::IGNORE,
+    ));
+    dialog.add_child(backdrop_id);
+    if let Some(id) = event.id {
+        dialog.insert(DialogId(id));
+        commands.entity(slot_id).insert(DialogId(id));
+    }
 }
 
 fn dismiss_dialog(commands: &mut Commands, entity: Entity) {
@@ -442,11 +509,21 @@ fn on_close_dialog(
     }
 }
 
+fn on_dialog_removed(
+    event: On<Remove, EditorDialog>,
+    ids: Query<&DialogId>,
+    mut commands: Commands,
+) {
+    let id = ids.get(event.entity).ok().map(|id| id.0);
+    commands.trigger(DialogClosedEvent { id });
+}
+
 fn on_action_button_click(
     event: On<ButtonClickEvent>,
     action_buttons: Query<&ChildOf, With<DialogActionButton>>,
     parents: Query<&ChildOf>,
     dialogs: Query<Entity, With<EditorDialog>>,
+    ids: Query<&DialogId>,
     mut commands: Commands,
 ) {
     let Ok(button_parent) = action_buttons.get(event.entity) else {
@@ -456,6 +533,28 @@ fn on_action_button_click(
     if let Some(dialog_entity) = find_dialog_ancestor(button_parent.parent(), &parents, &dialogs) {
         commands.trigger(DialogActionEvent {
             entity: dialog_entity,
+            id: ids.get(dialog_entity).ok().map(|id| id.0),
+        });
+        dismiss_dialog(&mut commands, dialog_entity);
+    }
+}
+
+fn on_secondary_button_click(
+    event: On<ButtonClickEvent>,
+    secondary_buttons: Query<&ChildOf, With<DialogSecondaryButton>>,
+    parents: Query<&ChildOf>,
+    dialogs: Query<Entity, With<EditorDialog>>,
+    ids: Query<&DialogId>,
+    mut commands: Commands,
+) {
+    let Ok(button_parent) = secondary_buttons.get(event.entity) else {
+        return;
+    };
+
+    if let Some(dialog_entity) = find_dialog_ancestor(button_parent.parent(), &parents, &dialogs) {
+        commands.trigger(DialogSecondaryActionEvent {
+            entity: dialog_entity,
+            id: ids.get(dialog_entity).ok().map(|id| id.0),
         });
         dismiss_dialog(&mut commands, dialog_entity);
     }
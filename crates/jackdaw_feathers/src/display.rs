@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+
+use crate::tokens;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<DisplayScale>()
+        .add_systems(PostUpdate, (apply_ui_scale, scale_fonts));
+}
+
+/// Display settings applied across the widgets: `ui_scale` multiplies every size,
+/// `font_size` replaces [`tokens::TEXT_SIZE`] with the other text sizes scaled along.
+#[derive(Resource, Clone, Copy, PartialEq, Debug)]
+pub struct DisplayScale {
+    pub ui_scale: f32,
+    pub font_size: f32,
+}
+
+impl Default for DisplayScale {
+    fn default() -> Self {
+        Self {
+            ui_scale: 1.0,
+            font_size: tokens::TEXT_SIZE,
+        }
+    }
+}
+
+impl DisplayScale {
+    pub fn clamped(self) -> Self {
+        Self {
+            ui_scale: self
+                .ui_scale
+                .clamp(tokens::UI_SCALE_MIN, tokens::UI_SCALE_MAX),
+            font_size: self
+                .font_size
+                .clamp(tokens::FONT_SIZE_MIN, tokens::FONT_SIZE_MAX),
+        }
+    }
+
+    /// Factor applied to the token text sizes.
+    pub fn font_scale(&self) -> f32 {
+        self.font_size / tokens::TEXT_SIZE
+    }
+}
+
+/// The size a text was spawned with, before the font scale was applied.
+#[derive(Component)]
+struct BaseFontSize(f32);
+
+fn apply_ui_scale(display: Res<DisplayScale>, mut ui_scale: ResMut<UiScale>) {
+    if display.is_changed() && ui_scale.0 != display.ui_scale {
+        ui_scale.0 = display.ui_scale;
+    }
+}
+
+fn scale_fonts(
+    mut commands: Commands,
+    display: Res<DisplayScale>,
+    mut added: Query<(Entity, &mut TextFont), Without<BaseFontSize>>,
+    mut scaled: Query<(&BaseFontSize, &mut TextFont)>,
+) {
+    let scale = display.font_scale();
+    for (entity, mut font) in &mut added {
+        commands.entity(entity).insert(BaseFontSize(font.font_size));
+        if scale != 1.0 {
+            font.font_size *= scale;
+        }
+    }
+    if display.is_changed() {
+        for (base, mut font) in &mut scaled {
+            font.font_size = base.0 * scale;
+        }
+    }
+}
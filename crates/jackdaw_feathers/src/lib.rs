@@ -6,7 +6,9 @@ pub mod color_picker;
 pub mod combobox;
 pub mod context_menu;
 pub mod cursor;
+pub mod curve_editor;
 pub mod dialog;
+pub mod display;
 pub mod file_browser;
 pub mod icons;
 pub mod inspector_field;
@@ -57,6 +59,8 @@ impl Plugin for EditorFeathersPlugin {
             color_picker::plugin,
             menu_bar::plugin,
             context_menu::plugin,
+            curve_editor::plugin,
+            display::plugin,
         ));
     }
 }
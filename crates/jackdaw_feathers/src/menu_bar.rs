@@ -1,7 +1,9 @@
 use bevy::{feathers::theme::ThemedText, prelude::*, ui::ui_transform::UiGlobalTransform};
 use jackdaw_widgets::menu_bar::{
     MenuAction, MenuBar, MenuBarDropdown, MenuBarDropdownItem, MenuBarItem, MenuBarState,
+    MenuBarSubmenu, MenuBarSubmenuItem,
 };
+use lucide_icons::Icon;
 
 use crate::button::{ButtonClickEvent, ButtonProps, ButtonVariant, button};
 use crate::tokens;
@@ -10,7 +12,8 @@ pub fn plugin(app: &mut App) {
     app.add_observer(on_dropdown_item_click)
         .add_observer(on_menu_bar_item_click)
         .add_observer(on_menu_bar_item_over)
-        .add_observer(on_menu_bar_item_out);
+        .add_observer(on_menu_bar_item_out)
+        .add_observer(on_dropdown_entry_over);
 }
 
 /// When a dropdown item is clicked, fire the MenuAction.
@@ -49,6 +52,7 @@ fn on_menu_bar_item_click(
     if let Some(dropdown) = state.dropdown_entity.take() {
         commands.entity(dropdown).despawn();
     }
+    state.submenu = None;
 
     if state.open_menu == Some(entity) {
         // Toggle off
@@ -64,7 +68,7 @@ fn on_menu_bar_item_click(
     let x = pos.x - size.x / 2.0;
     let y = pos.y + size.y / 2.0;
 
-    let dropdown = spawn_dropdown(&mut commands, x, y, &item.actions);
+    let dropdown = spawn_dropdown(&mut commands, x, y, item);
     state.dropdown_entity = Some(dropdown);
 }
 
@@ -94,6 +98,87 @@ fn on_menu_bar_item_out(
     }
 }
 
+/// Open the submenu of the hovered dropdown entry, closing any other one.
+fn on_dropdown_entry_over(
+    hover: On<Pointer<Over>>,
+    mut commands: Commands,
+    mut state: ResMut<MenuBarState>,
+    menus: Query<&MenuBarItem>,
+    submenu_items: Query<&MenuBarSubmenuItem>,
+    parents: Query<&ChildOf>,
+    layout: Query<(&ComputedNode, &UiGlobalTransform)>,
+) {
+    let Some(dropdown) = state.dropdown_entity else {
+        return;
+    };
+    // The dropdown row under the pointer: the ancestor parented directly to the dropdown.
+    let mut row = hover.event_target();
+    loop {
+        match parents.get(row) {
+            Ok(child_of) if child_of.parent() == dropdown => break,
+            Ok(child_of) => row = child_of.parent(),
+            Err(_) => return,
+        }
+    }
+
+    if let Some((owner, submenu)) = state.submenu {
+        if row == owner || row == submenu {
+            return;
+        }
+        commands.entity(submenu).despawn();
+        state.submenu = None;
+    }
+
+    let Ok(opener) = submenu_items.get(row) else {
+        return;
+    };
+    let Some(entries) = state
+        .open_menu
+        .and_then(|menu| menus.get(menu).ok())
+        .and_then(|menu| menu.submenu(&opener.action))
+    else {
+        return;
+    };
+    let (Ok((row_node, row_tf)), Ok((dropdown_node, dropdown_tf))) =
+        (layout.get(row), layout.get(dropdown))
+    else {
+        return;
+    };
+    let (_, _, row_pos) = row_tf.to_scale_angle_translation();
+    let (_, _, dropdown_pos) = dropdown_tf.to_scale_angle_translation();
+    let row_top = row_pos.y - row_node.size().y / 2.0;
+    let dropdown_top = dropdown_pos.y - dropdown_node.size().y / 2.0;
+    // Line the first submenu entry up with the row, inside the border and padding.
+    let top = (row_top - dropdown_top) * row_node.inverse_scale_factor() - 1.0 - tokens::SPACING_SM;
+
+    let submenu = commands
+        .spawn((
+            MenuBarSubmenu,
+            dropdown_container(Val::Percent(100.0), Val::Px(top)),
+            ChildOf(dropdown),
+        ))
+        .id();
+    spawn_dropdown_entries(&mut commands, submenu, entries, &[]);
+    if entries.is_empty() {
+        commands.spawn((
+            Node {
+                padding: UiRect::axes(Val::Px(tokens::SPACING_MD), Val::Px(tokens::SPACING_XS)),
+                ..Default::default()
+            },
+            children![(
+                Text::new("Empty"),
+                TextFont {
+                    font_size: tokens::FONT_MD,
+                    ..Default::default()
+                },
+                TextColor(tokens::TEXT_SECONDARY),
+            )],
+            ChildOf(submenu),
+        ));
+    }
+    state.submenu = Some((row, submenu));
+}
+
 /// Walk up from `start` through ChildOf to find an entity with `MenuBarItem`.
 fn find_ancestor(
     start: Entity,
@@ -162,6 +247,7 @@ fn spawn_menu_bar_item(
         MenuBarItem {
             label: label.to_string(),
             actions,
+            submenus: Vec::new(),
         },
         Node {
             padding: UiRect::axes(Val::Px(tokens::SPACING_MD), Val::Px(tokens::SPACING_XS)),
@@ -181,27 +267,39 @@ fn spawn_menu_bar_item(
     ));
 }
 
-fn spawn_dropdown(commands: &mut Commands, x: f32, y: f32, actions: &[(String, String)]) -> Entity {
+fn spawn_dropdown(commands: &mut Commands, x: f32, y: f32, item: &MenuBarItem) -> Entity {
     let dropdown = commands
-        .spawn((
-            MenuBarDropdown,
-            Node {
-                position_type: PositionType::Absolute,
-                left: Val::Px(x),
-                top: Val::Px(y),
-                flex_direction: FlexDirection::Column,
-                min_width: Val::Px(180.0),
-                padding: UiRect::axes(Val::Px(tokens::SPACING_XS), Val::Px(tokens::SPACING_SM)),
-                border: UiRect::all(Val::Px(1.0)),
-                border_radius: BorderRadius::all(Val::Px(tokens::BORDER_RADIUS_MD)),
-                ..Default::default()
-            },
-            BackgroundColor(tokens::MENU_BG),
-            BorderColor::all(tokens::BORDER_SUBTLE),
-            ZIndex(1000),
-        ))
+        .spawn((MenuBarDropdown, dropdown_container(Val::Px(x), Val::Px(y))))
         .id();
+    spawn_dropdown_entries(commands, dropdown, &item.actions, &item.submenus);
+    dropdown
+}
 
+fn dropdown_container(left: Val, top: Val) -> impl Bundle {
+    (
+        Node {
+            position_type: PositionType::Absolute,
+            left,
+            top,
+            flex_direction: FlexDirection::Column,
+            min_width: Val::Px(180.0),
+            padding: UiRect::axes(Val::Px(tokens::SPACING_XS), Val::Px(tokens::SPACING_SM)),
+            border: UiRect::all(Val::Px(1.0)),
+            border_radius: BorderRadius::all(Val::Px(tokens::BORDER_RADIUS_MD)),
+            ..Default::default()
+        },
+        BackgroundColor(tokens::MENU_BG),
+        BorderColor::all(tokens::BORDER_SUBTLE),
+        ZIndex(1000),
+    )
+}
+
+fn spawn_dropdown_entries(
+    commands: &mut Commands,
+    parent: Entity,
+    actions: &[(String, String)],
+    submenus: &[(String, Vec<(String, String)>)],
+) {
     for (action, label) in actions {
         if action == "---" {
             // Separator
@@ -213,12 +311,27 @@ fn spawn_dropdown(commands: &mut Commands, x: f32, y: f32, actions: &[(String, S
                     ..Default::default()
                 },
                 BackgroundColor(tokens::BORDER_SUBTLE),
-                ChildOf(dropdown),
+                ChildOf(parent),
             ));
             continue;
         }
 
-        commands.entity(dropdown).with_child((
+        if submenus.iter().any(|(a, _)| a == action) {
+            commands.entity(parent).with_child((
+                MenuBarSubmenuItem {
+                    action: action.clone(),
+                },
+                button(
+                    ButtonProps::new(label.clone())
+                        .with_variant(ButtonVariant::Ghost)
+                        .with_right_icon(Icon::ChevronRight)
+                        .align_left(),
+                ),
+            ));
+            continue;
+        }
+
+        commands.entity(parent).with_child((
             MenuBarDropdownItem {
                 action: action.clone(),
             },
@@ -229,6 +342,4 @@ fn spawn_dropdown(commands: &mut Commands, x: f32, y: f32, actions: &[(String, S
             ),
         ));
     }
-
-    dropdown
 }
@@ -2,9 +2,14 @@ use bevy::{feathers::theme::ThemedText, prelude::*};
 
 use crate::tokens;
 
+/// Marker on panel header bars, which docking uses as drag handles.
+#[derive(Component)]
+pub struct PanelHeader;
+
 /// A panel header bar with a title label.
-pub fn panel_header(title: &str) -> impl Bundle {
+pub fn panel_header(title: impl Into<String>) -> impl Bundle {
     (
+        PanelHeader,
         Node {
             flex_direction: FlexDirection::Row,
             align_items: AlignItems::Center,
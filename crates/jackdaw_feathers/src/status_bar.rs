@@ -14,6 +14,10 @@ pub struct StatusBarLeft;
 #[derive(Component)]
 pub struct StatusBarCenter;
 
+/// Marker for the grid size readout, which the editor makes clickable.
+#[derive(Component)]
+pub struct StatusBarGrid;
+
 /// Marker for the right status text (gizmo mode, scene path).
 #[derive(Component)]
 pub struct StatusBarRight;
@@ -52,6 +56,15 @@ pub fn status_bar() -> impl Bundle {
                 },
                 TextColor(tokens::TEXT_SECONDARY),
             ),
+            (
+                StatusBarGrid,
+                Text::new(""),
+                TextFont {
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                TextColor(tokens::TEXT_SECONDARY),
+            ),
             (
                 StatusBarRight,
                 Text::new(""),
@@ -86,6 +86,92 @@ pub enum TextEditPrefix {
 #[derive(Component)]
 struct TextEditSuffix(String);
 
+/// Unit a numeric field shows after its value. Typed values may carry any unit of the
+/// same kind, e.g. `25cm` in a meters field, and are converted into the field's unit.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NumericUnit {
+    Meters,
+    Centimeters,
+    Degrees,
+    Percent,
+}
+
+impl NumericUnit {
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Self::Meters => "m",
+            Self::Centimeters => "cm",
+            Self::Degrees => "deg",
+            Self::Percent => "%",
+        }
+    }
+
+    /// Decimal places shown unless the field sets its own precision.
+    pub fn default_precision(self) -> usize {
+        match self {
+            Self::Meters => 3,
+            Self::Centimeters | Self::Degrees => 1,
+            Self::Percent => 0,
+        }
+    }
+
+    /// Size of one of this unit in the base unit of its kind.
+    fn scale(self) -> f64 {
+        match self {
+            Self::Meters | Self::Degrees => 1.0,
+            Self::Centimeters | Self::Percent => 0.01,
+        }
+    }
+
+    /// Suffixes that can be typed into a field of this unit, with their scale to the
+    /// base unit. Longer suffixes come first so `cm` isn't read as `m`.
+    fn accepted(self) -> &'static [(&'static str, f64)] {
+        match self {
+            Self::Meters | Self::Centimeters => &[("mm", 0.001), ("cm", 0.01), ("m", 1.0)],
+            Self::Degrees => &[
+                ("deg", 1.0),
+                ("°", 1.0),
+                ("rad", 180.0 / std::f64::consts::PI),
+            ],
+            Self::Percent => &[("%", 0.01)],
+        }
+    }
+
+    /// Parse typed text, converting a trailing unit into this one.
+    pub fn parse(self, text: &str) -> Option<f64> {
+        let text = text.trim();
+        for &(suffix, scale) in self.accepted() {
+            if let Some(number) = text.strip_suffix(suffix) {
+                return number
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .map(|v| v * scale / self.scale());
+            }
+        }
+        text.parse().ok()
+    }
+}
+
+/// Decimal places shown by float fields without a unit or precision of their own.
+pub const DEFAULT_PRECISION: usize = 2;
+
+/// How a numeric field parses and rounds its value.
+#[derive(Component, Clone, Copy)]
+pub struct NumericFormat {
+    pub unit: Option<NumericUnit>,
+    pub precision: usize,
+}
+
+impl Default for NumericFormat {
+    fn default() -> Self {
+        Self {
+            unit: None,
+            precision: DEFAULT_PRECISION,
+        }
+    }
+}
+
 #[derive(Component)]
 struct TextEditSuffixNode(Entity);
 
@@ -121,6 +207,8 @@ pub struct TextEditConfig {
     filter: Option<FilterType>,
     prefix: Option<TextEditPrefix>,
     suffix: Option<String>,
+    unit: Option<NumericUnit>,
+    precision: Option<usize>,
     placeholder: String,
     default_value: Option<String>,
     min: f64,
@@ -138,6 +226,8 @@ pub struct TextEditProps {
     pub filter: Option<FilterType>,
     pub prefix: Option<TextEditPrefix>,
     pub suffix: Option<String>,
+    pub unit: Option<NumericUnit>,
+    pub precision: Option<usize>,
     pub min: f64,
     pub max: f64,
     pub allow_empty: bool,
@@ -155,6 +245,8 @@ impl Default for TextEditProps {
             filter: None,
             prefix: None,
             suffix: None,
+            unit: None,
+            precision: None,
             min: f64::MIN,
             max: f64::MAX,
             allow_empty: false,
@@ -181,6 +273,16 @@ impl TextEditProps {
         self.suffix = Some(suffix.into());
         self
     }
+    /// Show `unit` after the value and accept other units of its kind when typing.
+    pub fn with_unit(mut self, unit: NumericUnit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+    /// Decimal places the value is rounded to, overriding the unit's default.
+    pub fn with_precision(mut self, decimals: usize) -> Self {
+        self.precision = Some(decimals);
+        self
+    }
     pub fn with_default_value(mut self, value: impl Into<String>) -> Self {
         self.default_value = Some(value.into());
         self
@@ -238,12 +340,15 @@ pub fn text_edit(props: TextEditProps) -> impl Bundle {
         filter,
         prefix,
         suffix,
+        unit,
+        precision,
         min,
         max,
         allow_empty,
         drag_bottom,
         grow,
     } = props;
+    let suffix = suffix.or_else(|| unit.map(|u| u.suffix().to_string()));
 
     (
         Node {
@@ -260,6 +365,8 @@ pub fn text_edit(props: TextEditProps) -> impl Bundle {
             filter,
             prefix,
             suffix,
+            unit,
+            precision,
             placeholder,
             default_value,
             min,
@@ -303,10 +410,15 @@ fn setup_text_edit_input(
         }
 
         let is_numeric = config.variant.is_numeric();
-        let filter = config.filter.as_ref().map(|f| match f {
-            FilterType::Decimal => TextInputFilter::Decimal,
-            FilterType::Integer => TextInputFilter::Integer,
-        });
+        // Fields with a unit also accept typed unit suffixes
+        let filter = config
+            .filter
+            .as_ref()
+            .filter(|_| config.unit.is_none())
+            .map(|f| match f {
+                FilterType::Decimal => TextInputFilter::Decimal,
+                FilterType::Integer => TextInputFilter::Integer,
+            });
 
         let wrapper_entity = commands
             .spawn((
@@ -428,10 +540,19 @@ fn setup_text_edit_input(
         }
 
         if is_numeric {
-            text_input.insert(NumericRange {
-                min: config.min,
-                max: config.max,
-            });
+            text_input.insert((
+                NumericRange {
+                    min: config.min,
+                    max: config.max,
+                },
+                NumericFormat {
+                    unit: config.unit,
+                    precision: config
+                        .precision
+                        .or(config.unit.map(NumericUnit::default_precision))
+                        .unwrap_or(DEFAULT_PRECISION),
+                },
+            ));
         }
 
         if config.allow_empty {
@@ -493,13 +614,14 @@ fn apply_default_value(
         &TextInputBuffer,
         &mut TextInputQueue,
         Option<&NumericRange>,
+        Option<&NumericFormat>,
     )>,
 ) {
-    for (entity, default_value, variant, buffer, mut queue, range) in &mut text_edits {
+    for (entity, default_value, variant, buffer, mut queue, range, format) in &mut text_edits {
         if buffer.get_text().is_empty() {
             let text = if variant.is_numeric() {
                 let value = clamp_value(default_value.0.parse().unwrap_or(0.0), range);
-                format_numeric_value(value, *variant)
+                format_with_precision(value, *variant, precision(format))
             } else {
                 default_value.0.clone()
             };
@@ -600,6 +722,7 @@ fn handle_clamp_on_unfocus(
             Option<&TextEditSuffix>,
             Option<&NumericRange>,
             Option<&AllowEmpty>,
+            Option<&NumericFormat>,
         ),
         With<EditorTextEdit>,
     >,
@@ -612,13 +735,20 @@ fn handle_clamp_on_unfocus(
         return;
     }
 
-    let Ok((variant, buffer, mut queue, suffix, range, allow_empty)) =
+    let Ok((variant, buffer, mut queue, suffix, range, allow_empty, format)) =
         text_edits.get_mut(was_focused)
     else {
         return;
     };
 
-    let text = strip_suffix(&buffer.get_text(), suffix);
+    let mut text = strip_suffix(&buffer.get_text(), suffix);
+    // Typed units are converted, so consumers only ever see plain numbers
+    if let Some(value) = format
+        .and_then(|f| f.unit)
+        .and_then(|unit| unit.parse(&text))
+    {
+        text = value.to_string();
+    }
 
     commands.trigger(TextEditCommitEvent {
         entity: was_focused,
@@ -634,7 +764,7 @@ fn handle_clamp_on_unfocus(
     }
 
     let value = text.parse().unwrap_or(0.0);
-    update_input_value(&mut queue, value, *variant, range);
+    update_input_value(&mut queue, value, *variant, range, format);
 }
 
 fn handle_numeric_increment(
@@ -648,6 +778,7 @@ fn handle_numeric_increment(
             &mut TextInputQueue,
             Option<&TextEditSuffix>,
             Option<&NumericRange>,
+            Option<&NumericFormat>,
         ),
         With<EditorTextEdit>,
     >,
@@ -655,7 +786,8 @@ fn handle_numeric_increment(
     let Some(focused_entity) = focus.0 else {
         return;
     };
-    let Ok((_, variant, buffer, mut queue, suffix, range)) = text_edits.get_mut(focused_entity)
+    let Ok((_, variant, buffer, mut queue, suffix, range, format)) =
+        text_edits.get_mut(focused_entity)
     else {
         return;
     };
@@ -674,10 +806,9 @@ fn handle_numeric_increment(
 
     let shift = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
     let step = if shift { 10.0 } else { 1.0 };
-    let new_value = parse_numeric_value(&buffer.get_text(), suffix) + (direction * step);
-    let rounded = (new_value * 100.0).round() / 100.0;
+    let new_value = parse_numeric_value(&buffer.get_text(), suffix, format) + (direction * step);
 
-    update_input_value(&mut queue, rounded, *variant, range);
+    update_input_value(&mut queue, new_value, *variant, range, format);
 }
 
 fn handle_drag_value(
@@ -694,11 +825,15 @@ fn handle_drag_value(
             &mut TextInputQueue,
             Option<&TextEditSuffix>,
             Option<&NumericRange>,
+            Option<&NumericFormat>,
         ),
         With<EditorTextEdit>,
     >,
 ) {
-    let Ok(window) = windows.single() else { return };
+    // Drag in whichever window the field is shown in
+    let Some(window) = windows.iter().find(|w| w.focused) else {
+        return;
+    };
     let cursor_pos = window.cursor_position();
 
     for (entity, mut hitbox, interaction, child_of) in &mut drag_hitboxes {
@@ -709,12 +844,12 @@ fn handle_drag_value(
 
         if mouse.just_pressed(MouseButton::Left) && *interaction == Interaction::Pressed {
             if let Some(pos) = cursor_pos {
-                let Ok((_, buffer, _, suffix, _)) = text_edits.get(input_entity) else {
+                let Ok((_, buffer, _, suffix, _, format)) = text_edits.get(input_entity) else {
                     continue;
                 };
                 hitbox.dragging = true;
                 hitbox.start_x = pos.x;
-                hitbox.start_value = parse_numeric_value(&buffer.get_text(), suffix);
+                hitbox.start_value = parse_numeric_value(&buffer.get_text(), suffix, format);
                 commands
                     .entity(entity)
                     .insert(ActiveCursor(bevy::window::SystemCursorIcon::ColResize));
@@ -724,7 +859,7 @@ fn handle_drag_value(
 
         if mouse.just_released(MouseButton::Left) {
             if hitbox.dragging {
-                if let Ok((_, buffer, _, suffix, _)) = text_edits.get(input_entity) {
+                if let Ok((_, buffer, _, suffix, _, _)) = text_edits.get(input_entity) {
                     let text = strip_suffix(&buffer.get_text(), suffix);
                     commands.trigger(TextEditCommitEvent {
                         entity: input_entity,
@@ -741,7 +876,9 @@ fn handle_drag_value(
 
         if hitbox.dragging {
             if let Some(pos) = cursor_pos {
-                let Ok((variant, _, mut queue, _, range)) = text_edits.get_mut(input_entity) else {
+                let Ok((variant, _, mut queue, _, range, format)) =
+                    text_edits.get_mut(input_entity)
+                else {
                     continue;
                 };
 
@@ -759,9 +896,8 @@ fn handle_drag_value(
 
                 let steps = ((pos.x - hitbox.start_x) / sensitivity).floor() as f64;
                 let new_value = hitbox.start_value + (steps * amount);
-                let rounded = (new_value * 100.0).round() / 100.0;
 
-                update_input_value(&mut queue, rounded, *variant, range);
+                update_input_value(&mut queue, new_value, *variant, range, format);
             }
         }
     }
@@ -774,16 +910,39 @@ fn strip_suffix(text: &str, suffix: Option<&TextEditSuffix>) -> String {
         .to_string()
 }
 
-fn parse_numeric_value(text: &str, suffix: Option<&TextEditSuffix>) -> f64 {
-    strip_suffix(text, suffix).parse().unwrap_or(0.0)
+fn parse_numeric_value(
+    text: &str,
+    suffix: Option<&TextEditSuffix>,
+    format: Option<&NumericFormat>,
+) -> f64 {
+    let text = strip_suffix(text, suffix);
+    match format.and_then(|f| f.unit) {
+        Some(unit) => unit.parse(&text),
+        None => text.parse().ok(),
+    }
+    .unwrap_or(0.0)
+}
+
+fn precision(format: Option<&NumericFormat>) -> usize {
+    format.map_or(DEFAULT_PRECISION, |f| f.precision)
+}
+
+pub fn round_to_precision(value: f64, precision: usize) -> f64 {
+    let factor = 10f64.powi(precision as i32);
+    (value * factor).round() / factor
 }
 
 pub fn format_numeric_value(value: f64, variant: TextEditVariant) -> String {
+    format_with_precision(value, variant, DEFAULT_PRECISION)
+}
+
+/// Format a value for a numeric field, rounded to `precision` decimal places.
+pub fn format_with_precision(value: f64, variant: TextEditVariant, precision: usize) -> String {
     match variant {
         TextEditVariant::NumericI32 => (value.round() as i32).to_string(),
         TextEditVariant::NumericF32 => {
-            let rounded = (value * 100.0).round() / 100.0;
-            format!("{rounded:.2}")
+            let rounded = round_to_precision(value, precision);
+            format!("{rounded:.precision$}")
         }
         TextEditVariant::Default => value.to_string(),
     }
@@ -801,9 +960,13 @@ fn update_input_value(
     value: f64,
     variant: TextEditVariant,
     range: Option<&NumericRange>,
+    format: Option<&NumericFormat>,
 ) {
     let clamped = clamp_value(value, range);
-    set_text_input_value(queue, format_numeric_value(clamped, variant));
+    set_text_input_value(
+        queue,
+        format_with_precision(clamped, variant, precision(format)),
+    );
 }
 
 fn sync_text_edit_values(
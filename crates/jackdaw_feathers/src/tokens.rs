@@ -116,6 +116,13 @@ pub const TEXT_SIZE: f32 = 12.0;
 pub const TEXT_SIZE_LG: f32 = 14.0;
 pub const TEXT_SIZE_XL: f32 = 18.0;
 
+/// Range of the base font size display setting
+pub const FONT_SIZE_MIN: f32 = 8.0;
+pub const FONT_SIZE_MAX: f32 = 24.0;
+/// Range of the UI scale display setting
+pub const UI_SCALE_MIN: f32 = 0.75;
+pub const UI_SCALE_MAX: f32 = 2.0;
+
 // Keep old names as aliases for existing code
 pub const FONT_SM: f32 = TEXT_SIZE_SM;
 pub const FONT_MD: f32 = TEXT_SIZE;
@@ -3,7 +3,8 @@ use bevy_monitors::prelude::{MonitorSelf, Mutation, NotifyChanged};
 use jackdaw_widgets::tree_view::{
     EntityCategory, TreeChildrenPopulated, TreeFocused, TreeNode, TreeNodeExpandToggle,
     TreeNodeExpanded, TreeRowChildren, TreeRowClicked, TreeRowContent, TreeRowDot, TreeRowDropped,
-    TreeRowDroppedOnRoot, TreeRowLabel, TreeRowSelected, TreeRowStartRename,
+    TreeRowDroppedOnRoot, TreeRowExternalDropped, TreeRowGameVisibilityToggle,
+    TreeRowGameVisibilityToggled, TreeRowLabel, TreeRowSelected, TreeRowStartRename,
     TreeRowVisibilityToggle, TreeRowVisibilityToggled, TreeView,
 };
 
@@ -171,7 +172,9 @@ fn tree_row_content(
                 },
                 ThemedText,
             ),
-            // Visibility toggle (eye icon)
+            // Hidden-in-game toggle (gamepad icon)
+            game_visibility_toggle(source, &style.icon_font),
+            // Editor visibility toggle (eye icon)
             visibility_toggle(source, &style.icon_font)
         ],
         // Click handler for selection (left-click only)
@@ -234,7 +237,8 @@ fn tree_row_content(
                 }
             },
         ),
-        // Drag-and-drop: resolve source entities and fire TreeRowDropped
+        // Drag-and-drop: resolve source entities and fire TreeRowDropped, or
+        // TreeRowExternalDropped for drags that didn't start on a tree row
         observe(
             |mut drag_drop: On<Pointer<DragDrop>>,
              mut commands: Commands,
@@ -265,6 +269,11 @@ fn tree_row_content(
                 let Some(dragged_source) =
                     find_source_entity(drag_drop.dropped, &parent_query, &tree_nodes)
                 else {
+                    commands.trigger(TreeRowExternalDropped {
+                        entity: target_content,
+                        dropped: drag_drop.dropped,
+                        target_source: target_node.0,
+                    });
                     return;
                 };
 
@@ -332,26 +341,11 @@ fn expand_toggle(has_children: bool, icon_font: &Handle<Font>) -> impl Bundle {
     )
 }
 
-/// Eye icon for toggling entity visibility.
+/// Eye icon for toggling whether the entity is drawn in the editor viewport.
 fn visibility_toggle(source: Entity, icon_font: &Handle<Font>) -> impl Bundle {
     (
         TreeRowVisibilityToggle,
-        Node {
-            width: px(18.0),
-            height: px(18.0),
-            justify_content: JustifyContent::Center,
-            align_items: AlignItems::Center,
-            ..default()
-        },
-        children![(
-            Text::new(String::from(Icon::Eye.unicode())),
-            TextFont {
-                font: icon_font.clone(),
-                font_size: tokens::FONT_SM,
-                ..default()
-            },
-            TextColor(tokens::TEXT_SECONDARY.with_alpha(0.4)),
-        )],
+        row_toggle_icon(Icon::Eye, icon_font),
         observe(
             move |mut click: On<Pointer<Click>>, mut commands: Commands| {
                 if click.event.button != PointerButton::Primary {
@@ -364,37 +358,83 @@ fn visibility_toggle(source: Entity, icon_font: &Handle<Font>) -> impl Bundle {
                 });
             },
         ),
+        observe(brighten_row_toggle),
+        observe(dim_row_toggle),
+    )
+}
+
+/// Gamepad icon for toggling whether the entity is hidden in the exported game.
+fn game_visibility_toggle(source: Entity, icon_font: &Handle<Font>) -> impl Bundle {
+    (
+        TreeRowGameVisibilityToggle,
+        row_toggle_icon(Icon::Gamepad2, icon_font),
         observe(
-            |hover: On<Pointer<Over>>,
-             children_query: Query<&Children>,
-             mut text_color: Query<&mut TextColor>| {
-                let entity = hover.event_target();
-                if let Ok(children) = children_query.get(entity) {
-                    for child in children.iter() {
-                        if let Ok(mut color) = text_color.get_mut(child) {
-                            color.0 = tokens::TEXT_SECONDARY;
-                        }
-                    }
+            move |mut click: On<Pointer<Click>>, mut commands: Commands| {
+                if click.event.button != PointerButton::Primary {
+                    return;
                 }
+                click.propagate(false);
+                commands.trigger(TreeRowGameVisibilityToggled {
+                    entity: click.event_target(),
+                    source_entity: source,
+                });
             },
         ),
-        observe(
-            |out: On<Pointer<Out>>,
-             children_query: Query<&Children>,
-             mut text_color: Query<&mut TextColor>| {
-                let entity = out.event_target();
-                if let Ok(children) = children_query.get(entity) {
-                    for child in children.iter() {
-                        if let Ok(mut color) = text_color.get_mut(child) {
-                            color.0 = tokens::TEXT_SECONDARY.with_alpha(0.4);
-                        }
-                    }
-                }
+        observe(brighten_row_toggle),
+        observe(dim_row_toggle),
+    )
+}
+
+fn row_toggle_icon(icon: Icon, icon_font: &Handle<Font>) -> impl Bundle {
+    (
+        Node {
+            width: px(18.0),
+            height: px(18.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        children![(
+            Text::new(String::from(icon.unicode())),
+            TextFont {
+                font: icon_font.clone(),
+                font_size: tokens::FONT_SM,
+                ..default()
             },
-        ),
+            TextColor(tokens::TEXT_SECONDARY.with_alpha(0.4)),
+        )],
     )
 }
 
+// Hover only changes the alpha, so callers can tint the icon to show its state.
+fn brighten_row_toggle(
+    hover: On<Pointer<Over>>,
+    children_query: Query<&Children>,
+    mut text_color: Query<&mut TextColor>,
+) {
+    if let Ok(children) = children_query.get(hover.event_target()) {
+        for child in children.iter() {
+            if let Ok(mut color) = text_color.get_mut(child) {
+                color.0.set_alpha(1.0);
+            }
+        }
+    }
+}
+
+fn dim_row_toggle(
+    out: On<Pointer<Out>>,
+    children_query: Query<&Children>,
+    mut text_color: Query<&mut TextColor>,
+) {
+    if let Ok(children) = children_query.get(out.event_target()) {
+        for child in children.iter() {
+            if let Ok(mut color) = text_color.get_mut(child) {
+                color.0.set_alpha(0.4);
+            }
+        }
+    }
+}
+
 /// Colored dot indicating entity category.
 fn category_dot(category: EntityCategory) -> impl Bundle {
     let color = category_color(category);
@@ -20,6 +20,14 @@ pub struct BrushFaceData {
     pub uv_u_axis: Vec3,
     /// Explicit V tangent axis (Valve 220 style). Zero means "not initialized".
     pub uv_v_axis: Vec3,
+    /// Gameplay surface type (e.g. "metal", "wood", "water") for footsteps and
+    /// impact effects. Empty means untagged.
+    #[reflect(default)]
+    pub surface: String,
+    /// Faces of a brush sharing a non-zero smoothing group get smooth normals where they
+    /// meet. Zero keeps the face flat shaded.
+    #[reflect(default)]
+    pub smoothing_group: u32,
 }
 
 impl BrushFaceData {
@@ -147,6 +155,111 @@ pub fn triangulate_face(indices: &[usize]) -> Vec<[u32; 3]> {
     triangles
 }
 
+/// Per-corner normals of each face polygon. Corners of faces in a smoothing group
+/// average the normals of every face in that group meeting at the vertex, weighted by
+/// the angle each face spans there; other corners keep the face normal.
+pub fn compute_face_vertex_normals(
+    faces: &[BrushFaceData],
+    vertices: &[Vec3],
+    face_polygons: &[Vec<usize>],
+) -> Vec<Vec<Vec3>> {
+    // Angle-weighted contribution of each face at each of its corners
+    let corner_weights: Vec<Vec<f32>> = face_polygons
+        .iter()
+        .map(|polygon| {
+            let count = polygon.len();
+            (0..count)
+                .map(|i| {
+                    let here = vertices[polygon[i]];
+                    let prev = vertices[polygon[(i + count - 1) % count]] - here;
+                    let next = vertices[polygon[(i + 1) % count]] - here;
+                    prev.angle_between(next)
+                })
+                .collect()
+        })
+        .collect();
+
+    faces
+        .iter()
+        .zip(face_polygons)
+        .map(|(face, polygon)| {
+            polygon
+                .iter()
+                .map(|&vi| {
+                    if face.smoothing_group == 0 {
+                        return face.plane.normal;
+                    }
+                    let mut sum = Vec3::ZERO;
+                    for (other, other_polygon) in face_polygons.iter().enumerate() {
+                        if faces[other].smoothing_group != face.smoothing_group {
+                            continue;
+                        }
+                        if let Some(corner) = other_polygon.iter().position(|&v| v == vi) {
+                            sum += faces[other].plane.normal * corner_weights[other][corner];
+                        }
+                    }
+                    sum.try_normalize().unwrap_or(face.plane.normal)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Smoothing groups that smooth every edge between faces meeting at less than
+/// `max_angle` radians, e.g. the sides of a cylinder but not its caps. Faces with no
+/// such edge get group zero.
+pub fn auto_smoothing_groups(
+    faces: &[BrushFaceData],
+    face_polygons: &[Vec<usize>],
+    max_angle: f32,
+) -> Vec<u32> {
+    let shares_edge = |a: &[usize], b: &[usize]| a.iter().filter(|v| b.contains(v)).count() >= 2;
+    // Union-find over faces joined by smooth edges
+    let mut root: Vec<usize> = (0..faces.len()).collect();
+    fn find(root: &mut [usize], mut i: usize) -> usize {
+        while root[i] != i {
+            root[i] = root[root[i]];
+            i = root[i];
+        }
+        i
+    }
+    for a in 0..faces.len() {
+        for b in (a + 1)..faces.len() {
+            if face_polygons[a].len() >= 3
+                && face_polygons[b].len() >= 3
+                && shares_edge(&face_polygons[a], &face_polygons[b])
+                && faces[a].plane.normal.angle_between(faces[b].plane.normal) < max_angle
+            {
+                let (ra, rb) = (find(&mut root, a), find(&mut root, b));
+                root[ra] = rb;
+            }
+        }
+    }
+
+    let mut groups = vec![0; faces.len()];
+    let mut next_group = 1;
+    let mut assigned: Vec<(usize, u32)> = Vec::new();
+    for (face, group) in groups.iter_mut().enumerate() {
+        let r = find(&mut root, face);
+        if (0..faces.len())
+            .filter(|&f| find(&mut root, f) == r)
+            .count()
+            < 2
+        {
+            continue;
+        }
+        *group = match assigned.iter().find(|(root, _)| *root == r) {
+            Some(&(_, group)) => group,
+            None => {
+                assigned.push((r, next_group));
+                next_group += 1;
+                next_group - 1
+            }
+        };
+    }
+    groups
+}
+
 /// Compute tangent axes for a face from its normal (paraxial projection).
 pub fn compute_face_tangent_axes(normal: Vec3) -> (Vec3, Vec3) {
     let abs_n = normal.abs();
@@ -191,6 +304,73 @@ pub fn compute_face_uvs(
         .collect()
 }
 
+/// Copy `source`'s texture onto the adjacent `target` face, folding the UV axes over
+/// the shared edge so the texture continues around the corner without a seam.
+/// `edge_point` is any point on the edge the two faces share.
+pub fn wrap_face_texture(source: &BrushFaceData, target: &mut BrushFaceData, edge_point: Vec3) {
+    let (u_axis, v_axis) = if source.uv_u_axis != Vec3::ZERO && source.uv_v_axis != Vec3::ZERO {
+        (source.uv_u_axis, source.uv_v_axis)
+    } else {
+        compute_face_tangent_axes(source.plane.normal)
+    };
+    // The rotation between the two normals turns about the shared edge, so points on
+    // the edge keep their texture coordinates up to a constant offset.
+    let fold = Quat::from_rotation_arc(source.plane.normal, target.plane.normal);
+
+    target.material = source.material.clone();
+    target.uv_scale = source.uv_scale;
+    target.uv_rotation = source.uv_rotation;
+    target.uv_u_axis = fold * u_axis;
+    target.uv_v_axis = fold * v_axis;
+
+    let uv_on_source = compute_face_uvs(
+        &[edge_point],
+        &[0],
+        u_axis,
+        v_axis,
+        source.uv_offset,
+        source.uv_scale,
+        source.uv_rotation,
+    )[0];
+    let uv_on_target = compute_face_uvs(
+        &[edge_point],
+        &[0],
+        target.uv_u_axis,
+        target.uv_v_axis,
+        Vec2::ZERO,
+        target.uv_scale,
+        target.uv_rotation,
+    )[0];
+    target.uv_offset = Vec2::from(uv_on_source) - Vec2::from(uv_on_target);
+}
+
+/// Volume of the convex solid bounded by `faces`.
+pub fn brush_volume(faces: &[BrushFaceData]) -> f32 {
+    let (vertices, polygons) = compute_brush_geometry(faces);
+    let triangles: Vec<[u32; 3]> = polygons
+        .iter()
+        .flat_map(|polygon| triangulate_face(polygon))
+        .collect();
+    convex_volume(&vertices, &triangles)
+}
+
+/// Volume of a closed convex mesh, summed as tetrahedra from its centroid.
+pub fn convex_volume(vertices: &[Vec3], triangles: &[[u32; 3]]) -> f32 {
+    if vertices.is_empty() {
+        return 0.0;
+    }
+    let centroid = vertices.iter().sum::<Vec3>() / vertices.len() as f32;
+    triangles
+        .iter()
+        .map(|&[a, b, c]| {
+            let a = vertices[a as usize] - centroid;
+            let b = vertices[b as usize] - centroid;
+            let c = vertices[c as usize] - centroid;
+            a.dot(b.cross(c)).abs() / 6.0
+        })
+        .sum()
+}
+
 /// Transform brush face planes from local space to world space.
 pub fn brush_planes_to_world(
     faces: &[BrushFaceData],
@@ -213,6 +393,8 @@ pub fn brush_planes_to_world(
                 uv_rotation: face.uv_rotation,
                 uv_u_axis: (rotation * face.uv_u_axis).normalize_or_zero(),
                 uv_v_axis: (rotation * face.uv_v_axis).normalize_or_zero(),
+                surface: face.surface.clone(),
+                smoothing_group: face.smoothing_group,
             }
         })
         .collect()
@@ -346,3 +528,344 @@ pub fn clean_degenerate_faces(faces: &[BrushFaceData]) -> Vec<BrushFaceData> {
         .map(|(_, f)| f.clone())
         .collect()
 }
+
+/// Largest number of triangles [`mesh_to_convex_brushes`] will decompose. Triangles
+/// sharing a plane are cheap: a 3072-triangle box takes around 10 ms.
+pub const MAX_CSG_MESH_TRIANGLES: usize = 4096;
+
+/// Largest number of distinct triangle planes [`mesh_to_convex_brushes`] will decompose.
+/// Each plane can become a face of the pieces, and the pieces' geometry is solved from
+/// their planes. Measured in release builds, 48-plane spheres and tori decompose in
+/// under 50 ms, while a 60-plane torus already takes half a second.
+pub const MAX_CSG_MESH_PLANES: usize = 48;
+
+/// Decompose the solid enclosed by a closed triangle mesh into convex brushes, so it can
+/// take part in CSG. Each triangle's plane becomes a cutting plane: the mesh bounds are
+/// split along them until no triangle crosses a cell, and the cells inside the mesh are
+/// kept. Returns nothing for meshes over [`MAX_CSG_MESH_TRIANGLES`] or
+/// [`MAX_CSG_MESH_PLANES`].
+///
+/// Triangles must wind counter-clockwise seen from outside. Open meshes give unreliable
+/// results, since inside and outside are told apart by ray parity.
+pub fn mesh_to_convex_brushes(
+    vertices: &[Vec3],
+    triangles: &[[u32; 3]],
+) -> Vec<Vec<BrushFaceData>> {
+    let triangles: Vec<[Vec3; 3]> = triangles
+        .iter()
+        .filter_map(|t| {
+            let [a, b, c] = t.map(|i| vertices.get(i as usize).copied());
+            Some([a?, b?, c?])
+        })
+        .filter(|[a, b, c]| (*b - *a).cross(*c - *a).length_squared() > EPSILON * EPSILON)
+        .collect();
+    if triangles.len() < 4 || triangles.len() > MAX_CSG_MESH_TRIANGLES {
+        return Vec::new();
+    }
+    let mut planes: Vec<BrushPlane> = Vec::new();
+    for [a, b, c] in &triangles {
+        let normal = (*b - *a).cross(*c - *a).normalize();
+        let plane = BrushPlane {
+            normal,
+            distance: normal.dot(*a),
+        };
+        if !planes.iter().any(|p| same_plane(p, &plane)) {
+            if planes.len() == MAX_CSG_MESH_PLANES {
+                return Vec::new();
+            }
+            planes.push(plane);
+        }
+    }
+
+    let mut min = Vec3::splat(f32::MAX);
+    let mut max = Vec3::splat(f32::MIN);
+    for point in triangles.iter().flatten() {
+        min = min.min(*point);
+        max = max.max(*point);
+    }
+    let pad = Vec3::splat(0.01);
+    let bounds = [Vec3::X, Vec3::Y, Vec3::Z]
+        .into_iter()
+        .flat_map(|axis| {
+            [
+                plane_face(axis, axis.dot(max + pad)),
+                plane_face(-axis, -axis.dot(min - pad)),
+            ]
+        })
+        .collect();
+
+    let Some(cell) = MeshCell::new(bounds) else {
+        return Vec::new();
+    };
+    let mut pieces = Vec::new();
+    let all: Vec<usize> = (0..triangles.len()).collect();
+    split_mesh_cell(&triangles, cell, all, &mut pieces);
+    merge_brush_fragments(pieces)
+}
+
+fn plane_face(normal: Vec3, distance: f32) -> BrushFaceData {
+    let mut face = BrushFaceData {
+        plane: BrushPlane { normal, distance },
+        uv_scale: Vec2::ONE,
+        ..default()
+    };
+    face.ensure_uv_axes();
+    face
+}
+
+/// A convex cell of the decomposition: its bounding faces and the polygon each has on
+/// the cell's surface. Kept up to date by clipping, so splitting doesn't have to solve
+/// for the geometry of every plane the cell has gathered.
+struct MeshCell {
+    faces: Vec<BrushFaceData>,
+    polygons: Vec<Vec<Vec3>>,
+}
+
+impl MeshCell {
+    fn new(faces: Vec<BrushFaceData>) -> Option<Self> {
+        let (vertices, polygons) = compute_brush_geometry(&faces);
+        let polygons: Vec<Vec<Vec3>> = polygons
+            .iter()
+            .map(|polygon| polygon.iter().map(|&i| vertices[i]).collect())
+            .collect();
+        (vertices.len() >= 4).then_some(Self { faces, polygons })
+    }
+
+    fn vertices(&self) -> impl Iterator<Item = Vec3> + '_ {
+        self.polygons.iter().flatten().copied()
+    }
+
+    /// The part of the cell behind the plane, or `None` if too little is left.
+    fn clip(&self, normal: Vec3, distance: f32) -> Option<Self> {
+        let mut faces = Vec::with_capacity(self.faces.len() + 1);
+        let mut polygons = Vec::with_capacity(self.faces.len() + 1);
+        let mut cap: Vec<Vec3> = Vec::new();
+        for (face, polygon) in self.faces.iter().zip(&self.polygons) {
+            let mut clipped = Vec::with_capacity(polygon.len() + 1);
+            for (i, &here) in polygon.iter().enumerate() {
+                let next = polygon[(i + 1) % polygon.len()];
+                let (side_here, side_next) =
+                    (normal.dot(here) - distance, normal.dot(next) - distance);
+                if side_here <= EPSILON {
+                    clipped.push(here);
+                    if side_here >= -EPSILON {
+                        cap.push(here);
+                    }
+                }
+                if (side_here < -EPSILON && side_next > EPSILON)
+                    || (side_here > EPSILON && side_next < -EPSILON)
+                {
+                    let point = here.lerp(next, side_here / (side_here - side_next));
+                    clipped.push(point);
+                    cap.push(point);
+                }
+            }
+            if clipped.len() >= 3 {
+                faces.push(face.clone());
+                polygons.push(clipped);
+            }
+        }
+
+        let mut unique: Vec<Vec3> = Vec::with_capacity(cap.len());
+        for point in cap {
+            if !unique.iter().any(|p| (*p - point).length() < EPSILON) {
+                unique.push(point);
+            }
+        }
+        if unique.len() >= 3 {
+            let mut order: Vec<usize> = (0..unique.len()).collect();
+            sort_face_vertices_by_winding(&unique, &mut order, normal);
+            faces.push(plane_face(normal, distance));
+            polygons.push(order.into_iter().map(|i| unique[i]).collect());
+        }
+        (faces.len() >= 4).then_some(Self { faces, polygons })
+    }
+}
+
+/// Split `cell` along the plane of one of the triangles crossing it, recursing until no
+/// triangle is left, then keep the cell if it lies inside the mesh.
+fn split_mesh_cell(
+    triangles: &[[Vec3; 3]],
+    cell: MeshCell,
+    crossing: Vec<usize>,
+    pieces: &mut Vec<Vec<BrushFaceData>>,
+) {
+    // Only triangles reaching into the cell can split it. One lying on the cell's
+    // boundary, or whose plane misses the cell, leaves it whole.
+    let crossing: Vec<(usize, Vec3, f32)> = crossing
+        .into_iter()
+        .filter(|&index| triangle_enters_cell(triangles[index], &cell.faces))
+        .filter_map(|index| {
+            let [a, b, c] = triangles[index];
+            let normal = (b - a).cross(c - a).normalize();
+            let distance = normal.dot(a);
+            let (min, max) = cell
+                .vertices()
+                .map(|v| normal.dot(v) - distance)
+                .fold((f32::MAX, f32::MIN), |(lo, hi), side| {
+                    (lo.min(side), hi.max(side))
+                });
+            (min < -EPSILON && max > EPSILON).then_some((index, normal, distance))
+        })
+        .collect();
+    let Some(&(_, normal, distance)) = crossing.first() else {
+        let count = cell.polygons.iter().map(Vec::len).sum::<usize>();
+        let centre = cell.vertices().sum::<Vec3>() / count as f32;
+        if point_inside_mesh(triangles, centre) {
+            pieces.push(cell.faces);
+        }
+        return;
+    };
+
+    let (mut front, mut back) = (Vec::new(), Vec::new());
+    for &(index, ..) in &crossing[1..] {
+        let sides = triangles[index].map(|p| normal.dot(p) - distance);
+        if sides.iter().any(|&s| s > EPSILON) {
+            front.push(index);
+        }
+        if sides.iter().any(|&s| s < -EPSILON) {
+            back.push(index);
+        }
+        // Triangles in the splitting plane are used up with it
+    }
+
+    if let Some(front_cell) = cell.clip(-normal, -distance) {
+        split_mesh_cell(triangles, front_cell, front, pieces);
+    }
+    if let Some(back_cell) = cell.clip(normal, distance) {
+        split_mesh_cell(triangles, back_cell, back, pieces);
+    }
+}
+
+/// Whether any of `triangle` lies strictly inside the convex `cell`, by clipping it
+/// against each of the cell's planes.
+fn triangle_enters_cell(triangle: [Vec3; 3], cell: &[BrushFaceData]) -> bool {
+    let mut polygon = triangle.to_vec();
+    for face in cell {
+        let (normal, distance) = (face.plane.normal, face.plane.distance - EPSILON);
+        let mut clipped = Vec::with_capacity(polygon.len() + 1);
+        for (i, &here) in polygon.iter().enumerate() {
+            let next = polygon[(i + 1) % polygon.len()];
+            let (side_here, side_next) = (normal.dot(here) - distance, normal.dot(next) - distance);
+            if side_here <= 0.0 {
+                clipped.push(here);
+            }
+            if (side_here < 0.0 && side_next > 0.0) || (side_here > 0.0 && side_next < 0.0) {
+                let t = side_here / (side_here - side_next);
+                clipped.push(here.lerp(next, t));
+            }
+        }
+        if clipped.len() < 3 {
+            return false;
+        }
+        polygon = clipped;
+    }
+    // A sliver along the boundary doesn't count
+    let area: Vec3 = (1..polygon.len() - 1)
+        .map(|i| (polygon[i] - polygon[0]).cross(polygon[i + 1] - polygon[0]))
+        .sum();
+    area.length_squared() > EPSILON * EPSILON
+}
+
+/// Whether `point` is inside the closed mesh, by the parity of crossings along a ray.
+fn point_inside_mesh(triangles: &[[Vec3; 3]], point: Vec3) -> bool {
+    // An irregular direction, so the ray is unlikely to graze an edge
+    let direction = Vec3::new(0.5377, 0.6125, 0.5795).normalize();
+    let crossings = triangles
+        .iter()
+        .filter(|&&[a, b, c]| {
+            let (edge1, edge2) = (b - a, c - a);
+            let p = direction.cross(edge2);
+            let det = edge1.dot(p);
+            if det.abs() < f32::EPSILON {
+                return false;
+            }
+            let offset = point - a;
+            let u = offset.dot(p) / det;
+            let q = offset.cross(edge1);
+            let v = direction.dot(q) / det;
+            u >= 0.0 && v >= 0.0 && u + v <= 1.0 && edge2.dot(q) / det > EPSILON
+        })
+        .count();
+    crossings % 2 == 1
+}
+
+/// Simplify the fragments left by CSG: pairs of fragments whose union is convex are
+/// merged into one, dropping the faces between them, and faces sharing a plane within a
+/// fragment collapse into one. Repeats until nothing more merges.
+pub fn merge_brush_fragments(fragments: Vec<Vec<BrushFaceData>>) -> Vec<Vec<BrushFaceData>> {
+    let mut fragments: Vec<Vec<BrushFaceData>> = fragments
+        .iter()
+        .map(|faces| clean_degenerate_faces(&dedup_planes(faces)))
+        .filter(|faces| faces.len() >= 4)
+        .collect();
+    'merging: loop {
+        for i in 0..fragments.len() {
+            for j in (i + 1)..fragments.len() {
+                if let Some(merged) = merge_convex_pair(&fragments[i], &fragments[j]) {
+                    fragments[i] = merged;
+                    fragments.swap_remove(j);
+                    continue 'merging;
+                }
+            }
+        }
+        return fragments;
+    }
+}
+
+fn same_plane(a: &BrushPlane, b: &BrushPlane) -> bool {
+    (a.normal - b.normal).length() < 1e-3 && (a.distance - b.distance).abs() < 1e-3
+}
+
+/// `faces` without repeated planes; the first face on a plane wins.
+fn dedup_planes(faces: &[BrushFaceData]) -> Vec<BrushFaceData> {
+    let mut unique: Vec<BrushFaceData> = Vec::with_capacity(faces.len());
+    for face in faces {
+        if !unique.iter().any(|f| same_plane(&f.plane, &face.plane)) {
+            unique.push(face.clone());
+        }
+    }
+    unique
+}
+
+/// The union of two convex brushes if it is itself convex and they touch along a face.
+fn merge_convex_pair(a: &[BrushFaceData], b: &[BrushFaceData]) -> Option<Vec<BrushFaceData>> {
+    let touching = a.iter().any(|fa| {
+        b.iter().any(|fb| {
+            same_plane(
+                &fa.plane,
+                &BrushPlane {
+                    normal: -fb.plane.normal,
+                    distance: -fb.plane.distance,
+                },
+            )
+        })
+    });
+    if !touching {
+        return None;
+    }
+
+    // The hull of both keeps only the planes every vertex of both lies behind
+    let (a_verts, _) = compute_brush_geometry(a);
+    let (b_verts, _) = compute_brush_geometry(b);
+    let bounding: Vec<BrushFaceData> = a
+        .iter()
+        .chain(b)
+        .filter(|face| {
+            a_verts
+                .iter()
+                .chain(&b_verts)
+                .all(|v| face.plane.normal.dot(*v) <= face.plane.distance + EPSILON * 10.0)
+        })
+        .cloned()
+        .collect();
+    let merged = clean_degenerate_faces(&dedup_planes(&bounding));
+    if merged.len() < 4 {
+        return None;
+    }
+
+    // Convex only if the hull adds no volume
+    let separate = brush_volume(a) + brush_volume(b);
+    let combined = brush_volume(&merged);
+    ((combined - separate).abs() <= separate.max(EPSILON) * 1e-3).then_some(merged)
+}
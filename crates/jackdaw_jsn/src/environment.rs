@@ -0,0 +1,202 @@
+//! Scene-level environment: sky, ambient light, fog, and reflections.
+
+use bevy::{
+    core_pipeline::Skybox,
+    light::{EnvironmentMapLight, GlobalAmbientLight},
+    pbr::{Atmosphere, DistanceFog, FogFalloff, ScatteringMedium},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+/// Environment of the loaded scene, saved in the `.jsn` file's `environment`
+/// section. Applied to every 3D camera without [`IgnoreSceneEnvironment`].
+#[derive(Resource, Reflect, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[reflect(Resource, Default)]
+#[serde(default)]
+pub struct SceneEnvironment {
+    pub sky: SceneSky,
+    pub ambient_color: Color,
+    pub ambient_brightness: f32,
+    pub fog: Option<SceneFog>,
+    pub environment_map: Option<SceneEnvironmentMap>,
+}
+
+impl Default for SceneEnvironment {
+    fn default() -> Self {
+        Self {
+            sky: SceneSky::None,
+            ambient_color: Color::WHITE,
+            ambient_brightness: 400.0,
+            fog: None,
+            environment_map: None,
+        }
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub enum SceneSky {
+    /// Cameras show their clear color.
+    #[default]
+    None,
+    /// Atmospheric scattering lit by the scene's directional lights.
+    Procedural,
+    /// A cubemap image such as a `.ktx2` file, as an asset path.
+    Cubemap { image: String, brightness: f32 },
+}
+
+/// Linear distance fog.
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SceneFog {
+    pub color: Color,
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Default for SceneFog {
+    fn default() -> Self {
+        Self {
+            color: Color::srgb(0.6, 0.65, 0.7),
+            start: 20.0,
+            end: 200.0,
+        }
+    }
+}
+
+/// Image-based lighting for reflections, from prefiltered cubemaps given as asset paths.
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SceneEnvironmentMap {
+    pub diffuse: String,
+    pub specular: String,
+    pub intensity: f32,
+}
+
+impl Default for SceneEnvironmentMap {
+    fn default() -> Self {
+        Self {
+            diffuse: String::new(),
+            specular: String::new(),
+            intensity: 1000.0,
+        }
+    }
+}
+
+/// Cameras with this keep their own sky, fog, and reflections.
+#[derive(Component, Default)]
+pub struct IgnoreSceneEnvironment;
+
+/// What the environment added to a camera, so only that is taken away again.
+#[derive(Component, Clone, Copy, Default)]
+pub(crate) struct AppliedSceneEnvironment {
+    skybox: bool,
+    atmosphere: bool,
+    fog: bool,
+    environment_map: bool,
+}
+
+pub(crate) fn apply_scene_environment(
+    mut commands: Commands,
+    environment: Option<Res<SceneEnvironment>>,
+    cameras: Query<
+        (Entity, Option<&AppliedSceneEnvironment>),
+        (With<Camera3d>, Without<IgnoreSceneEnvironment>),
+    >,
+    new_cameras: Query<(), (Added<Camera3d>, Without<IgnoreSceneEnvironment>)>,
+    asset_server: Res<AssetServer>,
+    mediums: Option<ResMut<Assets<ScatteringMedium>>>,
+    mut medium: Local<Option<Handle<ScatteringMedium>>>,
+) {
+    let Some(environment) = environment else {
+        return;
+    };
+    if !environment.is_changed() && new_cameras.is_empty() {
+        return;
+    }
+
+    commands.insert_resource(GlobalAmbientLight {
+        color: environment.ambient_color,
+        brightness: environment.ambient_brightness,
+        affects_lightmapped_meshes: true,
+    });
+
+    let skybox = match &environment.sky {
+        SceneSky::Cubemap { image, brightness } if !image.is_empty() => Some(Skybox {
+            image: asset_server.load(image.clone()),
+            brightness: *brightness,
+            rotation: Quat::IDENTITY,
+        }),
+        _ => None,
+    };
+    let atmosphere = match (&environment.sky, mediums) {
+        (SceneSky::Procedural, Some(mut mediums)) => Some(Atmosphere::earthlike(
+            medium
+                .get_or_insert_with(|| mediums.add(ScatteringMedium::default()))
+                .clone(),
+        )),
+        _ => None,
+    };
+    let fog = environment.fog.as_ref().map(|fog| DistanceFog {
+        color: fog.color,
+        falloff: FogFalloff::Linear {
+            start: fog.start,
+            end: fog.end,
+        },
+        ..default()
+    });
+    let environment_map = environment
+        .environment_map
+        .as_ref()
+        .filter(|map| !map.diffuse.is_empty() && !map.specular.is_empty())
+        .map(|map| EnvironmentMapLight {
+            diffuse_map: asset_server.load(map.diffuse.clone()),
+            specular_map: asset_server.load(map.specular.clone()),
+            intensity: map.intensity,
+            ..default()
+        });
+
+    for (camera, applied) in &cameras {
+        let applied = applied.copied().unwrap_or_default();
+        let mut ec = commands.entity(camera);
+        match &skybox {
+            Some(skybox) => {
+                ec.insert(skybox.clone());
+            }
+            None if applied.skybox => {
+                ec.remove::<Skybox>();
+            }
+            None => {}
+        }
+        match &atmosphere {
+            Some(atmosphere) => {
+                ec.insert(atmosphere.clone());
+            }
+            None if applied.atmosphere => {
+                ec.remove::<Atmosphere>();
+            }
+            None => {}
+        }
+        match &fog {
+            Some(fog) => {
+                ec.insert(fog.clone());
+            }
+            None if applied.fog => {
+                ec.remove::<DistanceFog>();
+            }
+            None => {}
+        }
+        match &environment_map {
+            Some(map) => {
+                ec.insert(map.clone());
+            }
+            None if applied.environment_map => {
+                ec.remove::<EnvironmentMapLight>();
+            }
+            None => {}
+        }
+        ec.insert(AppliedSceneEnvironment {
+            skybox: skybox.is_some(),
+            atmosphere: atmosphere.is_some(),
+            fog: fog.is_some(),
+            environment_map: environment_map.is_some(),
+        });
+    }
+}
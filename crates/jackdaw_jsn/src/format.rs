@@ -1,11 +1,179 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::environment::SceneEnvironment;
+use crate::types::{CustomProperties, PropertyValue};
+
+/// Version written by this build. Bump it together with a new entry in [`MIGRATIONS`].
+pub const CURRENT_SCENE_VERSION: u32 = 3;
+
+/// A step that upgrades raw scene JSON by one version, pushing a warning for
+/// anything it had to drop or guess.
+type Migration = fn(&mut serde_json::Value, &mut Vec<String>);
+
+/// `MIGRATIONS[i]` upgrades a version `i + 1` scene to version `i + 2`.
+const MIGRATIONS: &[Migration] = &[migrate_v1_to_v2, migrate_v2_to_v3];
+
+/// What happened while upgrading a scene on load.
+#[derive(Clone, Debug, Default)]
+pub struct JsnLoadReport {
+    /// Version found in the file.
+    pub from_version: u32,
+    /// Version after migration (always [`CURRENT_SCENE_VERSION`]).
+    pub to_version: u32,
+    pub warnings: Vec<String>,
+}
+
+impl JsnLoadReport {
+    pub fn migrated(&self) -> bool {
+        self.from_version != self.to_version
+    }
+}
+
+/// Parse a `.jsn` scene, upgrading older files to the current format.
+pub fn parse_scene(text: &str) -> Result<(JsnScene, JsnLoadReport), String> {
+    let mut value: serde_json::Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    let report = migrate_scene(&mut value)?;
+    let scene = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    Ok((scene, report))
+}
+
+/// Upgrade raw scene JSON in place to [`CURRENT_SCENE_VERSION`].
+pub fn migrate_scene(value: &mut serde_json::Value) -> Result<JsnLoadReport, String> {
+    let from_version = scene_version(value);
+    if from_version > CURRENT_SCENE_VERSION {
+        return Err(format!(
+            "scene format version {from_version} is newer than this editor supports ({CURRENT_SCENE_VERSION})"
+        ));
+    }
+
+    let mut report = JsnLoadReport {
+        from_version,
+        to_version: CURRENT_SCENE_VERSION,
+        warnings: Vec::new(),
+    };
+    for migration in &MIGRATIONS[(from_version.max(1) - 1) as usize..] {
+        migration(value, &mut report.warnings);
+    }
+
+    if let Some(root) = value.as_object_mut() {
+        root.insert("version".into(), CURRENT_SCENE_VERSION.into());
+        if let Some(header) = root.get_mut("jsn").and_then(|h| h.as_object_mut()) {
+            header.insert(
+                "format_version".into(),
+                serde_json::json!([CURRENT_SCENE_VERSION, 0, 0]),
+            );
+        }
+    }
+    Ok(report)
+}
+
+/// The top-level `version`, falling back to the header's major format version.
+fn scene_version(value: &serde_json::Value) -> u32 {
+    value
+        .get("version")
+        .and_then(|v| v.as_u64())
+        .or_else(|| value.pointer("/jsn/format_version/0")?.as_u64())
+        .map_or(1, |v| v as u32)
+}
+
+/// Call `f` on every brush face in the scene.
+fn for_each_brush_face(value: &mut serde_json::Value, mut f: impl FnMut(&mut serde_json::Value)) {
+    let Some(entities) = value.get_mut("scene").and_then(|s| s.as_array_mut()) else {
+        return;
+    };
+    for entity in entities {
+        let Some(faces) = entity
+            .pointer_mut("/components/jackdaw_jsn::types::Brush/faces")
+            .and_then(|f| f.as_array_mut())
+        else {
+            continue;
+        };
+        faces.iter_mut().for_each(&mut f);
+    }
+}
+
+/// v1 stored asset lists instead of the typed asset table, and brush faces
+/// referenced textures by path and index instead of a material.
+fn migrate_v1_to_v2(value: &mut serde_json::Value, warnings: &mut Vec<String>) {
+    let Some(root) = value.as_object_mut() else {
+        return;
+    };
+    if !root.contains_key("jsn") {
+        root.insert(
+            "jsn".into(),
+            serde_json::to_value(JsnHeader::default()).unwrap_or_default(),
+        );
+    }
+    if !root.contains_key("metadata") {
+        root.insert(
+            "metadata".into(),
+            serde_json::to_value(JsnMetadata::default()).unwrap_or_default(),
+        );
+    }
+    match root.get_mut("assets").and_then(|a| a.as_object_mut()) {
+        Some(assets) => assets.retain(|kind, entries| {
+            if entries.is_object() {
+                return true;
+            }
+            if entries.as_array().is_some_and(|list| !list.is_empty()) {
+                warnings.push(format!(
+                    "Dropped legacy asset list \"{kind}\"; re-add these assets from the asset browser"
+                ));
+            }
+            false
+        }),
+        None => {
+            root.insert("assets".into(), serde_json::json!({}));
+        }
+    }
+
+    let mut unresolved = 0;
+    for_each_brush_face(value, |face| {
+        let Some(face) = face.as_object_mut() else {
+            return;
+        };
+        let texture = face.remove("texture_path");
+        let had_index = face
+            .remove("material_index")
+            .and_then(|i| i.as_u64())
+            .is_some_and(|i| i != 0);
+        if face.contains_key("material") {
+            return;
+        }
+        match texture {
+            Some(serde_json::Value::String(path)) => {
+                face.insert("material".into(), path.into());
+            }
+            _ if had_index => unresolved += 1,
+            _ => {}
+        }
+    });
+    if unresolved > 0 {
+        warnings.push(format!(
+            "{unresolved} brush faces used a material index without a texture and now use the default material"
+        ));
+    }
+}
+
+/// v3 added the per-face gameplay surface type.
+fn migrate_v2_to_v3(value: &mut serde_json::Value, _warnings: &mut Vec<String>) {
+    for_each_brush_face(value, |face| {
+        if let Some(face) = face.as_object_mut() {
+            face.entry("surface").or_insert_with(|| "".into());
+        }
+    });
+}
+
 /// Top-level `.jsn` file structure.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct JsnScene {
+    /// Scene format version, see [`CURRENT_SCENE_VERSION`]. Files written before
+    /// this field existed are versioned from their header.
+    #[serde(default)]
+    pub version: u32,
     /// Format header with version info.
     pub jsn: JsnHeader,
     /// Scene metadata (name, author, timestamps).
@@ -14,6 +182,9 @@ pub struct JsnScene {
     pub assets: JsnAssets,
     /// Reserved for future editor state (camera bookmarks, snap settings, etc.).
     pub editor: Option<JsnEditorState>,
+    /// Sky, ambient light, fog, and reflections. Absent in older files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<SceneEnvironment>,
     /// Per-entity scene data with reflection-based components.
     pub scene: Vec<JsnEntity>,
 }
@@ -108,7 +279,7 @@ pub struct JsnHeader {
 impl Default for JsnHeader {
     fn default() -> Self {
         Self {
-            format_version: [2, 0, 0],
+            format_version: [CURRENT_SCENE_VERSION, 0, 0],
             editor_version: env!("CARGO_PKG_VERSION").to_string(),
             bevy_version: "0.18".to_string(),
         }
@@ -178,4 +349,268 @@ pub struct JsnProjectConfig {
     /// Default scene to open (relative to project root, e.g. "assets/scenes/level1.jsn").
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_scene: Option<String>,
+    /// Surface types offered for brush faces (e.g. "metal", "wood", "water").
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub surface_types: Vec<String>,
+    /// Rules for tagging faces from their texture path. First match wins.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub surface_rules: Vec<JsnSurfaceRule>,
+    /// Maximum number of undo steps kept. Uses the editor default when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_limit: Option<usize>,
+}
+
+/// Maps texture paths to a surface type, e.g. `"textures/metal/*" -> "metal"`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JsnSurfaceRule {
+    /// Glob-style pattern; `*` matches any run of characters. Case-insensitive.
+    pub pattern: String,
+    pub surface: String,
+}
+
+impl JsnSurfaceRule {
+    pub fn matches(&self, path: &str) -> bool {
+        let pattern = self.pattern.to_lowercase();
+        let path = path.to_lowercase();
+        let parts: Vec<&str> = pattern.split('*').collect();
+        if parts.len() == 1 {
+            return path == pattern;
+        }
+
+        let (first, last) = (parts[0], parts[parts.len() - 1]);
+        if path.len() < first.len() + last.len()
+            || !path.starts_with(first)
+            || !path.ends_with(last)
+        {
+            return false;
+        }
+        let mut rest = &path[first.len()..path.len() - last.len()];
+        for part in &parts[1..parts.len() - 1] {
+            let Some(pos) = rest.find(part) else {
+                return false;
+            };
+            rest = &rest[pos + part.len()..];
+        }
+        true
+    }
+}
+
+/// Top-level `.jsn/texture_library.json` file: texture browser favorites, recently
+/// used textures and user tags. Paths are relative to the project's assets directory.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct JsnTextureLibrary {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub favorites: Vec<String>,
+    /// Most recent first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, Vec<String>>,
+}
+
+/// Top-level `.jsn/entity_classes.json` file: named entity classes and the custom
+/// properties each one expects, like an FGD file in Quake tooling. An entity's class
+/// is its `classname` custom property.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct JsnEntityClasses {
+    #[serde(default)]
+    pub classes: BTreeMap<String, JsnEntityClass>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct JsnEntityClass {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default)]
+    pub properties: BTreeMap<String, JsnPropertySchema>,
+    /// Set for point entities (player starts, spawners, ...): classes placed from the
+    /// Add menu and shown as an icon in the viewport.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub point: Option<JsnPointEntity>,
+}
+
+impl JsnEntityClass {
+    /// Non-optional properties that `properties` doesn't have.
+    pub fn missing_required<'a>(&'a self, properties: &CustomProperties) -> Vec<&'a str> {
+        self.properties
+            .iter()
+            .filter(|(name, schema)| !schema.optional && !properties.properties.contains_key(*name))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+/// How a point entity class is drawn in the editor viewport.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JsnPointEntity {
+    /// Image asset path of the billboard icon. A plain colored square when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Linear RGB tint of the icon.
+    #[serde(default = "default_point_color")]
+    pub color: [f32; 3],
+    /// Icon edge length in world units.
+    #[serde(default = "default_point_size")]
+    pub size: f32,
+}
+
+impl Default for JsnPointEntity {
+    fn default() -> Self {
+        Self {
+            icon: None,
+            color: default_point_color(),
+            size: default_point_size(),
+        }
+    }
+}
+
+fn default_point_color() -> [f32; 3] {
+    [1.0, 1.0, 1.0]
+}
+
+fn default_point_size() -> f32 {
+    0.5
+}
+
+/// Expected type, default and allowed values of one class property.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JsnPropertySchema {
+    /// A [`PropertyValue`] type name: "Bool", "Int", "Float", "String", "Vec2", "Vec3",
+    /// "Color", "Entity" or "List".
+    #[serde(rename = "type")]
+    pub value_type: String,
+    /// Plain JSON default: a number, bool, string, or `[x, y, z]`-style array for
+    /// vectors and colors. The type's zero value when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    /// Optional properties aren't reported as missing.
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    /// Allowed values of a string property. Empty allows anything.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+}
+
+impl JsnPropertySchema {
+    /// The value a newly added property starts with, or `None` for an unknown type.
+    pub fn default_value(&self) -> Option<PropertyValue> {
+        let mut value = PropertyValue::default_for_type(&self.value_type)?;
+        let Some(json) = &self.default else {
+            return Some(self.clamp(value));
+        };
+        let floats: Vec<f32> = json
+            .as_array()
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_f64())
+                    .map(|f| f as f32)
+                    .collect()
+            })
+            .unwrap_or_default();
+        match &mut value {
+            PropertyValue::Bool(v) => *v = json.as_bool().unwrap_or(*v),
+            PropertyValue::Int(v) => *v = json.as_i64().unwrap_or(*v),
+            PropertyValue::Float(v) => *v = json.as_f64().unwrap_or(*v),
+            PropertyValue::String(v) => {
+                if let Some(s) = json.as_str() {
+                    *v = s.to_string();
+                }
+            }
+            PropertyValue::Vec2(v) => {
+                if let [x, y] = floats[..] {
+                    *v = Vec2::new(x, y);
+                }
+            }
+            PropertyValue::Vec3(v) => {
+                if let [x, y, z] = floats[..] {
+                    *v = Vec3::new(x, y, z);
+                }
+            }
+            PropertyValue::Color(v) => match floats[..] {
+                [r, g, b] => *v = Color::srgb(r, g, b),
+                [r, g, b, a] => *v = Color::srgba(r, g, b, a),
+                _ => {}
+            },
+            PropertyValue::EntityRef(_) | PropertyValue::List(_) => {}
+        }
+        Some(self.clamp(value))
+    }
+
+    /// Why `value` doesn't satisfy this schema, if it doesn't.
+    pub fn validate(&self, value: &PropertyValue) -> Option<String> {
+        if value.type_label() != self.value_type {
+            return Some(format!(
+                "expected {}, found {}",
+                self.value_type,
+                value.type_label()
+            ));
+        }
+        let number = match value {
+            PropertyValue::Int(i) => Some(*i as f64),
+            PropertyValue::Float(f) => Some(*f),
+            _ => None,
+        };
+        if let Some(n) = number
+            && (self.min.is_some_and(|min| n < min) || self.max.is_some_and(|max| n > max))
+        {
+            return Some(match (self.min, self.max) {
+                (Some(min), Some(max)) => format!("must be between {min} and {max}"),
+                (Some(min), None) => format!("must be at least {min}"),
+                (None, Some(max)) => format!("must be at most {max}"),
+                (None, None) => unreachable!("out of range without bounds"),
+            });
+        }
+        if let PropertyValue::String(s) = value
+            && !self.choices.is_empty()
+            && !self.choices.contains(s)
+        {
+            return Some(format!("must be one of {}", self.choices.join(", ")));
+        }
+        None
+    }
+
+    /// Clamp numeric values into `min..=max`. Other values pass through.
+    pub fn clamp(&self, value: PropertyValue) -> PropertyValue {
+        let min = self.min.unwrap_or(f64::NEG_INFINITY);
+        let max = self.max.unwrap_or(f64::INFINITY);
+        if min > max {
+            return value;
+        }
+        match value {
+            PropertyValue::Int(i) => PropertyValue::Int((i as f64).clamp(min, max) as i64),
+            PropertyValue::Float(f) => PropertyValue::Float(f.clamp(min, max)),
+            other => other,
+        }
+    }
+}
+
+/// Top-level `.jsn` patch file, written by the embedded in-game editor.
+///
+/// Patches are keyed by entity `StableId` and `Name` rather than index, since the
+/// running game has no knowledge of the scene file's entity ordering.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JsnPatch {
+    /// Format header (same as scene files).
+    pub jsn: JsnHeader,
+    /// Per-entity overrides captured at runtime.
+    pub patches: Vec<JsnPatchEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JsnPatchEntry {
+    /// Name of the entity to patch.
+    pub name: String,
+    /// [`StableId`](crate::StableId) of the entity, matched before the name when present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stable_id: Option<bevy::asset::uuid::Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transform: Option<JsnTransform>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<JsnVisibility>,
 }
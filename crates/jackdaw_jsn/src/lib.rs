@@ -1,20 +1,37 @@
+pub mod environment;
 pub mod format;
 mod loader;
+mod material_override;
 mod mesh_rebuild;
+pub mod spline_mesh;
+mod sub_scene;
 pub mod types;
 
 use bevy::prelude::*;
 
 // Re-export core types for consumer convenience
 pub use types::{
-    Brush, BrushFaceData, BrushPlane, CustomProperties, GltfSource, JsnPrefab, JsnPrefabBaseline,
-    NavmeshRegion, PropertyValue, Terrain,
+    Brush, BrushFaceData, BrushPlane, CustomProperties, DynamicBody, ExtrusionProfile, FloatCurve,
+    FloatCurveKey, FuncGroup, FuncGroupKind, GltfSource, HiddenInGame, InstanceBatch,
+    InstanceGroup, JsnPrefab, JsnPrefabBaseline, LinkedDuplicate, LodGroup, LodLevel,
+    MaterialOverride, MaterialOverrideApplied, NavmeshRegion, OriginalMaterial, ParticleEmitter,
+    PropertyValue, Spline, SplineExtrusion, SplineExtrusionMesh, SplinePoint, StableId, SubScene,
+    Terrain, TransformAnimation, TransformKeyframe, TriggerVolume, VisibilityVolume,
+    VisibilityVolumeKind,
+};
+
+pub use environment::{
+    IgnoreSceneEnvironment, SceneEnvironment, SceneEnvironmentMap, SceneFog, SceneSky,
 };
 
 // Re-export geometry crate
 pub use jackdaw_geometry;
 
-pub use format::{JsnProject, JsnProjectConfig, JsnScene};
+pub use format::{
+    CURRENT_SCENE_VERSION, JsnEntityClass, JsnEntityClasses, JsnLoadReport, JsnPatch,
+    JsnPatchEntry, JsnPointEntity, JsnProject, JsnProjectConfig, JsnPropertySchema, JsnScene,
+    JsnSurfaceRule, JsnTextureLibrary, parse_scene,
+};
 pub use loader::JsnAssetLoader;
 
 pub struct JsnPlugin;
@@ -26,11 +43,48 @@ impl Plugin for JsnPlugin {
             .register_type::<BrushPlane>()
             .register_type::<CustomProperties>()
             .register_type::<PropertyValue>()
+            .register_type::<DynamicBody>()
+            .register_type::<FloatCurve>()
+            .register_type::<FloatCurveKey>()
+            .register_type::<FuncGroup>()
+            .register_type::<FuncGroupKind>()
             .register_type::<GltfSource>()
+            .register_type::<HiddenInGame>()
+            .register_type::<InstanceGroup>()
             .register_type::<JsnPrefab>()
+            .register_type::<LinkedDuplicate>()
+            .register_type::<LodGroup>()
+            .register_type::<LodLevel>()
+            .register_type::<MaterialOverride>()
             .register_type::<NavmeshRegion>()
+            .register_type::<ParticleEmitter>()
+            .register_type::<Spline>()
+            .register_type::<SplinePoint>()
+            .register_type::<SplineExtrusion>()
+            .register_type::<ExtrusionProfile>()
+            .register_type::<StableId>()
+            .register_type::<SubScene>()
             .register_type::<Terrain>()
+            .register_type::<TransformAnimation>()
+            .register_type::<TransformKeyframe>()
+            .register_type::<TriggerVolume>()
+            .register_type::<VisibilityVolume>()
+            .register_type::<VisibilityVolumeKind>()
+            .register_type::<SceneEnvironment>()
+            .register_type::<SceneSky>()
+            .register_type::<SceneFog>()
+            .register_type::<SceneEnvironmentMap>()
             .init_asset_loader::<JsnAssetLoader>()
-            .add_systems(Update, mesh_rebuild::rebuild_brush_meshes);
+            .add_systems(
+                Update,
+                (
+                    mesh_rebuild::rebuild_brush_meshes,
+                    mesh_rebuild::rebuild_instance_groups,
+                    spline_mesh::rebuild_spline_extrusions,
+                    sub_scene::spawn_sub_scenes,
+                    material_override::apply_material_overrides,
+                    environment::apply_scene_environment,
+                ),
+            );
     }
 }
@@ -10,7 +10,9 @@ use bevy::{
 };
 use serde::de::DeserializeSeed;
 
-use crate::format::{JsnEntity, JsnScene};
+use crate::environment::SceneEnvironment;
+use crate::format::{JsnEntity, parse_scene};
+use crate::types::HiddenInGame;
 
 /// Asset loader for `.jsn` files → `DynamicScene`.
 #[derive(Debug, TypePath)]
@@ -36,7 +38,7 @@ impl AssetLoader for JsnAssetLoader {
         &self,
         reader: &mut dyn Reader,
         _settings: &Self::Settings,
-        _load_context: &mut LoadContext<'_>,
+        load_context: &mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
         let mut bytes = Vec::new();
         reader
@@ -46,11 +48,13 @@ impl AssetLoader for JsnAssetLoader {
 
         let text = std::str::from_utf8(&bytes).map_err(|e| JsnLoadError::Parse(e.to_string()))?;
 
-        let jsn: JsnScene =
-            serde_json::from_str(text).map_err(|e| JsnLoadError::Parse(e.to_string()))?;
+        let (jsn, report) = parse_scene(text).map_err(JsnLoadError::Parse)?;
+        for warning in &report.warnings {
+            warn!("{}: {warning}", load_context.path());
+        }
 
         // Build a DynamicScene by spawning into a temporary world
-        let scene = build_dynamic_scene(&jsn.scene, &self.type_registry)
+        let scene = build_dynamic_scene(&jsn.scene, jsn.environment, &self.type_registry)
             .map_err(|e| JsnLoadError::Scene(e))?;
 
         Ok(scene)
@@ -62,12 +66,17 @@ impl AssetLoader for JsnAssetLoader {
 }
 
 /// Spawn JsnEntity list into a temp world, then extract a DynamicScene.
+/// The scene environment, if any, is carried along as a resource.
 fn build_dynamic_scene(
     entities: &[JsnEntity],
+    environment: Option<SceneEnvironment>,
     type_registry: &TypeRegistryArc,
 ) -> Result<DynamicScene, String> {
     let mut world = World::new();
     world.insert_resource(AppTypeRegistry(type_registry.clone()));
+    if let Some(environment) = environment {
+        world.insert_resource(environment);
+    }
 
     // First pass: spawn entities with core fields
     let mut spawned: Vec<Entity> = Vec::new();
@@ -123,9 +132,19 @@ fn build_dynamic_scene(
     }
     drop(registry);
 
+    // Entities flagged hidden-in-game start hidden at runtime
+    for &entity in &spawned {
+        if world.get::<HiddenInGame>(entity).is_some() {
+            world.entity_mut(entity).insert(Visibility::Hidden);
+        }
+    }
+
     // Extract all spawned entities into a DynamicScene
     let scene = DynamicSceneBuilder::from_world(&world)
         .extract_entities(spawned.into_iter())
+        .deny_all_resources()
+        .allow_resource::<SceneEnvironment>()
+        .extract_resources()
         .build();
 
     Ok(scene)
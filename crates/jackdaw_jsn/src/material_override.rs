@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::types::{MaterialOverride, MaterialOverrideApplied, OriginalMaterial};
+
+/// A derived material: the override applied to its shared material, and the resulting
+/// asset. Only ids are kept, so a copy is freed once no entity shows it any more.
+struct DerivedMaterial {
+    material_override: MaterialOverride,
+    id: AssetId<StandardMaterial>,
+}
+
+type DerivedKey = (AssetId<StandardMaterial>, [u32; 7]);
+
+/// Derived materials by shared material and override bits, so equal overrides share one.
+#[derive(Default)]
+pub(crate) struct DerivedMaterials {
+    entries: HashMap<DerivedKey, DerivedMaterial>,
+    /// Key of each derived material by its own id.
+    keys: HashMap<AssetId<StandardMaterial>, DerivedKey>,
+}
+
+impl DerivedMaterials {
+    /// The shared material `id` was derived from, if it is a derived material.
+    fn base_of(&self, id: AssetId<StandardMaterial>) -> Option<AssetId<StandardMaterial>> {
+        self.keys.get(&id).map(|(base, _)| *base)
+    }
+
+    fn insert(&mut self, key: DerivedKey, derived: DerivedMaterial) {
+        self.keys.insert(derived.id, key);
+        if let Some(stale) = self.entries.insert(key, derived) {
+            self.keys.remove(&stale.id);
+        }
+    }
+
+    /// Forget the derived material `id` once nothing uses it.
+    fn remove(&mut self, id: AssetId<StandardMaterial>) {
+        if let Some(key) = self.keys.remove(&id) {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+/// Point the `MeshMaterial3d` of every entity with a [`MaterialOverride`] at a derived copy
+/// of its shared material, and keep derived copies in step with edits to the shared one.
+/// While an [`OriginalMaterial`] holds the authored material in place of a stand-in, the
+/// copy goes there instead, so the stand-in is never taken for the shared material.
+pub(crate) fn apply_material_overrides(
+    mut commands: Commands,
+    mut derived: Local<DerivedMaterials>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut material_events: MessageReader<AssetEvent<StandardMaterial>>,
+    mut overridden: Query<(
+        Entity,
+        &MaterialOverride,
+        Option<&mut MeshMaterial3d<StandardMaterial>>,
+        Option<&mut OriginalMaterial>,
+        Option<&MaterialOverrideApplied>,
+    )>,
+    mut removed: RemovedComponents<MaterialOverride>,
+    mut restored: Query<
+        (
+            &MaterialOverrideApplied,
+            Option<&mut MeshMaterial3d<StandardMaterial>>,
+            Option<&mut OriginalMaterial>,
+        ),
+        Without<MaterialOverride>,
+    >,
+) {
+    for event in material_events.read() {
+        match event {
+            // Shared material edited: rebuild the copies derived from it
+            AssetEvent::Modified { id } => {
+                let Some(base) = materials.get(*id).cloned() else {
+                    continue;
+                };
+                for ((base_id, _), entry) in &derived.entries {
+                    if base_id == id
+                        && let Some(material) = materials.get_mut(entry.id)
+                    {
+                        *material = entry.material_override.apply(&base);
+                    }
+                }
+            }
+            AssetEvent::Unused { id } => derived.remove(*id),
+            _ => {}
+        }
+    }
+
+    for (entity, material_override, mesh_material, original, applied) in &mut overridden {
+        let Some(mut material) = material_slot(mesh_material, original) else {
+            continue;
+        };
+        // The slot holds the derived copy unless something assigned a new material
+        let base = match applied {
+            Some(applied) if *material == applied.derived => applied.base.clone(),
+            _ => derived
+                .base_of(material.id())
+                .and_then(|base| materials.get_strong_handle(base))
+                .unwrap_or_else(|| material.clone()),
+        };
+
+        let key = (base.id(), material_override.key());
+        let existing = derived
+            .entries
+            .get(&key)
+            .and_then(|entry| materials.get_strong_handle(entry.id));
+        let handle = match existing {
+            Some(handle) => handle,
+            None => {
+                // Shared material not loaded yet; try again next frame
+                let Some(base_material) = materials.get(&base) else {
+                    continue;
+                };
+                let material = material_override.apply(base_material);
+                let handle = materials.add(material);
+                derived.insert(
+                    key,
+                    DerivedMaterial {
+                        material_override: *material_override,
+                        id: handle.id(),
+                    },
+                );
+                handle
+            }
+        };
+
+        if *material != handle {
+            *material = handle.clone();
+        }
+        if applied.is_none_or(|applied| applied.base != base || applied.derived != handle) {
+            commands.entity(entity).insert(MaterialOverrideApplied {
+                base,
+                derived: handle,
+            });
+        }
+    }
+
+    for entity in removed.read() {
+        let Ok((applied, mesh_material, original)) = restored.get_mut(entity) else {
+            continue;
+        };
+        if let Some(mut material) = material_slot(mesh_material, original)
+            && *material == applied.derived
+        {
+            *material = applied.base.clone();
+        }
+        commands.entity(entity).remove::<MaterialOverrideApplied>();
+    }
+}
+
+/// Where an entity's material is authored: its [`OriginalMaterial`] while a stand-in is
+/// shown, else its `MeshMaterial3d`.
+fn material_slot<'a>(
+    mesh_material: Option<Mut<'a, MeshMaterial3d<StandardMaterial>>>,
+    original: Option<Mut<'a, OriginalMaterial>>,
+) -> Option<Mut<'a, Handle<StandardMaterial>>> {
+    match (original, mesh_material) {
+        (Some(original), _) => Some(original.map_unchanged(|original| &mut original.0)),
+        (None, Some(mesh_material)) => Some(mesh_material.map_unchanged(|mesh| &mut mesh.0)),
+        (None, None) => None,
+    }
+}
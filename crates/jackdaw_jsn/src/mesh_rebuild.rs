@@ -1,23 +1,34 @@
 use bevy::{
-    mesh::{Indices, PrimitiveTopology},
+    math::Affine3A,
+    mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
     prelude::*,
 };
 
-use crate::types::Brush;
+use crate::types::{Brush, InstanceBatch, InstanceGroup, TriggerVolume, VisibilityVolume};
 use jackdaw_geometry::{
-    compute_brush_geometry, compute_face_tangent_axes, compute_face_uvs, triangulate_face,
+    compute_brush_geometry, compute_face_tangent_axes, compute_face_uvs,
+    compute_face_vertex_normals, triangulate_face,
 };
 
 /// Simplified runtime mesh rebuild for consumers (no editor material palette,
 /// no BrushFaceEntity, no texture cache — just a single mesh child per brush).
+/// Trigger and visibility volumes aren't rendered.
 pub(crate) fn rebuild_brush_meshes(
     mut commands: Commands,
-    new_brushes: Query<(Entity, &Brush), Added<Brush>>,
+    new_brushes: Query<
+        (Entity, &Brush),
+        (
+            Added<Brush>,
+            Without<TriggerVolume>,
+            Without<VisibilityVolume>,
+        ),
+    >,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
     for (entity, brush) in &new_brushes {
         let (vertices, face_polygons) = compute_brush_geometry(&brush.faces);
+        let vertex_normals = compute_face_vertex_normals(&brush.faces, &vertices, &face_polygons);
 
         let mut all_positions: Vec<[f32; 3]> = Vec::new();
         let mut all_normals: Vec<[f32; 3]> = Vec::new();
@@ -33,10 +44,10 @@ pub(crate) fn rebuild_brush_meshes(
 
             let base_vertex = all_positions.len() as u32;
 
-            // Per-face vertices (duplicated for flat normals)
-            for &vi in indices {
+            // Per-face vertices, duplicated so unsmoothed faces stay flat
+            for (&vi, normal) in indices.iter().zip(&vertex_normals[face_idx]) {
                 all_positions.push(vertices[vi].to_array());
-                all_normals.push(face_data.plane.normal.to_array());
+                all_normals.push(normal.to_array());
             }
 
             let (u_axis, v_axis) =
@@ -97,3 +108,275 @@ pub(crate) fn rebuild_brush_meshes(
         let _ = child;
     }
 }
+
+/// Instance-space geometry of one [`InstanceBatch`], repeated once per instance in its
+/// merged mesh.
+#[derive(Component, Default)]
+pub(crate) struct InstanceBatchGeometry {
+    positions: Vec<Vec3>,
+    normals: Vec<Vec3>,
+    uvs: Vec<[f32; 2]>,
+    indices: Vec<u32>,
+}
+
+impl InstanceBatchGeometry {
+    /// Append `mesh`, placed by `offset`. Meshes that aren't triangle lists, or whose
+    /// data only lives on the GPU, are skipped.
+    fn append(&mut self, mesh: &Mesh, offset: Affine3A) {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return;
+        }
+        let Some(positions) = mesh
+            .try_attribute(Mesh::ATTRIBUTE_POSITION)
+            .ok()
+            .and_then(VertexAttributeValues::as_float3)
+        else {
+            return;
+        };
+        let normals = mesh
+            .try_attribute(Mesh::ATTRIBUTE_NORMAL)
+            .ok()
+            .and_then(VertexAttributeValues::as_float3);
+        let uvs = match mesh.try_attribute(Mesh::ATTRIBUTE_UV_0) {
+            Ok(VertexAttributeValues::Float32x2(uvs)) => Some(uvs),
+            _ => None,
+        };
+
+        let base = self.positions.len() as u32;
+        let normal_matrix = Mat3::from(offset.matrix3).inverse().transpose();
+        for (i, position) in positions.iter().enumerate() {
+            let normal = normals
+                .and_then(|normals| normals.get(i))
+                .map_or(Vec3::Y, |n| Vec3::from_array(*n));
+            self.positions
+                .push(offset.transform_point3(Vec3::from_array(*position)));
+            self.normals
+                .push((normal_matrix * normal).normalize_or(Vec3::Y));
+            self.uvs
+                .push(uvs.and_then(|uvs| uvs.get(i)).copied().unwrap_or_default());
+        }
+        match mesh.try_indices_option() {
+            Ok(Some(indices)) => self.indices.extend(indices.iter().map(|i| base + i as u32)),
+            _ => self.indices.extend(base..self.positions.len() as u32),
+        }
+    }
+}
+
+/// What an [`InstanceGroup`] was last drawn from, so edits only rewrite the instances
+/// that changed. Holding the scene handle keeps a glTF source loaded.
+#[derive(Component)]
+pub(crate) struct DrawnInstances {
+    source: InstanceSourceKey,
+    scene: Option<Handle<Scene>>,
+    instances: Vec<Transform>,
+    /// The source wasn't loaded yet; retried every frame until it is.
+    pending: bool,
+}
+
+type InstanceSourceKey = (
+    AssetId<Mesh>,
+    AssetId<StandardMaterial>,
+    Option<(String, usize)>,
+);
+
+fn instance_source_key(group: &InstanceGroup) -> InstanceSourceKey {
+    (
+        group.mesh.id(),
+        group.material.id(),
+        group
+            .gltf
+            .as_ref()
+            .map(|gltf| (gltf.path.clone(), gltf.scene_index)),
+    )
+}
+
+/// Draw each [`InstanceGroup`] as one merged mesh per material. Moving, adding or
+/// removing instances rewrites just those instances' vertices; the batches are only
+/// respawned when the group's mesh, material or glTF scene changes.
+pub(crate) fn rebuild_instance_groups(
+    mut commands: Commands,
+    mut groups: Query<(
+        Entity,
+        Ref<InstanceGroup>,
+        Option<&mut DrawnInstances>,
+        Option<&Children>,
+    )>,
+    batches: Query<(&Mesh3d, &InstanceBatchGeometry), With<InstanceBatch>>,
+    asset_server: Res<AssetServer>,
+    scenes: Res<Assets<Scene>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (entity, group, drawn, children) in &mut groups {
+        let source = instance_source_key(&group);
+        let source_changed = drawn.as_ref().is_none_or(|drawn| drawn.source != source);
+        let scene = match &drawn {
+            Some(drawn) if !source_changed => drawn.scene.clone(),
+            _ => group.gltf.as_ref().map(|gltf| {
+                asset_server
+                    .load(GltfAssetLabel::Scene(gltf.scene_index).from_asset(gltf.path.clone()))
+            }),
+        };
+
+        if let Some(mut drawn) = drawn
+            && !source_changed
+        {
+            if !drawn.pending {
+                if group.is_changed() {
+                    for child in children.into_iter().flatten() {
+                        let Ok((mesh, geometry)) = batches.get(*child) else {
+                            continue;
+                        };
+                        if let Some(mesh) = meshes.get_mut(&mesh.0) {
+                            write_instances(mesh, geometry, &drawn.instances, &group.instances);
+                        }
+                    }
+                    drawn.instances.clone_from(&group.instances);
+                }
+                continue;
+            }
+        } else {
+            for child in children.into_iter().flatten() {
+                if batches.contains(*child) {
+                    commands.entity(*child).despawn();
+                }
+            }
+        }
+
+        let Some(geometry) =
+            batch_geometry(&group, scene.as_ref(), &asset_server, &scenes, &meshes)
+        else {
+            commands.entity(entity).insert(DrawnInstances {
+                source,
+                scene,
+                instances: Vec::new(),
+                pending: true,
+            });
+            continue;
+        };
+
+        for (material, geometry) in geometry {
+            let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, default());
+            mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, Vec::<[f32; 3]>::new());
+            mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, Vec::<[f32; 3]>::new());
+            write_instances(&mut mesh, &geometry, &[], &group.instances);
+            commands.spawn((
+                InstanceBatch {
+                    triangles_per_instance: geometry.indices.len() / 3,
+                },
+                Mesh3d(meshes.add(mesh)),
+                MeshMaterial3d(material),
+                geometry,
+                Transform::default(),
+                ChildOf(entity),
+            ));
+        }
+        commands.entity(entity).insert(DrawnInstances {
+            source,
+            scene,
+            instances: group.instances.clone(),
+            pending: false,
+        });
+    }
+}
+
+/// One instance's geometry, merged per material. `None` until the source is loaded.
+fn batch_geometry(
+    group: &InstanceGroup,
+    scene: Option<&Handle<Scene>>,
+    asset_server: &AssetServer,
+    scenes: &Assets<Scene>,
+    meshes: &Assets<Mesh>,
+) -> Option<Vec<(Handle<StandardMaterial>, InstanceBatchGeometry)>> {
+    let Some(scene) = scene else {
+        let mut geometry = InstanceBatchGeometry::default();
+        geometry.append(meshes.get(&group.mesh)?, Affine3A::IDENTITY);
+        return Some(vec![(group.material.clone(), geometry)]);
+    };
+    if !asset_server.is_loaded_with_dependencies(scene) {
+        return None;
+    }
+
+    // Every mesh node of the glTF scene, placed relative to the scene root
+    let world = &scenes.get(scene)?.world;
+    let mut batches: Vec<(Handle<StandardMaterial>, InstanceBatchGeometry)> = Vec::new();
+    let Some(mut nodes) = world.try_query::<(Entity, &Mesh3d, &MeshMaterial3d<StandardMaterial>)>()
+    else {
+        return Some(batches);
+    };
+    for (node, mesh, material) in nodes.iter(world) {
+        let Some(mesh) = meshes.get(&mesh.0) else {
+            continue;
+        };
+        let mut offset = Affine3A::IDENTITY;
+        let mut current = Some(node);
+        while let Some(entity) = current {
+            if let Some(transform) = world.get::<Transform>(entity) {
+                offset = transform.compute_affine() * offset;
+            }
+            current = world.get::<ChildOf>(entity).map(ChildOf::parent);
+        }
+        let index = match batches.iter().position(|(m, _)| *m == material.0) {
+            Some(index) => index,
+            None => {
+                batches.push((material.0.clone(), InstanceBatchGeometry::default()));
+                batches.len() - 1
+            }
+        };
+        batches[index].1.append(mesh, offset);
+    }
+    Some(batches)
+}
+
+/// Rewrite the vertices of the instances that differ between `old` and `new`, and
+/// resize the mesh if the count changed.
+fn write_instances(
+    mesh: &mut Mesh,
+    geometry: &InstanceBatchGeometry,
+    old: &[Transform],
+    new: &[Transform],
+) {
+    let per_instance = geometry.positions.len();
+    let changed: Vec<usize> = (0..new.len())
+        .filter(|&i| old.get(i) != Some(&new[i]))
+        .collect();
+    let resized = old.len() != new.len();
+    if changed.is_empty() && !resized {
+        return;
+    }
+
+    if let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+    {
+        positions.resize(new.len() * per_instance, [0.0; 3]);
+        for &i in &changed {
+            let transform = new[i].compute_affine();
+            let range = i * per_instance..(i + 1) * per_instance;
+            for (dst, src) in positions[range].iter_mut().zip(&geometry.positions) {
+                *dst = transform.transform_point3(*src).to_array();
+            }
+        }
+    }
+    if let Some(VertexAttributeValues::Float32x3(normals)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_NORMAL)
+    {
+        normals.resize(new.len() * per_instance, [0.0; 3]);
+        for &i in &changed {
+            let normal_matrix =
+                Mat3::from_quat(new[i].rotation) * Mat3::from_diagonal(new[i].scale.recip());
+            let range = i * per_instance..(i + 1) * per_instance;
+            for (dst, src) in normals[range].iter_mut().zip(&geometry.normals) {
+                *dst = (normal_matrix * *src).normalize_or(Vec3::Y).to_array();
+            }
+        }
+    }
+    if resized {
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, geometry.uvs.repeat(new.len()));
+        let indices = (0..new.len())
+            .flat_map(|i| {
+                let base = (i * per_instance) as u32;
+                geometry.indices.iter().map(move |&index| base + index)
+            })
+            .collect();
+        mesh.insert_indices(Indices::U32(indices));
+    }
+}
@@ -0,0 +1,196 @@
+//! Meshes extruded along splines, see [`SplineExtrusion`].
+
+use std::f32::consts::TAU;
+
+use bevy::{
+    asset::RenderAssetUsages,
+    mesh::{Indices, PrimitiveTopology},
+    prelude::*,
+};
+
+use crate::types::{ExtrusionProfile, Spline, SplineExtrusion, SplineExtrusionMesh};
+
+/// One edge of the profile, swept into a strip of quads.
+struct ProfileEdge {
+    a: Vec2,
+    b: Vec2,
+    /// Per-end normals; equal for hard edges.
+    normal_a: Vec2,
+    normal_b: Vec2,
+}
+
+fn profile_edges(profile: &ExtrusionProfile) -> Vec<ProfileEdge> {
+    let hard = |a: Vec2, b: Vec2| {
+        let d = b - a;
+        let normal = Vec2::new(d.y, -d.x).normalize_or_zero();
+        ProfileEdge {
+            a,
+            b,
+            normal_a: normal,
+            normal_b: normal,
+        }
+    };
+    match profile {
+        ExtrusionProfile::Rect { width, height } => {
+            let x = width * 0.5;
+            let points = [
+                Vec2::new(-x, -height),
+                Vec2::new(x, -height),
+                Vec2::new(x, 0.0),
+                Vec2::new(-x, 0.0),
+            ];
+            (0..4)
+                .map(|i| hard(points[i], points[(i + 1) % 4]))
+                .collect()
+        }
+        ExtrusionProfile::Circle { radius, sides } => {
+            let sides = (*sides).max(3);
+            let at = |i: u32| {
+                let angle = i as f32 / sides as f32 * TAU;
+                Vec2::new(angle.cos(), angle.sin())
+            };
+            (0..sides)
+                .map(|i| ProfileEdge {
+                    a: at(i) * *radius,
+                    b: at(i + 1) * *radius,
+                    normal_a: at(i),
+                    normal_b: at(i + 1),
+                })
+                .collect()
+        }
+        ExtrusionProfile::Custom { points, closed } => {
+            let count = points.len();
+            let edges = if *closed {
+                count
+            } else {
+                count.saturating_sub(1)
+            };
+            if count < 2 {
+                return Vec::new();
+            }
+            (0..edges)
+                .map(|i| hard(points[i], points[(i + 1) % count]))
+                .collect()
+        }
+    }
+}
+
+/// Sweep the extrusion's profile along `spline`, in the spline's local space. Returns
+/// `None` when there is nothing to build.
+pub fn extrude_spline(spline: &Spline, extrusion: &SplineExtrusion) -> Option<Mesh> {
+    let samples = spline.tessellate(extrusion.segments_per_curve.max(1) as usize);
+    let edges = profile_edges(&extrusion.profile);
+    if samples.len() < 2 || edges.is_empty() {
+        return None;
+    }
+    let tile = extrusion.uv_tile_length.max(0.01);
+
+    // A frame per sample: right and up around the tangent, kept level with the ground
+    let mut frames = Vec::with_capacity(samples.len());
+    let mut distance = 0.0;
+    let mut previous_right = Vec3::X;
+    for (i, &(position, tangent)) in samples.iter().enumerate() {
+        if i > 0 {
+            distance += position.distance(samples[i - 1].0);
+        }
+        let right = Vec3::Y
+            .cross(tangent)
+            .try_normalize()
+            .unwrap_or(previous_right);
+        let up = tangent.cross(right).try_normalize().unwrap_or(Vec3::Y);
+        previous_right = right;
+        frames.push((position, right, up, distance / tile));
+    }
+
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut normals: Vec<[f32; 3]> = Vec::new();
+    let mut uvs: Vec<[f32; 2]> = Vec::new();
+    let mut indices: Vec<u32> = Vec::new();
+    let mut across = 0.0;
+    for edge in &edges {
+        let u_a = across / tile;
+        across += edge.a.distance(edge.b);
+        let u_b = across / tile;
+        let base = positions.len() as u32;
+        for &(origin, right, up, v) in &frames {
+            let ends = [(edge.a, edge.normal_a, u_a), (edge.b, edge.normal_b, u_b)];
+            for (point, normal, u) in ends {
+                positions.push((origin + right * point.x + up * point.y).to_array());
+                normals.push((right * normal.x + up * normal.y).to_array());
+                uvs.push([u, v]);
+            }
+        }
+        for i in 0..frames.len() as u32 - 1 {
+            let (a0, b0, a1, b1) = (
+                base + i * 2,
+                base + i * 2 + 1,
+                base + i * 2 + 2,
+                base + i * 2 + 3,
+            );
+            indices.extend_from_slice(&[a0, b0, b1, a0, b1, a1]);
+        }
+    }
+
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_indices(Indices::U32(indices));
+    Some(mesh)
+}
+
+/// Regenerate the mesh child of a [`SplineExtrusion`] whenever it or its spline changes,
+/// and drop it when the extrusion is removed.
+pub(crate) fn rebuild_spline_extrusions(
+    mut commands: Commands,
+    changed: Query<
+        (Entity, &Spline, &SplineExtrusion, Option<&Children>),
+        Or<(Changed<Spline>, Changed<SplineExtrusion>)>,
+    >,
+    mut removed: RemovedComponents<SplineExtrusion>,
+    all_children: Query<&Children>,
+    generated: Query<(), With<SplineExtrusionMesh>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut fallback_material: Local<Option<Handle<StandardMaterial>>>,
+) {
+    let clear = |commands: &mut Commands, children: Option<&Children>| {
+        for child in children.into_iter().flatten() {
+            if generated.contains(*child) {
+                commands.entity(*child).despawn();
+            }
+        }
+    };
+    for entity in removed.read() {
+        clear(&mut commands, all_children.get(entity).ok());
+    }
+    for (entity, spline, extrusion, children) in &changed {
+        clear(&mut commands, children);
+        let Some(mesh) = extrude_spline(spline, extrusion) else {
+            continue;
+        };
+        // No material assigned: plain grey, as for brushes
+        let material = if extrusion.material == Handle::default() {
+            fallback_material
+                .get_or_insert_with(|| {
+                    materials.add(StandardMaterial {
+                        base_color: Color::srgb(0.7, 0.7, 0.7),
+                        ..default()
+                    })
+                })
+                .clone()
+        } else {
+            extrusion.material.clone()
+        };
+        commands.spawn((
+            SplineExtrusionMesh,
+            Mesh3d(meshes.add(mesh)),
+            MeshMaterial3d(material),
+            Transform::default(),
+            ChildOf(entity),
+        ));
+    }
+}
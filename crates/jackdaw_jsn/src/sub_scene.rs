@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+use crate::types::SubScene;
+
+/// Load the referenced scene under every new or edited [`SubScene`]. A sub-scene
+/// that references a file already open further up its own hierarchy is skipped,
+/// since it would nest forever.
+pub(crate) fn spawn_sub_scenes(
+    mut commands: Commands,
+    changed: Query<(Entity, &SubScene), Changed<SubScene>>,
+    sub_scenes: Query<&SubScene>,
+    parents: Query<&ChildOf>,
+    asset_server: Res<AssetServer>,
+) {
+    for (entity, sub_scene) in &changed {
+        let mut ec = commands.entity(entity);
+        if sub_scene.path.is_empty() {
+            ec.remove::<DynamicSceneRoot>();
+            continue;
+        }
+
+        let recursive = parents.iter_ancestors(entity).any(|ancestor| {
+            sub_scenes
+                .get(ancestor)
+                .is_ok_and(|s| s.path == sub_scene.path)
+        });
+        if recursive {
+            warn!(
+                "Sub-scene '{}' references itself; not loading it again",
+                sub_scene.path
+            );
+            ec.remove::<DynamicSceneRoot>();
+            continue;
+        }
+
+        ec.insert(DynamicSceneRoot(asset_server.load(sub_scene.path.clone())));
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::{BTreeMap, HashMap};
 
-use bevy::prelude::*;
+use bevy::{asset::uuid::Uuid, prelude::*};
 use serde::{Deserialize, Serialize};
 
 // Re-export geometry types so consumers see them from jackdaw_jsn
@@ -210,6 +210,10 @@ pub enum PropertyValue {
     Vec2(Vec2),
     Vec3(Vec3),
     Color(Color),
+    /// Link to another entity by its [`StableId`]. Nil means unset.
+    EntityRef(Uuid),
+    /// Ordered values, e.g. waypoints or tags. The editor keeps all items one type.
+    List(Vec<PropertyValue>),
 }
 
 impl PropertyValue {
@@ -223,6 +227,8 @@ impl PropertyValue {
             Self::Vec2(_) => "Vec2",
             Self::Vec3(_) => "Vec3",
             Self::Color(_) => "Color",
+            Self::EntityRef(_) => "Entity",
+            Self::List(_) => "List",
         }
     }
 
@@ -236,23 +242,71 @@ impl PropertyValue {
             "Vec2" => Some(Self::Vec2(Vec2::ZERO)),
             "Vec3" => Some(Self::Vec3(Vec3::ZERO)),
             "Color" => Some(Self::Color(Color::WHITE)),
+            "Entity" => Some(Self::EntityRef(Uuid::nil())),
+            "List" => Some(Self::List(Vec::new())),
             _ => None,
         }
     }
 
     /// All available type names for the UI picker.
     pub fn all_type_names() -> &'static [&'static str] {
-        &["Bool", "Int", "Float", "String", "Vec2", "Vec3", "Color"]
+        &[
+            "Bool", "Int", "Float", "String", "Vec2", "Vec3", "Color", "Entity", "List",
+        ]
     }
 }
 
-#[derive(Component, Reflect, Clone)]
+#[derive(Component, Reflect, Clone, Debug)]
 #[reflect(Component)]
 pub struct GltfSource {
     pub path: String,
     pub scene_index: usize,
 }
 
+/// Reference to another `.jsn` scene, spawned as this entity's children.
+///
+/// Only the reference is serialized; the referenced scene's entities are loaded
+/// from its file each time, so edits to the source show up in every scene using it.
+#[derive(Component, Reflect, Clone, Debug, Default, PartialEq)]
+#[reflect(Component, Default)]
+pub struct SubScene {
+    /// Asset path of the `.jsn` file, relative to the asset root.
+    pub path: String,
+}
+
+/// Container for many copies of the same prop (scattered foliage, rocks, debris).
+///
+/// Only the container is serialized. At runtime every instance is merged into one
+/// [`InstanceBatch`] mesh per material, so the whole group draws in a few calls, and
+/// editing instances rewrites only their own vertices.
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component, Default)]
+pub struct InstanceGroup {
+    /// Shared mesh for primitive props. Unused when `gltf` is set.
+    pub mesh: Handle<Mesh>,
+    /// Shared material for primitive props. Unused when `gltf` is set.
+    pub material: Handle<StandardMaterial>,
+    /// Shared glTF scene for model props.
+    pub gltf: Option<GltfSource>,
+    /// Per-instance transforms, relative to the container.
+    pub instances: Vec<Transform>,
+}
+
+/// Runtime child drawing every instance of an [`InstanceGroup`] that uses one material,
+/// as a single merged mesh. Not serialized.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct InstanceBatch {
+    /// Triangles each instance adds; instance `i` owns the `i`th run of them.
+    pub triangles_per_instance: usize,
+}
+
+impl InstanceBatch {
+    /// Index into [`InstanceGroup::instances`] of the instance owning `triangle`.
+    pub fn instance_of(&self, triangle: usize) -> usize {
+        triangle / self.triangles_per_instance.max(1)
+    }
+}
+
 /// Tracks the source `.jsn` file for a prefab instance.
 #[derive(Component, Reflect, Clone, Debug, Default, Serialize, Deserialize)]
 #[reflect(Component, Default)]
@@ -336,3 +390,560 @@ impl Default for NavmeshRegion {
         }
     }
 }
+
+/// Persistent identity of a scene entity, stored in the JSN file.
+///
+/// `Entity` values change every time a scene is loaded; use this for references that
+/// must survive save/load, such as links between entities.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[reflect(Component, Default)]
+pub struct StableId(pub Uuid);
+
+impl StableId {
+    /// A new random id.
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl Default for StableId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Link shared by entities made with "Duplicate Linked". Members render with the same
+/// mesh and material assets, and brush members keep identical brush data.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[reflect(Component, Default)]
+pub struct LinkedDuplicate(pub Uuid);
+
+impl LinkedDuplicate {
+    /// A new link with no other members.
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl Default for LinkedDuplicate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hides the entity (and its children) when the scene is loaded in the game.
+/// The editor keeps drawing it; see the hierarchy's gamepad toggle.
+#[derive(Component, Reflect, Default, Clone, Copy, Debug)]
+#[reflect(Component, Default)]
+pub struct HiddenInGame;
+
+/// Marks a brush as a trigger volume: the game reads its shape, classname and custom
+/// properties but doesn't render it. The editor draws it translucent.
+#[derive(Component, Reflect, Default, Clone, Copy, Debug)]
+#[reflect(Component, Default)]
+pub struct TriggerVolume;
+
+/// Marks a brush as a visibility volume for the game's culling: its shape is exported but
+/// not rendered. The editor draws it translucent, tinted by kind.
+#[derive(Component, Reflect, Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub struct VisibilityVolume {
+    pub kind: VisibilityVolumeKind,
+}
+
+#[derive(Reflect, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[reflect(Default)]
+pub enum VisibilityVolumeKind {
+    /// A region of the level; geometry inside it belongs to the zone.
+    #[default]
+    Zone,
+    /// Solid for visibility: hides what is behind it.
+    Occluder,
+    /// Opening between two zones that can see each other through it.
+    Portal,
+}
+
+/// Takes part in the editor's physics simulation as a falling, colliding body; everything
+/// else with geometry stays fixed. For settling props, see the Simulate toggle.
+#[derive(Component, Reflect, Default, Clone, Copy, Debug)]
+#[reflect(Component, Default)]
+pub struct DynamicBody;
+
+/// Small per-entity variation layered over the entity's shared `StandardMaterial` without
+/// editing the asset. Entities with equal overrides of the same material share one derived
+/// material, and the scene file keeps pointing at the shared one.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component, Default)]
+pub struct MaterialOverride {
+    /// Multiplied into the base color.
+    pub tint: Color,
+    pub roughness_multiplier: f32,
+    pub metallic_multiplier: f32,
+    /// Multiplied into the emissive color.
+    pub emissive_boost: f32,
+}
+
+impl Default for MaterialOverride {
+    fn default() -> Self {
+        Self {
+            tint: Color::WHITE,
+            roughness_multiplier: 1.0,
+            metallic_multiplier: 1.0,
+            emissive_boost: 1.0,
+        }
+    }
+}
+
+impl MaterialOverride {
+    /// `base` with this override applied.
+    pub fn apply(&self, base: &StandardMaterial) -> StandardMaterial {
+        let color = base.base_color.to_linear();
+        let tint = self.tint.to_linear();
+        StandardMaterial {
+            base_color: LinearRgba::new(
+                color.red * tint.red,
+                color.green * tint.green,
+                color.blue * tint.blue,
+                color.alpha * tint.alpha,
+            )
+            .into(),
+            perceptual_roughness: (base.perceptual_roughness * self.roughness_multiplier)
+                .clamp(0.0, 1.0),
+            metallic: (base.metallic * self.metallic_multiplier).clamp(0.0, 1.0),
+            emissive: base.emissive * self.emissive_boost,
+            ..base.clone()
+        }
+    }
+
+    /// Bit pattern identifying equal overrides, for sharing derived materials.
+    pub(crate) fn key(&self) -> [u32; 7] {
+        let tint = self.tint.to_linear();
+        [
+            tint.red.to_bits(),
+            tint.green.to_bits(),
+            tint.blue.to_bits(),
+            tint.alpha.to_bits(),
+            self.roughness_multiplier.to_bits(),
+            self.metallic_multiplier.to_bits(),
+            self.emissive_boost.to_bits(),
+        ]
+    }
+}
+
+/// Runtime bookkeeping for an applied [`MaterialOverride`]: the `MeshMaterial3d` shows
+/// `derived`, while `base` is the shared material that gets saved.
+#[derive(Component, Clone, Debug)]
+pub struct MaterialOverrideApplied {
+    pub base: Handle<StandardMaterial>,
+    pub derived: Handle<StandardMaterial>,
+}
+
+/// The authored material of a mesh while its `MeshMaterial3d` shows a stand-in, such as an
+/// editor view mode. Material overrides and saving go through this instead.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct OriginalMaterial(pub Handle<StandardMaterial>);
+
+/// Distance-based level of detail. The entity's own geometry is level 0; each further
+/// level swaps in a simpler glTF model once the camera is at least its `distance` away.
+#[derive(Component, Reflect, Clone, Debug, Default, PartialEq)]
+#[reflect(Component, Default)]
+pub struct LodGroup {
+    /// Coarser levels, by increasing distance.
+    pub levels: Vec<LodLevel>,
+}
+
+#[derive(Reflect, Clone, Debug, Default, PartialEq)]
+#[reflect(Default)]
+pub struct LodLevel {
+    /// Asset path of the glTF model shown at this level.
+    pub path: String,
+    /// Camera distance from which this level replaces the previous one.
+    pub distance: f32,
+}
+
+impl LodGroup {
+    /// The level to show at `distance` from the camera, 0 being the entity's own geometry.
+    pub fn level_at(&self, distance: f32) -> usize {
+        self.levels
+            .iter()
+            .filter(|level| distance >= level.distance)
+            .count()
+    }
+}
+
+/// A structural group of brushes: the editor selects, moves and duplicates it as one
+/// entity unless it has been entered, and bakes and exports its brushes as one object.
+#[derive(Component, Reflect, Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub struct FuncGroup {
+    pub kind: FuncGroupKind,
+}
+
+#[derive(Reflect, Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[reflect(Default)]
+pub enum FuncGroupKind {
+    /// Plain grouping, like Quake's `func_group`.
+    #[default]
+    Group,
+    /// Detail brushwork (`func_detail`) that games needn't treat as world structure,
+    /// e.g. for visibility.
+    Detail,
+}
+
+/// A float over normalized time 0..1, for falloffs and animation. The inspector edits
+/// fields of this type with a curve editor.
+#[derive(Reflect, Clone, Debug, PartialEq)]
+#[reflect(Default)]
+pub struct FloatCurve {
+    /// Keyframes sorted by time.
+    pub keys: Vec<FloatCurveKey>,
+}
+
+/// A keyframe of a [`FloatCurve`]. Tangents are slopes in value units per unit of time.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+pub struct FloatCurveKey {
+    pub time: f32,
+    pub value: f32,
+    pub in_tangent: f32,
+    pub out_tangent: f32,
+}
+
+impl Default for FloatCurve {
+    fn default() -> Self {
+        Self {
+            keys: vec![
+                FloatCurveKey {
+                    time: 0.0,
+                    value: 0.0,
+                    in_tangent: 1.0,
+                    out_tangent: 1.0,
+                },
+                FloatCurveKey {
+                    time: 1.0,
+                    value: 1.0,
+                    in_tangent: 1.0,
+                    out_tangent: 1.0,
+                },
+            ],
+        }
+    }
+}
+
+impl FloatCurve {
+    /// Value at `time` with cubic Hermite interpolation between keys; constant outside them.
+    pub fn sample(&self, time: f32) -> f32 {
+        let (Some(first), Some(last)) = (self.keys.first(), self.keys.last()) else {
+            return 0.0;
+        };
+        if time <= first.time {
+            return first.value;
+        }
+        if time >= last.time {
+            return last.value;
+        }
+        let index = self.keys.partition_point(|k| k.time <= time).max(1);
+        let (a, b) = (self.keys[index - 1], self.keys[index]);
+        let span = b.time - a.time;
+        if span <= f32::EPSILON {
+            return b.value;
+        }
+        let t = (time - a.time) / span;
+        let (t2, t3) = (t * t, t * t * t);
+        (2.0 * t3 - 3.0 * t2 + 1.0) * a.value
+            + (t3 - 2.0 * t2 + t) * span * a.out_tangent
+            + (-2.0 * t3 + 3.0 * t2) * b.value
+            + (t3 - t2) * span * b.in_tangent
+    }
+}
+
+/// Keyframed transform animation, authored in the editor's timeline. Keys are sampled
+/// with linear interpolation; games play it back by calling [`TransformAnimation::sample`].
+#[derive(Component, Reflect, Clone, Debug, PartialEq)]
+#[reflect(Component, Default)]
+pub struct TransformAnimation {
+    /// Length of the clip in seconds.
+    pub duration: f32,
+    /// Whether playback wraps around at the end instead of holding the last pose.
+    pub looping: bool,
+    /// Keyframes sorted by time.
+    pub keys: Vec<TransformKeyframe>,
+}
+
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+pub struct TransformKeyframe {
+    /// Seconds from the start of the clip.
+    pub time: f32,
+    pub transform: Transform,
+}
+
+impl Default for TransformAnimation {
+    fn default() -> Self {
+        Self {
+            duration: 2.0,
+            looping: true,
+            keys: Vec::new(),
+        }
+    }
+}
+
+impl TransformAnimation {
+    /// Pose at `time` seconds, or `None` without keys. Looping clips wrap `time` into
+    /// the clip; others clamp it.
+    pub fn sample(&self, time: f32) -> Option<Transform> {
+        let first = self.keys.first()?;
+        let last = self.keys.last()?;
+        let time = if self.looping && self.duration > 0.0 {
+            time.rem_euclid(self.duration)
+        } else {
+            time.clamp(0.0, self.duration.max(0.0))
+        };
+        if time <= first.time {
+            return Some(first.transform);
+        }
+        if time >= last.time {
+            return Some(last.transform);
+        }
+        let index = self.keys.partition_point(|k| k.time <= time).max(1);
+        let (a, b) = (&self.keys[index - 1], &self.keys[index]);
+        let span = b.time - a.time;
+        if span <= f32::EPSILON {
+            return Some(b.transform);
+        }
+        let t = (time - a.time) / span;
+        Some(Transform {
+            translation: a.transform.translation.lerp(b.transform.translation, t),
+            rotation: a.transform.rotation.slerp(b.transform.rotation, t),
+            scale: a.transform.scale.lerp(b.transform.scale, t),
+        })
+    }
+
+    /// Insert a key at `time`, replacing any key already within a frame of it.
+    pub fn set_key(&mut self, time: f32, transform: Transform) {
+        const FRAME: f32 = 1.0 / 60.0;
+        if let Some(key) = self
+            .keys
+            .iter_mut()
+            .find(|k| (k.time - time).abs() < FRAME / 2.0)
+        {
+            key.transform = transform;
+            return;
+        }
+        let index = self.keys.partition_point(|k| k.time < time);
+        self.keys
+            .insert(index, TransformKeyframe { time, transform });
+    }
+}
+
+/// A simple particle effect. The editor previews it in the viewport; the game reads the
+/// same settings to drive its own particle system.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component, Default)]
+pub struct ParticleEmitter {
+    /// Particles spawned per second.
+    pub rate: f32,
+    /// Seconds each particle lives.
+    pub lifetime: f32,
+    /// Initial speed along the emitter's local +Y, in meters per second.
+    pub speed: f32,
+    /// Half-angle of the emission cone around +Y, in degrees.
+    pub cone_angle: f32,
+    /// Downward acceleration in meters per second squared.
+    pub gravity: f32,
+    /// Particle diameter in meters.
+    pub size: f32,
+    /// Multiplier on `size` over each particle's normalized lifetime.
+    pub size_over_lifetime: FloatCurve,
+    pub start_color: Color,
+    pub end_color: Color,
+    /// Particles alive at once; spawning pauses at the cap.
+    pub max_particles: u32,
+}
+
+impl Default for ParticleEmitter {
+    fn default() -> Self {
+        Self {
+            rate: 20.0,
+            lifetime: 2.0,
+            speed: 2.0,
+            cone_angle: 20.0,
+            gravity: 0.0,
+            size: 0.1,
+            size_over_lifetime: FloatCurve {
+                keys: vec![
+                    FloatCurveKey {
+                        time: 0.0,
+                        value: 1.0,
+                        ..Default::default()
+                    },
+                    FloatCurveKey {
+                        time: 1.0,
+                        value: 0.0,
+                        ..Default::default()
+                    },
+                ],
+            },
+            start_color: Color::WHITE,
+            end_color: Color::WHITE.with_alpha(0.0),
+            max_particles: 500,
+        }
+    }
+}
+
+/// A path of cubic Bézier segments through control points, in the entity's local space.
+/// Used for patrol paths, roads and camera rails.
+#[derive(Component, Reflect, Clone, Debug, Default, PartialEq)]
+#[reflect(Component, Default)]
+pub struct Spline {
+    pub points: Vec<SplinePoint>,
+    /// Whether the last point connects back to the first.
+    pub closed: bool,
+}
+
+/// A control point with its two tangent handles, stored as offsets from `position`.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+pub struct SplinePoint {
+    pub position: Vec3,
+    pub in_tangent: Vec3,
+    pub out_tangent: Vec3,
+}
+
+impl SplinePoint {
+    pub fn new(position: Vec3) -> Self {
+        Self {
+            position,
+            ..Default::default()
+        }
+    }
+}
+
+impl Spline {
+    pub fn segment_count(&self) -> usize {
+        match self.points.len() {
+            0 | 1 => 0,
+            n if self.closed => n,
+            n => n - 1,
+        }
+    }
+
+    /// Bézier control points of segment `index`.
+    pub fn segment(&self, index: usize) -> [Vec3; 4] {
+        let a = self.points[index];
+        let b = self.points[(index + 1) % self.points.len()];
+        [
+            a.position,
+            a.position + a.out_tangent,
+            b.position + b.in_tangent,
+            b.position,
+        ]
+    }
+
+    /// Position on segment `index` at `t` in 0..1.
+    pub fn position(&self, index: usize, t: f32) -> Vec3 {
+        let [p0, p1, p2, p3] = self.segment(index);
+        let u = 1.0 - t;
+        p0 * (u * u * u) + p1 * (3.0 * u * u * t) + p2 * (3.0 * u * t * t) + p3 * (t * t * t)
+    }
+
+    /// Derivative of the position on segment `index` at `t`. Falls back to the chord
+    /// where the handles make it vanish.
+    pub fn tangent(&self, index: usize, t: f32) -> Vec3 {
+        let [p0, p1, p2, p3] = self.segment(index);
+        let u = 1.0 - t;
+        let derivative =
+            (p1 - p0) * (3.0 * u * u) + (p2 - p1) * (6.0 * u * t) + (p3 - p2) * (3.0 * t * t);
+        if derivative.length_squared() > 1e-8 {
+            derivative
+        } else {
+            p3 - p0
+        }
+    }
+
+    /// Positions and unit tangents along the whole spline, `subdivisions` samples per
+    /// segment plus the end point.
+    pub fn tessellate(&self, subdivisions: usize) -> Vec<(Vec3, Vec3)> {
+        let subdivisions = subdivisions.max(1);
+        let segments = self.segment_count();
+        let mut samples = Vec::with_capacity(segments * subdivisions + 1);
+        for index in 0..segments {
+            for step in 0..subdivisions {
+                let t = step as f32 / subdivisions as f32;
+                samples.push((
+                    self.position(index, t),
+                    self.tangent(index, t).normalize_or_zero(),
+                ));
+            }
+        }
+        if segments > 0 {
+            let last = segments - 1;
+            samples.push((
+                self.position(last, 1.0),
+                self.tangent(last, 1.0).normalize_or_zero(),
+            ));
+        }
+        samples
+    }
+
+    /// Append a point, with handles along the direction from the previous point.
+    pub fn push_point(&mut self, position: Vec3) {
+        let mut point = SplinePoint::new(position);
+        if let Some(previous) = self.points.last() {
+            let handle = (position - previous.position) / 3.0;
+            point.in_tangent = -handle;
+            point.out_tangent = handle;
+            if self.points.len() == 1 {
+                self.points[0].in_tangent = -handle;
+                self.points[0].out_tangent = handle;
+            }
+        }
+        self.points.push(point);
+    }
+}
+
+/// Extrudes a 2D profile along the entity's [`Spline`] into a mesh, for roads, rails
+/// and pipes. The mesh is regenerated whenever either component changes.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component, Default)]
+pub struct SplineExtrusion {
+    pub profile: ExtrusionProfile,
+    /// Samples per spline segment; higher is smoother.
+    pub segments_per_curve: u32,
+    /// Meters covered by one repeat of the texture, along and across the path.
+    pub uv_tile_length: f32,
+    pub material: Handle<StandardMaterial>,
+}
+
+impl Default for SplineExtrusion {
+    fn default() -> Self {
+        Self {
+            profile: ExtrusionProfile::default(),
+            segments_per_curve: 16,
+            uv_tile_length: 4.0,
+            material: Handle::default(),
+        }
+    }
+}
+
+/// Cross-section swept along a spline. `x` points right of the path and `y` up.
+#[derive(Reflect, Clone, Debug, PartialEq)]
+pub enum ExtrusionProfile {
+    /// A slab whose top face lies on the spline, like a road.
+    Rect { width: f32, height: f32 },
+    /// A tube centred on the spline, like a pipe.
+    Circle { radius: f32, sides: u32 },
+    /// Any outline, listed counter-clockwise.
+    Custom { points: Vec<Vec2>, closed: bool },
+}
+
+impl Default for ExtrusionProfile {
+    fn default() -> Self {
+        Self::Rect {
+            width: 4.0,
+            height: 0.2,
+        }
+    }
+}
+
+/// Marks the mesh child generated for a [`SplineExtrusion`].
+#[derive(Component, Clone, Copy, Debug)]
+pub struct SplineExtrusionMesh;
@@ -20,12 +20,41 @@ pub struct MenuBarItem {
     pub label: String,
     /// (action_id, display_label) pairs for the dropdown.
     pub actions: Vec<(String, String)>,
+    /// Entries of submenus, keyed by the action_id of the dropdown entry that opens them.
+    pub submenus: Vec<(String, Vec<(String, String)>)>,
+}
+
+impl MenuBarItem {
+    /// Replace (or add) the entries of the submenu opened by `action`.
+    pub fn set_submenu(&mut self, action: &str, entries: Vec<(String, String)>) {
+        match self.submenus.iter_mut().find(|(a, _)| a == action) {
+            Some((_, existing)) => *existing = entries,
+            None => self.submenus.push((action.to_string(), entries)),
+        }
+    }
+
+    pub fn submenu(&self, action: &str) -> Option<&[(String, String)]> {
+        self.submenus
+            .iter()
+            .find(|(a, _)| a == action)
+            .map(|(_, entries)| entries.as_slice())
+    }
 }
 
 /// Marker on the dropdown container spawned when a menu is opened.
 #[derive(Component)]
 pub struct MenuBarDropdown;
 
+/// Marker on the dropdown entry that opens a submenu when hovered.
+#[derive(Component)]
+pub struct MenuBarSubmenuItem {
+    pub action: String,
+}
+
+/// Marker on an open submenu container (a child of the dropdown).
+#[derive(Component)]
+pub struct MenuBarSubmenu;
+
 /// Marker on individual items inside a menu dropdown.
 #[derive(Component)]
 pub struct MenuBarDropdownItem {
@@ -39,6 +68,8 @@ pub struct MenuBarState {
     pub open_menu: Option<Entity>,
     /// The dropdown entity, if spawned.
     pub dropdown_entity: Option<Entity>,
+    /// The open submenu and the dropdown entry that opened it. Despawned with the dropdown.
+    pub submenu: Option<(Entity, Entity)>,
 }
 
 /// Fired when a menu item is clicked.
@@ -56,6 +87,7 @@ fn close_menu_on_action(
         commands.entity(dropdown).despawn();
     }
     state.open_menu = None;
+    state.submenu = None;
 }
 
 fn close_menu_on_click_outside(
@@ -73,5 +105,6 @@ fn close_menu_on_click_outside(
             commands.entity(dropdown).despawn();
         }
         state.open_menu = None;
+        state.submenu = None;
     }
 }
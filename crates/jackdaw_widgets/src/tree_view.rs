@@ -72,6 +72,19 @@ pub struct TreeRowVisibilityToggled {
     pub source_entity: Entity,
 }
 
+/// Marker for the hidden-in-game toggle icon in a tree row.
+#[derive(Component)]
+pub struct TreeRowGameVisibilityToggle;
+
+/// Event fired when a hidden-in-game toggle is clicked
+#[derive(EntityEvent)]
+pub struct TreeRowGameVisibilityToggled {
+    #[event_target]
+    pub entity: Entity,
+    /// The source (scene) entity to toggle the flag on
+    pub source_entity: Entity,
+}
+
 /// Marker on the text input during inline rename
 #[derive(Component)]
 pub struct TreeRowInlineRename;
@@ -137,6 +150,18 @@ pub struct TreeRowDropped {
     pub target_source: Entity,
 }
 
+/// Event fired when something other than a tree row, such as an asset browser item, is
+/// dropped onto a tree row
+#[derive(EntityEvent)]
+pub struct TreeRowExternalDropped {
+    #[event_target]
+    pub entity: Entity,
+    /// The UI entity that was dragged
+    pub dropped: Entity,
+    /// The scene entity the row represents
+    pub target_source: Entity,
+}
+
 /// Event fired when a tree row is dropped onto the root container (deparent)
 #[derive(EntityEvent)]
 pub struct TreeRowDroppedOnRoot {
@@ -187,15 +212,8 @@ fn maintain_tree_index(
     }
 
     for removed_entity in removed.read() {
-        // Scan the map to find which source entity maps to this removed tree row.
-        // This is O(n) but only runs on removal frames, not every frame.
-        let source = index
-            .map
-            .iter()
-            .find(|(_, tree_row)| **tree_row == removed_entity)
-            .map(|(source, _)| *source);
-        if let Some(source) = source {
-            index.remove(source);
-        }
+        // Scan the map for every source entity mapping to this removed tree row; a row
+        // may stand for several. This is O(n) but only runs on removal frames.
+        index.map.retain(|_, tree_row| *tree_row != removed_entity);
     }
 }
@@ -0,0 +1,55 @@
+//! Example showing the embedded in-game editor overlay.
+//!
+//! Press F12 in the running game to open the hierarchy/inspector overlay, click
+//! an entity, tweak it with the arrow keys, and press Ctrl+E to export the
+//! changes to `editor_patch.jsn`. Apply the patch in the editor with
+//! File > Apply Patch.
+//!
+//! Run with: `cargo run --example embedded_editor`
+
+use bevy::prelude::*;
+use jackdaw::EmbeddedEditorPlugin;
+
+fn main() -> AppExit {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // In a real game, gate this behind a debug feature flag.
+        .add_plugins(EmbeddedEditorPlugin::default())
+        .add_systems(Startup, spawn_scene)
+        .run()
+}
+
+fn spawn_scene(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Name::new("Ground"),
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(20.0, 20.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+        Transform::default(),
+    ));
+
+    commands.spawn((
+        Name::new("Crate"),
+        Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.7, 0.5, 0.3))),
+        Transform::from_xyz(0.0, 0.5, 0.0),
+    ));
+
+    commands.spawn((
+        Name::new("Sun"),
+        DirectionalLight {
+            shadows_enabled: true,
+            illuminance: 10000.0,
+            ..default()
+        },
+        Transform::from_xyz(10.0, 20.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(5.0, 5.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+}
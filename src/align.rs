@@ -0,0 +1,432 @@
+use bevy::{ecs::system::SystemState, math::Affine3A, prelude::*};
+
+use jackdaw_geometry::compute_face_tangent_axes;
+use jackdaw_jsn::Brush;
+
+use crate::{
+    EditorEntity,
+    brush::{BrushMeshCache, SetBrush},
+    commands::{CommandGroup, CommandHistory, EditorCommand, SetTransform},
+    cursor3d::Cursor3d,
+    selection::Selection,
+    viewport_overlays,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlignAxis {
+    X,
+    Y,
+    Z,
+}
+
+impl AlignAxis {
+    fn index(self) -> usize {
+        match self {
+            Self::X => 0,
+            Self::Y => 1,
+            Self::Z => 2,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "x" => Some(Self::X),
+            "y" => Some(Self::Y),
+            "z" => Some(Self::Z),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlignEdge {
+    Min,
+    Center,
+    Max,
+}
+
+impl AlignEdge {
+    fn value(self, min: Vec3, max: Vec3, axis: AlignAxis) -> f32 {
+        let i = axis.index();
+        match self {
+            Self::Min => min[i],
+            Self::Center => (min[i] + max[i]) * 0.5,
+            Self::Max => max[i],
+        }
+    }
+}
+
+/// Where aligned entities are moved to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlignTarget {
+    /// The bounds of the whole selection.
+    Selection,
+    /// The bounds of the active (last selected) entity.
+    Active,
+}
+
+/// Point the selection is mirrored about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MirrorPivot {
+    WorldOrigin,
+    /// The center of the selection's bounds.
+    SelectionCenter,
+    Cursor,
+}
+
+/// World-space AABB of an entity: brush geometry, else descendant meshes, else its origin.
+pub(crate) fn world_aabb(
+    world: &mut World,
+    state: &mut SystemState<(
+        Query<(&GlobalTransform, Option<&BrushMeshCache>)>,
+        Query<&Children>,
+        Query<(&Mesh3d, &GlobalTransform)>,
+        Res<Assets<Mesh>>,
+    )>,
+    entity: Entity,
+) -> Option<(Vec3, Vec3)> {
+    let (entities, children_query, mesh_query, meshes) = state.get(world);
+    let (global_tf, brush_cache) = entities.get(entity).ok()?;
+
+    let mut points = Vec::new();
+    if let Some(cache) = brush_cache {
+        points.extend(cache.vertices.iter().map(|v| global_tf.transform_point(*v)));
+    }
+    if points.is_empty() {
+        viewport_overlays::collect_descendant_mesh_world_vertices(
+            entity,
+            &children_query,
+            &mesh_query,
+            &meshes,
+            &mut points,
+        );
+    }
+    if points.is_empty() {
+        let origin = global_tf.translation();
+        return Some((origin, origin));
+    }
+    Some(viewport_overlays::aabb_from_points(&points))
+}
+
+/// Selected entities that can be moved, skipping any whose ancestor is also selected
+/// (moving the ancestor already moves them).
+pub(crate) fn movable_selection(world: &World) -> Vec<Entity> {
+    let selected = &world.resource::<Selection>().entities;
+    selected
+        .iter()
+        .copied()
+        .filter(|&e| {
+            if world.get_entity(e).is_err()
+                || world.get::<EditorEntity>(e).is_some()
+                || world.get::<Transform>(e).is_none()
+            {
+                return false;
+            }
+            let mut current = e;
+            while let Some(&ChildOf(parent)) = world.get::<ChildOf>(current) {
+                if selected.contains(&parent) {
+                    return false;
+                }
+                current = parent;
+            }
+            true
+        })
+        .collect()
+}
+
+/// Build a `SetTransform` that moves `entity` by `world_delta`, accounting for its parent.
+fn translate_command(world: &World, entity: Entity, world_delta: Vec3) -> Option<SetTransform> {
+    let old_transform = *world.get::<Transform>(entity)?;
+    let parent_global = world
+        .get::<ChildOf>(entity)
+        .and_then(|c| world.get::<GlobalTransform>(c.0))
+        .copied()
+        .unwrap_or_default();
+    let local_delta = parent_global
+        .affine()
+        .inverse()
+        .transform_vector3(world_delta);
+    Some(SetTransform {
+        entity,
+        old_transform,
+        new_transform: Transform {
+            translation: old_transform.translation + local_delta,
+            ..old_transform
+        },
+    })
+}
+
+fn push_group(world: &mut World, cmds: Vec<Box<dyn EditorCommand>>, label: String) {
+    if cmds.is_empty() {
+        return;
+    }
+    for cmd in &cmds {
+        cmd.execute(world);
+    }
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(CommandGroup {
+        commands: cmds,
+        label,
+    }));
+}
+
+/// Align the min/center/max of each selected entity's world AABB on `axis`.
+pub fn align_selected(world: &mut World, axis: AlignAxis, edge: AlignEdge, target: AlignTarget) {
+    let entities = movable_selection(world);
+    let active = world.resource::<Selection>().primary();
+    if entities.len() < 2 && !(target == AlignTarget::Active && active.is_some()) {
+        return;
+    }
+
+    let mut state = SystemState::new(world);
+    let bounds: Vec<(Entity, Vec3, Vec3)> = entities
+        .iter()
+        .filter_map(|&e| world_aabb(world, &mut state, e).map(|(min, max)| (e, min, max)))
+        .collect();
+
+    let goal = match target {
+        AlignTarget::Active => {
+            let Some(active) = active else {
+                return;
+            };
+            let Some((min, max)) = world_aabb(world, &mut state, active) else {
+                return;
+            };
+            edge.value(min, max, axis)
+        }
+        AlignTarget::Selection => {
+            let min = bounds
+                .iter()
+                .fold(Vec3::MAX, |acc, (_, min, _)| acc.min(*min));
+            let max = bounds
+                .iter()
+                .fold(Vec3::MIN, |acc, (_, _, max)| acc.max(*max));
+            edge.value(min, max, axis)
+        }
+    };
+
+    let mut cmds: Vec<Box<dyn EditorCommand>> = Vec::new();
+    for (entity, min, max) in bounds {
+        if target == AlignTarget::Active && Some(entity) == active {
+            continue;
+        }
+        let offset = goal - edge.value(min, max, axis);
+        if offset.abs() < 1e-6 {
+            continue;
+        }
+        let mut delta = Vec3::ZERO;
+        delta[axis.index()] = offset;
+        if let Some(cmd) = translate_command(world, entity, delta) {
+            cmds.push(Box::new(cmd));
+        }
+    }
+    push_group(world, cmds, format!("Align {axis:?} {edge:?}"));
+}
+
+/// Space the selected entities' AABB centers evenly along `axis`, keeping the
+/// outermost two in place.
+pub fn distribute_selected(world: &mut World, axis: AlignAxis) {
+    let entities = movable_selection(world);
+    if entities.len() < 3 {
+        return;
+    }
+
+    let mut state = SystemState::new(world);
+    let i = axis.index();
+    let mut centers: Vec<(Entity, f32)> = entities
+        .iter()
+        .filter_map(|&e| {
+            world_aabb(world, &mut state, e).map(|(min, max)| (e, (min[i] + max[i]) * 0.5))
+        })
+        .collect();
+    if centers.len() < 3 {
+        return;
+    }
+    centers.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let first = centers[0].1;
+    let last = centers[centers.len() - 1].1;
+    let step = (last - first) / (centers.len() - 1) as f32;
+
+    let mut cmds: Vec<Box<dyn EditorCommand>> = Vec::new();
+    for (n, &(entity, center)) in centers.iter().enumerate() {
+        let offset = first + step * n as f32 - center;
+        if offset.abs() < 1e-6 {
+            continue;
+        }
+        let mut delta = Vec3::ZERO;
+        delta[i] = offset;
+        if let Some(cmd) = translate_command(world, entity, delta) {
+            cmds.push(Box::new(cmd));
+        }
+    }
+    push_group(world, cmds, format!("Distribute {axis:?}"));
+}
+
+/// Center of the bounds of `entities`.
+pub(crate) fn bounds_center(world: &mut World, entities: &[Entity]) -> Option<Vec3> {
+    let mut state = SystemState::new(world);
+    let (min, max) = entities
+        .iter()
+        .filter_map(|&e| world_aabb(world, &mut state, e))
+        .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)))?;
+    Some((min + max) * 0.5)
+}
+
+/// Mirror the selected entities across the plane through `pivot` perpendicular to
+/// `axis`, in one undo step. Brushes keep a positive scale: their planes are mirrored
+/// instead, so faces stay wound outwards.
+pub fn mirror_selected(world: &mut World, axis: AlignAxis, pivot: MirrorPivot) {
+    let entities = movable_selection(world);
+    if entities.is_empty() {
+        return;
+    }
+    let pivot_point = match pivot {
+        MirrorPivot::WorldOrigin => Vec3::ZERO,
+        MirrorPivot::SelectionCenter => {
+            let Some(center) = bounds_center(world, &entities) else {
+                return;
+            };
+            center
+        }
+        MirrorPivot::Cursor => world.resource::<Cursor3d>().position,
+    };
+    let mut diagonal = Vec3::ONE;
+    diagonal[axis.index()] = -1.0;
+    let reflection = Affine3A::from_translation(pivot_point)
+        * Affine3A::from_mat3(Mat3::from_diagonal(diagonal))
+        * Affine3A::from_translation(-pivot_point);
+
+    let mut cmds: Vec<Box<dyn EditorCommand>> = Vec::new();
+    for entity in entities {
+        let (Some(&old_transform), Some(global)) = (
+            world.get::<Transform>(entity),
+            world.get::<GlobalTransform>(entity),
+        ) else {
+            continue;
+        };
+        let global = global.affine();
+        let parent_inverse = world
+            .get::<ChildOf>(entity)
+            .and_then(|c| world.get::<GlobalTransform>(c.0))
+            .map(|g| g.affine().inverse())
+            .unwrap_or(Affine3A::IDENTITY);
+
+        let Some(brush) = world.get::<Brush>(entity) else {
+            // Anything else takes the reflection into its transform as a negative scale
+            let new_transform =
+                Transform::from_matrix((parent_inverse * reflection * global).into());
+            cmds.push(Box::new(SetTransform {
+                entity,
+                old_transform,
+                new_transform,
+            }));
+            continue;
+        };
+
+        // The brush only moves; its geometry is mirrored in local space.
+        let new_global = Affine3A {
+            translation: reflection.transform_point3a(global.translation),
+            ..global
+        };
+        let new_transform = Transform::from_matrix((parent_inverse * new_global).into());
+        // Maps the new local space back to the old one
+        let to_old = global.inverse() * reflection * new_global;
+        let new_brush = mirror_brush(brush, to_old);
+        cmds.push(Box::new(SetBrush {
+            entity,
+            old: brush.clone(),
+            new: new_brush,
+            label: "Mirror".to_string(),
+        }));
+        cmds.push(Box::new(SetTransform {
+            entity,
+            old_transform,
+            new_transform,
+        }));
+    }
+    push_group(world, cmds, format!("Mirror {axis:?}"));
+}
+
+/// The brush whose points `p` satisfy `to_old(p)` lying in `brush`, with texture axes
+/// carried along so textures mirror with the geometry.
+fn mirror_brush(brush: &Brush, to_old: Affine3A) -> Brush {
+    let linear_t = Mat3::from(to_old.matrix3).transpose();
+    let offset = Vec3::from(to_old.translation);
+    let mut brush = brush.clone();
+    for face in &mut brush.faces {
+        // n . (A p + b) <= d  becomes  (A^T n) . p <= d - n . b
+        let normal = linear_t * face.plane.normal;
+        let distance = face.plane.distance - face.plane.normal.dot(offset);
+        let length = normal.length();
+        if length < 1e-6 {
+            continue;
+        }
+
+        let (u_axis, v_axis) = if face.uv_u_axis != Vec3::ZERO && face.uv_v_axis != Vec3::ZERO {
+            (face.uv_u_axis, face.uv_v_axis)
+        } else {
+            compute_face_tangent_axes(face.plane.normal)
+        };
+        // Projections pick up a constant from the translation; fold it into the offset
+        let (du, dv) = (u_axis.dot(offset), v_axis.dot(offset));
+        let (sin_r, cos_r) = face.uv_rotation.sin_cos();
+        face.uv_offset += Vec2::new(
+            (du * cos_r - dv * sin_r) / face.uv_scale.x.max(0.001),
+            (du * sin_r + dv * cos_r) / face.uv_scale.y.max(0.001),
+        );
+        face.uv_u_axis = linear_t * u_axis;
+        face.uv_v_axis = linear_t * v_axis;
+        face.plane.normal = normal / length;
+        face.plane.distance = distance / length;
+    }
+    brush
+}
+
+/// Dispatch a `mirror.*` menu action, e.g. `mirror.x`, `mirror.origin.y`, `mirror.cursor.z`.
+pub fn handle_mirror_action(world: &mut World, action: &str) {
+    let Some(rest) = action.strip_prefix("mirror.") else {
+        return;
+    };
+    let (pivot, axis) = match rest.split_once('.') {
+        Some(("origin", axis)) => (MirrorPivot::WorldOrigin, axis),
+        Some(("cursor", axis)) => (MirrorPivot::Cursor, axis),
+        Some(_) => return,
+        None => (MirrorPivot::SelectionCenter, rest),
+    };
+    if let Some(axis) = AlignAxis::from_name(axis) {
+        mirror_selected(world, axis, pivot);
+    }
+}
+
+/// Dispatch an `align.*` menu action, e.g. `align.x_min`, `align.active.y_center`,
+/// `align.distribute_z`.
+pub fn handle_align_action(world: &mut World, action: &str) {
+    let Some(rest) = action.strip_prefix("align.") else {
+        return;
+    };
+    if let Some(axis) = rest.strip_prefix("distribute_") {
+        if let Some(axis) = AlignAxis::from_name(axis) {
+            distribute_selected(world, axis);
+        }
+        return;
+    }
+
+    let (target, rest) = match rest.strip_prefix("active.") {
+        Some(rest) => (AlignTarget::Active, rest),
+        None => (AlignTarget::Selection, rest),
+    };
+    let Some((axis, edge)) = rest.split_once('_') else {
+        return;
+    };
+    let Some(axis) = AlignAxis::from_name(axis) else {
+        return;
+    };
+    let edge = match edge {
+        "min" => AlignEdge::Min,
+        "center" => AlignEdge::Center,
+        "max" => AlignEdge::Max,
+        _ => return,
+    };
+    align_selected(world, axis, edge, target);
+}
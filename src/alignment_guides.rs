@@ -40,7 +40,7 @@ pub struct AlignmentGuideState {
 }
 
 /// Returns true if a translation drag is currently active.
-fn is_translate_drag_active(
+pub(crate) fn is_translate_drag_active(
     gizmo_drag: &GizmoDragState,
     gizmo_mode: &GizmoMode,
     modal_state: &ModalTransformState,
@@ -0,0 +1,394 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use jackdaw_feathers::{
+    combobox::{ComboBoxChangeEvent, combobox_with_selected},
+    dialog::{DialogActionEvent, DialogChildrenSlot, DialogClosedEvent, DialogId, OpenDialogEvent},
+    text_edit::{self, TextEditProps, TextEditValue},
+};
+
+use crate::{
+    commands::{CommandGroup, CommandHistory, EditorCommand, SpawnSnapshot},
+    entity_ops::duplicate_entity,
+    instancing::{AUTO_INSTANCE_THRESHOLD, instance_array},
+    selection::{Selected, Selection},
+    viewport_overlays::{aabb_from_points, collect_descendant_mesh_world_vertices},
+};
+
+const ARRAY_DIALOG: &str = "array_duplicate";
+
+pub struct ArrayDuplicatePlugin;
+
+impl Plugin for ArrayDuplicatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ArrayDuplicateSettings>()
+            .init_resource::<PendingArrayDuplicate>()
+            .add_observer(on_array_dialog_action)
+            .add_observer(on_array_dialog_closed)
+            .add_systems(
+                Update,
+                (populate_array_dialog, sync_array_fields, draw_array_preview)
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ArrayMode {
+    /// Each copy is offset from the previous one and rotated by `angle` around Y.
+    #[default]
+    Linear,
+    /// Copies are spread around a pivot at `offset` from the source, over `angle` degrees.
+    Radial,
+}
+
+/// Last-used array parameters. Kept between dialog invocations.
+#[derive(Resource, Clone, Debug)]
+pub struct ArrayDuplicateSettings {
+    pub mode: ArrayMode,
+    pub count: u32,
+    /// Linear: per-copy translation. Radial: pivot relative to the source.
+    pub offset: Vec3,
+    /// Linear: per-copy Y rotation. Radial: total arc (0 or 360 = full circle). Degrees.
+    pub angle: f32,
+}
+
+impl Default for ArrayDuplicateSettings {
+    fn default() -> Self {
+        Self {
+            mode: ArrayMode::Linear,
+            count: 3,
+            offset: Vec3::new(2.0, 0.0, 0.0),
+            angle: 0.0,
+        }
+    }
+}
+
+impl ArrayDuplicateSettings {
+    /// Local transforms of every copy (not including the source itself).
+    pub fn copy_transforms(&self, source: &Transform) -> Vec<Transform> {
+        let count = self.count.max(1);
+        match self.mode {
+            ArrayMode::Linear => (1..=count)
+                .map(|i| {
+                    let i = i as f32;
+                    Transform {
+                        translation: source.translation + self.offset * i,
+                        rotation: Quat::from_rotation_y((self.angle * i).to_radians())
+                            * source.rotation,
+                        scale: source.scale,
+                    }
+                })
+                .collect(),
+            ArrayMode::Radial => {
+                let arc = self.angle.to_radians();
+                let full_circle = arc.abs() < 1e-4 || (arc.abs() - TAU).abs() < 1e-4;
+                let step = if full_circle {
+                    TAU / (count + 1) as f32
+                } else {
+                    arc / count as f32
+                };
+                let pivot = source.translation + self.offset;
+                (1..=count)
+                    .map(|i| {
+                        let rot = Quat::from_rotation_y(step * i as f32);
+                        Transform {
+                            translation: pivot + rot * (source.translation - pivot),
+                            rotation: rot * source.rotation,
+                            scale: source.scale,
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// The entity the open Array Duplicate dialog applies to.
+#[derive(Resource, Default)]
+struct PendingArrayDuplicate {
+    source: Option<Entity>,
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum ArrayField {
+    Count,
+    OffsetX,
+    OffsetY,
+    OffsetZ,
+    Angle,
+}
+
+#[derive(Component)]
+struct ArrayModeSelector;
+
+/// Open the Array Duplicate dialog for the primary selection.
+pub fn open_array_duplicate_dialog(world: &mut World) {
+    let Some(primary) = world.resource::<Selection>().primary() else {
+        return;
+    };
+    if world.get::<Transform>(primary).is_none() {
+        return;
+    }
+    world.resource_mut::<PendingArrayDuplicate>().source = Some(primary);
+    world.trigger(
+        OpenDialogEvent::new("Array Duplicate", "Create")
+            .with_id(ARRAY_DIALOG)
+            .with_max_width(px(360))
+            .with_backdrop_opacity(0.15),
+    );
+}
+
+/// Fill the dialog's children slot with the array parameter fields.
+fn populate_array_dialog(
+    mut commands: Commands,
+    pending: Res<PendingArrayDuplicate>,
+    settings: Res<ArrayDuplicateSettings>,
+    slots: Query<(Entity, &DialogId), Added<DialogChildrenSlot>>,
+) {
+    if pending.source.is_none() {
+        return;
+    }
+    for (slot, id) in &slots {
+        if id.0 != ARRAY_DIALOG {
+            continue;
+        }
+        commands
+            .spawn((
+                ArrayModeSelector,
+                combobox_with_selected(
+                    vec!["Linear", "Radial"],
+                    match settings.mode {
+                        ArrayMode::Linear => 0,
+                        ArrayMode::Radial => 1,
+                    },
+                ),
+                ChildOf(slot),
+            ))
+            .observe(
+                |event: On<ComboBoxChangeEvent>, mut settings: ResMut<ArrayDuplicateSettings>| {
+                    settings.mode = if event.selected == 1 {
+                        ArrayMode::Radial
+                    } else {
+                        ArrayMode::Linear
+                    };
+                },
+            );
+        commands.spawn((
+            ArrayField::Count,
+            text_edit::text_edit(
+                TextEditProps::default()
+                    .with_label("Copies")
+                    .numeric_i32()
+                    .with_min(1.0)
+                    .with_max(1000.0)
+                    .with_default_value(settings.count.to_string()),
+            ),
+            ChildOf(slot),
+        ));
+
+        let row = commands
+            .spawn((
+                Node {
+                    column_gap: px(6),
+                    ..default()
+                },
+                ChildOf(slot),
+            ))
+            .id();
+        for (field, label, value) in [
+            (ArrayField::OffsetX, "Offset / Pivot X", settings.offset.x),
+            (ArrayField::OffsetY, "Y", settings.offset.y),
+            (ArrayField::OffsetZ, "Z", settings.offset.z),
+        ] {
+            commands.spawn((
+                field,
+                text_edit::text_edit(
+                    TextEditProps::default()
+                        .with_label(label)
+                        .numeric_f32()
+                        .grow()
+                        .with_default_value(value.to_string()),
+                ),
+                ChildOf(row),
+            ));
+        }
+
+        commands.spawn((
+            ArrayField::Angle,
+            text_edit::text_edit(
+                TextEditProps::default()
+                    .with_label("Angle (step when linear, arc when radial)")
+                    .numeric_f32()
+                    .with_suffix("°")
+                    .with_default_value(settings.angle.to_string()),
+            ),
+            ChildOf(slot),
+        ));
+    }
+}
+
+/// Copy the live field values into `ArrayDuplicateSettings` so the preview tracks edits.
+fn sync_array_fields(
+    pending: Res<PendingArrayDuplicate>,
+    mut settings: ResMut<ArrayDuplicateSettings>,
+    fields: Query<(&ArrayField, &TextEditValue), Changed<TextEditValue>>,
+) {
+    if pending.source.is_none() {
+        return;
+    }
+    for (field, value) in &fields {
+        let text = value.0.trim();
+        match field {
+            ArrayField::Count => {
+                if let Ok(count) = text.parse::<u32>() {
+                    settings.count = count.clamp(1, 1000);
+                }
+            }
+            ArrayField::OffsetX | ArrayField::OffsetY | ArrayField::OffsetZ | ArrayField::Angle => {
+                let Ok(v) = text.parse::<f32>() else {
+                    continue;
+                };
+                match field {
+                    ArrayField::OffsetX => settings.offset.x = v,
+                    ArrayField::OffsetY => settings.offset.y = v,
+                    ArrayField::OffsetZ => settings.offset.z = v,
+                    _ => settings.angle = v,
+                }
+            }
+        }
+    }
+}
+
+/// However the dialog closes, stop previewing; the Create action has taken the source by then.
+fn on_array_dialog_closed(
+    event: On<DialogClosedEvent>,
+    mut pending: ResMut<PendingArrayDuplicate>,
+) {
+    if event.id == Some(ARRAY_DIALOG) {
+        pending.source = None;
+    }
+}
+
+/// Draw a ghost box for every copy the dialog would create.
+fn draw_array_preview(
+    mut gizmos: Gizmos,
+    pending: Res<PendingArrayDuplicate>,
+    settings: Res<ArrayDuplicateSettings>,
+    transforms: Query<(&Transform, &GlobalTransform)>,
+    parents: Query<&ChildOf>,
+    children_query: Query<&Children>,
+    mesh_query: Query<(&Mesh3d, &GlobalTransform)>,
+    meshes: Res<Assets<Mesh>>,
+) {
+    let Some(source) = pending.source else {
+        return;
+    };
+    let Ok((local, global)) = transforms.get(source) else {
+        return;
+    };
+
+    // Source bounds in its own local space.
+    let mut points = Vec::new();
+    collect_descendant_mesh_world_vertices(
+        source,
+        &children_query,
+        &mesh_query,
+        &meshes,
+        &mut points,
+    );
+    let to_local = global.affine().inverse();
+    let (center, size) = if points.is_empty() {
+        (Vec3::ZERO, Vec3::splat(0.5))
+    } else {
+        let local_points: Vec<Vec3> = points
+            .iter()
+            .map(|p| to_local.transform_point3(*p))
+            .collect();
+        let (min, max) = aabb_from_points(&local_points);
+        ((min + max) * 0.5, (max - min).max(Vec3::splat(0.01)))
+    };
+    let bounds = Transform::from_translation(center).with_scale(size);
+
+    let parent_global = parents
+        .get(source)
+        .ok()
+        .and_then(|c| transforms.get(c.0).ok())
+        .map(|(_, g)| *g)
+        .unwrap_or_default();
+
+    let color = Color::srgba(0.4, 0.8, 1.0, 0.7);
+    for copy in settings.copy_transforms(local) {
+        let ghost = parent_global.mul_transform(copy).mul_transform(bounds);
+        gizmos.cube(ghost, color);
+    }
+}
+
+fn on_array_dialog_action(event: On<DialogActionEvent>, mut commands: Commands) {
+    if event.id != Some(ARRAY_DIALOG) {
+        return;
+    }
+    commands.queue(|world: &mut World| {
+        let Some(source) = world.resource_mut::<PendingArrayDuplicate>().source.take() else {
+            return;
+        };
+        let settings = world.resource::<ArrayDuplicateSettings>().clone();
+        commit_array_duplicate(world, source, &settings);
+    });
+}
+
+/// Spawn every copy and record the whole array as one undo step.
+pub fn commit_array_duplicate(
+    world: &mut World,
+    source: Entity,
+    settings: &ArrayDuplicateSettings,
+) {
+    let Some(&source_transform) = world.get::<Transform>(source) else {
+        return;
+    };
+    let transforms = settings.copy_transforms(&source_transform);
+
+    // Large arrays of one prop render and save far cheaper as a single instance group
+    if transforms.len() + 1 > AUTO_INSTANCE_THRESHOLD
+        && instance_array(world, source, &transforms).is_some()
+    {
+        return;
+    }
+
+    let mut copies = Vec::new();
+    for transform in transforms {
+        let Some(copy) = duplicate_entity(world, source) else {
+            continue;
+        };
+        if let Some(mut t) = world.get_mut::<Transform>(copy) {
+            *t = transform;
+        }
+        copies.push(copy);
+    }
+    if copies.is_empty() {
+        return;
+    }
+
+    let cmds: Vec<Box<dyn EditorCommand>> = copies
+        .iter()
+        .map(|&copy| {
+            Box::new(SpawnSnapshot::from_world(
+                world,
+                &[copy],
+                "Spawn array copy",
+            )) as Box<dyn EditorCommand>
+        })
+        .collect();
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(CommandGroup {
+        commands: cmds,
+        label: format!("Array duplicate ({} copies)", copies.len()),
+    }));
+
+    // Select the source plus its copies so the whole array can be moved together.
+    for &copy in &copies {
+        world.entity_mut(copy).insert(Selected);
+    }
+    world.resource_mut::<Selection>().entities = std::iter::once(source).chain(copies).collect();
+}
@@ -0,0 +1,461 @@
+//! Audit view: flags untextured brush faces, meshes without a usable material,
+//! and components whose asset paths fail to load.
+
+use bevy::{
+    asset::{LoadState, ReflectHandle, UntypedHandle},
+    prelude::*,
+    reflect::{ReflectRef, TypeRegistry},
+    ui_widgets::observe,
+};
+use jackdaw_feathers::{
+    button::{self, ButtonProps, ButtonVariant},
+    tokens,
+};
+use jackdaw_jsn::Brush;
+
+use crate::{
+    EditorEntity,
+    brush::{BrushFaceEntity, BrushMeshCache},
+    face_grid::FaceGridGizmoGroup,
+    selection::Selection,
+    view_modes::NormalsViewMaterial,
+    viewport::MainViewportCamera,
+};
+
+/// Seconds between automatic rescans while the audit is enabled.
+const RESCAN_INTERVAL: f32 = 1.0;
+
+pub struct AssetAuditPlugin;
+
+impl Plugin for AssetAuditPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AssetAudit>().add_systems(
+            Update,
+            (
+                scan_assets,
+                toggle_audit_panel,
+                rebuild_audit_list,
+                draw_audit_highlights,
+            )
+                .chain()
+                .run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AuditIssueKind {
+    /// Brush face using the default palette or a material without a base color texture.
+    UntexturedFace(usize),
+    /// Mesh with no material, or one whose asset isn't loaded.
+    MissingMaterial,
+    /// A component references an asset path that failed to load.
+    MissingAsset(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct AuditIssue {
+    pub entity: Entity,
+    pub kind: AuditIssueKind,
+    /// World-space point the camera jumps to.
+    pub position: Vec3,
+    /// World-space outline for face issues.
+    pub outline: Vec<Vec3>,
+}
+
+#[derive(Resource, Default)]
+pub struct AssetAudit {
+    pub enabled: bool,
+    pub issues: Vec<AuditIssue>,
+    /// Rescan on the next frame instead of waiting for the interval.
+    pub needs_scan: bool,
+}
+
+/// Marker for the audit panel below the viewport.
+#[derive(Component)]
+pub struct AssetAuditPanel;
+
+#[derive(Component)]
+struct AuditIssueList;
+
+#[derive(Component)]
+struct AuditSummaryLabel;
+
+pub fn toggle_audit(world: &mut World) {
+    let mut audit = world.resource_mut::<AssetAudit>();
+    audit.enabled = !audit.enabled;
+    audit.needs_scan = audit.enabled;
+    if !audit.enabled {
+        audit.issues.clear();
+    }
+}
+
+/// Builds the audit panel UI node. Starts hidden (`Display::None`).
+pub fn asset_audit_panel() -> impl Bundle {
+    (
+        AssetAuditPanel,
+        EditorEntity,
+        Node {
+            flex_direction: FlexDirection::Column,
+            width: percent(100),
+            height: px(160.0),
+            flex_shrink: 0.0,
+            display: Display::None,
+            ..Default::default()
+        },
+        BackgroundColor(tokens::PANEL_BG),
+        children![
+            (
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::SpaceBetween,
+                    padding: UiRect::axes(px(tokens::SPACING_MD), px(tokens::SPACING_XS)),
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+                BackgroundColor(tokens::PANEL_HEADER_BG),
+                children![
+                    (
+                        AuditSummaryLabel,
+                        Text::new("Asset Audit"),
+                        TextFont {
+                            font_size: tokens::FONT_SM,
+                            ..Default::default()
+                        },
+                        TextColor(tokens::TEXT_SECONDARY),
+                    ),
+                    (
+                        button::button(
+                            ButtonProps::new("Rescan").with_variant(ButtonVariant::Default),
+                        ),
+                        observe(|_: On<Pointer<Click>>, mut audit: ResMut<AssetAudit>| {
+                            audit.needs_scan = true;
+                        }),
+                    ),
+                ],
+            ),
+            (
+                AuditIssueList,
+                EditorEntity,
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    width: percent(100),
+                    flex_grow: 1.0,
+                    min_height: px(0.0),
+                    overflow: Overflow::scroll_y(),
+                    padding: UiRect::all(px(tokens::SPACING_XS)),
+                    ..Default::default()
+                },
+            ),
+        ],
+    )
+}
+
+fn toggle_audit_panel(audit: Res<AssetAudit>, mut panels: Query<&mut Node, With<AssetAuditPanel>>) {
+    if !audit.is_changed() {
+        return;
+    }
+    for mut node in &mut panels {
+        node.display = if audit.enabled {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+/// Recursively collect every `Handle<T>` inside a reflected value.
+fn collect_handles(
+    value: &dyn PartialReflect,
+    registry: &TypeRegistry,
+    out: &mut Vec<UntypedHandle>,
+) {
+    if let Some(reflect) = value.try_as_reflect() {
+        let type_id = reflect.reflect_type_info().type_id();
+        if let Some(reflect_handle) = registry.get_type_data::<ReflectHandle>(type_id) {
+            if let Some(handle) = reflect_handle.downcast_handle_untyped(reflect.as_any()) {
+                out.push(handle);
+            }
+            return;
+        }
+    }
+    match value.reflect_ref() {
+        ReflectRef::Struct(s) => s
+            .iter_fields()
+            .for_each(|f| collect_handles(f, registry, out)),
+        ReflectRef::TupleStruct(s) => s
+            .iter_fields()
+            .for_each(|f| collect_handles(f, registry, out)),
+        ReflectRef::Tuple(t) => t
+            .iter_fields()
+            .for_each(|f| collect_handles(f, registry, out)),
+        ReflectRef::List(l) => l.iter().for_each(|f| collect_handles(f, registry, out)),
+        ReflectRef::Array(a) => a.iter().for_each(|f| collect_handles(f, registry, out)),
+        ReflectRef::Map(m) => m
+            .iter()
+            .for_each(|(_, v)| collect_handles(v, registry, out)),
+        ReflectRef::Set(s) => s.iter().for_each(|f| collect_handles(f, registry, out)),
+        ReflectRef::Enum(e) => e
+            .iter_fields()
+            .for_each(|f| collect_handles(f.value(), registry, out)),
+        _ => {}
+    }
+}
+
+fn load_failed(asset_server: &AssetServer, handle: &UntypedHandle) -> bool {
+    matches!(
+        asset_server.get_load_state(handle.id()),
+        Some(LoadState::Failed(_))
+    )
+}
+
+fn scan_assets(world: &mut World, mut since_scan: Local<f32>) {
+    let delta = world.resource::<Time>().delta_secs();
+    let audit = world.resource::<AssetAudit>();
+    if !audit.enabled {
+        return;
+    }
+    *since_scan += delta;
+    if !audit.needs_scan && *since_scan < RESCAN_INTERVAL {
+        return;
+    }
+    *since_scan = 0.0;
+
+    let mut brushes = world.query_filtered::<(
+        Entity,
+        &Brush,
+        &BrushMeshCache,
+        &GlobalTransform,
+    ), Without<EditorEntity>>();
+    let mut meshes = world.query_filtered::<(
+        Entity,
+        &GlobalTransform,
+        Option<&MeshMaterial3d<StandardMaterial>>,
+        Has<MeshMaterial3d<NormalsViewMaterial>>,
+        Has<BrushFaceEntity>,
+    ), (With<Mesh3d>, Without<EditorEntity>)>();
+    let mut scene_entities =
+        world.query_filtered::<(Entity, &GlobalTransform), Without<EditorEntity>>();
+
+    let mut issues = Vec::new();
+    let asset_server = world.resource::<AssetServer>();
+    let materials = world.resource::<Assets<StandardMaterial>>();
+
+    // Brush faces
+    for (entity, brush, cache, global_tf) in brushes.iter(world) {
+        for (face_index, (face, polygon)) in
+            brush.faces.iter().zip(&cache.face_polygons).enumerate()
+        {
+            let textured = face.material != Handle::default()
+                && materials
+                    .get(&face.material)
+                    .is_some_and(|m| m.base_color_texture.is_some());
+            if textured {
+                continue;
+            }
+            let outline: Vec<Vec3> = polygon
+                .iter()
+                .map(|&i| global_tf.transform_point(cache.vertices[i]))
+                .collect();
+            let position = if outline.is_empty() {
+                global_tf.translation()
+            } else {
+                outline.iter().sum::<Vec3>() / outline.len() as f32
+            };
+            issues.push(AuditIssue {
+                entity,
+                kind: AuditIssueKind::UntexturedFace(face_index),
+                position,
+                outline,
+            });
+        }
+    }
+
+    // Meshes without a usable material. Brush faces are covered above, and the
+    // normals view mode swaps the standard material out on purpose.
+    for (entity, global_tf, material, normals_view, brush_face) in meshes.iter(world) {
+        if brush_face || normals_view {
+            continue;
+        }
+        let missing = material.is_none_or(|m| {
+            materials.get(&m.0).is_none()
+                && !matches!(
+                    asset_server.get_load_state(&m.0),
+                    Some(LoadState::Loading | LoadState::Loaded)
+                )
+        });
+        if missing {
+            issues.push(AuditIssue {
+                entity,
+                kind: AuditIssueKind::MissingMaterial,
+                position: global_tf.translation(),
+                outline: Vec::new(),
+            });
+        }
+    }
+
+    // Any reflected component holding a path that failed to load.
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    for (entity, global_tf) in scene_entities.iter(world) {
+        let entity_ref = world.entity(entity);
+        let mut handles = Vec::new();
+        for registration in registry.iter() {
+            let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+                continue;
+            };
+            if let Some(component) = reflect_component.reflect(entity_ref) {
+                collect_handles(component.as_partial_reflect(), &registry, &mut handles);
+            }
+        }
+        if let Some(brush) = entity_ref.get::<Brush>() {
+            // Face materials are loaded assets whose textures can fail independently.
+            for face in &brush.faces {
+                if let Some(texture) = materials
+                    .get(&face.material)
+                    .and_then(|m| m.base_color_texture.clone())
+                {
+                    handles.push(texture.untyped());
+                }
+            }
+        }
+        let mut reported: Vec<String> = Vec::new();
+        for handle in handles {
+            let Some(path) = handle.path() else {
+                continue;
+            };
+            let path = path.to_string();
+            if reported.contains(&path) || !load_failed(asset_server, &handle) {
+                continue;
+            }
+            issues.push(AuditIssue {
+                entity,
+                kind: AuditIssueKind::MissingAsset(path.clone()),
+                position: global_tf.translation(),
+                outline: Vec::new(),
+            });
+            reported.push(path);
+        }
+    }
+    drop(registry);
+
+    let mut audit = world.resource_mut::<AssetAudit>();
+    audit.needs_scan = false;
+    let unchanged = audit.issues.len() == issues.len()
+        && audit
+            .issues
+            .iter()
+            .zip(&issues)
+            .all(|(a, b)| a.entity == b.entity && a.kind == b.kind);
+    if unchanged {
+        // Keep positions fresh without triggering a list rebuild.
+        audit.bypass_change_detection().issues = issues;
+    } else {
+        audit.issues = issues;
+    }
+}
+
+fn issue_label(world_names: &Query<&Name>, issue: &AuditIssue) -> String {
+    let name = world_names
+        .get(issue.entity)
+        .map(|n| n.as_str().to_string())
+        .unwrap_or_else(|_| format!("Entity {}", issue.entity));
+    match &issue.kind {
+        AuditIssueKind::UntexturedFace(face) => format!("{name}: face {face} has no texture"),
+        AuditIssueKind::MissingMaterial => format!("{name}: mesh has no material"),
+        AuditIssueKind::MissingAsset(path) => format!("{name}: missing asset '{path}'"),
+    }
+}
+
+fn rebuild_audit_list(
+    mut commands: Commands,
+    audit: Res<AssetAudit>,
+    names: Query<&Name>,
+    lists: Query<(Entity, Option<&Children>), With<AuditIssueList>>,
+    mut summaries: Query<&mut Text, With<AuditSummaryLabel>>,
+) {
+    if !audit.is_changed() {
+        return;
+    }
+    for mut text in &mut summaries {
+        text.0 = match audit.issues.len() {
+            0 => "Asset Audit: no issues".to_string(),
+            1 => "Asset Audit: 1 issue".to_string(),
+            n => format!("Asset Audit: {n} issues"),
+        };
+    }
+    for (list, children) in &lists {
+        if let Some(children) = children {
+            for child in children.iter() {
+                commands.entity(child).despawn();
+            }
+        }
+        for (index, issue) in audit.issues.iter().enumerate() {
+            commands.spawn((
+                Node {
+                    padding: UiRect::axes(px(tokens::SPACING_SM), px(2.0)),
+                    border_radius: BorderRadius::all(px(tokens::BORDER_RADIUS_SM)),
+                    ..Default::default()
+                },
+                Text::new(issue_label(&names, issue)),
+                TextFont {
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                TextColor(tokens::TEXT_PRIMARY),
+                ChildOf(list),
+                observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+                    commands.queue(move |world: &mut World| focus_issue(world, index));
+                }),
+            ));
+        }
+    }
+}
+
+/// Select the offending entity and move the camera to look at it.
+fn focus_issue(world: &mut World, index: usize) {
+    let Some(issue) = world.resource::<AssetAudit>().issues.get(index).cloned() else {
+        return;
+    };
+    if world.get_entity(issue.entity).is_err() {
+        return;
+    }
+
+    let mut state = bevy::ecs::system::SystemState::<(Commands, ResMut<Selection>)>::new(world);
+    let (mut commands, mut selection) = state.get_mut(world);
+    selection.select_single(&mut commands, issue.entity);
+    state.apply(world);
+
+    let mut cameras = world.query_filtered::<&mut Transform, With<MainViewportCamera>>();
+    for mut transform in cameras.iter_mut(world) {
+        let forward = transform.forward().as_vec3();
+        transform.translation = issue.position - forward * 6.0;
+    }
+}
+
+fn draw_audit_highlights(mut gizmos: Gizmos<FaceGridGizmoGroup>, audit: Res<AssetAudit>) {
+    if !audit.enabled {
+        return;
+    }
+    let face_color = Color::srgb(1.0, 0.2, 0.8);
+    let missing_color = Color::srgb(1.0, 0.25, 0.2);
+    for issue in &audit.issues {
+        match issue.kind {
+            AuditIssueKind::UntexturedFace(_) => {
+                for (i, &a) in issue.outline.iter().enumerate() {
+                    let b = issue.outline[(i + 1) % issue.outline.len()];
+                    gizmos.line(a, b, face_color);
+                    gizmos.line(a, issue.position, face_color.with_alpha(0.3));
+                }
+            }
+            AuditIssueKind::MissingMaterial | AuditIssueKind::MissingAsset(_) => {
+                gizmos.sphere(
+                    Isometry3d::from_translation(issue.position),
+                    0.3,
+                    missing_color,
+                );
+            }
+        }
+    }
+}
@@ -1,5 +1,4 @@
 use std::path::{Path, PathBuf};
-use std::sync::{Mutex, mpsc};
 
 use bevy::{
     asset::RenderAssetUsages,
@@ -11,15 +10,18 @@ use bevy::{
     ui_widgets::observe,
     window::{PrimaryWindow, RawHandleWrapper},
 };
-use jackdaw_feathers::{file_browser, icons, icons::IconFont, popover, tokens};
+use jackdaw_feathers::{
+    file_browser, icons, icons::IconFont, panel_header::PanelHeader, popover, tokens,
+};
 use jackdaw_widgets::file_browser::{FileBrowserItem, FileItemDoubleClicked};
 use rfd::AsyncFileDialog;
 
 use crate::{
     EditorEntity,
     brush::{Brush, BrushEditMode, BrushSelection, EditMode, LastUsedMaterial, SetBrush},
-    commands::CommandHistory,
+    commands::{CommandGroup, CommandHistory, EditorCommand},
     material_browser::{MaterialRegistry, pbr_filename_regex},
+    project_watcher::{ProjectFilesChanged, ProjectWatcher},
     selection::Selection,
 };
 
@@ -39,45 +41,8 @@ pub fn is_ktx2_non_2d(path: &Path) -> bool {
     pixel_depth > 0 || layer_count > 1 || face_count > 1
 }
 
-/// Watches the asset root directory for filesystem changes using the `notify` crate.
-#[derive(Resource)]
-struct DirectoryWatcher {
-    _watcher: notify::RecommendedWatcher,
-    receiver: Mutex<mpsc::Receiver<()>>,
-}
-
-fn setup_directory_watcher(root: &Path, commands: &mut Commands) {
-    let (tx, rx) = mpsc::channel();
-    let watcher = notify::recommended_watcher(move |res: Result<notify::Event, notify::Error>| {
-        if let Ok(event) = res {
-            use notify::EventKind;
-            if matches!(
-                event.kind,
-                EventKind::Create(_)
-                    | EventKind::Remove(_)
-                    | EventKind::Modify(notify::event::ModifyKind::Name(_))
-            ) {
-                let _ = tx.send(());
-            }
-        }
-    });
-    match watcher {
-        Ok(mut w) => {
-            use notify::Watcher;
-            if w.watch(root, notify::RecursiveMode::Recursive).is_ok() {
-                commands.insert_resource(DirectoryWatcher {
-                    _watcher: w,
-                    receiver: Mutex::new(rx),
-                });
-            } else {
-                warn!("Failed to watch directory: {:?}", root);
-            }
-        }
-        Err(e) => {
-            warn!("Failed to create directory watcher: {}", e);
-        }
-    }
-}
+/// Source key of the browsed folder in [`ProjectWatcher`].
+const ASSET_BROWSER_WATCH: &str = "asset_browser";
 
 pub struct AssetBrowserPlugin;
 
@@ -209,7 +174,7 @@ struct AssetBrowserFolderTask(Task<Option<rfd::FileHandle>>);
 
 // ── Helpers (absorbed from texture_browser) ─────────────────────────────────
 
-fn is_image_file_path(path: &Path) -> bool {
+pub(crate) fn is_image_file_path(path: &Path) -> bool {
     let Some(ext) = path.extension() else {
         return false;
     };
@@ -243,7 +208,7 @@ fn read_ktx2_info(path: &Path) -> (u32, u32) {
 
 fn setup_initial_directory(
     mut state: ResMut<AssetBrowserState>,
-    mut commands: Commands,
+    mut watcher: ResMut<ProjectWatcher>,
     project_root: Option<Res<crate::project::ProjectRoot>>,
 ) {
     if let Some(project) = project_root {
@@ -259,7 +224,7 @@ fn setup_initial_directory(
     }
     state.needs_refresh = true;
 
-    setup_directory_watcher(&state.root_directory, &mut commands);
+    watcher.set_root(ASSET_BROWSER_WATCH, Some(state.root_directory.clone()));
 }
 
 fn refresh_browser_on_change(
@@ -640,6 +605,9 @@ fn handle_apply_texture(
         })
     };
 
+    // Selected faces in face mode, otherwise every face of every selected brush,
+    // as one undo step.
+    let mut cmds: Vec<Box<dyn EditorCommand>> = Vec::new();
     if *edit_mode == EditMode::BrushEdit(BrushEditMode::Face) && !brush_selection.faces.is_empty() {
         if let Some(entity) = brush_selection.entity {
             if let Ok(mut brush) = brushes.get_mut(entity) {
@@ -649,14 +617,12 @@ fn handle_apply_texture(
                         brush.faces[face_idx].material = material.clone();
                     }
                 }
-                let cmd = SetBrush {
+                cmds.push(Box::new(SetBrush {
                     entity,
                     old,
                     new: brush.clone(),
                     label: "Apply texture".into(),
-                };
-                history.undo_stack.push(Box::new(cmd));
-                history.redo_stack.clear();
+                }));
             }
         }
     } else {
@@ -666,17 +632,21 @@ fn handle_apply_texture(
                 for face in brush.faces.iter_mut() {
                     face.material = material.clone();
                 }
-                let cmd = SetBrush {
+                cmds.push(Box::new(SetBrush {
                     entity,
                     old,
                     new: brush.clone(),
                     label: "Apply texture".into(),
-                };
-                history.undo_stack.push(Box::new(cmd));
-                history.redo_stack.clear();
+                }));
             }
         }
     }
+    if !cmds.is_empty() {
+        history.push_executed(Box::new(CommandGroup {
+            commands: cmds,
+            label: "Apply texture".to_string(),
+        }));
+    }
 
     last_material.material = Some(material);
 }
@@ -1071,9 +1041,10 @@ fn poll_asset_browser_folder(world: &mut World) {
         state.current_directory = path.clone();
         state.needs_refresh = true;
 
-        // Set up filesystem watcher for the new root.
-        let mut commands = world.commands();
-        setup_directory_watcher(&path, &mut commands);
+        // Watch the new root for filesystem changes.
+        world
+            .resource_mut::<ProjectWatcher>()
+            .set_root(ASSET_BROWSER_WATCH, Some(path.clone()));
 
         let mut label_query = world.query_filtered::<&mut Text, With<AssetBrowserRootLabel>>();
         for mut text in label_query.iter_mut(world) {
@@ -1100,6 +1071,7 @@ pub fn asset_browser_panel(icon_font: Handle<Font>) -> impl Bundle {
         children![
             // Root directory header
             (
+                PanelHeader,
                 Node {
                     flex_direction: FlexDirection::Row,
                     align_items: AlignItems::Center,
@@ -1226,22 +1198,19 @@ fn asset_folder_button(icon_font: Handle<Font>) -> impl Bundle {
     )
 }
 
-/// Checks for filesystem events from the `notify` watcher and triggers browser refreshes.
+/// Refreshes the browsers when files are created, removed or renamed on disk.
 fn check_watcher_events(
-    watcher: Option<Res<DirectoryWatcher>>,
+    mut changes: MessageReader<ProjectFilesChanged>,
     mut browser: ResMut<AssetBrowserState>,
     mut material_browser: ResMut<crate::material_browser::MaterialBrowserState>,
+    mut template_browser: ResMut<crate::template_browser::TemplateBrowserState>,
 ) {
-    let Some(watcher) = watcher else { return };
-    let Ok(rx) = watcher.receiver.lock() else {
-        return;
-    };
-    let mut changed = false;
-    while rx.try_recv().is_ok() {
-        changed = true;
-    }
+    let changed = changes
+        .read()
+        .fold(false, |changed, change| changed | change.listing_changed);
     if changed {
         browser.needs_refresh = true;
         material_browser.needs_rescan = true;
+        template_browser.needs_refresh = true;
     }
 }
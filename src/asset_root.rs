@@ -0,0 +1,127 @@
+//! The default asset source, rooted at the open project's asset directory.
+//!
+//! `AssetPlugin::file_path` is fixed when the app is built, before a project is picked,
+//! so the default source reads through [`AssetRoot`] instead and follows whichever
+//! project is open.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use bevy::{
+    asset::io::{
+        AssetReader, AssetReaderError, AssetSource, AssetSourceBuilder, AssetSourceId, PathStream,
+        Reader, VecReader,
+    },
+    prelude::*,
+    tasks::futures_lite::stream,
+};
+
+/// Absolute directory the default asset source loads from.
+#[derive(Resource, Clone)]
+pub struct AssetRoot(Arc<RwLock<PathBuf>>);
+
+impl AssetRoot {
+    pub fn get(&self) -> PathBuf {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn set(&self, path: PathBuf) {
+        let mut root = self.0.write().unwrap_or_else(|e| e.into_inner());
+        if *root != path {
+            info!("Asset root: {}", path.display());
+            *root = path;
+        }
+    }
+}
+
+/// Makes the default asset source read through [`AssetRoot`], starting at `root`.
+/// Must be added before `AssetPlugin`. File watching stays on `root`: hot reload
+/// follows the project the editor started with.
+pub struct ProjectAssetSourcePlugin {
+    pub root: PathBuf,
+}
+
+impl Plugin for ProjectAssetSourcePlugin {
+    fn build(&self, app: &mut App) {
+        let root = AssetRoot(Arc::new(RwLock::new(self.root.clone())));
+        let reader_root = root.clone();
+        let path = self.root.to_string_lossy().to_string();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || Box::new(ProjectAssetReader(reader_root.clone())))
+                .with_writer(AssetSource::get_default_writer(path.clone()))
+                .with_watcher(AssetSource::get_default_watcher(
+                    path,
+                    Duration::from_millis(300),
+                ))
+                .with_watch_warning(AssetSource::get_default_watch_warning()),
+        )
+        .insert_resource(root);
+    }
+}
+
+struct ProjectAssetReader(AssetRoot);
+
+impl AssetReader for ProjectAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        read_file(self.0.get().join(path))
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let mut extension = path.extension().unwrap_or_default().to_os_string();
+        if !extension.is_empty() {
+            extension.push(".");
+        }
+        extension.push("meta");
+        read_file(self.0.get().join(path).with_extension(extension))
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let root = self.0.get();
+        let full_path = root.join(path);
+        let entries = std::fs::read_dir(&full_path).map_err(|e| io_error(e, full_path))?;
+        // Meta and hidden files aren't assets.
+        let paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                !path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("meta"))
+                    && !path
+                        .file_name()
+                        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+            })
+            .filter_map(|path| path.strip_prefix(&root).ok().map(Path::to_path_buf))
+            .collect();
+        Ok(Box::new(stream::iter(paths)))
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        self.0
+            .get()
+            .join(path)
+            .metadata()
+            .map(|metadata| metadata.is_dir())
+            .map_err(|_| AssetReaderError::NotFound(path.to_path_buf()))
+    }
+}
+
+fn read_file(full_path: PathBuf) -> Result<VecReader, AssetReaderError> {
+    std::fs::read(&full_path)
+        .map(VecReader::new)
+        .map_err(|e| io_error(e, full_path))
+}
+
+fn io_error(error: std::io::Error, full_path: PathBuf) -> AssetReaderError {
+    if error.kind() == std::io::ErrorKind::NotFound {
+        AssetReaderError::NotFound(full_path)
+    } else {
+        error.into()
+    }
+}
@@ -2,3 +2,199 @@
 pub use jackdaw_geometry::{
     brush_planes_to_world, brushes_intersect, clean_degenerate_faces, subtract_brush,
 };
+
+use bevy::{
+    ecs::entity::EntityHashMap,
+    mesh::PrimitiveTopology,
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task, futures_lite::future},
+};
+use jackdaw_geometry::mesh_to_convex_brushes;
+use jackdaw_jsn::{Brush, BrushFaceData};
+
+use super::BrushFaceEntity;
+
+/// Convex decompositions of closed meshes, worked out on the async compute pool so the
+/// CSG preview doesn't stall the editor. Each is kept with the world-space mesh it was
+/// made from and reused while that mesh stays the same.
+#[derive(Resource, Default)]
+pub struct MeshCsgCache {
+    entries: EntityHashMap<MeshCsgEntry>,
+}
+
+struct MeshCsgEntry {
+    vertices: Vec<Vec3>,
+    triangles: Vec<[u32; 3]>,
+    pieces: MeshCsgPieces,
+}
+
+enum MeshCsgPieces {
+    Pending(Task<Vec<Vec<BrushFaceData>>>),
+    Ready(Vec<Vec<BrushFaceData>>),
+}
+
+/// The triangles of a non-brush entity and its descendants, in world space.
+struct WorldMesh {
+    vertices: Vec<Vec3>,
+    triangles: Vec<[u32; 3]>,
+    material: Option<Handle<StandardMaterial>>,
+}
+
+impl WorldMesh {
+    fn of(world: &World, entity: Entity) -> Option<Self> {
+        if world.get::<Brush>(entity).is_some() {
+            return None;
+        }
+        let mut mesh = Self {
+            vertices: Vec::new(),
+            triangles: Vec::new(),
+            material: None,
+        };
+        collect_mesh_triangles(
+            world,
+            entity,
+            &mut mesh.vertices,
+            &mut mesh.triangles,
+            &mut mesh.material,
+        );
+        (!mesh.triangles.is_empty()).then_some(mesh)
+    }
+
+    fn matches(&self, entry: &MeshCsgEntry) -> bool {
+        self.vertices == entry.vertices && self.triangles == entry.triangles
+    }
+
+    fn with_material(&self, mut pieces: Vec<Vec<BrushFaceData>>) -> Vec<Vec<BrushFaceData>> {
+        if let Some(material) = &self.material {
+            for face in pieces.iter_mut().flatten() {
+                face.material = material.clone();
+            }
+        }
+        pieces
+    }
+}
+
+/// Convex world-space brushes filling the closed meshes of a non-brush entity and its
+/// descendants, so imported meshes can take part in CSG. Empty for brushes and for
+/// entities without triangle meshes. Reuses a finished decomposition from
+/// [`MeshCsgCache`], otherwise decomposes on the calling thread.
+pub fn mesh_csg_brushes(world: &World, entity: Entity) -> Vec<Vec<BrushFaceData>> {
+    let Some(mesh) = WorldMesh::of(world, entity) else {
+        return Vec::new();
+    };
+    let cached = world
+        .resource::<MeshCsgCache>()
+        .entries
+        .get(&entity)
+        .filter(|entry| mesh.matches(entry));
+    let pieces = match cached.map(|entry| &entry.pieces) {
+        Some(MeshCsgPieces::Ready(pieces)) => pieces.clone(),
+        _ => mesh_to_convex_brushes(&mesh.vertices, &mesh.triangles),
+    };
+    mesh.with_material(pieces)
+}
+
+/// Like [`mesh_csg_brushes`], but never decomposes on the calling thread. A mesh without
+/// a finished decomposition is queued on the async compute pool and `None` returned;
+/// [`MeshCsgCache`] shows as changed once the pieces are in.
+pub fn request_mesh_csg_brushes(
+    world: &mut World,
+    entity: Entity,
+) -> Option<Vec<Vec<BrushFaceData>>> {
+    let Some(mesh) = WorldMesh::of(world, entity) else {
+        return Some(Vec::new());
+    };
+    // Queuing isn't a result yet, so it mustn't wake anything watching the cache
+    let mut cache = world.resource_mut::<MeshCsgCache>();
+    let entries = &mut cache.bypass_change_detection().entries;
+    match entries.get(&entity).filter(|entry| mesh.matches(entry)) {
+        Some(MeshCsgEntry {
+            pieces: MeshCsgPieces::Ready(pieces),
+            ..
+        }) => Some(mesh.with_material(pieces.clone())),
+        Some(_) => None,
+        None => {
+            let (vertices, triangles) = (mesh.vertices.clone(), mesh.triangles.clone());
+            let task = AsyncComputeTaskPool::get()
+                .spawn(async move { mesh_to_convex_brushes(&vertices, &triangles) });
+            entries.insert(
+                entity,
+                MeshCsgEntry {
+                    vertices: mesh.vertices,
+                    triangles: mesh.triangles,
+                    pieces: MeshCsgPieces::Pending(task),
+                },
+            );
+            None
+        }
+    }
+}
+
+/// Collect finished decompositions and forget those of despawned entities.
+pub(crate) fn poll_mesh_csg_tasks(mut cache: ResMut<MeshCsgCache>, entities: Query<()>) {
+    let entries = &mut cache.bypass_change_detection().entries;
+    entries.retain(|&entity, _| entities.contains(entity));
+    let mut finished = false;
+    for entry in entries.values_mut() {
+        if let MeshCsgPieces::Pending(task) = &mut entry.pieces
+            && let Some(pieces) = future::block_on(future::poll_once(task))
+        {
+            entry.pieces = MeshCsgPieces::Ready(pieces);
+            finished = true;
+        }
+    }
+    if finished {
+        cache.set_changed();
+    }
+}
+
+fn collect_mesh_triangles(
+    world: &World,
+    entity: Entity,
+    vertices: &mut Vec<Vec3>,
+    triangles: &mut Vec<[u32; 3]>,
+    material: &mut Option<Handle<StandardMaterial>>,
+) {
+    if world.get::<BrushFaceEntity>(entity).is_some() {
+        return;
+    }
+    if let (Some(mesh3d), Some(global)) = (
+        world.get::<Mesh3d>(entity),
+        world.get::<GlobalTransform>(entity),
+    ) && let Some(mesh) = world.resource::<Assets<Mesh>>().get(&mesh3d.0)
+        && mesh.primitive_topology() == PrimitiveTopology::TriangleList
+        && let Some(positions) = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(|a| a.as_float3())
+    {
+        let base = vertices.len() as u32;
+        vertices.extend(
+            positions
+                .iter()
+                .map(|p| global.transform_point(Vec3::from(*p))),
+        );
+        let indices: Vec<u32> = match mesh.indices() {
+            Some(indices) => indices.iter().map(|i| i as u32).collect(),
+            None => (0..positions.len() as u32).collect(),
+        };
+        // Mirroring transforms flip the winding
+        let mirrored = global.affine().matrix3.determinant() < 0.0;
+        triangles.extend(indices.chunks_exact(3).map(|t| {
+            if mirrored {
+                [base + t[0], base + t[2], base + t[1]]
+            } else {
+                [base + t[0], base + t[1], base + t[2]]
+            }
+        }));
+        if material.is_none() {
+            *material = world
+                .get::<MeshMaterial3d<StandardMaterial>>(entity)
+                .map(|m| m.0.clone());
+        }
+    }
+    if let Some(children) = world.get::<Children>(entity) {
+        for child in children.iter() {
+            collect_mesh_triangles(world, child, vertices, triangles, material);
+        }
+    }
+}
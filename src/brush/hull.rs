@@ -213,6 +213,8 @@ pub(super) fn rebuild_brush_from_vertices(
             uv_rotation: old_face.uv_rotation,
             uv_u_axis: u_axis,
             uv_v_axis: v_axis,
+            surface: old_face.surface.clone(),
+            smoothing_group: old_face.smoothing_group,
         });
     }
 
@@ -1,6 +1,11 @@
 use std::collections::HashSet;
 
-use bevy::{input_focus::InputFocus, prelude::*};
+use bevy::{
+    input_focus::InputFocus,
+    light::{NotShadowCaster, NotShadowReceiver},
+    prelude::*,
+    window::PrimaryWindow,
+};
 
 use crate::{
     commands::{CommandHistory, snapshot_entity},
@@ -32,6 +37,7 @@ pub(super) fn handle_edit_mode_keys(
     vertex_drag: Res<VertexDragState>,
     edge_drag: Res<EdgeDragState>,
     clip_state: Res<ClipState>,
+    uv_tool: Res<super::UvToolState>,
 ) {
     if input_focus.0.is_some() || modal.active.is_some() {
         return;
@@ -57,6 +63,9 @@ pub(super) fn handle_edit_mode_keys(
     if face_drag.pending.is_some() || vertex_drag.pending.is_some() || edge_drag.pending.is_some() {
         return;
     }
+    if uv_tool.dragging().is_some() {
+        return;
+    }
 
     let ctrl = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
 
@@ -106,8 +115,9 @@ pub(super) fn handle_edit_mode_keys(
         }
     }
 
-    // Escape: exit to Object (unless Clip mode with pending points)
-    if keyboard.just_pressed(KeyCode::Escape) {
+    // Escape: exit to Object (unless Clip mode with pending points, or the texture
+    // tool is open and closes first)
+    if keyboard.just_pressed(KeyCode::Escape) && !uv_tool.active {
         if let EditMode::BrushEdit(BrushEditMode::Clip) = *edit_mode {
             if !clip_state.points.is_empty() {
                 // Let clip mode's own Escape handler clear the points first
@@ -128,7 +138,7 @@ pub(super) fn brush_face_interact(
     mut edit_mode: ResMut<EditMode>,
     mouse: Res<ButtonInput<MouseButton>>,
     keyboard: Res<ButtonInput<KeyCode>>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
     face_entities: Query<(Entity, &super::BrushFaceEntity, &GlobalTransform)>,
@@ -140,8 +150,12 @@ pub(super) fn brush_face_interact(
     input_focus: Res<InputFocus>,
     mut history: ResMut<CommandHistory>,
     mut commands: Commands,
-    snap_settings: Res<crate::snapping::SnapSettings>,
+    (snap_settings, uv_tool): (Res<crate::snapping::SnapSettings>, Res<super::UvToolState>),
 ) {
+    // The texture tool owns viewport clicks and drags while it's open
+    if uv_tool.active {
+        return;
+    }
     let in_face_edit = matches!(*edit_mode, EditMode::BrushEdit(BrushEditMode::Face));
 
     // PageUp/PageDown: nudge selected face vertices vertically (gabling)
@@ -200,8 +214,7 @@ pub(super) fn brush_face_interact(
                                 new: brush.clone(),
                                 label: "Nudge brush face".to_string(),
                             };
-                            history.undo_stack.push(Box::new(cmd));
-                            history.redo_stack.clear();
+                            history.push_executed(Box::new(cmd));
                         }
                     }
                 }
@@ -294,8 +307,7 @@ pub(super) fn brush_face_interact(
                                     new: brush.clone(),
                                     label: "Move brush face".to_string(),
                                 };
-                                history.undo_stack.push(Box::new(cmd));
-                                history.redo_stack.clear();
+                                history.push_executed(Box::new(cmd));
                             }
                         }
                     }
@@ -631,8 +643,7 @@ fn spawn_extruded_brush(
             scene_snapshot: snapshot,
         };
         let mut history = world.resource_mut::<CommandHistory>();
-        history.undo_stack.push(Box::new(cmd));
-        history.redo_stack.clear();
+        history.push_executed(Box::new(cmd));
     });
 }
 
@@ -640,7 +651,7 @@ pub(super) fn brush_vertex_interact(
     edit_mode: Res<EditMode>,
     mouse: Res<ButtonInput<MouseButton>>,
     keyboard: Res<ButtonInput<KeyCode>>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
     brush_transforms: Query<&GlobalTransform>,
@@ -699,8 +710,7 @@ pub(super) fn brush_vertex_interact(
                             new: brush.clone(),
                             label: "Nudge brush vertex".to_string(),
                         };
-                        history.undo_stack.push(Box::new(cmd));
-                        history.redo_stack.clear();
+                        history.push_executed(Box::new(cmd));
                     }
                 }
             }
@@ -777,8 +787,7 @@ pub(super) fn brush_vertex_interact(
                         new: brush.clone(),
                         label: label.to_string(),
                     };
-                    history.undo_stack.push(Box::new(cmd));
-                    history.redo_stack.clear();
+                    history.push_executed(Box::new(cmd));
                 }
             }
             drag_state.active = false;
@@ -982,7 +991,7 @@ pub(super) fn brush_edge_interact(
     edit_mode: Res<EditMode>,
     mouse: Res<ButtonInput<MouseButton>>,
     keyboard: Res<ButtonInput<KeyCode>>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
     brush_transforms: Query<&GlobalTransform>,
@@ -1045,8 +1054,7 @@ pub(super) fn brush_edge_interact(
                             new: brush.clone(),
                             label: "Nudge brush edge".to_string(),
                         };
-                        history.undo_stack.push(Box::new(cmd));
-                        history.redo_stack.clear();
+                        history.push_executed(Box::new(cmd));
                     }
                 }
             }
@@ -1117,8 +1125,7 @@ pub(super) fn brush_edge_interact(
                         new: brush.clone(),
                         label: "Move brush edge".to_string(),
                     };
-                    history.undo_stack.push(Box::new(cmd));
-                    history.redo_stack.clear();
+                    history.push_executed(Box::new(cmd));
                 }
             }
             drag_state.active = false;
@@ -1444,8 +1451,7 @@ pub(super) fn handle_brush_delete(
                     new: brush.clone(),
                     label: "Remove brush vertex".to_string(),
                 };
-                history.undo_stack.push(Box::new(cmd));
-                history.redo_stack.clear();
+                history.push_executed(Box::new(cmd));
                 brush_selection.vertices.clear();
             }
         }
@@ -1482,8 +1488,7 @@ pub(super) fn handle_brush_delete(
                     new: brush.clone(),
                     label: "Remove brush edge".to_string(),
                 };
-                history.undo_stack.push(Box::new(cmd));
-                history.redo_stack.clear();
+                history.push_executed(Box::new(cmd));
                 brush_selection.edges.clear();
             }
         }
@@ -1511,14 +1516,163 @@ pub(super) fn handle_brush_delete(
                 new: brush.clone(),
                 label: "Remove brush face".to_string(),
             };
-            history.undo_stack.push(Box::new(cmd));
-            history.redo_stack.clear();
+            history.push_executed(Box::new(cmd));
             brush_selection.faces.clear();
         }
         _ => {}
     }
 }
 
+/// Vertex mode topology edits: M merges the selected vertices into one at their
+/// centroid, K splits the face spanned by two selected vertices along the line between
+/// them. The smaller half gets a plane of its own, hinged on that line and folded into
+/// the brush until its far corner sits one grid step below the face.
+pub(super) fn handle_vertex_topology_keys(
+    edit_mode: Res<EditMode>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input_focus: Res<InputFocus>,
+    mut brush_selection: ResMut<BrushSelection>,
+    mut brushes: Query<&mut Brush>,
+    brush_caches: Query<&BrushMeshCache>,
+    mut history: ResMut<CommandHistory>,
+    vertex_drag: Res<VertexDragState>,
+    snap_settings: Res<crate::snapping::SnapSettings>,
+) {
+    if *edit_mode != EditMode::BrushEdit(BrushEditMode::Vertex) {
+        return;
+    }
+    if input_focus.0.is_some() || vertex_drag.active || vertex_drag.pending.is_some() {
+        return;
+    }
+    // Ctrl+K is CSG subtract
+    if keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        return;
+    }
+    let merge = keyboard.just_pressed(KeyCode::KeyM);
+    let split = keyboard.just_pressed(KeyCode::KeyK);
+    if !merge && !split {
+        return;
+    }
+    let Some(brush_entity) = brush_selection.entity else {
+        return;
+    };
+    let Ok(cache) = brush_caches.get(brush_entity) else {
+        return;
+    };
+    let Ok(mut brush) = brushes.get_mut(brush_entity) else {
+        return;
+    };
+    let selected: Vec<usize> = brush_selection
+        .vertices
+        .iter()
+        .copied()
+        .filter(|&vi| vi < cache.vertices.len())
+        .collect();
+
+    let old = brush.clone();
+    let (new_brush, label) = if merge {
+        if selected.len() < 2 {
+            return;
+        }
+        let centroid =
+            selected.iter().map(|&vi| cache.vertices[vi]).sum::<Vec3>() / selected.len() as f32;
+        let mut new_verts: Vec<Vec3> = cache
+            .vertices
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !selected.contains(i))
+            .map(|(_, v)| *v)
+            .collect();
+        new_verts.push(centroid);
+        if new_verts.len() < 4 {
+            return;
+        }
+        let Some((new_brush, _)) =
+            rebuild_brush_from_vertices(&old, &cache.vertices, &cache.face_polygons, &new_verts)
+        else {
+            return;
+        };
+        (new_brush, "Merge brush vertices")
+    } else {
+        let [a, b] = selected[..] else {
+            return;
+        };
+        // The face both vertices lie on, with the vertices not already joined by an edge
+        let Some((face_idx, ia, ib)) =
+            cache
+                .face_polygons
+                .iter()
+                .enumerate()
+                .find_map(|(face_idx, polygon)| {
+                    let ia = polygon.iter().position(|&vi| vi == a)?;
+                    let ib = polygon.iter().position(|&vi| vi == b)?;
+                    let gap = ia.abs_diff(ib);
+                    (gap != 1 && gap != polygon.len() - 1).then_some((face_idx, ia, ib))
+                })
+        else {
+            return;
+        };
+        let Some(face) = old.faces.get(face_idx) else {
+            return;
+        };
+
+        // The polygon's corners strictly on either side of the split line; fold the
+        // side with fewer of them
+        let polygon = &cache.face_polygons[face_idx];
+        let (lo, hi) = (ia.min(ib), ia.max(ib));
+        let inner = &polygon[lo + 1..hi];
+        let outer: Vec<usize> = polygon[hi + 1..]
+            .iter()
+            .chain(&polygon[..lo])
+            .copied()
+            .collect();
+        let folded = if inner.len() <= outer.len() {
+            inner
+        } else {
+            &outer[..]
+        };
+
+        // Offsets from the split line, within the face plane
+        let (pa, pb) = (cache.vertices[a], cache.vertices[b]);
+        let along = (pb - pa).normalize_or_zero();
+        let across = |vi: usize| {
+            let offset = cache.vertices[vi] - pa;
+            offset - along * offset.dot(along)
+        };
+        let reach = folded
+            .iter()
+            .map(|&vi| across(vi).length())
+            .fold(0.0, f32::max);
+        let Some(side) = folded.first().map(|&vi| across(vi).normalize_or_zero()) else {
+            return;
+        };
+        if along == Vec3::ZERO || side == Vec3::ZERO || reach <= f32::EPSILON {
+            return;
+        }
+
+        let angle = (snap_settings.grid_size() / reach).atan();
+        let normal = (face.plane.normal * angle.cos() + side * angle.sin()).normalize();
+        let mut new_face = face.clone();
+        new_face.plane = BrushPlane {
+            normal,
+            distance: normal.dot(pa),
+        };
+        let mut new_brush = old.clone();
+        new_brush.faces.push(new_face);
+        (new_brush, "Split brush face")
+    };
+
+    *brush = new_brush;
+    let cmd = SetBrush {
+        entity: brush_entity,
+        old,
+        new: brush.clone(),
+        label: label.to_string(),
+    };
+    history.push_executed(Box::new(cmd));
+    brush_selection.vertices.clear();
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
 pub enum ClipMode {
     #[default]
@@ -1534,12 +1688,19 @@ pub(crate) struct ClipState {
     pub mode: ClipMode,
 }
 
+/// Translucent quad showing the clip plane across the brush.
+#[derive(Component)]
+pub(crate) struct ClipPlanePreview;
+
+/// How far the clip plane preview reaches past the brush, relative to its size.
+const CLIP_PREVIEW_MARGIN: f32 = 1.25;
+
 pub(super) fn handle_clip_mode(
     edit_mode: Res<EditMode>,
     keyboard: Res<ButtonInput<KeyCode>>,
     mouse: Res<ButtonInput<MouseButton>>,
     input_focus: Res<InputFocus>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     brush_selection: Res<BrushSelection>,
@@ -1727,8 +1888,7 @@ pub(super) fn handle_clip_mode(
                         new: brush.clone(),
                         label: "Clip brush (keep front)".to_string(),
                     };
-                    history.undo_stack.push(Box::new(cmd));
-                    history.redo_stack.clear();
+                    history.push_executed(Box::new(cmd));
                 }
                 ClipMode::KeepBack => {
                     let old = brush.clone();
@@ -1739,8 +1899,7 @@ pub(super) fn handle_clip_mode(
                         new: brush.clone(),
                         label: "Clip brush (keep back)".to_string(),
                     };
-                    history.undo_stack.push(Box::new(cmd));
-                    history.redo_stack.clear();
+                    history.push_executed(Box::new(cmd));
                 }
                 ClipMode::Split => {
                     let old = brush.clone();
@@ -1791,8 +1950,7 @@ pub(super) fn handle_clip_mode(
                             label: "Split brush".to_string(),
                         };
                         let mut history = world.resource_mut::<CommandHistory>();
-                        history.undo_stack.push(Box::new(group));
-                        history.redo_stack.clear();
+                        history.push_executed(Box::new(group));
                     });
                     clip_state.points.clear();
                     clip_state.preview_plane = None;
@@ -1905,3 +2063,77 @@ pub(super) fn handle_clip_mode(
         gizmos.arrow(center, center + arrow_dir * 0.5, Color::srgb(1.0, 0.3, 0.3));
     }
 }
+
+pub(super) fn sync_clip_plane_preview(
+    mut commands: Commands,
+    clip_state: Res<ClipState>,
+    brush_selection: Res<BrushSelection>,
+    brush_transforms: Query<&GlobalTransform>,
+    brush_caches: Query<&BrushMeshCache>,
+    mut previews: Query<(Entity, &mut Transform), With<ClipPlanePreview>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut assets: Local<Option<(Handle<Mesh>, Handle<StandardMaterial>)>>,
+) {
+    let placement = (|| {
+        let plane = clip_state.preview_plane.as_ref()?;
+        let brush_entity = brush_selection.entity?;
+        let brush_global = brush_transforms.get(brush_entity).ok()?;
+        let cache = brush_caches.get(brush_entity).ok()?;
+        if cache.vertices.is_empty() {
+            return None;
+        }
+        // Centre the quad on the brush centre projected onto the plane, sized to cover
+        // the whole brush.
+        let centroid = cache.vertices.iter().sum::<Vec3>() / cache.vertices.len() as f32;
+        let radius = cache
+            .vertices
+            .iter()
+            .map(|v| v.distance(centroid))
+            .fold(0.0_f32, f32::max);
+        let center = centroid - plane.normal * (plane.normal.dot(centroid) - plane.distance);
+        let (_, brush_rot, _) = brush_global.to_scale_rotation_translation();
+        let world_normal = (brush_rot * plane.normal).normalize();
+        Some(Transform {
+            translation: brush_global.transform_point(center),
+            rotation: Quat::from_rotation_arc(Vec3::Z, world_normal),
+            scale: Vec3::new(radius, radius, 1.0) * 2.0 * CLIP_PREVIEW_MARGIN,
+        })
+    })();
+
+    let Some(placement) = placement else {
+        for (entity, _) in &previews {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+    if let Some((_, mut transform)) = previews.iter_mut().next() {
+        if *transform != placement {
+            *transform = placement;
+        }
+        return;
+    }
+
+    let (mesh, material) = assets.get_or_insert_with(|| {
+        (
+            meshes.add(Rectangle::new(1.0, 1.0)),
+            materials.add(StandardMaterial {
+                base_color: Color::srgba(1.0, 0.3, 0.3, 0.2),
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                cull_mode: None,
+                ..default()
+            }),
+        )
+    });
+    commands.spawn((
+        ClipPlanePreview,
+        Mesh3d(mesh.clone()),
+        MeshMaterial3d(material.clone()),
+        placement,
+        Visibility::Inherited,
+        NotShadowCaster,
+        NotShadowReceiver,
+        crate::EditorEntity,
+    ));
+}
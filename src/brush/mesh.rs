@@ -1,14 +1,19 @@
 use bevy::{
     mesh::{Indices, PrimitiveTopology},
+    platform::collections::{HashMap, HashSet},
     prelude::*,
 };
 
 use super::{BrushFaceEntity, BrushMaterialPalette, BrushMeshCache, BrushPreview};
 use crate::NonSerializable;
 use crate::draw_brush::DrawBrushState;
+use crate::selection::Selected;
+use crate::viewport::MainViewportCamera;
 use jackdaw_geometry::{
-    compute_brush_geometry, compute_face_tangent_axes, compute_face_uvs, triangulate_face,
+    compute_brush_geometry, compute_face_tangent_axes, compute_face_uvs,
+    compute_face_vertex_normals, triangulate_face,
 };
+use jackdaw_jsn::{LinkedDuplicate, TriggerVolume, VisibilityVolume};
 
 pub(super) fn setup_default_materials(
     mut materials: ResMut<Assets<StandardMaterial>>,
@@ -37,24 +42,137 @@ pub(super) fn setup_default_materials(
                 ..default()
             }));
     }
+    palette.trigger_material = materials.add(StandardMaterial {
+        base_color: Color::srgba(1.0, 0.55, 0.1, 0.3),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        cull_mode: None,
+        ..default()
+    });
+    // Zone, occluder, portal
+    let volume_colors = [
+        Color::srgba(0.2, 0.6, 1.0, 0.2),
+        Color::srgba(0.9, 0.2, 0.3, 0.3),
+        Color::srgba(0.3, 1.0, 0.5, 0.3),
+    ];
+    palette.visibility_volume_materials = volume_colors.map(|color| {
+        materials.add(StandardMaterial {
+            base_color: color,
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            cull_mode: None,
+            ..default()
+        })
+    });
+}
+
+/// Most brush meshes rebuilt in a single frame; the rest stay in [`BrushRebuildQueue`].
+const BRUSH_REBUILDS_PER_FRAME: usize = 32;
+
+/// Brushes whose `Brush` changed but whose face meshes have not been rebuilt yet.
+/// Large batches (CSG, undoing a big operation) drain over several frames.
+#[derive(Resource, Default)]
+pub struct BrushRebuildQueue {
+    pending: HashSet<Entity>,
+    rebuilt_last_frame: usize,
 }
 
+impl BrushRebuildQueue {
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Brushes rebuilt by the most recent pass.
+    pub fn rebuilt_last_frame(&self) -> usize {
+        self.rebuilt_last_frame
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+/// Queue changed brushes and rebuild up to [`BRUSH_REBUILDS_PER_FRAME`] of them:
+/// brushes being edited or selected first, then on-screen brushes nearest the camera.
 pub fn regenerate_brush_meshes(
     mut commands: Commands,
-    changed_brushes: Query<
-        (
-            Entity,
-            &super::Brush,
-            Option<&Children>,
-            Option<&super::BrushPreview>,
-        ),
-        Changed<super::Brush>,
-    >,
+    mut queue: ResMut<BrushRebuildQueue>,
+    changed_brushes: Query<Entity, Changed<super::Brush>>,
+    brushes: Query<(
+        &super::Brush,
+        Option<&Children>,
+        Option<&super::BrushPreview>,
+        Has<TriggerVolume>,
+        Option<&VisibilityVolume>,
+        Option<&LinkedDuplicate>,
+    )>,
+    priority_query: Query<(&GlobalTransform, Has<super::BrushPreview>, Has<Selected>)>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     mesh3d_query: Query<(), With<Mesh3d>>,
     mut meshes: ResMut<Assets<Mesh>>,
     palette: Res<BrushMaterialPalette>,
 ) {
-    for (entity, brush, children, preview) in &changed_brushes {
+    queue.rebuilt_last_frame = 0;
+    queue.pending.extend(changed_brushes.iter());
+    if queue.pending.is_empty() {
+        return;
+    }
+
+    let batch: Vec<Entity> = if queue.pending.len() <= BRUSH_REBUILDS_PER_FRAME {
+        queue.pending.drain().collect()
+    } else {
+        let camera = camera_query.single().ok();
+        let mut ranked: Vec<(u8, f32, Entity)> = queue
+            .pending
+            .iter()
+            .map(|&entity| {
+                let Ok((global, preview, selected)) = priority_query.get(entity) else {
+                    return (0, 0.0, entity);
+                };
+                let pos = global.translation();
+                let Some((camera, camera_global)) = camera else {
+                    return (if preview || selected { 0 } else { 1 }, 0.0, entity);
+                };
+                let on_screen = camera
+                    .world_to_ndc(camera_global, pos)
+                    .is_some_and(|ndc| ndc.x.abs() <= 1.2 && ndc.y.abs() <= 1.2 && ndc.z > 0.0);
+                let tier = if preview || selected {
+                    0
+                } else if on_screen {
+                    1
+                } else {
+                    2
+                };
+                (
+                    tier,
+                    pos.distance_squared(camera_global.translation()),
+                    entity,
+                )
+            })
+            .collect();
+        ranked.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+        ranked
+            .into_iter()
+            .take(BRUSH_REBUILDS_PER_FRAME)
+            .map(|(_, _, entity)| entity)
+            .inspect(|entity| {
+                queue.pending.remove(entity);
+            })
+            .collect()
+    };
+
+    queue.rebuilt_last_frame = batch.len();
+
+    // Linked brushes rebuilt together share one set of face meshes
+    let mut linked_meshes: HashMap<LinkedDuplicate, (Vec<Vec3>, Vec<Handle<Mesh>>)> =
+        HashMap::default();
+
+    for entity in batch {
+        // Despawned while queued
+        let Ok((brush, children, preview, trigger, volume, link)) = brushes.get(entity) else {
+            continue;
+        };
+
         // Despawn all Mesh3d children — covers both BrushFaceEntity children
         // from previous regen cycles and the runtime mesh child from JsnPlugin.
         if let Some(children) = children {
@@ -68,55 +186,77 @@ pub fn regenerate_brush_meshes(
         }
 
         let (vertices, face_polygons) = compute_brush_geometry(&brush.faces);
+        let vertex_normals = compute_face_vertex_normals(&brush.faces, &vertices, &face_polygons);
 
         let mut face_entities = Vec::with_capacity(brush.faces.len());
+        let shared_meshes = link
+            .and_then(|link| linked_meshes.get(link))
+            .filter(|(shared_vertices, handles)| {
+                *shared_vertices == vertices && handles.len() == brush.faces.len()
+            })
+            .map(|(_, handles)| handles.clone());
+        let mut face_meshes = Vec::with_capacity(brush.faces.len());
 
         for (face_idx, face_data) in brush.faces.iter().enumerate() {
             let indices = &face_polygons[face_idx];
             if indices.len() < 3 {
                 face_entities.push(Entity::PLACEHOLDER);
+                face_meshes.push(Handle::default());
                 continue;
             }
 
-            // Build per-face mesh with local vertex positions
-            let positions: Vec<[f32; 3]> =
-                indices.iter().map(|&vi| vertices[vi].to_array()).collect();
-            let normals: Vec<[f32; 3]> = vec![face_data.plane.normal.to_array(); indices.len()];
-            let (u_axis, v_axis) =
-                if face_data.uv_u_axis != Vec3::ZERO && face_data.uv_v_axis != Vec3::ZERO {
-                    (face_data.uv_u_axis, face_data.uv_v_axis)
-                } else {
-                    compute_face_tangent_axes(face_data.plane.normal)
-                };
-            let uvs = compute_face_uvs(
-                &vertices,
-                indices,
-                u_axis,
-                v_axis,
-                face_data.uv_offset,
-                face_data.uv_scale,
-                face_data.uv_rotation,
-            );
-            let w = face_data.plane.normal.dot(u_axis.cross(v_axis)).signum();
-            let tangent = [u_axis.x, u_axis.y, u_axis.z, w];
-            let tangents: Vec<[f32; 4]> = vec![tangent; indices.len()];
-
-            // Fan triangulate — local indices (0..positions.len())
-            let local_tris = triangulate_face(&(0..indices.len()).collect::<Vec<_>>());
-            let flat_indices: Vec<u32> =
-                local_tris.iter().flat_map(|t| t.iter().copied()).collect();
-
-            let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, default());
-            mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
-            mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
-            mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
-            mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, tangents);
-            mesh.insert_indices(Indices::U32(flat_indices));
-
-            let mesh_handle = meshes.add(mesh);
-
-            // Use the face's material handle if set, otherwise fall back to palette
-            let material = if face_data.material != Handle::default() {
+            let mesh_handle = if let Some(shared) = &shared_meshes {
+                shared[face_idx].clone()
+            } else {
+                // Build per-face mesh with local vertex positions
+                let positions: Vec<[f32; 3]> =
+                    indices.iter().map(|&vi| vertices[vi].to_array()).collect();
+                let normals: Vec<[f32; 3]> = vertex_normals[face_idx]
+                    .iter()
+                    .map(|n| n.to_array())
+                    .collect();
+                let (u_axis, v_axis) =
+                    if face_data.uv_u_axis != Vec3::ZERO && face_data.uv_v_axis != Vec3::ZERO {
+                        (face_data.uv_u_axis, face_data.uv_v_axis)
+                    } else {
+                        compute_face_tangent_axes(face_data.plane.normal)
+                    };
+                let uvs = compute_face_uvs(
+                    &vertices,
+                    indices,
+                    u_axis,
+                    v_axis,
+                    face_data.uv_offset,
+                    face_data.uv_scale,
+                    face_data.uv_rotation,
+                );
+                let w = face_data.plane.normal.dot(u_axis.cross(v_axis)).signum();
+                let tangent = [u_axis.x, u_axis.y, u_axis.z, w];
+                let tangents: Vec<[f32; 4]> = vec![tangent; indices.len()];
+
+                // Fan triangulate — local indices (0..positions.len())
+                let local_tris = triangulate_face(&(0..indices.len()).collect::<Vec<_>>());
+                let flat_indices: Vec<u32> =
+                    local_tris.iter().flat_map(|t| t.iter().copied()).collect();
+
+                let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, default());
+                mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+                mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+                mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+                mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, tangents);
+                mesh.insert_indices(Indices::U32(flat_indices));
+
+                meshes.add(mesh)
+            };
+            face_meshes.push(mesh_handle.clone());
+
+            // Triggers and visibility volumes are drawn translucent; otherwise use the
+            // face's material handle if set, falling back to the palette
+            let material = if trigger {
+                palette.trigger_material.clone()
+            } else if let Some(volume) = volume {
+                palette.visibility_volume_materials[volume.kind as usize].clone()
+            } else if face_data.material != Handle::default() {
                 face_data.material.clone()
             } else {
                 let mats = if preview.is_some() {
@@ -144,6 +284,12 @@ pub fn regenerate_brush_meshes(
             face_entities.push(face_entity);
         }
 
+        if let Some(link) = link
+            && shared_meshes.is_none()
+        {
+            linked_meshes.insert(*link, (vertices.clone(), face_meshes));
+        }
+
         commands.entity(entity).insert(BrushMeshCache {
             vertices,
             face_polygons,
@@ -194,8 +340,12 @@ pub(super) fn apply_brush_preview_materials(
     brush_query: Query<&BrushMeshCache>,
     face_query: Query<&BrushFaceEntity>,
     brush_data: Query<&super::Brush>,
+    triggers: Query<(), Or<(With<TriggerVolume>, With<VisibilityVolume>)>>,
 ) {
     for (entity, cache) in &added {
+        if triggers.contains(entity) {
+            continue;
+        }
         swap_face_materials(
             &mut commands,
             entity,
@@ -207,6 +357,9 @@ pub(super) fn apply_brush_preview_materials(
     }
 
     for entity in removed.read() {
+        if triggers.contains(entity) {
+            continue;
+        }
         if let Ok(cache) = brush_query.get(entity) {
             swap_face_materials(
                 &mut commands,
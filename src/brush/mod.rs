@@ -4,20 +4,27 @@ mod gizmo_overlay;
 mod hull;
 mod interaction;
 pub(crate) mod mesh;
+mod uv_tool;
 
-use bevy::prelude::*;
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
 
-use crate::commands::EditorCommand;
+use crate::{
+    commands::{CommandGroup, CommandHistory, EditorCommand, remap_entity},
+    selection::Selection,
+};
 
 pub use self::csg::{
-    brush_planes_to_world, brushes_intersect, clean_degenerate_faces, subtract_brush,
+    MeshCsgCache, brush_planes_to_world, brushes_intersect, clean_degenerate_faces,
+    mesh_csg_brushes, request_mesh_csg_brushes, subtract_brush,
 };
 pub use self::geometry::{compute_brush_geometry, compute_face_tangent_axes};
 pub use self::hull::HullFace;
 pub(crate) use self::hull::merge_hull_triangles;
 pub(crate) use self::interaction::{
-    BrushDragState, ClipState, EdgeDragState, VertexDragConstraint, VertexDragState,
+    BrushDragState, ClipMode, ClipState, EdgeDragState, VertexDragConstraint, VertexDragState,
 };
+pub use self::mesh::BrushRebuildQueue;
+pub use self::uv_tool::{UvDragKind, UvToolState};
 pub use jackdaw_jsn::{Brush, BrushFaceData, BrushPlane};
 
 /// Cached computed geometry (NOT serialized, rebuilt from Brush).
@@ -73,6 +80,10 @@ pub struct BrushSelection {
 pub struct BrushMaterialPalette {
     pub materials: Vec<Handle<StandardMaterial>>,
     pub preview_materials: Vec<Handle<StandardMaterial>>,
+    /// Translucent material every face of a trigger volume is drawn with.
+    pub trigger_material: Handle<StandardMaterial>,
+    /// Translucent materials of visibility volumes, indexed by `VisibilityVolumeKind`.
+    pub visibility_volume_materials: [Handle<StandardMaterial>; 3],
 }
 
 /// Remembers the last material applied via the texture/material browser, so new brushes inherit it.
@@ -104,6 +115,79 @@ impl EditorCommand for SetBrush {
     fn description(&self) -> &str {
         &self.label
     }
+
+    fn can_merge(&self, next: &dyn EditorCommand) -> bool {
+        (next as &dyn std::any::Any)
+            .downcast_ref::<SetBrush>()
+            .is_some_and(|next| next.entity == self.entity && next.label == self.label)
+    }
+
+    fn merge(&mut self, next: &dyn EditorCommand) {
+        if let Some(next) = (next as &dyn std::any::Any).downcast_ref::<SetBrush>() {
+            self.new = next.new.clone();
+        }
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+    }
+
+    fn memory_size(&self) -> usize {
+        let faces = self.old.faces.len() + self.new.faces.len();
+        std::mem::size_of::<Self>()
+            + faces * std::mem::size_of::<BrushFaceData>()
+            + self.label.len()
+    }
+}
+
+/// Edges between faces meeting at less than this are smoothed by
+/// [`auto_smooth_selected_brushes`]: the sides of a cylinder, but not a box.
+const AUTO_SMOOTH_ANGLE_DEGREES: f32 = 35.0;
+
+/// Assign smoothing groups to the faces of the selected brushes from the angles between
+/// them, replacing any set by hand.
+pub fn auto_smooth_selected_brushes(world: &mut World) {
+    let selected: Vec<Entity> = world.resource::<Selection>().entities.clone();
+    let mut cmds: Vec<Box<dyn EditorCommand>> = Vec::new();
+    for entity in selected {
+        let Some(old) = world.get::<Brush>(entity).cloned() else {
+            continue;
+        };
+        let (_, face_polygons) = compute_brush_geometry(&old.faces);
+        let groups = jackdaw_geometry::auto_smoothing_groups(
+            &old.faces,
+            &face_polygons,
+            AUTO_SMOOTH_ANGLE_DEGREES.to_radians(),
+        );
+        let mut new = old.clone();
+        for (face, group) in new.faces.iter_mut().zip(groups) {
+            face.smoothing_group = group;
+        }
+        if new
+            .faces
+            .iter()
+            .zip(&old.faces)
+            .all(|(a, b)| a.smoothing_group == b.smoothing_group)
+        {
+            continue;
+        }
+        let cmd = SetBrush {
+            entity,
+            old,
+            new,
+            label: "Auto smooth".to_string(),
+        };
+        cmd.execute(world);
+        cmds.push(Box::new(cmd));
+    }
+    if cmds.is_empty() {
+        return;
+    }
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(CommandGroup {
+        commands: cmds,
+        label: "Auto smooth brushes".to_string(),
+    }));
 }
 
 pub struct BrushPlugin;
@@ -121,6 +205,9 @@ impl Plugin for BrushPlugin {
             .init_resource::<EdgeDragState>()
             .init_resource::<ClipState>()
             .init_resource::<LastUsedMaterial>()
+            .init_resource::<UvToolState>()
+            .init_resource::<BrushRebuildQueue>()
+            .init_resource::<MeshCsgCache>()
             .add_systems(
                 OnEnter(crate::AppState::Editor),
                 mesh::setup_default_materials,
@@ -132,15 +219,22 @@ impl Plugin for BrushPlugin {
                     mesh::sync_brush_preview,
                     mesh::regenerate_brush_meshes,
                     mesh::apply_brush_preview_materials,
+                    uv_tool::uv_tool_interact,
                     interaction::brush_face_interact,
                     interaction::brush_vertex_interact,
                     interaction::brush_edge_interact,
                     interaction::handle_brush_delete,
+                    interaction::handle_vertex_topology_keys,
                     interaction::handle_clip_mode,
+                    interaction::sync_clip_plane_preview,
                     gizmo_overlay::draw_brush_edit_gizmos,
                 )
                     .chain()
                     .run_if(in_state(crate::AppState::Editor)),
+            )
+            .add_systems(
+                Update,
+                csg::poll_mesh_csg_tasks.run_if(in_state(crate::AppState::Editor)),
             );
     }
 }
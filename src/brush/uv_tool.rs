@@ -0,0 +1,282 @@
+//! Texture tool: in face mode, `U` toggles a tool where dragging in the viewport shifts
+//! the selected faces' textures, Ctrl+drag rotates them and Shift+drag scales them.
+//! Each drag previews live and is committed as one `SetBrush` on release.
+
+use bevy::{input_focus::InputFocus, prelude::*, window::PrimaryWindow};
+use jackdaw_geometry::compute_face_tangent_axes;
+use jackdaw_jsn::{Brush, BrushFaceData};
+
+use super::{BrushEditMode, BrushSelection, EditMode, SetBrush};
+use crate::{
+    commands::CommandHistory,
+    viewport::{MainViewportCamera, SceneViewport},
+    viewport_util::window_to_viewport_cursor,
+};
+
+/// Radians of texture rotation per pixel of horizontal mouse movement.
+const ROTATE_SPEED: f32 = 0.01;
+/// Exponential scale change per pixel of mouse movement.
+const SCALE_SPEED: f32 = 0.005;
+
+#[derive(Resource, Default)]
+pub struct UvToolState {
+    pub active: bool,
+    drag: Option<UvDrag>,
+}
+
+impl UvToolState {
+    /// The operation of the drag in progress, if any.
+    pub fn dragging(&self) -> Option<UvDragKind> {
+        self.drag.as_ref().map(|drag| drag.kind)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UvDragKind {
+    Shift,
+    Rotate,
+    Scale,
+}
+
+impl UvDragKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Shift => "Shift",
+            Self::Rotate => "Rotate",
+            Self::Scale => "Scale",
+        }
+    }
+}
+
+struct UvDrag {
+    kind: UvDragKind,
+    brush_entity: Entity,
+    start_cursor: Vec2,
+    last_cursor: Vec2,
+    /// Brush-local point under the cursor on the first selected face's plane.
+    start_hit: Option<Vec3>,
+    start_brush: Brush,
+}
+
+pub(super) fn uv_tool_interact(
+    mut tool: ResMut<UvToolState>,
+    edit_mode: Res<EditMode>,
+    brush_selection: Res<BrushSelection>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input_focus: Res<InputFocus>,
+    modal: Res<crate::modal_transform::ModalTransformState>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
+    viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
+    mut brushes: Query<(&mut Brush, &GlobalTransform)>,
+    mut history: ResMut<CommandHistory>,
+) {
+    let usable = *edit_mode == EditMode::BrushEdit(BrushEditMode::Face)
+        && brush_selection.entity.is_some()
+        && !brush_selection.faces.is_empty();
+    if !usable {
+        if let Some(drag) = tool.drag.take()
+            && let Ok((mut brush, _)) = brushes.get_mut(drag.brush_entity)
+        {
+            *brush = drag.start_brush;
+        }
+        if tool.active {
+            tool.active = false;
+        }
+        return;
+    }
+
+    let Some(brush_entity) = tool.drag.as_ref().map(|drag| drag.brush_entity) else {
+        if input_focus.0.is_some() || modal.active.is_some() {
+            return;
+        }
+        if keyboard.just_pressed(KeyCode::KeyU) {
+            tool.active = !tool.active;
+            return;
+        }
+        if tool.active && keyboard.just_pressed(KeyCode::Escape) {
+            tool.active = false;
+            return;
+        }
+        if tool.active && mouse.just_pressed(MouseButton::Left) {
+            start_drag(
+                &mut tool,
+                &brush_selection,
+                &keyboard,
+                &windows,
+                &camera_query,
+                &viewport_query,
+                &brushes,
+            );
+        }
+        return;
+    };
+
+    // Cancel: restore the brush as it was when the drag started
+    if keyboard.just_pressed(KeyCode::Escape) || mouse.just_pressed(MouseButton::Right) {
+        if let Some(drag) = tool.drag.take()
+            && let Ok((mut brush, _)) = brushes.get_mut(brush_entity)
+        {
+            *brush = drag.start_brush;
+        }
+        return;
+    }
+
+    // Release: commit the whole drag as one step
+    if !mouse.pressed(MouseButton::Left) {
+        let Some(drag) = tool.drag.take() else {
+            return;
+        };
+        if drag.last_cursor != drag.start_cursor
+            && let Ok((brush, _)) = brushes.get(brush_entity)
+        {
+            history.push_executed(Box::new(SetBrush {
+                entity: brush_entity,
+                old: drag.start_brush,
+                new: brush.clone(),
+                label: format!("{} texture", drag.kind.label()),
+            }));
+        }
+        return;
+    }
+
+    let Some(drag) = tool.drag.as_mut() else {
+        return;
+    };
+    let Some((cursor, hit)) = cursor_on_face(
+        &windows,
+        &camera_query,
+        &viewport_query,
+        &brushes,
+        brush_entity,
+        &drag.start_brush,
+        &brush_selection.faces,
+    ) else {
+        return;
+    };
+    if cursor == drag.last_cursor {
+        return;
+    }
+    drag.last_cursor = cursor;
+    let Ok((mut brush, _)) = brushes.get_mut(brush_entity) else {
+        return;
+    };
+
+    let mut new = drag.start_brush.clone();
+    let delta = cursor - drag.start_cursor;
+    for &face_idx in &brush_selection.faces {
+        let Some(face) = new.faces.get_mut(face_idx) else {
+            continue;
+        };
+        match drag.kind {
+            UvDragKind::Shift => {
+                let (Some(start), Some(hit)) = (drag.start_hit, hit) else {
+                    continue;
+                };
+                // Move the texture with the cursor: the texture-space delta of the
+                // surface movement, subtracted from the offset.
+                let moved = hit - start;
+                let (u_axis, v_axis) = face_axes(face);
+                let (sin_r, cos_r) = face.uv_rotation.sin_cos();
+                let (u, v) = (moved.dot(u_axis), moved.dot(v_axis));
+                let (ru, rv) = (u * cos_r - v * sin_r, u * sin_r + v * cos_r);
+                face.uv_offset -= Vec2::new(
+                    ru / face.uv_scale.x.max(0.001),
+                    rv / face.uv_scale.y.max(0.001),
+                );
+            }
+            UvDragKind::Rotate => {
+                face.uv_rotation += delta.x * ROTATE_SPEED;
+            }
+            UvDragKind::Scale => {
+                face.uv_scale *= ((delta.x - delta.y) * SCALE_SPEED).exp();
+            }
+        }
+    }
+    *brush = new;
+}
+
+fn start_drag(
+    tool: &mut UvToolState,
+    brush_selection: &BrushSelection,
+    keyboard: &ButtonInput<KeyCode>,
+    windows: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
+    viewport_query: &Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
+    brushes: &Query<(&mut Brush, &GlobalTransform)>,
+) {
+    let Some(brush_entity) = brush_selection.entity else {
+        return;
+    };
+    let Ok((brush, _)) = brushes.get(brush_entity) else {
+        return;
+    };
+    let Some((start_cursor, start_hit)) = cursor_on_face(
+        windows,
+        camera_query,
+        viewport_query,
+        brushes,
+        brush_entity,
+        brush,
+        &brush_selection.faces,
+    ) else {
+        return;
+    };
+    let kind = if keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        UvDragKind::Rotate
+    } else if keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        UvDragKind::Scale
+    } else {
+        UvDragKind::Shift
+    };
+    tool.drag = Some(UvDrag {
+        kind,
+        brush_entity,
+        start_cursor,
+        last_cursor: start_cursor,
+        start_hit,
+        start_brush: brush.clone(),
+    });
+}
+
+/// Viewport cursor position, and the brush-local point under it on the plane of the
+/// first selected face (`None` when the plane is edge-on or behind the camera).
+fn cursor_on_face(
+    windows: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
+    viewport_query: &Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
+    brushes: &Query<(&mut Brush, &GlobalTransform)>,
+    brush_entity: Entity,
+    brush: &Brush,
+    faces: &[usize],
+) -> Option<(Vec2, Option<Vec3>)> {
+    let window = windows.single().ok()?;
+    let cursor_pos = window.cursor_position()?;
+    let (camera, cam_tf) = camera_query.single().ok()?;
+    let viewport_cursor = window_to_viewport_cursor(cursor_pos, camera, viewport_query)?;
+
+    let hit = (|| {
+        let (_, brush_tf) = brushes.get(brush_entity).ok()?;
+        let plane = &brush.faces.get(*faces.first()?)?.plane;
+        let ray = camera.viewport_to_world(cam_tf, viewport_cursor).ok()?;
+        let to_local = brush_tf.affine().inverse();
+        let origin = to_local.transform_point3(ray.origin);
+        let direction = to_local.transform_vector3(*ray.direction);
+        let denom = plane.normal.dot(direction);
+        if denom.abs() < 1e-6 {
+            return None;
+        }
+        let t = (plane.distance - plane.normal.dot(origin)) / denom;
+        (t > 0.0).then(|| origin + direction * t)
+    })();
+    Some((viewport_cursor, hit))
+}
+
+fn face_axes(face: &BrushFaceData) -> (Vec3, Vec3) {
+    if face.uv_u_axis != Vec3::ZERO && face.uv_v_axis != Vec3::ZERO {
+        (face.uv_u_axis, face.uv_v_axis)
+    } else {
+        compute_face_tangent_axes(face.plane.normal)
+    }
+}
@@ -0,0 +1,632 @@
+//! Bake brushes to a static mesh: the selected brushes' faces are merged into one mesh
+//! primitive per material, shared vertices are welded and faces buried between two
+//! touching brushes are dropped. Coplanar faces with the same material form one chart
+//! of a second UV channel, packed without overlaps for lightmapping.
+//!
+//! The result is written as a glTF file under `assets/baked/` and replaces the brushes
+//! in the scene with a single entity referencing it, in one undo step.
+
+use std::{collections::HashMap, path::Path};
+
+use base64::Engine;
+use bevy::prelude::*;
+use jackdaw_geometry::{
+    EPSILON, compute_brush_geometry, compute_face_tangent_axes, compute_face_uvs,
+    compute_face_vertex_normals, triangulate_face,
+};
+use jackdaw_jsn::{Brush, FuncGroup, GltfSource, TriggerVolume, VisibilityVolume};
+use serde_json::json;
+
+use crate::{
+    commands::{CommandGroup, CommandHistory, DespawnEntity, EditorCommand, SpawnSnapshot},
+    project::ProjectRoot,
+    selection::{Selected, Selection, select_entities},
+    status_bar::StatusHints,
+};
+
+/// Space kept around each lightmap chart, relative to the square root of the total
+/// chart area, so neighbouring charts don't bleed into each other.
+const LIGHTMAP_PADDING: f32 = 0.02;
+
+/// Folder under the project's assets directory that baked meshes are written to.
+pub(crate) const BAKE_DIR: &str = "baked";
+
+/// One brush face in the baked mesh's space.
+struct BakeFace {
+    material: Handle<StandardMaterial>,
+    normal: Vec3,
+    distance: f32,
+    points: Vec<Vec3>,
+    /// Shading normal at each point; differs from `normal` on smoothed faces.
+    normals: Vec<Vec3>,
+    uvs: Vec<[f32; 2]>,
+}
+
+/// Vertex and index buffers of one material's primitive.
+#[derive(Default)]
+pub(crate) struct BakePrimitive {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    pub uv0: Vec<[f32; 2]>,
+    pub uv1: Vec<[f32; 2]>,
+    pub indices: Vec<u32>,
+    pub(crate) welded: HashMap<(usize, [i64; 3], [i64; 2]), u32>,
+}
+
+/// Bake the selected brushes into a glTF mesh and replace them with it.
+pub fn bake_selected_brushes(world: &mut World) {
+    let Some(assets_dir) = world
+        .get_resource::<ProjectRoot>()
+        .map(|project| project.assets_dir())
+    else {
+        warn!("Bake brushes: no project open");
+        return;
+    };
+    let selected: Vec<Entity> = world.resource::<Selection>().entities.clone();
+    let is_solid = |e: Entity| {
+        world.get::<Brush>(e).is_some()
+            && world.get::<TriggerVolume>(e).is_none()
+            && world.get::<VisibilityVolume>(e).is_none()
+    };
+    // Func groups bake whole, along with the group itself
+    let groups: Vec<Entity> = selected
+        .iter()
+        .copied()
+        .filter(|&e| world.get::<FuncGroup>(e).is_some())
+        .collect();
+    let brushes: Vec<Entity> = selected
+        .iter()
+        .copied()
+        .filter(|&e| is_solid(e))
+        .chain(groups.iter().flat_map(|&group| {
+            world
+                .get::<Children>(group)
+                .into_iter()
+                .flat_map(|children| children.iter())
+                .filter(|&child| is_solid(child))
+        }))
+        .collect();
+    if brushes.is_empty() {
+        return;
+    }
+
+    // Bake around the centre of the brushes so the mesh entity sits where they were.
+    let pivot = brush_pivot(world, &brushes);
+    let file_name = (0..)
+        .map(|n| format!("brushes_{n}.gltf"))
+        .find(|name| !assets_dir.join(BAKE_DIR).join(name).exists())
+        .unwrap_or_default();
+    let asset_path = format!("{BAKE_DIR}/{file_name}");
+    let Some(bake) = bake_brushes(world, &brushes, pivot, Path::new("..")) else {
+        return;
+    };
+    let written = std::fs::create_dir_all(assets_dir.join(BAKE_DIR)).and_then(|()| {
+        std::fs::write(
+            assets_dir.join(&asset_path),
+            serde_json::to_string_pretty(&bake.gltf).unwrap_or_default(),
+        )
+    });
+    if let Err(err) = written {
+        warn!("Bake brushes: failed to write '{asset_path}': {err}");
+        return;
+    }
+
+    // Snapshot the brushes before they go, for undo. A group's snapshot covers its
+    // brushes.
+    let removed: Vec<Entity> = groups
+        .iter()
+        .copied()
+        .chain(brushes.iter().copied().filter(|&e| {
+            world
+                .get::<ChildOf>(e)
+                .is_none_or(|c| !groups.contains(&c.parent()))
+        }))
+        .collect();
+    let mut undo_commands: Vec<Box<dyn EditorCommand>> = removed
+        .iter()
+        .map(|&e| Box::new(DespawnEntity::from_world(world, e)) as Box<dyn EditorCommand>)
+        .collect();
+
+    for &entity in removed.iter().chain(&brushes) {
+        if let Ok(mut ec) = world.get_entity_mut(entity) {
+            ec.remove::<Selected>();
+        }
+    }
+    world
+        .resource_mut::<Selection>()
+        .entities
+        .retain(|e| !removed.contains(e) && !brushes.contains(e));
+    for &entity in &removed {
+        if let Ok(entity_mut) = world.get_entity_mut(entity) {
+            entity_mut.despawn();
+        }
+    }
+
+    let scene = world
+        .resource::<AssetServer>()
+        .load(GltfAssetLabel::Scene(0).from_asset(asset_path.clone()));
+    let baked = world
+        .spawn((
+            Name::new("Baked Brushes"),
+            GltfSource {
+                path: asset_path.clone(),
+                scene_index: 0,
+            },
+            SceneRoot(scene),
+            Transform::from_translation(pivot),
+            Visibility::default(),
+        ))
+        .id();
+    undo_commands.push(Box::new(SpawnSnapshot::from_world(
+        world,
+        &[baked],
+        "Bake brushes",
+    )));
+
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(CommandGroup {
+        commands: undo_commands,
+        label: "Bake brushes to mesh".to_string(),
+    }));
+
+    select_entities(world, &[baked]);
+    info!(
+        "Baked {} brushes into '{asset_path}' ({} faces, {} lightmap charts)",
+        brushes.len(),
+        bake.faces,
+        bake.charts
+    );
+    world.resource_mut::<StatusHints>().prompt(format!(
+        "Baked {} brushes into '{asset_path}'",
+        brushes.len()
+    ));
+}
+
+/// A glTF document of merged brushes.
+pub(crate) struct BrushBake {
+    pub gltf: serde_json::Value,
+    pub faces: usize,
+    pub charts: usize,
+}
+
+/// Centre of the brushes, which the baked mesh is built around.
+pub(crate) fn brush_pivot(world: &World, brushes: &[Entity]) -> Vec3 {
+    brushes
+        .iter()
+        .filter_map(|&e| world.get::<GlobalTransform>(e))
+        .map(|tf| tf.translation())
+        .sum::<Vec3>()
+        / brushes.len().max(1) as f32
+}
+
+/// Merge the brushes into a glTF mesh around `pivot`. Textures are referenced by their
+/// asset path under `assets_dir`, the assets directory relative to the written file.
+pub(crate) fn bake_brushes(
+    world: &World,
+    brushes: &[Entity],
+    pivot: Vec3,
+    assets_dir: &Path,
+) -> Option<BrushBake> {
+    let mut faces = Vec::new();
+    for &entity in brushes {
+        let (Some(brush), Some(global)) = (
+            world.get::<Brush>(entity),
+            world.get::<GlobalTransform>(entity),
+        ) else {
+            continue;
+        };
+        collect_brush_faces(brush, global, pivot, &mut faces);
+    }
+    remove_buried_faces(&mut faces);
+    if faces.is_empty() {
+        return None;
+    }
+
+    let charts = group_charts(&faces);
+    let lightmap_uvs = pack_lightmap_uvs(&faces, &charts);
+    let (materials, primitives) = build_primitives(&faces, &charts, &lightmap_uvs);
+    Some(BrushBake {
+        gltf: build_gltf(world, "Baked Brushes", &materials, &primitives, assets_dir),
+        faces: faces.len(),
+        charts: charts.len(),
+    })
+}
+
+/// Faces of one brush, moved into the space of the baked mesh (world space relative to
+/// `pivot`). Texture coordinates stay in brush space, matching the editor's rendering.
+fn collect_brush_faces(
+    brush: &Brush,
+    global: &GlobalTransform,
+    pivot: Vec3,
+    faces: &mut Vec<BakeFace>,
+) {
+    let (vertices, polygons) = compute_brush_geometry(&brush.faces);
+    let vertex_normals = compute_face_vertex_normals(&brush.faces, &vertices, &polygons);
+    let normal_matrix = Mat3::from(global.affine().matrix3).inverse().transpose();
+    for ((face, polygon), corner_normals) in brush.faces.iter().zip(&polygons).zip(&vertex_normals)
+    {
+        if polygon.len() < 3 {
+            continue;
+        }
+        let (u_axis, v_axis) = if face.uv_u_axis != Vec3::ZERO && face.uv_v_axis != Vec3::ZERO {
+            (face.uv_u_axis, face.uv_v_axis)
+        } else {
+            compute_face_tangent_axes(face.plane.normal)
+        };
+        let uvs = compute_face_uvs(
+            &vertices,
+            polygon,
+            u_axis,
+            v_axis,
+            face.uv_offset,
+            face.uv_scale,
+            face.uv_rotation,
+        );
+        let points: Vec<Vec3> = polygon
+            .iter()
+            .map(|&vi| global.transform_point(vertices[vi]) - pivot)
+            .collect();
+        let normal = (normal_matrix * face.plane.normal).normalize();
+        let normals = corner_normals
+            .iter()
+            .map(|n| (normal_matrix * *n).normalize())
+            .collect();
+        faces.push(BakeFace {
+            material: face.material.clone(),
+            normal,
+            distance: normal.dot(points[0]),
+            points,
+            normals,
+            uvs,
+        });
+    }
+}
+
+/// Drop pairs of faces that exactly cover each other back to back, where two brushes
+/// touch. Neither can ever be seen.
+fn remove_buried_faces(faces: &mut Vec<BakeFace>) {
+    let same_points = |a: &BakeFace, b: &BakeFace| {
+        a.points.len() == b.points.len()
+            && a.points
+                .iter()
+                .all(|p| b.points.iter().any(|q| p.distance(*q) < EPSILON * 10.0))
+    };
+    let mut buried = vec![false; faces.len()];
+    for i in 0..faces.len() {
+        for j in (i + 1)..faces.len() {
+            if buried[i] || buried[j] {
+                continue;
+            }
+            let (a, b) = (&faces[i], &faces[j]);
+            if a.normal.dot(b.normal) < -1.0 + EPSILON
+                && (a.distance + b.distance).abs() < EPSILON * 10.0
+                && same_points(a, b)
+            {
+                buried[i] = true;
+                buried[j] = true;
+            }
+        }
+    }
+    let mut index = 0;
+    faces.retain(|_| {
+        let keep = !buried[index];
+        index += 1;
+        keep
+    });
+}
+
+/// Group coplanar faces sharing a material; each group is one lightmap chart.
+fn group_charts(faces: &[BakeFace]) -> Vec<Vec<usize>> {
+    let mut charts: Vec<Vec<usize>> = Vec::new();
+    for (fi, face) in faces.iter().enumerate() {
+        let existing = charts.iter_mut().find(|chart| {
+            let other = &faces[chart[0]];
+            other.material == face.material
+                && other.normal.dot(face.normal) > 1.0 - EPSILON
+                && (other.distance - face.distance).abs() < EPSILON * 10.0
+        });
+        match existing {
+            Some(chart) => chart.push(fi),
+            None => charts.push(vec![fi]),
+        }
+    }
+    charts
+}
+
+/// Lightmap UVs per face vertex. Charts are projected onto their plane at a uniform
+/// texel density and shelf-packed into the unit square.
+fn pack_lightmap_uvs(faces: &[BakeFace], charts: &[Vec<usize>]) -> Vec<Vec<[f32; 2]>> {
+    // Planar projection and bounds of every chart
+    let projected: Vec<(Vec3, Vec3, Vec2, Vec2)> = charts
+        .iter()
+        .map(|chart| {
+            let (u_axis, v_axis) = compute_face_tangent_axes(faces[chart[0]].normal);
+            let (mut min, mut max) = (Vec2::MAX, Vec2::MIN);
+            for &fi in chart {
+                for p in &faces[fi].points {
+                    let uv = Vec2::new(p.dot(u_axis), p.dot(v_axis));
+                    min = min.min(uv);
+                    max = max.max(uv);
+                }
+            }
+            (u_axis, v_axis, min, max - min)
+        })
+        .collect();
+
+    let total_area: f32 = projected
+        .iter()
+        .map(|(_, _, _, size)| size.x * size.y)
+        .sum();
+    let padding = total_area.sqrt().max(EPSILON) * LIGHTMAP_PADDING;
+    let padded: Vec<Vec2> = projected
+        .iter()
+        .map(|(_, _, _, size)| *size + Vec2::splat(padding * 2.0))
+        .collect();
+
+    // Shelf packing, tallest charts first
+    let shelf_width = padded
+        .iter()
+        .map(|size| size.x * size.y)
+        .sum::<f32>()
+        .sqrt()
+        .max(padded.iter().map(|size| size.x).fold(0.0, f32::max));
+    let mut order: Vec<usize> = (0..charts.len()).collect();
+    order.sort_by(|&a, &b| padded[b].y.total_cmp(&padded[a].y));
+    let mut offsets = vec![Vec2::ZERO; charts.len()];
+    let (mut cursor, mut shelf_height, mut extent) = (Vec2::ZERO, 0.0_f32, Vec2::ZERO);
+    for ci in order {
+        let size = padded[ci];
+        if cursor.x > 0.0 && cursor.x + size.x > shelf_width {
+            cursor = Vec2::new(0.0, cursor.y + shelf_height);
+            shelf_height = 0.0;
+        }
+        offsets[ci] = cursor;
+        cursor.x += size.x;
+        shelf_height = shelf_height.max(size.y);
+        extent = extent.max(cursor + Vec2::new(0.0, size.y));
+    }
+    let scale = 1.0 / extent.max_element().max(EPSILON);
+
+    let mut uvs: Vec<Vec<[f32; 2]>> = faces.iter().map(|_| Vec::new()).collect();
+    for (ci, chart) in charts.iter().enumerate() {
+        let (u_axis, v_axis, min, _) = projected[ci];
+        for &fi in chart {
+            uvs[fi] = faces[fi]
+                .points
+                .iter()
+                .map(|p| {
+                    let local = Vec2::new(p.dot(u_axis), p.dot(v_axis)) - min;
+                    ((offsets[ci] + Vec2::splat(padding) + local) * scale).to_array()
+                })
+                .collect();
+        }
+    }
+    uvs
+}
+
+/// One welded, triangulated primitive per material, in first-use order.
+fn build_primitives(
+    faces: &[BakeFace],
+    charts: &[Vec<usize>],
+    lightmap_uvs: &[Vec<[f32; 2]>],
+) -> (Vec<Handle<StandardMaterial>>, Vec<BakePrimitive>) {
+    let quantize = |v: f32| (v / EPSILON).round() as i64;
+    let mut materials: Vec<Handle<StandardMaterial>> = Vec::new();
+    let mut primitives: Vec<BakePrimitive> = Vec::new();
+
+    for (ci, chart) in charts.iter().enumerate() {
+        for &fi in chart {
+            let face = &faces[fi];
+            let pi = match materials.iter().position(|m| *m == face.material) {
+                Some(pi) => pi,
+                None => {
+                    materials.push(face.material.clone());
+                    primitives.push(BakePrimitive::default());
+                    primitives.len() - 1
+                }
+            };
+            let primitive = &mut primitives[pi];
+
+            // Vertices are welded within a chart when position and texture coordinate
+            // both match, so coplanar faces from different brushes join up.
+            let corner_indices: Vec<usize> = face
+                .points
+                .iter()
+                .zip(&face.normals)
+                .zip(&face.uvs)
+                .zip(&lightmap_uvs[fi])
+                .map(|(((p, normal), uv), lightmap_uv)| {
+                    let key = (
+                        ci,
+                        [quantize(p.x), quantize(p.y), quantize(p.z)],
+                        [quantize(uv[0]), quantize(uv[1])],
+                    );
+                    *primitive.welded.entry(key).or_insert_with(|| {
+                        primitive.positions.push(p.to_array());
+                        primitive.normals.push(normal.to_array());
+                        primitive.uv0.push(*uv);
+                        primitive.uv1.push(*lightmap_uv);
+                        primitive.positions.len() as u32 - 1
+                    }) as usize
+                })
+                .collect();
+            for tri in triangulate_face(&corner_indices) {
+                primitive.indices.extend_from_slice(&tri);
+            }
+        }
+    }
+    (materials, primitives)
+}
+
+/// glTF 2.0 document holding the primitives as one mesh named `name`, with the buffer
+/// embedded.
+pub(crate) fn build_gltf(
+    world: &World,
+    name: &str,
+    materials: &[Handle<StandardMaterial>],
+    primitives: &[BakePrimitive],
+    assets_dir: &Path,
+) -> serde_json::Value {
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut views = Vec::new();
+    let mut accessors = Vec::new();
+
+    // Append one tightly packed attribute and return its accessor index
+    let mut push_accessor = |bytes: &[u8],
+                             count: usize,
+                             kind: &str,
+                             component: u32,
+                             bounds: Option<([f32; 3], [f32; 3])>| {
+        while !buffer.len().is_multiple_of(4) {
+            buffer.push(0);
+        }
+        views.push(json!({
+            "buffer": 0,
+            "byteOffset": buffer.len(),
+            "byteLength": bytes.len(),
+        }));
+        buffer.extend_from_slice(bytes);
+        let mut accessor = json!({
+            "bufferView": views.len() - 1,
+            "componentType": component,
+            "count": count,
+            "type": kind,
+        });
+        if let Some((min, max)) = bounds {
+            accessor["min"] = json!(min);
+            accessor["max"] = json!(max);
+        }
+        accessors.push(accessor);
+        accessors.len() - 1
+    };
+    const FLOAT: u32 = 5126;
+    const UNSIGNED_INT: u32 = 5125;
+    let floats =
+        |values: &[f32]| -> Vec<u8> { values.iter().flat_map(|v| v.to_le_bytes()).collect() };
+
+    let mut gltf_primitives = Vec::new();
+    for (material, primitive) in primitives.iter().enumerate() {
+        let (min, max) = primitive
+            .positions
+            .iter()
+            .fold((Vec3::MAX, Vec3::MIN), |(min, max), p| {
+                (min.min(Vec3::from(*p)), max.max(Vec3::from(*p)))
+            });
+        let count = primitive.positions.len();
+        let position = push_accessor(
+            &floats(primitive.positions.as_flattened()),
+            count,
+            "VEC3",
+            FLOAT,
+            Some((min.to_array(), max.to_array())),
+        );
+        let normal = push_accessor(
+            &floats(primitive.normals.as_flattened()),
+            count,
+            "VEC3",
+            FLOAT,
+            None,
+        );
+        let uv0 = push_accessor(
+            &floats(primitive.uv0.as_flattened()),
+            count,
+            "VEC2",
+            FLOAT,
+            None,
+        );
+        let uv1 = push_accessor(
+            &floats(primitive.uv1.as_flattened()),
+            count,
+            "VEC2",
+            FLOAT,
+            None,
+        );
+        let index_bytes: Vec<u8> = primitive
+            .indices
+            .iter()
+            .flat_map(|i| i.to_le_bytes())
+            .collect();
+        let indices = push_accessor(
+            &index_bytes,
+            primitive.indices.len(),
+            "SCALAR",
+            UNSIGNED_INT,
+            None,
+        );
+        gltf_primitives.push(json!({
+            "attributes": {
+                "POSITION": position,
+                "NORMAL": normal,
+                "TEXCOORD_0": uv0,
+                "TEXCOORD_1": uv1,
+            },
+            "indices": indices,
+            "material": material,
+        }));
+    }
+
+    let mut images = Vec::new();
+    let gltf_materials: Vec<serde_json::Value> = materials
+        .iter()
+        .map(|handle| gltf_material(world, handle, assets_dir, &mut images))
+        .collect();
+    let textures: Vec<serde_json::Value> = (0..images.len())
+        .map(|source| json!({ "source": source }))
+        .collect();
+
+    let data = base64::engine::general_purpose::STANDARD.encode(&buffer);
+    json!({
+        "asset": { "version": "2.0", "generator": "jackdaw brush bake" },
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }],
+        "nodes": [{ "name": name, "mesh": 0 }],
+        "meshes": [{ "name": name, "primitives": gltf_primitives }],
+        "materials": gltf_materials,
+        "textures": textures,
+        "images": images,
+        "accessors": accessors,
+        "bufferViews": views,
+        "buffers": [{
+            "byteLength": buffer.len(),
+            "uri": format!("data:application/octet-stream;base64,{data}"),
+        }],
+    })
+}
+
+/// glTF material for a face material. Base colour textures are referenced by path,
+/// relative to the baked file; other maps aren't carried over.
+fn gltf_material(
+    world: &World,
+    handle: &Handle<StandardMaterial>,
+    assets_dir: &Path,
+    images: &mut Vec<serde_json::Value>,
+) -> serde_json::Value {
+    let Some(material) = world.resource::<Assets<StandardMaterial>>().get(handle) else {
+        return json!({
+            "pbrMetallicRoughness": { "baseColorFactor": [0.7, 0.7, 0.7, 1.0], "metallicFactor": 0.0 },
+        });
+    };
+    let color = material.base_color.to_linear();
+    let mut pbr = json!({
+        "baseColorFactor": [color.red, color.green, color.blue, color.alpha],
+        "metallicFactor": material.metallic,
+        "roughnessFactor": material.perceptual_roughness,
+    });
+    if let Some(path) = material
+        .base_color_texture
+        .as_ref()
+        .and_then(|texture| texture.path())
+    {
+        let uri = assets_dir
+            .join(path.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        images.push(json!({ "uri": uri }));
+        pbr["baseColorTexture"] = json!({ "index": images.len() - 1 });
+    }
+    let mut gltf_material = json!({ "pbrMetallicRoughness": pbr });
+    if matches!(material.alpha_mode, AlphaMode::Blend) {
+        gltf_material["alphaMode"] = json!("BLEND");
+    }
+    gltf_material
+}
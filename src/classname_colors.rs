@@ -0,0 +1,347 @@
+//! Classname colors: a color per `classname` custom property, set in the legend panel
+//! below the viewport. Entities of a colored class get an outline of that color in the
+//! viewport and a tinted label in the hierarchy, which makes gameplay markup easy to
+//! pick out in big levels. Saved per project in `.jsn/classname_colors.json`.
+
+use std::collections::BTreeMap;
+
+use bevy::{feathers::theme::ThemedText, prelude::*, ui_widgets::observe};
+use jackdaw_feathers::{
+    color_picker::{ColorPickerCommitEvent, ColorPickerProps, color_picker},
+    icons::{Icon, IconFont},
+    tokens,
+};
+use jackdaw_widgets::tree_view::{TreeNode, TreeRowContent, TreeRowLabel};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    EditorEntity, EditorHidden,
+    custom_properties::{CustomProperties, PropertyValue},
+    entity_classes::{CLASSNAME_PROPERTY, EntityClasses},
+    project::ProjectRoot,
+};
+
+pub struct ClassnameColorsPlugin;
+
+impl Plugin for ClassnameColorsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ClassnameColors>()
+            .init_resource::<ClassnameLegend>()
+            .add_systems(
+                Update,
+                (
+                    load_classname_colors,
+                    save_classname_colors,
+                    toggle_legend_panel,
+                    rebuild_legend,
+                    tint_hierarchy_rows,
+                )
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+/// Colors assigned to classnames, as sRGB.
+#[derive(Resource, Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct ClassnameColors {
+    pub colors: BTreeMap<String, [f32; 3]>,
+}
+
+impl ClassnameColors {
+    /// Color of the class `properties` names, if it has one.
+    pub fn color_of(&self, properties: &CustomProperties) -> Option<Color> {
+        let [r, g, b] = *self.colors.get(classname(properties)?)?;
+        Some(Color::srgb(r, g, b))
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ClassnameLegend {
+    pub open: bool,
+}
+
+/// The `classname` custom property, whether or not the class is defined.
+pub fn classname(properties: &CustomProperties) -> Option<&str> {
+    match properties.properties.get(CLASSNAME_PROPERTY) {
+        Some(PropertyValue::String(name)) if !name.is_empty() => Some(name),
+        _ => None,
+    }
+}
+
+pub fn toggle_legend(world: &mut World) {
+    let mut legend = world.resource_mut::<ClassnameLegend>();
+    legend.open = !legend.open;
+}
+
+/// Marker for the legend panel below the viewport.
+#[derive(Component)]
+pub struct ClassnameLegendPanel;
+
+#[derive(Component)]
+struct ClassnameLegendList;
+
+fn colors_path(project: &ProjectRoot) -> std::path::PathBuf {
+    project.jsn_dir().join("classname_colors.json")
+}
+
+fn load_classname_colors(project: Option<Res<ProjectRoot>>, mut colors: ResMut<ClassnameColors>) {
+    let Some(project) = project else {
+        return;
+    };
+    if !project.is_changed() {
+        return;
+    }
+    let loaded: ClassnameColors = std::fs::read_to_string(colors_path(&project))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default();
+    if *colors != loaded {
+        *colors = loaded;
+    }
+}
+
+fn save_classname_colors(project: Option<Res<ProjectRoot>>, colors: Res<ClassnameColors>) {
+    if !colors.is_changed() || colors.is_added() {
+        return;
+    }
+    let Some(project) = project else {
+        return;
+    };
+    let path = colors_path(&project);
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    match serde_json::to_string_pretty(&*colors) {
+        Ok(data) => {
+            if let Err(e) = std::fs::write(&path, data) {
+                warn!("Failed to write {}: {e}", path.display());
+            }
+        }
+        Err(e) => warn!("Failed to serialize classname colors: {e}"),
+    }
+}
+
+/// Builds the legend panel UI node. Starts hidden (`Display::None`).
+pub fn classname_legend_panel() -> impl Bundle {
+    (
+        ClassnameLegendPanel,
+        EditorEntity,
+        Node {
+            flex_direction: FlexDirection::Column,
+            width: percent(100),
+            height: px(160.0),
+            flex_shrink: 0.0,
+            display: Display::None,
+            ..Default::default()
+        },
+        BackgroundColor(tokens::PANEL_BG),
+        children![
+            (
+                Node {
+                    padding: UiRect::axes(px(tokens::SPACING_MD), px(tokens::SPACING_XS)),
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+                BackgroundColor(tokens::PANEL_HEADER_BG),
+                children![(
+                    Text::new("Classname Colors"),
+                    TextFont {
+                        font_size: tokens::FONT_SM,
+                        ..Default::default()
+                    },
+                    TextColor(tokens::TEXT_SECONDARY),
+                )],
+            ),
+            (
+                ClassnameLegendList,
+                EditorEntity,
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    width: percent(100),
+                    flex_grow: 1.0,
+                    min_height: px(0.0),
+                    overflow: Overflow::scroll_y(),
+                    padding: UiRect::all(px(tokens::SPACING_XS)),
+                    row_gap: px(2.0),
+                    ..Default::default()
+                },
+            ),
+        ],
+    )
+}
+
+fn toggle_legend_panel(
+    legend: Res<ClassnameLegend>,
+    mut panels: Query<&mut Node, With<ClassnameLegendPanel>>,
+) {
+    if !legend.is_changed() {
+        return;
+    }
+    for mut node in &mut panels {
+        node.display = if legend.open {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+/// One row per classname that is defined or used in the scene: color, name and how many
+/// entities have it.
+fn rebuild_legend(
+    mut commands: Commands,
+    legend: Res<ClassnameLegend>,
+    colors: Res<ClassnameColors>,
+    classes: Res<EntityClasses>,
+    icon_font: Res<IconFont>,
+    entities: Query<&CustomProperties, Without<EditorEntity>>,
+    lists: Query<(Entity, Option<&Children>), With<ClassnameLegendList>>,
+    mut shown: Local<Option<(BTreeMap<String, usize>, ClassnameColors)>>,
+) {
+    if !legend.open {
+        *shown = None;
+        return;
+    }
+    let mut counts: BTreeMap<String, usize> = classes
+        .definitions
+        .classes
+        .keys()
+        .map(|name| (name.clone(), 0))
+        .collect();
+    for properties in &entities {
+        if let Some(name) = classname(properties) {
+            *counts.entry(name.to_string()).or_default() += 1;
+        }
+    }
+    let current = (counts, colors.clone());
+    if shown.as_ref() == Some(&current) {
+        return;
+    }
+    let (counts, _) = shown.insert(current);
+
+    for (list, children) in &lists {
+        if let Some(children) = children {
+            for child in children.iter() {
+                commands.entity(child).despawn();
+            }
+        }
+        if counts.is_empty() {
+            commands.spawn((
+                Text::new("No classnames in the project or scene"),
+                TextFont {
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                TextColor(tokens::TEXT_SECONDARY),
+                ChildOf(list),
+            ));
+        }
+        for (name, count) in counts.iter() {
+            let color = colors.colors.get(name).copied();
+            let row = commands
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: px(tokens::SPACING_SM),
+                        padding: UiRect::horizontal(px(tokens::SPACING_SM)),
+                        ..Default::default()
+                    },
+                    ChildOf(list),
+                ))
+                .id();
+            let [r, g, b] = color.unwrap_or([1.0, 1.0, 1.0]);
+            let picked = name.clone();
+            commands
+                .spawn((
+                    color_picker(ColorPickerProps::new().with_color([r, g, b, 1.0])),
+                    ChildOf(row),
+                ))
+                .observe(
+                    move |event: On<ColorPickerCommitEvent>,
+                          mut colors: ResMut<ClassnameColors>| {
+                        let [r, g, b, _] = event.color;
+                        colors.colors.insert(picked.clone(), [r, g, b]);
+                    },
+                );
+            commands.spawn((
+                Text::new(format!("{name} ({count})")),
+                TextFont {
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                TextColor(match color {
+                    Some([r, g, b]) => Color::srgb(r, g, b),
+                    None => tokens::TEXT_SECONDARY,
+                }),
+                ChildOf(row),
+            ));
+            if color.is_some() {
+                let cleared = name.clone();
+                commands.spawn((
+                    Text::new(String::from(Icon::X.unicode())),
+                    TextFont {
+                        font: icon_font.0.clone(),
+                        font_size: tokens::FONT_SM,
+                        ..Default::default()
+                    },
+                    TextColor(tokens::TEXT_SECONDARY),
+                    ChildOf(row),
+                    observe(
+                        move |_: On<Pointer<Click>>, mut colors: ResMut<ClassnameColors>| {
+                            colors.colors.remove(&cleared);
+                        },
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+/// Tint hierarchy labels of entities with a colored class. Greyed rows of hidden
+/// entities keep their grey.
+fn tint_hierarchy_rows(
+    mut commands: Commands,
+    colors: Res<ClassnameColors>,
+    rows: Query<(Ref<TreeNode>, &Children)>,
+    contents: Query<&Children, With<TreeRowContent>>,
+    labels: Query<Has<ThemedText>, With<TreeRowLabel>>,
+    properties: Query<Ref<CustomProperties>>,
+    hidden: Query<(), With<EditorHidden>>,
+    mut removed: RemovedComponents<CustomProperties>,
+) {
+    let refresh_all = colors.is_changed() || removed.read().count() > 0;
+    for (node, children) in &rows {
+        let source = node.0;
+        let props = properties.get(source).ok();
+        let changed = node.is_added() || props.as_ref().is_some_and(Ref::is_changed);
+        if !refresh_all && !changed {
+            continue;
+        }
+        let Some(label) = children
+            .iter()
+            .filter_map(|child| contents.get(child).ok())
+            .flat_map(|content| content.iter())
+            .find(|&child| labels.contains(child))
+        else {
+            continue;
+        };
+        let themed = labels.get(label).unwrap_or(true);
+        match props.and_then(|p| colors.color_of(&p)) {
+            Some(color) => {
+                commands
+                    .entity(label)
+                    .remove::<ThemedText>()
+                    .insert(TextColor(color));
+            }
+            None if !themed && !hidden.contains(source) => {
+                commands
+                    .entity(label)
+                    .insert((ThemedText, TextColor(tokens::TEXT_PRIMARY)));
+            }
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,953 @@
+//! Experimental live collaboration. One editor hosts a session over TCP and others
+//! join it; every change goes through the host, which relays it to the other peers,
+//! so the host's scene is the authoritative one.
+//!
+//! Entities are matched across editors by their [`StableId`]. Whenever the undo history
+//! changes, the entities with saved components changed, added or removed since the last
+//! look are compared against what was last synced, and those that differ are sent as
+//! JSN, along with despawns; moved entities also stream while being dragged. Each
+//! peer's selection and viewport camera show up in the other viewports as colored ghosts.
+//!
+//! Messages are newline-delimited JSON. There is no conflict resolution beyond "last
+//! write wins" and no authentication, so only host sessions on trusted networks.
+
+use std::{
+    any::TypeId,
+    collections::{HashMap, HashSet},
+    io::{ErrorKind, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    path::PathBuf,
+    time::Duration,
+};
+
+use bevy::{
+    asset::uuid::Uuid,
+    ecs::{
+        archetype::ArchetypeId, change_detection::Tick, component::ComponentId, system::SystemState,
+    },
+    prelude::*,
+};
+use jackdaw_commands::CommandHistory;
+use jackdaw_feathers::{
+    dialog::{DialogActionEvent, DialogChildrenSlot, DialogClosedEvent, DialogId, OpenDialogEvent},
+    text_edit::{self, TextEditProps, TextEditValue},
+};
+use jackdaw_jsn::{
+    StableId,
+    format::{JsnAssets, JsnEntity, JsnTransform},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    EditorHidden, NonSerializable, align,
+    project::ProjectRoot,
+    scene_io,
+    selection::Selection,
+    stable_id::{resolve_stable_id, stable_id_of},
+    status_bar::StatusHints,
+    viewport::MainViewportCamera,
+};
+
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// How often each peer shares its selection and camera, in seconds.
+const PRESENCE_INTERVAL: f32 = 0.1;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Longest message accepted, in bytes. A welcome carries the whole scene, so this is
+/// generous; a peer sending more without a newline is dropped.
+const MAX_MESSAGE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Ghost colors, picked by peer id.
+const PEER_COLORS: [Color; 6] = [
+    Color::srgb(0.95, 0.45, 0.3),
+    Color::srgb(0.35, 0.75, 0.95),
+    Color::srgb(0.55, 0.9, 0.4),
+    Color::srgb(0.95, 0.8, 0.3),
+    Color::srgb(0.8, 0.5, 0.95),
+    Color::srgb(0.95, 0.5, 0.7),
+];
+
+pub struct CollabPlugin;
+
+impl Plugin for CollabPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CollabSession>()
+            .init_resource::<CollabGhosts>()
+            .init_resource::<PendingCollabDialog>()
+            .add_observer(on_collab_dialog_action)
+            .add_observer(on_collab_dialog_closed)
+            .add_systems(
+                Update,
+                (
+                    (track_local_changes, sync_session, draw_ghosts).chain(),
+                    populate_collab_dialog,
+                )
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+/// The session this editor is hosting or has joined, if any.
+#[derive(Resource, Default)]
+pub struct CollabSession {
+    state: Option<Session>,
+}
+
+impl CollabSession {
+    pub fn is_active(&self) -> bool {
+        self.state.is_some()
+    }
+}
+
+struct Session {
+    /// Accepts new peers while hosting; `None` on a joined session.
+    listener: Option<TcpListener>,
+    /// Connections to every peer when hosting, or just to the host.
+    connections: Vec<Connection>,
+    local_peer: u32,
+    next_peer: u32,
+    name: String,
+    /// State of each synced entity as last sent or received, see [`fingerprint`].
+    synced: HashMap<Uuid, serde_json::Value>,
+    peers: HashMap<u32, Presence>,
+    presence_timer: Timer,
+    history_changed: bool,
+    moved: HashSet<Entity>,
+    /// Change tick and archetype of each scene entity as of the last history change, to
+    /// find what the next one touched without serializing the whole scene.
+    last_scan: Tick,
+    archetypes: HashMap<Entity, ArchetypeId>,
+}
+
+impl Session {
+    fn new(listener: Option<TcpListener>, connections: Vec<Connection>) -> Self {
+        Self {
+            listener,
+            connections,
+            local_peer: 0,
+            next_peer: 1,
+            name: std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .unwrap_or_else(|_| "Editor".into()),
+            synced: HashMap::new(),
+            peers: HashMap::new(),
+            presence_timer: Timer::from_seconds(PRESENCE_INTERVAL, TimerMode::Repeating),
+            history_changed: false,
+            moved: HashSet::new(),
+            last_scan: Tick::new(0),
+            archetypes: HashMap::new(),
+        }
+    }
+
+    fn is_host(&self) -> bool {
+        self.listener.is_some()
+    }
+
+    /// Send to every peer, except the connection a relayed message came from.
+    fn broadcast(&mut self, message: &CollabMessage, except: Option<usize>) {
+        for (index, connection) in self.connections.iter_mut().enumerate() {
+            if Some(index) != except && connection.peer.is_some() {
+                connection.send(message);
+            }
+        }
+    }
+}
+
+struct Connection {
+    stream: TcpStream,
+    /// Set once the peer said hello (host side) or was welcomed (joined side).
+    peer: Option<u32>,
+    incoming: Vec<u8>,
+    outgoing: Vec<u8>,
+    closed: bool,
+}
+
+impl Connection {
+    fn new(stream: TcpStream) -> std::io::Result<Self> {
+        stream.set_nonblocking(true)?;
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            stream,
+            peer: None,
+            incoming: Vec::new(),
+            outgoing: Vec::new(),
+            closed: false,
+        })
+    }
+
+    fn send(&mut self, message: &CollabMessage) {
+        match serde_json::to_vec(message) {
+            Ok(mut line) => {
+                line.push(b'\n');
+                self.outgoing.extend(line);
+            }
+            Err(err) => warn!("Collaboration: failed to encode message: {err}"),
+        }
+    }
+
+    /// Flush what's queued and read whatever arrived, without blocking.
+    fn pump(&mut self) -> Vec<CollabMessage> {
+        while !self.outgoing.is_empty() {
+            match self.stream.write(&self.outgoing) {
+                Ok(0) => self.closed = true,
+                Ok(written) => {
+                    self.outgoing.drain(..written);
+                    continue;
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => {}
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(_) => self.closed = true,
+            }
+            break;
+        }
+
+        let mut buffer = [0u8; 64 * 1024];
+        // Complete lines are taken out below, so whatever is left is one partial line
+        let mut unterminated = self.incoming.len();
+        while !self.closed {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => self.closed = true,
+                Ok(read) => {
+                    let chunk = &buffer[..read];
+                    self.incoming.extend_from_slice(chunk);
+                    unterminated = match chunk.iter().rposition(|&b| b == b'\n') {
+                        Some(end) => read - end - 1,
+                        None => unterminated + read,
+                    };
+                    if unterminated > MAX_MESSAGE_BYTES {
+                        warn!("Collaboration: dropped a peer sending an oversized message");
+                        self.incoming.clear();
+                        self.closed = true;
+                    }
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(_) => self.closed = true,
+            }
+        }
+
+        let mut messages = Vec::new();
+        while let Some(end) = self.incoming.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.incoming.drain(..=end).collect();
+            match serde_json::from_slice(&line[..end]) {
+                Ok(message) => messages.push(message),
+                Err(err) => warn!("Collaboration: dropped malformed message: {err}"),
+            }
+        }
+        messages
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum CollabMessage {
+    Hello {
+        name: String,
+    },
+    /// The host's reply to `Hello`: the peer's id and the whole scene.
+    Welcome {
+        peer: u32,
+        batch: EntityBatch,
+    },
+    Entities {
+        batch: EntityBatch,
+    },
+    Despawn {
+        ids: Vec<Uuid>,
+    },
+    Presence {
+        presence: Presence,
+    },
+    Left {
+        peer: u32,
+    },
+}
+
+/// Entities with the inline assets they reference.
+#[derive(Serialize, Deserialize, Clone, Default)]
+struct EntityBatch {
+    entities: Vec<SyncedEntity>,
+    assets: JsnAssets,
+}
+
+/// One entity's JSN, with its parent by id since a batch is usually partial.
+#[derive(Serialize, Deserialize, Clone)]
+struct SyncedEntity {
+    id: Uuid,
+    parent: Option<Uuid>,
+    entity: JsnEntity,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct Presence {
+    peer: u32,
+    name: String,
+    camera: Option<JsnTransform>,
+    selection: Vec<Uuid>,
+}
+
+/// Other peers' cameras and selection bounds, drawn in the viewport.
+#[derive(Resource, Default)]
+struct CollabGhosts(Vec<Ghost>);
+
+struct Ghost {
+    color: Color,
+    camera: Option<Transform>,
+    selection: Vec<(Vec3, Vec3)>,
+}
+
+fn peer_color(peer: u32) -> Color {
+    PEER_COLORS[peer as usize % PEER_COLORS.len()]
+}
+
+/// Start hosting the open scene on `port`.
+pub fn host_session(world: &mut World, port: u16) {
+    leave_session(world);
+    let listener = match TcpListener::bind(("0.0.0.0", port)) {
+        Ok(listener) => listener,
+        Err(err) => {
+            warn!("Collaboration: failed to listen on port {port}: {err}");
+            return;
+        }
+    };
+    if let Err(err) = listener.set_nonblocking(true) {
+        warn!("Collaboration: {err}");
+        return;
+    }
+    let mut session = Session::new(Some(listener), Vec::new());
+    // Peers start from the host's scene, so all of it counts as synced
+    let batch = scene_batch(world, None);
+    for synced in &batch.entities {
+        session
+            .synced
+            .insert(synced.id, fingerprint(synced, &batch.assets));
+    }
+    touched_scene_entities(world, &mut session);
+    world.resource_mut::<CollabSession>().state = Some(session);
+    info!("Collaboration: hosting on port {port}");
+    world
+        .resource_mut::<StatusHints>()
+        .prompt(format!("Hosting a session on port {port}"));
+}
+
+/// Join the session hosted at `address`, replacing the open scene with the host's.
+pub fn join_session(world: &mut World, address: &str) {
+    leave_session(world);
+    let Some(addr) = address.to_socket_addrs().ok().and_then(|mut a| a.next()) else {
+        warn!("Collaboration: can't resolve '{address}'");
+        return;
+    };
+    let connection =
+        match TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).and_then(Connection::new) {
+            Ok(connection) => connection,
+            Err(err) => {
+                warn!("Collaboration: failed to connect to {address}: {err}");
+                return;
+            }
+        };
+    let mut session = Session::new(None, vec![connection]);
+    let hello = CollabMessage::Hello {
+        name: session.name.clone(),
+    };
+    session.connections[0].send(&hello);
+    world.resource_mut::<CollabSession>().state = Some(session);
+    info!("Collaboration: joining {address}");
+}
+
+/// Disconnect from the current session; the scene stays as it is.
+pub fn leave_session(world: &mut World) {
+    let Some(mut session) = world.resource_mut::<CollabSession>().state.take() else {
+        return;
+    };
+    if !session.is_host() {
+        let left = CollabMessage::Left {
+            peer: session.local_peer,
+        };
+        session.broadcast(&left, None);
+    }
+    // Best effort: the sockets close when dropped
+    for connection in &mut session.connections {
+        connection.pump();
+    }
+    world.resource_mut::<CollabGhosts>().0.clear();
+    info!("Collaboration: left the session");
+}
+
+/// Base path of asset references in synced JSN. Peers resolve it to their own copy of
+/// the project, so the same relative paths work on every machine.
+fn sync_asset_path(world: &World) -> PathBuf {
+    world
+        .get_resource::<ProjectRoot>()
+        .map(|project| project.assets_dir())
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// An entity's synced state with inline asset references (`#Name`) replaced by the
+/// assets themselves, since those names are numbered per batch.
+fn fingerprint(synced: &SyncedEntity, assets: &JsnAssets) -> serde_json::Value {
+    let mut value = serde_json::json!({
+        "parent": synced.parent,
+        "entity": synced.entity,
+    });
+    inline_asset_refs(&mut value, assets, 0);
+    value
+}
+
+fn inline_asset_refs(value: &mut serde_json::Value, assets: &JsnAssets, depth: usize) {
+    match value {
+        serde_json::Value::String(name) if name.starts_with('#') && depth < 4 => {
+            let asset = assets
+                .0
+                .values()
+                .find_map(|named| named.get(name.as_str()))
+                .cloned();
+            if let Some(mut asset) = asset {
+                inline_asset_refs(&mut asset, assets, depth + 1);
+                *value = asset;
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                inline_asset_refs(item, assets, depth);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for field in fields.values_mut() {
+                inline_asset_refs(field, assets, depth);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Serialize scene entities for syncing: `only` of them, or the whole scene.
+fn scene_batch(world: &mut World, only: Option<&[Entity]>) -> EntityBatch {
+    let entities = match only {
+        Some(entities) => entities.to_vec(),
+        None => {
+            let editor_set = scene_io::collect_editor_entities(world);
+            scene_io::collect_scene_entities_from_set(world, &editor_set)
+        }
+    };
+    let base = sync_asset_path(world);
+    let (jsn, assets) = scene_io::serialize_entities(world, &base, &entities);
+    let entities = entities
+        .iter()
+        .zip(jsn)
+        .filter_map(|(&entity, mut jsn)| {
+            let id = stable_id_of(world, entity)?;
+            let parent = world
+                .get::<ChildOf>(entity)
+                .and_then(|child_of| stable_id_of(world, child_of.parent()));
+            jsn.parent = None;
+            Some(SyncedEntity {
+                id,
+                parent,
+                entity: jsn,
+            })
+        })
+        .collect();
+    EntityBatch { entities, assets }
+}
+
+/// Spawn or overwrite the batch's entities, matched by id.
+fn apply_batch(world: &mut World, session: &mut Session, batch: &EntityBatch) {
+    let base = sync_asset_path(world);
+    let local_assets = scene_io::load_inline_assets(world, &batch.assets, &base);
+    let mut spawned = Vec::new();
+    let targets: Vec<Entity> = batch
+        .entities
+        .iter()
+        .map(|synced| {
+            resolve_stable_id(world, synced.id).unwrap_or_else(|| {
+                let entity = world.spawn(StableId(synced.id)).id();
+                spawned.push(entity);
+                entity
+            })
+        })
+        .collect();
+    let entities: Vec<JsnEntity> = batch.entities.iter().map(|s| s.entity.clone()).collect();
+    scene_io::apply_jsn_entities(world, &entities, &targets, &base, &local_assets);
+
+    for (synced, &target) in batch.entities.iter().zip(&targets) {
+        match synced.parent.and_then(|id| resolve_stable_id(world, id)) {
+            Some(parent) => {
+                world.entity_mut(target).insert(ChildOf(parent));
+            }
+            None => {
+                world.entity_mut(target).remove::<ChildOf>();
+            }
+        }
+        session
+            .synced
+            .insert(synced.id, fingerprint(synced, &batch.assets));
+    }
+    scene_io::reload_gltf_sources(world, &spawned);
+}
+
+fn despawn_ids(world: &mut World, session: &mut Session, ids: &[Uuid]) {
+    let despawned: Vec<Entity> = ids
+        .iter()
+        .filter_map(|&id| resolve_stable_id(world, id))
+        .collect();
+    world
+        .resource_mut::<Selection>()
+        .entities
+        .retain(|e| !despawned.contains(e));
+    for entity in despawned {
+        if let Ok(entity) = world.get_entity_mut(entity) {
+            entity.despawn();
+        }
+    }
+    for id in ids {
+        session.synced.remove(id);
+    }
+}
+
+fn track_local_changes(
+    history: Res<CommandHistory>,
+    moved: Query<
+        Entity,
+        (
+            Changed<Transform>,
+            With<StableId>,
+            Without<EditorHidden>,
+            Without<NonSerializable>,
+        ),
+    >,
+    mut collab: ResMut<CollabSession>,
+) {
+    let Some(session) = &mut collab.state else {
+        return;
+    };
+    if history.is_changed() {
+        session.history_changed = true;
+    }
+    session.moved.extend(&moved);
+}
+
+fn sync_session(world: &mut World) {
+    let Some(mut session) = world.resource_mut::<CollabSession>().state.take() else {
+        return;
+    };
+    let delta = world.resource::<Time>().delta();
+
+    // New peers are welcomed once they say hello
+    if let Some(listener) = &session.listener {
+        while let Ok((stream, addr)) = listener.accept() {
+            match Connection::new(stream) {
+                Ok(connection) => {
+                    info!("Collaboration: {addr} connected");
+                    session.connections.push(connection);
+                }
+                Err(err) => warn!("Collaboration: {err}"),
+            }
+        }
+    }
+
+    let mut received = Vec::new();
+    for (index, connection) in session.connections.iter_mut().enumerate() {
+        received.extend(connection.pump().into_iter().map(|m| (index, m)));
+    }
+
+    send_local_changes(world, &mut session);
+
+    for (from, message) in received {
+        handle_message(world, &mut session, from, message);
+    }
+
+    // Dropped peers
+    let mut lost_host = false;
+    let mut index = 0;
+    while index < session.connections.len() {
+        if !session.connections[index].closed {
+            index += 1;
+            continue;
+        }
+        let connection = session.connections.remove(index);
+        if !session.is_host() {
+            lost_host = true;
+        } else if let Some(peer) = connection.peer {
+            session.peers.remove(&peer);
+            session.broadcast(&CollabMessage::Left { peer }, None);
+            info!("Collaboration: peer {peer} disconnected");
+        }
+    }
+    if lost_host {
+        warn!("Collaboration: lost the connection to the host");
+        world.resource_mut::<CollabGhosts>().0.clear();
+        world
+            .resource_mut::<StatusHints>()
+            .prompt("Lost the connection to the session host");
+        return;
+    }
+
+    if session.presence_timer.tick(delta).just_finished() {
+        let presence = local_presence(world, &session);
+        session.broadcast(&CollabMessage::Presence { presence }, None);
+    }
+    update_ghosts(world, &session);
+
+    world.resource_mut::<CollabSession>().state = Some(session);
+}
+
+/// Send the entities that changed since they were last synced, and the ones that
+/// are gone.
+fn send_local_changes(world: &mut World, session: &mut Session) {
+    // A joined editor has nothing to compare against until the host's scene arrived
+    if !session.is_host() && session.connections.iter().all(|c| c.peer.is_none()) {
+        session.history_changed = false;
+        session.moved.clear();
+        return;
+    }
+    let history_changed = std::mem::take(&mut session.history_changed);
+    let mut entities: HashSet<Entity> = std::mem::take(&mut session.moved)
+        .into_iter()
+        .filter(|&e| world.get_entity(e).is_ok())
+        .collect();
+    if history_changed {
+        entities.extend(touched_scene_entities(world, session));
+    }
+    if entities.is_empty() && !history_changed {
+        return;
+    }
+
+    let entities: Vec<Entity> = entities.into_iter().collect();
+    let batch = scene_batch(world, Some(&entities));
+    let mut changed = Vec::new();
+    for synced in batch.entities {
+        let value = fingerprint(&synced, &batch.assets);
+        if session.synced.get(&synced.id) != Some(&value) {
+            session.synced.insert(synced.id, value);
+            changed.push(synced);
+        }
+    }
+    if !changed.is_empty() {
+        let message = CollabMessage::Entities {
+            batch: EntityBatch {
+                entities: changed,
+                assets: batch.assets,
+            },
+        };
+        session.broadcast(&message, None);
+    }
+
+    if history_changed {
+        let gone: Vec<Uuid> = session
+            .synced
+            .keys()
+            .filter(|&&id| resolve_stable_id(world, id).is_none())
+            .copied()
+            .collect();
+        if !gone.is_empty() {
+            for id in &gone {
+                session.synced.remove(id);
+            }
+            session.broadcast(&CollabMessage::Despawn { ids: gone }, None);
+        }
+    }
+}
+
+/// Scene entities with a saved component changed since the last scan, or with
+/// components added or removed, going by change ticks and archetypes.
+fn touched_scene_entities(world: &mut World, session: &mut Session) -> Vec<Entity> {
+    let tracked: HashSet<ComponentId> = {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let runtime = [
+            TypeId::of::<GlobalTransform>(),
+            TypeId::of::<InheritedVisibility>(),
+            TypeId::of::<ViewVisibility>(),
+            TypeId::of::<Children>(),
+        ];
+        registry
+            .iter()
+            .filter(|registration| {
+                registration.data::<ReflectComponent>().is_some()
+                    && !runtime.contains(&registration.type_id())
+                    && !scene_io::should_skip_component(
+                        registration.type_info().type_path_table().path(),
+                    )
+            })
+            .filter_map(|registration| world.components().get_id(registration.type_id()))
+            .collect()
+    };
+
+    let editor_set = scene_io::collect_editor_entities(world);
+    let scene = scene_io::collect_scene_entities_from_set(world, &editor_set);
+    let (since, now) = (session.last_scan, world.change_tick());
+    let mut archetypes = HashMap::with_capacity(scene.len());
+    let mut touched = Vec::new();
+    for entity in scene {
+        let entity_ref = world.entity(entity);
+        let archetype = entity_ref.archetype();
+        let changed = session.archetypes.get(&entity) != Some(&archetype.id())
+            || archetype.components().iter().any(|&id| {
+                tracked.contains(&id)
+                    && entity_ref
+                        .get_change_ticks_by_id(id)
+                        .is_some_and(|ticks| ticks.is_changed(since, now))
+            });
+        archetypes.insert(entity, archetype.id());
+        if changed {
+            touched.push(entity);
+        }
+    }
+    session.archetypes = archetypes;
+    session.last_scan = now;
+    touched
+}
+
+fn handle_message(
+    world: &mut World,
+    session: &mut Session,
+    from: usize,
+    mut message: CollabMessage,
+) {
+    let host = session.is_host();
+    let sender = session.connections[from].peer;
+    if host {
+        match (&mut message, sender) {
+            // Nothing but a hello counts until the peer has been welcomed
+            (CollabMessage::Hello { .. }, None) => {}
+            (_, None) | (CollabMessage::Hello { .. }, Some(_)) => return,
+            // A peer only speaks for itself
+            (CollabMessage::Presence { presence }, Some(peer)) => presence.peer = peer,
+            (CollabMessage::Left { peer }, Some(sender)) => *peer = sender,
+            _ => {}
+        }
+    }
+    match &message {
+        CollabMessage::Hello { name } if host => {
+            let peer = session.next_peer;
+            session.next_peer += 1;
+            info!("Collaboration: '{name}' joined as peer {peer}");
+            world
+                .resource_mut::<StatusHints>()
+                .prompt(format!("{name} joined the session"));
+            let batch = scene_batch(world, None);
+            let connection = &mut session.connections[from];
+            connection.peer = Some(peer);
+            connection.send(&CollabMessage::Welcome { peer, batch });
+            for presence in session.peers.values() {
+                let presence = presence.clone();
+                session.connections[from].send(&CollabMessage::Presence { presence });
+            }
+        }
+        CollabMessage::Welcome { peer, batch } if !host => {
+            session.local_peer = *peer;
+            session.connections[from].peer = Some(0);
+            scene_io::clear_scene_entities(world);
+            session.synced.clear();
+            apply_batch(world, session, batch);
+            // The host's scene is already synced
+            touched_scene_entities(world, session);
+            info!("Collaboration: joined as peer {peer}");
+            world
+                .resource_mut::<StatusHints>()
+                .prompt("Joined the session");
+        }
+        CollabMessage::Entities { batch } => {
+            apply_batch(world, session, batch);
+            if host {
+                session.broadcast(&message, Some(from));
+            }
+        }
+        CollabMessage::Despawn { ids } => {
+            despawn_ids(world, session, ids);
+            if host {
+                session.broadcast(&message, Some(from));
+            }
+        }
+        CollabMessage::Presence { presence } => {
+            if presence.peer != session.local_peer {
+                session.peers.insert(presence.peer, presence.clone());
+            }
+            if host {
+                session.broadcast(&message, Some(from));
+            }
+        }
+        CollabMessage::Left { peer } => {
+            session.peers.remove(peer);
+            if host {
+                session.connections[from].closed = true;
+                session.broadcast(&message, Some(from));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn local_presence(world: &mut World, session: &Session) -> Presence {
+    let camera = world
+        .query_filtered::<&GlobalTransform, With<MainViewportCamera>>()
+        .iter(world)
+        .next()
+        .map(|global| global.compute_transform().into());
+    let selection = world
+        .resource::<Selection>()
+        .entities
+        .iter()
+        .filter_map(|&e| stable_id_of(world, e))
+        .collect();
+    Presence {
+        peer: session.local_peer,
+        name: session.name.clone(),
+        camera,
+        selection,
+    }
+}
+
+fn update_ghosts(world: &mut World, session: &Session) {
+    let mut state = SystemState::new(world);
+    let mut ghosts = Vec::new();
+    for presence in session.peers.values() {
+        let selected: Vec<Entity> = presence
+            .selection
+            .iter()
+            .filter_map(|&id| resolve_stable_id(world, id))
+            .collect();
+        ghosts.push(Ghost {
+            color: peer_color(presence.peer),
+            camera: presence.camera.clone().map(Transform::from),
+            selection: selected
+                .into_iter()
+                .filter_map(|e| align::world_aabb(world, &mut state, e))
+                .collect(),
+        });
+    }
+    world.resource_mut::<CollabGhosts>().0 = ghosts;
+}
+
+fn draw_ghosts(ghosts: Res<CollabGhosts>, mut gizmos: Gizmos) {
+    for ghost in &ghosts.0 {
+        if let Some(camera) = ghost.camera {
+            // A small view pyramid looking down the camera's -Z
+            let forward = camera.forward() * 0.6;
+            let right = camera.right() * 0.4;
+            let up = camera.up() * 0.3;
+            let eye = camera.translation;
+            let corners = [
+                eye + forward + right + up,
+                eye + forward - right + up,
+                eye + forward - right - up,
+                eye + forward + right - up,
+            ];
+            for (i, &corner) in corners.iter().enumerate() {
+                gizmos.line(eye, corner, ghost.color);
+                gizmos.line(corner, corners[(i + 1) % 4], ghost.color);
+            }
+            gizmos.sphere(Isometry3d::from_translation(eye), 0.1, ghost.color);
+        }
+        for &(min, max) in &ghost.selection {
+            let size = (max - min).max(Vec3::splat(0.05));
+            gizmos.cube(
+                Transform::from_translation((min + max) * 0.5).with_scale(size * 1.02),
+                ghost.color,
+            );
+        }
+    }
+}
+
+const COLLAB_DIALOG: &str = "collab";
+
+#[derive(Resource, Default, PartialEq, Eq)]
+enum PendingCollabDialog {
+    #[default]
+    None,
+    Host,
+    Join,
+}
+
+/// Marker for the port or address input of the collaboration dialogs.
+#[derive(Component)]
+struct CollabAddressInput;
+
+/// Ask for the port to host on.
+pub fn open_host_dialog(world: &mut World) {
+    *world.resource_mut::<PendingCollabDialog>() = PendingCollabDialog::Host;
+    world.trigger(
+        OpenDialogEvent::new("Host Session (Experimental)", "Host")
+            .with_id(COLLAB_DIALOG)
+            .with_max_width(px(360)),
+    );
+}
+
+/// Ask for the address of the session to join.
+pub fn open_join_dialog(world: &mut World) {
+    *world.resource_mut::<PendingCollabDialog>() = PendingCollabDialog::Join;
+    world.trigger(
+        OpenDialogEvent::new("Join Session", "Join")
+            .with_id(COLLAB_DIALOG)
+            .with_max_width(px(360)),
+    );
+}
+
+fn populate_collab_dialog(
+    mut commands: Commands,
+    pending: Res<PendingCollabDialog>,
+    slots: Query<(Entity, &DialogId), Added<DialogChildrenSlot>>,
+) {
+    let (label, value) = match *pending {
+        PendingCollabDialog::None => return,
+        PendingCollabDialog::Host => ("Port", DEFAULT_PORT.to_string()),
+        PendingCollabDialog::Join => ("Host address", format!("127.0.0.1:{DEFAULT_PORT}")),
+    };
+    for (slot, id) in &slots {
+        if id.0 != COLLAB_DIALOG {
+            continue;
+        }
+        commands.spawn((
+            CollabAddressInput,
+            text_edit::text_edit(
+                TextEditProps::default()
+                    .with_label(label)
+                    .with_default_value(value.clone()),
+            ),
+            ChildOf(slot),
+        ));
+    }
+}
+
+/// However the dialog closes, forget the pending dialog; an action has used it by then.
+fn on_collab_dialog_closed(event: On<DialogClosedEvent>, mut pending: ResMut<PendingCollabDialog>) {
+    if event.id == Some(COLLAB_DIALOG) {
+        *pending = PendingCollabDialog::None;
+    }
+}
+
+fn on_collab_dialog_action(
+    event: On<DialogActionEvent>,
+    mut commands: Commands,
+    mut pending: ResMut<PendingCollabDialog>,
+    inputs: Query<&TextEditValue, With<CollabAddressInput>>,
+) {
+    if event.id != Some(COLLAB_DIALOG) {
+        return;
+    }
+    let value = inputs
+        .iter()
+        .next()
+        .map(|input| input.0.trim().to_string())
+        .unwrap_or_default();
+    match std::mem::take(&mut *pending) {
+        PendingCollabDialog::None => {}
+        PendingCollabDialog::Host => {
+            let Ok(port) = value.parse::<u16>() else {
+                warn!("Collaboration: '{value}' is not a valid port");
+                return;
+            };
+            commands.queue(move |world: &mut World| host_session(world, port));
+        }
+        PendingCollabDialog::Join => {
+            // A bare host name joins on the default port
+            let address = if value.contains(':') {
+                value
+            } else {
+                format!("{value}:{DEFAULT_PORT}")
+            };
+            commands.queue(move |world: &mut World| join_session(world, &address));
+        }
+    }
+}
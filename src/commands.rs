@@ -1,15 +1,17 @@
-use std::any::TypeId;
+use std::any::{Any, TypeId};
+use std::sync::Mutex;
 
 use bevy::{
     ecs::{
         component::ComponentId,
+        entity::EntityHashMap,
         reflect::{AppTypeRegistry, ReflectComponent},
     },
     prelude::*,
 };
 
 // Re-export the core command framework from the jackdaw_commands crate
-pub use jackdaw_commands::{CommandGroup, CommandHistory, EditorCommand};
+pub use jackdaw_commands::{CommandGroup, CommandHistory, EditorCommand, remap_entity};
 
 use crate::EditorEntity;
 
@@ -17,10 +19,17 @@ pub struct CommandHistoryPlugin;
 
 impl Plugin for CommandHistoryPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(CommandHistory::default()).add_systems(
-            Update,
-            handle_undo_redo_keys.run_if(in_state(crate::AppState::Editor)),
-        );
+        app.insert_resource(CommandHistory::default())
+            .init_resource::<RespawnedEntities>()
+            .add_systems(
+                Update,
+                (handle_undo_redo_keys, sync_history_limit)
+                    .run_if(in_state(crate::AppState::Editor)),
+            )
+            .add_systems(
+                Last,
+                remap_respawned_entities.run_if(in_state(crate::AppState::Editor)),
+            );
     }
 }
 
@@ -56,6 +65,30 @@ impl EditorCommand for SetComponentField {
     fn description(&self) -> &str {
         "Set component field"
     }
+
+    fn memory_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.field_path.len() + 2 * REFLECT_VALUE_ESTIMATE
+    }
+
+    fn can_merge(&self, next: &dyn EditorCommand) -> bool {
+        (next as &dyn Any)
+            .downcast_ref::<SetComponentField>()
+            .is_some_and(|next| {
+                next.entity == self.entity
+                    && next.component_type_id == self.component_type_id
+                    && next.field_path == self.field_path
+            })
+    }
+
+    fn merge(&mut self, next: &dyn EditorCommand) {
+        if let Some(next) = (next as &dyn Any).downcast_ref::<SetComponentField>() {
+            self.new_value = next.new_value.to_dynamic();
+        }
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+    }
 }
 
 fn apply_reflected_value(
@@ -112,6 +145,22 @@ impl EditorCommand for SetTransform {
     fn description(&self) -> &str {
         "Set transform"
     }
+
+    fn can_merge(&self, next: &dyn EditorCommand) -> bool {
+        (next as &dyn Any)
+            .downcast_ref::<SetTransform>()
+            .is_some_and(|next| next.entity == self.entity)
+    }
+
+    fn merge(&mut self, next: &dyn EditorCommand) {
+        if let Some(next) = (next as &dyn Any).downcast_ref::<SetTransform>() {
+            self.new_transform = next.new_transform;
+        }
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+    }
 }
 
 pub struct ReparentEntity {
@@ -132,6 +181,16 @@ impl EditorCommand for ReparentEntity {
     fn description(&self) -> &str {
         "Reparent entity"
     }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+        for parent in [&mut self.old_parent, &mut self.new_parent]
+            .into_iter()
+            .flatten()
+        {
+            remap_entity(parent, map);
+        }
+    }
 }
 
 fn set_parent(world: &mut World, entity: Entity, parent: Option<Entity>) {
@@ -145,6 +204,49 @@ fn set_parent(world: &mut World, entity: Entity, parent: Option<Entity>) {
     }
 }
 
+/// Rename an entity. `None` means the entity has no `Name`.
+pub struct SetName {
+    pub entity: Entity,
+    pub old_name: Option<String>,
+    pub new_name: Option<String>,
+}
+
+impl EditorCommand for SetName {
+    fn execute(&self, world: &mut World) {
+        set_name(world, self.entity, self.new_name.as_deref());
+    }
+
+    fn undo(&self, world: &mut World) {
+        set_name(world, self.entity, self.old_name.as_deref());
+    }
+
+    fn description(&self) -> &str {
+        "Rename entity"
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+    }
+}
+
+fn set_name(world: &mut World, entity: Entity, name: Option<&str>) {
+    let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+    match name {
+        // Mutate in place so label watchers see a change rather than a re-insert
+        Some(name) => match entity_mut.get_mut::<Name>() {
+            Some(mut existing) => existing.set(name.to_string()),
+            None => {
+                entity_mut.insert(Name::new(name.to_string()));
+            }
+        },
+        None => {
+            entity_mut.remove::<Name>();
+        }
+    }
+}
+
 pub struct AddComponent {
     pub entity: Entity,
     pub type_id: TypeId,
@@ -170,11 +272,11 @@ impl EditorCommand for AddComponent {
             return;
         };
 
-        reflect_component.insert(
-            &mut world.entity_mut(self.entity),
-            default_value.as_partial_reflect(),
-            &registry,
-        );
+        // The entity may be gone, e.g. despawned by a collaboration peer
+        let Ok(mut entity) = world.get_entity_mut(self.entity) else {
+            return;
+        };
+        reflect_component.insert(&mut entity, default_value.as_partial_reflect(), &registry);
     }
 
     fn undo(&self, world: &mut World) {
@@ -186,6 +288,10 @@ impl EditorCommand for AddComponent {
     fn description(&self) -> &str {
         "Add component"
     }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+    }
 }
 
 pub struct RemoveComponent {
@@ -214,16 +320,19 @@ impl EditorCommand for RemoveComponent {
             return;
         };
 
-        reflect_component.insert(
-            &mut world.entity_mut(self.entity),
-            &*self.snapshot,
-            &registry,
-        );
+        let Ok(mut entity) = world.get_entity_mut(self.entity) else {
+            return;
+        };
+        reflect_component.insert(&mut entity, &*self.snapshot, &registry);
     }
 
     fn description(&self) -> &str {
         "Remove component"
     }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+    }
 }
 
 pub struct SpawnEntity {
@@ -246,23 +355,44 @@ impl EditorCommand for SpawnEntity {
     fn description(&self) -> &str {
         &self.label
     }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        if let Some(spawned) = &mut self.spawned {
+            remap_entity(spawned, map);
+        }
+    }
 }
 
 pub struct DespawnEntity {
     pub entity: Entity,
     pub scene_snapshot: DynamicScene,
     pub parent: Option<Entity>,
+    /// Position among the parent's children, restored on undo.
+    pub sibling_index: Option<usize>,
+    /// Snapshot ids to the ids the entities had when last alive, so undo can report
+    /// which ids the respawned entities replace.
+    live_ids: EntityHashMap<Entity>,
     pub label: String,
 }
 
 impl DespawnEntity {
     pub fn from_world(world: &World, entity: Entity) -> Self {
         let parent = world.get::<ChildOf>(entity).map(|c| c.0);
+        let sibling_index = parent
+            .and_then(|parent| world.get::<Children>(parent))
+            .and_then(|children| children.iter().position(|child| child == entity));
         let scene = snapshot_entity(world, entity);
+        let live_ids = scene
+            .entities
+            .iter()
+            .map(|e| (e.entity, e.entity))
+            .collect();
         Self {
             entity,
             scene_snapshot: scene,
             parent,
+            sibling_index,
+            live_ids,
             label: format!("Despawn entity {entity}"),
         }
     }
@@ -276,14 +406,191 @@ impl EditorCommand for DespawnEntity {
     }
 
     fn undo(&self, world: &mut World) {
-        // Re-build the scene from scratch and write it back
+        // Re-build the scene from scratch and write it back. Live entities map to
+        // themselves so references leaving the snapshot, like the parent, stay intact.
         let scene = snapshot_rebuild(&self.scene_snapshot);
-        let _result = scene.write_to_world(world, &mut Default::default());
+        let mut entity_map: EntityHashMap<Entity> = world
+            .query::<Entity>()
+            .iter(world)
+            .map(|e| (e, e))
+            .collect();
+        if scene.write_to_world(world, &mut entity_map).is_err() {
+            return;
+        }
+
+        let root = self
+            .live_ids
+            .iter()
+            .find(|&(_, &live)| live == self.entity)
+            .and_then(|(snapshot_id, _)| entity_map.get(snapshot_id).copied());
+        if let (Some(root), Some(parent), Some(index)) = (root, self.parent, self.sibling_index)
+            && world.get_entity(parent).is_ok()
+        {
+            world.entity_mut(root).remove::<ChildOf>();
+            world.entity_mut(parent).insert_children(index, &[root]);
+        }
+
+        // The respawned entities have new ids; the history is patched once it's back
+        let respawned: Vec<(Entity, Entity)> = self
+            .live_ids
+            .iter()
+            .filter_map(|(snapshot_id, &live)| Some((live, *entity_map.get(snapshot_id)?)))
+            .collect();
+        if let Some(mut pending) = world.get_resource_mut::<RespawnedEntities>() {
+            pending.0.extend(respawned);
+        }
     }
 
     fn description(&self) -> &str {
         &self.label
     }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+        if let Some(parent) = &mut self.parent {
+            remap_entity(parent, map);
+        }
+        for live in self.live_ids.values_mut() {
+            remap_entity(live, map);
+        }
+    }
+
+    fn memory_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.label.len() + scene_memory_size(&self.scene_snapshot)
+    }
+}
+
+/// Undo record for entities created by an editor operation (duplicate, instancing, etc.).
+///
+/// The entities are expected to already exist when the command is pushed. Undo despawns
+/// them; redo rewrites the snapshot and tracks the freshly spawned ids so a later undo
+/// despawns the right entities.
+pub struct SpawnSnapshot {
+    pub scene_snapshot: DynamicScene,
+    roots: Mutex<Vec<SnapshotRoot>>,
+    pub label: String,
+}
+
+/// A root entity of a [`SpawnSnapshot`].
+struct SnapshotRoot {
+    /// Id in the snapshot, the key respawned entities are mapped from.
+    snapshot_id: Entity,
+    /// Id of the live entity.
+    live: Entity,
+    /// Parent it was attached to, if any.
+    parent: Option<Entity>,
+}
+
+impl SpawnSnapshot {
+    pub fn from_world(world: &World, roots: &[Entity], label: impl Into<String>) -> Self {
+        let mut entities = Vec::new();
+        for &root in roots {
+            collect_entity_ids(world, root, &mut entities);
+        }
+        // Runtime-generated children are rebuilt from their parent's data on respawn.
+        entities.retain(|&e| world.get::<crate::NonSerializable>(e).is_none());
+        let scene_snapshot = DynamicSceneBuilder::from_world(world)
+            .extract_entities(entities.into_iter())
+            .build();
+        Self {
+            scene_snapshot,
+            roots: Mutex::new(
+                roots
+                    .iter()
+                    .map(|&e| SnapshotRoot {
+                        snapshot_id: e,
+                        live: e,
+                        parent: world.get::<ChildOf>(e).map(|c| c.0),
+                    })
+                    .collect(),
+            ),
+            label: label.into(),
+        }
+    }
+
+    /// Current live ids of the root entities.
+    pub fn roots(&self) -> Vec<Entity> {
+        self.roots
+            .lock()
+            .map(|r| r.iter().map(|root| root.live).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl EditorCommand for SpawnSnapshot {
+    fn execute(&self, world: &mut World) {
+        let scene = snapshot_rebuild(&self.scene_snapshot);
+        let mut entity_map = EntityHashMap::default();
+        if scene.write_to_world(world, &mut entity_map).is_err() {
+            return;
+        }
+        let Ok(mut roots) = self.roots.lock() else {
+            return;
+        };
+        let mut respawned = Vec::new();
+        for root in roots.iter_mut() {
+            let Some(&new_root) = entity_map.get(&root.snapshot_id) else {
+                continue;
+            };
+            respawned.push((root.live, new_root));
+            root.live = new_root;
+            // Parents outside the snapshot are not part of the entity map, so re-attach explicitly.
+            if let Some(parent) = root.parent {
+                if world.get_entity(parent).is_ok() {
+                    world.entity_mut(new_root).insert(ChildOf(parent));
+                }
+            }
+        }
+        drop(roots);
+        if let Some(mut pending) = world.get_resource_mut::<RespawnedEntities>() {
+            pending.0.extend(respawned);
+        }
+    }
+
+    fn undo(&self, world: &mut World) {
+        for root in self.roots() {
+            if let Ok(mut ec) = world.get_entity_mut(root) {
+                ec.remove::<crate::selection::Selected>();
+                ec.despawn();
+            }
+        }
+    }
+
+    fn description(&self) -> &str {
+        &self.label
+    }
+
+    fn memory_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.label.len() + scene_memory_size(&self.scene_snapshot)
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        let Ok(roots) = self.roots.get_mut() else {
+            return;
+        };
+        for root in roots {
+            remap_entity(&mut root.live, map);
+            if let Some(parent) = &mut root.parent {
+                remap_entity(parent, map);
+            }
+        }
+    }
+}
+
+/// Rough per-value cost of a boxed reflected value, used for history memory accounting.
+const REFLECT_VALUE_ESTIMATE: usize = 128;
+
+/// Approximate bytes held by a scene snapshot.
+pub(crate) fn scene_memory_size(scene: &DynamicScene) -> usize {
+    scene
+        .entities
+        .iter()
+        .map(|e| {
+            std::mem::size_of::<bevy::scene::DynamicEntity>()
+                + e.components.len() * REFLECT_VALUE_ESTIMATE
+        })
+        .sum::<usize>()
+        + scene.resources.len() * REFLECT_VALUE_ESTIMATE
 }
 
 /// Create a DynamicScene snapshot of a single entity and all its descendants.
@@ -297,6 +604,10 @@ pub(crate) fn snapshot_entity(world: &World, entity: Entity) -> DynamicScene {
 
 pub(crate) fn collect_entity_ids(world: &World, entity: Entity, out: &mut Vec<Entity>) {
     out.push(entity);
+    // Sub-scene contents are respawned from their source file.
+    if world.get::<jackdaw_jsn::SubScene>(entity).is_some() {
+        return;
+    }
     if let Some(children) = world.get::<Children>(entity) {
         for child in children.iter() {
             if world.get::<EditorEntity>(child).is_none() {
@@ -331,27 +642,45 @@ fn handle_undo_redo_keys(world: &mut World) {
         return;
     }
 
-    let mut history = world.resource_mut::<CommandHistory>();
-    // Take ownership to avoid borrow conflict with world
-    let command = if shift {
-        history.redo_stack.pop()
-    } else {
-        history.undo_stack.pop()
-    };
-
-    if let Some(command) = command {
+    // Go through the history so replays are counted, see `CommandHistory::replays`
+    world.resource_scope(|world, mut history: Mut<CommandHistory>| {
         if shift {
-            command.execute(world);
-            world
-                .resource_mut::<CommandHistory>()
-                .undo_stack
-                .push(command);
+            history.redo(world);
         } else {
-            command.undo(world);
-            world
-                .resource_mut::<CommandHistory>()
-                .redo_stack
-                .push(command);
+            history.undo(world);
         }
+    });
+}
+
+/// Entities an undo or redo respawned under new ids, old id to new. Drained by
+/// [`remap_respawned_entities`] once the history is back in the world.
+#[derive(Resource, Default)]
+pub struct RespawnedEntities(pub EntityHashMap<Entity>);
+
+/// Point the recorded history at the new ids of respawned entities, so steps touching
+/// a deleted-then-restored entity keep working.
+fn remap_respawned_entities(
+    mut history: ResMut<CommandHistory>,
+    mut respawned: ResMut<RespawnedEntities>,
+) {
+    if respawned.0.is_empty() {
+        return;
     }
+    let map = std::mem::take(&mut respawned.0);
+    history.remap_entities(&map);
+}
+
+/// Apply the project's `history_limit` whenever a project is opened or its config changes.
+fn sync_history_limit(
+    mut history: ResMut<CommandHistory>,
+    project: Option<Res<crate::project::ProjectRoot>>,
+) {
+    let Some(project) = project.filter(|p| p.is_changed()) else {
+        return;
+    };
+    history.max_depth = project
+        .config
+        .project
+        .history_limit
+        .unwrap_or(jackdaw_commands::DEFAULT_HISTORY_DEPTH);
 }
@@ -0,0 +1,292 @@
+//! Ghost preview of CSG operations. Subtract and merge first show their result as a
+//! translucent, wireframed overlay that follows the selection; Enter applies it and Esc
+//! cancels. The preview keeps its own geometry, apart from the brushes' `BrushMeshCache`.
+
+use bevy::{
+    input_focus::InputFocus,
+    light::{NotShadowCaster, NotShadowReceiver},
+    mesh::{Indices, PrimitiveTopology},
+    prelude::*,
+};
+use jackdaw_geometry::compute_brush_geometry;
+use jackdaw_jsn::Brush;
+
+use crate::{
+    EditorEntity,
+    brush::{MeshCsgCache, request_mesh_csg_brushes},
+    draw_brush,
+    selection::Selection,
+    status_bar::StatusHints,
+};
+
+const SUBTRACT_COLOR: Color = Color::srgba(1.0, 0.55, 0.15, 0.25);
+const MERGE_COLOR: Color = Color::srgba(0.3, 0.85, 0.45, 0.25);
+const CSG_PREVIEW_HINTS: &str = "csg_preview";
+
+pub struct CsgPreviewPlugin;
+
+impl Plugin for CsgPreviewPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CsgPreview>().add_systems(
+            Update,
+            (
+                handle_csg_preview_keys,
+                mark_csg_preview_dirty,
+                rebuild_csg_preview,
+                draw_csg_preview_wireframe,
+            )
+                .chain()
+                .run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CsgOp {
+    /// The selection cuts the brushes it touches.
+    Subtract,
+    /// The selected brushes merge into their convex hull; `force` skips the convexity check.
+    Merge { force: bool },
+}
+
+impl CsgOp {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Subtract => "Subtract",
+            Self::Merge { .. } => "Merge",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::Subtract => SUBTRACT_COLOR,
+            Self::Merge { .. } => MERGE_COLOR,
+        }
+    }
+}
+
+/// The pending CSG operation and the geometry of its result.
+#[derive(Resource, Default)]
+pub struct CsgPreview {
+    pub op: Option<CsgOp>,
+    /// World-space vertices and face polygons of each resulting piece.
+    pieces: Vec<(Vec<Vec3>, Vec<Vec<usize>>)>,
+    /// Brushes hidden because the preview replaces them, with their visibility before.
+    hidden: Vec<(Entity, Visibility)>,
+    fill: Option<Entity>,
+    dirty: bool,
+}
+
+/// Start previewing `op` on the current selection, replacing any pending preview.
+pub fn begin_csg_preview(world: &mut World, op: CsgOp) {
+    end_csg_preview(world);
+    let mut preview = world.resource_mut::<CsgPreview>();
+    preview.op = Some(op);
+    preview.dirty = true;
+}
+
+/// Apply the pending operation.
+pub fn confirm_csg_preview(world: &mut World) {
+    let Some(op) = world.resource::<CsgPreview>().op else {
+        return;
+    };
+    end_csg_preview(world);
+    match op {
+        CsgOp::Subtract => draw_brush::csg_subtract_selected_impl(world),
+        CsgOp::Merge { force: false } => draw_brush::join_selected_brushes_impl(world),
+        CsgOp::Merge { force: true } => draw_brush::force_join_selected_brushes_impl(world),
+    }
+}
+
+/// Drop the pending operation and its ghost, showing the brushes it hid again.
+pub fn end_csg_preview(world: &mut World) {
+    clear_preview_geometry(world);
+    *world.resource_mut::<CsgPreview>() = CsgPreview::default();
+    world.resource_mut::<StatusHints>().clear(CSG_PREVIEW_HINTS);
+}
+
+fn clear_preview_geometry(world: &mut World) {
+    let mut preview = world.resource_mut::<CsgPreview>();
+    let hidden = std::mem::take(&mut preview.hidden);
+    let fill = preview.fill.take();
+    preview.pieces.clear();
+    for (entity, visibility) in hidden {
+        if let Some(mut current) = world.get_mut::<Visibility>(entity) {
+            *current = visibility;
+        }
+    }
+    if let Some(fill) = fill
+        && let Ok(entity) = world.get_entity_mut(fill)
+    {
+        entity.despawn();
+    }
+}
+
+fn handle_csg_preview_keys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input_focus: Res<InputFocus>,
+    preview: Res<CsgPreview>,
+    mut commands: Commands,
+) {
+    if preview.op.is_none() || input_focus.0.is_some() {
+        return;
+    }
+    if keyboard.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) {
+        commands.queue(confirm_csg_preview);
+    } else if keyboard.just_pressed(KeyCode::Escape) {
+        commands.queue(end_csg_preview);
+    }
+}
+
+/// The result follows the selection and any brush moved or edited meanwhile, and comes
+/// in once the selected meshes are decomposed.
+fn mark_csg_preview_dirty(
+    selection: Res<Selection>,
+    mesh_csg: Res<MeshCsgCache>,
+    mut preview: ResMut<CsgPreview>,
+    changed: Query<(), (With<Brush>, Or<(Changed<Brush>, Changed<GlobalTransform>)>)>,
+) {
+    if preview.op.is_some()
+        && (selection.is_changed() || mesh_csg.is_changed() || !changed.is_empty())
+    {
+        preview.dirty = true;
+    }
+}
+
+fn rebuild_csg_preview(world: &mut World) {
+    let (Some(op), true) = ({
+        let preview = world.resource::<CsgPreview>();
+        (preview.op, preview.dirty)
+    }) else {
+        return;
+    };
+    if world.resource::<Selection>().entities.is_empty() {
+        end_csg_preview(world);
+        return;
+    }
+    clear_preview_geometry(world);
+
+    let (pieces, replaced): (Vec<_>, Vec<Entity>) = match op {
+        CsgOp::Subtract => {
+            // Selected meshes cut as convex pieces, decomposed off the main thread
+            let selected = world.resource::<Selection>().entities.clone();
+            let requested: Vec<Option<Vec<_>>> = selected
+                .iter()
+                .map(|&entity| request_mesh_csg_brushes(world, entity))
+                .collect();
+            let Some(mesh_cutters) = requested.into_iter().collect::<Option<Vec<_>>>() else {
+                world.resource_mut::<StatusHints>().set(
+                    CSG_PREVIEW_HINTS,
+                    format!("{}: preparing mesh cutters | Esc: cancel", op.label()),
+                );
+                world.resource_mut::<CsgPreview>().dirty = false;
+                return;
+            };
+            let mesh_cutters = mesh_cutters.into_iter().flatten().collect();
+            let results = draw_brush::csg_subtract_fragments(world, mesh_cutters);
+            let replaced: Vec<Entity> = results.iter().map(|(e, _)| *e).collect();
+            let pieces = results
+                .into_iter()
+                .flat_map(|(_, fragments)| fragments)
+                .map(|faces| compute_brush_geometry(&faces))
+                .filter(|(vertices, _)| vertices.len() >= 4)
+                .collect();
+            (pieces, replaced)
+        }
+        CsgOp::Merge { .. } => (
+            draw_brush::join_hull_preview(world).into_iter().collect(),
+            Vec::new(),
+        ),
+    };
+
+    let hidden: Vec<(Entity, Visibility)> = replaced
+        .into_iter()
+        .filter_map(|entity| {
+            let mut visibility = world.get_mut::<Visibility>(entity)?;
+            let before = *visibility;
+            *visibility = Visibility::Hidden;
+            Some((entity, before))
+        })
+        .collect();
+    let fill = preview_mesh(&pieces).map(|mesh| {
+        let mesh = world.resource_mut::<Assets<Mesh>>().add(mesh);
+        let material = world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial {
+                base_color: op.color(),
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                cull_mode: None,
+                ..default()
+            });
+        world
+            .spawn((
+                Mesh3d(mesh),
+                MeshMaterial3d(material),
+                Transform::default(),
+                NotShadowCaster,
+                NotShadowReceiver,
+                EditorEntity,
+            ))
+            .id()
+    });
+
+    let hint = if pieces.is_empty() {
+        format!("{}: nothing to change | Esc: cancel", op.label())
+    } else {
+        format!("{} preview | Enter: apply | Esc: cancel", op.label())
+    };
+    world
+        .resource_mut::<StatusHints>()
+        .set(CSG_PREVIEW_HINTS, hint);
+
+    let mut preview = world.resource_mut::<CsgPreview>();
+    preview.pieces = pieces;
+    preview.hidden = hidden;
+    preview.fill = fill;
+    preview.dirty = false;
+}
+
+/// One translucent mesh holding every piece, faces fanned into triangles.
+fn preview_mesh(pieces: &[(Vec<Vec3>, Vec<Vec<usize>>)]) -> Option<Mesh> {
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut normals: Vec<[f32; 3]> = Vec::new();
+    let mut indices: Vec<u32> = Vec::new();
+    for (vertices, polygons) in pieces {
+        for polygon in polygons.iter().filter(|p| p.len() >= 3) {
+            let points: Vec<Vec3> = polygon.iter().map(|&i| vertices[i]).collect();
+            let normal = (points[1] - points[0])
+                .cross(points[2] - points[0])
+                .normalize_or_zero();
+            let base = positions.len() as u32;
+            positions.extend(points.iter().map(|p| p.to_array()));
+            normals.extend(std::iter::repeat_n(normal.to_array(), points.len()));
+            for i in 1..points.len() as u32 - 1 {
+                indices.extend_from_slice(&[base, base + i, base + i + 1]);
+            }
+        }
+    }
+    if indices.is_empty() {
+        return None;
+    }
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, default());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_indices(Indices::U32(indices));
+    Some(mesh)
+}
+
+fn draw_csg_preview_wireframe(mut gizmos: Gizmos, preview: Res<CsgPreview>) {
+    let Some(op) = preview.op else {
+        return;
+    };
+    let color = op.color().with_alpha(1.0);
+    for (vertices, polygons) in &preview.pieces {
+        for polygon in polygons {
+            gizmos.linestrip(
+                polygon.iter().chain(polygon.first()).map(|&i| vertices[i]),
+                color,
+            );
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! The 3D cursor: a point in the scene used as a pivot, e.g. for mirroring.
+
+use bevy::prelude::*;
+
+use crate::{align, selection::Selection};
+
+pub struct Cursor3dPlugin;
+
+impl Plugin for Cursor3dPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Cursor3d>().add_systems(
+            Update,
+            draw_cursor3d.run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Cursor3d {
+    pub position: Vec3,
+}
+
+/// Move the cursor to the center of the selection's bounds.
+pub fn cursor_to_selection(world: &mut World) {
+    let entities = world.resource::<Selection>().entities.clone();
+    if let Some(center) = align::bounds_center(world, &entities) {
+        world.resource_mut::<Cursor3d>().position = center;
+    }
+}
+
+fn draw_cursor3d(cursor: Res<Cursor3d>, mut gizmos: Gizmos) {
+    // Hidden at the origin, where it coincides with the world pivot anyway
+    if cursor.position == Vec3::ZERO {
+        return;
+    }
+    let size = 0.25;
+    let color = Color::srgb(1.0, 0.35, 0.35);
+    gizmos.sphere(Isometry3d::from_translation(cursor.position), size, color);
+    for axis in [Vec3::X, Vec3::Y, Vec3::Z] {
+        gizmos.line(
+            cursor.position - axis * size * 2.0,
+            cursor.position + axis * size * 2.0,
+            Color::WHITE,
+        );
+    }
+}
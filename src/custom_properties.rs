@@ -1,4 +1,4 @@
-use bevy::prelude::*;
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
 
 // Re-export types from jackdaw_jsn
 pub use jackdaw_jsn::{CustomProperties, PropertyValue};
@@ -11,6 +11,16 @@ impl Plugin for CustomPropertiesPlugin {
     }
 }
 
+/// An entity-reference property waiting for its target. While this resource exists,
+/// the next entity selected in the viewport or hierarchy becomes the property's value.
+#[derive(Resource, Clone)]
+pub struct PendingEntityPick {
+    pub source_entity: Entity,
+    pub property_name: String,
+    /// Item of a list property to set, if any.
+    pub index: Option<usize>,
+}
+
 /// Undo command that stores old/new snapshots of the entire CustomProperties component.
 pub struct SetCustomProperties {
     pub entity: Entity,
@@ -34,4 +44,8 @@ impl crate::commands::EditorCommand for SetCustomProperties {
     fn description(&self) -> &str {
         "Set custom properties"
     }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        crate::commands::remap_entity(&mut self.entity, map);
+    }
 }
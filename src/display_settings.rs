@@ -0,0 +1,321 @@
+//! Editor-wide display settings: UI scale, base font size, viewport theme colors and
+//! camera navigation, set from View > Display Settings and kept in `settings.json` in the config directory.
+//! The inspector's section layout is kept there too.
+
+use std::{collections::BTreeSet, path::PathBuf};
+
+use bevy::prelude::*;
+use jackdaw_camera::JackdawCameraSettings;
+use jackdaw_feathers::{
+    combobox::{ComboBoxChangeEvent, combobox_with_selected},
+    dialog::{DialogActionEvent, DialogChildrenSlot, DialogClosedEvent, DialogId, OpenDialogEvent},
+    display::DisplayScale,
+    text_edit::{self, TextEditProps, TextEditValue},
+    tokens,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::selection_outline::OutlineColors;
+
+/// UI scale choices offered in the dialog, in percent.
+const UI_SCALE_STEPS: [u32; 6] = [75, 100, 125, 150, 175, 200];
+const DISPLAY_DIALOG: &str = "display_settings";
+
+pub struct DisplaySettingsPlugin;
+
+impl Plugin for DisplaySettingsPlugin {
+    fn build(&self, app: &mut App) {
+        let display = read_editor_settings().display;
+        app.insert_resource(
+            DisplayScale {
+                ui_scale: display.ui_scale,
+                font_size: display.font_size,
+            }
+            .clamped(),
+        )
+        .init_resource::<PendingDisplaySettings>()
+        .init_resource::<PendingNavigation>()
+        .add_observer(on_display_settings_confirm)
+        .add_observer(on_display_dialog_closed)
+        .add_systems(
+            Update,
+            populate_display_dialog.run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+/// Settings that apply to the editor regardless of the open project.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct EditorSettings {
+    pub display: DisplaySettings,
+    pub theme: ThemeSettings,
+    pub navigation: NavigationSettings,
+    pub inspector: InspectorSettings,
+    pub history: HistorySettings,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct DisplaySettings {
+    pub ui_scale: f32,
+    pub font_size: f32,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        let scale = DisplayScale::default();
+        Self {
+            ui_scale: scale.ui_scale,
+            font_size: scale.font_size,
+        }
+    }
+}
+
+/// Viewport camera controls.
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct NavigationSettings {
+    /// Alt + mouse buttons orbit, pan and dolly around the selection.
+    pub orbit: bool,
+}
+
+/// Undo history behavior.
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct HistorySettings {
+    /// Selection changes are undo steps, and undoing an edit restores the selection it changed.
+    pub undo_selection: bool,
+}
+
+/// Inspector component sections, keyed by component type path.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+#[serde(default)]
+pub struct InspectorSettings {
+    /// Components whose sections show collapsed.
+    pub collapsed: BTreeSet<String>,
+    /// Components in the order their sections were dragged into. Sections not listed
+    /// follow alphabetically.
+    pub order: Vec<String>,
+}
+
+/// Viewport colors as `#rrggbbaa` hex strings.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ThemeSettings {
+    pub selection_outline: String,
+    pub hover_outline: String,
+}
+
+impl Default for ThemeSettings {
+    fn default() -> Self {
+        let colors = OutlineColors::default();
+        Self {
+            selection_outline: colors.selection.to_srgba().to_hex(),
+            hover_outline: colors.hover.to_srgba().to_hex(),
+        }
+    }
+}
+
+impl ThemeSettings {
+    /// The outline colors, falling back to the defaults for unreadable values.
+    pub fn outline_colors(&self) -> OutlineColors {
+        let defaults = OutlineColors::default();
+        let parse = |hex: &str, fallback: Color| Srgba::hex(hex).map_or(fallback, Color::from);
+        OutlineColors {
+            selection: parse(&self.selection_outline, defaults.selection),
+            hover: parse(&self.hover_outline, defaults.hover),
+        }
+    }
+}
+
+fn settings_path() -> Option<PathBuf> {
+    crate::project::config_dir().map(|d| d.join("settings.json"))
+}
+
+pub fn read_editor_settings() -> EditorSettings {
+    settings_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_editor_settings(settings: &EditorSettings) {
+    let Some(path) = settings_path() else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(data) = serde_json::to_string_pretty(settings) {
+        let _ = std::fs::write(&path, data);
+    }
+}
+
+/// The display settings being edited while the dialog is open.
+#[derive(Resource, Default)]
+struct PendingDisplaySettings(Option<DisplayScale>);
+
+/// The navigation mode chosen in the open dialog.
+#[derive(Resource, Default)]
+struct PendingNavigation(NavigationSettings);
+
+#[derive(Component)]
+struct FontSizeInput;
+
+#[derive(Component)]
+struct SelectionColorInput;
+
+#[derive(Component)]
+struct HoverColorInput;
+
+pub fn open_display_dialog(world: &mut World) {
+    let current = *world.resource::<DisplayScale>();
+    world.resource_mut::<PendingDisplaySettings>().0 = Some(current);
+    world.resource_mut::<PendingNavigation>().0 = read_editor_settings().navigation;
+    world.trigger(
+        OpenDialogEvent::new("Display Settings", "Apply")
+            .with_id(DISPLAY_DIALOG)
+            .with_max_width(px(320)),
+    );
+}
+
+fn populate_display_dialog(
+    mut commands: Commands,
+    pending: Res<PendingDisplaySettings>,
+    navigation: Res<PendingNavigation>,
+    outline_colors: Res<OutlineColors>,
+    slots: Query<(Entity, &DialogId), Added<DialogChildrenSlot>>,
+) {
+    let Some(current) = pending.0 else {
+        return;
+    };
+    let hex = |color: Color| color.to_srgba().to_hex();
+    for (slot, id) in &slots {
+        if id.0 != DISPLAY_DIALOG {
+            continue;
+        }
+        let options: Vec<String> = UI_SCALE_STEPS
+            .iter()
+            .map(|step| format!("UI Scale: {step}%"))
+            .collect();
+        let selected = UI_SCALE_STEPS
+            .iter()
+            .position(|&step| (step as f32 / 100.0 - current.ui_scale).abs() < 0.01)
+            .unwrap_or(1);
+        commands
+            .spawn((combobox_with_selected(options, selected), ChildOf(slot)))
+            .observe(
+                |event: On<ComboBoxChangeEvent>, mut pending: ResMut<PendingDisplaySettings>| {
+                    if let (Some(settings), Some(&step)) =
+                        (&mut pending.0, UI_SCALE_STEPS.get(event.selected))
+                    {
+                        settings.ui_scale = step as f32 / 100.0;
+                    }
+                },
+            );
+        commands.spawn((
+            FontSizeInput,
+            text_edit::text_edit(
+                TextEditProps::default()
+                    .with_label("Font Size")
+                    .numeric_i32()
+                    .with_min(tokens::FONT_SIZE_MIN as f64)
+                    .with_max(tokens::FONT_SIZE_MAX as f64)
+                    .with_default_value(current.font_size.round().to_string()),
+            ),
+            ChildOf(slot),
+        ));
+        commands.spawn((
+            SelectionColorInput,
+            text_edit::text_edit(
+                TextEditProps::default()
+                    .with_label("Selection Outline")
+                    .with_default_value(hex(outline_colors.selection)),
+            ),
+            ChildOf(slot),
+        ));
+        commands.spawn((
+            HoverColorInput,
+            text_edit::text_edit(
+                TextEditProps::default()
+                    .with_label("Hover Outline")
+                    .with_default_value(hex(outline_colors.hover)),
+            ),
+            ChildOf(slot),
+        ));
+        let options = vec![
+            "Navigation: Fly".to_string(),
+            "Navigation: Fly + Alt Orbit".to_string(),
+        ];
+        commands
+            .spawn((
+                combobox_with_selected(options, usize::from(navigation.0.orbit)),
+                ChildOf(slot),
+            ))
+            .observe(
+                |event: On<ComboBoxChangeEvent>, mut navigation: ResMut<PendingNavigation>| {
+                    navigation.0.orbit = event.selected == 1;
+                },
+            );
+    }
+}
+
+fn on_display_settings_confirm(
+    event: On<DialogActionEvent>,
+    mut pending: ResMut<PendingDisplaySettings>,
+    font_size: Query<&TextEditValue, With<FontSizeInput>>,
+    selection_color: Query<&TextEditValue, With<SelectionColorInput>>,
+    hover_color: Query<&TextEditValue, With<HoverColorInput>>,
+    mut display: ResMut<DisplayScale>,
+    mut outline_colors: ResMut<OutlineColors>,
+    navigation: Res<PendingNavigation>,
+    mut cameras: Query<&mut JackdawCameraSettings>,
+) {
+    if event.id != Some(DISPLAY_DIALOG) {
+        return;
+    }
+    let Some(mut settings) = pending.0.take() else {
+        return;
+    };
+    if let Some(size) = font_size
+        .iter()
+        .next()
+        .and_then(|value| value.0.trim().parse::<f32>().ok())
+    {
+        settings.font_size = size;
+    }
+    let settings = settings.clamped();
+    if *display != settings {
+        *display = settings;
+    }
+    let mut editor_settings = read_editor_settings();
+    editor_settings.display = DisplaySettings {
+        ui_scale: settings.ui_scale,
+        font_size: settings.font_size,
+    };
+    if let Some(value) = selection_color.iter().next() {
+        editor_settings.theme.selection_outline = value.0.trim().to_string();
+    }
+    if let Some(value) = hover_color.iter().next() {
+        editor_settings.theme.hover_outline = value.0.trim().to_string();
+    }
+    let colors = editor_settings.theme.outline_colors();
+    if *outline_colors != colors {
+        *outline_colors = colors;
+    }
+    editor_settings.navigation = navigation.0;
+    for mut camera in &mut cameras {
+        camera.orbit = navigation.0.orbit;
+    }
+    save_editor_settings(&editor_settings);
+}
+
+fn on_display_dialog_closed(
+    event: On<DialogClosedEvent>,
+    mut pending: ResMut<PendingDisplaySettings>,
+) {
+    if event.id == Some(DISPLAY_DIALOG) {
+        pending.0 = None;
+    }
+}
@@ -0,0 +1,1078 @@
+//! Dockable panels. Dragging a panel's header re-docks it: onto the start or end of
+//! another panel to split beside it, onto its middle to join it as a tab, or over the
+//! viewport to float it as a window region. The arrangement is saved to the config
+//! directory whenever it changes and restored on startup; the View menu switches
+//! between preset layouts. Panels can also be opened in their own OS window, and go
+//! back to their home area when it's closed.
+
+use std::{collections::HashSet, path::PathBuf};
+
+use bevy::{feathers::theme::ThemedText, prelude::*, window::WindowCloseRequested};
+use jackdaw_feathers::{panel_header::PanelHeader, split_panel, tokens};
+use jackdaw_widgets::split_panel::{Panel, PanelHandle};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    EditorEntity,
+    layout::{SecondaryWindow, spawn_secondary_window},
+    viewport::SceneViewport,
+};
+
+/// Fraction of a panel, at either end along its area's axis, that docks beside it
+/// rather than as a tab.
+const EDGE_FRACTION: f32 = 0.25;
+const FLOAT_SIZE: Vec2 = Vec2::new(320.0, 400.0);
+/// Where the pointer holds a freshly floated panel, from its top-left corner.
+const FLOAT_GRAB: Vec2 = Vec2::new(40.0, 12.0);
+const WINDOW_SIZE: UVec2 = UVec2::new(480, 720);
+/// Seconds without further resizing before split ratios are saved.
+const SAVE_DELAY: f32 = 1.0;
+
+pub struct DockingPlugin;
+
+impl Plugin for DockingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DockDrag>()
+            .add_observer(on_header_drag_start)
+            .add_observer(on_header_drag)
+            .add_observer(on_header_drag_end)
+            .add_observer(on_dock_tab_click)
+            .add_systems(
+                OnEnter(crate::AppState::Editor),
+                restore_layout
+                    .after(crate::spawn_layout)
+                    .after(crate::extensions::spawn_extension_panels),
+            )
+            .add_systems(
+                Update,
+                (
+                    update_tab_groups,
+                    save_layout_on_resize,
+                    close_panel_windows,
+                )
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+/// A split group panels can be docked into.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum DockArea {
+    Left,
+    Right,
+    Bottom,
+}
+
+/// A panel that can be re-docked by dragging its header. `id` identifies it in saved
+/// layouts; `home` is the area it returns to when a layout doesn't place it.
+#[derive(Component, Clone)]
+pub struct DockPanel {
+    pub id: String,
+    pub title: String,
+    pub home: DockArea,
+}
+
+impl DockPanel {
+    pub fn new(id: impl Into<String>, title: impl Into<String>, home: DockArea) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            home,
+        }
+    }
+}
+
+/// The editor's root node, which floating panels are laid over.
+#[derive(Component)]
+pub struct DockRoot;
+
+/// Panels sharing one slot of an area, one visible at a time. Its first child is the
+/// tab bar.
+#[derive(Component)]
+struct DockTabs {
+    active: usize,
+}
+
+#[derive(Component)]
+struct DockTabBar;
+
+#[derive(Component)]
+struct DockTab {
+    group: Entity,
+    index: usize,
+}
+
+/// A panel floated over the viewport.
+#[derive(Component)]
+struct FloatingPanel;
+
+/// On a secondary window showing a single panel.
+#[derive(Component)]
+struct PanelWindow {
+    panel: Entity,
+    secondary: SecondaryWindow,
+}
+
+/// Highlight of where the dragged panel would land.
+#[derive(Component)]
+struct DockDropIndicator;
+
+#[derive(Resource, Default)]
+struct DockDrag {
+    panel: Option<Entity>,
+    indicator: Option<Entity>,
+}
+
+#[derive(Clone, Copy)]
+enum DropTarget {
+    /// Split into the area, before the given slot or at its end.
+    Area {
+        area: Entity,
+        before: Option<Entity>,
+    },
+    /// Join the slot as a tab.
+    Tab { slot: Entity },
+    /// Float with the top-left corner here.
+    Float { position: Vec2 },
+}
+
+/// A saved panel arrangement.
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct DockLayout {
+    pub areas: Vec<AreaLayout>,
+    #[serde(default)]
+    pub floating: Vec<FloatingLayout>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AreaLayout {
+    pub area: DockArea,
+    /// Share of the area within its parent split, if it should change.
+    #[serde(default)]
+    pub ratio: Option<f32>,
+    pub slots: Vec<SlotLayout>,
+}
+
+/// Panels sharing one split of an area; more than one makes a tab group.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SlotLayout {
+    pub panels: Vec<String>,
+    #[serde(default)]
+    pub active: usize,
+    pub ratio: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FloatingLayout {
+    pub panel: String,
+    pub position: [f32; 2],
+    pub size: [f32; 2],
+}
+
+fn slot(panels: &[&str], ratio: f32) -> SlotLayout {
+    SlotLayout {
+        panels: panels.iter().map(|p| p.to_string()).collect(),
+        active: 0,
+        ratio,
+    }
+}
+
+fn area(area: DockArea, slots: Vec<SlotLayout>) -> AreaLayout {
+    AreaLayout {
+        area,
+        ratio: None,
+        slots,
+    }
+}
+
+fn modeling_layout() -> DockLayout {
+    DockLayout {
+        areas: vec![
+            area(
+                DockArea::Left,
+                vec![slot(&["hierarchy"], 3.0), slot(&["minimap"], 1.0)],
+            ),
+            area(
+                DockArea::Right,
+                vec![slot(&["inspector"], 3.0), slot(&["world_settings"], 1.0)],
+            ),
+            area(
+                DockArea::Bottom,
+                vec![
+                    slot(&["assets"], 2.0),
+                    slot(&["textures"], 2.0),
+                    slot(&["materials"], 1.0),
+                    slot(&["templates"], 1.0),
+                ],
+            ),
+        ],
+        floating: Vec::new(),
+    }
+}
+
+/// Browsers next to the inspector, where assigning textures and materials happens.
+fn texturing_layout() -> DockLayout {
+    DockLayout {
+        areas: vec![
+            area(
+                DockArea::Left,
+                vec![slot(&["hierarchy"], 3.0), slot(&["minimap"], 1.0)],
+            ),
+            area(
+                DockArea::Right,
+                vec![
+                    slot(&["inspector"], 3.0),
+                    slot(&["textures", "materials"], 3.0),
+                ],
+            ),
+            area(
+                DockArea::Bottom,
+                vec![
+                    slot(&["assets"], 2.0),
+                    slot(&["templates"], 1.0),
+                    slot(&["world_settings"], 1.0),
+                ],
+            ),
+        ],
+        floating: Vec::new(),
+    }
+}
+
+/// Hierarchy and templates on the left and a tall inspector for editing properties.
+fn scripting_layout() -> DockLayout {
+    DockLayout {
+        areas: vec![
+            area(
+                DockArea::Left,
+                vec![slot(&["hierarchy"], 2.0), slot(&["templates"], 1.0)],
+            ),
+            area(DockArea::Right, vec![slot(&["inspector"], 4.0)]),
+            area(
+                DockArea::Bottom,
+                vec![
+                    slot(&["assets"], 2.0),
+                    slot(&["world_settings"], 1.0),
+                    slot(&["minimap"], 1.0),
+                    slot(&["textures", "materials"], 2.0),
+                ],
+            ),
+        ],
+        floating: Vec::new(),
+    }
+}
+
+/// Switch to a named preset layout: "modeling", "texturing" or "scripting".
+pub fn apply_preset(world: &mut World, preset: &str) {
+    let layout = match preset {
+        "modeling" => modeling_layout(),
+        "texturing" => texturing_layout(),
+        "scripting" => scripting_layout(),
+        _ => return,
+    };
+    apply_layout(world, &layout);
+    save_layout(world);
+}
+
+fn layout_path() -> Option<PathBuf> {
+    crate::project::config_dir().map(|d| d.join("layout.json"))
+}
+
+fn restore_layout(world: &mut World) {
+    let Some(layout) = layout_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|data| serde_json::from_str::<DockLayout>(&data).ok())
+    else {
+        return;
+    };
+    apply_layout(world, &layout);
+}
+
+fn save_layout(world: &mut World) {
+    let Some(path) = layout_path() else {
+        return;
+    };
+    let layout = capture_layout(world);
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(data) = serde_json::to_string_pretty(&layout) {
+        let _ = std::fs::write(&path, data);
+    }
+}
+
+/// Save split ratios once the user stops dragging dividers.
+fn save_layout_on_resize(
+    mut commands: Commands,
+    changed: Query<(), Changed<Panel>>,
+    time: Res<Time>,
+    mut due: Local<Option<f32>>,
+) {
+    let now = time.elapsed_secs();
+    if !changed.is_empty() {
+        *due = Some(now + SAVE_DELAY);
+    } else if due.is_some_and(|due| now >= due) {
+        *due = None;
+        commands.queue(save_layout);
+    }
+}
+
+fn capture_layout(world: &mut World) -> DockLayout {
+    let areas: Vec<(Entity, DockArea)> = world
+        .query::<(Entity, &DockArea)>()
+        .iter(world)
+        .map(|(e, a)| (e, *a))
+        .collect();
+    let areas = areas
+        .into_iter()
+        .map(|(entity, dock_area)| AreaLayout {
+            area: dock_area,
+            ratio: world.get::<Panel>(entity).map(|p| p.ratio),
+            slots: area_slots(world, entity)
+                .into_iter()
+                .map(|slot| {
+                    let ratio = world.get::<Panel>(slot).map_or(1.0, |p| p.ratio);
+                    match world.get::<DockTabs>(slot) {
+                        Some(tabs) => SlotLayout {
+                            panels: tab_panels(world, slot)
+                                .into_iter()
+                                .filter_map(|p| world.get::<DockPanel>(p).map(|d| d.id.clone()))
+                                .collect(),
+                            active: tabs.active,
+                            ratio,
+                        },
+                        None => SlotLayout {
+                            panels: world
+                                .get::<DockPanel>(slot)
+                                .map(|d| d.id.clone())
+                                .into_iter()
+                                .collect(),
+                            active: 0,
+                            ratio,
+                        },
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+
+    let floating = world
+        .query_filtered::<(&Node, &Children), With<FloatingPanel>>()
+        .iter(world)
+        .filter_map(|(node, children)| {
+            let panel = children.iter().find_map(|c| world.get::<DockPanel>(c))?;
+            let px = |val: Val| match val {
+                Val::Px(v) => v,
+                _ => 0.0,
+            };
+            Some(FloatingLayout {
+                panel: panel.id.clone(),
+                position: [px(node.left), px(node.top)],
+                size: [px(node.width), px(node.height)],
+            })
+        })
+        .collect();
+
+    DockLayout { areas, floating }
+}
+
+fn apply_layout(world: &mut World, layout: &DockLayout) {
+    let panels: Vec<(Entity, String, DockArea)> = world
+        .query::<(Entity, &DockPanel)>()
+        .iter(world)
+        .map(|(e, p)| (e, p.id.clone(), p.home))
+        .collect();
+    let areas: Vec<(Entity, DockArea)> = world
+        .query::<(Entity, &DockArea)>()
+        .iter(world)
+        .map(|(e, a)| (e, *a))
+        .collect();
+    if areas.is_empty() {
+        return;
+    }
+
+    // Take every panel out of its container, then drop the containers
+    for &(panel, ..) in &panels {
+        world.entity_mut(panel).remove::<ChildOf>();
+    }
+    let containers: Vec<Entity> = world
+        .query_filtered::<Entity, Or<(With<DockTabs>, With<FloatingPanel>)>>()
+        .iter(world)
+        .collect();
+    for container in containers {
+        world.entity_mut(container).despawn();
+    }
+    let windows: Vec<SecondaryWindow> = world
+        .query::<&PanelWindow>()
+        .iter(world)
+        .map(|w| w.secondary)
+        .collect();
+    for window in windows {
+        window.despawn(world);
+    }
+
+    let find = |id: &str| panels.iter().find(|(_, p, _)| p == id).map(|(e, ..)| *e);
+    let mut placed: HashSet<Entity> = HashSet::new();
+    let mut slots: Vec<(Entity, DockArea, Vec<Entity>)> =
+        areas.iter().map(|&(e, a)| (e, a, Vec::new())).collect();
+
+    for area_layout in &layout.areas {
+        let Some(index) = slots.iter().position(|(_, a, _)| *a == area_layout.area) else {
+            continue;
+        };
+        if let Some(ratio) = area_layout.ratio
+            && let Some(mut panel) = world.get_mut::<Panel>(slots[index].0)
+        {
+            panel.ratio = ratio;
+        }
+        for slot_layout in &area_layout.slots {
+            let members: Vec<Entity> = slot_layout
+                .panels
+                .iter()
+                .filter_map(|id| find(id))
+                .filter(|e| placed.insert(*e))
+                .collect();
+            match members.as_slice() {
+                [] => continue,
+                [panel] => {
+                    make_slot(world, *panel, slot_layout.ratio);
+                    slots[index].2.push(*panel);
+                }
+                _ => {
+                    let group = spawn_tab_group(world, slot_layout.ratio);
+                    for &member in &members {
+                        make_inner(world, member);
+                        world.entity_mut(member).insert(ChildOf(group));
+                    }
+                    if let Some(mut tabs) = world.get_mut::<DockTabs>(group) {
+                        tabs.active = slot_layout.active.min(members.len() - 1);
+                    }
+                    rebuild_tab_bar(world, group);
+                    slots[index].2.push(group);
+                }
+            }
+        }
+    }
+
+    for floating in &layout.floating {
+        if let Some(panel) = find(&floating.panel)
+            && placed.insert(panel)
+        {
+            spawn_floating(
+                world,
+                panel,
+                Vec2::from(floating.position),
+                Vec2::from(floating.size),
+            );
+        }
+    }
+
+    // Panels the layout doesn't mention, e.g. ones added since it was saved, go home
+    for &(panel, _, home) in &panels {
+        if placed.contains(&panel) {
+            continue;
+        }
+        if let Some((_, _, area_slots)) = slots.iter_mut().find(|(_, a, _)| *a == home) {
+            make_slot(world, panel, 1.0);
+            area_slots.push(panel);
+        }
+    }
+
+    for (area, _, area_slots) in slots {
+        normalize_area(world, area, area_slots);
+    }
+}
+
+/// The panels and tab groups of an area, in order.
+fn area_slots(world: &World, area: Entity) -> Vec<Entity> {
+    world
+        .get::<Children>(area)
+        .map(|children| {
+            children
+                .iter()
+                .filter(|&c| world.get::<PanelHandle>(c).is_none())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn tab_panels(world: &World, group: Entity) -> Vec<Entity> {
+    world
+        .get::<Children>(group)
+        .map(|children| {
+            children
+                .iter()
+                .filter(|&c| world.get::<DockPanel>(c).is_some())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Order an area's children as `slots` with one divider between each pair, reusing
+/// the dividers it already has.
+fn normalize_area(world: &mut World, area: Entity, slots: Vec<Entity>) {
+    let mut handles: Vec<Entity> = world
+        .get::<Children>(area)
+        .map(|children| {
+            children
+                .iter()
+                .filter(|&c| world.get::<PanelHandle>(c).is_some())
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut order = Vec::with_capacity(slots.len() * 2);
+    for (index, &slot) in slots.iter().enumerate() {
+        if index > 0 {
+            let handle = handles
+                .pop()
+                .unwrap_or_else(|| world.spawn(split_panel::panel_handle()).id());
+            order.push(handle);
+        }
+        order.push(slot);
+    }
+    for handle in handles {
+        world.entity_mut(handle).despawn();
+    }
+    world.entity_mut(area).replace_children(&order);
+    // Resize the remaining panels to share the area
+    for slot in slots {
+        if let Some(mut panel) = world.get_mut::<Panel>(slot) {
+            panel.set_changed();
+        }
+    }
+}
+
+/// Prepare an entity to fill a split of an area.
+fn make_slot(world: &mut World, entity: Entity, ratio: f32) {
+    let mut entity = world.entity_mut(entity);
+    entity.insert(Panel { ratio });
+    if let Some(mut node) = entity.get_mut::<Node>() {
+        node.width = Val::Percent(100.0);
+        node.height = Val::Percent(100.0);
+        node.display = Display::Flex;
+    }
+}
+
+/// Prepare a panel to fill a tab group or floating window.
+fn make_inner(world: &mut World, entity: Entity) {
+    let mut entity = world.entity_mut(entity);
+    entity.remove::<Panel>();
+    if let Some(mut node) = entity.get_mut::<Node>() {
+        node.width = Val::Percent(100.0);
+        node.height = Val::Auto;
+        node.flex_grow = 1.0;
+        node.min_height = Val::Px(0.0);
+        node.display = Display::Flex;
+    }
+}
+
+fn spawn_tab_group(world: &mut World, ratio: f32) -> Entity {
+    world
+        .spawn((
+            DockTabs { active: 0 },
+            EditorEntity,
+            Panel { ratio },
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            },
+            BackgroundColor(tokens::PANEL_BG),
+            children![(
+                DockTabBar,
+                EditorEntity,
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    width: Val::Percent(100.0),
+                    height: Val::Px(tokens::ROW_HEIGHT),
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+                BackgroundColor(tokens::TOOLBAR_BG),
+            )],
+        ))
+        .id()
+}
+
+fn rebuild_tab_bar(world: &mut World, group: Entity) {
+    let panels = tab_panels(world, group);
+    let Some(bar) = world
+        .get::<Children>(group)
+        .and_then(|c| c.iter().find(|&c| world.get::<DockTabBar>(c).is_some()))
+    else {
+        return;
+    };
+    world.entity_mut(bar).despawn_related::<Children>();
+    for (index, panel) in panels.into_iter().enumerate() {
+        let title = world
+            .get::<DockPanel>(panel)
+            .map(|p| p.title.clone())
+            .unwrap_or_default();
+        world.spawn((
+            DockTab { group, index },
+            EditorEntity,
+            Node {
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Val::Px(tokens::SPACING_MD)),
+                ..Default::default()
+            },
+            BackgroundColor(tokens::TOOLBAR_BG),
+            ChildOf(bar),
+            children![(
+                Text::new(title),
+                TextFont {
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                ThemedText,
+                Pickable::IGNORE,
+            )],
+        ));
+    }
+    if let Some(mut tabs) = world.get_mut::<DockTabs>(group) {
+        tabs.set_changed();
+    }
+}
+
+fn spawn_floating(world: &mut World, panel: Entity, position: Vec2, size: Vec2) {
+    let Some(root) = world
+        .query_filtered::<Entity, With<DockRoot>>()
+        .iter(world)
+        .next()
+    else {
+        return;
+    };
+    let window = world
+        .spawn((
+            FloatingPanel,
+            EditorEntity,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(position.x),
+                top: Val::Px(position.y),
+                width: Val::Px(size.x),
+                height: Val::Px(size.y),
+                flex_direction: FlexDirection::Column,
+                border: UiRect::all(Val::Px(1.0)),
+                ..Default::default()
+            },
+            BorderColor::all(tokens::BORDER_STRONG),
+            BackgroundColor(tokens::PANEL_BG),
+            ZIndex(10),
+            ChildOf(root),
+        ))
+        .id();
+    make_inner(world, panel);
+    world.entity_mut(panel).insert(ChildOf(window));
+}
+
+/// Take a panel out of wherever it's docked. A tab group left with one panel is
+/// replaced by it, which is returned as `(group, remaining panel)`.
+fn undock(world: &mut World, panel: Entity) -> Option<(Entity, Entity)> {
+    let parent = world.get::<ChildOf>(panel)?.parent();
+    if world.get::<DockTabs>(parent).is_some() {
+        world.entity_mut(panel).remove::<ChildOf>();
+        let remaining = tab_panels(world, parent);
+        if let [last] = remaining[..] {
+            let ratio = world.get::<Panel>(parent).map_or(1.0, |p| p.ratio);
+            make_slot(world, last, ratio);
+            if let Some(area) = world.get::<ChildOf>(parent).map(ChildOf::parent) {
+                let slots = area_slots(world, area)
+                    .into_iter()
+                    .map(|s| if s == parent { last } else { s })
+                    .collect();
+                normalize_area(world, area, slots);
+            }
+            world.entity_mut(parent).despawn();
+            return Some((parent, last));
+        }
+        if let Some(mut tabs) = world.get_mut::<DockTabs>(parent) {
+            tabs.active = tabs.active.min(remaining.len().saturating_sub(1));
+        }
+        rebuild_tab_bar(world, parent);
+    } else if world.get::<DockArea>(parent).is_some() {
+        let slots = area_slots(world, parent)
+            .into_iter()
+            .filter(|&s| s != panel)
+            .collect();
+        world.entity_mut(panel).remove::<ChildOf>();
+        normalize_area(world, parent, slots);
+    } else if world.get::<FloatingPanel>(parent).is_some() {
+        world.entity_mut(panel).remove::<ChildOf>();
+        world.entity_mut(parent).despawn();
+    }
+    None
+}
+
+fn move_panel(world: &mut World, panel: Entity, target: DropTarget) {
+    let parent = world.get::<ChildOf>(panel).map(ChildOf::parent);
+    match target {
+        // Already where it would land
+        DropTarget::Tab { slot } if parent == Some(slot) => return,
+        DropTarget::Area {
+            before: Some(before),
+            ..
+        } if before == panel => return,
+        _ => {}
+    }
+
+    let replaced = undock(world, panel);
+    let remap = |entity: Entity| match replaced {
+        Some((group, last)) if group == entity => last,
+        _ => entity,
+    };
+
+    match target {
+        DropTarget::Area { area, before } => {
+            let mut slots = area_slots(world, area);
+            let index = before
+                .map(remap)
+                .and_then(|b| slots.iter().position(|&s| s == b))
+                .unwrap_or(slots.len());
+            let ratio = if slots.is_empty() {
+                1.0
+            } else {
+                slots
+                    .iter()
+                    .filter_map(|&s| world.get::<Panel>(s).map(|p| p.ratio))
+                    .sum::<f32>()
+                    / slots.len() as f32
+            };
+            make_slot(world, panel, ratio);
+            slots.insert(index, panel);
+            normalize_area(world, area, slots);
+        }
+        DropTarget::Tab { slot } => {
+            let slot = remap(slot);
+            let group = if world.get::<DockTabs>(slot).is_some() {
+                slot
+            } else {
+                let Some(area) = world.get::<ChildOf>(slot).map(ChildOf::parent) else {
+                    return;
+                };
+                let ratio = world.get::<Panel>(slot).map_or(1.0, |p| p.ratio);
+                let group = spawn_tab_group(world, ratio);
+                let slots = area_slots(world, area)
+                    .into_iter()
+                    .map(|s| if s == slot { group } else { s })
+                    .collect();
+                make_inner(world, slot);
+                world.entity_mut(slot).insert(ChildOf(group));
+                normalize_area(world, area, slots);
+                group
+            };
+            make_inner(world, panel);
+            world.entity_mut(panel).insert(ChildOf(group));
+            let count = tab_panels(world, group).len();
+            if let Some(mut tabs) = world.get_mut::<DockTabs>(group) {
+                tabs.active = count - 1;
+            }
+            rebuild_tab_bar(world, group);
+        }
+        DropTarget::Float { position } => spawn_floating(world, panel, position, FLOAT_SIZE),
+    }
+}
+
+/// Move the panel with this id into its own OS window.
+pub fn open_in_window(world: &mut World, id: &str) {
+    let Some((panel, title)) = world
+        .query::<(Entity, &DockPanel)>()
+        .iter(world)
+        .find(|(_, p)| p.id == id)
+        .map(|(e, p)| (e, p.title.clone()))
+    else {
+        return;
+    };
+    if world
+        .query::<&PanelWindow>()
+        .iter(world)
+        .any(|w| w.panel == panel)
+    {
+        return;
+    }
+    undock(world, panel);
+    let secondary = spawn_secondary_window(world, &title, WINDOW_SIZE);
+    make_inner(world, panel);
+    world.entity_mut(panel).insert(ChildOf(secondary.root));
+    world
+        .entity_mut(secondary.window)
+        .insert(PanelWindow { panel, secondary });
+    save_layout(world);
+}
+
+/// Closing a panel window docks the panel back into its home area.
+fn close_panel_windows(
+    mut commands: Commands,
+    mut requests: MessageReader<WindowCloseRequested>,
+    windows: Query<&PanelWindow>,
+) {
+    for request in requests.read() {
+        let Ok(panel_window) = windows.get(request.window) else {
+            continue;
+        };
+        let panel = panel_window.panel;
+        let secondary = panel_window.secondary;
+        commands.queue(move |world: &mut World| {
+            let home = world.get::<DockPanel>(panel).map(|p| p.home);
+            let area = world
+                .query::<(Entity, &DockArea)>()
+                .iter(world)
+                .find(|(_, a)| Some(**a) == home)
+                .map(|(e, _)| e);
+            world.entity_mut(panel).remove::<ChildOf>();
+            if let Some(area) = area {
+                move_panel(world, panel, DropTarget::Area { area, before: None });
+            }
+            secondary.despawn(world);
+            save_layout(world);
+        });
+    }
+}
+
+fn node_rect(world: &World, entity: Entity) -> Option<Rect> {
+    let computed = world.get::<ComputedNode>(entity)?;
+    let transform = world.get::<UiGlobalTransform>(entity)?;
+    let scale = computed.inverse_scale_factor();
+    Some(Rect::from_center_size(
+        transform.translation * scale,
+        computed.size() * scale,
+    ))
+}
+
+/// Where a panel dropped at `position` would go, and the region to highlight.
+fn find_drop_target(
+    world: &mut World,
+    dragged: Entity,
+    position: Vec2,
+) -> Option<(DropTarget, Rect)> {
+    let areas: Vec<(Entity, FlexDirection)> = world
+        .query_filtered::<(Entity, &Node), With<DockArea>>()
+        .iter(world)
+        .map(|(e, n)| (e, n.flex_direction))
+        .collect();
+    for (area, direction) in areas {
+        let Some(area_rect) = node_rect(world, area) else {
+            continue;
+        };
+        if !area_rect.contains(position) {
+            continue;
+        }
+        let slots = area_slots(world, area);
+        if slots.is_empty() {
+            return Some((DropTarget::Area { area, before: None }, area_rect));
+        }
+        let horizontal = matches!(direction, FlexDirection::Row | FlexDirection::RowReverse);
+        for (index, &slot) in slots.iter().enumerate() {
+            let Some(rect) = node_rect(world, slot) else {
+                continue;
+            };
+            if !rect.contains(position) {
+                continue;
+            }
+            if slot == dragged {
+                return None;
+            }
+            let t = if horizontal {
+                (position.x - rect.min.x) / rect.width()
+            } else {
+                (position.y - rect.min.y) / rect.height()
+            };
+            let part = |from: f32, to: f32| {
+                if horizontal {
+                    Rect::new(
+                        rect.min.x + rect.width() * from,
+                        rect.min.y,
+                        rect.min.x + rect.width() * to,
+                        rect.max.y,
+                    )
+                } else {
+                    Rect::new(
+                        rect.min.x,
+                        rect.min.y + rect.height() * from,
+                        rect.max.x,
+                        rect.min.y + rect.height() * to,
+                    )
+                }
+            };
+            return Some(if t < EDGE_FRACTION {
+                (
+                    DropTarget::Area {
+                        area,
+                        before: Some(slot),
+                    },
+                    part(0.0, EDGE_FRACTION),
+                )
+            } else if t > 1.0 - EDGE_FRACTION {
+                (
+                    DropTarget::Area {
+                        area,
+                        before: slots.get(index + 1).copied(),
+                    },
+                    part(1.0 - EDGE_FRACTION, 1.0),
+                )
+            } else {
+                (DropTarget::Tab { slot }, rect)
+            });
+        }
+        return None;
+    }
+
+    let viewport = world
+        .query_filtered::<Entity, With<SceneViewport>>()
+        .iter(world)
+        .next()?;
+    let rect = node_rect(world, viewport)?;
+    if !rect.contains(position) {
+        return None;
+    }
+    let corner = position - FLOAT_GRAB;
+    Some((
+        DropTarget::Float { position: corner },
+        Rect::from_corners(corner, corner + FLOAT_SIZE),
+    ))
+}
+
+/// The dock panel a header belongs to.
+fn header_panel(
+    header: Entity,
+    parents: &Query<&ChildOf>,
+    panels: &Query<(), With<DockPanel>>,
+) -> Option<Entity> {
+    std::iter::once(header)
+        .chain(parents.iter_ancestors(header))
+        .find(|&e| panels.contains(e))
+}
+
+fn on_header_drag_start(
+    event: On<Pointer<DragStart>>,
+    mut commands: Commands,
+    headers: Query<(), With<PanelHeader>>,
+    parents: Query<&ChildOf>,
+    panels: Query<(), With<DockPanel>>,
+    windowed: Query<&PanelWindow>,
+    mut drag: ResMut<DockDrag>,
+) {
+    let header = event.event_target();
+    if !headers.contains(header) {
+        return;
+    }
+    let Some(panel) = header_panel(header, &parents, &panels) else {
+        return;
+    };
+    // Drop targets are in the primary window
+    if windowed.iter().any(|w| w.panel == panel) {
+        return;
+    }
+    if let Some(indicator) = drag.indicator.take() {
+        commands.entity(indicator).try_despawn();
+    }
+    drag.panel = Some(panel);
+    drag.indicator = Some(
+        commands
+            .spawn((
+                DockDropIndicator,
+                EditorEntity,
+                Node {
+                    position_type: PositionType::Absolute,
+                    display: Display::None,
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..Default::default()
+                },
+                BackgroundColor(tokens::DROP_TARGET_BG),
+                BorderColor::all(tokens::DROP_TARGET_BORDER),
+                GlobalZIndex(150),
+                Pickable::IGNORE,
+            ))
+            .id(),
+    );
+}
+
+fn on_header_drag(event: On<Pointer<Drag>>, mut commands: Commands, drag: Res<DockDrag>) {
+    let (Some(panel), Some(indicator)) = (drag.panel, drag.indicator) else {
+        return;
+    };
+    let position = event.pointer_location.position;
+    commands.queue(move |world: &mut World| {
+        let target = find_drop_target(world, panel, position);
+        let Some(mut node) = world.get_mut::<Node>(indicator) else {
+            return;
+        };
+        match target {
+            Some((_, rect)) => {
+                node.display = Display::Flex;
+                node.left = Val::Px(rect.min.x);
+                node.top = Val::Px(rect.min.y);
+                node.width = Val::Px(rect.width());
+                node.height = Val::Px(rect.height());
+            }
+            None => node.display = Display::None,
+        }
+    });
+}
+
+fn on_header_drag_end(
+    event: On<Pointer<DragEnd>>,
+    mut commands: Commands,
+    mut drag: ResMut<DockDrag>,
+) {
+    let Some(panel) = drag.panel.take() else {
+        return;
+    };
+    if let Some(indicator) = drag.indicator.take() {
+        commands.entity(indicator).try_despawn();
+    }
+    let position = event.pointer_location.position;
+    commands.queue(move |world: &mut World| {
+        let Some((target, _)) = find_drop_target(world, panel, position) else {
+            return;
+        };
+        move_panel(world, panel, target);
+        save_layout(world);
+    });
+}
+
+fn on_dock_tab_click(
+    event: On<Pointer<Click>>,
+    tabs: Query<&DockTab>,
+    mut groups: Query<&mut DockTabs>,
+) {
+    let Ok(tab) = tabs.get(event.event_target()) else {
+        return;
+    };
+    if let Ok(mut group) = groups.get_mut(tab.group)
+        && group.active != tab.index
+    {
+        group.active = tab.index;
+    }
+}
+
+/// Show only the active panel of each tab group and highlight its tab.
+fn update_tab_groups(
+    groups: Query<(Entity, &DockTabs, &Children), Changed<DockTabs>>,
+    mut nodes: Query<&mut Node, With<DockPanel>>,
+    mut tabs: Query<(&DockTab, &mut BackgroundColor)>,
+) {
+    for (group, dock_tabs, children) in &groups {
+        let mut index = 0;
+        for child in children.iter() {
+            let Ok(mut node) = nodes.get_mut(child) else {
+                continue;
+            };
+            node.display = if index == dock_tabs.active {
+                Display::Flex
+            } else {
+                Display::None
+            };
+            index += 1;
+        }
+        for (tab, mut bg) in &mut tabs {
+            if tab.group == group {
+                bg.0 = if tab.index == dock_tabs.active {
+                    tokens::ACTIVE_BG
+                } else {
+                    tokens::TOOLBAR_BG
+                };
+            }
+        }
+    }
+}
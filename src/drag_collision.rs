@@ -0,0 +1,221 @@
+//! "Collide while dragging": translation drags sweep the dragged entity's bounding box
+//! against the rest of the scene and stop at contacts, sliding along the free axes. Makes
+//! it easy to butt props against walls without them sinking in.
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use jackdaw_jsn::Terrain;
+
+use crate::{
+    EditorEntity,
+    alignment_guides::is_translate_drag_active,
+    brush::{BrushFaceEntity, BrushMeshCache},
+    gizmos::{GizmoDragState, GizmoMode},
+    modal_transform::{ModalTransformState, ViewportDragState},
+    viewport_overlays,
+};
+
+/// Gap below which boxes count as touching rather than overlapping.
+const CONTACT_EPSILON: f32 = 1e-4;
+
+pub struct DragCollisionPlugin;
+
+impl Plugin for DragCollisionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DragCollisionSettings>()
+            .init_resource::<DragCollisionCache>()
+            .add_systems(
+                Update,
+                clear_drag_collision_cache.run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct DragCollisionSettings {
+    pub enabled: bool,
+}
+
+/// Boxes gathered when a drag starts, reused until it ends.
+#[derive(Resource, Default)]
+pub struct DragCollisionCache {
+    entity: Option<Entity>,
+    /// World bounds of the dragged entity at the start of the drag.
+    moving: Option<(Vec3, Vec3)>,
+    obstacles: Vec<(Vec3, Vec3)>,
+}
+
+fn clear_drag_collision_cache(
+    mut cache: ResMut<DragCollisionCache>,
+    gizmo_drag: Res<GizmoDragState>,
+    gizmo_mode: Res<GizmoMode>,
+    modal_state: Res<ModalTransformState>,
+    viewport_drag: Res<ViewportDragState>,
+) {
+    if cache.entity.is_some()
+        && !is_translate_drag_active(&gizmo_drag, &gizmo_mode, &modal_state, &viewport_drag)
+    {
+        *cache = DragCollisionCache::default();
+    }
+}
+
+/// Limits drag offsets so the dragged entity doesn't move into other geometry.
+#[derive(SystemParam)]
+pub struct DragCollision<'w, 's> {
+    settings: Res<'w, DragCollisionSettings>,
+    cache: ResMut<'w, DragCollisionCache>,
+    brushes: Query<'w, 's, (Entity, &'static GlobalTransform, &'static BrushMeshCache)>,
+    mesh_entities: Query<
+        'w,
+        's,
+        (Entity, &'static InheritedVisibility),
+        (
+            With<Mesh3d>,
+            Without<BrushFaceEntity>,
+            Without<EditorEntity>,
+        ),
+    >,
+    mesh_query: Query<'w, 's, (&'static Mesh3d, &'static GlobalTransform)>,
+    children_query: Query<'w, 's, &'static Children>,
+    parents: Query<'w, 's, &'static ChildOf>,
+    excluded: Query<'w, 's, (), Or<(With<EditorEntity>, With<Terrain>)>>,
+    meshes: Res<'w, Assets<Mesh>>,
+}
+
+impl DragCollision<'_, '_> {
+    /// `offset` from the start of the drag, shortened where `entity` would run into
+    /// other geometry. Unchanged while the mode is off or `entity` has no bounds.
+    pub fn limit(&mut self, entity: Entity, offset: Vec3) -> Vec3 {
+        if !self.settings.enabled {
+            return offset;
+        }
+        if self.cache.entity != Some(entity) {
+            self.fill_cache(entity);
+        }
+        let Some((min, max)) = self.cache.moving else {
+            return offset;
+        };
+        sweep_aabb(min, max, offset, &self.cache.obstacles)
+    }
+
+    fn fill_cache(&mut self, entity: Entity) {
+        let moving = self.world_bounds(entity);
+        let mut obstacles = Vec::new();
+        if let Some((min, max)) = moving {
+            let brushes = self
+                .brushes
+                .iter()
+                .filter(|(_, _, cache)| !cache.vertices.is_empty())
+                .map(|(e, global_tf, cache)| {
+                    let points: Vec<Vec3> = cache
+                        .vertices
+                        .iter()
+                        .map(|v| global_tf.transform_point(*v))
+                        .collect();
+                    (e, viewport_overlays::aabb_from_points(&points))
+                });
+            let meshes = self
+                .mesh_entities
+                .iter()
+                .filter(|(_, visibility)| visibility.get())
+                .filter_map(|(e, _)| Some((e, self.own_mesh_bounds(e)?)));
+            for (e, bounds) in brushes.chain(meshes) {
+                // Already overlapping at the start: don't trap the entity inside it
+                if !self.is_obstacle(e, entity) || overlaps(bounds, (min, max)) {
+                    continue;
+                }
+                obstacles.push(bounds);
+            }
+        }
+        *self.cache = DragCollisionCache {
+            entity: Some(entity),
+            moving,
+            obstacles,
+        };
+    }
+
+    /// Skips the dragged entity's own hierarchy, editor helpers and terrain.
+    fn is_obstacle(&self, candidate: Entity, dragged: Entity) -> bool {
+        let mut current = candidate;
+        loop {
+            if current == dragged || self.excluded.contains(current) {
+                return false;
+            }
+            match self.parents.get(current) {
+                Ok(child_of) => current = child_of.parent(),
+                Err(_) => return true,
+            }
+        }
+    }
+
+    fn world_bounds(&self, entity: Entity) -> Option<(Vec3, Vec3)> {
+        let mut points = Vec::new();
+        if let Ok((_, global_tf, cache)) = self.brushes.get(entity) {
+            points.extend(cache.vertices.iter().map(|v| global_tf.transform_point(*v)));
+        }
+        viewport_overlays::collect_descendant_mesh_world_vertices(
+            entity,
+            &self.children_query,
+            &self.mesh_query,
+            &self.meshes,
+            &mut points,
+        );
+        (!points.is_empty()).then(|| viewport_overlays::aabb_from_points(&points))
+    }
+
+    fn own_mesh_bounds(&self, entity: Entity) -> Option<(Vec3, Vec3)> {
+        let (mesh3d, global_tf) = self.mesh_query.get(entity).ok()?;
+        let positions = self
+            .meshes
+            .get(&mesh3d.0)?
+            .attribute(Mesh::ATTRIBUTE_POSITION)?
+            .as_float3()?;
+        let points: Vec<Vec3> = positions
+            .iter()
+            .map(|p| global_tf.transform_point(Vec3::from_array(*p)))
+            .collect();
+        (!points.is_empty()).then(|| viewport_overlays::aabb_from_points(&points))
+    }
+}
+
+fn overlaps((a_min, a_max): (Vec3, Vec3), (b_min, b_max): (Vec3, Vec3)) -> bool {
+    (0..3).all(|axis| {
+        a_max[axis] > b_min[axis] + CONTACT_EPSILON && a_min[axis] < b_max[axis] - CONTACT_EPSILON
+    })
+}
+
+/// Move the box `min..max` by `offset` one axis at a time, stopping each axis at the
+/// first obstacle in the way. Blocked axes stop while the others keep going, so the box
+/// slides along walls and floors.
+pub fn sweep_aabb(min: Vec3, max: Vec3, offset: Vec3, obstacles: &[(Vec3, Vec3)]) -> Vec3 {
+    let mut moved = Vec3::ZERO;
+    for axis in 0..3 {
+        let step = offset[axis];
+        if step == 0.0 {
+            continue;
+        }
+        let (box_min, box_max) = (min + moved, max + moved);
+        let mut allowed = step;
+        for &(obstacle_min, obstacle_max) in obstacles {
+            let in_path = (0..3).filter(|&other| other != axis).all(|other| {
+                box_max[other] > obstacle_min[other] + CONTACT_EPSILON
+                    && box_min[other] < obstacle_max[other] - CONTACT_EPSILON
+            });
+            if !in_path {
+                continue;
+            }
+            if step > 0.0 {
+                let gap = obstacle_min[axis] - box_max[axis];
+                if gap >= -CONTACT_EPSILON {
+                    allowed = allowed.min(gap.max(0.0));
+                }
+            } else {
+                let gap = obstacle_max[axis] - box_min[axis];
+                if gap <= CONTACT_EPSILON {
+                    allowed = allowed.max(gap.min(0.0));
+                }
+            }
+        }
+        moved[axis] = allowed;
+    }
+    moved
+}
@@ -1,18 +1,20 @@
 use bevy::{
+    ecs::entity::EntityHashMap,
     input_focus::InputFocus,
     light::{NotShadowCaster, NotShadowReceiver},
     mesh::{Indices, PrimitiveTopology},
     picking::mesh_picking::ray_cast::{MeshRayCast, MeshRayCastSettings, RayCastVisibility},
     prelude::*,
     ui::UiGlobalTransform,
+    window::PrimaryWindow,
 };
 
 use crate::{
     EditorEntity,
-    brush::{BrushFaceEntity, BrushMaterialPalette},
+    brush::{BrushFaceEntity, BrushMaterialPalette, mesh_csg_brushes},
     commands::{
-        CommandGroup, CommandHistory, DespawnEntity, EditorCommand, snapshot_entity,
-        snapshot_rebuild,
+        CommandGroup, CommandHistory, DespawnEntity, EditorCommand, RespawnedEntities,
+        remap_entity, snapshot_entity, snapshot_rebuild,
     },
     selection::{Selected, Selection},
     snapping::SnapSettings,
@@ -20,9 +22,9 @@ use crate::{
     viewport_util::window_to_viewport_cursor,
 };
 use jackdaw_geometry::{
-    brush_planes_to_world, brushes_intersect, clean_degenerate_faces, compute_brush_geometry,
-    compute_face_tangent_axes, compute_face_uvs, intersect_brushes, subtract_brush,
-    triangulate_face,
+    brush_planes_to_world, brush_volume, brushes_intersect, clean_degenerate_faces,
+    compute_brush_geometry, compute_face_tangent_axes, compute_face_uvs, convex_volume,
+    intersect_brushes, merge_brush_fragments, subtract_brush, triangulate_face,
 };
 use jackdaw_jsn::{Brush, BrushFaceData, BrushPlane};
 
@@ -90,8 +92,7 @@ pub struct CreateBrushCommand {
 impl EditorCommand for CreateBrushCommand {
     fn execute(&self, world: &mut World) {
         // Redo: respawn from snapshot
-        let scene = snapshot_rebuild(&self.scene_snapshot);
-        let _result = scene.write_to_world(world, &mut Default::default());
+        respawn_brush(world, self.entity, &self.scene_snapshot);
     }
 
     fn undo(&self, world: &mut World) {
@@ -103,6 +104,31 @@ impl EditorCommand for CreateBrushCommand {
     fn description(&self) -> &str {
         "Draw brush"
     }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+    }
+
+    fn memory_size(&self) -> usize {
+        std::mem::size_of::<Self>() + crate::commands::scene_memory_size(&self.scene_snapshot)
+    }
+}
+
+/// Write a brush snapshot back into the world. The brush comes back under a new id, so
+/// report it in place of `entity`; the history is patched once it's back.
+fn respawn_brush(world: &mut World, entity: Entity, snapshot: &DynamicScene) {
+    let scene = snapshot_rebuild(snapshot);
+    let mut entity_map = EntityHashMap::default();
+    if scene.write_to_world(world, &mut entity_map).is_err() {
+        return;
+    }
+    // The root is extracted first
+    let root = snapshot.entities.first().map(|e| e.entity);
+    if let Some(&respawned) = root.and_then(|root| entity_map.get(&root))
+        && let Some(mut pending) = world.get_resource_mut::<RespawnedEntities>()
+    {
+        pending.0.insert(entity, respawned);
+    }
 }
 
 #[derive(Default, Reflect, GizmoConfigGroup)]
@@ -230,7 +256,7 @@ fn draw_brush_activate(
 
 fn draw_brush_update(
     mut draw_state: ResMut<DrawBrushState>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
     keyboard: Res<ButtonInput<KeyCode>>,
@@ -368,7 +394,7 @@ fn draw_brush_update(
 fn draw_brush_release(
     mouse: Res<ButtonInput<MouseButton>>,
     mut draw_state: ResMut<DrawBrushState>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
 ) {
@@ -425,7 +451,7 @@ fn draw_brush_release(
 fn draw_brush_confirm(
     mouse: Res<ButtonInput<MouseButton>>,
     mut draw_state: ResMut<DrawBrushState>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
     mut commands: Commands,
@@ -526,7 +552,7 @@ fn draw_brush_cancel(
     keyboard: Res<ButtonInput<KeyCode>>,
     mouse: Res<ButtonInput<MouseButton>>,
     mut draw_state: ResMut<DrawBrushState>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
 ) {
@@ -795,8 +821,7 @@ fn spawn_drawn_brush(active: &ActiveDraw, commands: &mut Commands) {
             scene_snapshot: snapshot,
         };
         let mut history = world.resource_mut::<CommandHistory>();
-        history.undo_stack.push(Box::new(cmd));
-        history.redo_stack.clear();
+        history.push_executed(Box::new(cmd));
     });
 }
 
@@ -941,6 +966,8 @@ fn append_to_brush(active: &ActiveDraw, commands: &mut Commands) {
                     uv_rotation: old_face.uv_rotation,
                     uv_u_axis: old_face.uv_u_axis,
                     uv_v_axis: old_face.uv_v_axis,
+                    surface: old_face.surface.clone(),
+                    smoothing_group: old_face.smoothing_group,
                 }
             } else {
                 // New face from the appended shape — use last-used material
@@ -975,8 +1002,7 @@ fn append_to_brush(active: &ActiveDraw, commands: &mut Commands) {
             label: "Append brush geometry".to_string(),
         };
         let mut history = world.resource_mut::<CommandHistory>();
-        history.undo_stack.push(Box::new(cmd));
-        history.redo_stack.clear();
+        history.push_executed(Box::new(cmd));
     });
 }
 
@@ -1207,8 +1233,7 @@ fn spawn_polygon_brush(active: &ActiveDraw, commands: &mut Commands) {
             scene_snapshot: snapshot,
         };
         let mut history = world.resource_mut::<CommandHistory>();
-        history.undo_stack.push(Box::new(cmd));
-        history.redo_stack.clear();
+        history.push_executed(Box::new(cmd));
     });
 }
 
@@ -1400,7 +1425,8 @@ fn manage_draw_preview_mesh(
                 continue;
             }
 
-            let kept_fragments = subtract_brush(&world_target, &cutter_planes);
+            let kept_fragments =
+                merge_brush_fragments(subtract_brush(&world_target, &cutter_planes));
 
             // Only hide the original brush if subtraction produced valid fragments;
             // otherwise the cutter is degenerate (e.g. inverted depth) and we keep
@@ -1659,39 +1685,7 @@ fn subtract_drawn_brush(active: &ActiveDraw, commands: &mut Commands) {
 
             // Perform subtraction
             let raw_fragments = subtract_brush(&world_target, &cutter_planes);
-
-            let mut fragment_data: Vec<(Brush, Transform)> = Vec::new();
-            for fragment_faces in &raw_fragments {
-                // Compute vertices to find centroid (world space)
-                let (world_verts, _) = compute_brush_geometry(fragment_faces);
-                if world_verts.len() < 4 {
-                    continue;
-                }
-                let centroid: Vec3 = world_verts.iter().sum::<Vec3>() / world_verts.len() as f32;
-
-                // Convert to local space around centroid
-                let local_faces: Vec<BrushFaceData> = fragment_faces
-                    .iter()
-                    .map(|f| BrushFaceData {
-                        plane: BrushPlane {
-                            normal: f.plane.normal,
-                            distance: f.plane.distance - f.plane.normal.dot(centroid),
-                        },
-                        ..f.clone()
-                    })
-                    .collect();
-
-                // Clean degenerate faces
-                let clean = clean_degenerate_faces(&local_faces);
-                if clean.len() < 4 {
-                    continue;
-                }
-
-                fragment_data.push((
-                    Brush { faces: clean },
-                    Transform::from_translation(centroid),
-                ));
-            }
+            let fragment_data = fragments_to_brushes(&merge_brush_fragments(raw_fragments));
 
             results.push(SubtractionResult {
                 original_entity: *entity,
@@ -1757,8 +1751,7 @@ fn subtract_drawn_brush(active: &ActiveDraw, commands: &mut Commands) {
             fragments: fragment_snapshots,
         };
         let mut history = world.resource_mut::<CommandHistory>();
-        history.undo_stack.push(Box::new(cmd));
-        history.redo_stack.clear();
+        history.push_executed(Box::new(cmd));
     });
 }
 
@@ -1787,9 +1780,8 @@ impl EditorCommand for SubtractBrushCommand {
                 e.despawn();
             }
         }
-        for (_, snapshot) in &self.fragments {
-            let scene = snapshot_rebuild(snapshot);
-            let _ = scene.write_to_world(world, &mut Default::default());
+        for (entity, snapshot) in &self.fragments {
+            respawn_brush(world, *entity, snapshot);
         }
     }
 
@@ -1810,15 +1802,30 @@ impl EditorCommand for SubtractBrushCommand {
                 e.despawn();
             }
         }
-        for (_, snapshot) in &self.originals {
-            let scene = snapshot_rebuild(snapshot);
-            let _ = scene.write_to_world(world, &mut Default::default());
+        for (entity, snapshot) in &self.originals {
+            respawn_brush(world, *entity, snapshot);
         }
     }
 
     fn description(&self) -> &str {
         "Subtract brush"
     }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        for (entity, _) in self.originals.iter_mut().chain(&mut self.fragments) {
+            remap_entity(entity, map);
+        }
+    }
+
+    fn memory_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self
+                .originals
+                .iter()
+                .chain(&self.fragments)
+                .map(|(_, scene)| crate::commands::scene_memory_size(scene))
+                .sum::<usize>()
+    }
 }
 
 fn join_selected_brushes(
@@ -1835,11 +1842,88 @@ fn join_selected_brushes(
         return;
     }
 
-    commands.queue(join_selected_brushes_impl);
+    // Shift+J merges even when the brushes' union isn't convex
+    let force = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    commands.queue(move |world: &mut World| {
+        crate::csg_preview::begin_csg_preview(world, crate::csg_preview::CsgOp::Merge { force });
+    });
 }
 
 /// Core logic for Join (convex merge) — callable from both keyboard shortcut and menu.
+/// Refuses when the union of the selected brushes isn't convex, since the merged
+/// brush would then fill space none of them covered.
 pub fn join_selected_brushes_impl(world: &mut World) {
+    join_brushes(world, false);
+}
+
+/// Join the selected brushes into their convex hull even if their union isn't convex.
+pub fn force_join_selected_brushes_impl(world: &mut World) {
+    join_brushes(world, true);
+}
+
+/// World-space hull the selected brushes would merge into, as vertices and face
+/// polygons, for previewing a join.
+pub(crate) fn join_hull_preview(world: &mut World) -> Option<(Vec<Vec3>, Vec<Vec<usize>>)> {
+    use avian3d::parry::math::Point as ParryPoint;
+    use avian3d::parry::transformation::convex_hull;
+
+    let selected: Vec<Entity> = world.resource::<Selection>().entities.clone();
+    let points: Vec<ParryPoint<f32>> = selected
+        .iter()
+        .filter_map(|&e| Some((world.get::<Brush>(e)?, world.get::<GlobalTransform>(e)?)))
+        .flat_map(|(brush, global)| {
+            compute_brush_geometry(&brush.faces)
+                .0
+                .into_iter()
+                .map(|v| global.transform_point(v))
+        })
+        .map(|v| ParryPoint::new(v.x, v.y, v.z))
+        .collect();
+    if points.len() < 4 {
+        return None;
+    }
+    let (hull_verts, hull_tris) = convex_hull(&points);
+    if hull_verts.len() < 4 || hull_tris.is_empty() {
+        return None;
+    }
+    let positions: Vec<Vec3> = hull_verts
+        .iter()
+        .map(|p| Vec3::new(p.x, p.y, p.z))
+        .collect();
+    let polygons = crate::brush::merge_hull_triangles(&positions, &hull_tris)
+        .into_iter()
+        .map(|face| face.vertex_indices)
+        .collect();
+    Some((positions, polygons))
+}
+
+/// Whether the union of `brushes` fills their convex hull, compared by volume.
+/// Overlaps are subtracted pairwise, so three brushes sharing one region read as
+/// non-convex; forcing the merge covers that case.
+fn union_is_convex(world: &World, brushes: &[Entity], hull_volume: f32) -> bool {
+    let world_faces: Vec<Vec<BrushFaceData>> = brushes
+        .iter()
+        .filter_map(|&entity| {
+            let brush = world.get::<Brush>(entity)?;
+            let (_, rotation, translation) = world
+                .get::<GlobalTransform>(entity)?
+                .to_scale_rotation_translation();
+            Some(brush_planes_to_world(&brush.faces, rotation, translation))
+        })
+        .collect();
+
+    let mut union_volume: f32 = world_faces.iter().map(|faces| brush_volume(faces)).sum();
+    for (i, a) in world_faces.iter().enumerate() {
+        for b in &world_faces[i + 1..] {
+            if let Some(overlap) = intersect_brushes(&[a.as_slice(), b.as_slice()]) {
+                union_volume -= brush_volume(&overlap);
+            }
+        }
+    }
+    hull_volume - union_volume <= hull_volume * 1e-3 + jackdaw_geometry::EPSILON
+}
+
+fn join_brushes(world: &mut World, force: bool) {
     let candidates: Vec<Entity> = world.resource::<Selection>().entities.clone();
     let mut brush_query = world.query::<&Brush>();
     let selected_brushes: Vec<Entity> = candidates
@@ -1912,6 +1996,19 @@ pub fn join_selected_brushes_impl(world: &mut World) {
             return;
         }
 
+        if !force
+            && !union_is_convex(
+                world,
+                &selected_brushes,
+                convex_volume(&hull_positions, &hull_tris),
+            )
+        {
+            warn!(
+                "Join brushes: the selected brushes don't form a convex shape (Shift+J or Merge Brushes (Force) merges anyway)"
+            );
+            return;
+        }
+
         // Build new face data, matching old primary faces where possible
         let old_face_polygons = compute_brush_geometry(&old_primary_brush.faces).1;
         let last_mat = world
@@ -1980,6 +2077,8 @@ pub fn join_selected_brushes_impl(world: &mut World) {
                     uv_rotation: old_face.uv_rotation,
                     uv_u_axis: old_face.uv_u_axis,
                     uv_v_axis: old_face.uv_v_axis,
+                    surface: old_face.surface.clone(),
+                    smoothing_group: old_face.smoothing_group,
                 }
             } else {
                 let (u, v) = compute_face_tangent_axes(hull_face.normal);
@@ -2042,11 +2141,10 @@ pub fn join_selected_brushes_impl(world: &mut World) {
 
         // Push grouped undo command
         let mut history = world.resource_mut::<CommandHistory>();
-        history.undo_stack.push(Box::new(CommandGroup {
+        history.push_executed(Box::new(CommandGroup {
             commands: undo_commands,
             label: "Join brushes".to_string(),
         }));
-        history.redo_stack.clear();
     }
 }
 
@@ -2071,11 +2169,97 @@ fn csg_subtract_selected(
         return;
     }
 
-    commands.queue(csg_subtract_selected_impl);
+    commands.queue(|world: &mut World| {
+        crate::csg_preview::begin_csg_preview(world, crate::csg_preview::CsgOp::Subtract);
+    });
 }
 
 /// Core logic for CSG Subtract — selected brushes are cutters, non-selected are targets.
 pub fn csg_subtract_selected_impl(world: &mut World) {
+    struct SubtractionResult {
+        original_entity: Entity,
+        fragments: Vec<(Brush, Transform)>,
+    }
+
+    let selected: Vec<Entity> = world.resource::<Selection>().entities.clone();
+    let mesh_cutters = selected
+        .iter()
+        .flat_map(|&entity| mesh_csg_brushes(world, entity))
+        .collect();
+    let results: Vec<SubtractionResult> = csg_subtract_fragments(world, mesh_cutters)
+        .into_iter()
+        .map(|(original_entity, fragments)| SubtractionResult {
+            original_entity,
+            fragments: fragments_to_brushes(&fragments),
+        })
+        .collect();
+    if results.is_empty() {
+        return;
+    }
+
+    // Snapshot originals
+    let mut original_snapshots: Vec<(Entity, DynamicScene)> = Vec::new();
+    for result in &results {
+        let snapshot = DynamicSceneBuilder::from_world(world)
+            .extract_entities(std::iter::once(result.original_entity))
+            .build();
+        original_snapshots.push((result.original_entity, snapshot));
+    }
+
+    // Clean up selection: remove targets about to be despawned
+    {
+        let despawning: Vec<Entity> = original_snapshots.iter().map(|(e, _)| *e).collect();
+        let mut selection = world.resource_mut::<Selection>();
+        selection.entities.retain(|e| !despawning.contains(e));
+    }
+    for (entity, _) in &original_snapshots {
+        if let Ok(mut e) = world.get_entity_mut(*entity) {
+            e.remove::<Selected>();
+        }
+    }
+
+    // Despawn originals
+    for (entity, _) in &original_snapshots {
+        if let Ok(e) = world.get_entity_mut(*entity) {
+            e.despawn();
+        }
+    }
+
+    // Spawn fragments
+    let mut fragment_snapshots: Vec<(Entity, DynamicScene)> = Vec::new();
+    for result in &results {
+        for (brush, transform) in &result.fragments {
+            let entity = world
+                .spawn((
+                    Name::new("Brush"),
+                    brush.clone(),
+                    *transform,
+                    Visibility::default(),
+                ))
+                .id();
+            let snapshot = DynamicSceneBuilder::from_world(world)
+                .extract_entities(std::iter::once(entity))
+                .build();
+            fragment_snapshots.push((entity, snapshot));
+        }
+    }
+
+    // Push undo command
+    let cmd = SubtractBrushCommand {
+        originals: original_snapshots,
+        fragments: fragment_snapshots,
+    };
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(cmd));
+}
+
+/// World-space fragments left of each brush the selected cutters would cut, without
+/// changing anything. `mesh_cutters` are the convex pieces of the selected closed
+/// meshes, which cut as well.
+pub(crate) fn csg_subtract_fragments(
+    world: &mut World,
+    mesh_cutters: Vec<Vec<BrushFaceData>>,
+) -> Vec<(Entity, Vec<Vec<BrushFaceData>>)> {
     let selection = world.resource::<Selection>();
     let selected_set: Vec<Entity> = selection.entities.clone();
 
@@ -2095,26 +2279,22 @@ pub fn csg_subtract_selected_impl(world: &mut World) {
         .filter(|(e, _, _)| !selected_set.contains(e))
         .collect();
 
-    if cutters.is_empty() || targets.is_empty() {
-        return;
-    }
-
     // Transform cutter faces to world space
-    let cutter_world_faces: Vec<Vec<BrushFaceData>> = cutters
+    let mut cutter_world_faces: Vec<Vec<BrushFaceData>> = cutters
         .iter()
         .map(|(_, brush, gt)| {
             let (_, rotation, translation) = gt.to_scale_rotation_translation();
             brush_planes_to_world(&brush.faces, rotation, translation)
         })
         .collect();
+    cutter_world_faces.extend(mesh_cutters);
 
-    // For each target, check intersection with each cutter and subtract
-    struct SubtractionResult {
-        original_entity: Entity,
-        fragments: Vec<(Brush, Transform)>,
+    if cutter_world_faces.is_empty() || targets.is_empty() {
+        return Vec::new();
     }
 
-    let mut results: Vec<SubtractionResult> = Vec::new();
+    // For each target, check intersection with each cutter and subtract
+    let mut results = Vec::new();
 
     for (entity, brush, global_transform) in &targets {
         let entity = *entity;
@@ -2154,102 +2334,126 @@ pub fn csg_subtract_selected_impl(world: &mut World) {
             }
         }
 
-        // Convert world-space fragments to local-space brushes
-        let mut fragment_data: Vec<(Brush, Transform)> = Vec::new();
-        for fragment_faces in &current_fragments {
-            let (world_verts, _) = compute_brush_geometry(fragment_faces);
-            if world_verts.len() < 4 {
-                continue;
-            }
-            let centroid: Vec3 = world_verts.iter().sum::<Vec3>() / world_verts.len() as f32;
+        results.push((entity, merge_brush_fragments(current_fragments)));
+    }
+    results
+}
 
-            let local_faces: Vec<BrushFaceData> = fragment_faces
-                .iter()
-                .map(|f| BrushFaceData {
-                    plane: BrushPlane {
-                        normal: f.plane.normal,
-                        distance: f.plane.distance - f.plane.normal.dot(centroid),
-                    },
-                    ..f.clone()
-                })
-                .collect();
+/// Convert world-space fragments to brushes centred on their own vertices.
+fn fragments_to_brushes(fragments: &[Vec<BrushFaceData>]) -> Vec<(Brush, Transform)> {
+    let mut fragment_data: Vec<(Brush, Transform)> = Vec::new();
+    for fragment_faces in fragments {
+        let (world_verts, _) = compute_brush_geometry(fragment_faces);
+        if world_verts.len() < 4 {
+            continue;
+        }
+        let centroid: Vec3 = world_verts.iter().sum::<Vec3>() / world_verts.len() as f32;
 
-            let clean = clean_degenerate_faces(&local_faces);
-            if clean.len() < 4 {
-                continue;
-            }
+        let local_faces: Vec<BrushFaceData> = fragment_faces
+            .iter()
+            .map(|f| BrushFaceData {
+                plane: BrushPlane {
+                    normal: f.plane.normal,
+                    distance: f.plane.distance - f.plane.normal.dot(centroid),
+                },
+                ..f.clone()
+            })
+            .collect();
 
-            fragment_data.push((
-                Brush { faces: clean },
-                Transform::from_translation(centroid),
-            ));
+        let clean = clean_degenerate_faces(&local_faces);
+        if clean.len() < 4 {
+            continue;
         }
 
-        results.push(SubtractionResult {
-            original_entity: entity,
-            fragments: fragment_data,
-        });
+        fragment_data.push((
+            Brush { faces: clean },
+            Transform::from_translation(centroid),
+        ));
     }
+    fragment_data
+}
 
-    if results.is_empty() {
+/// CSG Carve Mesh — the selected brushes are subtracted from the selected closed meshes,
+/// e.g. to cut a doorway into an imported wall. Each mesh is replaced by convex brushes
+/// of what remains.
+pub fn csg_carve_selected_meshes_impl(world: &mut World) {
+    let selected: Vec<Entity> = world.resource::<Selection>().entities.clone();
+    let cutters: Vec<Vec<BrushFaceData>> = selected
+        .iter()
+        .filter_map(|&e| {
+            let brush = world.get::<Brush>(e)?;
+            let (_, rotation, translation) = world
+                .get::<GlobalTransform>(e)?
+                .to_scale_rotation_translation();
+            Some(brush_planes_to_world(&brush.faces, rotation, translation))
+        })
+        .collect();
+    if cutters.is_empty() {
         return;
     }
 
-    // Snapshot originals
-    let mut original_snapshots: Vec<(Entity, DynamicScene)> = Vec::new();
-    for result in &results {
-        let snapshot = DynamicSceneBuilder::from_world(world)
-            .extract_entities(std::iter::once(result.original_entity))
-            .build();
-        original_snapshots.push((result.original_entity, snapshot));
+    let mut originals: Vec<(Entity, DynamicScene)> = Vec::new();
+    let mut fragments: Vec<(Brush, Transform)> = Vec::new();
+    for &entity in &selected {
+        let pieces = mesh_csg_brushes(world, entity);
+        if pieces.is_empty() {
+            continue;
+        }
+        let mut current_fragments = pieces;
+        for cutter_faces in &cutters {
+            let mut next_fragments = Vec::new();
+            for fragment in &current_fragments {
+                if brushes_intersect(fragment, cutter_faces) {
+                    next_fragments.extend(subtract_brush(fragment, cutter_faces));
+                } else {
+                    next_fragments.push(fragment.clone());
+                }
+            }
+            current_fragments = next_fragments;
+        }
+        originals.push((entity, snapshot_entity(world, entity)));
+        fragments.extend(fragments_to_brushes(&merge_brush_fragments(
+            current_fragments,
+        )));
+    }
+    if originals.is_empty() {
+        world
+            .resource_mut::<crate::status_bar::StatusHints>()
+            .prompt("Select a closed mesh and the brushes to carve it with");
+        return;
     }
 
-    // Clean up selection: remove targets about to be despawned
     {
-        let despawning: Vec<Entity> = original_snapshots.iter().map(|(e, _)| *e).collect();
+        let despawning: Vec<Entity> = originals.iter().map(|(e, _)| *e).collect();
         let mut selection = world.resource_mut::<Selection>();
         selection.entities.retain(|e| !despawning.contains(e));
     }
-    for (entity, _) in &original_snapshots {
+    for (entity, _) in &originals {
         if let Ok(mut e) = world.get_entity_mut(*entity) {
             e.remove::<Selected>();
         }
-    }
-
-    // Despawn originals
-    for (entity, _) in &original_snapshots {
         if let Ok(e) = world.get_entity_mut(*entity) {
             e.despawn();
         }
     }
 
-    // Spawn fragments
     let mut fragment_snapshots: Vec<(Entity, DynamicScene)> = Vec::new();
-    for result in &results {
-        for (brush, transform) in &result.fragments {
-            let entity = world
-                .spawn((
-                    Name::new("Brush"),
-                    brush.clone(),
-                    *transform,
-                    Visibility::default(),
-                ))
-                .id();
-            let snapshot = DynamicSceneBuilder::from_world(world)
-                .extract_entities(std::iter::once(entity))
-                .build();
-            fragment_snapshots.push((entity, snapshot));
-        }
+    for (brush, transform) in fragments {
+        let entity = world
+            .spawn((Name::new("Brush"), brush, transform, Visibility::default()))
+            .id();
+        let snapshot = DynamicSceneBuilder::from_world(world)
+            .extract_entities(std::iter::once(entity))
+            .build();
+        fragment_snapshots.push((entity, snapshot));
     }
 
-    // Push undo command
     let cmd = SubtractBrushCommand {
-        originals: original_snapshots,
+        originals,
         fragments: fragment_snapshots,
     };
     let mut history = world.resource_mut::<CommandHistory>();
-    history.undo_stack.push(Box::new(cmd));
-    history.redo_stack.clear();
+    history.push_executed(Box::new(cmd));
 }
 
 fn csg_intersect_selected(
@@ -2388,6 +2592,5 @@ pub fn csg_intersect_selected_impl(world: &mut World) {
         fragments: fragment_snapshots,
     };
     let mut history = world.resource_mut::<CommandHistory>();
-    history.undo_stack.push(Box::new(cmd));
-    history.redo_stack.clear();
+    history.push_executed(Box::new(cmd));
 }
@@ -0,0 +1,468 @@
+//! Lightweight in-game editor overlay.
+//!
+//! [`EmbeddedEditorPlugin`] can be added to a shipped game (typically behind a
+//! `debug` feature). It does not depend on the full editor layout or
+//! [`AppState`](crate::AppState); pressing the toggle key opens a hierarchy and
+//! inspector overlay on top of the game viewport. Transform tweaks made there
+//! can be exported as a `.jsn` patch and applied to the source scene later via
+//! File > Apply Patch in the editor.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use jackdaw_jsn::{
+    StableId,
+    format::{JsnHeader, JsnPatch, JsnPatchEntry, JsnTransform, JsnVisibility},
+};
+
+use crate::EditorEntity;
+
+const OVERLAY_BG: Color = Color::srgba(0.08, 0.08, 0.1, 0.88);
+const ROW_BG: Color = Color::NONE;
+const ROW_HOVER_BG: Color = Color::srgba(1.0, 1.0, 1.0, 0.08);
+const ROW_SELECTED_BG: Color = Color::srgba(0.25, 0.45, 0.85, 0.6);
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const DIM_TEXT_COLOR: Color = Color::srgb(0.6, 0.6, 0.65);
+const FONT_SIZE: f32 = 13.0;
+
+pub struct EmbeddedEditorPlugin {
+    /// Key that opens and closes the overlay.
+    pub toggle_key: KeyCode,
+    /// File the patch is written to when exporting (Ctrl+E while the overlay is open).
+    pub patch_path: PathBuf,
+}
+
+impl Default for EmbeddedEditorPlugin {
+    fn default() -> Self {
+        Self {
+            toggle_key: KeyCode::F12,
+            patch_path: PathBuf::from("editor_patch.jsn"),
+        }
+    }
+}
+
+impl Plugin for EmbeddedEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(EmbeddedEditorConfig {
+            toggle_key: self.toggle_key,
+            patch_path: self.patch_path.clone(),
+        })
+        .init_resource::<EmbeddedEditorState>()
+        .add_systems(
+            Update,
+            (
+                toggle_overlay,
+                (
+                    refresh_entity_list,
+                    handle_row_interaction,
+                    tweak_selected_transform,
+                    toggle_selected_visibility,
+                    update_inspector_text,
+                    export_patch_key,
+                )
+                    .chain()
+                    .run_if(|state: Res<EmbeddedEditorState>| state.open),
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Resource, Clone)]
+pub struct EmbeddedEditorConfig {
+    pub toggle_key: KeyCode,
+    pub patch_path: PathBuf,
+}
+
+#[derive(Resource, Default)]
+pub struct EmbeddedEditorState {
+    pub open: bool,
+    pub selected: Option<Entity>,
+    /// Transform/visibility of each entity before its first runtime tweak.
+    /// Only entities in this map are written to the exported patch.
+    pub originals: HashMap<Entity, (Transform, Option<Visibility>)>,
+}
+
+#[derive(Component)]
+#[require(EditorEntity)]
+struct EmbeddedOverlayRoot;
+
+#[derive(Component)]
+struct EmbeddedEntityList;
+
+#[derive(Component)]
+struct EmbeddedEntityRow(Entity);
+
+#[derive(Component)]
+struct EmbeddedInspectorText;
+
+fn toggle_overlay(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    config: Res<EmbeddedEditorConfig>,
+    mut state: ResMut<EmbeddedEditorState>,
+    roots: Query<Entity, With<EmbeddedOverlayRoot>>,
+) {
+    if !keyboard.just_pressed(config.toggle_key) {
+        return;
+    }
+    state.open = !state.open;
+
+    for root in &roots {
+        commands.entity(root).despawn();
+    }
+    if state.open {
+        commands.spawn(overlay_root());
+    }
+}
+
+fn overlay_root() -> impl Bundle {
+    (
+        EmbeddedOverlayRoot,
+        GlobalZIndex(i32::MAX - 1),
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            height: percent(100),
+            justify_content: JustifyContent::SpaceBetween,
+            padding: UiRect::all(px(8)),
+            ..default()
+        },
+        Pickable::IGNORE,
+        children![
+            (
+                Node {
+                    width: px(240),
+                    max_height: percent(100),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(px(6)),
+                    row_gap: px(2),
+                    ..default()
+                },
+                BackgroundColor(OVERLAY_BG),
+                children![
+                    (
+                        Text::new("Hierarchy"),
+                        TextFont::from_font_size(FONT_SIZE + 1.0),
+                        TextColor(DIM_TEXT_COLOR),
+                    ),
+                    (
+                        EmbeddedEntityList,
+                        Node {
+                            flex_direction: FlexDirection::Column,
+                            overflow: Overflow::scroll_y(),
+                            ..default()
+                        },
+                    ),
+                ],
+            ),
+            (
+                Node {
+                    width: px(300),
+                    align_self: AlignSelf::FlexStart,
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(px(6)),
+                    row_gap: px(4),
+                    ..default()
+                },
+                BackgroundColor(OVERLAY_BG),
+                children![
+                    (
+                        Text::new("Inspector"),
+                        TextFont::from_font_size(FONT_SIZE + 1.0),
+                        TextColor(DIM_TEXT_COLOR),
+                    ),
+                    (
+                        EmbeddedInspectorText,
+                        Text::new(""),
+                        TextFont::from_font_size(FONT_SIZE),
+                        TextColor(TEXT_COLOR),
+                    ),
+                ],
+            ),
+        ],
+    )
+}
+
+/// Rebuild the entity rows whenever the set of named scene entities changes.
+fn refresh_entity_list(
+    mut commands: Commands,
+    mut known: Local<Vec<Entity>>,
+    list: Single<(Entity, Option<&Children>), With<EmbeddedEntityList>>,
+    scene_entities: Query<(Entity, &Name), (With<Transform>, Without<EditorEntity>)>,
+) {
+    let (list_entity, children) = *list;
+
+    let mut entries: Vec<(Entity, &Name)> = scene_entities.iter().collect();
+    entries.sort_by(|a, b| a.1.as_str().cmp(b.1.as_str()));
+    let current: Vec<Entity> = entries.iter().map(|(e, _)| *e).collect();
+
+    // Freshly spawned overlay has no rows yet, so always populate it.
+    if children.is_some_and(|c| !c.is_empty()) && *known == current {
+        return;
+    }
+    *known = current;
+
+    commands.entity(list_entity).despawn_children();
+    for (entity, name) in entries {
+        commands.spawn((
+            EmbeddedEntityRow(entity),
+            Button,
+            Node {
+                padding: UiRect::axes(px(4), px(1)),
+                ..default()
+            },
+            BackgroundColor(ROW_BG),
+            ChildOf(list_entity),
+            children![(
+                Text::new(name.as_str()),
+                TextFont::from_font_size(FONT_SIZE),
+                TextColor(TEXT_COLOR),
+                Pickable::IGNORE,
+            )],
+        ));
+    }
+}
+
+fn handle_row_interaction(
+    mut state: ResMut<EmbeddedEditorState>,
+    mut rows: Query<(
+        &EmbeddedEntityRow,
+        &Interaction,
+        Ref<Interaction>,
+        &mut BackgroundColor,
+    )>,
+) {
+    for (row, interaction, interaction_ref, _) in &rows {
+        if interaction_ref.is_changed() && *interaction == Interaction::Pressed {
+            state.selected = Some(row.0);
+        }
+    }
+
+    for (row, interaction, _, mut bg) in &mut rows {
+        let color = if state.selected == Some(row.0) {
+            ROW_SELECTED_BG
+        } else if *interaction == Interaction::Hovered {
+            ROW_HOVER_BG
+        } else {
+            ROW_BG
+        };
+        if bg.0 != color {
+            bg.0 = color;
+        }
+    }
+}
+
+/// Remember the pre-tweak state of an entity the first time it is modified.
+fn record_original(
+    state: &mut EmbeddedEditorState,
+    entity: Entity,
+    transform: &Transform,
+    visibility: Option<&Visibility>,
+) {
+    state
+        .originals
+        .entry(entity)
+        .or_insert((*transform, visibility.copied()));
+}
+
+/// Arrows / PageUp / PageDown nudge the selected entity, `[` / `]` rotate it
+/// around Y, `-` / `=` scale it. Hold Shift for larger steps.
+fn tweak_selected_transform(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut state: ResMut<EmbeddedEditorState>,
+    mut transforms: Query<(&mut Transform, Option<&Visibility>), Without<EditorEntity>>,
+) {
+    let Some(selected) = state.selected else {
+        return;
+    };
+    let Ok((mut transform, visibility)) = transforms.get_mut(selected) else {
+        return;
+    };
+
+    let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let step = if shift { 1.0 } else { 0.1 };
+    let angle = if shift { 45f32 } else { 15f32 }.to_radians();
+    let scale_factor = if shift { 1.5 } else { 1.1 };
+
+    let mut offset = Vec3::ZERO;
+    if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        offset.x -= step;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowRight) {
+        offset.x += step;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        offset.z -= step;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        offset.z += step;
+    }
+    if keyboard.just_pressed(KeyCode::PageUp) {
+        offset.y += step;
+    }
+    if keyboard.just_pressed(KeyCode::PageDown) {
+        offset.y -= step;
+    }
+
+    let mut yaw = 0.0;
+    if keyboard.just_pressed(KeyCode::BracketLeft) {
+        yaw += angle;
+    }
+    if keyboard.just_pressed(KeyCode::BracketRight) {
+        yaw -= angle;
+    }
+
+    let mut scale = 1.0;
+    if keyboard.just_pressed(KeyCode::Minus) {
+        scale /= scale_factor;
+    }
+    if keyboard.just_pressed(KeyCode::Equal) {
+        scale *= scale_factor;
+    }
+
+    if offset == Vec3::ZERO && yaw == 0.0 && scale == 1.0 {
+        return;
+    }
+
+    record_original(&mut state, selected, &transform, visibility);
+    transform.translation += offset;
+    transform.rotate_y(yaw);
+    transform.scale *= scale;
+}
+
+/// H toggles visibility of the selected entity.
+fn toggle_selected_visibility(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut state: ResMut<EmbeddedEditorState>,
+    mut query: Query<(&Transform, Option<&mut Visibility>), Without<EditorEntity>>,
+) {
+    if !keyboard.just_pressed(KeyCode::KeyH) {
+        return;
+    }
+    let Some(selected) = state.selected else {
+        return;
+    };
+    let Ok((transform, Some(mut visibility))) = query.get_mut(selected) else {
+        return;
+    };
+
+    record_original(&mut state, selected, transform, Some(&visibility));
+    *visibility = match *visibility {
+        Visibility::Hidden => Visibility::Inherited,
+        _ => Visibility::Hidden,
+    };
+}
+
+fn update_inspector_text(
+    state: Res<EmbeddedEditorState>,
+    config: Res<EmbeddedEditorConfig>,
+    mut text: Single<&mut Text, With<EmbeddedInspectorText>>,
+    entities: Query<(&Name, &Transform, Option<&Visibility>)>,
+) {
+    let mut out = String::new();
+    match state.selected.and_then(|e| entities.get(e).ok()) {
+        Some((name, transform, visibility)) => {
+            let (ry, rx, rz) = transform.rotation.to_euler(EulerRot::YXZ);
+            let t = transform.translation;
+            let s = transform.scale;
+            out.push_str(&format!("{}\n\n", name.as_str()));
+            out.push_str(&format!(
+                "Position  {:>8.2} {:>8.2} {:>8.2}\n",
+                t.x, t.y, t.z
+            ));
+            out.push_str(&format!(
+                "Rotation  {:>8.1} {:>8.1} {:>8.1}\n",
+                rx.to_degrees(),
+                ry.to_degrees(),
+                rz.to_degrees()
+            ));
+            out.push_str(&format!(
+                "Scale     {:>8.2} {:>8.2} {:>8.2}\n",
+                s.x, s.y, s.z
+            ));
+            if let Some(visibility) = visibility {
+                out.push_str(&format!("Visibility {visibility:?}\n"));
+            }
+        }
+        None => out.push_str("Click an entity to inspect it\n"),
+    }
+
+    out.push_str(&format!(
+        "\nArrows/PgUp/PgDn: move   [ ]: rotate\n-/=: scale   H: hide   Shift: coarse\n\
+         Ctrl+E: export {} change(s) to {}",
+        state.originals.len(),
+        config.patch_path.display()
+    ));
+
+    if text.0 != out {
+        text.0 = out;
+    }
+}
+
+fn export_patch_key(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<EmbeddedEditorState>,
+    config: Res<EmbeddedEditorConfig>,
+    entities: Query<(&Name, &Transform, Option<&Visibility>, Option<&StableId>)>,
+) {
+    let ctrl = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    if !ctrl || !keyboard.just_pressed(KeyCode::KeyE) {
+        return;
+    }
+
+    let patch = build_patch(&state, &entities);
+    if patch.patches.is_empty() {
+        info!("Embedded editor: nothing to export");
+        return;
+    }
+
+    match serde_json::to_string_pretty(&patch) {
+        Ok(json) => match std::fs::write(&config.patch_path, json) {
+            Ok(()) => info!(
+                "Embedded editor: wrote {} patch(es) to {}",
+                patch.patches.len(),
+                config.patch_path.display()
+            ),
+            Err(e) => warn!(
+                "Embedded editor: failed to write {}: {e}",
+                config.patch_path.display()
+            ),
+        },
+        Err(e) => warn!("Embedded editor: failed to serialize patch: {e}"),
+    }
+}
+
+/// Collect every entity tweaked at runtime whose state actually differs from
+/// its original.
+fn build_patch(
+    state: &EmbeddedEditorState,
+    entities: &Query<(&Name, &Transform, Option<&Visibility>, Option<&StableId>)>,
+) -> JsnPatch {
+    let mut patches = Vec::new();
+    for (&entity, (original_transform, original_visibility)) in &state.originals {
+        let Ok((name, transform, visibility, stable_id)) = entities.get(entity) else {
+            continue;
+        };
+        let transform_changed = transform != original_transform;
+        let visibility_changed = visibility.copied() != *original_visibility;
+        if !transform_changed && !visibility_changed {
+            continue;
+        }
+        patches.push(JsnPatchEntry {
+            name: name.as_str().to_string(),
+            stable_id: stable_id.map(|id| id.0),
+            transform: transform_changed.then(|| JsnTransform::from(*transform)),
+            visibility: visibility
+                .filter(|_| visibility_changed)
+                .map(|v| JsnVisibility::from(*v)),
+        });
+    }
+    patches.sort_by(|a, b| a.name.cmp(&b.name));
+
+    JsnPatch {
+        jsn: JsnHeader::default(),
+        patches,
+    }
+}
@@ -0,0 +1,370 @@
+//! Project entity classes: the custom properties each `classname` expects, loaded
+//! from `.jsn/entity_classes.json` and reloaded when that file changes.
+//!
+//! Point entity classes also get an Add menu entry and a billboard icon in the
+//! viewport. Their classname and properties are saved as ordinary custom properties,
+//! which is all a game needs to spawn them.
+
+use std::time::SystemTime;
+
+use bevy::prelude::*;
+use jackdaw_jsn::{JsnEntityClass, JsnEntityClasses, JsnPointEntity, JsnPropertySchema};
+use jackdaw_widgets::menu_bar::MenuBarItem;
+
+use crate::{
+    EditorHidden, NonSerializable,
+    commands::{CommandHistory, EditorCommand, SpawnSnapshot},
+    custom_properties::{CustomProperties, PropertyValue, SetCustomProperties},
+    project::ProjectRoot,
+    selection::select_entities,
+    viewport::MainViewportCamera,
+};
+
+/// Custom property holding an entity's class name.
+pub const CLASSNAME_PROPERTY: &str = "classname";
+
+/// Menu action prefix of the Add menu's point entity entries.
+pub const ADD_POINT_ENTITY_ACTION: &str = "add.point_entity.";
+
+/// How often the schema files are checked for changes.
+const POLL_SECS: f32 = 1.0;
+
+pub struct EntityClassesPlugin;
+
+impl Plugin for EntityClassesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EntityClasses>()
+            .add_systems(
+                Update,
+                (
+                    load_entity_classes,
+                    sync_add_menu_point_entities,
+                    sync_point_entity_icons,
+                )
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            )
+            .add_systems(
+                PostUpdate,
+                face_point_entity_icons
+                    .before(bevy::transform::TransformSystems::Propagate)
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+/// Entity classes of the open project.
+#[derive(Resource, Default)]
+pub struct EntityClasses {
+    pub definitions: JsnEntityClasses,
+    /// Modification times of the schema files when last loaded.
+    modified: Vec<Option<SystemTime>>,
+}
+
+impl EntityClasses {
+    /// The class named by the `classname` property, if it's defined.
+    pub fn class_of(&self, properties: &CustomProperties) -> Option<(&str, &JsnEntityClass)> {
+        let Some(PropertyValue::String(name)) = properties.properties.get(CLASSNAME_PROPERTY)
+        else {
+            return None;
+        };
+        self.definitions
+            .classes
+            .get_key_value(name)
+            .map(|(name, class)| (name.as_str(), class))
+    }
+
+    /// Schema of `property_name` in the class of `properties`.
+    pub fn property_schema(
+        &self,
+        properties: &CustomProperties,
+        property_name: &str,
+    ) -> Option<&JsnPropertySchema> {
+        self.class_of(properties)?.1.properties.get(property_name)
+    }
+
+    /// Set the `classname` of `properties` and add the class properties it doesn't
+    /// have yet, with their defaults. Only the classname is set for an unknown class.
+    pub fn fill_class(&self, properties: &mut CustomProperties, class_name: &str) {
+        properties.properties.insert(
+            CLASSNAME_PROPERTY.to_string(),
+            PropertyValue::String(class_name.to_string()),
+        );
+        let Some(class) = self.definitions.classes.get(class_name) else {
+            return;
+        };
+        for (name, schema) in &class.properties {
+            if properties.properties.contains_key(name) {
+                continue;
+            }
+            if let Some(value) = schema.default_value() {
+                properties.properties.insert(name.clone(), value);
+            }
+        }
+    }
+
+    /// Viewport appearance of `properties`' class, if it's a point entity class.
+    pub fn point_entity(&self, properties: &CustomProperties) -> Option<(&str, &JsnPointEntity)> {
+        let (name, class) = self.class_of(properties)?;
+        class.point.as_ref().map(|point| (name, point))
+    }
+}
+
+fn load_entity_classes(
+    project: Option<Res<ProjectRoot>>,
+    mut classes: ResMut<EntityClasses>,
+    time: Res<Time>,
+    mut since_poll: Local<f32>,
+) {
+    let Some(project) = project else {
+        return;
+    };
+    *since_poll += time.delta_secs();
+    if !project.is_changed() && *since_poll < POLL_SECS {
+        return;
+    }
+    *since_poll = 0.0;
+
+    // The project's schema files, merged in order; later files override earlier classes.
+    let paths = project.schema_files();
+    let modified: Vec<Option<SystemTime>> = paths
+        .iter()
+        .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect();
+    if !project.is_changed() && modified == classes.modified {
+        return;
+    }
+    classes.modified = modified;
+
+    let mut definitions = JsnEntityClasses::default();
+    for path in paths.iter().filter(|path| path.is_file()) {
+        let parsed = std::fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|json| {
+                serde_json::from_str::<JsnEntityClasses>(&json).map_err(|err| err.to_string())
+            });
+        match parsed {
+            Ok(file) => {
+                info!(
+                    "Loaded {} entity classes from {}",
+                    file.classes.len(),
+                    path.display()
+                );
+                definitions.classes.extend(file.classes);
+            }
+            Err(err) => warn!("Failed to load entity classes '{}': {err}", path.display()),
+        }
+    }
+    classes.definitions = definitions;
+}
+
+/// Set an entity's `classname` and add the class properties it doesn't have yet, with
+/// their defaults. One undo step.
+pub fn apply_entity_class(world: &mut World, entity: Entity, class_name: &str) {
+    let Some(old) = world.get::<CustomProperties>(entity).cloned() else {
+        return;
+    };
+    let classes = world.resource::<EntityClasses>();
+    if !classes.definitions.classes.contains_key(class_name) {
+        return;
+    }
+    let mut new = old.clone();
+    classes.fill_class(&mut new, class_name);
+    push_properties(world, entity, old, new);
+}
+
+/// Add one property of the entity's class with its default value.
+pub fn add_class_property(world: &mut World, entity: Entity, property_name: &str) {
+    let Some(old) = world.get::<CustomProperties>(entity).cloned() else {
+        return;
+    };
+    let Some(value) = world
+        .resource::<EntityClasses>()
+        .property_schema(&old, property_name)
+        .and_then(|schema| schema.default_value())
+    else {
+        return;
+    };
+    let mut new = old.clone();
+    new.properties.insert(property_name.to_string(), value);
+    push_properties(world, entity, old, new);
+}
+
+fn push_properties(
+    world: &mut World,
+    entity: Entity,
+    old: CustomProperties,
+    new: CustomProperties,
+) {
+    let cmd = SetCustomProperties {
+        entity,
+        old_properties: old,
+        new_properties: new,
+    };
+    cmd.execute(world);
+
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(cmd));
+}
+
+/// Spawn an entity of a point entity class at the origin with its default properties
+/// and select it. One undo step.
+pub fn spawn_point_entity(world: &mut World, class_name: &str) {
+    let classes = world.resource::<EntityClasses>();
+    if !classes.definitions.classes.contains_key(class_name) {
+        return;
+    }
+    let mut properties = CustomProperties::default();
+    classes.fill_class(&mut properties, class_name);
+
+    let entity = world
+        .spawn((
+            Name::new(class_name.to_string()),
+            Transform::default(),
+            Visibility::default(),
+            properties,
+        ))
+        .id();
+    let cmd = SpawnSnapshot::from_world(world, &[entity], format!("Add {class_name}"));
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(cmd));
+
+    select_entities(world, &[entity]);
+}
+
+/// Keep the Add menu's point entity section in step with the loaded classes.
+fn sync_add_menu_point_entities(classes: Res<EntityClasses>, mut menus: Query<&mut MenuBarItem>) {
+    if !classes.is_changed() {
+        return;
+    }
+    let Some(mut menu) = menus.iter_mut().find(|menu| menu.label == "Add") else {
+        return;
+    };
+    if let Some(first) = menu
+        .actions
+        .iter()
+        .position(|(action, _)| action.starts_with(ADD_POINT_ENTITY_ACTION))
+    {
+        // Drop the section along with the separator in front of it.
+        menu.actions.truncate(first.saturating_sub(1));
+    }
+
+    let points: Vec<(String, String)> = classes
+        .definitions
+        .classes
+        .iter()
+        .filter(|(_, class)| class.point.is_some())
+        .map(|(name, _)| (format!("{ADD_POINT_ENTITY_ACTION}{name}"), name.clone()))
+        .collect();
+    if !points.is_empty() {
+        menu.actions.push(("---".to_string(), String::new()));
+        menu.actions.extend(points);
+    }
+}
+
+/// Billboard child drawn for entities of a point entity class.
+#[derive(Component)]
+pub struct PointEntityIcon {
+    /// Class and appearance the icon was built from.
+    class: String,
+    size: f32,
+}
+
+/// Shared quad mesh of the point entity icons.
+#[derive(Default)]
+struct IconQuad(Option<Handle<Mesh>>);
+
+/// Spawn, rebuild or remove icons when classes or custom properties change.
+fn sync_point_entity_icons(
+    mut commands: Commands,
+    classes: Res<EntityClasses>,
+    changed: Query<(), Changed<CustomProperties>>,
+    entities: Query<(Entity, &CustomProperties, Option<&Children>)>,
+    icons: Query<&PointEntityIcon>,
+    orphans: Query<(Entity, &ChildOf), With<PointEntityIcon>>,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut quad: Local<IconQuad>,
+) {
+    // Icons whose entity lost its custom properties altogether.
+    for (icon, child_of) in &orphans {
+        if !entities.contains(child_of.parent()) {
+            commands.entity(icon).despawn();
+        }
+    }
+
+    for (entity, properties, children) in &entities {
+        if !classes.is_changed() && !changed.contains(entity) {
+            continue;
+        }
+        let existing = children
+            .into_iter()
+            .flat_map(|children| children.iter())
+            .find(|&child| icons.contains(child));
+        let wanted = classes.point_entity(properties);
+
+        if let Some(icon) = existing {
+            let current = icons.get(icon).ok();
+            let up_to_date = !classes.is_changed()
+                && wanted.is_some_and(|(name, point)| {
+                    current.is_some_and(|c| c.class == name && c.size == point.size)
+                });
+            if up_to_date {
+                continue;
+            }
+            commands.entity(icon).despawn();
+        }
+        let Some((name, point)) = wanted else {
+            continue;
+        };
+
+        let mesh = quad
+            .0
+            .get_or_insert_with(|| meshes.add(Rectangle::new(1.0, 1.0)))
+            .clone();
+        let [r, g, b] = point.color;
+        let material = materials.add(StandardMaterial {
+            base_color: Color::linear_rgb(r, g, b),
+            base_color_texture: point.icon.as_ref().map(|path| asset_server.load(path)),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            cull_mode: None,
+            ..default()
+        });
+        commands.spawn((
+            PointEntityIcon {
+                class: name.to_string(),
+                size: point.size,
+            },
+            Mesh3d(mesh),
+            MeshMaterial3d(material),
+            Transform::from_scale(Vec3::splat(point.size)),
+            Visibility::default(),
+            ChildOf(entity),
+            EditorHidden,
+            NonSerializable,
+        ));
+    }
+}
+
+/// Turn icons toward the viewport camera and keep their size independent of the
+/// entity's scale.
+fn face_point_entity_icons(
+    camera: Query<&GlobalTransform, With<MainViewportCamera>>,
+    parents: Query<&GlobalTransform, Without<PointEntityIcon>>,
+    mut icons: Query<(&PointEntityIcon, &ChildOf, &mut Transform)>,
+) {
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let camera_rotation = camera.compute_transform().rotation;
+    for (icon, child_of, mut transform) in &mut icons {
+        let Ok(parent) = parents.get(child_of.parent()) else {
+            continue;
+        };
+        let (scale, rotation, _) = parent.to_scale_rotation_translation();
+        transform.rotation = rotation.inverse() * camera_rotation;
+        transform.scale = Vec3::splat(icon.size) / scale.max(Vec3::splat(f32::EPSILON));
+    }
+}
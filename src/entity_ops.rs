@@ -194,11 +194,16 @@ pub fn spawn_gltf(
     entity
 }
 
-pub fn spawn_gltf_in_world(world: &mut World, path: &str, position: Vec3) {
+/// Spawn the glTF at `path`, as a child of `parent` when given (`position` is then local
+/// to it).
+pub fn spawn_gltf_in_world(world: &mut World, path: &str, position: Vec3, parent: Option<Entity>) {
     let mut system_state: SystemState<(Commands, Res<AssetServer>, ResMut<Selection>)> =
         SystemState::new(world);
     let (mut commands, asset_server, mut selection) = system_state.get_mut(world);
-    spawn_gltf(&mut commands, &asset_server, path, position, &mut selection);
+    let entity = spawn_gltf(&mut commands, &asset_server, path, position, &mut selection);
+    if let Some(parent) = parent {
+        commands.entity(entity).insert(ChildOf(parent));
+    }
     system_state.apply(world);
 }
 
@@ -244,8 +249,7 @@ pub fn delete_selected(world: &mut World) {
             label: "Delete entities".to_string(),
         };
         let mut history = world.resource_mut::<CommandHistory>();
-        history.undo_stack.push(Box::new(group));
-        history.redo_stack.clear();
+        history.push_executed(Box::new(group));
     }
 }
 
@@ -267,82 +271,88 @@ pub fn duplicate_selected(world: &mut World) {
     let mut new_entities = Vec::new();
 
     for &entity in &entities {
-        if world.get_entity(entity).is_err() {
-            continue;
-        }
-        if world.get::<EditorEntity>(entity).is_some() {
-            continue;
+        if let Some(new_root) = duplicate_entity(world, entity) {
+            new_entities.push(new_root);
         }
+    }
+
+    // Select the new entities
+    let mut selection = world.resource_mut::<Selection>();
+    selection.entities = new_entities;
+    for &entity in &selection.entities.clone() {
+        world.entity_mut(entity).insert(Selected);
+    }
+}
 
-        // Snapshot the entity (and descendants) via DynamicSceneBuilder
-        let mut snapshot_entities = Vec::new();
-        crate::commands::collect_entity_ids(world, entity, &mut snapshot_entities);
-        let scene = DynamicSceneBuilder::from_world(world)
-            .extract_entities(snapshot_entities.into_iter())
-            .build();
+/// Clone an entity and its descendants, giving the copy a numbered name and the
+/// same parent as the original. Returns the new root entity.
+pub(crate) fn duplicate_entity(world: &mut World, entity: Entity) -> Option<Entity> {
+    if world.get_entity(entity).is_err() {
+        return None;
+    }
+    if world.get::<EditorEntity>(entity).is_some() {
+        return None;
+    }
 
-        // Write the snapshot back to create a clone
-        let mut entity_map = Default::default();
-        if scene.write_to_world(world, &mut entity_map).is_err() {
-            continue;
-        }
+    // Snapshot the entity (and descendants) via DynamicSceneBuilder
+    let mut snapshot_entities = Vec::new();
+    crate::commands::collect_entity_ids(world, entity, &mut snapshot_entities);
+    let scene = DynamicSceneBuilder::from_world(world)
+        .extract_entities(snapshot_entities.into_iter())
+        .build();
 
-        // Find the cloned root entity
-        let Some(&new_root) = entity_map.get(&entity) else {
-            continue;
-        };
+    // Write the snapshot back to create a clone
+    let mut entity_map = Default::default();
+    if scene.write_to_world(world, &mut entity_map).is_err() {
+        return None;
+    }
 
-        // Rename with incremented number suffix
-        if let Some(name) = world.get::<Name>(new_root) {
-            // Strip trailing " (Copy)" chains and trailing " N" to find base name
-            let mut base = name.as_str().to_string();
-            while base.ends_with(" (Copy)") {
-                base.truncate(base.len() - 7);
-            }
-            if let Some(pos) = base.rfind(' ') {
-                if base[pos + 1..].parse::<u32>().is_ok() {
-                    base.truncate(pos);
-                }
+    // Find the cloned root entity
+    let &new_root = entity_map.get(&entity)?;
+
+    // Rename with incremented number suffix
+    if let Some(name) = world.get::<Name>(new_root) {
+        // Strip trailing " (Copy)" chains and trailing " N" to find base name
+        let mut base = name.as_str().to_string();
+        while base.ends_with(" (Copy)") {
+            base.truncate(base.len() - 7);
+        }
+        if let Some(pos) = base.rfind(' ') {
+            if base[pos + 1..].parse::<u32>().is_ok() {
+                base.truncate(pos);
             }
+        }
 
-            // Find highest existing number for this base name
-            let mut max_num = 0u32;
-            let mut query = world.query::<&Name>();
-            for existing in query.iter(world) {
-                let s = existing.as_str();
-                if s == base {
-                    max_num = max_num.max(1);
-                } else if let Some(rest) = s.strip_prefix(base.as_str()) {
-                    if let Some(num_str) = rest.strip_prefix(' ') {
-                        if let Ok(n) = num_str.parse::<u32>() {
-                            max_num = max_num.max(n);
-                        }
+        // Find highest existing number for this base name
+        let mut max_num = 0u32;
+        let mut query = world.query::<&Name>();
+        for existing in query.iter(world) {
+            let s = existing.as_str();
+            if s == base {
+                max_num = max_num.max(1);
+            } else if let Some(rest) = s.strip_prefix(base.as_str()) {
+                if let Some(num_str) = rest.strip_prefix(' ') {
+                    if let Ok(n) = num_str.parse::<u32>() {
+                        max_num = max_num.max(n);
                     }
                 }
             }
-
-            let new_name = format!("{} {}", base, max_num + 1);
-            world.entity_mut(new_root).insert(Name::new(new_name));
-        }
-
-        // Preserve parent relationship from original
-        let parent = world.get::<ChildOf>(entity).map(|c| c.0);
-        if let Some(parent) = parent {
-            world.entity_mut(new_root).insert(ChildOf(parent));
-        } else {
-            // Original was a root entity — remove any ChildOf the scene write may have added
-            world.entity_mut(new_root).remove::<ChildOf>();
         }
 
-        new_entities.push(new_root);
+        let new_name = format!("{} {}", base, max_num + 1);
+        world.entity_mut(new_root).insert(Name::new(new_name));
     }
 
-    // Select the new entities
-    let mut selection = world.resource_mut::<Selection>();
-    selection.entities = new_entities;
-    for &entity in &selection.entities.clone() {
-        world.entity_mut(entity).insert(Selected);
+    // Preserve parent relationship from original
+    let parent = world.get::<ChildOf>(entity).map(|c| c.0);
+    if let Some(parent) = parent {
+        world.entity_mut(new_root).insert(ChildOf(parent));
+    } else {
+        // Original was a root entity — remove any ChildOf the scene write may have added
+        world.entity_mut(new_root).remove::<ChildOf>();
     }
+
+    Some(new_root)
 }
 
 fn handle_entity_keys(world: &mut World) {
@@ -367,6 +377,14 @@ fn handle_entity_keys(world: &mut World) {
     if draw_active {
         return;
     }
+    // Spline edit mode uses Delete for points
+    if world
+        .resource::<crate::spline::SplineEditState>()
+        .active
+        .is_some()
+    {
+        return;
+    }
 
     // Don't process entity ops during brush edit mode (Delete etc. handled by brush systems)
     let in_brush_edit = !matches!(
@@ -380,6 +398,7 @@ fn handle_entity_keys(world: &mut World) {
     let keyboard = world.resource::<ButtonInput<KeyCode>>();
     let ctrl = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
     let alt = keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+    let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
     let delete_pressed = keyboard.just_pressed(KeyCode::Delete);
     let d_pressed = keyboard.just_pressed(KeyCode::KeyD);
     let g_pressed = keyboard.just_pressed(KeyCode::KeyG);
@@ -408,10 +427,22 @@ fn handle_entity_keys(world: &mut World) {
                 .resource_mut::<crate::modal_transform::ViewportDragState>()
                 .pending_grab = Some(entity);
         }
+    } else if alt && d_pressed {
+        crate::linked_duplicate::duplicate_linked_selected(world);
+        let selection = world.resource::<Selection>();
+        if let Some(entity) = selection.primary() {
+            world
+                .resource_mut::<crate::modal_transform::ViewportDragState>()
+                .pending_grab = Some(entity);
+        }
     } else if ctrl && c_pressed {
         copy_components(world);
     } else if ctrl && v_pressed {
         paste_components(world);
+    } else if ctrl && shift && g_pressed {
+        crate::grouping::ungroup_selected(world);
+    } else if ctrl && g_pressed {
+        crate::grouping::group_selected(world);
     } else if alt && g_pressed {
         reset_transform_selected(world, TransformReset::Position);
     } else if alt && r_pressed {
@@ -528,8 +559,7 @@ fn reset_transform_selected(world: &mut World, reset: TransformReset) {
             label: label.to_string(),
         };
         let mut history = world.resource_mut::<CommandHistory>();
-        history.undo_stack.push(Box::new(group));
-        history.redo_stack.clear();
+        history.push_executed(Box::new(group));
     }
 }
 
@@ -571,8 +601,7 @@ fn nudge_selected(world: &mut World, offset: Vec3) {
             label: "Nudge".to_string(),
         };
         let mut history = world.resource_mut::<CommandHistory>();
-        history.undo_stack.push(Box::new(group));
-        history.redo_stack.clear();
+        history.push_executed(Box::new(group));
     }
 }
 
@@ -614,8 +643,7 @@ fn rotate_selected(world: &mut World, rotation: Quat) {
             label: "Rotate 90\u{00b0}".to_string(),
         };
         let mut history = world.resource_mut::<CommandHistory>();
-        history.undo_stack.push(Box::new(group));
-        history.redo_stack.clear();
+        history.push_executed(Box::new(group));
     }
 }
 
@@ -750,8 +778,7 @@ fn toggle_visibility_selected(world: &mut World) {
             label: "Toggle visibility".to_string(),
         };
         let mut history = world.resource_mut::<CommandHistory>();
-        history.undo_stack.push(Box::new(group));
-        history.redo_stack.clear();
+        history.push_executed(Box::new(group));
     }
 }
 
@@ -759,7 +786,7 @@ fn toggle_visibility_selected(world: &mut World) {
 ///
 /// Bevy's default asset source reads from `<base>/assets/` where `<base>` is
 /// `BEVY_ASSET_ROOT`, `CARGO_MANIFEST_DIR`, or the executable's parent directory.
-fn to_asset_path(path: &str) -> String {
+pub(crate) fn to_asset_path(path: &str) -> String {
     let path = Path::new(path);
     if let Some(assets_dir) = get_assets_base_dir() {
         if let Ok(relative) = path.strip_prefix(&assets_dir) {
@@ -784,7 +811,7 @@ fn to_asset_path(path: &str) -> String {
 fn get_assets_base_dir() -> Option<std::path::PathBuf> {
     // Try ProjectRoot via recent projects config
     if let Some(project_dir) = crate::project::read_last_project() {
-        let assets = project_dir.join("assets");
+        let assets = crate::project::project_assets_dir(&project_dir);
         if assets.is_dir() {
             return Some(assets);
         }
@@ -1,24 +1,33 @@
 use std::any::TypeId;
 use std::collections::{HashMap, HashSet};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use bevy::{
     asset::{AssetPath, UntypedHandle},
-    ecs::reflect::AppTypeRegistry,
+    camera::RenderTarget,
+    ecs::{entity::EntityHashMap, reflect::AppTypeRegistry},
     prelude::*,
     reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    render::view::screenshot::{Screenshot, ScreenshotCaptured, save_to_disk},
     tasks::IoTaskPool,
 };
-use jackdaw_jsn::format::{JsnEntity, JsnScene};
-use serde::de::DeserializeSeed;
+use jackdaw_jsn::{Brush, CustomProperties, PropertyValue, StableId, format::JsnEntity};
+use serde::{Deserialize, Serialize, de::DeserializeSeed};
 
 use crate::{
     EditorEntity,
     commands::{CommandHistory, DespawnEntity, EditorCommand, collect_entity_ids},
     scene_io::should_skip_component,
     selection::{Selected, Selection},
+    template_browser::TemplateBrowserState,
+    viewport::MainViewportCamera,
 };
 
+/// Where templates are saved. Subfolders are categories in the templates panel.
+pub(crate) const TEMPLATES_DIR: &str = "assets/templates";
+const TEMPLATE_EXTENSION: &str = ".template.json";
+const THUMBNAIL_EXTENSION: &str = ".template.png";
+
 pub struct EntityTemplatesPlugin;
 
 impl Plugin for EntityTemplatesPlugin {
@@ -27,6 +36,38 @@ impl Plugin for EntityTemplatesPlugin {
     }
 }
 
+/// A template on disk: its entities, plus the values offered for overriding each time
+/// it's instantiated. Older templates are a bare entity list.
+#[derive(Serialize, Deserialize)]
+pub struct TemplateFile {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<TemplateParameter>,
+    pub entities: Vec<JsnEntity>,
+}
+
+/// A value of the template exposed in the instantiation dialog.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TemplateParameter {
+    pub label: String,
+    /// Index into [`TemplateFile::entities`]; the root is 0.
+    #[serde(default)]
+    pub entity: usize,
+    #[serde(flatten)]
+    pub target: TemplateParameterTarget,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TemplateParameterTarget {
+    Name,
+    /// A custom property; only bool, number and string values can be overridden.
+    Property {
+        key: String,
+    },
+    /// The material of a mesh, or of every face of a brush.
+    Material,
+}
+
 /// Tracks which entity to save when the template save dialog is confirmed.
 #[derive(Resource, Default)]
 pub struct PendingTemplateSave {
@@ -45,6 +86,9 @@ pub fn save_entity_template(world: &mut World, name: &str) {
         return;
     }
 
+    // Shading previews swap materials; save the authored ones.
+    crate::view_modes::restore_original_materials(world);
+
     // Collect entity + descendants
     let mut entities = Vec::new();
     collect_entity_ids(world, primary, &mut entities);
@@ -114,7 +158,11 @@ pub fn save_entity_template(world: &mut World, name: &str) {
         })
         .collect();
 
-    let json = match serde_json::to_string_pretty(&jsn_entities) {
+    let file = TemplateFile {
+        parameters: default_parameters(world.entity(primary)),
+        entities: jsn_entities,
+    };
+    let json = match serde_json::to_string_pretty(&file) {
         Ok(json) => json,
         Err(err) => {
             warn!("Failed to serialize template: {err}");
@@ -122,25 +170,167 @@ pub fn save_entity_template(world: &mut World, name: &str) {
         }
     };
 
-    // Ensure templates directory exists and write
-    let safe_name = sanitize_filename(name);
-    let path = format!("assets/templates/{safe_name}.template.json");
+    // Ensure the template's category directory exists and write
+    let path = template_path(name);
+    if let Some(dir) = path.parent() {
+        if let Err(err) = std::fs::create_dir_all(dir) {
+            warn!("Failed to create templates directory: {err}");
+            return;
+        }
+    }
+    capture_thumbnail(world, &path);
 
     IoTaskPool::get()
         .spawn(async move {
-            if let Err(err) = std::fs::create_dir_all("assets/templates") {
-                warn!("Failed to create templates directory: {err}");
-                return;
-            }
             match std::fs::write(&path, &json) {
-                Ok(()) => info!("Template saved to {path}"),
+                Ok(()) => info!("Template saved to {}", path.display()),
                 Err(err) => warn!("Failed to write template file: {err}"),
             }
         })
         .detach();
 }
 
-pub fn instantiate_template(world: &mut World, path: &str, position: Vec3) {
+/// Parameters a new template exposes: the root's name, its simple custom properties,
+/// and its material. Edit the template file to expose others.
+fn default_parameters(root: EntityRef) -> Vec<TemplateParameter> {
+    let mut parameters = vec![TemplateParameter {
+        label: "Name".into(),
+        entity: 0,
+        target: TemplateParameterTarget::Name,
+    }];
+    if let Some(properties) = root.get::<CustomProperties>() {
+        for (key, value) in &properties.properties {
+            if matches!(
+                value,
+                PropertyValue::Bool(_)
+                    | PropertyValue::Int(_)
+                    | PropertyValue::Float(_)
+                    | PropertyValue::String(_)
+            ) {
+                parameters.push(TemplateParameter {
+                    label: key.clone(),
+                    entity: 0,
+                    target: TemplateParameterTarget::Property { key: key.clone() },
+                });
+            }
+        }
+    }
+    if root.contains::<Brush>() || root.contains::<MeshMaterial3d<StandardMaterial>>() {
+        parameters.push(TemplateParameter {
+            label: "Material".into(),
+            entity: 0,
+            target: TemplateParameterTarget::Material,
+        });
+    }
+    parameters
+}
+
+/// Template file for a name; `Props/Crate` saves `Crate` in the `Props` category.
+pub(crate) fn template_path(name: &str) -> PathBuf {
+    let mut path = PathBuf::from(TEMPLATES_DIR);
+    let segments: Vec<String> = name
+        .split('/')
+        .map(sanitize_filename)
+        .filter(|s| !s.is_empty())
+        .collect();
+    let Some((file, dirs)) = segments.split_last() else {
+        return path.join(format!("template{TEMPLATE_EXTENSION}"));
+    };
+    path.extend(dirs);
+    path.join(format!("{file}{TEMPLATE_EXTENSION}"))
+}
+
+/// The name a template is saved under, including its category: the inverse of
+/// [`template_path`].
+pub(crate) fn template_name(path: &Path) -> String {
+    let relative = path.strip_prefix(TEMPLATES_DIR).unwrap_or(path);
+    let name = relative.to_string_lossy().replace('\\', "/");
+    name.strip_suffix(TEMPLATE_EXTENSION)
+        .unwrap_or(&name)
+        .to_string()
+}
+
+pub(crate) fn is_template_file(path: &Path) -> bool {
+    path.to_string_lossy()
+        .to_lowercase()
+        .ends_with(TEMPLATE_EXTENSION)
+}
+
+/// The thumbnail image saved alongside a template.
+pub(crate) fn thumbnail_path(template: &Path) -> PathBuf {
+    let file = template
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let stem = file.strip_suffix(TEMPLATE_EXTENSION).unwrap_or(&file);
+    template.with_file_name(format!("{stem}{THUMBNAIL_EXTENSION}"))
+}
+
+/// Save what the viewport shows right now as the template's thumbnail.
+fn capture_thumbnail(world: &mut World, template: &Path) {
+    let mut cameras = world.query_filtered::<&RenderTarget, With<MainViewportCamera>>();
+    let Some(RenderTarget::Image(target)) = cameras.iter(world).next() else {
+        return;
+    };
+    let image = target.handle.clone();
+    let mut save = save_to_disk(thumbnail_path(template));
+    world.spawn(Screenshot::image(image)).observe(
+        move |captured: On<ScreenshotCaptured>, mut browser: ResMut<TemplateBrowserState>| {
+            save(captured);
+            browser.needs_refresh = true;
+        },
+    );
+}
+
+/// Rename or recategorize a template, moving its thumbnail along with it.
+pub fn rename_template(path: &Path, new_name: &str) -> std::io::Result<PathBuf> {
+    let new_path = template_path(new_name);
+    if new_path == path {
+        return Ok(new_path);
+    }
+    if new_path.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} already exists", new_path.display()),
+        ));
+    }
+    if let Some(dir) = new_path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::rename(path, &new_path)?;
+    let thumbnail = thumbnail_path(path);
+    if thumbnail.exists() {
+        std::fs::rename(&thumbnail, thumbnail_path(&new_path))?;
+    }
+    remove_empty_category(path);
+    Ok(new_path)
+}
+
+/// Delete a template and its thumbnail.
+pub fn delete_template(path: &Path) -> std::io::Result<()> {
+    std::fs::remove_file(path)?;
+    let thumbnail = thumbnail_path(path);
+    if thumbnail.exists() {
+        std::fs::remove_file(thumbnail)?;
+    }
+    remove_empty_category(path);
+    Ok(())
+}
+
+/// Drop a category folder once its last template has left it.
+fn remove_empty_category(template: &Path) {
+    let Some(dir) = template.parent() else {
+        return;
+    };
+    if dir != Path::new(TEMPLATES_DIR) {
+        // Fails harmlessly when the folder still has files
+        let _ = std::fs::remove_dir(dir);
+    }
+}
+
+/// Spawn the template at `path`, as children of `parent` when given (`position` is then
+/// local to it).
+pub fn instantiate_template(world: &mut World, path: &str, position: Vec3, parent: Option<Entity>) {
     let json = match std::fs::read_to_string(path) {
         Ok(json) => json,
         Err(err) => {
@@ -149,22 +339,41 @@ pub fn instantiate_template(world: &mut World, path: &str, position: Vec3) {
         }
     };
 
-    let jsn_entities: Vec<JsnEntity> = match serde_json::from_str(&json) {
-        Ok(v) => v,
-        Err(err) => {
-            warn!("Failed to parse template file: {err}");
-            return;
-        }
+    let file = match serde_json::from_str::<TemplateFile>(&json) {
+        Ok(file) => file,
+        Err(err) => match serde_json::from_str::<Vec<JsnEntity>>(&json) {
+            Ok(entities) => TemplateFile {
+                parameters: Vec::new(),
+                entities,
+            },
+            Err(_) => {
+                warn!("Failed to parse template file: {err}");
+                return;
+            }
+        },
     };
 
     let parent_path = Path::new(path).parent().unwrap_or(Path::new(""));
     let local_assets = HashMap::new();
-    let (_spawned, roots) =
-        spawn_jsn_entities(world, &jsn_entities, position, parent_path, &local_assets);
-    finalize_instantiation(world, &roots);
+    let (spawned, roots) =
+        spawn_jsn_entities(world, &file.entities, position, parent_path, &local_assets);
+    attach_roots(world, &roots, parent);
+    if file.parameters.is_empty() {
+        finalize_instantiation(world, &roots);
+    } else {
+        // The spawned entities preview the result while the dialog is open
+        crate::template_parameters::open_parameters_dialog(world, file.parameters, spawned, roots);
+    }
 }
 
-pub fn instantiate_jsn_prefab(world: &mut World, path: &str, position: Vec3) {
+/// Spawn the prefab at `path`, as children of `parent` when given (`position` is then
+/// local to it).
+pub fn instantiate_jsn_prefab(
+    world: &mut World,
+    path: &str,
+    position: Vec3,
+    parent: Option<Entity>,
+) {
     let json = match std::fs::read_to_string(path) {
         Ok(json) => json,
         Err(err) => {
@@ -173,8 +382,13 @@ pub fn instantiate_jsn_prefab(world: &mut World, path: &str, position: Vec3) {
         }
     };
 
-    let jsn: JsnScene = match serde_json::from_str(&json) {
-        Ok(v) => v,
+    let jsn = match jackdaw_jsn::parse_scene(&json) {
+        Ok((jsn, report)) => {
+            for warning in &report.warnings {
+                warn!("{path}: {warning}");
+            }
+            jsn
+        }
         Err(err) => {
             warn!("Failed to parse JSN prefab file: {err}");
             return;
@@ -189,6 +403,7 @@ pub fn instantiate_jsn_prefab(world: &mut World, path: &str, position: Vec3) {
     let jsn_entities = &jsn.scene;
     let (spawned, roots) =
         spawn_jsn_entities(world, jsn_entities, position, parent_path, &local_assets);
+    attach_roots(world, &roots, parent);
 
     // Attach JsnPrefab component to root entities
     for &root in &roots {
@@ -204,9 +419,18 @@ pub fn instantiate_jsn_prefab(world: &mut World, path: &str, position: Vec3) {
     finalize_instantiation(world, &roots);
 }
 
+fn attach_roots(world: &mut World, roots: &[Entity], parent: Option<Entity>) {
+    let Some(parent) = parent else {
+        return;
+    };
+    for &root in roots {
+        world.entity_mut(root).insert(ChildOf(parent));
+    }
+}
+
 /// Spawn entities from JsnEntity data, offset roots by position.
 /// Returns (all_spawned_entities, root_entities).
-fn spawn_jsn_entities(
+pub(crate) fn spawn_jsn_entities(
     world: &mut World,
     jsn_entities: &[JsnEntity],
     position: Vec3,
@@ -347,8 +571,9 @@ fn build_prefab_baselines(world: &mut World, spawned: &[Entity]) {
             if should_skip_component(type_path) {
                 continue;
             }
-            // Skip JsnPrefab/JsnPrefabBaseline themselves
-            if type_path.contains("JsnPrefab") {
+            // Skip JsnPrefab/JsnPrefabBaseline themselves, and the per-instance id
+            if type_path.contains("JsnPrefab") || registration.type_id() == TypeId::of::<StableId>()
+            {
                 continue;
             }
             let Some(reflect_component) = registration.data::<ReflectComponent>() else {
@@ -369,7 +594,7 @@ fn build_prefab_baselines(world: &mut World, spawned: &[Entity]) {
 }
 
 /// Finalize instantiation: set up undo + select roots.
-fn finalize_instantiation(world: &mut World, roots: &[Entity]) {
+pub(crate) fn finalize_instantiation(world: &mut World, roots: &[Entity]) {
     // Build DespawnEntity snapshots for undo
     let mut despawn_cmds: Vec<DespawnEntity> = Vec::new();
     for &root in roots {
@@ -402,8 +627,7 @@ fn finalize_instantiation(world: &mut World, roots: &[Entity]) {
             snapshots: despawn_cmds,
         };
         let mut history = world.resource_mut::<CommandHistory>();
-        history.undo_stack.push(Box::new(cmd));
-        history.redo_stack.clear();
+        history.push_executed(Box::new(cmd));
     }
 }
 
@@ -429,6 +653,21 @@ impl EditorCommand for InstantiateEntities {
     fn description(&self) -> &str {
         "Instantiate template"
     }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        for snapshot in &mut self.snapshots {
+            snapshot.remap_entities(map);
+        }
+    }
+
+    fn memory_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self
+                .snapshots
+                .iter()
+                .map(|s| s.memory_size())
+                .sum::<usize>()
+    }
 }
 
 /// Sanitize a filename: allow alphanumeric, hyphens, underscores, spaces.
@@ -0,0 +1,223 @@
+//! Extension points for crates building on the editor. Insert an [`EditorExtensions`]
+//! before adding [`EditorPlugin`](crate::EditorPlugin):
+//!
+//! ```ignore
+//! app.insert_resource(
+//!     EditorExtensions::default()
+//!         .command("mygame.reset_spawns", reset_spawns)
+//!         .menu_item("Tools", "mygame.reset_spawns", "Reset Spawn Points")
+//!         .template("Enemy Spawn", |world| world.spawn(EnemySpawn::default()).id())
+//!         .panel("Wave Editor", |panel| { panel.insert(wave_editor()); })
+//!         .display::<EnemySpawn>(),
+//! )
+//! .add_plugins(EditorPlugin);
+//! ```
+
+use std::{collections::HashMap, sync::Arc};
+
+use bevy::{prelude::*, reflect::GetTypeRegistration};
+use jackdaw_feathers::{panel_header, split_panel, tokens};
+use jackdaw_widgets::menu_bar::MenuAction;
+
+use crate::{
+    EditorEntity,
+    docking::{DockArea, DockPanel},
+    inspector::{Displayable, ReflectDisplayable},
+};
+
+type WorldAction = Arc<dyn Fn(&mut World) + Send + Sync>;
+type TemplateSpawner = Arc<dyn Fn(&mut World) -> Entity + Send + Sync>;
+type PanelBuilder = Arc<dyn Fn(&mut EntityCommands) + Send + Sync>;
+
+/// Menu items, panels, inspector displays, entity templates and commands registered
+/// by downstream crates.
+#[derive(Resource, Default)]
+pub struct EditorExtensions {
+    commands: HashMap<String, WorldAction>,
+    menu_items: Vec<ExtensionMenuItem>,
+    templates: Vec<(String, TemplateSpawner)>,
+    panels: Vec<(String, PanelBuilder)>,
+    /// Type registrations, applied once while `EditorPlugin` builds.
+    displays: Vec<fn(&mut App)>,
+}
+
+struct ExtensionMenuItem {
+    menu: String,
+    action: String,
+    label: String,
+}
+
+impl EditorExtensions {
+    /// A named action, run whenever a [`MenuAction`] with this id is triggered: from a
+    /// menu item, a macro, or another plugin.
+    pub fn command(
+        mut self,
+        id: impl Into<String>,
+        run: impl Fn(&mut World) + Send + Sync + 'static,
+    ) -> Self {
+        self.commands.insert(id.into(), Arc::new(run));
+        self
+    }
+
+    /// A menu item triggering `action`, which may be a built-in action or one added
+    /// with [`command`](Self::command). Unknown menus are added after the built-in ones.
+    pub fn menu_item(
+        mut self,
+        menu: impl Into<String>,
+        action: impl Into<String>,
+        label: impl Into<String>,
+    ) -> Self {
+        self.menu_items.push(ExtensionMenuItem {
+            menu: menu.into(),
+            action: action.into(),
+            label: label.into(),
+        });
+        self
+    }
+
+    /// An entry of the Add menu. The spawned entity is selected and its creation undoable.
+    pub fn template(
+        mut self,
+        label: impl Into<String>,
+        spawn: impl Fn(&mut World) -> Entity + Send + Sync + 'static,
+    ) -> Self {
+        self.templates.push((label.into(), Arc::new(spawn)));
+        self
+    }
+
+    /// A panel docked next to the asset browsers; `build` fills in its body.
+    pub fn panel(
+        mut self,
+        title: impl Into<String>,
+        build: impl Fn(&mut EntityCommands) + Send + Sync + 'static,
+    ) -> Self {
+        self.panels.push((title.into(), Arc::new(build)));
+        self
+    }
+
+    /// Show `T` in the inspector through its [`Displayable`] implementation.
+    pub fn display<T: Displayable + Reflect + TypePath + GetTypeRegistration>(mut self) -> Self {
+        self.displays.push(|app| {
+            app.register_type::<T>()
+                .register_type_data::<T, ReflectDisplayable>();
+        });
+        self
+    }
+}
+
+fn template_action(index: usize) -> String {
+    format!("extension.template.{index}")
+}
+
+/// Register what needs the `App` and keep the rest for runtime.
+pub(crate) fn build_extensions(app: &mut App) {
+    app.init_resource::<EditorExtensions>();
+    let displays = std::mem::take(&mut app.world_mut().resource_mut::<EditorExtensions>().displays);
+    for register in displays {
+        register(app);
+    }
+    app.add_observer(run_extension_action).add_systems(
+        OnEnter(crate::AppState::Editor),
+        spawn_extension_panels.after(crate::spawn_layout),
+    );
+}
+
+/// Add extension entries to the built-in menus before they're spawned.
+pub(crate) fn extend_menus(world: &World, menus: &mut Vec<(String, Vec<(String, String)>)>) {
+    let extensions = world.resource::<EditorExtensions>();
+    let mut extended: Vec<usize> = Vec::new();
+    let items = extensions
+        .menu_items
+        .iter()
+        .map(|item| (item.menu.clone(), item.action.clone(), item.label.clone()))
+        .chain(
+            extensions
+                .templates
+                .iter()
+                .enumerate()
+                .map(|(i, (label, _))| ("Add".to_string(), template_action(i), label.clone())),
+        );
+    for (menu, action, label) in items {
+        let index = match menus.iter().position(|(title, _)| *title == menu) {
+            Some(index) => index,
+            None => {
+                menus.push((menu, Vec::new()));
+                menus.len() - 1
+            }
+        };
+        // Set extension items apart from the built-in ones
+        if !extended.contains(&index) {
+            extended.push(index);
+            if !menus[index].1.is_empty() {
+                menus[index].1.push(("---".into(), String::new()));
+            }
+        }
+        menus[index].1.push((action, label));
+    }
+}
+
+fn run_extension_action(event: On<MenuAction>, mut commands: Commands) {
+    let action = event.action.clone();
+    commands.queue(move |world: &mut World| {
+        let extensions = world.resource::<EditorExtensions>();
+        if let Some(run) = extensions.commands.get(&action).cloned() {
+            run(world);
+            return;
+        }
+        let Some(spawn) = action
+            .strip_prefix("extension.template.")
+            .and_then(|i| i.parse::<usize>().ok())
+            .and_then(|i| extensions.templates.get(i))
+            .map(|(_, spawn)| spawn.clone())
+        else {
+            return;
+        };
+        let entity = spawn(world);
+        crate::entity_templates::finalize_instantiation(world, &[entity]);
+    });
+}
+
+pub(crate) fn spawn_extension_panels(
+    mut commands: Commands,
+    extensions: Res<EditorExtensions>,
+    group: Single<Entity, With<crate::layout::BottomPanels>>,
+) {
+    for (title, build) in &extensions.panels {
+        commands.spawn((split_panel::panel_handle(), ChildOf(*group)));
+        let panel = commands
+            .spawn((
+                split_panel::panel(1),
+                DockPanel::new(
+                    format!("extension.{title}"),
+                    title.clone(),
+                    DockArea::Bottom,
+                ),
+                EditorEntity,
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    ..Default::default()
+                },
+                BackgroundColor(tokens::PANEL_BG),
+                ChildOf(*group),
+                children![panel_header::panel_header(title.clone())],
+            ))
+            .id();
+        let body = commands
+            .spawn((
+                EditorEntity,
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    flex_grow: 1.0,
+                    min_height: Val::Px(0.0),
+                    overflow: Overflow::scroll_y(),
+                    padding: UiRect::all(Val::Px(tokens::SPACING_SM)),
+                    ..Default::default()
+                },
+                ChildOf(panel),
+            ))
+            .id();
+        build(&mut commands.entity(body));
+    }
+}
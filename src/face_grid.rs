@@ -34,23 +34,21 @@ fn configure_face_grid_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
     config.depth_bias = -0.0001;
 }
 
-/// Draw wireframe edges on all brushes (bright cyan on selected, subtle grey on unselected).
+/// Draw subtle wireframe edges on unselected brushes; the selection outline covers the rest.
 fn draw_brush_edges(
     mut gizmos: Gizmos<FaceGridGizmoGroup>,
     settings: Res<OverlaySettings>,
-    brushes: Query<(&BrushMeshCache, &GlobalTransform, Has<Selected>), Without<CutPreviewHidden>>,
+    brushes: Query<
+        (&BrushMeshCache, &GlobalTransform),
+        (Without<CutPreviewHidden>, Without<Selected>),
+    >,
 ) {
     if !settings.show_brush_wireframe {
         return;
     }
 
-    for (cache, global_tf, is_selected) in &brushes {
-        let color: Color = if is_selected {
-            tailwind::CYAN_400.into()
-        } else {
-            Color::from(tailwind::GRAY_500).with_alpha(0.5)
-        };
-
+    let color = Color::from(tailwind::GRAY_500).with_alpha(0.5);
+    for (cache, global_tf) in &brushes {
         let mut drawn_edges = HashSet::new();
         for polygon in &cache.face_polygons {
             for i in 0..polygon.len() {
@@ -1,11 +1,13 @@
 use bevy::{
     prelude::*,
     ui::UiGlobalTransform,
-    window::{CursorGrabMode, CursorOptions},
+    window::{CursorGrabMode, CursorOptions, PrimaryWindow},
 };
+use jackdaw_feathers::tokens;
 
 use crate::{
-    commands::{CommandHistory, SetTransform},
+    EditorEntity,
+    commands::{CommandGroup, CommandHistory, EditorCommand, SetTransform, SpawnSnapshot},
     modal_transform::ModalTransformState,
     selection::{Selected, Selection},
     snapping::SnapSettings,
@@ -13,10 +15,16 @@ use crate::{
     viewport_util::{point_to_segment_dist, window_to_viewport_cursor},
 };
 
+// Handle sizes are in gizmo units, scaled so `AXIS_LENGTH` spans `GIZMO_SCREEN_SIZE` pixels
 const AXIS_LENGTH: f32 = 1.5;
 const AXIS_TIP_LENGTH: f32 = 0.3;
 const ROTATE_RING_RADIUS: f32 = 1.2;
+const SCREEN_RING_RADIUS: f32 = 1.45;
 const SCALE_CUBE_SIZE: f32 = 0.15;
+const PLANE_HANDLE_OFFSET: f32 = 0.35;
+const PLANE_HANDLE_SIZE: f32 = 0.3;
+const SCREEN_HANDLE_SIZE: f32 = 0.2;
+const GIZMO_SCREEN_SIZE: f32 = 110.0;
 
 const COLOR_X: Color = Color::srgb(1.0, 0.2, 0.2);
 const COLOR_Y: Color = Color::srgb(0.2, 1.0, 0.2);
@@ -24,8 +32,10 @@ const COLOR_Z: Color = Color::srgb(0.2, 0.4, 1.0);
 const COLOR_X_BRIGHT: Color = Color::srgb(1.0, 0.5, 0.5);
 const COLOR_Y_BRIGHT: Color = Color::srgb(0.5, 1.0, 0.5);
 const COLOR_Z_BRIGHT: Color = Color::srgb(0.5, 0.7, 1.0);
+const COLOR_SCREEN: Color = Color::srgb(0.8, 0.8, 0.8);
+const COLOR_SCREEN_BRIGHT: Color = Color::WHITE;
+const COLOR_SWEEP: Color = Color::srgba(1.0, 1.0, 1.0, 0.12);
 const TRANSLATE_SENSITIVITY: f32 = 0.003;
-const ROTATE_SENSITIVITY: f32 = 0.01;
 const SCALE_SENSITIVITY: f32 = 0.005;
 const MIN_SCALE: f32 = 0.01;
 const AXIS_HIT_DISTANCE: f32 = 20.0;
@@ -52,21 +62,58 @@ pub enum GizmoAxis {
     Z,
 }
 
+/// A grabbable part of the transform gizmo.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GizmoHandle {
+    Axis(GizmoAxis),
+    /// Translate square in the plane of the two other axes.
+    Plane(GizmoAxis),
+    /// Center square: translates in the camera plane, or scales uniformly in Scale mode.
+    Screen,
+}
+
+/// Whether scene geometry hides the transform gizmo.
+#[derive(Resource, Default, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum GizmoOcclusion {
+    /// Drawn on top of everything.
+    #[default]
+    XRay,
+    /// Hidden behind geometry in front of it.
+    Occluded,
+}
+
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct TransformGizmoGroup;
+
 #[derive(Resource, Default)]
 pub struct GizmoDragState {
     pub active: bool,
-    pub axis: Option<GizmoAxis>,
+    pub handle: Option<GizmoHandle>,
     pub drag_start_screen: Vec2,
+    /// Where the cursor ray first hit the drag plane, for plane and screen handles.
+    pub drag_start_point: Vec3,
     pub start_transform: Transform,
     pub entity: Option<Entity>,
+    /// Screen angle the cursor has swept around the gizmo center, for rotate drags.
     pub accumulated_delta: f32,
+    pub last_cursor: Vec2,
+    /// Rotation applied so far, in radians, after snapping.
+    pub angle: f32,
+    /// Copies spawned by an Alt-drag, recorded for undo when the drag ends.
+    pub duplicates: Vec<Entity>,
+    /// Copies other than the dragged one, with their start transforms.
+    pub followers: Vec<(Entity, Transform)>,
 }
 
 #[derive(Resource, Default)]
 pub struct GizmoHoverState {
-    pub hovered_axis: Option<GizmoAxis>,
+    pub hovered: Option<GizmoHandle>,
 }
 
+/// Angle label following the cursor during a rotate drag.
+#[derive(Component)]
+struct RotateReadout;
+
 pub struct TransformGizmosPlugin;
 
 impl Plugin for TransformGizmosPlugin {
@@ -75,13 +122,17 @@ impl Plugin for TransformGizmosPlugin {
             .init_resource::<GizmoSpace>()
             .init_resource::<GizmoDragState>()
             .init_resource::<GizmoHoverState>()
+            .init_resource::<GizmoOcclusion>()
+            .init_gizmo_group::<TransformGizmoGroup>()
             .add_systems(
                 Update,
                 (
+                    apply_gizmo_occlusion,
                     handle_gizmo_mode_keys,
                     handle_gizmo_hover,
                     handle_gizmo_drag,
                     draw_gizmos,
+                    update_rotate_readout,
                 )
                     .chain()
                     .run_if(in_state(crate::AppState::Editor)),
@@ -89,6 +140,28 @@ impl Plugin for TransformGizmosPlugin {
     }
 }
 
+pub fn toggle_gizmo_occlusion(world: &mut World) {
+    let mut occlusion = world.resource_mut::<GizmoOcclusion>();
+    *occlusion = match *occlusion {
+        GizmoOcclusion::XRay => GizmoOcclusion::Occluded,
+        GizmoOcclusion::Occluded => GizmoOcclusion::XRay,
+    };
+}
+
+fn apply_gizmo_occlusion(
+    occlusion: Res<GizmoOcclusion>,
+    mut config_store: ResMut<GizmoConfigStore>,
+) {
+    if !occlusion.is_changed() {
+        return;
+    }
+    let (config, _) = config_store.config_mut::<TransformGizmoGroup>();
+    config.depth_bias = match *occlusion {
+        GizmoOcclusion::XRay => -1.0,
+        GizmoOcclusion::Occluded => 0.0,
+    };
+}
+
 fn handle_gizmo_mode_keys(
     keyboard: Res<ButtonInput<KeyCode>>,
     mut mode: ResMut<GizmoMode>,
@@ -128,7 +201,7 @@ fn handle_gizmo_hover(
     selection: Res<Selection>,
     transforms: Query<&GlobalTransform, With<Selected>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     mode: Res<GizmoMode>,
     space: Res<GizmoSpace>,
     mut hover: ResMut<GizmoHoverState>,
@@ -138,7 +211,7 @@ fn handle_gizmo_hover(
     edit_mode: Res<crate::brush::EditMode>,
     draw_state: Res<crate::draw_brush::DrawBrushState>,
 ) {
-    hover.hovered_axis = None;
+    hover.hovered = None;
 
     if drag_state.active || modal.active.is_some() || draw_state.active.is_some() {
         return;
@@ -149,11 +222,6 @@ fn handle_gizmo_hover(
         return;
     }
 
-    // No hover detection in Translate mode (direct drag replaces gizmo)
-    if *mode == GizmoMode::Translate {
-        return;
-    }
-
     let Some(primary) = selection.primary() else {
         return;
     };
@@ -179,46 +247,108 @@ fn handle_gizmo_hover(
 
     let gizmo_pos = global_tf.translation();
     let rotation = gizmo_rotation(global_tf, &space);
-
-    let axes = [
-        (GizmoAxis::X, rotation * Vec3::X),
-        (GizmoAxis::Y, rotation * Vec3::Y),
-        (GizmoAxis::Z, rotation * Vec3::Z),
-    ];
+    let scale = gizmo_scale(camera, cam_tf, gizmo_pos);
 
     // Project gizmo origin and axis endpoints to screen space, find closest axis
     let Some(origin_screen) = camera.world_to_viewport(cam_tf, gizmo_pos).ok() else {
         return;
     };
 
+    if *mode != GizmoMode::Rotate {
+        // The center and plane squares sit over the axes, so they win
+        let screen_half = SCREEN_HANDLE_SIZE * scale * 0.5;
+        let corner = gizmo_pos + (cam_tf.right().as_vec3() + cam_tf.up().as_vec3()) * screen_half;
+        if let Ok(corner_screen) = camera.world_to_viewport(cam_tf, corner) {
+            let half = (corner_screen - origin_screen).abs().max_element();
+            let offset = (viewport_cursor - origin_screen).abs();
+            if offset.x <= half && offset.y <= half {
+                hover.hovered = Some(GizmoHandle::Screen);
+                return;
+            }
+        }
+    }
+    if *mode == GizmoMode::Translate {
+        for axis in [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z] {
+            let corners = plane_handle_corners(gizmo_pos, rotation, axis, scale)
+                .map(|corner| camera.world_to_viewport(cam_tf, corner).ok());
+            if let [Some(a), Some(b), Some(c), Some(d)] = corners
+                && point_in_quad(viewport_cursor, [a, b, c, d])
+            {
+                hover.hovered = Some(GizmoHandle::Plane(axis));
+                return;
+            }
+        }
+    }
+
+    if *mode == GizmoMode::Rotate {
+        let mut best = None;
+        let mut best_dist = AXIS_HIT_DISTANCE;
+        for (handle, normal, radius) in [
+            (
+                GizmoHandle::Screen,
+                cam_tf.back().as_vec3(),
+                SCREEN_RING_RADIUS,
+            ),
+            (
+                GizmoHandle::Axis(GizmoAxis::X),
+                rotation * Vec3::X,
+                ROTATE_RING_RADIUS,
+            ),
+            (
+                GizmoHandle::Axis(GizmoAxis::Y),
+                rotation * Vec3::Y,
+                ROTATE_RING_RADIUS,
+            ),
+            (
+                GizmoHandle::Axis(GizmoAxis::Z),
+                rotation * Vec3::Z,
+                ROTATE_RING_RADIUS,
+            ),
+        ] {
+            let Some(dist) = ring_screen_distance(
+                camera,
+                cam_tf,
+                gizmo_pos,
+                normal,
+                radius * scale,
+                viewport_cursor,
+            ) else {
+                continue;
+            };
+            if dist < best_dist {
+                best_dist = dist;
+                best = Some(handle);
+            }
+        }
+        hover.hovered = best;
+        return;
+    }
+
     let mut best_axis = None;
     let mut best_dist = f32::MAX;
     let threshold = AXIS_HIT_DISTANCE;
 
-    for (axis, dir) in &axes {
-        let endpoint = match *mode {
-            GizmoMode::Translate | GizmoMode::Scale => gizmo_pos + *dir * AXIS_LENGTH,
-            GizmoMode::Rotate => gizmo_pos + *dir * ROTATE_RING_RADIUS,
-        };
+    for axis in [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z] {
+        let endpoint = gizmo_pos + axis_dir(rotation, axis) * AXIS_LENGTH * scale;
         let Some(end_screen) = camera.world_to_viewport(cam_tf, endpoint).ok() else {
             continue;
         };
         let dist = point_to_segment_dist(viewport_cursor, origin_screen, end_screen);
         if dist < threshold && dist < best_dist {
             best_dist = dist;
-            best_axis = Some(*axis);
+            best_axis = Some(axis);
         }
     }
 
-    hover.hovered_axis = best_axis;
+    hover.hovered = best_axis.map(GizmoHandle::Axis);
 }
 
 fn handle_gizmo_drag(
     selection: Res<Selection>,
     mut transforms: Query<(&GlobalTransform, &mut Transform), With<Selected>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
-    windows: Query<&Window>,
-    mut cursor_query: Query<&mut CursorOptions, With<Window>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut cursor_query: Query<&mut CursorOptions, With<PrimaryWindow>>,
     mouse: Res<ButtonInput<MouseButton>>,
     keyboard: Res<ButtonInput<KeyCode>>,
     mode: Res<GizmoMode>,
@@ -229,9 +359,11 @@ fn handle_gizmo_drag(
     snap_settings: Res<SnapSettings>,
     modal: Res<ModalTransformState>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
-    (edit_mode, draw_state): (
+    (edit_mode, draw_state, mut collision, mut commands): (
         Res<crate::brush::EditMode>,
         Res<crate::draw_brush::DrawBrushState>,
+        crate::drag_collision::DragCollision,
+        Commands,
     ),
 ) {
     // Suppress gizmo drag during modal operations, brush edit mode, or draw mode
@@ -245,14 +377,6 @@ fn handle_gizmo_drag(
         return;
     }
 
-    // No gizmo drag in Translate mode
-    if *mode == GizmoMode::Translate {
-        if drag_state.active {
-            drag_state.active = false;
-        }
-        return;
-    }
-
     let Some(primary) = selection.primary() else {
         if drag_state.active {
             drag_state.active = false;
@@ -274,16 +398,35 @@ fn handle_gizmo_drag(
         return;
     };
 
-    // Start drag
+    // Start drag. Alt over a handle duplicates; anywhere else it's left to orbiting
     if mouse.just_pressed(MouseButton::Left) && !drag_state.active {
-        if let Some(axis) = hover.hovered_axis {
-            if let Ok((_, transform)) = transforms.get(primary) {
+        if let Some(handle) = hover.hovered {
+            if let Ok((global_tf, transform)) = transforms.get(primary) {
+                let rotation = gizmo_rotation(global_tf, &space);
+                // A ring seen edge-on misses its plane; the sweep then starts at the center
+                let start_point = drag_plane_hit(
+                    camera,
+                    cam_tf,
+                    viewport_cursor,
+                    global_tf.translation(),
+                    drag_plane_normal(handle, *mode, rotation, cam_tf),
+                )
+                .unwrap_or(global_tf.translation());
                 drag_state.active = true;
-                drag_state.axis = Some(axis);
+                drag_state.handle = Some(handle);
                 drag_state.drag_start_screen = viewport_cursor;
+                drag_state.drag_start_point = start_point;
                 drag_state.start_transform = *transform;
                 drag_state.entity = Some(primary);
                 drag_state.accumulated_delta = 0.0;
+                drag_state.last_cursor = viewport_cursor;
+                drag_state.angle = 0.0;
+                drag_state.duplicates.clear();
+                drag_state.followers.clear();
+                // Alt-drag moves copies of the selection, leaving the originals in place
+                if keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]) {
+                    commands.queue(start_duplicate_drag);
+                }
                 // Confine cursor during drag
                 if let Ok(mut cursor_opts) = cursor_query.single_mut() {
                     cursor_opts.grab_mode = CursorGrabMode::Confined;
@@ -301,21 +444,65 @@ fn handle_gizmo_drag(
         let Ok((global_tf, mut transform)) = transforms.get_mut(entity) else {
             return;
         };
-        let Some(axis) = drag_state.axis else {
+        let Some(handle) = drag_state.handle else {
             return;
         };
 
         let rotation = gizmo_rotation(global_tf, &space);
-        let axis_dir = match axis {
-            GizmoAxis::X => rotation * Vec3::X,
-            GizmoAxis::Y => rotation * Vec3::Y,
-            GizmoAxis::Z => rotation * Vec3::Z,
-        };
-
         let gizmo_pos = global_tf.translation();
+        let ctrl = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
 
-        match *mode {
-            GizmoMode::Translate => {
+        match (*mode, handle) {
+            (GizmoMode::Rotate, _) => {
+                let Some(origin_screen) = camera.world_to_viewport(cam_tf, gizmo_pos).ok() else {
+                    return;
+                };
+                // Follow the cursor around the gizmo center, so full turns keep counting
+                let last = drag_state.last_cursor - origin_screen;
+                let current = viewport_cursor - origin_screen;
+                if last.length_squared() > 1.0 && current.length_squared() > 1.0 {
+                    drag_state.accumulated_delta += last.angle_to(current);
+                }
+                drag_state.last_cursor = viewport_cursor;
+
+                // Screen y points down, so a clockwise sweep is a positive screen angle
+                let axis = rotate_axis(handle, rotation, cam_tf);
+                let raw_angle = if axis.dot(cam_tf.translation() - gizmo_pos) > 0.0 {
+                    -drag_state.accumulated_delta
+                } else {
+                    drag_state.accumulated_delta
+                };
+                let angle = snap_settings.snap_rotate_if(raw_angle, ctrl);
+                drag_state.angle = angle;
+                let rotation_delta = Quat::from_axis_angle(axis, angle);
+                transform.rotation = rotation_delta * drag_state.start_transform.rotation;
+            }
+            (GizmoMode::Scale, GizmoHandle::Screen) => {
+                // Dragging up or right grows, down or left shrinks
+                let mouse_delta = viewport_cursor - drag_state.drag_start_screen;
+                let factor = 1.0 + (mouse_delta.x - mouse_delta.y) * SCALE_SENSITIVITY;
+                let new_scale =
+                    (drag_state.start_transform.scale * factor).max(Vec3::splat(MIN_SCALE));
+                transform.scale = snap_settings.snap_scale_vec3_if(new_scale, ctrl);
+            }
+            // Plane and center squares follow the cursor across their drag plane
+            (_, GizmoHandle::Plane(_) | GizmoHandle::Screen) => {
+                let Some(point) = drag_plane_hit(
+                    camera,
+                    cam_tf,
+                    viewport_cursor,
+                    drag_state.drag_start_point,
+                    drag_plane_normal(handle, *mode, rotation, cam_tf),
+                ) else {
+                    return;
+                };
+                let raw_delta = point - drag_state.drag_start_point;
+                let snapped_delta = snap_settings.snap_translate_vec3_if(raw_delta, ctrl);
+                let snapped_delta = collision.limit(entity, snapped_delta);
+                transform.translation = drag_state.start_transform.translation + snapped_delta;
+            }
+            (GizmoMode::Translate, GizmoHandle::Axis(axis)) => {
+                let axis_dir = axis_dir(rotation, axis);
                 // Project mouse movement onto axis in screen space
                 let Some(origin_screen) = camera.world_to_viewport(cam_tf, gizmo_pos).ok() else {
                     return;
@@ -332,25 +519,13 @@ fn handle_gizmo_drag(
                 let cam_dist = (cam_tf.translation() - gizmo_pos).length();
                 let scale = cam_dist * TRANSLATE_SENSITIVITY;
 
-                let ctrl = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
                 let raw_delta = axis_dir * projected * scale;
                 let snapped_delta = snap_settings.snap_translate_vec3_if(raw_delta, ctrl);
+                let snapped_delta = collision.limit(entity, snapped_delta);
                 transform.translation = drag_state.start_transform.translation + snapped_delta;
             }
-            GizmoMode::Rotate => {
-                let mouse_delta = viewport_cursor - drag_state.drag_start_screen;
-                let screen_axis = match axis {
-                    GizmoAxis::X => Vec2::Y,
-                    GizmoAxis::Y => Vec2::X,
-                    GizmoAxis::Z => Vec2::X,
-                };
-                let ctrl = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
-                let raw_angle = mouse_delta.dot(screen_axis) * ROTATE_SENSITIVITY;
-                let angle = snap_settings.snap_rotate_if(raw_angle, ctrl);
-                let rotation_delta = Quat::from_axis_angle(axis_dir, angle);
-                transform.rotation = rotation_delta * drag_state.start_transform.rotation;
-            }
-            GizmoMode::Scale => {
+            (GizmoMode::Scale, GizmoHandle::Axis(axis)) => {
+                let axis_dir = axis_dir(rotation, axis);
                 let Some(origin_screen) = camera.world_to_viewport(cam_tf, gizmo_pos).ok() else {
                     return;
                 };
@@ -362,35 +537,61 @@ fn handle_gizmo_drag(
                 let mouse_delta = viewport_cursor - drag_state.drag_start_screen;
                 let projected = mouse_delta.dot(screen_axis) * SCALE_SENSITIVITY;
 
-                let ctrl = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
-                let mut new_scale = drag_state.start_transform.scale;
-                match axis {
-                    GizmoAxis::X => new_scale.x = (new_scale.x + projected).max(MIN_SCALE),
-                    GizmoAxis::Y => new_scale.y = (new_scale.y + projected).max(MIN_SCALE),
-                    GizmoAxis::Z => new_scale.z = (new_scale.z + projected).max(MIN_SCALE),
-                }
+                let mask = match axis {
+                    GizmoAxis::X => Vec3::X,
+                    GizmoAxis::Y => Vec3::Y,
+                    GizmoAxis::Z => Vec3::Z,
+                };
+                // Shift scales the two other axes instead
+                let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+                let mask = if shift { Vec3::ONE - mask } else { mask };
+                let new_scale = (drag_state.start_transform.scale + mask * projected)
+                    .max(Vec3::splat(MIN_SCALE));
                 transform.scale = snap_settings.snap_scale_vec3_if(new_scale, ctrl);
             }
         }
+
+        // The rest of an Alt-drag's copies follow the primary's change, turning and
+        // scaling about it
+        let current = *transform;
+        let start = drag_state.start_transform;
+        let rotation_delta = current.rotation * start.rotation.inverse();
+        let scale_ratio = Vec3::select(
+            start.scale.abs().cmpgt(Vec3::splat(f32::EPSILON)),
+            current.scale / start.scale,
+            Vec3::ONE,
+        );
+        for &(follower, follower_start) in &drag_state.followers {
+            if let Ok((_, mut t)) = transforms.get_mut(follower) {
+                let offset =
+                    start.rotation.inverse() * (follower_start.translation - start.translation);
+                t.translation = current.translation + current.rotation * (offset * scale_ratio);
+                t.rotation = rotation_delta * follower_start.rotation;
+                t.scale = follower_start.scale * scale_ratio;
+            }
+        }
         return;
     }
 
     // End drag — push undo command
     if drag_state.active && mouse.just_released(MouseButton::Left) {
-        if let Some(entity) = drag_state.entity {
+        if !drag_state.duplicates.is_empty() {
+            let duplicates = std::mem::take(&mut drag_state.duplicates);
+            commands.queue(move |world: &mut World| commit_duplicate_drag(world, &duplicates));
+        } else if let Some(entity) = drag_state.entity {
             if let Ok((_, transform)) = transforms.get(entity) {
                 let cmd = SetTransform {
                     entity,
                     old_transform: drag_state.start_transform,
                     new_transform: *transform,
                 };
-                history.undo_stack.push(Box::new(cmd));
-                history.redo_stack.clear();
+                history.push_executed(Box::new(cmd));
             }
         }
         drag_state.active = false;
-        drag_state.axis = None;
+        drag_state.handle = None;
         drag_state.entity = None;
+        drag_state.followers.clear();
         // Release cursor confinement
         if let Ok(mut cursor_opts) = cursor_query.single_mut() {
             cursor_opts.grab_mode = CursorGrabMode::None;
@@ -398,10 +599,124 @@ fn handle_gizmo_drag(
     }
 }
 
+/// Duplicate the selection and move the drag over to the copies.
+fn start_duplicate_drag(world: &mut World) {
+    let originals = world.resource::<Selection>().entities.clone();
+    let mut copies = Vec::new();
+    for &original in &originals {
+        if let Some(copy) = crate::entity_ops::duplicate_entity(world, original) {
+            copies.push(copy);
+        }
+    }
+    let Some(&primary) = copies.last() else {
+        return;
+    };
+
+    for &original in &originals {
+        if let Ok(mut ec) = world.get_entity_mut(original) {
+            ec.remove::<Selected>();
+        }
+    }
+    for &copy in &copies {
+        world.entity_mut(copy).insert(Selected);
+    }
+    world.resource_mut::<Selection>().entities = copies.clone();
+
+    let followers = copies
+        .iter()
+        .filter(|&&copy| copy != primary)
+        .filter_map(|&copy| world.get::<Transform>(copy).map(|t| (copy, *t)))
+        .collect();
+    let mut drag_state = world.resource_mut::<GizmoDragState>();
+    drag_state.entity = Some(primary);
+    drag_state.duplicates = copies;
+    drag_state.followers = followers;
+}
+
+/// Record an Alt-drag's copies, at where they were dropped, as one undo step.
+fn commit_duplicate_drag(world: &mut World, duplicates: &[Entity]) {
+    let cmds: Vec<Box<dyn EditorCommand>> = duplicates
+        .iter()
+        .filter(|&&copy| world.get_entity(copy).is_ok())
+        .map(|&copy| {
+            Box::new(SpawnSnapshot::from_world(
+                world,
+                &[copy],
+                "Duplicate entity",
+            )) as Box<dyn EditorCommand>
+        })
+        .collect();
+    if cmds.is_empty() {
+        return;
+    }
+    world
+        .resource_mut::<CommandHistory>()
+        .push_executed(Box::new(CommandGroup {
+            commands: cmds,
+            label: "Duplicate and move".to_string(),
+        }));
+}
+
+fn update_rotate_readout(
+    mut commands: Commands,
+    drag_state: Res<GizmoDragState>,
+    mode: Res<GizmoMode>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    viewport_query: Query<(Entity, &ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
+    mut readouts: Query<(Entity, &mut Node, &mut Text), With<RotateReadout>>,
+) {
+    let cursor = windows.single().ok().and_then(Window::cursor_position);
+    let (true, GizmoMode::Rotate, Some(cursor), Ok((viewport, computed, vp_tf))) =
+        (drag_state.active, *mode, cursor, viewport_query.single())
+    else {
+        for (readout, ..) in &readouts {
+            commands.entity(readout).despawn();
+        }
+        return;
+    };
+
+    // Position in the viewport node's logical space, just below-right of the cursor
+    let scale = computed.inverse_scale_factor();
+    let vp_top_left = vp_tf.translation * scale - computed.size() * scale / 2.0;
+    let local = cursor - vp_top_left + Vec2::splat(tokens::SPACING_LG);
+    let label = format!("{:.1}°", drag_state.angle.to_degrees());
+
+    if let Ok((_, mut node, mut text)) = readouts.single_mut() {
+        node.left = px(local.x);
+        node.top = px(local.y);
+        if text.0 != label {
+            text.0 = label;
+        }
+        return;
+    }
+    commands.spawn((
+        RotateReadout,
+        EditorEntity,
+        Text::new(label),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_PRIMARY),
+        Node {
+            position_type: PositionType::Absolute,
+            left: px(local.x),
+            top: px(local.y),
+            padding: UiRect::axes(px(tokens::SPACING_SM), px(tokens::SPACING_XS)),
+            border_radius: BorderRadius::all(tokens::CORNER_RADIUS_LG),
+            ..Default::default()
+        },
+        BackgroundColor(tokens::PANEL_BG.with_alpha(0.92)),
+        Pickable::IGNORE,
+        ChildOf(viewport),
+    ));
+}
+
 fn draw_gizmos(
-    mut gizmos: Gizmos,
+    mut gizmos: Gizmos<TransformGizmoGroup>,
     selection: Res<Selection>,
     transforms: Query<&GlobalTransform, With<Selected>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     mode: Res<GizmoMode>,
     space: Res<GizmoSpace>,
     hover: Res<GizmoHoverState>,
@@ -414,105 +729,181 @@ fn draw_gizmos(
         return;
     }
 
-    // Don't draw in Translate mode (no gizmo)
-    if *mode == GizmoMode::Translate {
-        return;
-    }
-
     let Some(primary) = selection.primary() else {
         return;
     };
     let Ok(global_tf) = transforms.get(primary) else {
         return;
     };
+    let Ok((camera, cam_tf)) = camera_query.single() else {
+        return;
+    };
 
     let pos = global_tf.translation();
     let rotation = gizmo_rotation(global_tf, &space);
+    let scale = gizmo_scale(camera, cam_tf, pos);
 
     let right = rotation * Vec3::X;
     let up = rotation * Vec3::Y;
     let forward = rotation * Vec3::Z;
 
-    let active_axis = if drag_state.active {
-        drag_state.axis
+    let active = if drag_state.active {
+        drag_state.handle
     } else {
-        hover.hovered_axis
+        hover.hovered
     };
 
-    let x_color = axis_color(GizmoAxis::X, active_axis);
-    let y_color = axis_color(GizmoAxis::Y, active_axis);
-    let z_color = axis_color(GizmoAxis::Z, active_axis);
+    let x_color = axis_color(
+        GizmoAxis::X,
+        active == Some(GizmoHandle::Axis(GizmoAxis::X)),
+    );
+    let y_color = axis_color(
+        GizmoAxis::Y,
+        active == Some(GizmoHandle::Axis(GizmoAxis::Y)),
+    );
+    let z_color = axis_color(
+        GizmoAxis::Z,
+        active == Some(GizmoHandle::Axis(GizmoAxis::Z)),
+    );
 
     match *mode {
         GizmoMode::Translate => {
+            let length = AXIS_LENGTH * scale;
+            let tip = AXIS_TIP_LENGTH * scale;
             gizmos
-                .arrow(pos, pos + right * AXIS_LENGTH, x_color)
-                .with_tip_length(AXIS_TIP_LENGTH);
+                .arrow(pos, pos + right * length, x_color)
+                .with_tip_length(tip);
             gizmos
-                .arrow(pos, pos + up * AXIS_LENGTH, y_color)
-                .with_tip_length(AXIS_TIP_LENGTH);
+                .arrow(pos, pos + up * length, y_color)
+                .with_tip_length(tip);
             gizmos
-                .arrow(pos, pos + forward * AXIS_LENGTH, z_color)
-                .with_tip_length(AXIS_TIP_LENGTH);
+                .arrow(pos, pos + forward * length, z_color)
+                .with_tip_length(tip);
+
+            // Plane squares, colored by the axis normal to the plane
+            for axis in [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z] {
+                let color = axis_color(axis, active == Some(GizmoHandle::Plane(axis)));
+                let corners = plane_handle_corners(pos, rotation, axis, scale);
+                for i in 0..4 {
+                    gizmos.line(corners[i], corners[(i + 1) % 4], color);
+                }
+            }
+
+            let screen_color = if active == Some(GizmoHandle::Screen) {
+                COLOR_SCREEN_BRIGHT
+            } else {
+                COLOR_SCREEN
+            };
+            gizmos.rect(
+                Isometry3d::new(pos, cam_tf.rotation()),
+                Vec2::splat(SCREEN_HANDLE_SIZE * scale),
+                screen_color,
+            );
         }
         GizmoMode::Rotate => {
             // Draw rotation rings
+            let radius = ROTATE_RING_RADIUS * scale;
             gizmos.circle(
                 Isometry3d::new(pos, Quat::from_rotation_arc(Vec3::Z, right)),
-                ROTATE_RING_RADIUS,
+                radius,
                 x_color,
             );
             gizmos.circle(
                 Isometry3d::new(pos, Quat::from_rotation_arc(Vec3::Z, up)),
-                ROTATE_RING_RADIUS,
+                radius,
                 y_color,
             );
             gizmos.circle(
                 Isometry3d::new(pos, Quat::from_rotation_arc(Vec3::Z, forward)),
-                ROTATE_RING_RADIUS,
+                radius,
                 z_color,
             );
+            // Outer ring turning about the view axis
+            let screen_color = if active == Some(GizmoHandle::Screen) {
+                COLOR_SCREEN_BRIGHT
+            } else {
+                COLOR_SCREEN
+            };
+            gizmos.circle(
+                Isometry3d::new(pos, cam_tf.rotation()),
+                SCREEN_RING_RADIUS * scale,
+                screen_color,
+            );
+
+            if let (true, Some(handle)) = (drag_state.active, drag_state.handle) {
+                draw_rotation_sweep(
+                    &mut gizmos,
+                    &drag_state,
+                    handle,
+                    pos,
+                    rotation,
+                    cam_tf,
+                    scale,
+                );
+            }
         }
         GizmoMode::Scale => {
-            // Draw scale handles: lines with cubes at the end
-            let cube_half = SCALE_CUBE_SIZE;
+            // Draw scale handles: lines with cubes at the end, and a uniform cube at the center
+            let cube_size = SCALE_CUBE_SIZE * 2.0 * scale;
             for (dir, color) in [(right, x_color), (up, y_color), (forward, z_color)] {
-                let end = pos + dir * AXIS_LENGTH;
+                let end = pos + dir * AXIS_LENGTH * scale;
                 gizmos.line(pos, end, color);
-                // Draw a small cube at the end using lines
-                let x = Vec3::X * cube_half;
-                let y = Vec3::Y * cube_half;
-                let z = Vec3::Z * cube_half;
-                let corners = [
-                    end - x - y - z,
-                    end + x - y - z,
-                    end + x + y - z,
-                    end - x + y - z,
-                    end - x - y + z,
-                    end + x - y + z,
-                    end + x + y + z,
-                    end - x + y + z,
-                ];
-                // Bottom face
-                gizmos.line(corners[0], corners[1], color);
-                gizmos.line(corners[1], corners[2], color);
-                gizmos.line(corners[2], corners[3], color);
-                gizmos.line(corners[3], corners[0], color);
-                // Top face
-                gizmos.line(corners[4], corners[5], color);
-                gizmos.line(corners[5], corners[6], color);
-                gizmos.line(corners[6], corners[7], color);
-                gizmos.line(corners[7], corners[4], color);
-                // Verticals
-                gizmos.line(corners[0], corners[4], color);
-                gizmos.line(corners[1], corners[5], color);
-                gizmos.line(corners[2], corners[6], color);
-                gizmos.line(corners[3], corners[7], color);
+                gizmos.cube(
+                    Transform::from_translation(end)
+                        .with_rotation(rotation)
+                        .with_scale(Vec3::splat(cube_size)),
+                    color,
+                );
             }
+            let center_color = if active == Some(GizmoHandle::Screen) {
+                COLOR_SCREEN_BRIGHT
+            } else {
+                COLOR_SCREEN
+            };
+            gizmos.cube(
+                Transform::from_translation(pos)
+                    .with_rotation(rotation)
+                    .with_scale(Vec3::splat(SCREEN_HANDLE_SIZE * scale)),
+                center_color,
+            );
         }
     }
 }
 
+/// Shade the arc a rotate drag has swept, from where it started to the current angle.
+fn draw_rotation_sweep(
+    gizmos: &mut Gizmos<TransformGizmoGroup>,
+    drag_state: &GizmoDragState,
+    handle: GizmoHandle,
+    pos: Vec3,
+    rotation: Quat,
+    cam_tf: &GlobalTransform,
+    scale: f32,
+) {
+    let axis = rotate_axis(handle, rotation, cam_tf);
+    let from = (drag_state.drag_start_point - pos)
+        .reject_from(axis)
+        .normalize_or_zero();
+    if from == Vec3::ZERO {
+        return;
+    }
+    let radius = match handle {
+        GizmoHandle::Screen => SCREEN_RING_RADIUS,
+        _ => ROTATE_RING_RADIUS,
+    } * scale;
+
+    // A fan of spokes, one every few degrees, reads as a filled wedge
+    let steps = ((drag_state.angle.abs() / 0.05).ceil() as usize).clamp(1, 128);
+    for i in 0..=steps {
+        let angle = drag_state.angle * i as f32 / steps as f32;
+        let spoke = Quat::from_axis_angle(axis, angle) * from * radius;
+        gizmos.line(pos, pos + spoke, COLOR_SWEEP);
+    }
+    let end = Quat::from_axis_angle(axis, drag_state.angle) * from * radius;
+    gizmos.line(pos, pos + from * radius, COLOR_SCREEN);
+    gizmos.line(pos, pos + end, COLOR_SCREEN_BRIGHT);
+}
+
 fn gizmo_rotation(global_tf: &GlobalTransform, space: &GizmoSpace) -> Quat {
     match space {
         GizmoSpace::World => Quat::IDENTITY,
@@ -523,8 +914,121 @@ fn gizmo_rotation(global_tf: &GlobalTransform, space: &GizmoSpace) -> Quat {
     }
 }
 
-fn axis_color(axis: GizmoAxis, active: Option<GizmoAxis>) -> Color {
-    let is_active = active == Some(axis);
+/// World units per gizmo unit at `pos`, keeping the gizmo a constant size on screen.
+fn gizmo_scale(camera: &Camera, cam_tf: &GlobalTransform, pos: Vec3) -> f32 {
+    let (Ok(origin), Ok(side)) = (
+        camera.world_to_viewport(cam_tf, pos),
+        camera.world_to_viewport(cam_tf, pos + cam_tf.right().as_vec3()),
+    ) else {
+        return 1.0;
+    };
+    let pixels_per_unit = origin.distance(side);
+    if pixels_per_unit <= f32::EPSILON {
+        return 1.0;
+    }
+    GIZMO_SCREEN_SIZE / (AXIS_LENGTH * pixels_per_unit)
+}
+
+fn axis_dir(rotation: Quat, axis: GizmoAxis) -> Vec3 {
+    match axis {
+        GizmoAxis::X => rotation * Vec3::X,
+        GizmoAxis::Y => rotation * Vec3::Y,
+        GizmoAxis::Z => rotation * Vec3::Z,
+    }
+}
+
+/// Corners of the square translating in the plane normal to `axis`, in winding order.
+fn plane_handle_corners(pos: Vec3, rotation: Quat, axis: GizmoAxis, scale: f32) -> [Vec3; 4] {
+    let (a, b) = match axis {
+        GizmoAxis::X => (GizmoAxis::Y, GizmoAxis::Z),
+        GizmoAxis::Y => (GizmoAxis::X, GizmoAxis::Z),
+        GizmoAxis::Z => (GizmoAxis::X, GizmoAxis::Y),
+    };
+    let a = axis_dir(rotation, a) * scale;
+    let b = axis_dir(rotation, b) * scale;
+    let near = PLANE_HANDLE_OFFSET;
+    let far = PLANE_HANDLE_OFFSET + PLANE_HANDLE_SIZE;
+    [
+        pos + a * near + b * near,
+        pos + a * far + b * near,
+        pos + a * far + b * far,
+        pos + a * near + b * far,
+    ]
+}
+
+/// Normal of the plane a handle drags in: the plane square's own plane, a rotation
+/// ring's plane, or the camera plane otherwise.
+fn drag_plane_normal(
+    handle: GizmoHandle,
+    mode: GizmoMode,
+    rotation: Quat,
+    cam_tf: &GlobalTransform,
+) -> Vec3 {
+    match (handle, mode) {
+        (GizmoHandle::Plane(axis), _) | (GizmoHandle::Axis(axis), GizmoMode::Rotate) => {
+            axis_dir(rotation, axis)
+        }
+        _ => cam_tf.forward().as_vec3(),
+    }
+}
+
+/// Axis a rotate handle turns about: its own axis, or the view axis for the outer ring.
+fn rotate_axis(handle: GizmoHandle, rotation: Quat, cam_tf: &GlobalTransform) -> Vec3 {
+    match handle {
+        GizmoHandle::Axis(axis) | GizmoHandle::Plane(axis) => axis_dir(rotation, axis),
+        GizmoHandle::Screen => cam_tf.back().as_vec3(),
+    }
+}
+
+/// Screen distance from `cursor` to a ring of `radius` around `center`, sampled as a
+/// polyline.
+fn ring_screen_distance(
+    camera: &Camera,
+    cam_tf: &GlobalTransform,
+    center: Vec3,
+    normal: Vec3,
+    radius: f32,
+    cursor: Vec2,
+) -> Option<f32> {
+    const SEGMENTS: usize = 48;
+    let orientation = Quat::from_rotation_arc(Vec3::Z, normal.normalize_or_zero());
+    let points: Vec<Vec2> = (0..=SEGMENTS)
+        .filter_map(|i| {
+            let angle = i as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+            let offset = orientation * Vec3::new(angle.cos(), angle.sin(), 0.0) * radius;
+            camera.world_to_viewport(cam_tf, center + offset).ok()
+        })
+        .collect();
+    points
+        .windows(2)
+        .map(|pair| point_to_segment_dist(cursor, pair[0], pair[1]))
+        .min_by(f32::total_cmp)
+}
+
+/// Where the cursor ray meets the plane through `origin` with `normal`.
+fn drag_plane_hit(
+    camera: &Camera,
+    cam_tf: &GlobalTransform,
+    viewport_cursor: Vec2,
+    origin: Vec3,
+    normal: Vec3,
+) -> Option<Vec3> {
+    let ray = camera.viewport_to_world(cam_tf, viewport_cursor).ok()?;
+    let normal = Dir3::new(normal).ok()?;
+    let distance = ray.intersect_plane(origin, InfinitePlane3d::new(normal))?;
+    Some(ray.get_point(distance))
+}
+
+/// Whether `point` lies inside the convex quad with corners in winding order.
+fn point_in_quad(point: Vec2, corners: [Vec2; 4]) -> bool {
+    let sides = [0, 1, 2, 3].map(|i| {
+        let edge = corners[(i + 1) % 4] - corners[i];
+        edge.perp_dot(point - corners[i])
+    });
+    sides.iter().all(|&side| side >= 0.0) || sides.iter().all(|&side| side <= 0.0)
+}
+
+fn axis_color(axis: GizmoAxis, is_active: bool) -> Color {
     match axis {
         GizmoAxis::X => {
             if is_active {
@@ -0,0 +1,231 @@
+//! Re-instantiates glTF models placed in the scene when their files change on disk.
+//! The root entity is kept, so its transform and components stay as they are, and
+//! transform/visibility edits made to the model's named nodes are carried over.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    time::Duration,
+};
+
+use bevy::{prelude::*, scene::SceneInstanceReady};
+use jackdaw_feathers::{
+    icons::{EditorFont, IconFont},
+    toast::{ToastVariant, toast},
+};
+
+use crate::{
+    EditorEntity,
+    entity_ops::GltfSource,
+    project::ProjectRoot,
+    project_watcher::{ProjectFilesChanged, resolve_asset_file},
+};
+
+pub struct GltfReloadPlugin;
+
+impl Plugin for GltfReloadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(on_gltf_instance_ready).add_systems(
+            Update,
+            (reload_changed_gltfs, capture_gltf_overrides)
+                .chain()
+                .after(crate::project_watcher::publish_file_changes)
+                .run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+/// Node transforms and visibilities as the glTF spawned them, by node path.
+#[derive(Component, Default)]
+struct GltfSpawnedNodes(HashMap<String, (Transform, Visibility)>);
+
+/// Nodes edited away from [`GltfSpawnedNodes`], to reapply once the reloaded
+/// model has been spawned.
+#[derive(Component, Default)]
+struct GltfReloadOverrides(HashMap<String, (Transform, Visibility)>);
+
+fn is_gltf_file_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| matches!(e.to_lowercase().as_str(), "gltf" | "glb" | "bin"))
+}
+
+/// Ask the asset server to reload changed glTF files that the scene uses. A changed
+/// `.bin` buffer reloads the `.gltf` files next to it.
+fn reload_changed_gltfs(
+    mut changes: MessageReader<ProjectFilesChanged>,
+    project: Option<Res<ProjectRoot>>,
+    asset_server: Res<AssetServer>,
+    sources: Query<&SceneRoot, With<GltfSource>>,
+) {
+    let changed: HashSet<&Path> = changes
+        .read()
+        .flat_map(|change| &change.modified)
+        .filter(|path| is_gltf_file_path(path))
+        .map(|path| path.as_path())
+        .collect();
+    let Some(project) = project else {
+        return;
+    };
+    if changed.is_empty() {
+        return;
+    }
+
+    let root = project.assets_dir();
+    let changed_buffer_dirs: HashSet<&Path> = changed
+        .iter()
+        .filter(|p| p.extension().is_some_and(|e| e.eq_ignore_ascii_case("bin")))
+        .filter_map(|p| p.parent())
+        .collect();
+    let mut reloaded = HashSet::new();
+    for scene_root in &sources {
+        let Some(path) = asset_server.get_path(scene_root.0.id()) else {
+            continue;
+        };
+        let path = path.without_label().into_owned();
+        let file = resolve_asset_file(&root, &path);
+        let buffer_changed = file
+            .parent()
+            .is_some_and(|dir| changed_buffer_dirs.contains(dir));
+        if (changed.contains(file.as_path()) || buffer_changed) && reloaded.insert(path.clone()) {
+            asset_server.reload(path);
+        }
+    }
+}
+
+/// Path of `entity` below `root`, from the names of the nodes in between.
+fn node_path(
+    entity: Entity,
+    root: Entity,
+    names: &Query<&Name>,
+    parents: &Query<&ChildOf>,
+) -> Option<String> {
+    let mut segments = Vec::new();
+    let mut current = entity;
+    while current != root {
+        segments.push(names.get(current).ok()?.as_str().to_string());
+        current = parents.get(current).ok()?.parent();
+    }
+    segments.reverse();
+    Some(segments.join("/"))
+}
+
+fn named_nodes(
+    root: Entity,
+    children: &Query<&Children>,
+    names: &Query<&Name>,
+    parents: &Query<&ChildOf>,
+    nodes: &Query<(&Transform, &Visibility)>,
+) -> HashMap<String, (Transform, Visibility)> {
+    children
+        .iter_descendants(root)
+        .filter_map(|entity| {
+            let path = node_path(entity, root, names, parents)?;
+            let (transform, visibility) = nodes.get(entity).ok()?;
+            Some((path, (*transform, *visibility)))
+        })
+        .collect()
+}
+
+/// A reloaded glTF scene is respawned by the scene spawner later this frame; record
+/// which nodes were edited before their entities go away.
+fn capture_gltf_overrides(
+    mut commands: Commands,
+    mut events: MessageReader<AssetEvent<Scene>>,
+    sources: Query<(Entity, &SceneRoot, Option<&GltfSpawnedNodes>), With<GltfSource>>,
+    children: Query<&Children>,
+    names: Query<&Name>,
+    parents: Query<&ChildOf>,
+    nodes: Query<(&Transform, &Visibility)>,
+) {
+    let modified: HashSet<AssetId<Scene>> = events
+        .read()
+        .filter_map(|event| match *event {
+            AssetEvent::Modified { id } => Some(id),
+            _ => None,
+        })
+        .collect();
+    if modified.is_empty() {
+        return;
+    }
+    for (root, scene_root, spawned) in &sources {
+        if !modified.contains(&scene_root.0.id()) {
+            continue;
+        }
+        let mut overrides = GltfReloadOverrides::default();
+        if let Some(spawned) = spawned {
+            for (path, current) in named_nodes(root, &children, &names, &parents, &nodes) {
+                if spawned
+                    .0
+                    .get(&path)
+                    .is_some_and(|original| *original != current)
+                {
+                    overrides.0.insert(path, current);
+                }
+            }
+        }
+        commands.entity(root).insert(overrides);
+    }
+}
+
+/// Remember how the model spawned, and after a reload put the edited nodes back.
+fn on_gltf_instance_ready(
+    trigger: On<SceneInstanceReady>,
+    mut commands: Commands,
+    sources: Query<(&GltfSource, Option<&GltfReloadOverrides>)>,
+    children: Query<&Children>,
+    names: Query<&Name>,
+    parents: Query<&ChildOf>,
+    mut nodes: Query<(&mut Transform, &mut Visibility)>,
+    editor_font: Res<EditorFont>,
+    icon_font: Res<IconFont>,
+) {
+    let root = trigger.event_target();
+    let Ok((source, overrides)) = sources.get(root) else {
+        return;
+    };
+
+    let spawned = {
+        let nodes = nodes.as_readonly();
+        named_nodes(root, &children, &names, &parents, &nodes)
+    };
+    commands
+        .entity(root)
+        .insert(GltfSpawnedNodes(spawned))
+        .remove::<GltfReloadOverrides>();
+
+    // First spawn: nothing was reloaded.
+    let Some(overrides) = overrides else {
+        return;
+    };
+    let mut restored = 0;
+    for entity in children.iter_descendants(root) {
+        let Some(path) = node_path(entity, root, &names, &parents) else {
+            continue;
+        };
+        let Some((transform, visibility)) = overrides.0.get(&path) else {
+            continue;
+        };
+        if let Ok((mut t, mut v)) = nodes.get_mut(entity) {
+            *t = *transform;
+            *v = *visibility;
+            restored += 1;
+        }
+    }
+
+    let name = Path::new(&source.path)
+        .file_name()
+        .map(|f| f.to_string_lossy().into_owned())
+        .unwrap_or_else(|| source.path.clone());
+    info!("Reloaded {name} ({restored} edited nodes kept)");
+    commands.spawn((
+        EditorEntity,
+        toast(
+            ToastVariant::Info,
+            format!("Reloaded {name}"),
+            Duration::from_secs(2),
+            &editor_font.0,
+            &icon_font.0,
+        ),
+    ));
+}
@@ -0,0 +1,427 @@
+use std::sync::Mutex;
+
+use bevy::{ecs::entity::EntityHashMap, input_focus::InputFocus, prelude::*};
+use jackdaw_jsn::{Brush, FuncGroup, InstanceGroup};
+
+use crate::{
+    EditorEntity, EditorHidden,
+    commands::{CommandGroup, CommandHistory, EditorCommand, RespawnedEntities, remap_entity},
+    selection::{Selected, Selection},
+    status_bar::StatusHints,
+};
+
+const ENTERED_GROUP_HINTS: &str = "entered_group";
+
+pub struct GroupingPlugin;
+
+impl Plugin for GroupingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnteredGroup>().add_systems(
+            Update,
+            (
+                exit_group_key,
+                validate_entered_group,
+                update_entered_group_hints,
+            )
+                .chain()
+                .run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+/// The func group whose brushes are being edited individually. Clicks in the viewport
+/// select its members instead of the group itself.
+#[derive(Resource, Default)]
+pub struct EnteredGroup(pub Option<Entity>);
+
+/// One entity moved into (or out of) a group.
+pub struct GroupMember {
+    pub entity: Entity,
+    /// Parent while outside the group.
+    pub outer_parent: Option<Entity>,
+    /// Local transform while outside the group.
+    pub outer_transform: Transform,
+    /// Local transform while inside the group.
+    pub inner_transform: Transform,
+}
+
+/// Wraps entities under an empty parent, or (with `ungroup`) dissolves one.
+///
+/// The empty is respawned on every regroup, so its current id is tracked here and the
+/// rest of the history is pointed at the new id.
+pub struct GroupEntities {
+    group: Mutex<Entity>,
+    pub name: String,
+    pub parent: Option<Entity>,
+    pub transform: Transform,
+    /// Makes the empty a func group.
+    pub func_group: Option<FuncGroup>,
+    pub members: Vec<GroupMember>,
+    pub ungroup: bool,
+}
+
+impl GroupEntities {
+    /// Current id of the group empty.
+    pub fn group(&self) -> Entity {
+        self.group.lock().map(|g| *g).unwrap_or(Entity::PLACEHOLDER)
+    }
+
+    fn form(&self, world: &mut World) {
+        let mut ec = world.spawn((
+            Name::new(self.name.clone()),
+            self.transform,
+            Visibility::default(),
+        ));
+        if let Some(parent) = self.parent {
+            ec.insert(ChildOf(parent));
+        }
+        if let Some(func_group) = self.func_group {
+            ec.insert(func_group);
+        }
+        let group = ec.id();
+        for member in &self.members {
+            if let Ok(mut ec) = world.get_entity_mut(member.entity) {
+                ec.insert((ChildOf(group), member.inner_transform));
+            }
+        }
+        let previous = self
+            .group
+            .lock()
+            .map(|mut current| std::mem::replace(&mut *current, group))
+            .unwrap_or(Entity::PLACEHOLDER);
+        if previous != Entity::PLACEHOLDER
+            && let Some(mut pending) = world.get_resource_mut::<RespawnedEntities>()
+        {
+            pending.0.insert(previous, group);
+        }
+    }
+
+    fn dissolve(&self, world: &mut World) {
+        for member in &self.members {
+            let Ok(mut ec) = world.get_entity_mut(member.entity) else {
+                continue;
+            };
+            ec.insert(member.outer_transform);
+            match member.outer_parent {
+                Some(parent) => {
+                    ec.insert(ChildOf(parent));
+                }
+                None => {
+                    ec.remove::<ChildOf>();
+                }
+            }
+        }
+        if let Ok(mut ec) = world.get_entity_mut(self.group()) {
+            ec.remove::<Selected>();
+            ec.despawn();
+        }
+    }
+}
+
+impl EditorCommand for GroupEntities {
+    fn execute(&self, world: &mut World) {
+        if self.ungroup {
+            self.dissolve(world);
+        } else {
+            self.form(world);
+        }
+    }
+
+    fn undo(&self, world: &mut World) {
+        if self.ungroup {
+            self.form(world);
+        } else {
+            self.dissolve(world);
+        }
+    }
+
+    fn description(&self) -> &str {
+        if self.ungroup { "Ungroup" } else { "Group" }
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        if let Ok(group) = self.group.get_mut() {
+            remap_entity(group, map);
+        }
+        if let Some(parent) = &mut self.parent {
+            remap_entity(parent, map);
+        }
+        for member in &mut self.members {
+            remap_entity(&mut member.entity, map);
+            if let Some(parent) = &mut member.outer_parent {
+                remap_entity(parent, map);
+            }
+        }
+    }
+}
+
+fn parent_global_transform(world: &World, parent: Option<Entity>) -> GlobalTransform {
+    parent
+        .and_then(|p| world.get::<GlobalTransform>(p).copied())
+        .unwrap_or_default()
+}
+
+fn is_descendant_of_any(world: &World, entity: Entity, others: &[Entity]) -> bool {
+    let mut current = entity;
+    while let Some(&ChildOf(parent)) = world.get::<ChildOf>(current) {
+        if others.contains(&parent) {
+            return true;
+        }
+        current = parent;
+    }
+    false
+}
+
+fn median(mut values: Vec<f32>) -> f32 {
+    values.sort_by(f32::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) * 0.5
+    } else {
+        values[mid]
+    }
+}
+
+/// Wrap the selected entities under a new "Group" empty placed at their median point.
+pub fn group_selected(world: &mut World) {
+    group_selection(world, "Group", None, |_, _| true);
+}
+
+/// Wrap the selected brushes in a new func group, which selects, moves and exports as
+/// one object.
+pub fn func_group_selected(world: &mut World) {
+    let func_group = FuncGroup::default();
+    group_selection(world, "Func Group", Some(func_group), |world, e| {
+        world.get::<Brush>(e).is_some()
+    });
+}
+
+fn group_selection(
+    world: &mut World,
+    name: &str,
+    func_group: Option<FuncGroup>,
+    include: impl Fn(&World, Entity) -> bool,
+) {
+    let selected = world.resource::<Selection>().entities.clone();
+    let entities: Vec<Entity> = selected
+        .iter()
+        .copied()
+        .filter(|&e| {
+            world.get_entity(e).is_ok()
+                && world.get::<EditorEntity>(e).is_none()
+                && world.get::<Transform>(e).is_some()
+                && !is_descendant_of_any(world, e, &selected)
+                && include(world, e)
+        })
+        .collect();
+    if entities.is_empty() {
+        return;
+    }
+
+    let globals: Vec<GlobalTransform> = entities
+        .iter()
+        .map(|&e| world.get::<GlobalTransform>(e).copied().unwrap_or_default())
+        .collect();
+    let positions: Vec<Vec3> = globals.iter().map(|g| g.translation()).collect();
+    let center = Vec3::new(
+        median(positions.iter().map(|p| p.x).collect()),
+        median(positions.iter().map(|p| p.y).collect()),
+        median(positions.iter().map(|p| p.z).collect()),
+    );
+
+    // Keep the group where the selection lives if it all shares one parent.
+    let first_parent = world.get::<ChildOf>(entities[0]).map(|c| c.0);
+    let parent = entities
+        .iter()
+        .all(|&e| world.get::<ChildOf>(e).map(|c| c.0) == first_parent)
+        .then_some(first_parent)
+        .flatten();
+    let parent_global = parent_global_transform(world, parent);
+    let transform =
+        Transform::from_translation(parent_global.affine().inverse().transform_point3(center));
+    let group_global = parent_global.mul_transform(transform);
+
+    let members = entities
+        .iter()
+        .zip(&globals)
+        .map(|(&entity, global)| GroupMember {
+            entity,
+            outer_parent: world.get::<ChildOf>(entity).map(|c| c.0),
+            outer_transform: world.get::<Transform>(entity).copied().unwrap_or_default(),
+            inner_transform: global.reparented_to(&group_global),
+        })
+        .collect();
+
+    let cmd = GroupEntities {
+        group: Mutex::new(Entity::PLACEHOLDER),
+        name: name.to_string(),
+        parent,
+        transform,
+        func_group,
+        members,
+        ungroup: false,
+    };
+    cmd.execute(world);
+    let group = cmd.group();
+
+    world
+        .resource_mut::<CommandHistory>()
+        .push_executed(Box::new(cmd));
+
+    select(world, vec![group]);
+}
+
+/// Whether `entity` is a plain empty that can be dissolved by ungrouping.
+fn is_group(world: &World, entity: Entity) -> bool {
+    world.get::<EditorEntity>(entity).is_none()
+        && world.get::<Children>(entity).is_some()
+        && world.get::<Mesh3d>(entity).is_none()
+        && world.get::<SceneRoot>(entity).is_none()
+        && world.get::<Brush>(entity).is_none()
+        && world.get::<InstanceGroup>(entity).is_none()
+}
+
+/// Move the children of each selected empty up to its parent and delete the empty.
+pub fn ungroup_selected(world: &mut World) {
+    let selected = world.resource::<Selection>().entities.clone();
+
+    let mut cmds = Vec::new();
+    let mut released = Vec::new();
+    for group in selected {
+        if world.get_entity(group).is_err() || !is_group(world, group) {
+            continue;
+        }
+        let parent = world.get::<ChildOf>(group).map(|c| c.0);
+        let parent_global = parent_global_transform(world, parent);
+        let members: Vec<GroupMember> = world
+            .get::<Children>(group)
+            .map(|c| c.iter().collect::<Vec<_>>())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|&child| {
+                world.get::<EditorEntity>(child).is_none()
+                    && world.get::<EditorHidden>(child).is_none()
+            })
+            .filter_map(|child| {
+                let inner_transform = *world.get::<Transform>(child)?;
+                let global = world.get::<GlobalTransform>(child)?;
+                Some(GroupMember {
+                    entity: child,
+                    outer_parent: parent,
+                    outer_transform: global.reparented_to(&parent_global),
+                    inner_transform,
+                })
+            })
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+        released.extend(members.iter().map(|m| m.entity));
+
+        let cmd = GroupEntities {
+            group: Mutex::new(group),
+            name: world
+                .get::<Name>(group)
+                .map(|n| n.as_str().to_string())
+                .unwrap_or_else(|| "Group".to_string()),
+            parent,
+            transform: world.get::<Transform>(group).copied().unwrap_or_default(),
+            func_group: world.get::<FuncGroup>(group).copied(),
+            members,
+            ungroup: true,
+        };
+        cmd.execute(world);
+        cmds.push(Box::new(cmd) as Box<dyn EditorCommand>);
+    }
+    if cmds.is_empty() {
+        return;
+    }
+
+    world
+        .resource_mut::<CommandHistory>()
+        .push_executed(Box::new(CommandGroup {
+            commands: cmds,
+            label: "Ungroup".to_string(),
+        }));
+
+    select(world, released);
+}
+
+/// Enter the selected func group so its brushes can be selected and edited one by one.
+pub fn enter_selected_group(world: &mut World) {
+    let primary = world.resource::<Selection>().primary();
+    let Some(group) = primary.filter(|&e| world.get::<FuncGroup>(e).is_some()) else {
+        world
+            .resource_mut::<StatusHints>()
+            .prompt("Select a func group to enter");
+        return;
+    };
+    world.resource_mut::<EnteredGroup>().0 = Some(group);
+    select(world, Vec::new());
+}
+
+/// Leave the entered func group, selecting it as a whole again.
+pub fn exit_group(world: &mut World) {
+    let Some(group) = world.resource_mut::<EnteredGroup>().0.take() else {
+        return;
+    };
+    if world.get_entity(group).is_ok() {
+        select(world, vec![group]);
+    }
+}
+
+/// Esc with nothing selected leaves the entered group.
+fn exit_group_key(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input_focus: Res<InputFocus>,
+    entered: Res<EnteredGroup>,
+    selection: Res<Selection>,
+    mut commands: Commands,
+) {
+    if entered.0.is_some()
+        && input_focus.0.is_none()
+        && selection.entities.is_empty()
+        && keyboard.just_pressed(KeyCode::Escape)
+    {
+        commands.queue(exit_group);
+    }
+}
+
+fn validate_entered_group(mut entered: ResMut<EnteredGroup>, groups: Query<(), With<FuncGroup>>) {
+    if entered.0.is_some_and(|group| !groups.contains(group)) {
+        entered.0 = None;
+    }
+}
+
+fn update_entered_group_hints(
+    entered: Res<EnteredGroup>,
+    names: Query<&Name>,
+    mut hints: ResMut<StatusHints>,
+) {
+    if !entered.is_changed() {
+        return;
+    }
+    match entered.0 {
+        Some(group) => {
+            let name = names.get(group).map_or("Func Group", |n| n.as_str());
+            hints.set(
+                ENTERED_GROUP_HINTS,
+                format!("Editing '{name}' | Esc with nothing selected: exit group"),
+            );
+        }
+        None => hints.clear(ENTERED_GROUP_HINTS),
+    }
+}
+
+fn select(world: &mut World, entities: Vec<Entity>) {
+    let previous = std::mem::take(&mut world.resource_mut::<Selection>().entities);
+    for e in previous {
+        if let Ok(mut ec) = world.get_entity_mut(e) {
+            ec.remove::<Selected>();
+        }
+    }
+    for &e in &entities {
+        world.entity_mut(e).insert(Selected);
+    }
+    world.resource_mut::<Selection>().entities = entities;
+}
@@ -0,0 +1,486 @@
+//! Batch processing of `.jsn` scenes without a window, for CI pipelines of games
+//! built on the editor's format:
+//!
+//! ```text
+//! jackdaw --headless validate levels/arena.jsn
+//! jackdaw --headless migrate levels/arena.jsn [--out upgraded.jsn]
+//! jackdaw --headless export-gltf levels/arena.jsn [--out arena.gltf]
+//! jackdaw --headless bake-brushes levels/arena.jsn [--out baked.jsn]
+//! ```
+//!
+//! The project is found by walking up from the scene, or given with `--project <dir>`.
+//! Failures are printed to stderr and make the process exit with an error code.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use bevy::{
+    asset::{AssetPlugin, UnapprovedPathMode},
+    prelude::*,
+    reflect::serde::TypedReflectDeserializer,
+    render::{RenderPlugin, settings::WgpuSettings},
+    window::ExitCondition,
+    winit::WinitPlugin,
+};
+use jackdaw_geometry::compute_brush_geometry;
+use jackdaw_jsn::{
+    Brush, GltfSource, SubScene, TriggerVolume, VisibilityVolume,
+    format::{JsnEntity, JsnScene},
+};
+use serde::de::DeserializeSeed;
+
+use crate::{
+    asset_catalog::{self, AssetCatalog},
+    asset_root::ProjectAssetSourcePlugin,
+    brush_bake::{self, BAKE_DIR},
+    project::{self, ProjectRoot},
+    scene_io::{self, JsnDeserializerProcessor},
+    visibility_flags::HiddenInEditor,
+};
+
+pub const USAGE: &str = "\
+usage: jackdaw --headless <command> <scene.jsn> [--out <path>] [--project <dir>]
+
+commands:
+  validate       check the scene for errors without changing it
+  migrate        re-save the scene in the latest format
+  export-gltf    export the scene's brushes to a glTF file (default <scene>.gltf)
+  bake-brushes   replace the scene's brushes with a baked mesh under assets/baked/
+                 (default <scene>.baked.jsn)";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeadlessCommand {
+    Validate,
+    Migrate,
+    ExportGltf,
+    BakeBrushes,
+}
+
+#[derive(Clone, Debug)]
+pub struct HeadlessArgs {
+    pub command: HeadlessCommand,
+    pub scene: PathBuf,
+    /// Where to write the result; the scene itself (or `<scene>.gltf`) when unset.
+    pub out: Option<PathBuf>,
+    pub project: Option<PathBuf>,
+}
+
+impl HeadlessArgs {
+    /// Parse the arguments following `--headless`.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut args = args.into_iter();
+        let command = match args.next().as_deref() {
+            Some("validate") => HeadlessCommand::Validate,
+            Some("migrate") => HeadlessCommand::Migrate,
+            Some("export-gltf") => HeadlessCommand::ExportGltf,
+            Some("bake-brushes") => HeadlessCommand::BakeBrushes,
+            Some(other) => return Err(format!("unknown command '{other}'")),
+            None => return Err("missing command".into()),
+        };
+        let mut scene = None;
+        let mut out = None;
+        let mut project = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--out" | "-o" => {
+                    out = Some(PathBuf::from(args.next().ok_or("--out needs a path")?));
+                }
+                "--project" => {
+                    project = Some(PathBuf::from(
+                        args.next().ok_or("--project needs a directory")?,
+                    ));
+                }
+                _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+                _ if scene.is_none() => scene = Some(PathBuf::from(arg)),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+        Ok(Self {
+            command,
+            scene: scene.ok_or("missing scene path")?,
+            out,
+            project,
+        })
+    }
+}
+
+/// Run one batch command to completion.
+pub fn run(args: HeadlessArgs) -> AppExit {
+    let result = match args.command {
+        HeadlessCommand::Validate => validate(&args),
+        HeadlessCommand::Migrate => migrate(&args),
+        HeadlessCommand::ExportGltf => export_gltf(&args),
+        HeadlessCommand::BakeBrushes => bake(&args),
+    };
+    match result {
+        Ok(()) => AppExit::Success,
+        Err(err) => {
+            eprintln!("error: {err}");
+            AppExit::error()
+        }
+    }
+}
+
+fn read_scene(path: &Path) -> Result<(JsnScene, jackdaw_jsn::JsnLoadReport), String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|err| format!("failed to read '{}': {err}", path.display()))?;
+    jackdaw_jsn::parse_scene(&text).map_err(|err| format!("'{}': {err}", path.display()))
+}
+
+fn write_scene(path: &Path, scene: &JsnScene) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(scene).map_err(|err| err.to_string())?;
+    std::fs::write(path, json).map_err(|err| format!("failed to write '{}': {err}", path.display()))
+}
+
+/// The project the scene belongs to: the closest ancestor folder with a project file.
+fn find_project(args: &HeadlessArgs) -> Option<PathBuf> {
+    if let Some(project) = &args.project {
+        return Some(project.clone());
+    }
+    let scene = std::path::absolute(&args.scene).ok()?;
+    scene
+        .ancestors()
+        .skip(1)
+        .find(|dir| {
+            dir.join(".jsn/project.jsn").is_file()
+                || dir.join("project.jsn").is_file()
+                || project::project_settings_path(dir).is_file()
+        })
+        .map(Path::to_path_buf)
+}
+
+/// A windowless app with the scene types registered, the project's assets mounted,
+/// and its asset catalog loaded.
+fn headless_app(project_root: Option<&Path>) -> App {
+    let assets_dir = project_root
+        .map(project::project_assets_dir)
+        .unwrap_or_else(|| PathBuf::from("assets"));
+
+    let mut app = App::new();
+    app.add_plugins(ProjectAssetSourcePlugin { root: assets_dir })
+        .add_plugins(
+            DefaultPlugins
+                .set(AssetPlugin {
+                    unapproved_path_mode: UnapprovedPathMode::Allow,
+                    ..default()
+                })
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    close_when_requested: false,
+                    ..default()
+                })
+                .set(RenderPlugin {
+                    render_creation: WgpuSettings {
+                        backends: None,
+                        ..default()
+                    }
+                    .into(),
+                    ..default()
+                })
+                .disable::<WinitPlugin>(),
+        )
+        .add_plugins(jackdaw_jsn::JsnPlugin)
+        .register_type::<HiddenInEditor>()
+        .init_resource::<AssetCatalog>();
+
+    if let Some(root) = project_root
+        && let Some(config) = project::load_project_config(root)
+    {
+        app.insert_resource(ProjectRoot {
+            root: root.to_path_buf(),
+            config,
+            settings: project::read_project_settings(root),
+        });
+    }
+    app.finish();
+    app.cleanup();
+    if app.world().contains_resource::<ProjectRoot>() {
+        asset_catalog::load_catalog(app.world_mut());
+    }
+    app
+}
+
+/// Spawn the scene into `app`'s world and run a frame so transforms propagate.
+fn spawn_scene(app: &mut App, scene: &JsnScene, parent_path: &Path) {
+    let world = app.world_mut();
+    world.insert_resource(scene.environment.clone().unwrap_or_default());
+    let local_assets = scene_io::load_inline_assets(world, &scene.assets, parent_path);
+    scene_io::load_scene_from_jsn(world, &scene.scene, parent_path, &local_assets);
+    app.update();
+}
+
+/// Brushes that bake to geometry: everything but trigger volumes.
+fn solid_brushes(world: &mut World) -> Vec<Entity> {
+    world
+        .query_filtered::<Entity, (
+            With<Brush>,
+            Without<TriggerVolume>,
+            Without<VisibilityVolume>,
+        )>()
+        .iter(world)
+        .collect()
+}
+
+fn parent_dir(path: &Path) -> &Path {
+    path.parent().unwrap_or(Path::new("."))
+}
+
+fn validate(args: &HeadlessArgs) -> Result<(), String> {
+    let (scene, report) = read_scene(&args.scene)?;
+    let project_root = find_project(args);
+    let mut app = headless_app(project_root.as_deref());
+    let assets_dir = project_root
+        .as_deref()
+        .map(project::project_assets_dir)
+        .unwrap_or_else(|| PathBuf::from("assets"));
+
+    let mut warnings: Vec<String> = report.warnings.clone();
+    if report.migrated() {
+        warnings.push(format!(
+            "scene is format version {}, the latest is {}",
+            report.from_version, report.to_version
+        ));
+    }
+    let mut errors = check_entities(app.world(), &scene.scene, parent_dir(&args.scene));
+    errors.extend(check_assets(app.world(), &scene));
+    errors.extend(check_files(&scene.scene, &assets_dir));
+
+    // Brush geometry needs the components deserialized
+    spawn_scene(&mut app, &scene, parent_dir(&args.scene));
+    let world = app.world_mut();
+    let brushes: Vec<(Entity, Brush)> = world
+        .query::<(Entity, &Brush)>()
+        .iter(world)
+        .map(|(e, b)| (e, b.clone()))
+        .collect();
+    for (entity, brush) in brushes {
+        let label = world
+            .get::<Name>(entity)
+            .map(|n| format!("'{n}'"))
+            .unwrap_or_else(|| format!("{entity}"));
+        let (vertices, face_polygons) = compute_brush_geometry(&brush.faces);
+        if brush.faces.len() < 4 || vertices.len() < 4 {
+            errors.push(format!("brush {label} has no volume"));
+        } else if face_polygons.iter().any(|polygon| polygon.len() < 3) {
+            warnings.push(format!(
+                "brush {label} has faces that don't touch its volume"
+            ));
+        }
+    }
+
+    for warning in &warnings {
+        eprintln!("warning: {warning}");
+    }
+    for error in &errors {
+        eprintln!("error: {error}");
+    }
+    if !errors.is_empty() {
+        return Err(format!(
+            "{} error(s) in '{}'",
+            errors.len(),
+            args.scene.display()
+        ));
+    }
+    println!(
+        "'{}' is valid: {} entities, {} warning(s)",
+        args.scene.display(),
+        scene.scene.len(),
+        warnings.len()
+    );
+    Ok(())
+}
+
+/// Parent indices and component types, deserialized without spawning anything.
+fn check_entities(world: &World, entities: &[JsnEntity], parent_path: &Path) -> Vec<String> {
+    let mut errors = Vec::new();
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let asset_server = world.resource::<AssetServer>();
+    let catalog_assets = world.resource::<AssetCatalog>().handles.clone();
+    let local_assets = HashMap::new();
+    // Entity references only need to resolve to some entity here
+    let entity_map = vec![Entity::PLACEHOLDER; entities.len()];
+
+    for (index, entity) in entities.iter().enumerate() {
+        let label = entity
+            .name
+            .as_ref()
+            .map(|name| format!("entity {index} '{name}'"))
+            .unwrap_or_else(|| format!("entity {index}"));
+        if let Some(parent) = entity.parent
+            && parent >= entities.len()
+        {
+            errors.push(format!("{label}: parent {parent} doesn't exist"));
+        }
+        for (type_path, value) in &entity.components {
+            let Some(registration) = registry.get_with_type_path(type_path) else {
+                errors.push(format!("{label}: unknown component '{type_path}'"));
+                continue;
+            };
+            if registration.data::<ReflectComponent>().is_none() {
+                errors.push(format!("{label}: '{type_path}' isn't a component"));
+                continue;
+            }
+            let mut processor = JsnDeserializerProcessor {
+                asset_server,
+                parent_path,
+                local_assets: &local_assets,
+                catalog_assets: &catalog_assets,
+                entity_map: &entity_map,
+            };
+            let deserializer =
+                TypedReflectDeserializer::with_processor(registration, &registry, &mut processor);
+            if let Err(err) = deserializer.deserialize(value) {
+                errors.push(format!("{label}: invalid '{type_path}': {err}"));
+            }
+        }
+    }
+    errors
+}
+
+fn check_assets(world: &World, scene: &JsnScene) -> Vec<String> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    scene
+        .assets
+        .0
+        .keys()
+        .filter(|type_path| registry.get_with_type_path(type_path).is_none())
+        .map(|type_path| format!("unknown asset type '{type_path}'"))
+        .collect()
+}
+
+/// glTF files and sub-scenes the scene references, relative to the asset root.
+fn check_files(entities: &[JsnEntity], assets_dir: &Path) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (index, entity) in entities.iter().enumerate() {
+        let paths = [
+            (GltfSource::type_path(), "path"),
+            (SubScene::type_path(), "path"),
+        ]
+        .into_iter()
+        .filter_map(|(type_path, field)| entity.components.get(type_path)?.get(field)?.as_str());
+        for path in paths {
+            // Drop sub-asset labels like `#Scene0`
+            let file = path.split('#').next().unwrap_or(path);
+            if !assets_dir.join(file).is_file() {
+                errors.push(format!("entity {index}: missing file '{path}'"));
+            }
+        }
+    }
+    errors
+}
+
+fn migrate(args: &HeadlessArgs) -> Result<(), String> {
+    let (scene, report) = read_scene(&args.scene)?;
+    for warning in &report.warnings {
+        eprintln!("warning: {warning}");
+    }
+    let out = args.out.as_deref().unwrap_or(&args.scene);
+    write_scene(out, &scene)?;
+    println!(
+        "Wrote '{}' (format version {} -> {})",
+        out.display(),
+        report.from_version,
+        report.to_version
+    );
+    Ok(())
+}
+
+fn export_gltf(args: &HeadlessArgs) -> Result<(), String> {
+    let (scene, _) = read_scene(&args.scene)?;
+    let project_root = find_project(args);
+    let mut app = headless_app(project_root.as_deref());
+    spawn_scene(&mut app, &scene, parent_dir(&args.scene));
+
+    let out = args
+        .out
+        .clone()
+        .unwrap_or_else(|| args.scene.with_extension("gltf"));
+    let assets_dir = project_root
+        .as_deref()
+        .map(project::project_assets_dir)
+        .unwrap_or_else(|| PathBuf::from("assets"));
+    // Texture URIs are relative to the written file
+    let out_dir = std::path::absolute(parent_dir(&out)).map_err(|err| err.to_string())?;
+    let assets_dir = std::path::absolute(&assets_dir).map_err(|err| err.to_string())?;
+    let texture_prefix = pathdiff::diff_paths(&assets_dir, &out_dir).unwrap_or(assets_dir);
+
+    let world = app.world_mut();
+    let brushes = solid_brushes(world);
+    let bake = brush_bake::bake_brushes(world, &brushes, Vec3::ZERO, &texture_prefix)
+        .ok_or("the scene has no brush geometry to export")?;
+    let json = serde_json::to_string_pretty(&bake.gltf).map_err(|err| err.to_string())?;
+    std::fs::write(&out, json)
+        .map_err(|err| format!("failed to write '{}': {err}", out.display()))?;
+    println!(
+        "Exported {} brushes to '{}' ({} faces)",
+        brushes.len(),
+        out.display(),
+        bake.faces
+    );
+    Ok(())
+}
+
+fn bake(args: &HeadlessArgs) -> Result<(), String> {
+    let (mut scene, _) = read_scene(&args.scene)?;
+    let project_root = find_project(args).ok_or("baking brushes needs a project")?;
+    let mut app = headless_app(Some(&project_root));
+    let parent_path = parent_dir(&args.scene).to_path_buf();
+    spawn_scene(&mut app, &scene, &parent_path);
+
+    let world = app.world_mut();
+    let brushes = solid_brushes(world);
+    let pivot = brush_bake::brush_pivot(world, &brushes);
+    let bake = brush_bake::bake_brushes(world, &brushes, pivot, Path::new(".."))
+        .ok_or("the scene has no brush geometry to bake")?;
+
+    let stem = args
+        .scene
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "scene".into());
+    let asset_path = format!("{BAKE_DIR}/{stem}_brushes.gltf");
+    let assets_dir = project::project_assets_dir(&project_root);
+    std::fs::create_dir_all(assets_dir.join(BAKE_DIR))
+        .and_then(|()| {
+            std::fs::write(
+                assets_dir.join(&asset_path),
+                serde_json::to_string_pretty(&bake.gltf).unwrap_or_default(),
+            )
+        })
+        .map_err(|err| format!("failed to write '{asset_path}': {err}"))?;
+
+    for &entity in &brushes {
+        world.entity_mut(entity).despawn();
+    }
+    world.spawn((
+        Name::new("Baked Brushes"),
+        GltfSource {
+            path: asset_path.clone(),
+            scene_index: 0,
+        },
+        Transform::from_translation(pivot),
+        Visibility::default(),
+    ));
+
+    let (entities, assets) = scene_io::serialize_scene_entities(world, &parent_path);
+    scene.scene = entities;
+    scene.assets = assets;
+    scene.metadata.modified = scene_io::chrono_now();
+    // Never overwrite the source scene unless asked to
+    let out = args
+        .out
+        .clone()
+        .unwrap_or_else(|| args.scene.with_extension("baked.jsn"));
+    write_scene(&out, &scene)?;
+    println!(
+        "Baked {} brushes into '{asset_path}' ({} faces, {} lightmap charts), wrote '{}'",
+        brushes.len(),
+        bake.faces,
+        bake.charts,
+        out.display()
+    );
+    Ok(())
+}
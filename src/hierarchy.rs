@@ -1,29 +1,41 @@
 use std::any::TypeId;
 use std::collections::HashSet;
 
-use bevy::{input_focus::InputFocus, prelude::*, ui::ui_transform::UiGlobalTransform};
+use bevy::{
+    feathers::theme::ThemedText, input_focus::InputFocus, prelude::*,
+    ui::ui_transform::UiGlobalTransform, window::PrimaryWindow,
+};
 use bevy_monitors::prelude::{Mutation, NotifyChanged};
 use jackdaw_feathers::{
     context_menu::spawn_context_menu,
-    icons::IconFont,
+    icons::{Icon, IconFont},
     text_edit::{self, EditorTextEdit, TextEditCommitEvent, TextEditProps, TextEditValue},
     tokens,
     tree_view::{ROW_BG, TreeRowStyle, tree_row},
 };
 use jackdaw_widgets::context_menu::{ContextMenuAction, ContextMenuCloseSet, ContextMenuState};
+use jackdaw_widgets::file_browser::FileBrowserItem;
 use jackdaw_widgets::tree_view::{
     EntityCategory, TreeChildrenPopulated, TreeFocused, TreeIndex, TreeNode, TreeNodeExpanded,
     TreeRowChildren, TreeRowClicked, TreeRowContent, TreeRowDropped, TreeRowDroppedOnRoot,
+    TreeRowExternalDropped, TreeRowGameVisibilityToggle, TreeRowGameVisibilityToggled,
     TreeRowInlineRename, TreeRowLabel, TreeRowRenamed, TreeRowSelected, TreeRowStartRename,
-    TreeRowVisibilityToggled,
+    TreeRowVisibilityToggle, TreeRowVisibilityToggled,
 };
+use serde::{Deserialize, Serialize};
 
 use crate::{
     EditorEntity, EditorHidden,
-    commands::{CommandHistory, EditorCommand, ReparentEntity, SetComponentField},
+    commands::{
+        AddComponent, CommandGroup, CommandHistory, EditorCommand, RemoveComponent, ReparentEntity,
+        SetName,
+    },
     entity_ops,
     layout::HierarchyFilter,
+    project::ProjectRoot,
     selection::{Selected, Selection},
+    viewport::find_ancestor_component,
+    visibility_flags::{HiddenInEditor, HiddenInGame},
 };
 use jackdaw_feathers::dialog::{DialogActionEvent, DialogChildrenSlot};
 
@@ -54,6 +66,97 @@ pub struct HierarchyShowAll(pub bool);
 #[derive(Component)]
 pub struct HierarchyShowAllButton;
 
+/// When `true`, chains of unnamed single-child transform nodes (typical of glTF
+/// imports) are folded into the row of their first meaningful descendant, with a
+/// breadcrumb label. View-only: the scene itself is untouched.
+#[derive(Resource, Default)]
+pub struct HierarchyCollapseChains(pub bool);
+
+/// On a row standing for a collapsed chain: the nodes folded into it, top first. Each
+/// is in the [`TreeIndex`] under this row.
+#[derive(Component)]
+struct CollapsedChain(Vec<Entity>);
+
+/// Marker for the collapse-chains toggle button in the hierarchy panel.
+#[derive(Component)]
+pub struct HierarchyCollapseChainsButton;
+
+/// Marker for the button opening the hierarchy sort / group options menu.
+#[derive(Component)]
+pub struct HierarchyOptionsButton;
+
+/// How sibling rows are ordered in the hierarchy panel.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HierarchySort {
+    /// By category (cameras, lights, meshes, ...), then name.
+    #[default]
+    Type,
+    /// Alphabetically, ignoring case.
+    Name,
+    /// By entity id, which follows spawn order unless ids were recycled.
+    Creation,
+}
+
+/// Display options of the hierarchy panel, stored per project in `.jsn/hierarchy.json`.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct HierarchySettings {
+    pub sort: HierarchySort,
+    /// Place root rows under Lights / Cameras / Brushes / Meshes / Other headers.
+    pub group_by_type: bool,
+    /// List `EditorHidden` entities too, greyed out.
+    pub show_hidden: bool,
+}
+
+/// Root row groups when [`HierarchySettings::group_by_type`] is on, in display order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum HierarchyGroup {
+    Lights,
+    Cameras,
+    Brushes,
+    Meshes,
+    Other,
+}
+
+impl HierarchyGroup {
+    fn of(world: &World, entity: Entity) -> Self {
+        if world.get::<jackdaw_jsn::Brush>(entity).is_some() {
+            return Self::Brushes;
+        }
+        match classify_entity(world, entity) {
+            EntityCategory::Light => Self::Lights,
+            EntityCategory::Camera => Self::Cameras,
+            EntityCategory::Mesh | EntityCategory::Scene => Self::Meshes,
+            EntityCategory::Entity => Self::Other,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Lights => "Lights",
+            Self::Cameras => "Cameras",
+            Self::Brushes => "Brushes",
+            Self::Meshes => "Meshes",
+            Self::Other => "Other",
+        }
+    }
+}
+
+/// Header row above the root rows of one [`HierarchyGroup`].
+#[derive(Component)]
+#[require(EditorEntity)]
+struct HierarchyGroupHeader(HierarchyGroup);
+
+/// Orders sibling rows: group, header before rows, then category, name and entity id
+/// as far as [`HierarchySettings::sort`] uses them.
+type RowSortKey = (
+    Option<HierarchyGroup>,
+    bool,
+    Option<EntityCategory>,
+    String,
+    Entity,
+);
+
 pub struct HierarchyPlugin;
 
 impl Plugin for HierarchyPlugin {
@@ -61,6 +164,8 @@ impl Plugin for HierarchyPlugin {
         app.init_resource::<ContextMenuState>()
             .init_resource::<PendingTemplateDefaultName>()
             .init_resource::<HierarchyShowAll>()
+            .init_resource::<HierarchyCollapseChains>()
+            .init_resource::<HierarchySettings>()
             .add_systems(
                 OnEnter(crate::AppState::Editor),
                 (
@@ -73,12 +178,21 @@ impl Plugin for HierarchyPlugin {
                 (
                     apply_hierarchy_filter,
                     cancel_inline_rename,
+                    handle_rename_key,
                     auto_focus_inline_rename,
                     handle_hierarchy_right_click.after(ContextMenuCloseSet),
                     populate_template_dialog,
                     toggle_show_all_button,
                     update_show_all_button_appearance,
                     on_show_all_changed,
+                    toggle_collapse_chains_button,
+                    update_collapse_chains_button_appearance,
+                    on_collapse_chains_changed,
+                    open_hierarchy_options_menu.after(ContextMenuCloseSet),
+                    load_hierarchy_settings,
+                    save_hierarchy_settings,
+                    on_hierarchy_settings_changed,
+                    prune_empty_group_headers,
                     jackdaw_feathers::tree_view::tree_keyboard_navigation,
                 )
                     .run_if(in_state(crate::AppState::Editor)),
@@ -94,12 +208,17 @@ impl Plugin for HierarchyPlugin {
             .add_observer(on_entity_deselected)
             .add_observer(on_tree_row_dropped)
             .add_observer(on_tree_row_dropped_on_root)
+            .add_observer(on_asset_dropped_on_row)
             .add_observer(on_tree_row_start_rename)
             .add_observer(on_tree_row_renamed)
             .add_observer(on_context_menu_action)
             .add_observer(on_visibility_toggled)
+            .add_observer(on_game_visibility_toggled)
+            .add_observer(on_visibility_flag_added)
+            .add_observer(on_visibility_flag_removed)
             .add_observer(on_template_dialog_action)
-            .add_observer(on_entity_hidden);
+            .add_observer(on_entity_hidden)
+            .add_observer(on_entity_unhidden);
     }
 }
 
@@ -117,47 +236,270 @@ fn classify_entity(world: &World, entity: Entity) -> EntityCategory {
     if world.get::<Mesh3d>(entity).is_some() {
         return EntityCategory::Mesh;
     }
-    if world.get::<SceneRoot>(entity).is_some() {
+    if world.get::<SceneRoot>(entity).is_some()
+        || world.get::<jackdaw_jsn::SubScene>(entity).is_some()
+    {
         return EntityCategory::Scene;
     }
     EntityCategory::Entity
 }
 
+/// Whether `entity` gets no tree row: editor entities never do, `EditorHidden` ones
+/// only when [`HierarchySettings::show_hidden`] is on.
+fn excluded_from_tree(world: &World, entity: Entity) -> bool {
+    world.get::<EditorEntity>(entity).is_some()
+        || (world.get::<EditorHidden>(entity).is_some()
+            && !world.resource::<HierarchySettings>().show_hidden)
+}
+
 /// Check if an entity has any non-editor children.
 fn has_visible_children(world: &World, entity: Entity) -> bool {
     let Some(children) = world.get::<Children>(entity) else {
         return false;
     };
-    children.iter().any(|child| {
-        world.get::<EditorEntity>(child).is_none() && world.get::<EditorHidden>(child).is_none()
-    })
+    children
+        .iter()
+        .any(|child| !excluded_from_tree(world, child))
+}
+
+/// If `entity` is an unnamed, plain transform node with exactly one visible child,
+/// return that child.
+fn collapsible_child(world: &World, entity: Entity) -> Option<Entity> {
+    if world.get::<Name>(entity).is_some()
+        || classify_entity(world, entity) != EntityCategory::Entity
+    {
+        return None;
+    }
+    let mut visible = world
+        .get::<Children>(entity)?
+        .iter()
+        .filter(|&child| !excluded_from_tree(world, child));
+    let child = visible.next()?;
+    visible.next().is_none().then_some(child)
+}
+
+/// Walk down a chain of collapsible nodes starting at `entity`. Returns the entity
+/// the row should represent and the nodes folded into it.
+fn resolve_collapsed_chain(world: &World, entity: Entity) -> (Entity, Vec<Entity>) {
+    let mut folded = Vec::new();
+    let mut current = entity;
+    while let Some(child) = collapsible_child(world, current) {
+        folded.push(current);
+        current = child;
+    }
+    (current, folded)
+}
+
+/// Respawn a collapsed chain row from the top of its chain, after one of its nodes
+/// changed name or children and the chain or its label no longer holds.
+fn rebuild_chain_row(world: &mut World, row: Entity) {
+    let Some(folded) = world.get::<CollapsedChain>(row).map(|c| c.0.clone()) else {
+        return;
+    };
+    let Some(container) = world.get::<ChildOf>(row).map(|c| c.parent()) else {
+        return;
+    };
+    let source = world.get::<TreeNode>(row).map(|node| node.0);
+    let mut index = world.resource_mut::<TreeIndex>();
+    for member in folded.iter().copied().chain(source) {
+        if index.get(member) == Some(row) {
+            index.remove(member);
+        }
+    }
+    world.entity_mut(row).despawn();
+    if let Some(&top) = folded.first()
+        && world.get_entity(top).is_ok()
+        && !excluded_from_tree(world, top)
+    {
+        spawn_single_tree_row(world, top, container);
+    }
 }
 
 /// Spawn a single (non-recursive) tree row for a source entity.
 /// Updates TreeIndex immediately.
 fn spawn_single_tree_row(world: &mut World, source: Entity, parent_container: Entity) -> Entity {
-    let label = world
+    let (source, folded) = if world.resource::<HierarchyCollapseChains>().0 {
+        resolve_collapsed_chain(world, source)
+    } else {
+        (source, Vec::new())
+    };
+    let skipped: Vec<String> = folded.iter().map(|e| format!("Entity {e}")).collect();
+    let name = world
         .get::<Name>(source)
         .map(|n| n.as_str().to_string())
         .unwrap_or_else(|| format!("Entity {source}"));
+    let label = match skipped.as_slice() {
+        [] => name,
+        [only] => format!("{only} › {name}"),
+        [first, ..] => format!("{first} › … › {name}"),
+    };
     let has_children = has_visible_children(world, source);
     let category = classify_entity(world, source);
     let icon_font = world.resource::<IconFont>().0.clone();
     let style = TreeRowStyle { icon_font };
 
     let tree_row_entity = world
-        .spawn((
-            tree_row(&label, has_children, false, source, category, &style),
-            ChildOf(parent_container),
+        .spawn(tree_row(
+            &label,
+            has_children,
+            false,
+            source,
+            category,
+            &style,
         ))
         .id();
+    insert_row_sorted(world, parent_container, tree_row_entity, source);
+    if world.get::<EditorHidden>(source).is_some() {
+        set_row_greyed(world, tree_row_entity, true);
+    }
 
-    world
-        .resource_mut::<TreeIndex>()
-        .insert(source, tree_row_entity);
+    let mut index = world.resource_mut::<TreeIndex>();
+    index.insert(source, tree_row_entity);
+    // Folded nodes resolve to the chain's row, so selecting or renaming one finds it
+    for &node in &folded {
+        index.insert(node, tree_row_entity);
+    }
+    if !folded.is_empty() {
+        world
+            .entity_mut(tree_row_entity)
+            .insert(CollapsedChain(folded));
+    }
+    sync_row_flag_icons(world, source);
     tree_row_entity
 }
 
+/// Sort key of the row for `source`. `root` rows are grouped when grouping is on.
+fn row_sort_key(
+    world: &World,
+    source: Entity,
+    root: bool,
+    settings: &HierarchySettings,
+) -> RowSortKey {
+    let group = (root && settings.group_by_type).then(|| HierarchyGroup::of(world, source));
+    let category = (settings.sort == HierarchySort::Type).then(|| classify_entity(world, source));
+    let name = if settings.sort == HierarchySort::Creation {
+        String::new()
+    } else {
+        world
+            .get::<Name>(source)
+            .map(|n| n.as_str().to_lowercase())
+            .unwrap_or_else(|| format!("entity {source}"))
+    };
+    (group, true, category, name, source)
+}
+
+fn header_sort_key(group: HierarchyGroup) -> RowSortKey {
+    (Some(group), false, None, String::new(), Entity::PLACEHOLDER)
+}
+
+/// Insert (or move) `row` into `container` at its sorted position, adding the group
+/// header first when root rows are grouped by type.
+fn insert_row_sorted(world: &mut World, container: Entity, row: Entity, source: Entity) {
+    let root = world.get::<HierarchyTreeContainer>(container).is_some();
+    let settings = world.resource::<HierarchySettings>().clone();
+    world.entity_mut(row).remove::<ChildOf>();
+
+    let key = row_sort_key(world, source, root, &settings);
+    if let Some(group) = key.0 {
+        let has_header = world.get::<Children>(container).is_some_and(|children| {
+            children.iter().any(|child| {
+                world
+                    .get::<HierarchyGroupHeader>(child)
+                    .is_some_and(|h| h.0 == group)
+            })
+        });
+        if !has_header {
+            let header = spawn_group_header(world, group);
+            insert_child_sorted(world, container, header, &header_sort_key(group), &settings);
+        }
+    }
+    insert_child_sorted(world, container, row, &key, &settings);
+}
+
+fn insert_child_sorted(
+    world: &mut World,
+    container: Entity,
+    child: Entity,
+    key: &RowSortKey,
+    settings: &HierarchySettings,
+) {
+    let root = world.get::<HierarchyTreeContainer>(container).is_some();
+    let siblings: Vec<Entity> = world
+        .get::<Children>(container)
+        .map(|c| c.to_vec())
+        .unwrap_or_default();
+    let sibling_key = |sibling: Entity| -> Option<RowSortKey> {
+        if let Some(header) = world.get::<HierarchyGroupHeader>(sibling) {
+            return Some(header_sort_key(header.0));
+        }
+        let source = world.get::<TreeNode>(sibling)?.0;
+        Some(row_sort_key(world, source, root, settings))
+    };
+
+    // Rebuilds and expansion add rows in order, so try appending first.
+    let index = match siblings.last().and_then(|&last| sibling_key(last)) {
+        Some(last) if last <= *key => siblings.len(),
+        _ => siblings
+            .iter()
+            .position(|&sibling| sibling_key(sibling).is_some_and(|k| k > *key))
+            .unwrap_or(siblings.len()),
+    };
+    world.entity_mut(container).insert_children(index, &[child]);
+}
+
+fn spawn_group_header(world: &mut World, group: HierarchyGroup) -> Entity {
+    world
+        .spawn((
+            HierarchyGroupHeader(group),
+            Node {
+                padding: UiRect::new(
+                    px(tokens::SPACING_SM),
+                    px(0.0),
+                    px(tokens::SPACING_SM),
+                    px(tokens::SPACING_XS),
+                ),
+                ..default()
+            },
+            children![(
+                Text::new(group.label()),
+                TextFont {
+                    font_size: tokens::FONT_SM,
+                    ..default()
+                },
+                TextColor(tokens::TEXT_SECONDARY),
+            )],
+        ))
+        .id()
+}
+
+/// Grey out (or restore) the label of a tree row, used for `EditorHidden` entities.
+fn set_row_greyed(world: &mut World, tree_row: Entity, greyed: bool) {
+    let mut label = None;
+    for child in world.get::<Children>(tree_row).into_iter().flatten() {
+        if world.get::<TreeRowContent>(*child).is_none() {
+            continue;
+        }
+        label = world
+            .get::<Children>(*child)
+            .into_iter()
+            .flatten()
+            .copied()
+            .find(|&c| world.get::<TreeRowLabel>(c).is_some());
+        break;
+    }
+    let Some(label) = label else {
+        return;
+    };
+    let mut label = world.entity_mut(label);
+    if greyed {
+        label
+            .remove::<ThemedText>()
+            .insert(TextColor(tokens::TEXT_SECONDARY.with_alpha(0.6)));
+    } else {
+        label.insert((ThemedText, TextColor(tokens::TEXT_PRIMARY)));
+    }
+}
+
 /// Populate the hierarchy tree with root-level entities only (non-recursive).
 /// Children are spawned lazily when parents are expanded.
 fn rebuild_hierarchy(world: &mut World) {
@@ -172,37 +514,24 @@ fn rebuild_hierarchy(world: &mut World) {
 
     // Collect all root scene entities (Transform, no ChildOf, no editor markers)
     let roots: Vec<Entity> = world
-        .query_filtered::<Entity, (
-            With<Transform>,
-            Without<EditorEntity>,
-            Without<EditorHidden>,
-            Without<ChildOf>,
-        )>()
+        .query_filtered::<Entity, (With<Transform>, Without<ChildOf>)>()
         .iter(world)
         .collect();
 
     let show_all = world.resource::<HierarchyShowAll>().0;
+    let settings = world.resource::<HierarchySettings>().clone();
 
-    // Sort by (category, name) for consistent ordering
-    let mut root_data: Vec<(Entity, EntityCategory, String)> = roots
+    // Sort up front so rows are appended in order
+    let mut root_data: Vec<(RowSortKey, Entity)> = roots
         .into_iter()
+        .filter(|&e| !excluded_from_tree(world, e))
         .filter(|&e| !world.resource::<TreeIndex>().contains(e))
         .filter(|&e| show_all || world.get::<Name>(e).is_some())
-        .map(|e| {
-            let category = classify_entity(world, e);
-            let name = world
-                .get::<Name>(e)
-                .map(|n| n.as_str().to_string())
-                .unwrap_or_else(|| format!("Entity {e}"));
-            (e, category, name)
-        })
+        .map(|e| (row_sort_key(world, e, true, &settings), e))
         .collect();
+    root_data.sort();
 
-    root_data.sort_by(|(_, cat_a, name_a), (_, cat_b, name_b)| {
-        cat_a.cmp(cat_b).then_with(|| name_a.cmp(name_b))
-    });
-
-    for (entity, _category, _name) in root_data {
+    for (_key, entity) in root_data {
         spawn_single_tree_row(world, entity, container);
     }
 }
@@ -213,7 +542,7 @@ fn on_root_entity_added(
     mut commands: Commands,
     tree_index: Res<TreeIndex>,
     container: Option<Single<Entity, With<HierarchyTreeContainer>>>,
-    editor_check: Query<(), Or<(With<EditorEntity>, With<EditorHidden>)>>,
+    editor_check: Query<(), With<EditorEntity>>,
     child_of_check: Query<(), With<ChildOf>>,
 ) {
     let entity = trigger.event_target();
@@ -237,9 +566,7 @@ fn on_root_entity_added(
         if world.get::<ChildOf>(entity).is_some() {
             return;
         }
-        if world.get::<EditorEntity>(entity).is_some()
-            || world.get::<EditorHidden>(entity).is_some()
-        {
+        if excluded_from_tree(world, entity) {
             return;
         }
         // In named-only mode, skip entities without a Name
@@ -261,14 +588,23 @@ fn on_name_changed(
     content_query: Query<&Children, With<TreeRowContent>>,
     mut label_query: Query<&mut Text, With<TreeRowLabel>>,
     container: Option<Single<Entity, With<HierarchyTreeContainer>>>,
-    editor_check: Query<(), Or<(With<EditorEntity>, With<EditorHidden>)>>,
+    editor_check: Query<(), With<EditorEntity>>,
     child_of_check: Query<(), With<ChildOf>>,
+    chain_rows: Query<(), With<CollapsedChain>>,
 ) {
     let entity = trigger.event_target();
     let Ok(name) = name_query.get(entity) else {
         return;
     };
 
+    // A named node no longer collapses, and the chain's label ends in the name
+    if let Some(row) = tree_index.get(entity)
+        && chain_rows.contains(row)
+    {
+        commands.queue(move |world: &mut World| rebuild_chain_row(world, row));
+        return;
+    }
+
     if let Some(tree_entity) = tree_index.get(entity) {
         // Update existing label: TreeNode → Children → TreeRowContent → Children → TreeRowLabel
         let Ok(children) = tree_nodes.get(tree_entity) else {
@@ -302,9 +638,7 @@ fn on_name_changed(
             if world.get::<ChildOf>(entity).is_some() {
                 return;
             }
-            if world.get::<EditorEntity>(entity).is_some()
-                || world.get::<EditorHidden>(entity).is_some()
-            {
+            if excluded_from_tree(world, entity) {
                 return;
             }
             spawn_single_tree_row(world, entity, container);
@@ -322,11 +656,13 @@ fn setup_name_watcher(mut commands: Commands) {
 /// When an entity's Name is mutated in-place (e.g. via inspector), update the tree row label.
 fn on_name_mutated(
     trigger: On<Mutation<Name>>,
+    mut commands: Commands,
     name_query: Query<&Name>,
     tree_index: Res<TreeIndex>,
     tree_nodes: Query<&Children, With<TreeNode>>,
     content_query: Query<&Children, With<TreeRowContent>>,
     mut label_query: Query<&mut Text, With<TreeRowLabel>>,
+    chain_rows: Query<(), With<CollapsedChain>>,
 ) {
     let entity = trigger.mutated;
     let Ok(name) = name_query.get(entity) else {
@@ -335,6 +671,10 @@ fn on_name_mutated(
     let Some(tree_entity) = tree_index.get(entity) else {
         return;
     };
+    if chain_rows.contains(tree_entity) {
+        commands.queue(move |world: &mut World| rebuild_chain_row(world, tree_entity));
+        return;
+    }
     let Ok(children) = tree_nodes.get(tree_entity) else {
         return;
     };
@@ -356,16 +696,17 @@ fn on_entity_reparented(
     trigger: On<Add, ChildOf>,
     mut commands: Commands,
     tree_index: Res<TreeIndex>,
-    editor_check: Query<(), Or<(With<EditorEntity>, With<EditorHidden>)>>,
+    editor_check: Query<(), With<EditorEntity>>,
     tree_node_check: Query<(), With<TreeNode>>,
     child_of_query: Query<&ChildOf>,
     children_query: Query<&Children>,
     tree_row_children: Query<Entity, With<TreeRowChildren>>,
     populated_query: Query<&TreeChildrenPopulated>,
+    chain_rows: Query<&CollapsedChain>,
 ) {
     let entity = trigger.event_target();
 
-    // Skip editor/hidden entities and tree row UI entities
+    // Skip editor entities and tree row UI entities
     if editor_check.contains(entity) || tree_node_check.contains(entity) {
         return;
     }
@@ -374,6 +715,36 @@ fn on_entity_reparented(
         return;
     };
 
+    // A node leaving a collapsed chain, or a folded node gaining a second child, breaks
+    // the chain up; rebuild its row, then give the moved entity its own row as usual
+    let moved_chain = tree_index.get(entity).filter(|&row| {
+        chain_rows
+            .get(row)
+            .is_ok_and(|chain| chain.0.first() != Some(&entity))
+    });
+    let parent_chain = tree_index.get(new_parent).filter(|&row| {
+        chain_rows
+            .get(row)
+            .is_ok_and(|chain| chain.0.contains(&new_parent))
+    });
+    if moved_chain.is_some() || parent_chain.is_some() {
+        commands.queue(move |world: &mut World| {
+            for row in moved_chain.into_iter().chain(parent_chain) {
+                if world.get_entity(row).is_ok() {
+                    rebuild_chain_row(world, row);
+                }
+            }
+            if let Some(container) = populated_row_container(world, new_parent)
+                && !world.resource::<TreeIndex>().contains(entity)
+                && !excluded_from_tree(world, entity)
+                && (world.resource::<HierarchyShowAll>().0 || world.get::<Name>(entity).is_some())
+            {
+                spawn_single_tree_row(world, entity, container);
+            }
+        });
+        return;
+    }
+
     // Find the new parent's TreeRowChildren container via TreeIndex + child walk
     let parent_container = tree_index.get(new_parent).and_then(|parent_tree| {
         children_query
@@ -385,7 +756,9 @@ fn on_entity_reparented(
     // If tree row already exists for this entity → reparent it
     if let Some(tree_entity) = tree_index.get(entity) {
         if let Some(container) = parent_container {
-            commands.entity(tree_entity).insert(ChildOf(container));
+            commands.queue(move |world: &mut World| {
+                insert_row_sorted(world, container, tree_entity, entity);
+            });
         } else {
             // Parent has no tree row yet — remove this incorrectly-rooted tree row.
             // Lazy loading will re-create it when the parent is expanded.
@@ -415,7 +788,7 @@ fn on_entity_reparented(
 
     let container = parent_container;
     commands.queue(move |world: &mut World| {
-        if world.resource::<TreeIndex>().contains(entity) {
+        if world.resource::<TreeIndex>().contains(entity) || excluded_from_tree(world, entity) {
             return;
         }
         // In named-only mode, skip entities without a Name
@@ -426,6 +799,18 @@ fn on_entity_reparented(
     });
 }
 
+/// The children container of `parent`'s tree row, if its children are already shown.
+fn populated_row_container(world: &World, parent: Entity) -> Option<Entity> {
+    let row = world.resource::<TreeIndex>().get(parent)?;
+    if !world.get::<TreeChildrenPopulated>(row).is_some_and(|p| p.0) {
+        return None;
+    }
+    world
+        .get::<Children>(row)?
+        .iter()
+        .find(|&child| world.get::<TreeRowChildren>(child).is_some())
+}
+
 /// When an entity's Name is removed, despawn its tree row.
 fn on_entity_removed(
     trigger: On<Despawn, Name>,
@@ -441,21 +826,40 @@ fn on_entity_removed(
     }
 }
 
-/// When EditorHidden is added, remove the tree row if one exists (handles race with observers).
+/// When EditorHidden is added, remove the tree row if one exists (handles race with observers),
+/// or grey it out when hidden entities are shown.
 fn on_entity_hidden(
     trigger: On<Add, EditorHidden>,
     mut commands: Commands,
     tree_index: Res<TreeIndex>,
+    settings: Res<HierarchySettings>,
 ) {
     let entity = trigger.event_target();
 
     if let Some(tree_entity) = tree_index.get(entity) {
-        if let Ok(mut ec) = commands.get_entity(tree_entity) {
+        if settings.show_hidden {
+            commands.queue(move |world: &mut World| {
+                set_row_greyed(world, tree_entity, true);
+            });
+        } else if let Ok(mut ec) = commands.get_entity(tree_entity) {
             ec.despawn();
         }
     }
 }
 
+/// When EditorHidden is removed from a shown hidden entity, restore its row label.
+fn on_entity_unhidden(
+    trigger: On<Remove, EditorHidden>,
+    mut commands: Commands,
+    tree_index: Res<TreeIndex>,
+) {
+    if let Some(tree_entity) = tree_index.get(trigger.event_target()) {
+        commands.queue(move |world: &mut World| {
+            set_row_greyed(world, tree_entity, false);
+        });
+    }
+}
+
 /// When a tree node is expanded for the first time, spawn tree rows for its children.
 fn on_tree_node_expanded(
     trigger: On<Mutation<TreeNodeExpanded>>,
@@ -506,37 +910,50 @@ fn on_tree_node_expanded(
             .map(|c| c.iter().collect())
             .unwrap_or_default();
 
-        let mut child_data: Vec<(Entity, String, EntityCategory)> = Vec::new();
+        let settings = world.resource::<HierarchySettings>().clone();
+        let mut child_data: Vec<(RowSortKey, Entity)> = Vec::new();
         for child in source_children {
-            if world.get::<EditorEntity>(child).is_some()
-                || world.get::<EditorHidden>(child).is_some()
-            {
+            if excluded_from_tree(world, child) {
                 continue;
             }
             // Skip children that already have tree rows
             if world.resource::<TreeIndex>().contains(child) {
                 continue;
             }
-            let name = world
-                .get::<Name>(child)
-                .map(|n| n.as_str().to_string())
-                .unwrap_or_else(|| format!("Entity {child}"));
-            let category = classify_entity(world, child);
-            child_data.push((child, name, category));
+            child_data.push((row_sort_key(world, child, false, &settings), child));
         }
 
-        // Sort by (category, name)
-        child_data.sort_by(|(_, name_a, cat_a), (_, name_b, cat_b)| {
-            cat_a.cmp(cat_b).then_with(|| name_a.cmp(name_b))
-        });
+        // Sort up front so rows are appended in order
+        child_data.sort();
 
         // Spawn tree rows
-        for (child_entity, _name, _category) in child_data {
+        for (_key, child_entity) in child_data {
             spawn_single_tree_row(world, child_entity, container);
         }
     });
 }
 
+/// Maximum time between two clicks on a row to count as a double-click.
+const DOUBLE_CLICK_SECS: f32 = 0.4;
+
+/// F2 renames the primary selection inline, unless a text field has focus.
+fn handle_rename_key(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    selection: Res<Selection>,
+    input_focus: Res<InputFocus>,
+    mut commands: Commands,
+) {
+    if !keyboard.just_pressed(KeyCode::F2) || input_focus.0.is_some() {
+        return;
+    }
+    if let Some(primary) = selection.primary() {
+        commands.trigger(TreeRowStartRename {
+            entity: Entity::PLACEHOLDER,
+            source_entity: primary,
+        });
+    }
+}
+
 /// Handle tree row click → select the source entity.
 /// Plain click on selected entity → deselect. Ctrl+Click → toggle.
 fn on_tree_row_clicked(
@@ -545,11 +962,31 @@ fn on_tree_row_clicked(
     mut selection: ResMut<Selection>,
     mut focused: ResMut<TreeFocused>,
     keyboard: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut last_click: Local<Option<(Entity, f32)>>,
     parent_query: Query<&ChildOf>,
     tree_nodes: Query<Entity, With<TreeNode>>,
 ) {
     let ctrl = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
 
+    // Double-click: keep the row selected and start an inline rename
+    let now = time.elapsed_secs();
+    let double_click = !ctrl
+        && last_click.is_some_and(|(entity, at)| {
+            entity == event.source_entity && now - at < DOUBLE_CLICK_SECS
+        });
+    *last_click = (!double_click).then_some((event.source_entity, now));
+    if double_click {
+        if !selection.is_selected(event.source_entity) {
+            selection.select_single(&mut commands, event.source_entity);
+        }
+        commands.trigger(TreeRowStartRename {
+            entity: event.entity,
+            source_entity: event.source_entity,
+        });
+        return;
+    }
+
     if ctrl {
         selection.toggle(&mut commands, event.source_entity);
     } else if selection.is_selected(event.source_entity) {
@@ -671,12 +1108,39 @@ fn on_tree_row_dropped(
         cmd.execute(world);
         world
             .resource_mut::<CommandHistory>()
-            .undo_stack
-            .push(Box::new(cmd));
-        world.resource_mut::<CommandHistory>().redo_stack.clear();
+            .push_executed(Box::new(cmd));
     });
 }
 
+/// Handle an asset browser item dropped on a tree row → instantiate it as a child of that
+/// entity at its local origin.
+fn on_asset_dropped_on_row(
+    event: On<TreeRowExternalDropped>,
+    file_items: Query<&FileBrowserItem>,
+    parents: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    let Some(item) = find_ancestor_component(event.dropped, &file_items, &parents) else {
+        return;
+    };
+    let parent = Some(event.target_source);
+    let path = item.path.clone();
+    let path_lower = path.to_lowercase();
+    if path_lower.ends_with(".jsn") {
+        commands.queue(move |world: &mut World| {
+            crate::entity_templates::instantiate_jsn_prefab(world, &path, Vec3::ZERO, parent);
+        });
+    } else if path_lower.ends_with(".template.json") {
+        commands.queue(move |world: &mut World| {
+            crate::entity_templates::instantiate_template(world, &path, Vec3::ZERO, parent);
+        });
+    } else if path_lower.ends_with(".gltf") || path_lower.ends_with(".glb") {
+        commands.queue(move |world: &mut World| {
+            entity_ops::spawn_gltf_in_world(world, &path, Vec3::ZERO, parent);
+        });
+    }
+}
+
 /// Handle tree row dropped on root container → deparent the scene entity.
 fn on_tree_row_dropped_on_root(
     event: On<TreeRowDroppedOnRoot>,
@@ -704,16 +1168,14 @@ fn on_tree_row_dropped_on_root(
         cmd.execute(world);
         world
             .resource_mut::<CommandHistory>()
-            .undo_stack
-            .push(Box::new(cmd));
-        world.resource_mut::<CommandHistory>().redo_stack.clear();
+            .push_executed(Box::new(cmd));
     });
 
     // Move the tree row to the root container
     if let Some(tree_entity) = tree_index.get(dragged) {
-        commands
-            .entity(tree_entity)
-            .insert(ChildOf(container_entity));
+        commands.queue(move |world: &mut World| {
+            insert_row_sorted(world, container_entity, tree_entity, dragged);
+        });
     }
 }
 
@@ -722,7 +1184,7 @@ fn handle_hierarchy_right_click(
     mouse: Res<ButtonInput<MouseButton>>,
     mut commands: Commands,
     mut state: ResMut<ContextMenuState>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     selection: Res<Selection>,
     tree_row_contents: Query<(Entity, &ChildOf), With<TreeRowContent>>,
     tree_nodes: Query<&TreeNode>,
@@ -820,12 +1282,18 @@ fn handle_hierarchy_right_click(
 fn on_context_menu_action(
     event: On<ContextMenuAction>,
     mut commands: Commands,
+    mut settings: ResMut<HierarchySettings>,
     global_transforms: Query<&GlobalTransform>,
     mut camera_query: Query<&mut Transform, With<jackdaw_camera::JackdawCameraSettings>>,
 ) {
     let target_entity = event.target_entity;
 
     match event.action.as_str() {
+        "hierarchy.sort_type" => settings.sort = HierarchySort::Type,
+        "hierarchy.sort_name" => settings.sort = HierarchySort::Name,
+        "hierarchy.sort_creation" => settings.sort = HierarchySort::Creation,
+        "hierarchy.group_by_type" => settings.group_by_type = !settings.group_by_type,
+        "hierarchy.show_hidden" => settings.show_hidden = !settings.show_hidden,
         "hierarchy.focus" => {
             if let Some(target) = target_entity {
                 if let Ok(global_tf) = global_transforms.get(target) {
@@ -931,43 +1399,110 @@ fn on_context_menu_action(
     }
 }
 
-/// Toggle entity visibility when the eye icon is clicked.
-fn on_visibility_toggled(
-    event: On<TreeRowVisibilityToggled>,
-    mut commands: Commands,
-    visibility_query: Query<&Visibility>,
-) {
+/// Toggle the editor-only hidden flag when the eye icon is clicked.
+fn on_visibility_toggled(event: On<TreeRowVisibilityToggled>, mut commands: Commands) {
     let source = event.source_entity;
+    commands.queue(move |world: &mut World| toggle_flag::<HiddenInEditor>(world, source));
+}
 
-    let current = visibility_query
-        .get(source)
-        .copied()
-        .unwrap_or(Visibility::Inherited);
+/// Toggle the hidden-in-game flag when the gamepad icon is clicked.
+fn on_game_visibility_toggled(event: On<TreeRowGameVisibilityToggled>, mut commands: Commands) {
+    let source = event.source_entity;
+    commands.queue(move |world: &mut World| toggle_flag::<HiddenInGame>(world, source));
+}
 
-    let new_visibility = match current {
-        Visibility::Hidden => Visibility::Inherited,
-        _ => Visibility::Hidden,
+/// Add or remove a marker component on `entity`, with undo.
+fn toggle_flag<C: Component + Reflect + Default>(world: &mut World, entity: Entity) {
+    if world.get_entity(entity).is_err() {
+        return;
+    }
+    let type_id = TypeId::of::<C>();
+    let component_id = world.register_component::<C>();
+    let cmd: Box<dyn EditorCommand> = if world.get::<C>(entity).is_some() {
+        Box::new(RemoveComponent {
+            entity,
+            type_id,
+            component_id,
+            snapshot: Box::new(C::default()),
+        })
+    } else {
+        Box::new(AddComponent {
+            entity,
+            type_id,
+            component_id,
+        })
     };
+    cmd.execute(world);
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(cmd);
+}
 
-    // Apply with undo
-    let old_value: Box<dyn bevy::reflect::PartialReflect> = Box::new(current);
-    let new_value: Box<dyn bevy::reflect::PartialReflect> = Box::new(new_visibility);
+fn on_visibility_flag_added(
+    trigger: On<Add, (HiddenInEditor, HiddenInGame)>,
+    mut commands: Commands,
+) {
+    let source = trigger.event_target();
+    commands.queue(move |world: &mut World| sync_row_flag_icons(world, source));
+}
 
-    let cmd = SetComponentField {
-        entity: source,
-        component_type_id: TypeId::of::<Visibility>(),
-        field_path: String::new(),
-        old_value,
-        new_value,
+fn on_visibility_flag_removed(
+    trigger: On<Remove, (HiddenInEditor, HiddenInGame)>,
+    mut commands: Commands,
+) {
+    let source = trigger.event_target();
+    commands.queue(move |world: &mut World| sync_row_flag_icons(world, source));
+}
+
+/// Show the entity's visibility flags on its row: a crossed eye when hidden in the
+/// editor, a tinted gamepad when hidden in the game.
+fn sync_row_flag_icons(world: &mut World, source: Entity) {
+    let Some(tree_row) = world.resource::<TreeIndex>().get(source) else {
+        return;
     };
+    let hidden_in_editor = world.get::<HiddenInEditor>(source).is_some();
+    let hidden_in_game = world.get::<HiddenInGame>(source).is_some();
 
-    commands.queue(move |world: &mut World| {
-        let cmd = Box::new(cmd);
-        cmd.execute(world);
-        let mut history = world.resource_mut::<CommandHistory>();
-        history.undo_stack.push(cmd);
-        history.redo_stack.clear();
-    });
+    let toggles: Vec<Entity> = world
+        .get::<Children>(tree_row)
+        .into_iter()
+        .flatten()
+        .filter(|&&c| world.get::<TreeRowContent>(c).is_some())
+        .filter_map(|&c| world.get::<Children>(c))
+        .flatten()
+        .copied()
+        .filter(|&c| {
+            world.get::<TreeRowVisibilityToggle>(c).is_some()
+                || world.get::<TreeRowGameVisibilityToggle>(c).is_some()
+        })
+        .collect();
+
+    for toggle in toggles {
+        let (glyph, color) = if world.get::<TreeRowVisibilityToggle>(toggle).is_some() {
+            let icon = if hidden_in_editor {
+                Icon::EyeOff
+            } else {
+                Icon::Eye
+            };
+            (icon, tokens::TEXT_SECONDARY)
+        } else if hidden_in_game {
+            (Icon::Gamepad2, tokens::TEXT_ACCENT)
+        } else {
+            (Icon::Gamepad2, tokens::TEXT_SECONDARY)
+        };
+        let Some(icon) = world
+            .get::<Children>(toggle)
+            .and_then(|c| c.first().copied())
+        else {
+            continue;
+        };
+        let alpha = world.get::<TextColor>(icon).map_or(0.4, |c| c.0.alpha());
+        if let Some(mut text) = world.get_mut::<Text>(icon) {
+            text.0 = String::from(glyph.unicode());
+        }
+        if let Some(mut text_color) = world.get_mut::<TextColor>(icon) {
+            text_color.0 = color.with_alpha(alpha);
+        }
+    }
 }
 
 /// Marker for inline rename text_edit entity, linking back to the label entity and source entity.
@@ -1176,35 +1711,73 @@ fn handle_inline_rename_commit(
     });
 }
 
-/// Commit inline rename: update Name with undo.
-fn on_tree_row_renamed(event: On<TreeRowRenamed>, mut commands: Commands, names: Query<&Name>) {
+/// Commit inline rename: update Name with undo. Renaming one of several selected
+/// entities renames all of them with a numbered suffix.
+fn on_tree_row_renamed(
+    event: On<TreeRowRenamed>,
+    mut commands: Commands,
+    selection: Res<Selection>,
+) {
     let source = event.source_entity;
-    let new_name = event.new_name.clone();
-
-    // Apply name change with undo
-    let old_name = names
-        .get(source)
-        .map(|n| n.as_str().to_string())
-        .unwrap_or_default();
-
-    if old_name == new_name {
-        return;
-    }
+    let targets = if selection.entities.len() > 1 && selection.is_selected(source) {
+        selection.entities.clone()
+    } else {
+        vec![source]
+    };
+    let pattern = event.new_name.clone();
 
     commands.queue(move |world: &mut World| {
-        let cmd = SetComponentField {
-            entity: source,
-            component_type_id: TypeId::of::<Name>(),
-            field_path: String::new(),
-            old_value: Box::new(Name::new(old_name)),
-            new_value: Box::new(Name::new(new_name)),
+        rename_entities(world, &targets, &pattern);
+    });
+}
+
+/// Rename `targets` as one undo step. A single entity gets `pattern` verbatim; several
+/// get numbered names, see [`numbered_name`].
+pub fn rename_entities(world: &mut World, targets: &[Entity], pattern: &str) {
+    let mut cmds: Vec<Box<dyn EditorCommand>> = Vec::new();
+    for (i, &entity) in targets.iter().enumerate() {
+        let new_name = if targets.len() == 1 {
+            pattern.to_string()
+        } else {
+            numbered_name(pattern, i + 1)
+        };
+        let old_name = world.get::<Name>(entity).map(|n| n.as_str().to_string());
+        if old_name.as_deref() == Some(new_name.as_str()) {
+            continue;
+        }
+        let cmd = SetName {
+            entity,
+            old_name,
+            new_name: Some(new_name),
         };
-        let cmd = Box::new(cmd);
         cmd.execute(world);
-        let mut history = world.resource_mut::<CommandHistory>();
-        history.undo_stack.push(cmd);
-        history.redo_stack.clear();
-    });
+        cmds.push(Box::new(cmd));
+    }
+
+    let cmd: Box<dyn EditorCommand> = match cmds.len() {
+        0 => return,
+        1 => cmds.pop().unwrap(),
+        n => Box::new(CommandGroup {
+            commands: cmds,
+            label: format!("Rename {n} entities"),
+        }),
+    };
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(cmd);
+}
+
+/// `Crate_##` → `Crate_01`: a run of `#` is replaced by the zero-padded index.
+/// Without `#` the index is appended as `Crate_1`.
+fn numbered_name(pattern: &str, index: usize) -> String {
+    let Some(start) = pattern.find('#') else {
+        return format!("{pattern}_{index}");
+    };
+    let width = pattern[start..].chars().take_while(|&c| c == '#').count();
+    format!(
+        "{}{index:0width$}{}",
+        &pattern[..start],
+        &pattern[start + width..]
+    )
 }
 
 /// When the template dialog opens, populate its children slot with a name input.
@@ -1313,6 +1886,185 @@ fn on_show_all_changed(show_all: Res<HierarchyShowAll>, mut commands: Commands)
     }
 }
 
+/// Toggle chain collapsing when the button is pressed.
+fn toggle_collapse_chains_button(
+    mut collapse: ResMut<HierarchyCollapseChains>,
+    interactions: Query<&Interaction, (Changed<Interaction>, With<HierarchyCollapseChainsButton>)>,
+) {
+    for interaction in &interactions {
+        if *interaction == Interaction::Pressed {
+            collapse.0 = !collapse.0;
+        }
+    }
+}
+
+/// Update the collapse-chains button icon color based on active state.
+fn update_collapse_chains_button_appearance(
+    collapse: Res<HierarchyCollapseChains>,
+    buttons: Query<&Children, With<HierarchyCollapseChainsButton>>,
+    mut text_colors: Query<&mut TextColor>,
+) {
+    if !collapse.is_changed() {
+        return;
+    }
+    let color = if collapse.0 {
+        tokens::TEXT_PRIMARY
+    } else {
+        tokens::TEXT_SECONDARY
+    };
+    for children in &buttons {
+        for child in children.iter() {
+            if let Ok(mut tc) = text_colors.get_mut(child) {
+                tc.0 = color;
+            }
+        }
+    }
+}
+
+/// When chain collapsing is toggled, clear and rebuild the hierarchy.
+fn on_collapse_chains_changed(collapse: Res<HierarchyCollapseChains>, mut commands: Commands) {
+    if collapse.is_changed() && !collapse.is_added() {
+        commands.queue(|world: &mut World| {
+            clear_all_tree_rows(world);
+            rebuild_hierarchy(world);
+        });
+    }
+}
+
+/// Open the sort / group options menu below the options button.
+fn open_hierarchy_options_menu(
+    mut commands: Commands,
+    mut state: ResMut<ContextMenuState>,
+    settings: Res<HierarchySettings>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    interactions: Query<&Interaction, (Changed<Interaction>, With<HierarchyOptionsButton>)>,
+) {
+    if !interactions.iter().any(|i| *i == Interaction::Pressed) {
+        return;
+    }
+    let Some(cursor_pos) = windows.single().ok().and_then(|w| w.cursor_position()) else {
+        return;
+    };
+
+    if let Some(menu) = state.menu_entity.take() {
+        if let Ok(mut ec) = commands.get_entity(menu) {
+            ec.despawn();
+        }
+    }
+
+    let mark = |on: bool| if on { "• " } else { "   " };
+    let labels = [
+        (
+            "hierarchy.sort_type",
+            format!("{}Sort by Type", mark(settings.sort == HierarchySort::Type)),
+        ),
+        (
+            "hierarchy.sort_name",
+            format!("{}Sort by Name", mark(settings.sort == HierarchySort::Name)),
+        ),
+        (
+            "hierarchy.sort_creation",
+            format!(
+                "{}Sort by Creation Order",
+                mark(settings.sort == HierarchySort::Creation)
+            ),
+        ),
+        (
+            "hierarchy.group_by_type",
+            format!("{}Group by Type", mark(settings.group_by_type)),
+        ),
+        (
+            "hierarchy.show_hidden",
+            format!("{}Show Hidden Entities", mark(settings.show_hidden)),
+        ),
+    ];
+    let items: Vec<(&str, &str)> = labels
+        .iter()
+        .map(|(action, label)| (*action, label.as_str()))
+        .collect();
+
+    let menu = spawn_context_menu(&mut commands, cursor_pos, None, &items);
+    state.menu_entity = Some(menu);
+    state.target_entity = None;
+}
+
+fn hierarchy_settings_path(project: &ProjectRoot) -> std::path::PathBuf {
+    project.jsn_dir().join("hierarchy.json")
+}
+
+fn load_hierarchy_settings(
+    project: Option<Res<ProjectRoot>>,
+    mut settings: ResMut<HierarchySettings>,
+) {
+    let Some(project) = project else {
+        return;
+    };
+    if !project.is_changed() {
+        return;
+    }
+    let loaded: HierarchySettings = std::fs::read_to_string(hierarchy_settings_path(&project))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default();
+    if *settings != loaded {
+        *settings = loaded;
+    }
+}
+
+fn save_hierarchy_settings(project: Option<Res<ProjectRoot>>, settings: Res<HierarchySettings>) {
+    if !settings.is_changed() || settings.is_added() {
+        return;
+    }
+    let Some(project) = project else {
+        return;
+    };
+    let path = hierarchy_settings_path(&project);
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    match serde_json::to_string_pretty(&*settings) {
+        Ok(data) => {
+            if let Err(e) = std::fs::write(&path, data) {
+                warn!("Failed to write {}: {e}", path.display());
+            }
+        }
+        Err(e) => warn!("Failed to serialize hierarchy settings: {e}"),
+    }
+}
+
+/// When the sort / group / hidden options change, clear and rebuild the hierarchy.
+fn on_hierarchy_settings_changed(settings: Res<HierarchySettings>, mut commands: Commands) {
+    if settings.is_changed() && !settings.is_added() {
+        commands.queue(|world: &mut World| {
+            clear_all_tree_rows(world);
+            rebuild_hierarchy(world);
+        });
+    }
+}
+
+/// Despawn group headers whose rows have all been removed or moved. Rows are sorted,
+/// so a header is empty when the next root row is another header or there is none.
+fn prune_empty_group_headers(
+    mut commands: Commands,
+    container: Option<Single<Ref<Children>, With<HierarchyTreeContainer>>>,
+    headers: Query<(), With<HierarchyGroupHeader>>,
+) {
+    let Some(children) = container else {
+        return;
+    };
+    if !children.is_changed() {
+        return;
+    }
+    for (i, child) in children.iter().enumerate() {
+        let empty = children
+            .get(i + 1)
+            .is_none_or(|&next| headers.contains(next));
+        if headers.contains(child) && empty {
+            commands.entity(child).despawn();
+        }
+    }
+}
+
 /// Despawn all tree rows and clear the TreeIndex.
 pub fn clear_all_tree_rows(world: &mut World) {
     let container = world
@@ -0,0 +1,441 @@
+use crate::commands::{CommandHistory, EditorCommand, SetComponentField};
+use crate::entity_ops::to_asset_path;
+use crate::material_browser::{TextureSlot, is_16bit_png};
+use crate::viewport::find_ancestor_component;
+use std::any::TypeId;
+use std::path::Path;
+
+use bevy::{
+    ecs::reflect::{AppTypeRegistry, ReflectComponent},
+    gltf::GltfAssetLabel,
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task, futures_lite::future},
+    ui_widgets::observe,
+};
+use jackdaw_feathers::{icons::Icon, tokens};
+use jackdaw_widgets::file_browser::FileBrowserItem;
+use rfd::{AsyncFileDialog, FileHandle};
+
+use super::InspectorDirty;
+use super::material_display::{self, edit_material};
+
+/// Asset types whose handle fields get a picker instead of `<opaque>`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum AssetKind {
+    Image,
+    Mesh,
+    Material,
+}
+
+impl AssetKind {
+    pub(super) fn of(value: &dyn PartialReflect) -> Option<Self> {
+        if value.try_downcast_ref::<Handle<Image>>().is_some() {
+            Some(Self::Image)
+        } else if value.try_downcast_ref::<Handle<Mesh>>().is_some() {
+            Some(Self::Mesh)
+        } else if value
+            .try_downcast_ref::<Handle<StandardMaterial>>()
+            .is_some()
+        {
+            Some(Self::Material)
+        } else {
+            None
+        }
+    }
+
+    fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Self::Image => &[
+                "png", "jpg", "jpeg", "ktx2", "dds", "tga", "hdr", "exr", "webp",
+            ],
+            // Meshes and materials come out of glTF files
+            Self::Mesh | Self::Material => &["gltf", "glb"],
+        }
+    }
+
+    fn accepts(self, path: &str) -> bool {
+        Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.extensions().contains(&ext.to_lowercase().as_str()))
+    }
+
+    fn filter_name(self) -> &'static str {
+        match self {
+            Self::Image => "Image",
+            Self::Mesh => "glTF Mesh",
+            Self::Material => "glTF Material",
+        }
+    }
+
+    /// Load `path` as this kind of asset. glTF files give their first mesh or material.
+    fn load(self, asset_server: &AssetServer, path: String) -> Box<dyn PartialReflect> {
+        match self {
+            Self::Image => Box::new(asset_server.load::<Image>(path)),
+            Self::Mesh => Box::new(
+                asset_server.load::<Mesh>(
+                    GltfAssetLabel::Primitive {
+                        mesh: 0,
+                        primitive: 0,
+                    }
+                    .from_asset(path),
+                ),
+            ),
+            Self::Material => Box::new(
+                asset_server.load::<StandardMaterial>(
+                    GltfAssetLabel::Material {
+                        index: 0,
+                        is_scale_inverted: false,
+                    }
+                    .from_asset(path),
+                ),
+            ),
+        }
+    }
+}
+
+/// Text shown for a handle field: the asset path, or what kind of handle it is.
+pub(super) fn handle_label(value: &dyn PartialReflect) -> String {
+    fn label<A: Asset>(handle: &Handle<A>) -> String {
+        match handle.path() {
+            Some(path) => path.to_string(),
+            None if handle.is_strong() => "(generated)".to_string(),
+            None => "(none)".to_string(),
+        }
+    }
+    if let Some(handle) = value.try_downcast_ref::<Handle<Image>>() {
+        label(handle)
+    } else if let Some(handle) = value.try_downcast_ref::<Handle<Mesh>>() {
+        label(handle)
+    } else if let Some(handle) = value.try_downcast_ref::<Handle<StandardMaterial>>() {
+        label(handle)
+    } else {
+        String::new()
+    }
+}
+
+/// The handle an asset picker edits.
+#[derive(Component, Clone)]
+pub(super) struct AssetFieldBinding {
+    pub(super) source_entity: Entity,
+    pub(super) target: AssetFieldTarget,
+    pub(super) kind: AssetKind,
+}
+
+#[derive(Clone)]
+pub(super) enum AssetFieldTarget {
+    /// A handle field of a reflected component.
+    Field {
+        component_type_id: TypeId,
+        field_path: String,
+    },
+    /// A texture slot of the entity's `StandardMaterial`, edited through the material asset.
+    MaterialTexture(TextureSlot),
+}
+
+/// Path display of an asset picker, kept in sync with the field by `refresh_asset_fields`.
+#[derive(Component)]
+pub(super) struct AssetFieldPath;
+
+#[derive(Resource)]
+pub(super) struct AssetPickerTask {
+    task: Task<Option<FileHandle>>,
+    binding: AssetFieldBinding,
+}
+
+/// Asset picker row: label, current path (also a drop target for files from the asset
+/// browser) and a browse button.
+pub(super) fn asset_field(
+    name: &str,
+    current: String,
+    binding: AssetFieldBinding,
+    depth: usize,
+    icon_font: Handle<Font>,
+) -> impl Bundle {
+    (
+        binding,
+        Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: px(tokens::SPACING_XS),
+            padding: UiRect::left(px(depth as f32 * tokens::SPACING_MD)),
+            ..Default::default()
+        },
+        children![
+            (
+                Text::new(format!("{name}:")),
+                TextFont {
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                TextColor(tokens::TEXT_SECONDARY),
+                Node {
+                    min_width: px(20.0),
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+            ),
+            (
+                Node {
+                    flex_grow: 1.0,
+                    min_width: px(0.0),
+                    padding: UiRect::axes(px(tokens::SPACING_XS), px(2.0)),
+                    overflow: Overflow::clip(),
+                    ..Default::default()
+                },
+                BackgroundColor(tokens::INPUT_BG),
+                observe(on_asset_field_drop),
+                observe(on_asset_field_drag_enter),
+                observe(on_asset_field_drag_leave),
+                children![(
+                    AssetFieldPath,
+                    Text::new(current),
+                    TextFont {
+                        font_size: tokens::FONT_SM,
+                        ..Default::default()
+                    },
+                    TextColor(tokens::TEXT_PRIMARY),
+                    Pickable::IGNORE,
+                )],
+            ),
+            (
+                Text::new(String::from(Icon::FolderOpen.unicode())),
+                TextFont {
+                    font: icon_font,
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                TextColor(tokens::TEXT_SECONDARY),
+                observe(on_asset_field_browse),
+            ),
+        ],
+    )
+}
+
+fn on_asset_field_browse(
+    click: On<Pointer<Click>>,
+    bindings: Query<&AssetFieldBinding>,
+    parents: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    let Some(binding) = find_ancestor_component(click.event_target(), &bindings, &parents).cloned()
+    else {
+        return;
+    };
+    commands.queue(move |world: &mut World| {
+        if world.contains_resource::<AssetPickerTask>() {
+            return;
+        }
+        let raw_handle = crate::scene_io::get_window_handle(world);
+        let mut dialog = AsyncFileDialog::new()
+            .set_title("Select asset")
+            .add_filter(binding.kind.filter_name(), binding.kind.extensions());
+        if let Some(project) = world.get_resource::<crate::project::ProjectRoot>() {
+            dialog = dialog.set_directory(project.assets_dir());
+        }
+        if let Some(ref rh) = raw_handle {
+            // SAFETY: called on the main thread during an exclusive system
+            let handle = unsafe { rh.get_handle() };
+            dialog = dialog.set_parent(&handle);
+        }
+        let task = AsyncComputeTaskPool::get().spawn(async move { dialog.pick_file().await });
+        world.insert_resource(AssetPickerTask { task, binding });
+    });
+}
+
+pub(super) fn poll_asset_picker_dialog(world: &mut World) {
+    let Some(mut picker) = world.remove_resource::<AssetPickerTask>() else {
+        return;
+    };
+    let Some(result) = future::block_on(future::poll_once(&mut picker.task)) else {
+        world.insert_resource(picker);
+        return;
+    };
+    if let Some(file) = result {
+        assign_asset(world, &picker.binding, &file.path().to_string_lossy());
+    }
+}
+
+fn on_asset_field_drop(
+    mut drop: On<Pointer<DragDrop>>,
+    file_items: Query<&FileBrowserItem>,
+    bindings: Query<&AssetFieldBinding>,
+    parents: Query<&ChildOf>,
+    mut backgrounds: Query<&mut BackgroundColor>,
+    mut commands: Commands,
+) {
+    drop.propagate(false);
+    if let Ok(mut bg) = backgrounds.get_mut(drop.event_target()) {
+        bg.0 = tokens::INPUT_BG;
+    }
+    let Some(item) = find_ancestor_component(drop.dropped, &file_items, &parents) else {
+        return;
+    };
+    let Some(binding) = find_ancestor_component(drop.event_target(), &bindings, &parents) else {
+        return;
+    };
+    if item.is_directory || !binding.kind.accepts(&item.path) {
+        return;
+    }
+    let binding = binding.clone();
+    let path = item.path.clone();
+    commands.queue(move |world: &mut World| assign_asset(world, &binding, &path));
+}
+
+fn on_asset_field_drag_enter(
+    mut enter: On<Pointer<DragEnter>>,
+    mut backgrounds: Query<&mut BackgroundColor>,
+) {
+    enter.propagate(false);
+    if let Ok(mut bg) = backgrounds.get_mut(enter.event_target()) {
+        bg.0 = tokens::DROP_TARGET_BG;
+    }
+}
+
+fn on_asset_field_drag_leave(
+    mut leave: On<Pointer<DragLeave>>,
+    mut backgrounds: Query<&mut BackgroundColor>,
+) {
+    leave.propagate(false);
+    if let Ok(mut bg) = backgrounds.get_mut(leave.event_target()) {
+        bg.0 = tokens::INPUT_BG;
+    }
+}
+
+/// Point the bound handle at the asset at `path`, undoably.
+fn assign_asset(world: &mut World, binding: &AssetFieldBinding, path: &str) {
+    match &binding.target {
+        AssetFieldTarget::Field {
+            component_type_id,
+            field_path,
+        } => assign_field_asset(
+            world,
+            binding.source_entity,
+            *component_type_id,
+            field_path,
+            binding.kind,
+            path,
+        ),
+        AssetFieldTarget::MaterialTexture(slot) => {
+            let slot = *slot;
+            // 16-bit PNGs decode as R16Uint, which the depth map slot can't sample
+            if slot == TextureSlot::DepthMap && is_16bit_png(Path::new(path)) {
+                return;
+            }
+            let image = slot.load(world.resource::<AssetServer>(), to_asset_path(path));
+            edit_material(world, binding.source_entity, |mat| {
+                slot.set_on(mat, Some(image));
+            });
+        }
+    }
+}
+
+fn assign_field_asset(
+    world: &mut World,
+    source_entity: Entity,
+    component_type_id: TypeId,
+    field_path: &str,
+    kind: AssetKind,
+    path: &str,
+) {
+    let new_value = kind.load(world.resource::<AssetServer>(), to_asset_path(path));
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let Some(reflect_component) = registry
+        .get(component_type_id)
+        .and_then(|registration| registration.data::<ReflectComponent>())
+    else {
+        return;
+    };
+    let Ok(entity_ref) = world.get_entity(source_entity) else {
+        return;
+    };
+    let Some(reflected) = reflect_component.reflect(entity_ref) else {
+        return;
+    };
+    let old_value = if field_path.is_empty() {
+        reflected.to_dynamic()
+    } else {
+        let Ok(field) = reflected.reflect_path(field_path) else {
+            return;
+        };
+        field.to_dynamic()
+    };
+    drop(registry);
+
+    let cmd = SetComponentField {
+        entity: source_entity,
+        component_type_id,
+        field_path: field_path.to_string(),
+        old_value,
+        new_value,
+    };
+    cmd.execute(world);
+    world
+        .resource_mut::<CommandHistory>()
+        .push_executed(Box::new(cmd));
+
+    // Material fields below a material picker show the old material until rebuilt
+    if component_type_id == TypeId::of::<MeshMaterial3d<StandardMaterial>>() {
+        world.entity_mut(source_entity).insert(InspectorDirty);
+    }
+}
+
+/// Keep picker paths in sync with their fields, e.g. after undo.
+pub(super) fn refresh_asset_fields(world: &mut World) {
+    let mut pickers = world.query::<&AssetFieldBinding>();
+    let mut path_texts = world.query_filtered::<(Entity, &ChildOf), With<AssetFieldPath>>();
+    let labels: Vec<(Entity, String)> = {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        path_texts
+            .iter(world)
+            .filter_map(|(text, child_of)| {
+                // Path text sits in the drop box, a child of the picker row
+                let row = world.get::<ChildOf>(child_of.parent())?.parent();
+                let binding = pickers.get(world, row).ok()?;
+                let label = match &binding.target {
+                    AssetFieldTarget::Field {
+                        component_type_id,
+                        field_path,
+                    } => {
+                        // An overridden material shows the shared one it derives from
+                        if *component_type_id == TypeId::of::<MeshMaterial3d<StandardMaterial>>() {
+                            let handle =
+                                material_display::material_handle(world, binding.source_entity)?;
+                            return Some((text, handle_label(&handle)));
+                        }
+                        let reflected = registry
+                            .get(*component_type_id)?
+                            .data::<ReflectComponent>()?
+                            .reflect(world.get_entity(binding.source_entity).ok()?)?;
+                        let field = if field_path.is_empty() {
+                            reflected.as_partial_reflect()
+                        } else {
+                            reflected.reflect_path(field_path.as_str()).ok()?
+                        };
+                        handle_label(field)
+                    }
+                    AssetFieldTarget::MaterialTexture(slot) => {
+                        let handle =
+                            material_display::material_handle(world, binding.source_entity)?;
+                        let material = world.resource::<Assets<StandardMaterial>>().get(&handle)?;
+                        match slot.get_from(material) {
+                            Some(image) => handle_label(&image),
+                            None => "(none)".to_string(),
+                        }
+                    }
+                };
+                Some((text, label))
+            })
+            .collect()
+    };
+    for (text, label) in labels {
+        if let Some(mut current) = world.get_mut::<Text>(text)
+            && current.0 != label
+        {
+            current.0 = label;
+        }
+    }
+}
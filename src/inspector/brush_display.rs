@@ -4,9 +4,11 @@ use crate::commands::CommandHistory;
 
 use bevy::prelude::*;
 use jackdaw_feathers::{
-    text_edit::{self, TextEditCommitEvent, TextEditProps},
+    combobox::{ComboBoxChangeEvent, combobox_with_selected},
+    text_edit::{self, NumericUnit, TextEditCommitEvent, TextEditProps},
     tokens,
 };
+use jackdaw_geometry::{compute_brush_geometry, wrap_face_texture};
 
 use super::{BrushFaceField, BrushFaceFieldBinding, BrushFacePropsContainer};
 
@@ -14,10 +16,26 @@ use super::{BrushFaceField, BrushFaceFieldBinding, BrushFacePropsContainer};
 #[derive(Event, Debug, Clone)]
 pub(crate) struct ApplyTextureToAllFaces;
 
+/// Wrap the first selected face's texture onto its neighbours, continuing its
+/// alignment around the corners. With more faces selected, only those are wrapped.
+#[derive(Event, Debug, Clone)]
+pub(crate) struct WrapTextureToAdjacentFaces;
+
 /// Apply a UV scale preset to all selected faces.
 #[derive(Event, Debug, Clone)]
 pub(crate) struct ApplyUvScalePreset(pub f32);
 
+/// Set the surface type of all selected faces (empty string clears it).
+#[derive(Event, Debug, Clone)]
+pub(crate) struct SetFaceSurface(pub String);
+
+/// Set the smoothing group of all selected faces (zero makes them flat).
+#[derive(Event, Debug, Clone)]
+pub(crate) struct SetFaceSmoothingGroup(pub u32);
+
+/// Smoothing groups offered in the face properties, besides flat.
+const SMOOTHING_GROUPS: u32 = 8;
+
 pub(super) fn spawn_brush_display(
     commands: &mut Commands,
     parent: Entity,
@@ -87,6 +105,8 @@ fn hash_face_data(face: &BrushFaceData) -> u64 {
     face.uv_scale.x.to_bits().hash(&mut hasher);
     face.uv_scale.y.to_bits().hash(&mut hasher);
     face.uv_rotation.to_bits().hash(&mut hasher);
+    face.surface.hash(&mut hasher);
+    face.smoothing_group.hash(&mut hasher);
     hasher.finish()
 }
 
@@ -98,6 +118,7 @@ pub(crate) fn update_brush_face_properties(
     container_query: Query<(Entity, Option<&Children>), With<BrushFacePropsContainer>>,
     mut local_state: Local<BrushFacePropsState>,
     materials: Res<Assets<StandardMaterial>>,
+    project: Option<Res<crate::project::ProjectRoot>>,
 ) {
     let Ok((container_entity, container_children)) = container_query.single() else {
         return;
@@ -310,6 +331,47 @@ pub(crate) fn update_brush_face_properties(
                 }
             },
         );
+
+        // "Wrap to Adjacent Faces" button
+        let wrap_btn = commands
+            .spawn((
+                Node {
+                    padding: UiRect::axes(Val::Px(tokens::SPACING_SM), Val::Px(2.0)),
+                    border_radius: BorderRadius::all(Val::Px(3.0)),
+                    ..Default::default()
+                },
+                BackgroundColor(tokens::INPUT_BG),
+                ChildOf(container_entity),
+            ))
+            .id();
+        commands.spawn((
+            Text::new("Wrap to Adjacent Faces"),
+            TextFont {
+                font_size: tokens::FONT_SM,
+                ..Default::default()
+            },
+            TextColor(tokens::TEXT_PRIMARY),
+            ChildOf(wrap_btn),
+        ));
+        commands
+            .entity(wrap_btn)
+            .observe(|_: On<Pointer<Click>>, mut commands: Commands| {
+                commands.trigger(WrapTextureToAdjacentFaces);
+            });
+        commands.entity(wrap_btn).observe(
+            |hover: On<Pointer<Over>>, mut bg: Query<&mut BackgroundColor>| {
+                if let Ok(mut bg) = bg.get_mut(hover.event_target()) {
+                    bg.0 = tokens::HOVER_BG;
+                }
+            },
+        );
+        commands.entity(wrap_btn).observe(
+            |out: On<Pointer<Out>>, mut bg: Query<&mut BackgroundColor>| {
+                if let Ok(mut bg) = bg.get_mut(out.event_target()) {
+                    bg.0 = tokens::INPUT_BG;
+                }
+            },
+        );
     } else {
         commands.spawn((
             Text::new("No Material"),
@@ -442,6 +504,7 @@ pub(crate) fn update_brush_face_properties(
             TextEditProps::default()
                 .numeric_f32()
                 .grow()
+                .with_unit(NumericUnit::Degrees)
                 .with_default_value(rotation_degrees.to_string()),
         ),
         BrushFaceFieldBinding {
@@ -449,6 +512,102 @@ pub(crate) fn update_brush_face_properties(
         },
         ChildOf(rot_row),
     ));
+
+    // Surface type
+    let surface_row = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: px(tokens::SPACING_XS),
+                width: Val::Percent(100.0),
+                ..Default::default()
+            },
+            ChildOf(container_entity),
+        ))
+        .id();
+
+    commands.spawn((
+        Text::new("Surface"),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_SECONDARY),
+        Node {
+            min_width: px(60.0),
+            flex_shrink: 0.0,
+            ..Default::default()
+        },
+        ChildOf(surface_row),
+    ));
+
+    let mut surface_options = vec!["None".to_string()];
+    surface_options.extend(crate::surface_types::project_surface_types(
+        project.as_deref(),
+    ));
+    if !face.surface.is_empty() && !surface_options.contains(&face.surface) {
+        surface_options.push(face.surface.clone());
+    }
+    let selected = surface_options
+        .iter()
+        .position(|s| *s == face.surface)
+        .unwrap_or(0);
+    commands
+        .spawn((
+            combobox_with_selected(surface_options, selected),
+            ChildOf(surface_row),
+        ))
+        .observe(|event: On<ComboBoxChangeEvent>, mut commands: Commands| {
+            let surface = if event.selected == 0 {
+                String::new()
+            } else {
+                event.label.clone()
+            };
+            commands.trigger(SetFaceSurface(surface));
+        });
+
+    // Smoothing group
+    let smoothing_row = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: px(tokens::SPACING_XS),
+                width: Val::Percent(100.0),
+                ..Default::default()
+            },
+            ChildOf(container_entity),
+        ))
+        .id();
+
+    commands.spawn((
+        Text::new("Smoothing"),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_SECONDARY),
+        Node {
+            min_width: px(60.0),
+            flex_shrink: 0.0,
+            ..Default::default()
+        },
+        ChildOf(smoothing_row),
+    ));
+
+    let max_group = SMOOTHING_GROUPS.max(face.smoothing_group);
+    let smoothing_options: Vec<String> = std::iter::once("Flat".to_string())
+        .chain((1..=max_group).map(|group| format!("Group {group}")))
+        .collect();
+    commands
+        .spawn((
+            combobox_with_selected(smoothing_options, face.smoothing_group as usize),
+            ChildOf(smoothing_row),
+        ))
+        .observe(|event: On<ComboBoxChangeEvent>, mut commands: Commands| {
+            commands.trigger(SetFaceSmoothingGroup(event.selected as u32));
+        });
 }
 
 fn spawn_brush_face_field_row(
@@ -579,8 +738,7 @@ fn apply_brush_face_field(
         new: brush.clone(),
         label: "Edit face UV".to_string(),
     };
-    history.undo_stack.push(Box::new(cmd));
-    history.redo_stack.clear();
+    history.push_executed(Box::new(cmd));
 }
 
 pub(crate) fn handle_clear_material(
@@ -616,8 +774,7 @@ pub(crate) fn handle_clear_material(
         new: brush.clone(),
         label: "Clear material".to_string(),
     };
-    history.undo_stack.push(Box::new(cmd));
-    history.redo_stack.clear();
+    history.push_executed(Box::new(cmd));
 }
 
 pub(crate) fn handle_clear_texture(
@@ -653,8 +810,7 @@ pub(crate) fn handle_clear_texture(
         new: brush.clone(),
         label: "Clear texture".to_string(),
     };
-    history.undo_stack.push(Box::new(cmd));
-    history.redo_stack.clear();
+    history.push_executed(Box::new(cmd));
 }
 
 pub(crate) fn handle_apply_texture_to_all(
@@ -697,8 +853,75 @@ pub(crate) fn handle_apply_texture_to_all(
         new: brush.clone(),
         label: "Apply material to all faces".to_string(),
     };
-    history.undo_stack.push(Box::new(cmd));
-    history.redo_stack.clear();
+    history.push_executed(Box::new(cmd));
+}
+
+pub(crate) fn handle_wrap_texture_to_adjacent(
+    _event: On<WrapTextureToAdjacentFaces>,
+    brush_selection: Res<BrushSelection>,
+    edit_mode: Res<EditMode>,
+    mut brushes: Query<&mut Brush>,
+    mut history: ResMut<CommandHistory>,
+) {
+    if *edit_mode != EditMode::BrushEdit(BrushEditMode::Face) {
+        return;
+    }
+    let Some(brush_entity) = brush_selection.entity else {
+        return;
+    };
+    let Some(&source_idx) = brush_selection.faces.first() else {
+        return;
+    };
+    let Ok(mut brush) = brushes.get_mut(brush_entity) else {
+        return;
+    };
+    if source_idx >= brush.faces.len() {
+        return;
+    }
+
+    let (vertices, polygons) = compute_brush_geometry(&brush.faces);
+    let shared_edge = |a: usize, b: usize| {
+        let shared: Vec<usize> = polygons[a]
+            .iter()
+            .copied()
+            .filter(|vi| polygons[b].contains(vi))
+            .collect();
+        (shared.len() >= 2).then(|| vertices[shared[0]])
+    };
+
+    // Walk outwards from the source face so each face continues from a neighbour
+    // that's already aligned, carrying the texture around the whole brush.
+    let mut remaining: Vec<usize> = if brush_selection.faces.len() > 1 {
+        brush_selection.faces[1..].to_vec()
+    } else {
+        (0..brush.faces.len())
+            .filter(|&fi| fi != source_idx && shared_edge(source_idx, fi).is_some())
+            .collect()
+    };
+    let old = brush.clone();
+    let mut frontier = vec![source_idx];
+    while let Some(from) = frontier.pop() {
+        let mut i = 0;
+        while i < remaining.len() {
+            let to = remaining[i];
+            let Some(edge_point) = shared_edge(from, to) else {
+                i += 1;
+                continue;
+            };
+            let source = brush.faces[from].clone();
+            wrap_face_texture(&source, &mut brush.faces[to], edge_point);
+            remaining.swap_remove(i);
+            frontier.push(to);
+        }
+    }
+
+    let cmd = SetBrush {
+        entity: brush_entity,
+        old,
+        new: brush.clone(),
+        label: "Wrap texture to adjacent faces".to_string(),
+    };
+    history.push_executed(Box::new(cmd));
 }
 
 pub(crate) fn handle_uv_scale_preset(
@@ -735,6 +958,77 @@ pub(crate) fn handle_uv_scale_preset(
         new: brush.clone(),
         label: "Set UV scale preset".to_string(),
     };
-    history.undo_stack.push(Box::new(cmd));
-    history.redo_stack.clear();
+    history.push_executed(Box::new(cmd));
+}
+
+pub(crate) fn handle_set_face_smoothing_group(
+    event: On<SetFaceSmoothingGroup>,
+    brush_selection: Res<BrushSelection>,
+    edit_mode: Res<EditMode>,
+    mut brushes: Query<&mut Brush>,
+    mut history: ResMut<CommandHistory>,
+) {
+    if *edit_mode != EditMode::BrushEdit(BrushEditMode::Face) {
+        return;
+    }
+    let Some(brush_entity) = brush_selection.entity else {
+        return;
+    };
+    if brush_selection.faces.is_empty() {
+        return;
+    }
+    let Ok(mut brush) = brushes.get_mut(brush_entity) else {
+        return;
+    };
+
+    let old = brush.clone();
+    for &face_idx in &brush_selection.faces {
+        if face_idx < brush.faces.len() {
+            brush.faces[face_idx].smoothing_group = event.0;
+        }
+    }
+
+    let cmd = SetBrush {
+        entity: brush_entity,
+        old,
+        new: brush.clone(),
+        label: "Set face smoothing".to_string(),
+    };
+    history.push_executed(Box::new(cmd));
+}
+
+pub(crate) fn handle_set_face_surface(
+    event: On<SetFaceSurface>,
+    brush_selection: Res<BrushSelection>,
+    edit_mode: Res<EditMode>,
+    mut brushes: Query<&mut Brush>,
+    mut history: ResMut<CommandHistory>,
+) {
+    if *edit_mode != EditMode::BrushEdit(BrushEditMode::Face) {
+        return;
+    }
+    let Some(brush_entity) = brush_selection.entity else {
+        return;
+    };
+    if brush_selection.faces.is_empty() {
+        return;
+    }
+    let Ok(mut brush) = brushes.get_mut(brush_entity) else {
+        return;
+    };
+
+    let old = brush.clone();
+    for &face_idx in &brush_selection.faces {
+        if face_idx < brush.faces.len() {
+            brush.faces[face_idx].surface = event.0.clone();
+        }
+    }
+
+    let cmd = SetBrush {
+        entity: brush_entity,
+        old,
+        new: brush.clone(),
+        label: "Set face surface".to_string(),
+    };
+    history.push_executed(Box::new(cmd));
 }
@@ -0,0 +1,552 @@
+//! Compare mode: two entities' components side by side in their own window, with fields
+//! that differ highlighted and buttons to copy a field from one entity to the other.
+
+use crate::commands::{CommandHistory, EditorCommand, SetComponentField};
+use crate::layout::{SecondaryWindow, spawn_secondary_window};
+use crate::selection::Selection;
+use std::any::TypeId;
+use std::collections::BTreeMap;
+
+use bevy::{
+    ecs::reflect::{AppTypeRegistry, ReflectComponent},
+    prelude::*,
+    reflect::ReflectRef,
+    ui_widgets::observe,
+    window::WindowCloseRequested,
+};
+use jackdaw_feathers::{
+    icons::{Icon, IconFont},
+    tokens,
+};
+
+use super::MAX_REFLECT_DEPTH;
+
+const COMPARE_WINDOW_SIZE: UVec2 = UVec2::new(720, 640);
+const DIFF_COLOR: Color = Color::srgb(1.0, 0.6, 0.3);
+const DIFF_ROW_BG: Color = Color::srgba(1.0, 0.6, 0.3, 0.12);
+
+/// OS window comparing `left` and `right`.
+#[derive(Component)]
+pub(super) struct CompareWindow {
+    secondary: SecondaryWindow,
+    left: Entity,
+    right: Entity,
+}
+
+/// Scrolling list of rows in a compare window, and the rows it currently shows.
+#[derive(Component, Default)]
+pub(super) struct CompareList(Vec<CompareRow>);
+
+#[derive(Clone, PartialEq)]
+enum CompareRow {
+    /// A component, present on the left, the right or both.
+    Component {
+        name: String,
+        left: bool,
+        right: bool,
+    },
+    Field {
+        component_type_id: TypeId,
+        field_path: String,
+        label: String,
+        depth: usize,
+        left: Option<String>,
+        right: Option<String>,
+        differs: bool,
+    },
+}
+
+/// Open a compare window for the first two selected entities.
+pub fn open_compare_window(world: &mut World) {
+    let &[left, right, ..] = world.resource::<Selection>().entities.as_slice() else {
+        warn!("Select two entities to compare");
+        return;
+    };
+    let title = format!(
+        "Compare: {} / {}",
+        entity_label(world, left),
+        entity_label(world, right)
+    );
+    let secondary = spawn_secondary_window(world, &title, COMPARE_WINDOW_SIZE);
+    world.entity_mut(secondary.window).insert(CompareWindow {
+        secondary,
+        left,
+        right,
+    });
+
+    let header = world
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                width: percent(100),
+                height: px(tokens::ROW_HEIGHT),
+                padding: UiRect::horizontal(px(tokens::SPACING_MD)),
+                flex_shrink: 0.0,
+                ..Default::default()
+            },
+            BackgroundColor(tokens::PANEL_HEADER_BG),
+            ChildOf(secondary.root),
+        ))
+        .id();
+    for (text, width) in [
+        (String::from("Field"), 34.0),
+        (entity_label(world, left), 30.0),
+        (String::new(), 6.0),
+        (entity_label(world, right), 30.0),
+    ] {
+        world.spawn((
+            Text::new(text),
+            TextFont {
+                font_size: tokens::FONT_MD,
+                ..Default::default()
+            },
+            TextColor(tokens::TEXT_PRIMARY),
+            Node {
+                width: percent(width),
+                ..Default::default()
+            },
+            ChildOf(header),
+        ));
+    }
+    world.spawn((
+        CompareList::default(),
+        Node {
+            flex_direction: FlexDirection::Column,
+            flex_grow: 1.0,
+            min_height: px(0.0),
+            overflow: Overflow::scroll_y(),
+            padding: UiRect::all(px(tokens::SPACING_SM)),
+            row_gap: px(1.0),
+            ..Default::default()
+        },
+        BackgroundColor(tokens::PANEL_BG),
+        ChildOf(secondary.root),
+    ));
+}
+
+fn entity_label(world: &World, entity: Entity) -> String {
+    match world.get::<Name>(entity) {
+        Some(name) => name.to_string(),
+        None => format!("Entity {entity}"),
+    }
+}
+
+pub(super) fn close_compare_windows(
+    mut commands: Commands,
+    mut requests: MessageReader<WindowCloseRequested>,
+    windows: Query<&CompareWindow>,
+) {
+    for request in requests.read() {
+        let Ok(window) = windows.get(request.window) else {
+            continue;
+        };
+        let secondary = window.secondary;
+        commands.queue(move |world: &mut World| secondary.despawn(world));
+    }
+}
+
+/// Rebuild each compare list whose rows changed, e.g. after an edit, a copy or an undo.
+/// Windows whose entities were despawned close.
+pub(super) fn refresh_compare_windows(world: &mut World) {
+    let mut windows = world.query::<&CompareWindow>();
+    let windows: Vec<(SecondaryWindow, Entity, Entity)> = windows
+        .iter(world)
+        .map(|window| (window.secondary, window.left, window.right))
+        .collect();
+
+    for (secondary, left, right) in windows {
+        if world.get_entity(left).is_err() || world.get_entity(right).is_err() {
+            secondary.despawn(world);
+            continue;
+        }
+        let Some(list) = world.get::<Children>(secondary.root).and_then(|children| {
+            children
+                .iter()
+                .find(|&child| world.get::<CompareList>(child).is_some())
+        }) else {
+            continue;
+        };
+        let rows = compare_rows(world, left, right);
+        if world
+            .get::<CompareList>(list)
+            .is_some_and(|shown| shown.0 == rows)
+        {
+            continue;
+        }
+        world.entity_mut(list).despawn_related::<Children>();
+        let icon_font = world.resource::<IconFont>().0.clone();
+        for row in &rows {
+            spawn_compare_row(world, list, row, left, right, &icon_font);
+        }
+        world.entity_mut(list).insert(CompareList(rows));
+    }
+}
+
+/// Reflected components of `entity` the inspector would show, by type path.
+fn inspectable_components(
+    world: &World,
+    registry: &bevy::reflect::TypeRegistry,
+    entity: Entity,
+) -> BTreeMap<String, (TypeId, String)> {
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        return BTreeMap::new();
+    };
+    let components = world.components();
+    entity_ref
+        .archetype()
+        .iter_components()
+        .filter_map(|component_id| {
+            let type_id = components.get_info(component_id)?.type_id()?;
+            let registration = registry.get(type_id)?;
+            registration.data::<ReflectComponent>()?;
+            let table = registration.type_info().type_path_table();
+            let path = table.path();
+            if path.starts_with("jackdaw") && !path.starts_with("jackdaw_jsn") {
+                return None;
+            }
+            Some((path.to_string(), (type_id, table.short_path().to_string())))
+        })
+        .collect()
+}
+
+fn compare_rows(world: &World, left: Entity, right: Entity) -> Vec<CompareRow> {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let mut components = inspectable_components(world, &registry, left);
+    let right_components = inspectable_components(world, &registry, right);
+    let on_left: Vec<String> = components.keys().cloned().collect();
+    components.extend(right_components.clone());
+
+    let mut rows = Vec::new();
+    for (path, (type_id, name)) in components {
+        let has_left = on_left.contains(&path);
+        let has_right = right_components.contains_key(&path);
+        rows.push(CompareRow::Component {
+            name,
+            left: has_left,
+            right: has_right,
+        });
+        if !(has_left && has_right) {
+            continue;
+        }
+        let Some(reflect_component) = registry
+            .get(type_id)
+            .and_then(|registration| registration.data::<ReflectComponent>())
+        else {
+            continue;
+        };
+        let (Some(left_value), Some(right_value)) = (
+            reflect_component.reflect(world.entity(left)),
+            reflect_component.reflect(world.entity(right)),
+        ) else {
+            continue;
+        };
+        push_field_rows(
+            &mut rows,
+            type_id,
+            "value".to_string(),
+            left_value.as_partial_reflect(),
+            Some(right_value.as_partial_reflect()),
+            String::new(),
+            0,
+        );
+    }
+    rows
+}
+
+/// One row per leaf field of `left`, paired with the same field of `right`. Structs and
+/// tuple structs are expanded; everything else is compared as a whole.
+fn push_field_rows(
+    rows: &mut Vec<CompareRow>,
+    component_type_id: TypeId,
+    label: String,
+    left: &dyn PartialReflect,
+    right: Option<&dyn PartialReflect>,
+    base_path: String,
+    depth: usize,
+) {
+    let child_path = |name: &str| {
+        if base_path.is_empty() && name.parse::<usize>().is_ok() {
+            format!(".{name}")
+        } else if base_path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{base_path}.{name}")
+        }
+    };
+    type Child<'a> = (
+        String,
+        &'a dyn PartialReflect,
+        Option<&'a dyn PartialReflect>,
+    );
+    let children: Vec<Child> = match left.reflect_ref() {
+        ReflectRef::Struct(s) if depth < MAX_REFLECT_DEPTH => {
+            let right = right.and_then(|right| match right.reflect_ref() {
+                ReflectRef::Struct(right) => Some(right),
+                _ => None,
+            });
+            (0..s.field_len())
+                .filter_map(|i| {
+                    let name = s.name_at(i)?;
+                    let right_field = right.and_then(|right| right.field(name));
+                    Some((name.to_string(), s.field_at(i)?, right_field))
+                })
+                .collect()
+        }
+        ReflectRef::TupleStruct(ts) if depth < MAX_REFLECT_DEPTH => {
+            let right = right.and_then(|right| match right.reflect_ref() {
+                ReflectRef::TupleStruct(right) => Some(right),
+                _ => None,
+            });
+            (0..ts.field_len())
+                .filter_map(|i| {
+                    let right_field = right.and_then(|right| right.field(i));
+                    Some((i.to_string(), ts.field(i)?, right_field))
+                })
+                .collect()
+        }
+        _ => {
+            let differs = match right {
+                Some(right) => left
+                    .reflect_partial_eq(right)
+                    .map_or_else(|| format!("{left:?}") != format!("{right:?}"), |eq| !eq),
+                None => true,
+            };
+            rows.push(CompareRow::Field {
+                component_type_id,
+                field_path: base_path,
+                label,
+                depth,
+                left: Some(format!("{left:?}")),
+                right: right.map(|right| format!("{right:?}")),
+                differs,
+            });
+            return;
+        }
+    };
+
+    for (name, value, right_field) in children {
+        let path = child_path(&name);
+        // Only leaf rows are copyable, so expanded structs just get a label row
+        if matches!(
+            value.reflect_ref(),
+            ReflectRef::Struct(_) | ReflectRef::TupleStruct(_)
+        ) && depth + 1 < MAX_REFLECT_DEPTH
+        {
+            rows.push(CompareRow::Field {
+                component_type_id,
+                field_path: path.clone(),
+                label: name.clone(),
+                depth: depth + 1,
+                left: None,
+                right: None,
+                differs: false,
+            });
+        }
+        push_field_rows(
+            rows,
+            component_type_id,
+            name,
+            value,
+            right_field,
+            path,
+            depth + 1,
+        );
+    }
+}
+
+fn spawn_compare_row(
+    world: &mut World,
+    list: Entity,
+    row: &CompareRow,
+    left: Entity,
+    right: Entity,
+    icon_font: &Handle<Font>,
+) {
+    let text_font = TextFont {
+        font_size: tokens::FONT_SM,
+        ..Default::default()
+    };
+    match row {
+        CompareRow::Component {
+            name,
+            left: has_left,
+            right: has_right,
+        } => {
+            let header = world
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Row,
+                        padding: UiRect::axes(px(tokens::SPACING_SM), px(tokens::SPACING_XS)),
+                        margin: UiRect::top(px(tokens::SPACING_SM)),
+                        ..Default::default()
+                    },
+                    BackgroundColor(tokens::PANEL_HEADER_BG),
+                    ChildOf(list),
+                ))
+                .id();
+            let missing = |present: bool| if present { "" } else { "(missing)" };
+            let color = if *has_left && *has_right {
+                tokens::TEXT_DISPLAY_COLOR.into()
+            } else {
+                DIFF_COLOR
+            };
+            for (text, width) in [
+                (name.as_str(), 34.0),
+                (missing(*has_left), 30.0),
+                ("", 6.0),
+                (missing(*has_right), 30.0),
+            ] {
+                world.spawn((
+                    Text::new(text),
+                    text_font.clone(),
+                    TextColor(color),
+                    Node {
+                        width: percent(width),
+                        ..Default::default()
+                    },
+                    ChildOf(header),
+                ));
+            }
+        }
+        CompareRow::Field {
+            component_type_id,
+            field_path,
+            label,
+            depth,
+            left: left_value,
+            right: right_value,
+            differs,
+        } => {
+            let field_row = world
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::horizontal(px(tokens::SPACING_SM)),
+                        ..Default::default()
+                    },
+                    BackgroundColor(if *differs { DIFF_ROW_BG } else { Color::NONE }),
+                    ChildOf(list),
+                ))
+                .id();
+            let value_color = if *differs {
+                DIFF_COLOR
+            } else {
+                tokens::TEXT_PRIMARY
+            };
+            world.spawn((
+                Text::new(label.clone()),
+                text_font.clone(),
+                TextColor(tokens::TEXT_SECONDARY),
+                Node {
+                    width: percent(34),
+                    padding: UiRect::left(px(*depth as f32 * tokens::SPACING_MD)),
+                    ..Default::default()
+                },
+                ChildOf(field_row),
+            ));
+            world.spawn((
+                Text::new(left_value.clone().unwrap_or_default()),
+                text_font.clone(),
+                TextColor(value_color),
+                Node {
+                    width: percent(30),
+                    overflow: Overflow::clip(),
+                    ..Default::default()
+                },
+                ChildOf(field_row),
+            ));
+            let buttons = world
+                .spawn((
+                    Node {
+                        width: percent(6),
+                        justify_content: JustifyContent::Center,
+                        column_gap: px(tokens::SPACING_XS),
+                        ..Default::default()
+                    },
+                    ChildOf(field_row),
+                ))
+                .id();
+            if *differs && right_value.is_some() {
+                for (icon, from, to) in [
+                    (Icon::ArrowRight, left, right),
+                    (Icon::ArrowLeft, right, left),
+                ] {
+                    let component_type_id = *component_type_id;
+                    let field_path = field_path.clone();
+                    world.spawn((
+                        Text::new(String::from(icon.unicode())),
+                        TextFont {
+                            font: icon_font.clone(),
+                            font_size: tokens::FONT_SM,
+                            ..Default::default()
+                        },
+                        TextColor(tokens::TEXT_SECONDARY),
+                        ChildOf(buttons),
+                        observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+                            let field_path = field_path.clone();
+                            commands.queue(move |world: &mut World| {
+                                copy_field(world, from, to, component_type_id, &field_path);
+                            });
+                        }),
+                    ));
+                }
+            }
+            world.spawn((
+                Text::new(right_value.clone().unwrap_or_default()),
+                text_font,
+                TextColor(value_color),
+                Node {
+                    width: percent(30),
+                    overflow: Overflow::clip(),
+                    ..Default::default()
+                },
+                ChildOf(field_row),
+            ));
+        }
+    }
+}
+
+/// Set the field at `field_path` on `to` to its value on `from`, undoably.
+fn copy_field(
+    world: &mut World,
+    from: Entity,
+    to: Entity,
+    component_type_id: TypeId,
+    field_path: &str,
+) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let Some(reflect_component) = registry
+        .get(component_type_id)
+        .and_then(|registration| registration.data::<ReflectComponent>())
+    else {
+        return;
+    };
+    let field_value = |entity: Entity| -> Option<Box<dyn PartialReflect>> {
+        let reflected = reflect_component.reflect(world.get_entity(entity).ok()?)?;
+        if field_path.is_empty() {
+            Some(reflected.to_dynamic())
+        } else {
+            Some(reflected.reflect_path(field_path).ok()?.to_dynamic())
+        }
+    };
+    let (Some(new_value), Some(old_value)) = (field_value(from), field_value(to)) else {
+        return;
+    };
+    drop(registry);
+
+    let cmd = SetComponentField {
+        entity: to,
+        component_type_id,
+        field_path: field_path.to_string(),
+        old_value,
+        new_value,
+    };
+    cmd.execute(world);
+    world
+        .resource_mut::<CommandHistory>()
+        .push_executed(Box::new(cmd));
+}
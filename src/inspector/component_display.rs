@@ -1,4 +1,5 @@
 use crate::EditorEntity;
+use crate::commands::{CommandHistory, RemoveComponent};
 use crate::custom_properties::CustomProperties;
 use crate::selection::{Selected, Selection};
 use std::any::TypeId;
@@ -21,15 +22,18 @@ use jackdaw_widgets::collapsible::{
 };
 
 use jackdaw_feathers::text_edit::{self, TextEditProps, TextEditValue};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use bevy_monitors::prelude::{Addition, Monitor, NotifyAdded};
 
 use super::{
     AddComponentButton, CollapseAllButton, ComponentDisplay, ComponentDisplayBody, ComponentName,
-    ComponentPicker, Inspector, InspectorDirty, InspectorGroupSection, InspectorSearch,
-    InspectorTarget, ReflectDisplayable, ReflectEditorMeta, brush_display, custom_props_display,
-    extract_module_group, material_display, reflect_fields,
+    ComponentPicker, Inspector, InspectorDirty, InspectorGroupSection, InspectorPinned,
+    InspectorSearch, InspectorTarget, ReflectDisplayable, ReflectEditorMeta, brush_display,
+    custom_props_display, extract_module_group, linked_display, lod_display, material_display,
+    reflect_fields,
+    section_layout::{self, ComponentTypeKey, InspectorLayout},
+    sub_scene_display, sun_display, transform_display,
 };
 
 pub(crate) fn add_component_displays(
@@ -39,10 +43,11 @@ pub(crate) fn add_component_displays(
     type_registry: Res<AppTypeRegistry>,
     selection: Res<Selection>,
     entity_query: Query<(&Archetype, EntityRef), (With<Selected>, Without<EditorEntity>)>,
-    inspector: Single<Entity, With<Inspector>>,
+    inspectors: Query<Entity, (With<Inspector>, Without<InspectorPinned>)>,
     names: Query<&Name>,
     icon_font: Res<IconFont>,
     editor_font: Res<EditorFont>,
+    layout: Res<InspectorLayout>,
 ) {
     let Some(primary) = selection.primary() else {
         return;
@@ -54,26 +59,30 @@ pub(crate) fn add_component_displays(
     let source_entity = entity_ref.entity();
     let sel_count = selection.entities.len();
 
-    build_inspector_displays(
-        &mut commands,
-        components,
-        &type_registry,
-        source_entity,
-        archetype,
-        entity_ref,
-        *inspector,
-        sel_count,
-        &names,
-        &icon_font,
-        &editor_font,
-    );
+    // Pinned inspectors keep showing their own entity
+    for inspector in &inspectors {
+        build_inspector_displays(
+            &mut commands,
+            components,
+            &type_registry,
+            source_entity,
+            archetype,
+            entity_ref,
+            inspector,
+            sel_count,
+            &names,
+            &icon_font,
+            &editor_font,
+            &layout,
+        );
 
-    // Set up monitoring: watch the selected entity for InspectorDirty
-    commands.entity(*inspector).insert((
-        InspectorTarget(primary),
-        Monitor(primary),
-        NotifyAdded::<InspectorDirty>::default(),
-    ));
+        // Set up monitoring: watch the selected entity for InspectorDirty
+        commands.entity(inspector).insert((
+            InspectorTarget(primary),
+            Monitor(primary),
+            NotifyAdded::<InspectorDirty>::default(),
+        ));
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -89,6 +98,7 @@ fn build_inspector_displays(
     names: &Query<&Name>,
     icon_font: &IconFont,
     editor_font: &EditorFont,
+    layout: &InspectorLayout,
 ) {
     // Show multi-selection header when multiple entities are selected
     if selection_count > 1 {
@@ -167,14 +177,26 @@ fn build_inspector_displays(
     ));
 
     commands.entity(collapse_btn).observe(
-        |_: On<Pointer<Click>>,
-         mut sections: Query<(&mut CollapsibleSection, &Children), With<ComponentDisplay>>,
-         mut nodes: Query<&mut Node, With<CollapsibleBody>>| {
+        move |_: On<Pointer<Click>>,
+              mut sections: Query<
+            (Entity, &mut CollapsibleSection, &Children),
+            With<ComponentDisplay>,
+        >,
+              mut nodes: Query<&mut Node, With<CollapsibleBody>>,
+              parents: Query<&ChildOf>,
+              inspectors: Query<(), With<Inspector>>| {
+            let ours =
+                |entity| owning_inspector(entity, &parents, &inspectors) == Some(inspector_entity);
             // If any section is expanded, collapse all; otherwise expand all
-            let any_expanded = sections.iter().any(|(s, _)| !s.collapsed);
+            let any_expanded = sections
+                .iter()
+                .any(|(entity, s, _)| ours(entity) && !s.collapsed);
             let target_collapsed = any_expanded;
 
-            for (mut section, children) in &mut sections {
+            for (entity, mut section, children) in &mut sections {
+                if !ours(entity) {
+                    continue;
+                }
                 section.collapsed = target_collapsed;
                 for child in children.iter() {
                     if let Ok(mut node) = nodes.get_mut(child) {
@@ -210,9 +232,9 @@ fn build_inspector_displays(
     // Check for prefab baseline (override tracking)
     let baseline = entity_ref.get::<jackdaw_jsn::JsnPrefabBaseline>().cloned();
 
-    // (short_name, module_group, component_id)
+    // (short_name, module_group, component_id, type_path)
     let mut custom_groups = std::collections::HashSet::new();
-    let mut comp_list: Vec<(String, String, ComponentId)> = archetype
+    let mut comp_list: Vec<(String, String, ComponentId, String)> = archetype
         .iter_components()
         .filter_map(|component_id| {
             let info = components.get_info(component_id)?;
@@ -237,7 +259,7 @@ fn build_inspector_displays(
                 } else {
                     extract_module_group(table.module_path())
                 };
-                return Some((short, module_group, component_id));
+                return Some((short, module_group, component_id, full_path.to_string()));
             }
 
             // Fallback: use Components name
@@ -249,17 +271,20 @@ fn build_inspector_displays(
                 name.shortname().to_string(),
                 "Other".to_string(),
                 component_id,
+                name.to_string(),
             ))
         })
         .collect();
 
-    // Sort: custom-category groups first, then alphabetical within each tier
+    // Sort: custom-category groups first, then alphabetical within each tier, except for
+    // sections dragged into an order of their own
     comp_list.sort_by(|a, b| {
         let a_custom = custom_groups.contains(&a.1);
         let b_custom = custom_groups.contains(&b.1);
         b_custom
             .cmp(&a_custom)
             .then_with(|| a.1.cmp(&b.1))
+            .then_with(|| layout.position(&a.3).cmp(&layout.position(&b.3)))
             .then_with(|| a.0.to_lowercase().cmp(&b.0.to_lowercase()))
     });
 
@@ -267,7 +292,7 @@ fn build_inspector_displays(
     let mut current_group = String::new();
     let mut group_container = inspector_entity;
 
-    for (name, module_group, component_id) in &comp_list {
+    for (name, module_group, component_id, type_key) in &comp_list {
         // Start a new group section if the module changed
         if *module_group != current_group {
             current_group = module_group.clone();
@@ -394,7 +419,18 @@ fn build_inspector_displays(
         );
         commands
             .entity(display_entity)
-            .insert(ChildOf(group_container));
+            .insert((ChildOf(group_container), ComponentTypeKey(type_key.clone())));
+        if layout.0.collapsed.contains(type_key) {
+            commands
+                .entity(display_entity)
+                .insert(CollapsibleSection { collapsed: true });
+            commands
+                .entity(body_entity)
+                .entry::<Node>()
+                .and_modify(|mut node| {
+                    node.display = Display::None;
+                });
+        }
 
         // Try Displayable first, then reflection, then fallback
         let type_id = components
@@ -454,6 +490,84 @@ fn build_inspector_displays(
                 continue;
             }
 
+            // Priority 3d: SubScene — source path with reload/open actions
+            if type_id == TypeId::of::<jackdaw_jsn::SubScene>() {
+                if let Some(sub_scene) = reflected.downcast_ref::<jackdaw_jsn::SubScene>() {
+                    sub_scene_display::spawn_sub_scene_display(
+                        commands,
+                        body_entity,
+                        source_entity,
+                        sub_scene,
+                    );
+                }
+                continue;
+            }
+
+            // Priority 3d2: LinkedDuplicate — instance count with select/unlink actions
+            if type_id == TypeId::of::<jackdaw_jsn::LinkedDuplicate>() {
+                linked_display::spawn_linked_display(commands, body_entity, source_entity);
+                continue;
+            }
+
+            // Priority 3e: LodGroup — level models and switch distances
+            if type_id == TypeId::of::<jackdaw_jsn::LodGroup>() {
+                if let Some(group) = reflected.downcast_ref::<jackdaw_jsn::LodGroup>() {
+                    lod_display::spawn_lod_display(
+                        commands,
+                        body_entity,
+                        source_entity,
+                        group,
+                        &icon_font.0,
+                    );
+                }
+                continue;
+            }
+
+            // Priority 3f: DirectionalLight — reflected fields plus sun position
+            if type_id == TypeId::of::<DirectionalLight>() {
+                reflect_fields::spawn_reflected_fields(
+                    commands,
+                    body_entity,
+                    reflected,
+                    0,
+                    String::new(),
+                    source_entity,
+                    type_id,
+                    names,
+                    type_registry,
+                    &editor_font.0,
+                    &icon_font.0,
+                );
+                if let Some(transform) = entity_ref.get::<Transform>() {
+                    sun_display::spawn_sun_display(commands, body_entity, source_entity, transform);
+                }
+                continue;
+            }
+
+            // Priority 3g: Transform — parent-space fields plus a world-space alternative
+            if type_id == TypeId::of::<Transform>() {
+                let parent_section = transform_display::spawn_transform_display(
+                    commands,
+                    body_entity,
+                    source_entity,
+                    entity_ref.get::<GlobalTransform>(),
+                );
+                reflect_fields::spawn_reflected_fields(
+                    commands,
+                    parent_section,
+                    reflected,
+                    0,
+                    String::new(),
+                    source_entity,
+                    type_id,
+                    names,
+                    type_registry,
+                    &editor_font.0,
+                    &icon_font.0,
+                );
+                continue;
+            }
+
             // Priority 3: Generic reflection display
             reflect_fields::spawn_reflected_fields(
                 commands,
@@ -495,7 +609,7 @@ fn build_inspector_displays(
 pub(crate) fn remove_component_displays(
     _: On<Remove, Selected>,
     mut commands: Commands,
-    inspector: Single<(Entity, Option<&Children>), With<Inspector>>,
+    inspectors: Query<(Entity, Option<&Children>), (With<Inspector>, Without<InspectorPinned>)>,
     displays: Query<
         Entity,
         Or<(
@@ -505,37 +619,46 @@ pub(crate) fn remove_component_displays(
         )>,
     >,
 ) {
-    let (entity, children) = inspector.into_inner();
-
-    // Clean up monitoring components
-    commands
-        .entity(entity)
-        .remove::<(InspectorTarget, Monitor, NotifyAdded<InspectorDirty>)>();
+    for (entity, children) in &inspectors {
+        // Clean up monitoring components
+        commands
+            .entity(entity)
+            .remove::<(InspectorTarget, Monitor, NotifyAdded<InspectorDirty>)>();
 
-    let Some(children) = children else {
-        return;
-    };
+        let Some(children) = children else {
+            continue;
+        };
 
-    for child in displays.iter_many(children.collection()) {
-        if let Ok(mut ec) = commands.get_entity(child) {
-            ec.despawn();
+        for child in displays.iter_many(children.collection()) {
+            if let Ok(mut ec) = commands.get_entity(child) {
+                ec.despawn();
+            }
         }
     }
 }
 
-/// Handles `Addition<InspectorDirty>` on the Inspector entity: despawn existing
+/// Handles `Addition<InspectorDirty>` on an Inspector entity: despawn existing
 /// displays and rebuild from the monitored source entity.
 pub(crate) fn on_inspector_dirty(
-    _: On<Addition<InspectorDirty>>,
+    trigger: On<Addition<InspectorDirty>>,
     mut commands: Commands,
     components: &Components,
     type_registry: Res<AppTypeRegistry>,
-    inspector: Single<(Entity, &InspectorTarget, Option<&Children>), With<Inspector>>,
+    inspectors: Query<
+        (
+            Entity,
+            &InspectorTarget,
+            Option<&Children>,
+            Has<InspectorPinned>,
+        ),
+        With<Inspector>,
+    >,
     entity_query: Query<(&Archetype, EntityRef), Without<EditorEntity>>,
     selection: Res<Selection>,
     names: Query<&Name>,
     icon_font: Res<IconFont>,
     editor_font: Res<EditorFont>,
+    layout: Res<InspectorLayout>,
     displays: Query<
         Entity,
         Or<(
@@ -545,7 +668,10 @@ pub(crate) fn on_inspector_dirty(
         )>,
     >,
 ) {
-    let (inspector_entity, target, children) = inspector.into_inner();
+    let Ok((inspector_entity, target, children, pinned)) = inspectors.get(trigger.event_target())
+    else {
+        return;
+    };
     let source_entity = target.0;
 
     // Despawn existing display children
@@ -566,7 +692,7 @@ pub(crate) fn on_inspector_dirty(
     let Ok((archetype, entity_ref)) = entity_query.get(source_entity) else {
         return;
     };
-    let sel_count = selection.entities.len();
+    let sel_count = if pinned { 1 } else { selection.entities.len() };
 
     build_inspector_displays(
         &mut commands,
@@ -580,6 +706,7 @@ pub(crate) fn on_inspector_dirty(
         &names,
         &icon_font,
         &editor_font,
+        &layout,
     );
 }
 
@@ -726,10 +853,19 @@ fn spawn_component_display(
         TextColor(tokens::TEXT_SECONDARY),
         ChildOf(header),
         bevy::ui_widgets::observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
-            commands.entity(entity).remove_by_id(component);
+            commands.queue(move |world: &mut World| {
+                remove_component(world, entity, component);
+            });
         }),
     ));
 
+    // Drag a header onto another to reorder sections
+    commands
+        .entity(header)
+        .observe(section_layout::on_header_drop)
+        .observe(section_layout::on_header_drag_enter)
+        .observe(section_layout::on_header_drag_leave);
+
     // Hover effect on header
     commands.entity(header).observe(
         |hover: On<Pointer<Over>>, mut bg: Query<&mut BackgroundColor, With<CollapsibleHeader>>| {
@@ -752,52 +888,142 @@ fn spawn_component_display(
     (section_entity, body_entity)
 }
 
-/// Filter inspector components based on the search input.
+/// The inspector `entity` is part of.
+fn owning_inspector(
+    mut entity: Entity,
+    parents: &Query<&ChildOf>,
+    inspectors: &Query<(), With<Inspector>>,
+) -> Option<Entity> {
+    while !inspectors.contains(entity) {
+        entity = parents.get(entity).ok()?.parent();
+    }
+    Some(entity)
+}
+
+/// Filter inspector components based on the search input of their inspector.
 pub(crate) fn filter_inspector_components(
-    search_query: Query<&TextEditValue, (With<InspectorSearch>, Changed<TextEditValue>)>,
+    search_query: Query<(Entity, &TextEditValue), (With<InspectorSearch>, Changed<TextEditValue>)>,
+    inspectors: Query<(), With<Inspector>>,
+    parents: Query<&ChildOf>,
     components: Query<(Entity, &ComponentName), With<ComponentDisplay>>,
     groups: Query<(Entity, &Children), With<InspectorGroupSection>>,
     mut node_query: Query<&mut Node>,
 ) {
-    let Ok(search) = search_query.single() else {
-        return;
-    };
-    let filter = search.0.trim().to_lowercase();
+    for (search_entity, search) in &search_query {
+        let inspector = owning_inspector(search_entity, &parents, &inspectors);
+        let filter = search.0.trim().to_lowercase();
 
-    // Track which component entities are visible
-    let mut visible_components: HashSet<Entity> = HashSet::new();
+        // Track which component entities are visible
+        let mut visible_components: HashSet<Entity> = HashSet::new();
 
-    // Filter individual component displays by name
-    for (entity, comp_name) in &components {
-        let matches = filter.is_empty() || comp_name.0.to_lowercase().contains(&filter);
+        // Filter individual component displays by name
+        for (entity, comp_name) in &components {
+            if owning_inspector(entity, &parents, &inspectors) != inspector {
+                continue;
+            }
+            let matches = filter.is_empty() || comp_name.0.to_lowercase().contains(&filter);
 
-        if let Ok(mut node) = node_query.get_mut(entity) {
-            node.display = if matches {
-                Display::Flex
-            } else {
-                Display::None
-            };
+            if let Ok(mut node) = node_query.get_mut(entity) {
+                node.display = if matches {
+                    Display::Flex
+                } else {
+                    Display::None
+                };
+            }
+
+            if matches {
+                visible_components.insert(entity);
+            }
         }
 
-        if matches {
-            visible_components.insert(entity);
+        // Hide group sections where all children are hidden
+        for (group_entity, children) in &groups {
+            if owning_inspector(group_entity, &parents, &inspectors) != inspector {
+                continue;
+            }
+            let has_visible_child = children
+                .iter()
+                .any(|child| visible_components.contains(&child));
+
+            if let Ok(mut node) = node_query.get_mut(group_entity) {
+                node.display = if filter.is_empty() || has_visible_child {
+                    Display::Flex
+                } else {
+                    Display::None
+                };
+            }
         }
     }
+}
 
-    // Hide group sections where all children are hidden
-    for (group_entity, children) in &groups {
-        let has_visible_child = children
-            .iter()
-            .any(|child| visible_components.contains(&child));
-
-        if let Ok(mut node) = node_query.get_mut(group_entity) {
-            node.display = if filter.is_empty() || has_visible_child {
-                Display::Flex
-            } else {
-                Display::None
-            };
+/// Rebuild an inspector when its target gains or loses a reflected component outside the
+/// inspector's own buttons, e.g. by undoing an add or remove.
+pub(crate) fn rebuild_on_component_set_change(
+    mut commands: Commands,
+    mut seen: Local<HashMap<Entity, usize>>,
+    components: &Components,
+    type_registry: Res<AppTypeRegistry>,
+    inspectors: Query<&InspectorTarget, With<Inspector>>,
+    entity_query: Query<&Archetype, Without<EditorEntity>>,
+) {
+    let registry = type_registry.read();
+    let mut counts = HashMap::new();
+    for target in &inspectors {
+        let Ok(archetype) = entity_query.get(target.0) else {
+            continue;
+        };
+        let count = archetype
+            .iter_components()
+            .filter(|&component_id| {
+                components
+                    .get_info(component_id)
+                    .and_then(|info| info.type_id())
+                    .and_then(|type_id| registry.get(type_id))
+                    .is_some_and(|registration| registration.data::<ReflectComponent>().is_some())
+            })
+            .count();
+        if seen
+            .get(&target.0)
+            .is_some_and(|&previous| previous != count)
+        {
+            commands.entity(target.0).insert(InspectorDirty);
         }
+        counts.insert(target.0, count);
     }
+    *seen = counts;
+}
+
+/// Remove a component through the undo history, snapshotting its value so undo restores it.
+fn remove_component(world: &mut World, entity: Entity, component_id: ComponentId) {
+    let type_id = world
+        .components()
+        .get_info(component_id)
+        .and_then(|info| info.type_id());
+    let snapshot = type_id.and_then(|type_id| {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let reflect_component = registry.get(type_id)?.data::<ReflectComponent>()?;
+        let reflected = reflect_component.reflect(world.get_entity(entity).ok()?)?;
+        Some(reflected.to_dynamic())
+    });
+
+    let (Some(type_id), Some(snapshot)) = (type_id, snapshot) else {
+        // Not reflectable, so there is no value to restore on undo
+        if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+            entity_mut.remove_by_id(component_id);
+        }
+        return;
+    };
+    world.resource_scope(|world, mut history: Mut<CommandHistory>| {
+        history.execute(
+            Box::new(RemoveComponent {
+                entity,
+                type_id,
+                component_id,
+                snapshot,
+            }),
+            world,
+        );
+    });
 }
 
 /// Revert a single component on a prefab instance back to its baseline value.
@@ -1,6 +1,4 @@
 use crate::EditorEntity;
-use crate::commands::EditorCommand;
-use crate::selection::{Selected, Selection};
 use std::any::TypeId;
 use std::collections::{BTreeMap, HashSet};
 
@@ -21,7 +19,7 @@ use jackdaw_feathers::tokens;
 
 use super::{
     AddComponentButton, ComponentPicker, ComponentPickerEntry, ComponentPickerSearch,
-    ComponentPickerSectionHeader, Inspector, ReflectEditorMeta,
+    ComponentPickerSectionHeader, Inspector, InspectorTarget, ReflectEditorMeta,
 };
 
 /// Grouping key for sorting: custom categories first, then Game, then Bevy.
@@ -45,26 +43,28 @@ struct ComponentInfo {
 pub(crate) fn on_add_component_button_click(
     event: On<jackdaw_feathers::button::ButtonClickEvent>,
     add_buttons: Query<&ChildOf, With<AddComponentButton>>,
-    existing_pickers: Query<Entity, With<ComponentPicker>>,
+    existing_pickers: Query<(Entity, &ChildOf), With<ComponentPicker>>,
     mut commands: Commands,
-    selection: Res<Selection>,
     type_registry: Res<AppTypeRegistry>,
     components: &Components,
-    entity_query: Query<&Archetype, (With<Selected>, Without<EditorEntity>)>,
-    inspector: Single<Entity, With<Inspector>>,
+    entity_query: Query<&Archetype, Without<EditorEntity>>,
+    inspectors: Query<&InspectorTarget, With<Inspector>>,
 ) {
     // Check if this click is on an AddComponentButton
-    if add_buttons.get(event.entity).is_err() {
+    let Ok(&ChildOf(inspector)) = add_buttons.get(event.entity) else {
         return;
-    }
+    };
 
     // Toggle: if picker already open, close it
-    if let Some(picker) = existing_pickers.iter().next() {
+    if let Some((picker, _)) = existing_pickers
+        .iter()
+        .find(|(_, child_of)| child_of.parent() == inspector)
+    {
         commands.entity(picker).despawn();
         return;
     }
 
-    let Some(primary) = selection.primary() else {
+    let Ok(&InspectorTarget(primary)) = inspectors.get(inspector) else {
         return;
     };
     let Ok(archetype) = entity_query.get(primary) else {
@@ -160,7 +160,7 @@ pub(crate) fn on_add_component_button_click(
             },
             BackgroundColor(tokens::PANEL_BG),
             BorderColor::all(tokens::BORDER_SUBTLE),
-            ChildOf(*inspector),
+            ChildOf(inspector),
         ))
         .id();
 
@@ -274,12 +274,11 @@ pub(crate) fn on_add_component_button_click(
                                 type_id,
                                 component_id,
                             };
-                            let cmd = Box::new(cmd);
-                            cmd.execute(world);
-                            let mut history =
-                                world.resource_mut::<crate::commands::CommandHistory>();
-                            history.undo_stack.push(cmd);
-                            history.redo_stack.clear();
+                            world.resource_scope(
+                                |world, mut history: Mut<crate::commands::CommandHistory>| {
+                                    history.execute(Box::new(cmd), world);
+                                },
+                            );
 
                             // Signal the inspector to rebuild
                             world.entity_mut(source_entity).insert(InspectorDirty);
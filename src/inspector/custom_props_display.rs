@@ -1,9 +1,17 @@
 use crate::commands::{CommandHistory, EditorCommand};
-use crate::custom_properties::{CustomProperties, PropertyValue, SetCustomProperties};
+use crate::custom_properties::{
+    CustomProperties, PendingEntityPick, PropertyValue, SetCustomProperties,
+};
+use crate::entity_classes::{EntityClasses, add_class_property, apply_entity_class};
+use crate::selection::{Selection, select_entities};
+use crate::stable_id::{StableIds, resolve_stable_id, stable_id_of};
 
+use bevy::asset::uuid::Uuid;
 use bevy::prelude::*;
 use bevy::ui_widgets::observe;
-use jackdaw_feathers::combobox::{ComboBoxSelectedIndex, combobox_with_selected};
+use jackdaw_feathers::combobox::{
+    ComboBoxChangeEvent, ComboBoxSelectedIndex, combobox_with_selected,
+};
 use jackdaw_feathers::{
     checkbox::{CheckboxCommitEvent, CheckboxProps, checkbox},
     color_picker::{ColorPickerCommitEvent, ColorPickerProps, color_picker},
@@ -42,6 +50,10 @@ pub(super) fn spawn_custom_properties_display(
 
         // Property name label
         commands.spawn((
+            CustomPropertyLabel {
+                source_entity,
+                property_name: prop_name.clone(),
+            },
             Text::new(format!("{}:", prop_name)),
             TextFont {
                 font: editor_font.clone(),
@@ -57,175 +69,19 @@ pub(super) fn spawn_custom_properties_display(
             ChildOf(row),
         ));
 
-        let name = prop_name.clone();
-        match prop_value {
-            PropertyValue::Bool(val) => {
-                let checked = *val;
-                commands.spawn((
-                    checkbox(
-                        CheckboxProps::new("").checked(checked),
-                        editor_font,
-                        icon_font,
-                    ),
-                    CustomPropertyBinding {
-                        source_entity,
-                        property_name: name,
-                    },
-                    ChildOf(row),
-                ));
-            }
-            PropertyValue::Int(val) => {
-                commands.spawn((
-                    text_edit::text_edit(
-                        TextEditProps::default()
-                            .numeric_f32()
-                            .grow()
-                            .with_default_value((*val).to_string()),
-                    ),
-                    CustomPropertyBinding {
-                        source_entity,
-                        property_name: name,
-                    },
-                    ChildOf(row),
-                ));
-            }
-            PropertyValue::Float(val) => {
-                commands.spawn((
-                    text_edit::text_edit(
-                        TextEditProps::default()
-                            .numeric_f32()
-                            .grow()
-                            .with_default_value(val.to_string()),
-                    ),
-                    CustomPropertyBinding {
-                        source_entity,
-                        property_name: name,
-                    },
-                    ChildOf(row),
-                ));
-            }
-            PropertyValue::String(val) => {
-                commands.spawn((
-                    text_edit::text_edit(
-                        TextEditProps::default()
-                            .grow()
-                            .with_default_value(val.clone())
-                            .allow_empty(),
-                    ),
-                    CustomPropertyBinding {
-                        source_entity,
-                        property_name: name,
-                    },
-                    ChildOf(row),
-                ));
-            }
-            PropertyValue::Vec2(val) => {
-                let v = *val;
-                let n_x = name.clone();
-                let n_y = name.clone();
-                spawn_custom_axis(
-                    commands,
-                    row,
-                    "X",
-                    v.x as f64,
-                    AXIS_X_COLOR,
-                    source_entity,
-                    n_x,
-                    |new_f, old| {
-                        if let PropertyValue::Vec2(v) = old {
-                            v.x = new_f as f32;
-                        }
-                    },
-                );
-                spawn_custom_axis(
-                    commands,
-                    row,
-                    "Y",
-                    v.y as f64,
-                    AXIS_Y_COLOR,
-                    source_entity,
-                    n_y,
-                    |new_f, old| {
-                        if let PropertyValue::Vec2(v) = old {
-                            v.y = new_f as f32;
-                        }
-                    },
-                );
-            }
-            PropertyValue::Vec3(val) => {
-                let v = *val;
-                let n_x = name.clone();
-                let n_y = name.clone();
-                let n_z = name.clone();
-                spawn_custom_axis(
-                    commands,
-                    row,
-                    "X",
-                    v.x as f64,
-                    AXIS_X_COLOR,
-                    source_entity,
-                    n_x,
-                    |new_f, old| {
-                        if let PropertyValue::Vec3(v) = old {
-                            v.x = new_f as f32;
-                        }
-                    },
-                );
-                spawn_custom_axis(
-                    commands,
-                    row,
-                    "Y",
-                    v.y as f64,
-                    AXIS_Y_COLOR,
-                    source_entity,
-                    n_y,
-                    |new_f, old| {
-                        if let PropertyValue::Vec3(v) = old {
-                            v.y = new_f as f32;
-                        }
-                    },
-                );
-                spawn_custom_axis(
-                    commands,
-                    row,
-                    "Z",
-                    v.z as f64,
-                    AXIS_Z_COLOR,
-                    source_entity,
-                    n_z,
-                    |new_f, old| {
-                        if let PropertyValue::Vec3(v) = old {
-                            v.z = new_f as f32;
-                        }
-                    },
-                );
-            }
-            PropertyValue::Color(val) => {
-                let srgba = val.to_srgba();
-                let rgba = [srgba.red, srgba.green, srgba.blue, srgba.alpha];
-                let n = name.clone();
-                commands
-                    .spawn((
-                        color_picker(ColorPickerProps::new().with_color(rgba)),
-                        ChildOf(row),
-                    ))
-                    .observe(
-                        move |event: On<ColorPickerCommitEvent>, mut commands: Commands| {
-                            let color = event.color;
-                            let n = n.clone();
-                            commands.queue(move |world: &mut World| {
-                                let new_color =
-                                    Color::srgba(color[0], color[1], color[2], color[3]);
-                                apply_custom_property_with_undo(
-                                    world,
-                                    source_entity,
-                                    &n,
-                                    PropertyValue::Color(new_color),
-                                );
-                            });
-                        },
-                    );
-            }
+        if let PropertyValue::List(items) = prop_value {
+            spawn_list_header(commands, row, source_entity, prop_name, items, icon_font);
+        } else {
+            spawn_value_editor(
+                commands,
+                row,
+                source_entity,
+                prop_name.clone(),
+                None,
+                prop_value,
+                editor_font,
+                icon_font,
+            );
         }
 
         // Remove property button (X icon)
@@ -246,17 +102,506 @@ pub(super) fn spawn_custom_properties_display(
                 });
             }),
         ));
+
+        if let PropertyValue::List(items) = prop_value {
+            spawn_list_items(
+                commands,
+                parent,
+                source_entity,
+                prop_name,
+                items,
+                editor_font,
+                icon_font,
+            );
+        }
     }
 
     // "Add Property" row
     spawn_add_property_row(commands, parent, source_entity, editor_font, icon_font);
 }
 
+/// Spawn the editing widget for one property value into `row`. `index` addresses an
+/// item of a list property instead of the property itself.
+fn spawn_value_editor(
+    commands: &mut Commands,
+    row: Entity,
+    source_entity: Entity,
+    name: String,
+    index: Option<usize>,
+    value: &PropertyValue,
+    editor_font: &Handle<Font>,
+    icon_font: &Handle<Font>,
+) {
+    match value {
+        PropertyValue::Bool(val) => {
+            let checked = *val;
+            commands.spawn((
+                checkbox(
+                    CheckboxProps::new("").checked(checked),
+                    editor_font,
+                    icon_font,
+                ),
+                CustomPropertyBinding {
+                    source_entity,
+                    property_name: name,
+                    index,
+                },
+                ChildOf(row),
+            ));
+        }
+        PropertyValue::Int(val) => {
+            commands.spawn((
+                text_edit::text_edit(
+                    TextEditProps::default()
+                        .numeric_f32()
+                        .grow()
+                        .with_default_value((*val).to_string()),
+                ),
+                CustomPropertyBinding {
+                    source_entity,
+                    property_name: name,
+                    index,
+                },
+                ChildOf(row),
+            ));
+        }
+        PropertyValue::Float(val) => {
+            commands.spawn((
+                text_edit::text_edit(
+                    TextEditProps::default()
+                        .numeric_f32()
+                        .grow()
+                        .with_default_value(val.to_string()),
+                ),
+                CustomPropertyBinding {
+                    source_entity,
+                    property_name: name,
+                    index,
+                },
+                ChildOf(row),
+            ));
+        }
+        PropertyValue::String(val) => {
+            commands.spawn((
+                text_edit::text_edit(
+                    TextEditProps::default()
+                        .grow()
+                        .with_default_value(val.clone())
+                        .allow_empty(),
+                ),
+                CustomPropertyBinding {
+                    source_entity,
+                    property_name: name,
+                    index,
+                },
+                ChildOf(row),
+            ));
+        }
+        PropertyValue::Vec2(val) => {
+            let v = *val;
+            let n_x = name.clone();
+            let n_y = name.clone();
+            spawn_custom_axis(
+                commands,
+                row,
+                "X",
+                v.x as f64,
+                AXIS_X_COLOR,
+                source_entity,
+                n_x,
+                index,
+                |new_f, old| {
+                    if let PropertyValue::Vec2(v) = old {
+                        v.x = new_f as f32;
+                    }
+                },
+            );
+            spawn_custom_axis(
+                commands,
+                row,
+                "Y",
+                v.y as f64,
+                AXIS_Y_COLOR,
+                source_entity,
+                n_y,
+                index,
+                |new_f, old| {
+                    if let PropertyValue::Vec2(v) = old {
+                        v.y = new_f as f32;
+                    }
+                },
+            );
+        }
+        PropertyValue::Vec3(val) => {
+            let v = *val;
+            let n_x = name.clone();
+            let n_y = name.clone();
+            let n_z = name.clone();
+            spawn_custom_axis(
+                commands,
+                row,
+                "X",
+                v.x as f64,
+                AXIS_X_COLOR,
+                source_entity,
+                n_x,
+                index,
+                |new_f, old| {
+                    if let PropertyValue::Vec3(v) = old {
+                        v.x = new_f as f32;
+                    }
+                },
+            );
+            spawn_custom_axis(
+                commands,
+                row,
+                "Y",
+                v.y as f64,
+                AXIS_Y_COLOR,
+                source_entity,
+                n_y,
+                index,
+                |new_f, old| {
+                    if let PropertyValue::Vec3(v) = old {
+                        v.y = new_f as f32;
+                    }
+                },
+            );
+            spawn_custom_axis(
+                commands,
+                row,
+                "Z",
+                v.z as f64,
+                AXIS_Z_COLOR,
+                source_entity,
+                n_z,
+                index,
+                |new_f, old| {
+                    if let PropertyValue::Vec3(v) = old {
+                        v.z = new_f as f32;
+                    }
+                },
+            );
+        }
+        PropertyValue::Color(val) => {
+            let srgba = val.to_srgba();
+            let rgba = [srgba.red, srgba.green, srgba.blue, srgba.alpha];
+            let n = name.clone();
+            commands
+                .spawn((
+                    color_picker(ColorPickerProps::new().with_color(rgba)),
+                    ChildOf(row),
+                ))
+                .observe(
+                    move |event: On<ColorPickerCommitEvent>, mut commands: Commands| {
+                        let color = event.color;
+                        let n = n.clone();
+                        commands.queue(move |world: &mut World| {
+                            let new_color = Color::srgba(color[0], color[1], color[2], color[3]);
+                            apply_custom_property_with_undo(
+                                world,
+                                source_entity,
+                                &n,
+                                index,
+                                PropertyValue::Color(new_color),
+                            );
+                        });
+                    },
+                );
+        }
+        PropertyValue::EntityRef(id) => {
+            let id = *id;
+            // Target name, filled in by `update_entity_ref_links`. Click to select it.
+            commands.spawn((
+                EntityRefLink(id),
+                Text::new(""),
+                TextFont {
+                    font: editor_font.clone(),
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                TextColor(tokens::TEXT_ACCENT),
+                Node {
+                    flex_grow: 1.0,
+                    ..Default::default()
+                },
+                ChildOf(row),
+                observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+                    commands.queue(move |world: &mut World| {
+                        if let Some(target) = resolve_stable_id(world, id) {
+                            select_entities(world, &[target]);
+                        }
+                    });
+                }),
+            ));
+
+            // Eyedropper: the next entity selected becomes the target
+            commands.spawn((
+                Text::new(String::from(Icon::Pipette.unicode())),
+                TextFont {
+                    font: icon_font.clone(),
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                TextColor(tokens::TEXT_SECONDARY),
+                ChildOf(row),
+                observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+                    commands.insert_resource(PendingEntityPick {
+                        source_entity,
+                        property_name: name.clone(),
+                        index,
+                    });
+                }),
+            ));
+        }
+        PropertyValue::List(_) => {
+            // Lists are edited one level deep; nested lists are shown but not editable.
+            commands.spawn((
+                Text::new("List"),
+                TextFont {
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                TextColor(tokens::TEXT_SECONDARY),
+                ChildOf(row),
+            ));
+        }
+    }
+}
+
+/// Item count, the item type selector while the list is empty, and the add button.
+fn spawn_list_header(
+    commands: &mut Commands,
+    row: Entity,
+    source_entity: Entity,
+    name: &str,
+    items: &[PropertyValue],
+    icon_font: &Handle<Font>,
+) {
+    commands.spawn((
+        Text::new(format!("{} items", items.len())),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_SECONDARY),
+        Node {
+            flex_grow: 1.0,
+            ..Default::default()
+        },
+        ChildOf(row),
+    ));
+
+    // The first item fixes the type; until then, let the user choose it
+    if items.is_empty() {
+        commands.spawn((
+            ListItemTypeSelector {
+                source_entity,
+                property_name: name.to_string(),
+            },
+            combobox_with_selected(list_item_type_names(), 2), // default to "Float"
+            ChildOf(row),
+        ));
+    }
+
+    let n = name.to_string();
+    commands.spawn((
+        Text::new(String::from(Icon::Plus.unicode())),
+        TextFont {
+            font: icon_font.clone(),
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_ACCENT),
+        ChildOf(row),
+        observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+            let n = n.clone();
+            commands.queue(move |world: &mut World| {
+                add_list_item(world, source_entity, &n);
+            });
+        }),
+    ));
+}
+
+/// One indented row per list item: index, value editor, reorder and remove buttons.
+fn spawn_list_items(
+    commands: &mut Commands,
+    parent: Entity,
+    source_entity: Entity,
+    name: &str,
+    items: &[PropertyValue],
+    editor_font: &Handle<Font>,
+    icon_font: &Handle<Font>,
+) {
+    let last = items.len().saturating_sub(1);
+    for (i, item) in items.iter().enumerate() {
+        let row = commands
+            .spawn((
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: px(tokens::SPACING_XS),
+                    width: Val::Percent(100.0),
+                    padding: UiRect::left(px(tokens::SPACING_LG)),
+                    ..Default::default()
+                },
+                ChildOf(parent),
+            ))
+            .id();
+
+        commands.spawn((
+            Text::new(i.to_string()),
+            TextFont {
+                font: editor_font.clone(),
+                font_size: tokens::FONT_SM,
+                ..Default::default()
+            },
+            Node {
+                min_width: px(12.0),
+                flex_shrink: 0.0,
+                ..Default::default()
+            },
+            TextColor(tokens::TEXT_SECONDARY),
+            ChildOf(row),
+        ));
+
+        spawn_value_editor(
+            commands,
+            row,
+            source_entity,
+            name.to_string(),
+            Some(i),
+            item,
+            editor_font,
+            icon_font,
+        );
+
+        for (icon, enabled, edit) in [
+            (Icon::ChevronUp, i > 0, ListEdit::MoveUp(i)),
+            (Icon::ChevronDown, i < last, ListEdit::MoveDown(i)),
+            (Icon::X, true, ListEdit::Remove(i)),
+        ] {
+            let n = name.to_string();
+            let color = if enabled {
+                tokens::TEXT_SECONDARY
+            } else {
+                tokens::TEXT_SECONDARY.with_alpha(0.3)
+            };
+            commands.spawn((
+                Text::new(String::from(icon.unicode())),
+                TextFont {
+                    font: icon_font.clone(),
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                TextColor(color),
+                ChildOf(row),
+                observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+                    let n = n.clone();
+                    commands.queue(move |world: &mut World| {
+                        apply_list_edit(world, source_entity, &n, edit);
+                    });
+                }),
+            ));
+        }
+    }
+}
+
+/// Item types offered for new lists. Nested lists aren't editable.
+fn list_item_type_names() -> Vec<String> {
+    PropertyValue::all_type_names()
+        .iter()
+        .filter(|t| **t != "List")
+        .map(|t| t.to_string())
+        .collect()
+}
+
+/// Item type selector of an empty list property.
+#[derive(Component)]
+pub(super) struct ListItemTypeSelector {
+    source_entity: Entity,
+    property_name: String,
+}
+
+#[derive(Clone, Copy)]
+enum ListEdit {
+    MoveUp(usize),
+    MoveDown(usize),
+    Remove(usize),
+}
+
+/// Text showing the target of an entity-reference property.
+#[derive(Component)]
+pub(super) struct EntityRefLink(Uuid);
+
+/// Keep entity-reference links showing the current name of their target.
+pub(crate) fn update_entity_ref_links(
+    mut links: Query<(&EntityRefLink, &mut Text)>,
+    stable_ids: Res<StableIds>,
+    names: Query<&Name>,
+) {
+    for (link, mut text) in &mut links {
+        let label = if link.0.is_nil() {
+            "None"
+        } else {
+            match stable_ids.entity(link.0) {
+                Some(target) => names.get(target).map_or("Unnamed", |n| n.as_str()),
+                None => "Missing",
+            }
+        };
+        if text.0 != label {
+            text.0 = label.to_string();
+        }
+    }
+}
+
+/// Complete a pending entity pick once another entity is selected, then return the
+/// selection to the entity that owns the property. Esc cancels.
+pub(crate) fn resolve_entity_pick(
+    pick: Option<Res<PendingEntityPick>>,
+    selection: Res<Selection>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut commands: Commands,
+) {
+    let Some(pick) = pick else {
+        return;
+    };
+    if keyboard.just_pressed(KeyCode::Escape) {
+        commands.remove_resource::<PendingEntityPick>();
+        return;
+    }
+    let Some(target) = selection.primary() else {
+        return;
+    };
+    if target == pick.source_entity {
+        return;
+    }
+    let pick = pick.clone();
+    commands.remove_resource::<PendingEntityPick>();
+    commands.queue(move |world: &mut World| {
+        if world.get_entity(pick.source_entity).is_err() {
+            return;
+        }
+        match stable_id_of(world, target) {
+            Some(id) => apply_custom_property_with_undo(
+                world,
+                pick.source_entity,
+                &pick.property_name,
+                pick.index,
+                PropertyValue::EntityRef(id),
+            ),
+            None => warn!("Entity {target} can't be referenced"),
+        }
+        select_entities(world, &[pick.source_entity]);
+    });
+}
+
 /// Marker that links a custom property axis input to its property name and mutation function.
 #[derive(Component)]
 pub(super) struct CustomAxisBinding {
     source_entity: Entity,
     property_name: String,
+    index: Option<usize>,
     mutate: fn(f64, &mut PropertyValue),
 }
 
@@ -268,6 +613,7 @@ fn spawn_custom_axis(
     label_color: Color,
     source_entity: Entity,
     property_name: String,
+    index: Option<usize>,
     mutate: fn(f64, &mut PropertyValue),
 ) {
     commands.spawn((
@@ -294,6 +640,7 @@ fn spawn_custom_axis(
         CustomAxisBinding {
             source_entity,
             property_name,
+            index,
             mutate,
         },
         ChildOf(parent),
@@ -362,6 +709,155 @@ fn spawn_add_property_row(
             });
         }),
     ));
+
+    // Class-driven controls, filled in by `populate_schema_slots`
+    commands.spawn((
+        SchemaSlot(source_entity),
+        Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: px(tokens::SPACING_XS),
+            width: Val::Percent(100.0),
+            ..Default::default()
+        },
+        ChildOf(parent),
+    ));
+}
+
+/// Labels of properties whose value breaks the class schema.
+const SCHEMA_WARNING_COLOR: Color = Color::srgb(1.0, 0.75, 0.3);
+
+/// Name label of a custom property row, tinted when the value breaks the schema.
+#[derive(Component)]
+pub(super) struct CustomPropertyLabel {
+    source_entity: Entity,
+    property_name: String,
+}
+
+/// Container for the class picker or the "add class property" dropdown.
+#[derive(Component)]
+pub(super) struct SchemaSlot(Entity);
+
+/// Lists missing and invalid class properties of the inspected entity.
+#[derive(Component)]
+pub(super) struct SchemaIssues(Entity);
+
+/// Offer a class picker for entities without a known class, otherwise a dropdown
+/// of class properties the entity doesn't have yet.
+pub(crate) fn populate_schema_slots(
+    mut commands: Commands,
+    slots: Query<(Entity, &SchemaSlot), Added<SchemaSlot>>,
+    classes: Res<EntityClasses>,
+    properties: Query<&CustomProperties>,
+) {
+    if classes.definitions.classes.is_empty() {
+        return;
+    }
+    for (slot, &SchemaSlot(source_entity)) in &slots {
+        let Ok(cp) = properties.get(source_entity) else {
+            continue;
+        };
+
+        let (placeholder, options, apply): (_, Vec<String>, fn(&mut World, Entity, &str)) =
+            match classes.class_of(cp) {
+                None => (
+                    "Apply class...",
+                    classes.definitions.classes.keys().cloned().collect(),
+                    apply_entity_class,
+                ),
+                Some((_, class)) => (
+                    "Add class property...",
+                    class
+                        .properties
+                        .keys()
+                        .filter(|name| !cp.properties.contains_key(*name))
+                        .cloned()
+                        .collect(),
+                    add_class_property,
+                ),
+            };
+
+        if !options.is_empty() {
+            let items: Vec<String> = std::iter::once(placeholder.to_string())
+                .chain(options.iter().cloned())
+                .collect();
+            commands
+                .spawn((combobox_with_selected(items, 0), ChildOf(slot)))
+                .observe(
+                    move |event: On<ComboBoxChangeEvent>, mut commands: Commands| {
+                        let Some(choice) = event
+                            .selected
+                            .checked_sub(1)
+                            .and_then(|i| options.get(i))
+                            .cloned()
+                        else {
+                            return;
+                        };
+                        commands.queue(move |world: &mut World| {
+                            apply(world, source_entity, &choice);
+                            rebuild_inspector(world, source_entity);
+                        });
+                    },
+                );
+        }
+
+        commands.spawn((
+            SchemaIssues(source_entity),
+            Text::new(""),
+            TextFont {
+                font_size: tokens::FONT_SM,
+                ..Default::default()
+            },
+            TextColor(SCHEMA_WARNING_COLOR),
+            ChildOf(slot),
+        ));
+    }
+}
+
+/// Tint property labels that break the class schema and list the problems.
+pub(crate) fn update_schema_feedback(
+    mut labels: Query<(&CustomPropertyLabel, &mut TextColor)>,
+    mut issues: Query<(&SchemaIssues, &mut Text)>,
+    classes: Res<EntityClasses>,
+    properties: Query<&CustomProperties>,
+) {
+    for (label, mut color) in &mut labels {
+        let invalid = properties.get(label.source_entity).is_ok_and(|cp| {
+            let schema = classes.property_schema(cp, &label.property_name);
+            let value = cp.properties.get(&label.property_name);
+            schema
+                .zip(value)
+                .is_some_and(|(schema, value)| schema.validate(value).is_some())
+        });
+        let target = if invalid {
+            SCHEMA_WARNING_COLOR
+        } else {
+            tokens::TEXT_PRIMARY
+        };
+        if color.0 != target {
+            color.0 = target;
+        }
+    }
+
+    for (&SchemaIssues(source_entity), mut text) in &mut issues {
+        let mut lines = Vec::new();
+        if let Ok(cp) = properties.get(source_entity)
+            && let Some((class_name, class)) = classes.class_of(cp)
+        {
+            let missing = class.missing_required(cp);
+            if !missing.is_empty() {
+                lines.push(format!("{class_name} requires: {}", missing.join(", ")));
+            }
+            for (name, schema) in &class.properties {
+                if let Some(problem) = cp.properties.get(name).and_then(|v| schema.validate(v)) {
+                    lines.push(format!("{name}: {problem}"));
+                }
+            }
+        }
+        let new_text = lines.join("\n");
+        if text.0 != new_text {
+            text.0 = new_text;
+        }
+    }
 }
 
 /// Read the name input and type selector, then add a new property.
@@ -410,8 +906,7 @@ fn add_custom_property_from_ui(world: &mut World, source_entity: Entity) {
     cmd.execute(world);
 
     let mut history = world.resource_mut::<CommandHistory>();
-    history.undo_stack.push(Box::new(cmd));
-    history.redo_stack.clear();
+    history.push_executed(Box::new(cmd));
 
     // Rebuild inspector
     rebuild_inspector(world, source_entity);
@@ -434,17 +929,107 @@ fn remove_custom_property(world: &mut World, source_entity: Entity, property_nam
     cmd.execute(world);
 
     let mut history = world.resource_mut::<CommandHistory>();
-    history.undo_stack.push(Box::new(cmd));
-    history.redo_stack.clear();
+    history.push_executed(Box::new(cmd));
+
+    rebuild_inspector(world, source_entity);
+}
+
+/// Append a default item to a list property, of the list's type or the type chosen
+/// in its selector while it's empty.
+fn add_list_item(world: &mut World, source_entity: Entity, property_name: &str) {
+    let first_type = world.get::<CustomProperties>(source_entity).and_then(|cp| {
+        match cp.properties.get(property_name) {
+            Some(PropertyValue::List(items)) => items.first().map(|item| item.type_label()),
+            _ => None,
+        }
+    });
+    let item_type = match first_type {
+        Some(label) => label.to_string(),
+        None => {
+            let names = list_item_type_names();
+            let mut query = world.query::<(&ListItemTypeSelector, &ComboBoxSelectedIndex)>();
+            query
+                .iter(world)
+                .find(|(selector, _)| {
+                    selector.source_entity == source_entity
+                        && selector.property_name == property_name
+                })
+                .map(|(_, index)| names[index.0.min(names.len() - 1)].clone())
+                .unwrap_or_else(|| "Float".to_string())
+        }
+    };
+    let Some(item) = PropertyValue::default_for_type(&item_type) else {
+        return;
+    };
+    edit_list_property(world, source_entity, property_name, |items| {
+        items.push(item)
+    });
+}
+
+fn apply_list_edit(world: &mut World, source_entity: Entity, property_name: &str, edit: ListEdit) {
+    edit_list_property(world, source_entity, property_name, |items| match edit {
+        ListEdit::MoveUp(i) if i > 0 && i < items.len() => items.swap(i, i - 1),
+        ListEdit::MoveDown(i) if i + 1 < items.len() => items.swap(i, i + 1),
+        ListEdit::Remove(i) if i < items.len() => {
+            items.remove(i);
+        }
+        _ => {}
+    });
+}
+
+/// Change the items of a list property with undo, then rebuild the inspector.
+fn edit_list_property(
+    world: &mut World,
+    source_entity: Entity,
+    property_name: &str,
+    edit: impl FnOnce(&mut Vec<PropertyValue>),
+) {
+    let Some(cp) = world.get::<CustomProperties>(source_entity) else {
+        return;
+    };
+    let old = cp.clone();
+    let mut new = old.clone();
+    let Some(PropertyValue::List(items)) = new.properties.get_mut(property_name) else {
+        return;
+    };
+    edit(items);
+    if new.properties == old.properties {
+        return;
+    }
+
+    let cmd = SetCustomProperties {
+        entity: source_entity,
+        old_properties: old,
+        new_properties: new,
+    };
+    cmd.execute(world);
+
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(cmd));
 
     rebuild_inspector(world, source_entity);
 }
 
-/// Apply a custom property value change with undo.
+/// The property `name`, or its item at `index` if it's a list.
+fn property_value<'a>(
+    cp: &'a CustomProperties,
+    name: &str,
+    index: Option<usize>,
+) -> Option<&'a PropertyValue> {
+    let value = cp.properties.get(name)?;
+    match (value, index) {
+        (_, None) => Some(value),
+        (PropertyValue::List(items), Some(i)) => items.get(i),
+        _ => None,
+    }
+}
+
+/// Apply a custom property value change with undo. `index` targets an item of a list.
 fn apply_custom_property_with_undo(
     world: &mut World,
     source_entity: Entity,
     property_name: &str,
+    index: Option<usize>,
     new_value: PropertyValue,
 ) {
     let Some(cp) = world.get::<CustomProperties>(source_entity) else {
@@ -452,7 +1037,27 @@ fn apply_custom_property_with_undo(
     };
     let old = cp.clone();
     let mut new = old.clone();
-    new.properties.insert(property_name.to_string(), new_value);
+    match index {
+        None => {
+            let new_value = match world
+                .resource::<EntityClasses>()
+                .property_schema(&old, property_name)
+            {
+                Some(schema) => schema.clamp(new_value),
+                None => new_value,
+            };
+            new.properties.insert(property_name.to_string(), new_value);
+        }
+        Some(i) => {
+            let Some(PropertyValue::List(items)) = new.properties.get_mut(property_name) else {
+                return;
+            };
+            let Some(item) = items.get_mut(i) else {
+                return;
+            };
+            *item = new_value;
+        }
+    }
 
     let cmd = SetCustomProperties {
         entity: source_entity,
@@ -462,8 +1067,7 @@ fn apply_custom_property_with_undo(
     cmd.execute(world);
 
     let mut history = world.resource_mut::<CommandHistory>();
-    history.undo_stack.push(Box::new(cmd));
-    history.redo_stack.clear();
+    history.push_executed(Box::new(cmd));
 }
 
 /// Handle TextEditCommitEvent for custom property numeric/string fields + axis bindings.
@@ -486,13 +1090,14 @@ pub(crate) fn on_custom_property_text_commit(
         if let Ok(binding) = bindings.get(parent) {
             let source = binding.source_entity;
             let name = binding.property_name.clone();
+            let index = binding.index;
             let text = event.text.clone();
             commands.queue(move |world: &mut World| {
                 // Determine current type and apply accordingly
                 let Some(cp) = world.get::<CustomProperties>(source) else {
                     return;
                 };
-                let Some(current_val) = cp.properties.get(&name) else {
+                let Some(current_val) = property_value(cp, &name, index) else {
                     return;
                 };
                 let new_val = match current_val {
@@ -501,7 +1106,7 @@ pub(crate) fn on_custom_property_text_commit(
                     PropertyValue::String(_) => PropertyValue::String(text),
                     other => other.clone(),
                 };
-                apply_custom_property_with_undo(world, source, &name, new_val);
+                apply_custom_property_with_undo(world, source, &name, index, new_val);
             });
             return;
         }
@@ -510,18 +1115,19 @@ pub(crate) fn on_custom_property_text_commit(
         if let Ok(axis) = axis_bindings.get(parent) {
             let source = axis.source_entity;
             let name = axis.property_name.clone();
+            let index = axis.index;
             let mutate = axis.mutate;
             let new_f: f64 = event.text.parse().unwrap_or(0.0);
             commands.queue(move |world: &mut World| {
                 let Some(cp) = world.get::<CustomProperties>(source) else {
                     return;
                 };
-                let Some(current) = cp.properties.get(&name) else {
+                let Some(current) = property_value(cp, &name, index) else {
                     return;
                 };
                 let mut new_val = current.clone();
                 mutate(new_f, &mut new_val);
-                apply_custom_property_with_undo(world, source, &name, new_val);
+                apply_custom_property_with_undo(world, source, &name, index, new_val);
             });
             return;
         }
@@ -541,8 +1147,9 @@ pub(crate) fn on_custom_property_checkbox_commit(
     };
     let source = binding.source_entity;
     let name = binding.property_name.clone();
+    let index = binding.index;
     let checked = event.checked;
     commands.queue(move |world: &mut World| {
-        apply_custom_property_with_undo(world, source, &name, PropertyValue::Bool(checked));
+        apply_custom_property_with_undo(world, source, &name, index, PropertyValue::Bool(checked));
     });
 }
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use jackdaw_feathers::{
+    button::{ButtonProps, button},
+    tokens,
+};
+use jackdaw_jsn::LinkedDuplicate;
+
+/// Text showing how many entities share the link of the entity it was spawned for.
+#[derive(Component)]
+pub(super) struct LinkedCountText(Entity);
+
+pub(super) fn spawn_linked_display(commands: &mut Commands, parent: Entity, source_entity: Entity) {
+    commands.spawn((
+        LinkedCountText(source_entity),
+        Text::new(""),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_SECONDARY),
+        ChildOf(parent),
+    ));
+
+    let row = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                column_gap: px(tokens::SPACING_XS),
+                width: Val::Percent(100.0),
+                ..Default::default()
+            },
+            ChildOf(parent),
+        ))
+        .id();
+
+    commands
+        .spawn((button(ButtonProps::new("Select Linked")), ChildOf(row)))
+        .observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+            commands.queue(move |world: &mut World| {
+                crate::linked_duplicate::select_linked(world, source_entity);
+            });
+        });
+
+    commands
+        .spawn((button(ButtonProps::new("Make Unique")), ChildOf(row)))
+        .observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+            commands.queue(move |world: &mut World| {
+                crate::linked_duplicate::make_unique(world, source_entity);
+            });
+        });
+}
+
+/// Keep the linked-instance counts current as members are duplicated or deleted.
+pub(super) fn refresh_linked_counts(
+    links: Query<&LinkedDuplicate>,
+    mut texts: Query<(&LinkedCountText, &mut Text)>,
+) {
+    for (count_text, mut text) in &mut texts {
+        let Ok(link) = links.get(count_text.0) else {
+            continue;
+        };
+        let count = links.iter().filter(|other| *other == link).count();
+        let label = if count == 1 {
+            "1 linked instance".to_string()
+        } else {
+            format!("{count} linked instances")
+        };
+        if text.0 != label {
+            text.0 = label;
+        }
+    }
+}
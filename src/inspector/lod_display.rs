@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+use jackdaw_feathers::{
+    button::{ButtonProps, button},
+    icons::Icon,
+    text_edit::{self, NumericUnit, TextEditCommitEvent, TextEditProps},
+    tokens,
+};
+use jackdaw_jsn::{LodGroup, LodLevel};
+
+use super::rebuild_inspector;
+use crate::{
+    commands::{CommandHistory, EditorCommand},
+    lod::SetLodGroup,
+};
+
+#[derive(Clone, Copy)]
+enum LodLevelField {
+    Path,
+    Distance,
+}
+
+#[derive(Component)]
+pub(super) struct LodLevelBinding {
+    source_entity: Entity,
+    index: usize,
+    field: LodLevelField,
+}
+
+/// One row per coarser level (model path and switch distance), plus add/remove.
+pub(super) fn spawn_lod_display(
+    commands: &mut Commands,
+    parent: Entity,
+    source_entity: Entity,
+    group: &LodGroup,
+    icon_font: &Handle<Font>,
+) {
+    commands.spawn((
+        Text::new("LOD 0: this entity's own geometry"),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_SECONDARY),
+        ChildOf(parent),
+    ));
+
+    for (index, level) in group.levels.iter().enumerate() {
+        let row = commands
+            .spawn((
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: px(tokens::SPACING_XS),
+                    width: Val::Percent(100.0),
+                    ..Default::default()
+                },
+                ChildOf(parent),
+            ))
+            .id();
+        commands.spawn((
+            Text::new(format!("LOD {}", index + 1)),
+            TextFont {
+                font_size: tokens::FONT_SM,
+                ..Default::default()
+            },
+            TextColor(tokens::TEXT_SECONDARY),
+            Node {
+                min_width: px(40.0),
+                flex_shrink: 0.0,
+                ..Default::default()
+            },
+            ChildOf(row),
+        ));
+        commands.spawn((
+            text_edit::text_edit(
+                TextEditProps::default()
+                    .grow()
+                    .with_placeholder("models/prop_lod1.glb")
+                    .with_default_value(level.path.clone())
+                    .allow_empty(),
+            ),
+            LodLevelBinding {
+                source_entity,
+                index,
+                field: LodLevelField::Path,
+            },
+            ChildOf(row),
+        ));
+        let distance_slot = commands
+            .spawn((
+                Node {
+                    width: px(72.0),
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+                ChildOf(row),
+            ))
+            .id();
+        commands.spawn((
+            text_edit::text_edit(
+                TextEditProps::default()
+                    .numeric_f32()
+                    .grow()
+                    .with_min(0.0)
+                    .with_unit(NumericUnit::Meters)
+                    .with_default_value(level.distance.to_string()),
+            ),
+            LodLevelBinding {
+                source_entity,
+                index,
+                field: LodLevelField::Distance,
+            },
+            ChildOf(distance_slot),
+        ));
+        commands
+            .spawn((
+                Text::new(String::from(Icon::X.unicode())),
+                TextFont {
+                    font: icon_font.clone(),
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                TextColor(tokens::TEXT_SECONDARY),
+                ChildOf(row),
+            ))
+            .observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+                commands.queue(move |world: &mut World| {
+                    edit_lod_group(world, source_entity, |group| {
+                        if index < group.levels.len() {
+                            group.levels.remove(index);
+                        }
+                    });
+                    rebuild_inspector(world, source_entity);
+                });
+            });
+    }
+
+    commands
+        .spawn((button(ButtonProps::new("+ Add Level")), ChildOf(parent)))
+        .observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+            commands.queue(move |world: &mut World| {
+                edit_lod_group(world, source_entity, |group| {
+                    // Each level twice as far out as the last
+                    let distance = group.levels.last().map_or(20.0, |l| l.distance * 2.0);
+                    group.levels.push(LodLevel {
+                        path: String::new(),
+                        distance,
+                    });
+                });
+                rebuild_inspector(world, source_entity);
+            });
+        });
+}
+
+fn edit_lod_group(world: &mut World, entity: Entity, edit: impl FnOnce(&mut LodGroup)) {
+    let Some(old) = world.get::<LodGroup>(entity).cloned() else {
+        return;
+    };
+    let mut new = old.clone();
+    edit(&mut new);
+    if new == old {
+        return;
+    }
+    let cmd = SetLodGroup { entity, old, new };
+    cmd.execute(world);
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(cmd));
+}
+
+pub(super) fn on_lod_level_commit(
+    event: On<TextEditCommitEvent>,
+    bindings: Query<&LodLevelBinding>,
+    child_of_query: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    // Walk up from committed entity to find a level binding
+    let mut current = event.entity;
+    for _ in 0..4 {
+        let Ok(child_of) = child_of_query.get(current) else {
+            return;
+        };
+        current = child_of.parent();
+        let Ok(binding) = bindings.get(current) else {
+            continue;
+        };
+        let (entity, index, field) = (binding.source_entity, binding.index, binding.field);
+        let text = event.text.trim().to_string();
+        commands.queue(move |world: &mut World| {
+            edit_lod_group(world, entity, |group| {
+                let Some(level) = group.levels.get_mut(index) else {
+                    return;
+                };
+                match field {
+                    LodLevelField::Path => level.path = text,
+                    LodLevelField::Distance => {
+                        if let Ok(distance) = text.parse::<f32>() {
+                            level.distance = distance.max(0.0);
+                        }
+                    }
+                }
+            });
+        });
+        return;
+    }
+}
@@ -1,12 +1,160 @@
-use bevy::prelude::*;
+use std::any::TypeId;
+use std::collections::HashSet;
+use std::mem::discriminant;
+
+use bevy::{input_focus::InputFocus, prelude::*, render::render_resource::Face};
 use jackdaw_feathers::{
-    text_edit::{self, TextEditCommitEvent, TextEditProps},
+    button::{ButtonProps, button},
+    checkbox::{CheckboxCommitEvent, CheckboxProps, CheckboxState, checkbox},
+    color_picker::{ColorPickerCommitEvent, ColorPickerProps, ColorPickerState, color_picker},
+    combobox::{ComboBoxChangeEvent, ComboBoxSelectedIndex, combobox_with_selected},
+    icons::{EditorFont, Icon, IconFont},
+    text_edit::{
+        self, TextEditCommitEvent, TextEditDragging, TextEditProps, TextEditValue, TextEditVariant,
+        TextInputQueue, set_text_input_value,
+    },
     tokens,
 };
 
-/// Marker for material field UI entities
+use super::asset_field::{
+    AssetFieldBinding, AssetFieldTarget, AssetKind, asset_field, handle_label,
+};
+use super::rebuild_inspector;
+use super::reflect_fields::find_text_edit_entities;
+use crate::EditorEntity;
+use crate::material_browser::{self, TextureSlot};
+use crate::material_preview::PreviewSphere;
+use crate::selection::Selection;
+
+const ALPHA_MODES: [(&str, AlphaMode); 7] = [
+    ("Opaque", AlphaMode::Opaque),
+    ("Mask", AlphaMode::Mask(0.5)),
+    ("Blend", AlphaMode::Blend),
+    ("Premultiplied", AlphaMode::Premultiplied),
+    ("Alpha to Coverage", AlphaMode::AlphaToCoverage),
+    ("Add", AlphaMode::Add),
+    ("Multiply", AlphaMode::Multiply),
+];
+
+const CULL_MODES: [(&str, Option<Face>); 3] = [
+    ("None", None),
+    ("Back", Some(Face::Back)),
+    ("Front", Some(Face::Front)),
+];
+
+/// Which `StandardMaterial` property a material widget edits.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum MaterialField {
+    BaseColor,
+    Emissive,
+    Metallic,
+    Roughness,
+    Reflectance,
+    AlphaMode,
+    CullMode,
+    DoubleSided,
+}
+
+impl MaterialField {
+    fn number(self, mat: &StandardMaterial) -> Option<f32> {
+        match self {
+            Self::Metallic => Some(mat.metallic),
+            Self::Roughness => Some(mat.perceptual_roughness),
+            Self::Reflectance => Some(mat.reflectance),
+            _ => None,
+        }
+    }
+
+    fn set_number(self, mat: &mut StandardMaterial, value: f32) {
+        match self {
+            Self::Metallic => mat.metallic = value,
+            Self::Roughness => mat.perceptual_roughness = value,
+            Self::Reflectance => mat.reflectance = value,
+            _ => {}
+        }
+    }
+
+    /// sRGB color shown in the picker.
+    fn color(self, mat: &StandardMaterial) -> Option<[f32; 4]> {
+        let srgba = match self {
+            Self::BaseColor => mat.base_color.to_srgba(),
+            Self::Emissive => Color::LinearRgba(mat.emissive).to_srgba(),
+            _ => return None,
+        };
+        Some([srgba.red, srgba.green, srgba.blue, srgba.alpha])
+    }
+
+    fn set_color(self, mat: &mut StandardMaterial, c: [f32; 4]) {
+        match self {
+            Self::BaseColor => mat.base_color = Color::srgba(c[0], c[1], c[2], c[3]),
+            // Emissive light has no alpha
+            Self::Emissive => mat.emissive = Color::srgb(c[0], c[1], c[2]).to_linear(),
+            _ => {}
+        }
+    }
+
+    /// Selected combobox index.
+    fn option(self, mat: &StandardMaterial) -> Option<usize> {
+        match self {
+            Self::AlphaMode => ALPHA_MODES
+                .iter()
+                .position(|(_, mode)| discriminant(mode) == discriminant(&mat.alpha_mode)),
+            Self::CullMode => CULL_MODES
+                .iter()
+                .position(|(_, face)| *face == mat.cull_mode),
+            _ => None,
+        }
+    }
+
+    fn set_option(self, mat: &mut StandardMaterial, index: usize) {
+        match self {
+            Self::AlphaMode => {
+                // Re-picking "Mask" keeps the current cutoff
+                if let Some((_, mode)) = ALPHA_MODES.get(index)
+                    && discriminant(mode) != discriminant(&mat.alpha_mode)
+                {
+                    mat.alpha_mode = *mode;
+                }
+            }
+            Self::CullMode => {
+                if let Some((_, face)) = CULL_MODES.get(index) {
+                    mat.cull_mode = *face;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn flag(self, mat: &StandardMaterial) -> Option<bool> {
+        match self {
+            Self::DoubleSided => Some(mat.double_sided),
+            _ => None,
+        }
+    }
+
+    fn set_flag(self, mat: &mut StandardMaterial, value: bool) {
+        if self == Self::DoubleSided {
+            mat.double_sided = value;
+        }
+    }
+}
+
+/// Binding that links a material widget to a source entity and material property.
+#[derive(Component)]
+pub(super) struct MaterialFieldBinding {
+    pub(super) source_entity: Entity,
+    pub(super) field: MaterialField,
+}
+
+/// Shared materials the user chose to edit in place rather than make unique.
+#[derive(Resource, Default)]
+pub(super) struct SharedMaterialEdits(pub(super) HashSet<AssetId<StandardMaterial>>);
+
+/// Last material color pushed into a picker, so `refresh_material_fields` only
+/// overwrites the picker when the material itself changes (undo, other editors)
+/// and not while the user drags the picker.
 #[derive(Component)]
-struct MaterialFieldMarker;
+struct MaterialColorSeen([f32; 4]);
 
 /// Spawn material fields in a deferred command to access `Assets<StandardMaterial>`.
 pub(super) fn spawn_material_display_deferred(
@@ -19,31 +167,57 @@ pub(super) fn spawn_material_display_deferred(
     });
 }
 
-fn spawn_material_fields(world: &mut World, body_entity: Entity, source_entity: Entity) {
-    // Look up the Handle<StandardMaterial> from MeshMaterial3d
-    let handle = {
-        let Some(mat) = world.get::<MeshMaterial3d<StandardMaterial>>(source_entity) else {
-            return;
-        };
-        mat.0.clone()
+/// The material `source_entity` is edited through: the authored one, even while a view mode
+/// or a `MaterialOverride` shows something else.
+pub(super) fn material_handle(
+    world: &World,
+    source_entity: Entity,
+) -> Option<Handle<StandardMaterial>> {
+    crate::view_modes::authored_material(world.get_entity(source_entity).ok()?)
+}
+
+/// Edit the material of `source_entity` undoably, snapshotting the whole asset.
+pub(super) fn edit_material(
+    world: &mut World,
+    source_entity: Entity,
+    edit: impl FnOnce(&mut StandardMaterial),
+) {
+    let Some(handle) = material_handle(world, source_entity) else {
+        return;
     };
+    material_browser::edit_material(world, handle, edit);
+}
 
-    let mat_data = {
-        let materials = world.resource::<Assets<StandardMaterial>>();
-        materials.get(&handle).map(|material| {
-            (
-                material.base_color,
-                material.metallic,
-                material.perceptual_roughness,
-                material.reflectance,
-                material.emissive,
-                format!("{:?}", material.alpha_mode),
-            )
-        })
+fn spawn_material_fields(world: &mut World, body_entity: Entity, source_entity: Entity) {
+    let Some(handle) = material_handle(world, source_entity) else {
+        return;
     };
 
-    let Some((base_color, metallic, perceptual_roughness, reflectance, emissive, alpha_mode_str)) =
-        mat_data
+    // Which material, with a picker to load another
+    let icon_font = world.resource::<IconFont>().0.clone();
+    let editor_font = world.resource::<EditorFont>().0.clone();
+    world.spawn((
+        asset_field(
+            "material",
+            handle_label(&handle),
+            AssetFieldBinding {
+                source_entity,
+                target: AssetFieldTarget::Field {
+                    component_type_id: TypeId::of::<MeshMaterial3d<StandardMaterial>>(),
+                    field_path: ".0".to_string(),
+                },
+                kind: AssetKind::Material,
+            },
+            0,
+            icon_font.clone(),
+        ),
+        ChildOf(body_entity),
+    ));
+
+    let Some(material) = world
+        .resource::<Assets<StandardMaterial>>()
+        .get(&handle)
+        .cloned()
     else {
         world.spawn((
             Text::new("(material not loaded)"),
@@ -57,166 +231,260 @@ fn spawn_material_fields(world: &mut World, body_entity: Entity, source_entity:
         return;
     };
 
-    // base_color (Color picker)
-    {
-        let srgba = base_color.to_srgba();
-        let row = world
-            .spawn((
-                Node {
-                    flex_direction: FlexDirection::Row,
-                    align_items: AlignItems::Center,
-                    column_gap: Val::Px(tokens::SPACING_XS),
-                    ..Default::default()
-                },
-                ChildOf(body_entity),
-            ))
-            .id();
+    // Shared materials stay read-only until the user picks how to edit them
+    let users = material_users(world, &handle);
+    if users.len() > 1 {
+        let edit_shared = world
+            .resource::<SharedMaterialEdits>()
+            .0
+            .contains(&handle.id());
+        spawn_shared_banner(world, body_entity, source_entity, users.len(), edit_shared);
+        if !edit_shared {
+            return;
+        }
+    }
+
+    for (label, field) in [
+        ("base_color", MaterialField::BaseColor),
+        ("emissive", MaterialField::Emissive),
+    ] {
+        let Some(rgba) = field.color(&material) else {
+            continue;
+        };
+        let row = spawn_field_row(world, body_entity, label);
         world.spawn((
-            Text::new("base_color:"),
-            TextFont {
-                font_size: tokens::FONT_SM,
-                ..Default::default()
+            color_picker(ColorPickerProps::new().with_color(rgba)),
+            MaterialFieldBinding {
+                source_entity,
+                field,
             },
-            TextColor(tokens::TEXT_SECONDARY),
-            Node {
-                min_width: Val::Px(20.0),
-                flex_shrink: 0.0,
-                ..Default::default()
+            MaterialColorSeen(rgba),
+            ChildOf(row),
+        ));
+    }
+
+    for (label, field) in [
+        ("metallic", MaterialField::Metallic),
+        ("roughness", MaterialField::Roughness),
+        ("reflectance", MaterialField::Reflectance),
+    ] {
+        let Some(value) = field.number(&material) else {
+            continue;
+        };
+        let row = spawn_field_row(world, body_entity, label);
+        world.spawn((
+            text_edit::text_edit(
+                TextEditProps::default()
+                    .numeric_f32()
+                    .grow()
+                    .with_default_value(value.to_string()),
+            ),
+            MaterialFieldBinding {
+                source_entity,
+                field,
+            },
+            ChildOf(row),
+        ));
+    }
+
+    for (label, field, options) in [
+        (
+            "alpha_mode",
+            MaterialField::AlphaMode,
+            ALPHA_MODES
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>(),
+        ),
+        (
+            "cull_mode",
+            MaterialField::CullMode,
+            CULL_MODES.iter().map(|(name, _)| *name).collect(),
+        ),
+    ] {
+        let selected = field.option(&material).unwrap_or(0);
+        let row = spawn_field_row(world, body_entity, label);
+        world.spawn((
+            combobox_with_selected(options, selected),
+            ComboBoxSelectedIndex(selected),
+            MaterialFieldBinding {
+                source_entity,
+                field,
             },
             ChildOf(row),
         ));
-        let rgba = [srgba.red, srgba.green, srgba.blue, srgba.alpha];
-        let picker = world
+    }
+
+    let row = spawn_field_row(world, body_entity, "double_sided");
+    world.spawn((
+        checkbox(
+            CheckboxProps::new("").checked(material.double_sided),
+            &editor_font,
+            &icon_font,
+        ),
+        MaterialFieldBinding {
+            source_entity,
+            field: MaterialField::DoubleSided,
+        },
+        ChildOf(row),
+    ));
+
+    // Texture slots: asset pickers editing the material, plus a clear button
+    for slot in TextureSlot::ALL {
+        let current = match slot.get_from(&material) {
+            Some(image) => handle_label(&image),
+            None => "(none)".to_string(),
+        };
+        let row = world
             .spawn((
-                jackdaw_feathers::color_picker::color_picker(
-                    jackdaw_feathers::color_picker::ColorPickerProps::new().with_color(rgba),
+                asset_field(
+                    slot.label(),
+                    current,
+                    AssetFieldBinding {
+                        source_entity,
+                        target: AssetFieldTarget::MaterialTexture(slot),
+                        kind: AssetKind::Image,
+                    },
+                    0,
+                    icon_font.clone(),
                 ),
-                MaterialFieldMarker,
-                ChildOf(row),
+                ChildOf(body_entity),
             ))
             .id();
-        world.entity_mut(picker).observe(
-            move |event: On<jackdaw_feathers::color_picker::ColorPickerCommitEvent>,
-                  mut materials: ResMut<Assets<StandardMaterial>>,
-                  mat_query: Query<&MeshMaterial3d<StandardMaterial>>| {
-                let Ok(mat_comp) = mat_query.get(source_entity) else {
-                    return;
-                };
-                if let Some(material) = materials.get_mut(&mat_comp.0) {
-                    let c = event.color;
-                    material.base_color = Color::srgba(c[0], c[1], c[2], c[3]);
-                }
-            },
-        );
+        world
+            .spawn((
+                Text::new(String::from(Icon::X.unicode())),
+                TextFont {
+                    font: icon_font.clone(),
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                TextColor(tokens::TEXT_SECONDARY),
+                ChildOf(row),
+            ))
+            .observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+                commands.queue(move |world: &mut World| {
+                    edit_material(world, source_entity, |mat| slot.set_on(mat, None));
+                });
+            });
     }
+}
 
-    // metallic (f32 numeric input)
-    spawn_material_numeric_field(
-        world,
-        body_entity,
-        "metallic",
-        metallic as f64,
-        source_entity,
-        |mat, val| {
-            mat.metallic = val as f32;
-        },
-    );
-
-    // perceptual_roughness
-    spawn_material_numeric_field(
-        world,
-        body_entity,
-        "roughness",
-        perceptual_roughness as f64,
-        source_entity,
-        |mat, val| {
-            mat.perceptual_roughness = val as f32;
-        },
-    );
-
-    // reflectance
-    spawn_material_numeric_field(
-        world,
-        body_entity,
-        "reflectance",
-        reflectance as f64,
-        source_entity,
-        |mat, val| {
-            mat.reflectance = val as f32;
-        },
-    );
-
-    // emissive (show as text for now - it's LinearRgba which is complex)
-    {
-        let emissive_text = format!(
-            "emissive: ({:.2}, {:.2}, {:.2})",
-            emissive.red, emissive.green, emissive.blue
-        );
-        world.spawn((
-            Text::new(emissive_text),
-            TextFont {
-                font_size: tokens::FONT_SM,
+/// Scene entities whose material, overridden or not, is `handle`.
+fn material_users(world: &mut World, handle: &Handle<StandardMaterial>) -> Vec<Entity> {
+    let mut query = world.query_filtered::<Entity, (
+        With<MeshMaterial3d<StandardMaterial>>,
+        Without<EditorEntity>,
+        Without<PreviewSphere>,
+    )>();
+    let entities: Vec<Entity> = query.iter(world).collect();
+    entities
+        .into_iter()
+        .filter(|&entity| material_handle(world, entity).as_ref() == Some(handle))
+        .collect()
+}
+
+fn spawn_shared_banner(
+    world: &mut World,
+    parent: Entity,
+    source_entity: Entity,
+    users: usize,
+    edit_shared: bool,
+) {
+    let banner = world
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(tokens::SPACING_XS),
+                padding: UiRect::all(Val::Px(tokens::SPACING_SM)),
+                margin: UiRect::vertical(Val::Px(tokens::SPACING_XS)),
+                border: UiRect::all(Val::Px(1.0)),
+                border_radius: BorderRadius::all(Val::Px(tokens::BORDER_RADIUS_SM)),
                 ..Default::default()
             },
-            TextColor(tokens::TEXT_SECONDARY),
-            ChildOf(body_entity),
-        ));
-    }
-
-    // alpha_mode (read-only for now)
+            BorderColor::all(tokens::BORDER_STRONG),
+            ChildOf(parent),
+        ))
+        .id();
+    let message = if edit_shared {
+        format!("Editing material shared by {users} entities")
+    } else {
+        format!("Material is shared by {users} entities")
+    };
     world.spawn((
-        Text::new(format!("alpha_mode: {alpha_mode_str}")),
+        Text::new(message),
         TextFont {
             font_size: tokens::FONT_SM,
             ..Default::default()
         },
-        TextColor(tokens::TEXT_SECONDARY),
-        ChildOf(body_entity),
+        TextColor(tokens::TEXT_PRIMARY),
+        ChildOf(banner),
     ));
-}
 
-/// Binding that links a material text_edit to a source entity and material field mutator.
-#[derive(Component)]
-pub(super) struct MaterialFieldBinding {
-    pub(super) source_entity: Entity,
-    pub(super) apply_fn: fn(&mut StandardMaterial, f64),
+    let buttons = world
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(tokens::SPACING_XS),
+                ..Default::default()
+            },
+            ChildOf(banner),
+        ))
+        .id();
+    world
+        .spawn((button(ButtonProps::new("Make Unique")), ChildOf(buttons)))
+        .observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+            commands.queue(move |world: &mut World| make_material_unique(world, source_entity));
+        });
+    if !edit_shared {
+        world
+            .spawn((button(ButtonProps::new("Edit Shared")), ChildOf(buttons)))
+            .observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+                commands.queue(move |world: &mut World| {
+                    let Some(handle) = material_handle(world, source_entity) else {
+                        return;
+                    };
+                    let id = handle.id();
+                    world.resource_mut::<SharedMaterialEdits>().0.insert(id);
+                    rebuild_inspector(world, source_entity);
+                });
+            });
+    }
 }
 
-/// Handle TextEditCommitEvent for material field bindings.
-pub(super) fn on_material_text_commit(
-    event: On<TextEditCommitEvent>,
-    bindings: Query<&MaterialFieldBinding>,
-    child_of_query: Query<&ChildOf>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    mat_query: Query<&MeshMaterial3d<StandardMaterial>>,
-) {
-    let mut current = event.entity;
-    for _ in 0..4 {
-        let Ok(child_of) = child_of_query.get(current) else {
-            break;
-        };
-        if let Ok(binding) = bindings.get(child_of.parent()) {
-            let value: f64 = event.text.parse().unwrap_or(0.0);
-            let Ok(mat_comp) = mat_query.get(binding.source_entity) else {
-                return;
-            };
-            if let Some(material) = materials.get_mut(&mat_comp.0) {
-                (binding.apply_fn)(material, value);
-            }
-            return;
-        }
-        current = child_of.parent();
+/// Give the selected entities that share `source_entity`'s material their own copy of it.
+fn make_material_unique(world: &mut World, source_entity: Entity) {
+    let Some(handle) = material_handle(world, source_entity) else {
+        return;
+    };
+    let Some(material) = world
+        .resource::<Assets<StandardMaterial>>()
+        .get(&handle)
+        .cloned()
+    else {
+        return;
+    };
+
+    let mut targets: Vec<Entity> = world
+        .resource::<Selection>()
+        .entities
+        .iter()
+        .copied()
+        .filter(|&entity| material_handle(world, entity).as_ref() == Some(&handle))
+        .collect();
+    if !targets.contains(&source_entity) {
+        targets.push(source_entity);
     }
+
+    let unique = world
+        .resource_mut::<Assets<StandardMaterial>>()
+        .add(material);
+    material_browser::assign_material_to_meshes(world, &targets, unique);
+    rebuild_inspector(world, source_entity);
 }
 
-fn spawn_material_numeric_field(
-    world: &mut World,
-    parent: Entity,
-    label: &str,
-    value: f64,
-    source_entity: Entity,
-    apply_fn: fn(&mut StandardMaterial, f64),
-) {
+/// Row with a property label; the caller spawns the editing widget into it.
+fn spawn_field_row(world: &mut World, parent: Entity, label: &str) -> Entity {
     let row = world
         .spawn((
             Node {
@@ -243,18 +511,145 @@ fn spawn_material_numeric_field(
         },
         ChildOf(row),
     ));
+    row
+}
 
-    world.spawn((
-        text_edit::text_edit(
-            TextEditProps::default()
-                .numeric_f32()
-                .grow()
-                .with_default_value(value.to_string()),
-        ),
-        MaterialFieldBinding {
-            source_entity,
-            apply_fn,
-        },
-        ChildOf(row),
-    ));
+/// Handle TextEditCommitEvent for material field bindings.
+pub(super) fn on_material_text_commit(
+    event: On<TextEditCommitEvent>,
+    bindings: Query<&MaterialFieldBinding>,
+    child_of_query: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    let mut current = event.entity;
+    for _ in 0..4 {
+        let Ok(child_of) = child_of_query.get(current) else {
+            break;
+        };
+        if let Ok(binding) = bindings.get(child_of.parent()) {
+            let value: f32 = event.text.parse().unwrap_or(0.0);
+            let (source_entity, field) = (binding.source_entity, binding.field);
+            commands.queue(move |world: &mut World| {
+                edit_material(world, source_entity, |mat| field.set_number(mat, value));
+            });
+            return;
+        }
+        current = child_of.parent();
+    }
+}
+
+pub(super) fn on_material_color_commit(
+    event: On<ColorPickerCommitEvent>,
+    bindings: Query<&MaterialFieldBinding>,
+    mut commands: Commands,
+) {
+    let Ok(binding) = bindings.get(event.entity) else {
+        return;
+    };
+    let (source_entity, field, color) = (binding.source_entity, binding.field, event.color);
+    commands.queue(move |world: &mut World| {
+        edit_material(world, source_entity, |mat| field.set_color(mat, color));
+    });
+}
+
+pub(super) fn on_material_combobox_change(
+    event: On<ComboBoxChangeEvent>,
+    bindings: Query<&MaterialFieldBinding>,
+    mut commands: Commands,
+) {
+    let Ok(binding) = bindings.get(event.entity) else {
+        return;
+    };
+    let (source_entity, field, selected) = (binding.source_entity, binding.field, event.selected);
+    commands.queue(move |world: &mut World| {
+        edit_material(world, source_entity, |mat| field.set_option(mat, selected));
+    });
+}
+
+pub(super) fn on_material_checkbox_commit(
+    event: On<CheckboxCommitEvent>,
+    bindings: Query<&MaterialFieldBinding>,
+    mut commands: Commands,
+) {
+    let Ok(binding) = bindings.get(event.entity) else {
+        return;
+    };
+    let (source_entity, field, checked) = (binding.source_entity, binding.field, event.checked);
+    commands.queue(move |world: &mut World| {
+        edit_material(world, source_entity, |mat| field.set_flag(mat, checked));
+    });
+}
+
+/// Keep material widgets in step with the material asset (undo, texture slots
+/// that reset scalars, edits from the material browser).
+pub(super) fn refresh_material_fields(world: &mut World) {
+    let mut query = world.query::<(Entity, &MaterialFieldBinding)>();
+    let bindings: Vec<(Entity, Entity, MaterialField)> = query
+        .iter(world)
+        .map(|(entity, binding)| (entity, binding.source_entity, binding.field))
+        .collect();
+    if bindings.is_empty() {
+        return;
+    }
+
+    let input_focus = world.resource::<InputFocus>().0;
+    for (ui_entity, source_entity, field) in bindings {
+        let Some(material) = material_handle(world, source_entity)
+            .and_then(|handle| world.resource::<Assets<StandardMaterial>>().get(&handle))
+        else {
+            continue;
+        };
+        let number = field.number(material);
+        let color = field.color(material);
+        let option = field.option(material);
+        let flag = field.flag(material);
+
+        if let Some(value) = number {
+            let current: f32 = world
+                .get::<TextEditValue>(ui_entity)
+                .and_then(|text| text.0.parse().ok())
+                .unwrap_or(0.0);
+            // Skip if the field is being drag-adjusted or the user is typing in it
+            if (current - value).abs() > 0.0005
+                && let Some((wrapper_entity, inner_entity)) =
+                    find_text_edit_entities(world, ui_entity)
+                && world.get::<TextEditDragging>(wrapper_entity).is_none()
+                && input_focus != Some(inner_entity)
+            {
+                let formatted =
+                    text_edit::format_numeric_value(value as f64, TextEditVariant::NumericF32);
+                if let Some(mut queue) = world.get_mut::<TextInputQueue>(inner_entity) {
+                    set_text_input_value(&mut queue, formatted);
+                }
+            }
+        }
+
+        if let Some(rgba) = color
+            && world
+                .get::<MaterialColorSeen>(ui_entity)
+                .is_some_and(|seen| seen.0 != rgba)
+        {
+            world.entity_mut(ui_entity).insert(MaterialColorSeen(rgba));
+            if let Some(mut state) = world.get_mut::<ColorPickerState>(ui_entity) {
+                state.set_from_rgba(rgba);
+            }
+        }
+
+        if let Some(index) = option
+            && world
+                .get::<ComboBoxSelectedIndex>(ui_entity)
+                .is_some_and(|selected| selected.0 != index)
+        {
+            world
+                .entity_mut(ui_entity)
+                .insert(ComboBoxSelectedIndex(index));
+        }
+
+        if let Some(checked) = flag
+            && let Some(mut state) = world.get_mut::<CheckboxState>(ui_entity)
+            && state.checked != checked
+        {
+            state.checked = checked;
+        }
+    }
 }
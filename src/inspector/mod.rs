@@ -1,9 +1,22 @@
+mod asset_field;
 mod brush_display;
+mod compare;
 mod component_display;
 mod component_picker;
 mod custom_props_display;
+mod linked_display;
+mod lod_display;
 mod material_display;
+mod pinning;
 mod reflect_fields;
+mod section_layout;
+mod sub_scene_display;
+mod sun_display;
+mod transform_display;
+
+pub use compare::open_compare_window;
+pub use pinning::{InspectorPinned, inspector_panel, open_pinned_inspector};
+pub(crate) use reflect_fields::find_text_edit_entities;
 
 use crate::EditorEntity;
 use std::any::TypeId;
@@ -110,6 +123,8 @@ pub struct InspectorPlugin;
 impl Plugin for InspectorPlugin {
     fn build(&self, app: &mut App) {
         app.register_type_data::<Name, ReflectDisplayable>()
+            .insert_resource(section_layout::InspectorLayout::load())
+            .init_resource::<material_display::SharedMaterialEdits>()
             .add_observer(component_display::remove_component_displays)
             .add_observer(component_display::add_component_displays)
             .add_observer(component_display::on_inspector_dirty)
@@ -121,20 +136,50 @@ impl Plugin for InspectorPlugin {
             .add_observer(brush_display::handle_clear_texture)
             .add_observer(brush_display::handle_clear_material)
             .add_observer(brush_display::handle_apply_texture_to_all)
+            .add_observer(brush_display::handle_wrap_texture_to_adjacent)
             .add_observer(brush_display::handle_uv_scale_preset)
+            .add_observer(brush_display::handle_set_face_surface)
+            .add_observer(brush_display::handle_set_face_smoothing_group)
             .add_observer(brush_display::on_brush_face_text_commit)
             .add_observer(on_name_field_commit)
             .add_observer(material_display::on_material_text_commit)
+            .add_observer(material_display::on_material_color_commit)
+            .add_observer(material_display::on_material_combobox_change)
+            .add_observer(material_display::on_material_checkbox_commit)
+            .add_observer(sun_display::on_sun_field_commit)
+            .add_observer(lod_display::on_lod_level_commit)
             .add_systems(
                 Update,
                 (
-                    reflect_fields::refresh_inspector_fields,
+                    (
+                        reflect_fields::apply_dragged_field_values,
+                        reflect_fields::refresh_inspector_fields,
+                        sun_display::refresh_sun_fields,
+                        material_display::refresh_material_fields,
+                        component_display::rebuild_on_component_set_change,
+                        linked_display::refresh_linked_counts,
+                    ),
+                    transform_display::update_transform_space_display,
+                    custom_props_display::update_entity_ref_links,
+                    custom_props_display::resolve_entity_pick,
+                    custom_props_display::populate_schema_slots,
+                    custom_props_display::update_schema_feedback,
                     component_picker::filter_component_picker,
                     brush_display::update_brush_face_properties,
                     component_display::filter_inspector_components,
+                    pinning::unpin_despawned_targets,
+                    pinning::update_pin_headers,
+                    pinning::close_pinned_inspector_windows,
+                    section_layout::track_collapsed_sections,
+                    section_layout::save_inspector_layout,
+                    compare::close_compare_windows,
+                    compare::refresh_compare_windows,
+                    asset_field::poll_asset_picker_dialog,
+                    asset_field::refresh_asset_fields,
                 )
                     .run_if(in_state(crate::AppState::Editor)),
-            );
+            )
+            .add_systems(PostUpdate, reflect_fields::end_drag_merges);
     }
 }
 
@@ -143,7 +188,7 @@ fn on_name_field_commit(
     event: On<jackdaw_feathers::text_edit::TextEditCommitEvent>,
     name_inputs: Query<&NameFieldInput>,
     child_of_query: Query<&ChildOf>,
-    mut names: Query<&mut Name>,
+    mut commands: Commands,
 ) {
     // Walk up from the committed entity to find a NameFieldInput
     let mut current = event.entity;
@@ -163,9 +208,11 @@ fn on_name_field_commit(
         return;
     };
 
-    if let Ok(mut name) = names.get_mut(source_entity) {
-        *name = Name::new(event.text.clone());
-    }
+    // One undo entry per commit, not per keystroke
+    let text = event.text.clone();
+    commands.queue(move |world: &mut World| {
+        crate::hierarchy::rename_entities(world, &[source_entity], &text);
+    });
 }
 
 #[derive(Component)]
@@ -224,6 +271,11 @@ pub(super) struct FieldBinding {
     pub(super) field_path: String,
 }
 
+/// On a rotation field showing one Euler angle, in degrees, of the quaternion at the
+/// `FieldBinding` path.
+#[derive(Component)]
+pub(super) struct EulerAxisBinding(pub(super) usize);
+
 /// Container for brush face properties (texture, UV, etc). Populated dynamically.
 #[derive(Component)]
 pub(super) struct BrushFacePropsContainer;
@@ -249,6 +301,8 @@ pub(super) enum BrushFaceField {
 pub(super) struct CustomPropertyBinding {
     pub(super) source_entity: Entity,
     pub(super) property_name: String,
+    /// Item of a list property this field edits, if any.
+    pub(super) index: Option<usize>,
 }
 
 /// Marker for the "Add Property" row container.
@@ -0,0 +1,266 @@
+use crate::EditorEntity;
+use crate::layout::{SecondaryWindow, spawn_secondary_window};
+use crate::selection::Selection;
+
+use bevy::{
+    feathers::theme::ThemedText, prelude::*, ui_widgets::observe, window::WindowCloseRequested,
+};
+use bevy_monitors::prelude::{Monitor, NotifyAdded};
+use jackdaw_feathers::{icons::Icon, panel_header::PanelHeader, tokens};
+
+use super::{
+    AddComponentButton, ComponentDisplay, ComponentPicker, Inspector, InspectorDirty,
+    InspectorTarget,
+};
+
+const PINNED_WINDOW_SIZE: UVec2 = UVec2::new(360, 640);
+
+/// On an inspector locked to its `InspectorTarget`: selection changes leave it alone.
+#[derive(Component)]
+pub struct InspectorPinned;
+
+/// An inspector panel: header with title and pin button, over the inspector itself.
+#[derive(Component)]
+pub(super) struct InspectorPanel;
+
+#[derive(Component)]
+pub(super) struct InspectorPanelTitle;
+
+#[derive(Component)]
+pub(super) struct InspectorPinButton;
+
+/// OS window holding a second inspector, pinned when opened.
+#[derive(Component)]
+pub(super) struct PinnedInspectorWindow(SecondaryWindow);
+
+/// The inspector panel with a pin toggle in its header.
+pub fn inspector_panel(icon_font: Handle<Font>) -> impl Bundle {
+    (
+        InspectorPanel,
+        Node {
+            height: percent(100),
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        },
+        BackgroundColor(tokens::PANEL_BG),
+        children![
+            (
+                PanelHeader,
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::SpaceBetween,
+                    width: percent(100),
+                    height: px(tokens::ROW_HEIGHT),
+                    padding: UiRect::horizontal(px(tokens::SPACING_MD)),
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+                BackgroundColor(tokens::PANEL_HEADER_BG),
+                children![
+                    (
+                        InspectorPanelTitle,
+                        Text::new("Inspector"),
+                        TextFont {
+                            font_size: tokens::FONT_MD,
+                            ..Default::default()
+                        },
+                        ThemedText,
+                    ),
+                    (
+                        InspectorPinButton,
+                        Text::new(String::from(Icon::Pin.unicode())),
+                        TextFont {
+                            font: icon_font,
+                            font_size: tokens::FONT_MD,
+                            ..Default::default()
+                        },
+                        TextColor(tokens::TEXT_SECONDARY),
+                        observe(on_pin_button_click),
+                    ),
+                ],
+            ),
+            (
+                Inspector,
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: px(tokens::SPACING_SM),
+                    overflow: Overflow::scroll_y(),
+                    flex_grow: 1.0,
+                    min_height: px(0.0),
+                    padding: UiRect::all(px(tokens::SPACING_SM)),
+                    ..Default::default()
+                },
+            ),
+        ],
+    )
+}
+
+/// The inspector in the same panel as `entity`.
+fn panel_inspector(world: &World, entity: Entity) -> Option<Entity> {
+    let mut current = entity;
+    while world.get::<InspectorPanel>(current).is_none() {
+        current = world.get::<ChildOf>(current)?.parent();
+    }
+    world
+        .get::<Children>(current)?
+        .iter()
+        .find(|&child| world.get::<Inspector>(child).is_some())
+}
+
+fn on_pin_button_click(click: On<Pointer<Click>>, mut commands: Commands) {
+    let button = click.event_target();
+    commands.queue(move |world: &mut World| {
+        if let Some(inspector) = panel_inspector(world, button) {
+            toggle_pin(world, inspector);
+        }
+    });
+}
+
+/// Pin `inspector` to what it shows, or unpin it back to following the selection.
+pub(super) fn toggle_pin(world: &mut World, inspector: Entity) {
+    if world.get::<InspectorPinned>(inspector).is_some() {
+        world.entity_mut(inspector).remove::<InspectorPinned>();
+        show_primary_selection(world, inspector);
+    } else if world.get::<InspectorTarget>(inspector).is_some() {
+        world.entity_mut(inspector).insert(InspectorPinned);
+    }
+}
+
+/// Point `inspector` at the primary selection and rebuild it, or clear it without one.
+fn show_primary_selection(world: &mut World, inspector: Entity) {
+    match world.resource::<Selection>().primary() {
+        Some(primary) => show_entity(world, inspector, primary),
+        None => clear_inspector(world, inspector),
+    }
+}
+
+fn show_entity(world: &mut World, inspector: Entity, entity: Entity) {
+    world.entity_mut(inspector).insert((
+        InspectorTarget(entity),
+        Monitor(entity),
+        NotifyAdded::<InspectorDirty>::default(),
+    ));
+    // The dirty observer rebuilds every inspector monitoring the entity
+    world.entity_mut(entity).remove::<InspectorDirty>();
+    world.entity_mut(entity).insert(InspectorDirty);
+}
+
+fn clear_inspector(world: &mut World, inspector: Entity) {
+    world
+        .entity_mut(inspector)
+        .remove::<(InspectorTarget, Monitor, NotifyAdded<InspectorDirty>)>();
+    let children: Vec<Entity> = world
+        .get::<Children>(inspector)
+        .map(|children| children.to_vec())
+        .unwrap_or_default();
+    for child in children {
+        let entity = world.entity(child);
+        if entity.contains::<ComponentDisplay>()
+            || entity.contains::<AddComponentButton>()
+            || entity.contains::<ComponentPicker>()
+        {
+            world.entity_mut(child).despawn();
+        }
+    }
+}
+
+/// Open a second inspector in its own window, pinned to the primary selection.
+pub fn open_pinned_inspector(world: &mut World) {
+    let Some(primary) = world.resource::<Selection>().primary() else {
+        return;
+    };
+    let icon_font = world
+        .resource::<jackdaw_feathers::icons::IconFont>()
+        .0
+        .clone();
+    let secondary = spawn_secondary_window(world, "Inspector", PINNED_WINDOW_SIZE);
+    let panel = world
+        .spawn((inspector_panel(icon_font), ChildOf(secondary.root)))
+        .id();
+    world
+        .entity_mut(secondary.window)
+        .insert(PinnedInspectorWindow(secondary));
+    let Some(inspector) = panel_inspector(world, panel) else {
+        return;
+    };
+    world.entity_mut(inspector).insert(InspectorPinned);
+    show_entity(world, inspector, primary);
+}
+
+pub(super) fn close_pinned_inspector_windows(
+    mut commands: Commands,
+    mut requests: MessageReader<WindowCloseRequested>,
+    windows: Query<&PinnedInspectorWindow>,
+) {
+    for request in requests.read() {
+        let Ok(window) = windows.get(request.window) else {
+            continue;
+        };
+        let secondary = window.0;
+        commands.queue(move |world: &mut World| secondary.despawn(world));
+    }
+}
+
+/// Pinned inspectors whose entity was despawned go back to following the selection.
+pub(super) fn unpin_despawned_targets(
+    mut commands: Commands,
+    inspectors: Query<(Entity, &InspectorTarget), With<InspectorPinned>>,
+    entities: Query<(), Without<EditorEntity>>,
+) {
+    for (inspector, target) in &inspectors {
+        if entities.contains(target.0) {
+            continue;
+        }
+        commands.queue(move |world: &mut World| toggle_pin(world, inspector));
+    }
+}
+
+/// Show the pin state in each panel header: a highlighted pin and the pinned entity's name.
+pub(super) fn update_pin_headers(
+    inspectors: Query<(Entity, Option<&InspectorTarget>, Has<InspectorPinned>), With<Inspector>>,
+    parents: Query<&ChildOf>,
+    panels: Query<&Children, With<InspectorPanel>>,
+    headers: Query<&Children, With<PanelHeader>>,
+    mut titles: Query<&mut Text, With<InspectorPanelTitle>>,
+    mut pins: Query<&mut TextColor, With<InspectorPinButton>>,
+    names: Query<&Name>,
+) {
+    for (inspector, target, pinned) in &inspectors {
+        let Ok(panel) = parents.get(inspector).map(ChildOf::parent) else {
+            continue;
+        };
+        let Ok(panel_children) = panels.get(panel) else {
+            continue;
+        };
+        let title = match target.filter(|_| pinned) {
+            Some(target) => match names.get(target.0) {
+                Ok(name) => format!("Inspector: {name} (pinned)"),
+                Err(_) => "Inspector (pinned)".to_string(),
+            },
+            None => "Inspector".to_string(),
+        };
+        let pin_color = if pinned {
+            tokens::TEXT_ACCENT
+        } else {
+            tokens::TEXT_SECONDARY
+        };
+        for header in panel_children.iter() {
+            let Ok(header_children) = headers.get(header) else {
+                continue;
+            };
+            for child in header_children.iter() {
+                if let Ok(mut text) = titles.get_mut(child)
+                    && text.0 != title
+                {
+                    text.0.clone_from(&title);
+                }
+                if let Ok(mut color) = pins.get_mut(child)
+                    && color.0 != pin_color
+                {
+                    color.0 = pin_color;
+                }
+            }
+        }
+    }
+}
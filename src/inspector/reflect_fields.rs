@@ -12,17 +12,29 @@ use bevy::{
 };
 use jackdaw_feathers::{
     checkbox::{CheckboxCommitEvent, CheckboxProps, CheckboxState, checkbox},
-    color_picker::{ColorPickerCommitEvent, ColorPickerProps, color_picker},
+    color_picker::{
+        ColorPickerChangeEvent, ColorPickerCommitEvent, ColorPickerProps, color_picker,
+    },
     combobox::{ComboBoxChangeEvent, combobox_with_selected},
+    curve_editor::{
+        CurveEditorChangeEvent, CurveEditorCommitEvent, CurveEditorProps, curve_editor,
+    },
     list_view,
     text_edit::{
-        self, TextEditCommitEvent, TextEditConfig, TextEditDragging, TextEditProps, TextEditValue,
-        TextEditVariant, TextEditWrapper, TextInputQueue, set_text_input_value,
+        self, NumericFormat, NumericUnit, TextEditCommitEvent, TextEditConfig, TextEditDragging,
+        TextEditProps, TextEditValue, TextEditVariant, TextEditWrapper, TextInputQueue,
+        set_text_input_value,
     },
     tokens,
 };
+use jackdaw_jsn::FloatCurve;
 
-use super::{AXIS_X_COLOR, AXIS_Y_COLOR, AXIS_Z_COLOR, FieldBinding, MAX_REFLECT_DEPTH};
+use super::asset_field::{
+    AssetFieldBinding, AssetFieldTarget, AssetKind, asset_field, handle_label,
+};
+use super::{
+    AXIS_X_COLOR, AXIS_Y_COLOR, AXIS_Z_COLOR, EulerAxisBinding, FieldBinding, MAX_REFLECT_DEPTH,
+};
 
 pub(crate) fn spawn_reflected_fields(
     commands: &mut Commands,
@@ -298,6 +310,28 @@ fn spawn_field_row(
         return;
     }
 
+    // Image, mesh and material handles -> asset picker
+    if let Some(kind) = AssetKind::of(value) {
+        commands.spawn((
+            asset_field(
+                name,
+                handle_label(value),
+                AssetFieldBinding {
+                    source_entity,
+                    target: AssetFieldTarget::Field {
+                        component_type_id,
+                        field_path,
+                    },
+                    kind,
+                },
+                depth,
+                icon_font.clone(),
+            ),
+            ChildOf(parent),
+        ));
+        return;
+    }
+
     // List/Array -> expand with ListView
     if let ReflectRef::List(list) = value.reflect_ref() {
         spawn_text_row(
@@ -428,8 +462,25 @@ fn spawn_field_row(
         return;
     }
 
+    // Rotations as Euler angles in degrees
+    if let Some(quat) = value.try_downcast_ref::<Quat>() {
+        spawn_rotation_row(
+            commands,
+            parent,
+            name,
+            quat,
+            field_path,
+            source_entity,
+            component_type_id,
+            depth,
+        );
+        return;
+    }
+
     // Vec3 compact row with colored XYZ labels
     if let Some(vec3) = value.try_downcast_ref::<Vec3>() {
+        let unit = (component_type_id == TypeId::of::<Transform>() && field_path == "translation")
+            .then_some(NumericUnit::Meters);
         spawn_vec3_row(
             commands,
             parent,
@@ -439,6 +490,7 @@ fn spawn_field_row(
             source_entity,
             component_type_id,
             depth,
+            unit,
         );
         return;
     }
@@ -458,6 +510,21 @@ fn spawn_field_row(
         return;
     }
 
+    // Float curve with the curve editor
+    if let Some(curve) = value.try_downcast_ref::<FloatCurve>() {
+        spawn_curve_field(
+            commands,
+            parent,
+            name,
+            curve,
+            field_path,
+            source_entity,
+            component_type_id,
+            depth,
+        );
+        return;
+    }
+
     // Color field with picker
     if let Some(color) = value.try_downcast_ref::<Color>() {
         spawn_color_field(
@@ -786,7 +853,65 @@ fn spawn_vec3_row(
     source_entity: Entity,
     component_type_id: TypeId,
     depth: usize,
+    unit: Option<NumericUnit>,
+) {
+    let row = spawn_axis_row(commands, parent, name, depth);
+    for (axis, value, color) in [
+        ("x", vec3.x, AXIS_X_COLOR),
+        ("y", vec3.y, AXIS_Y_COLOR),
+        ("z", vec3.z, AXIS_Z_COLOR),
+    ] {
+        spawn_axis_input(
+            commands,
+            row,
+            &axis.to_uppercase(),
+            value as f64,
+            color,
+            format!("{field_path}.{axis}"),
+            source_entity,
+            component_type_id,
+            unit,
+        );
+    }
+}
+
+fn spawn_rotation_row(
+    commands: &mut Commands,
+    parent: Entity,
+    name: &str,
+    quat: &Quat,
+    field_path: String,
+    source_entity: Entity,
+    component_type_id: TypeId,
+    depth: usize,
 ) {
+    let row = spawn_axis_row(commands, parent, name, depth);
+    let (x, y, z) = quat.to_euler(EulerRot::XYZ);
+    for (index, (label, angle, color)) in [
+        ("X", x, AXIS_X_COLOR),
+        ("Y", y, AXIS_Y_COLOR),
+        ("Z", z, AXIS_Z_COLOR),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let input = spawn_axis_input(
+            commands,
+            row,
+            label,
+            angle.to_degrees() as f64,
+            color,
+            field_path.clone(),
+            source_entity,
+            component_type_id,
+            Some(NumericUnit::Degrees),
+        );
+        commands.entity(input).insert(EulerAxisBinding(index));
+    }
+}
+
+/// A row with a field label, for axis inputs to be added to.
+fn spawn_axis_row(commands: &mut Commands, parent: Entity, name: &str, depth: usize) -> Entity {
     let left_padding = depth as f32 * tokens::SPACING_MD;
     let row = commands
         .spawn((
@@ -817,37 +942,7 @@ fn spawn_vec3_row(
         ThemedText,
         ChildOf(row),
     ));
-
-    spawn_axis_input(
-        commands,
-        row,
-        "X",
-        vec3.x as f64,
-        AXIS_X_COLOR,
-        format!("{field_path}.x"),
-        source_entity,
-        component_type_id,
-    );
-    spawn_axis_input(
-        commands,
-        row,
-        "Y",
-        vec3.y as f64,
-        AXIS_Y_COLOR,
-        format!("{field_path}.y"),
-        source_entity,
-        component_type_id,
-    );
-    spawn_axis_input(
-        commands,
-        row,
-        "Z",
-        vec3.z as f64,
-        AXIS_Z_COLOR,
-        format!("{field_path}.z"),
-        source_entity,
-        component_type_id,
-    );
+    row
 }
 
 fn spawn_vec2_row(
@@ -899,6 +994,7 @@ fn spawn_vec2_row(
         format!("{field_path}.x"),
         source_entity,
         component_type_id,
+        None,
     );
     spawn_axis_input(
         commands,
@@ -909,6 +1005,7 @@ fn spawn_vec2_row(
         format!("{field_path}.y"),
         source_entity,
         component_type_id,
+        None,
     );
 }
 
@@ -921,7 +1018,8 @@ fn spawn_axis_input(
     field_path: String,
     source_entity: Entity,
     component_type_id: TypeId,
-) {
+    unit: Option<NumericUnit>,
+) -> Entity {
     // Axis label
     commands.spawn((
         Text::new(label),
@@ -938,20 +1036,24 @@ fn spawn_axis_input(
     ));
 
     // Numeric input
-    commands.spawn((
-        text_edit::text_edit(
-            TextEditProps::default()
-                .numeric_f32()
-                .grow()
-                .with_default_value(value.to_string()),
-        ),
-        FieldBinding {
-            source_entity,
-            component_type_id,
-            field_path,
-        },
-        ChildOf(parent),
-    ));
+    let mut props = TextEditProps::default()
+        .numeric_f32()
+        .grow()
+        .with_default_value(value.to_string());
+    if let Some(unit) = unit {
+        props = props.with_unit(unit);
+    }
+    commands
+        .spawn((
+            text_edit::text_edit(props),
+            FieldBinding {
+                source_entity,
+                component_type_id,
+                field_path,
+            },
+            ChildOf(parent),
+        ))
+        .id()
 }
 
 fn spawn_bool_toggle(
@@ -1064,12 +1166,98 @@ fn spawn_color_field(
             },
             ChildOf(row),
         ))
+        .observe({
+            let path = path.clone();
+            // Dragging in the picker applies live; the whole drag undoes as one step.
+            move |event: On<ColorPickerChangeEvent>, mut commands: Commands| {
+                let color = event.color;
+                let path = path.clone();
+                commands.queue(move |world: &mut World| {
+                    world.resource_mut::<CommandHistory>().begin_merge();
+                    apply_color_with_undo(world, source_entity, component_type_id, &path, color);
+                });
+            }
+        })
         .observe(
             move |event: On<ColorPickerCommitEvent>, mut commands: Commands| {
                 let color = event.color;
                 let path = path.clone();
                 commands.queue(move |world: &mut World| {
                     apply_color_with_undo(world, source_entity, component_type_id, &path, color);
+                    world.resource_mut::<CommandHistory>().end_merge();
+                });
+            },
+        );
+}
+
+fn spawn_curve_field(
+    commands: &mut Commands,
+    parent: Entity,
+    name: &str,
+    curve: &FloatCurve,
+    field_path: String,
+    source_entity: Entity,
+    component_type_id: TypeId,
+    depth: usize,
+) {
+    let left_padding = depth as f32 * tokens::SPACING_MD;
+    let column = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: px(tokens::SPACING_XS),
+                padding: UiRect::left(px(left_padding)),
+                width: percent(100),
+                ..Default::default()
+            },
+            ChildOf(parent),
+        ))
+        .id();
+
+    commands.spawn((
+        Text::new(format!("{name}:")),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        ThemedText,
+        ChildOf(column),
+    ));
+
+    let path = field_path.clone();
+    commands
+        .spawn((
+            curve_editor(CurveEditorProps::default().with_curve(curve.clone())),
+            FieldBinding {
+                source_entity,
+                component_type_id,
+                field_path,
+            },
+            ChildOf(column),
+        ))
+        .observe({
+            let path = path.clone();
+            // Dragging a key applies live; the whole drag undoes as one step.
+            move |event: On<CurveEditorChangeEvent>, mut commands: Commands| {
+                let curve = event.curve.clone();
+                let path = path.clone();
+                commands.queue(move |world: &mut World| {
+                    world.resource_mut::<CommandHistory>().begin_merge();
+                    apply_field_with_undo(world, source_entity, component_type_id, &path, |_| {
+                        Some(Box::new(curve.clone()))
+                    });
+                });
+            }
+        })
+        .observe(
+            move |event: On<CurveEditorCommitEvent>, mut commands: Commands| {
+                let curve = event.curve.clone();
+                let path = path.clone();
+                commands.queue(move |world: &mut World| {
+                    apply_field_with_undo(world, source_entity, component_type_id, &path, |_| {
+                        Some(Box::new(curve.clone()))
+                    });
+                    world.resource_mut::<CommandHistory>().end_merge();
                 });
             },
         );
@@ -1078,15 +1266,14 @@ fn spawn_color_field(
 /// Apply a color change with undo support (propagates to all selected entities).
 fn apply_color_with_undo(
     world: &mut World,
-    _entity: Entity,
+    entity: Entity,
     component_type_id: TypeId,
     field_path: &str,
     new_rgba: [f32; 4],
 ) {
     let registry = world.resource::<AppTypeRegistry>().clone();
 
-    let selection = world.resource::<Selection>();
-    let targets: Vec<Entity> = selection.entities.clone();
+    let targets = edit_targets(world, entity);
 
     let new_color = Color::srgba(new_rgba[0], new_rgba[1], new_rgba[2], new_rgba[3]);
 
@@ -1135,9 +1322,7 @@ fn apply_color_with_undo(
         })
     };
     cmd.execute(world);
-    let mut history = world.resource_mut::<CommandHistory>();
-    history.undo_stack.push(cmd);
-    history.redo_stack.clear();
+    world.resource_mut::<CommandHistory>().push_executed(cmd);
 }
 
 fn spawn_numeric_field(
@@ -1255,16 +1440,60 @@ fn spawn_editable_field(
 /// Propagates the edit to all selected entities that have the same component.
 fn apply_field_value_with_undo(
     world: &mut World,
-    _entity: Entity,
+    entity: Entity,
     component_type_id: TypeId,
     field_path: &str,
     new_value_str: &str,
 ) {
-    let registry = world.resource::<AppTypeRegistry>().clone();
+    apply_field_with_undo(world, entity, component_type_id, field_path, |field| {
+        let mut new_val = field.to_dynamic();
+        parse_into_reflect(&mut *new_val, new_value_str).then_some(new_val)
+    });
+}
+
+/// Set one Euler angle, in degrees, of a quaternion field, with undo, on all selected
+/// entities with the component.
+fn apply_euler_angle_with_undo(
+    world: &mut World,
+    entity: Entity,
+    component_type_id: TypeId,
+    field_path: &str,
+    axis: usize,
+    degrees: f32,
+) {
+    apply_field_with_undo(world, entity, component_type_id, field_path, |field| {
+        let quat = field.try_downcast_ref::<Quat>()?;
+        let (x, y, z) = quat.to_euler(EulerRot::XYZ);
+        let mut angles = [x, y, z];
+        *angles.get_mut(axis)? = degrees.to_radians();
+        let new_quat = Quat::from_euler(EulerRot::XYZ, angles[0], angles[1], angles[2]);
+        Some(new_quat.to_dynamic())
+    });
+}
 
-    // Collect all selected entities
+/// Entities an edit of `entity`'s fields applies to: the whole selection when `entity` is
+/// part of it, otherwise (in a pinned inspector) just `entity`.
+fn edit_targets(world: &World, entity: Entity) -> Vec<Entity> {
     let selection = world.resource::<Selection>();
-    let targets: Vec<Entity> = selection.entities.clone();
+    if selection.entities.contains(&entity) {
+        selection.entities.clone()
+    } else {
+        vec![entity]
+    }
+}
+
+/// Replace a field on every selected entity with the component by the value `new_value`
+/// derives from its current one, as a single undo entry.
+fn apply_field_with_undo(
+    world: &mut World,
+    entity: Entity,
+    component_type_id: TypeId,
+    field_path: &str,
+    new_value: impl Fn(&dyn PartialReflect) -> Option<Box<dyn PartialReflect>>,
+) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+
+    let targets = edit_targets(world, entity);
 
     let mut sub_commands: Vec<Box<dyn EditorCommand>> = Vec::new();
 
@@ -1287,11 +1516,9 @@ fn apply_field_value_with_undo(
             continue;
         };
         let old_value = field.to_dynamic();
-
-        let mut new_val = old_value.to_dynamic();
-        if !parse_into_reflect(&mut *new_val, new_value_str) {
+        let Some(new_val) = new_value(field) else {
             continue;
-        }
+        };
 
         sub_commands.push(Box::new(SetComponentField {
             entity: target,
@@ -1316,9 +1543,7 @@ fn apply_field_value_with_undo(
         })
     };
     cmd.execute(world);
-    let mut history = world.resource_mut::<CommandHistory>();
-    history.undo_stack.push(cmd);
-    history.redo_stack.clear();
+    world.resource_mut::<CommandHistory>().push_executed(cmd);
 }
 
 /// Parse a string value into a reflected value, returning true on success.
@@ -1609,7 +1834,11 @@ fn format_partial_reflect_value(value: &dyn PartialReflect) -> String {
 /// Handle TextEditCommitEvent for inspector field bindings (numeric and string fields).
 pub(crate) fn on_text_edit_commit(
     event: On<TextEditCommitEvent>,
-    bindings: Query<(&FieldBinding, Option<&TextEditVariant>)>,
+    bindings: Query<(
+        &FieldBinding,
+        Option<&TextEditVariant>,
+        Option<&EulerAxisBinding>,
+    )>,
     child_of_query: Query<&ChildOf>,
     mut commands: Commands,
 ) {
@@ -1620,22 +1849,41 @@ pub(crate) fn on_text_edit_commit(
         let Ok(child_of) = child_of_query.get(current) else {
             break;
         };
-        if let Ok((binding, variant)) = bindings.get(child_of.parent()) {
+        if let Ok((binding, variant, euler_axis)) = bindings.get(child_of.parent()) {
             found = Some((
                 binding.source_entity,
                 binding.component_type_id,
                 binding.field_path.clone(),
                 variant.copied(),
+                euler_axis.map(|axis| axis.0),
             ));
             break;
         }
         current = child_of.parent();
     }
 
-    let Some((source_entity, component_type_id, path, variant)) = found else {
+    let Some((source_entity, component_type_id, path, variant, euler_axis)) = found else {
         return;
     };
 
+    if let Some(axis) = euler_axis {
+        let Ok(degrees) = event.text.trim().parse::<f32>() else {
+            return;
+        };
+        commands.queue(move |world: &mut World| {
+            apply_euler_angle_with_undo(
+                world,
+                source_entity,
+                component_type_id,
+                &path,
+                axis,
+                degrees,
+            );
+            world.resource_mut::<CommandHistory>().end_merge();
+        });
+        return;
+    }
+
     // For numeric fields, use the text as-is (already formatted)
     // For string fields, use text directly
     let value_str = if variant.is_some_and(|v| v.is_numeric()) {
@@ -1648,9 +1896,72 @@ pub(crate) fn on_text_edit_commit(
 
     commands.queue(move |world: &mut World| {
         apply_field_value_with_undo(world, source_entity, component_type_id, &path, &value_str);
+        // Ends a drag-scrub started in `apply_dragged_field_values`.
+        world.resource_mut::<CommandHistory>().end_merge();
     });
 }
 
+/// Apply numeric drag-scrubs as they happen. Every step of one drag folds into a
+/// single undo entry, finalized by the commit on release.
+pub(crate) fn apply_dragged_field_values(
+    mut commands: Commands,
+    fields: Query<
+        (
+            &FieldBinding,
+            &TextEditValue,
+            &Children,
+            Option<&EulerAxisBinding>,
+        ),
+        Changed<TextEditValue>,
+    >,
+    dragging: Query<(), With<TextEditDragging>>,
+) {
+    for (binding, value, children, euler_axis) in &fields {
+        if !children.iter().any(|child| dragging.contains(child)) {
+            continue;
+        }
+        let Ok(val) = value.0.trim().parse::<f64>() else {
+            continue;
+        };
+        let source_entity = binding.source_entity;
+        let component_type_id = binding.component_type_id;
+        let path = binding.field_path.clone();
+        let value_str = format!("{val}");
+        let euler_axis = euler_axis.map(|axis| axis.0);
+        commands.queue(move |world: &mut World| {
+            world.resource_mut::<CommandHistory>().begin_merge();
+            match euler_axis {
+                Some(axis) => apply_euler_angle_with_undo(
+                    world,
+                    source_entity,
+                    component_type_id,
+                    &path,
+                    axis,
+                    val as f32,
+                ),
+                None => apply_field_value_with_undo(
+                    world,
+                    source_entity,
+                    component_type_id,
+                    &path,
+                    &value_str,
+                ),
+            }
+        });
+    }
+}
+
+/// Close any merge left open by a drag once the mouse is released. Commits queued on
+/// release have been applied by now, so they still fold into the drag's undo step.
+pub(crate) fn end_drag_merges(
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut history: ResMut<CommandHistory>,
+) {
+    if mouse.just_released(MouseButton::Left) {
+        history.end_merge();
+    }
+}
+
 pub(crate) fn on_checkbox_commit(
     event: On<CheckboxCommitEvent>,
     bindings: Query<&FieldBinding>,
@@ -1671,40 +1982,44 @@ pub(crate) fn on_checkbox_commit(
 /// Refreshes inspector field values using reflection -- handles all component types generically.
 /// Uses exclusive world access to avoid query conflicts.
 pub(crate) fn refresh_inspector_fields(world: &mut World) {
-    let selection = world.resource::<Selection>();
-    let Some(primary) = selection.primary() else {
-        return;
-    };
-
     let type_registry = world.resource::<AppTypeRegistry>().clone();
     let registry = type_registry.read();
 
-    // Collect numeric binding info: outer entity + current TextEditValue
-    let mut numeric_lookups: Vec<(Entity, TypeId, String, String)> = Vec::new();
-    let mut query = world.query::<(Entity, &FieldBinding, &TextEditValue, &TextEditConfig)>();
-    for (entity, binding, value, config) in query.iter(world) {
-        if binding.source_entity == primary && config.variant.is_numeric() {
+    // Collect numeric binding info: outer entity + current TextEditValue. Every inspector
+    // is refreshed, pinned ones showing something other than the selection included.
+    let mut numeric_lookups: Vec<(Entity, Entity, TypeId, String, String, Option<usize>)> =
+        Vec::new();
+    let mut query = world.query::<(
+        Entity,
+        &FieldBinding,
+        &TextEditValue,
+        &TextEditConfig,
+        Option<&EulerAxisBinding>,
+    )>();
+    for (entity, binding, value, config, euler_axis) in query.iter(world) {
+        if config.variant.is_numeric() {
             numeric_lookups.push((
                 entity,
+                binding.source_entity,
                 binding.component_type_id,
                 binding.field_path.clone(),
                 value.0.clone(),
+                euler_axis.map(|axis| axis.0),
             ));
         }
     }
 
     // Collect checkbox binding info and current state
-    let mut bool_lookups: Vec<(Entity, TypeId, String, bool)> = Vec::new();
+    let mut bool_lookups: Vec<(Entity, Entity, TypeId, String, bool)> = Vec::new();
     let mut checkbox_query = world.query::<(Entity, &FieldBinding, &CheckboxState)>();
     for (entity, binding, state) in checkbox_query.iter(world) {
-        if binding.source_entity == primary {
-            bool_lookups.push((
-                entity,
-                binding.component_type_id,
-                binding.field_path.clone(),
-                state.checked,
-            ));
-        }
+        bool_lookups.push((
+            entity,
+            binding.source_entity,
+            binding.component_type_id,
+            binding.field_path.clone(),
+            state.checked,
+        ));
     }
 
     if numeric_lookups.is_empty() && bool_lookups.is_empty() {
@@ -1715,11 +2030,12 @@ pub(crate) fn refresh_inspector_fields(world: &mut World) {
     // For numeric fields: we need to find inner EditorTextEdit entity and set its value
     let mut numeric_updates: Vec<(Entity, f64)> = Vec::new();
     let mut bool_updates: Vec<(Entity, bool)> = Vec::new();
-    let Ok(entity_ref) = world.get_entity(primary) else {
-        return;
-    };
 
-    for (ui_entity, comp_type_id, field_path, current_text) in &numeric_lookups {
+    for (ui_entity, source, comp_type_id, field_path, current_text, euler_axis) in &numeric_lookups
+    {
+        let Ok(entity_ref) = world.get_entity(*source) else {
+            continue;
+        };
         let Some(registration) = registry.get(*comp_type_id) else {
             continue;
         };
@@ -1732,7 +2048,13 @@ pub(crate) fn refresh_inspector_fields(world: &mut World) {
         let Ok(field) = reflected.reflect_path(field_path.as_str()) else {
             continue;
         };
-        let value = reflect_field_to_f64(field);
+        let value = match euler_axis {
+            Some(axis) => field.try_downcast_ref::<Quat>().and_then(|quat| {
+                let (x, y, z) = quat.to_euler(EulerRot::XYZ);
+                [x, y, z].get(*axis).map(|angle| angle.to_degrees() as f64)
+            }),
+            None => reflect_field_to_f64(field),
+        };
         let Some(value) = value else {
             continue;
         };
@@ -1743,7 +2065,10 @@ pub(crate) fn refresh_inspector_fields(world: &mut World) {
         }
     }
 
-    for (ui_entity, comp_type_id, field_path, current_checked) in &bool_lookups {
+    for (ui_entity, source, comp_type_id, field_path, current_checked) in &bool_lookups {
+        let Ok(entity_ref) = world.get_entity(*source) else {
+            continue;
+        };
         let Some(registration) = registry.get(*comp_type_id) else {
             continue;
         };
@@ -1783,7 +2108,10 @@ pub(crate) fn refresh_inspector_fields(world: &mut World) {
         }
 
         if let Some(variant) = world.get::<TextEditVariant>(inner_entity).copied() {
-            let formatted = text_edit::format_numeric_value(value, variant);
+            let precision = world
+                .get::<NumericFormat>(inner_entity)
+                .map_or(text_edit::DEFAULT_PRECISION, |f| f.precision);
+            let formatted = text_edit::format_with_precision(value, variant, precision);
             if let Some(mut queue) = world.get_mut::<TextInputQueue>(inner_entity) {
                 set_text_input_value(&mut queue, formatted);
             }
@@ -1800,7 +2128,10 @@ pub(crate) fn refresh_inspector_fields(world: &mut World) {
 
 /// Walk from an outer text_edit entity to find the wrapper and inner EditorTextEdit entities.
 /// Returns (wrapper_entity, inner_entity).
-fn find_text_edit_entities(world: &World, outer_entity: Entity) -> Option<(Entity, Entity)> {
+pub(crate) fn find_text_edit_entities(
+    world: &World,
+    outer_entity: Entity,
+) -> Option<(Entity, Entity)> {
     let children = world.get::<Children>(outer_entity)?;
     for child in children.iter() {
         if let Some(wrapper) = world.get::<TextEditWrapper>(child) {
@@ -2045,15 +2376,14 @@ fn spawn_enum_field(
 /// Apply an enum variant change with undo support.
 fn apply_enum_variant_with_undo(
     world: &mut World,
-    _entity: Entity,
+    entity: Entity,
     component_type_id: TypeId,
     field_path: &str,
     variant_name: &str,
 ) {
     let registry = world.resource::<AppTypeRegistry>().clone();
 
-    let selection = world.resource::<Selection>();
-    let targets: Vec<Entity> = selection.entities.clone();
+    let targets = edit_targets(world, entity);
 
     let reg = registry.read();
     let Some(registration) = reg.get(component_type_id) else {
@@ -2112,9 +2442,7 @@ fn apply_enum_variant_with_undo(
         })
     };
     cmd.execute(world);
-    let mut history = world.resource_mut::<CommandHistory>();
-    history.undo_stack.push(cmd);
-    history.redo_stack.clear();
+    world.resource_mut::<CommandHistory>().push_executed(cmd);
 }
 
 /// Build the appropriate DynamicVariant for a given variant name,
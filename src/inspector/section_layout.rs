@@ -0,0 +1,151 @@
+use crate::display_settings::{InspectorSettings, read_editor_settings, save_editor_settings};
+
+use bevy::prelude::*;
+use jackdaw_feathers::tokens;
+use jackdaw_widgets::collapsible::{CollapsibleHeader, CollapsibleSection};
+
+use super::{InspectorDirty, InspectorTarget};
+
+/// Collapsed state and order of component sections, shared by every inspector and saved
+/// with the editor settings.
+#[derive(Resource, Default)]
+pub struct InspectorLayout(pub InspectorSettings);
+
+impl InspectorLayout {
+    pub fn load() -> Self {
+        Self(read_editor_settings().inspector)
+    }
+
+    /// Sort key among sections of the same group: dragged order first, then the rest.
+    pub(super) fn position(&self, key: &str) -> usize {
+        self.0
+            .order
+            .iter()
+            .position(|k| k == key)
+            .unwrap_or(usize::MAX)
+    }
+}
+
+/// Type path of the component a section displays.
+#[derive(Component)]
+pub(super) struct ComponentTypeKey(pub(super) String);
+
+pub(super) fn save_inspector_layout(layout: Res<InspectorLayout>) {
+    if !layout.is_changed() || layout.is_added() {
+        return;
+    }
+    let mut settings = read_editor_settings();
+    if settings.inspector == layout.0 {
+        return;
+    }
+    settings.inspector = layout.0.clone();
+    save_editor_settings(&settings);
+}
+
+/// Remember sections collapsed or expanded by hand or by collapse-all.
+pub(super) fn track_collapsed_sections(
+    sections: Query<(&ComponentTypeKey, &CollapsibleSection), Changed<CollapsibleSection>>,
+    mut layout: ResMut<InspectorLayout>,
+) {
+    for (key, section) in &sections {
+        if layout.0.collapsed.contains(&key.0) == section.collapsed {
+            continue;
+        }
+        if section.collapsed {
+            layout.0.collapsed.insert(key.0.clone());
+        } else {
+            layout.0.collapsed.remove(&key.0);
+        }
+    }
+}
+
+/// Section `entity` belongs to, walking up from a header or one of its children.
+fn section_of(
+    mut entity: Entity,
+    parents: &Query<&ChildOf>,
+    keys: &Query<&ComponentTypeKey>,
+) -> Option<Entity> {
+    while !keys.contains(entity) {
+        entity = parents.get(entity).ok()?.parent();
+    }
+    Some(entity)
+}
+
+/// Dropping a component header on another moves its section in front of that one, within
+/// the same group, and rebuilds the inspector in the new order.
+pub(super) fn on_header_drop(
+    mut drop: On<Pointer<DragDrop>>,
+    parents: Query<&ChildOf>,
+    keys: Query<&ComponentTypeKey>,
+    children: Query<&Children>,
+    targets: Query<&InspectorTarget>,
+    mut headers: Query<&mut BackgroundColor, With<CollapsibleHeader>>,
+    mut layout: ResMut<InspectorLayout>,
+    mut commands: Commands,
+) {
+    drop.propagate(false);
+    let header = drop.event_target();
+    if let Ok(mut bg) = headers.get_mut(header) {
+        bg.0 = tokens::PANEL_HEADER_BG;
+    }
+    let (Some(target), Some(dragged)) = (
+        section_of(header, &parents, &keys),
+        section_of(drop.dropped, &parents, &keys),
+    ) else {
+        return;
+    };
+    let Ok(body) = parents.get(target).map(ChildOf::parent) else {
+        return;
+    };
+    if target == dragged || parents.get(dragged).map(ChildOf::parent) != Ok(body) {
+        return;
+    }
+    let (Ok(target_key), Ok(dragged_key)) = (keys.get(target), keys.get(dragged)) else {
+        return;
+    };
+
+    let mut sequence: Vec<String> = children
+        .get(body)
+        .into_iter()
+        .flat_map(|c| c.iter())
+        .filter_map(|section| keys.get(section).ok())
+        .filter(|key| key.0 != dragged_key.0)
+        .map(|key| key.0.clone())
+        .collect();
+    let index = sequence
+        .iter()
+        .position(|key| *key == target_key.0)
+        .unwrap_or(sequence.len());
+    sequence.insert(index, dragged_key.0.clone());
+    layout.0.order.retain(|key| !sequence.contains(key));
+    layout.0.order.extend(sequence);
+
+    let mut current = body;
+    while let Ok(&ChildOf(parent)) = parents.get(current) {
+        if let Ok(target) = targets.get(parent) {
+            commands.entity(target.0).insert(InspectorDirty);
+            return;
+        }
+        current = parent;
+    }
+}
+
+pub(super) fn on_header_drag_enter(
+    mut enter: On<Pointer<DragEnter>>,
+    mut headers: Query<&mut BackgroundColor, With<CollapsibleHeader>>,
+) {
+    enter.propagate(false);
+    if let Ok(mut bg) = headers.get_mut(enter.event_target()) {
+        bg.0 = tokens::DROP_TARGET_BG;
+    }
+}
+
+pub(super) fn on_header_drag_leave(
+    mut leave: On<Pointer<DragLeave>>,
+    mut headers: Query<&mut BackgroundColor, With<CollapsibleHeader>>,
+) {
+    leave.propagate(false);
+    if let Ok(mut bg) = headers.get_mut(leave.event_target()) {
+        bg.0 = tokens::PANEL_HEADER_BG;
+    }
+}
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+use jackdaw_feathers::{
+    button::{ButtonProps, button},
+    tokens,
+};
+use jackdaw_jsn::SubScene;
+
+use crate::project::ProjectRoot;
+
+pub(super) fn spawn_sub_scene_display(
+    commands: &mut Commands,
+    parent: Entity,
+    source_entity: Entity,
+    sub_scene: &SubScene,
+) {
+    let label = if sub_scene.path.is_empty() {
+        "No scene referenced".to_string()
+    } else {
+        sub_scene.path.clone()
+    };
+    commands.spawn((
+        Text::new(label),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_SECONDARY),
+        ChildOf(parent),
+    ));
+
+    let row = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                column_gap: px(tokens::SPACING_XS),
+                width: Val::Percent(100.0),
+                ..Default::default()
+            },
+            ChildOf(parent),
+        ))
+        .id();
+
+    commands
+        .spawn((button(ButtonProps::new("Reload")), ChildOf(row)))
+        .observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+            commands.queue(move |world: &mut World| {
+                crate::sub_scene::reload_sub_scene(world, source_entity);
+            });
+        });
+
+    let path = sub_scene.path.clone();
+    commands
+        .spawn((button(ButtonProps::new("Open Source")), ChildOf(row)))
+        .observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+            let path = path.clone();
+            commands.queue(move |world: &mut World| {
+                let Some(assets_dir) = world.get_resource::<ProjectRoot>().map(|p| p.assets_dir())
+                else {
+                    return;
+                };
+                crate::scene_io::open_scene_file(world, &assets_dir.join(&path));
+            });
+        });
+}
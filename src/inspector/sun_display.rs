@@ -0,0 +1,272 @@
+use std::f32::consts::PI;
+
+use bevy::{input_focus::InputFocus, prelude::*};
+use jackdaw_feathers::{
+    button::{ButtonProps, button},
+    text_edit::{
+        self, TextEditCommitEvent, TextEditDragging, TextEditProps, TextEditValue, TextEditVariant,
+        TextInputQueue, set_text_input_value,
+    },
+    tokens,
+};
+
+use super::reflect_fields::find_text_edit_entities;
+use crate::commands::{CommandHistory, SetTransform};
+
+/// Highest the sun climbs in the time-of-day model, in degrees.
+const NOON_ELEVATION: f32 = 60.0;
+
+const TIME_PRESETS: [(&str, f32); 5] = [
+    ("Sunrise", 6.5),
+    ("Morning", 9.0),
+    ("Noon", 12.0),
+    ("Afternoon", 15.0),
+    ("Sunset", 17.5),
+];
+
+#[derive(Clone, Copy)]
+enum SunField {
+    /// Compass bearing of the sun in degrees: 0 is north (-Z), 90 is east (+X).
+    Azimuth,
+    /// Degrees above the horizon.
+    Elevation,
+    /// Hours, 0-24.
+    TimeOfDay,
+}
+
+#[derive(Component)]
+pub(super) struct SunFieldBinding {
+    source_entity: Entity,
+    field: SunField,
+}
+
+/// Azimuth and elevation, in degrees, of the sun a light with `rotation` shines from.
+fn sun_angles(rotation: Quat) -> (f32, f32) {
+    let to_sun = rotation * Vec3::Z;
+    let elevation = to_sun.y.clamp(-1.0, 1.0).asin().to_degrees();
+    let azimuth = to_sun.x.atan2(-to_sun.z).to_degrees().rem_euclid(360.0);
+    (azimuth, elevation)
+}
+
+/// Rotation of a light shining from the sun at `azimuth`/`elevation` degrees.
+fn sun_rotation(azimuth: f32, elevation: f32) -> Quat {
+    let (azimuth, elevation) = (
+        azimuth.to_radians(),
+        elevation.clamp(-90.0, 90.0).to_radians(),
+    );
+    let to_sun = Vec3::new(
+        azimuth.sin() * elevation.cos(),
+        elevation.sin(),
+        -azimuth.cos() * elevation.cos(),
+    );
+    Transform::default().looking_to(-to_sun, Vec3::Y).rotation
+}
+
+/// Sun angles at `hour`: rising in the east at 6, highest in the south at noon,
+/// setting in the west at 18, below the horizon overnight.
+fn time_of_day_angles(hour: f32) -> (f32, f32) {
+    let hour = hour.rem_euclid(24.0);
+    let azimuth = (90.0 + (hour - 6.0) * 15.0).rem_euclid(360.0);
+    let elevation = NOON_ELEVATION * (PI * (hour - 6.0) / 12.0).sin();
+    (azimuth, elevation)
+}
+
+/// Inverse of [`time_of_day_angles`] for the azimuth alone.
+fn hour_from_azimuth(azimuth: f32) -> f32 {
+    ((azimuth - 90.0) / 15.0 + 6.0).rem_euclid(24.0)
+}
+
+fn field_value(field: SunField, rotation: Quat) -> f32 {
+    let (azimuth, elevation) = sun_angles(rotation);
+    match field {
+        SunField::Azimuth => azimuth,
+        SunField::Elevation => elevation,
+        SunField::TimeOfDay => hour_from_azimuth(azimuth),
+    }
+}
+
+/// Sun position controls under `DirectionalLight`, editing the light's rotation.
+pub(super) fn spawn_sun_display(
+    commands: &mut Commands,
+    parent: Entity,
+    source_entity: Entity,
+    transform: &Transform,
+) {
+    commands.spawn((
+        Text::new("Sun Position"),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_PRIMARY),
+        Node {
+            margin: UiRect::top(px(tokens::SPACING_SM)),
+            ..Default::default()
+        },
+        ChildOf(parent),
+    ));
+
+    for (label, field) in [
+        ("Azimuth", SunField::Azimuth),
+        ("Elevation", SunField::Elevation),
+        ("Time of Day", SunField::TimeOfDay),
+    ] {
+        let row = commands
+            .spawn((
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: px(tokens::SPACING_XS),
+                    width: Val::Percent(100.0),
+                    ..Default::default()
+                },
+                ChildOf(parent),
+            ))
+            .id();
+        commands.spawn((
+            Text::new(label),
+            TextFont {
+                font_size: tokens::FONT_SM,
+                ..Default::default()
+            },
+            TextColor(tokens::TEXT_SECONDARY),
+            Node {
+                min_width: px(80.0),
+                flex_shrink: 0.0,
+                ..Default::default()
+            },
+            ChildOf(row),
+        ));
+        let value = field_value(field, transform.rotation);
+        let props = TextEditProps::default()
+            .numeric_f32()
+            .grow()
+            .with_default_value(value.to_string());
+        let props = match field {
+            SunField::Azimuth => props,
+            SunField::Elevation => props.with_min(-90.0).with_max(90.0),
+            SunField::TimeOfDay => props.with_min(0.0).with_max(24.0),
+        };
+        commands.spawn((
+            text_edit::text_edit(props),
+            SunFieldBinding {
+                source_entity,
+                field,
+            },
+            ChildOf(row),
+        ));
+    }
+
+    let presets = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                flex_wrap: FlexWrap::Wrap,
+                column_gap: px(tokens::SPACING_XS),
+                row_gap: px(tokens::SPACING_XS),
+                width: Val::Percent(100.0),
+                ..Default::default()
+            },
+            ChildOf(parent),
+        ))
+        .id();
+    for (label, hour) in TIME_PRESETS {
+        commands
+            .spawn((button(ButtonProps::new(label)), ChildOf(presets)))
+            .observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+                let (azimuth, elevation) = time_of_day_angles(hour);
+                commands.queue(move |world: &mut World| {
+                    set_sun_rotation(world, source_entity, sun_rotation(azimuth, elevation));
+                });
+            });
+    }
+}
+
+fn set_sun_rotation(world: &mut World, entity: Entity, rotation: Quat) {
+    let Some(&old_transform) = world.get::<Transform>(entity) else {
+        return;
+    };
+    let new_transform = old_transform.with_rotation(rotation);
+    world.resource_scope(|world, mut history: Mut<CommandHistory>| {
+        history.execute(
+            Box::new(SetTransform {
+                entity,
+                old_transform,
+                new_transform,
+            }),
+            world,
+        );
+    });
+}
+
+pub(super) fn on_sun_field_commit(
+    event: On<TextEditCommitEvent>,
+    bindings: Query<&SunFieldBinding>,
+    transforms: Query<&Transform>,
+    child_of_query: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    // Walk up from committed entity to find a field binding
+    let mut current = event.entity;
+    for _ in 0..4 {
+        let Ok(child_of) = child_of_query.get(current) else {
+            return;
+        };
+        current = child_of.parent();
+        let Ok(binding) = bindings.get(current) else {
+            continue;
+        };
+        let Ok(transform) = transforms.get(binding.source_entity) else {
+            return;
+        };
+        let value: f32 = event.text.parse().unwrap_or(0.0);
+        let (azimuth, elevation) = sun_angles(transform.rotation);
+        let (azimuth, elevation) = match binding.field {
+            SunField::Azimuth => (value, elevation),
+            SunField::Elevation => (azimuth, value),
+            SunField::TimeOfDay => time_of_day_angles(value),
+        };
+        let entity = binding.source_entity;
+        commands.queue(move |world: &mut World| {
+            set_sun_rotation(world, entity, sun_rotation(azimuth, elevation));
+        });
+        return;
+    }
+}
+
+/// Keep the sun fields in step with the light's rotation (gizmo drags, undo).
+pub(crate) fn refresh_sun_fields(world: &mut World) {
+    let mut updates: Vec<(Entity, f32)> = Vec::new();
+    let mut query = world.query::<(Entity, &SunFieldBinding, &TextEditValue)>();
+    for (entity, binding, value) in query.iter(world) {
+        let Some(transform) = world.get::<Transform>(binding.source_entity) else {
+            continue;
+        };
+        let target = field_value(binding.field, transform.rotation);
+        let current: f32 = value.0.parse().unwrap_or(0.0);
+        if (current - target).abs() > 0.05 {
+            updates.push((entity, target));
+        }
+    }
+    if updates.is_empty() {
+        return;
+    }
+
+    let input_focus = world.resource::<InputFocus>().0;
+    for (outer_entity, value) in updates {
+        let Some((wrapper_entity, inner_entity)) = find_text_edit_entities(world, outer_entity)
+        else {
+            continue;
+        };
+        // Skip if the field is being drag-adjusted or the user is typing in it
+        if world.get::<TextEditDragging>(wrapper_entity).is_some()
+            || input_focus == Some(inner_entity)
+        {
+            continue;
+        }
+        let formatted = text_edit::format_numeric_value(value as f64, TextEditVariant::NumericF32);
+        if let Some(mut queue) = world.get_mut::<TextInputQueue>(inner_entity) {
+            set_text_input_value(&mut queue, formatted);
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use bevy::{feathers::theme::ThemedText, prelude::*};
+use jackdaw_feathers::{
+    button::{ButtonProps, button},
+    text_edit::{self, NumericUnit, TextEditProps},
+    tokens,
+};
+
+use super::{AXIS_X_COLOR, AXIS_Y_COLOR, AXIS_Z_COLOR};
+use crate::transform_panel::{TransformField, TransformFieldBinding, TransformPanelState};
+
+/// One of the two field sets under `Transform`; only the one for the current space shows.
+#[derive(Component)]
+pub(super) struct TransformSpaceSection {
+    world_space: bool,
+}
+
+#[derive(Component)]
+pub(super) struct TransformSpaceButton;
+
+/// Space toggle and world-space fields under `Transform`. Returns the container for the
+/// parent-space (reflected) fields.
+pub(super) fn spawn_transform_display(
+    commands: &mut Commands,
+    parent: Entity,
+    source_entity: Entity,
+    global: Option<&GlobalTransform>,
+) -> Entity {
+    let toggle_row = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::SpaceBetween,
+                width: Val::Percent(100.0),
+                ..Default::default()
+            },
+            ChildOf(parent),
+        ))
+        .id();
+    commands.spawn((
+        Text::new("Space:"),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        ThemedText,
+        ChildOf(toggle_row),
+    ));
+    commands
+        .spawn((
+            TransformSpaceButton,
+            button(ButtonProps::new("Parent")),
+            ChildOf(toggle_row),
+        ))
+        .observe(
+            |_: On<Pointer<Click>>, mut state: ResMut<TransformPanelState>| {
+                state.world_space = !state.world_space;
+            },
+        );
+
+    let parent_section = commands
+        .spawn((
+            TransformSpaceSection { world_space: false },
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: px(tokens::SPACING_XS),
+                width: Val::Percent(100.0),
+                ..Default::default()
+            },
+            ChildOf(parent),
+        ))
+        .id();
+    let world_section = commands
+        .spawn((
+            TransformSpaceSection { world_space: true },
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: px(tokens::SPACING_XS),
+                width: Val::Percent(100.0),
+                display: Display::None,
+                ..Default::default()
+            },
+            ChildOf(parent),
+        ))
+        .id();
+
+    let world = global
+        .map(GlobalTransform::compute_transform)
+        .unwrap_or_default();
+    let (rx, ry, rz) = world.rotation.to_euler(EulerRot::XYZ);
+    let rows: [(_, fn(usize) -> TransformField, _, _); 3] = [
+        (
+            "translation",
+            TransformField::Location,
+            world.translation.to_array(),
+            Some(NumericUnit::Meters),
+        ),
+        (
+            "rotation",
+            TransformField::Rotation,
+            [rx.to_degrees(), ry.to_degrees(), rz.to_degrees()],
+            Some(NumericUnit::Degrees),
+        ),
+        ("scale", TransformField::Scale, world.scale.to_array(), None),
+    ];
+    for (name, field, values, unit) in rows {
+        let row = commands
+            .spawn((
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: px(tokens::SPACING_XS),
+                    width: Val::Percent(100.0),
+                    ..Default::default()
+                },
+                ChildOf(world_section),
+            ))
+            .id();
+        commands.spawn((
+            Text::new(format!("{name}:")),
+            TextFont {
+                font_size: tokens::FONT_SM,
+                ..Default::default()
+            },
+            Node {
+                min_width: px(20.0),
+                flex_shrink: 0.0,
+                ..Default::default()
+            },
+            ThemedText,
+            ChildOf(row),
+        ));
+        for (axis, (label, color)) in [
+            ("X", AXIS_X_COLOR),
+            ("Y", AXIS_Y_COLOR),
+            ("Z", AXIS_Z_COLOR),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            commands.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                TextColor(color),
+                Node {
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+                ChildOf(row),
+            ));
+            let mut props = TextEditProps::default()
+                .numeric_f32()
+                .grow()
+                .with_default_value(values[axis].to_string());
+            if let Some(unit) = unit {
+                props = props.with_unit(unit);
+            }
+            commands.spawn((
+                text_edit::text_edit(props),
+                TransformFieldBinding {
+                    entity: source_entity,
+                    field: field(axis),
+                    world_space: true,
+                },
+                ChildOf(row),
+            ));
+        }
+    }
+
+    parent_section
+}
+
+/// Show the field set for the current space and name it on the toggle.
+pub(crate) fn update_transform_space_display(
+    state: Res<TransformPanelState>,
+    mut sections: Query<(&TransformSpaceSection, &mut Node)>,
+    buttons: Query<&Children, With<TransformSpaceButton>>,
+    mut texts: Query<&mut Text>,
+) {
+    let display_for = |world_space: bool| {
+        if world_space == state.world_space {
+            Display::Flex
+        } else {
+            Display::None
+        }
+    };
+    for (section, mut node) in &mut sections {
+        let display = display_for(section.world_space);
+        if node.display != display {
+            node.display = display;
+        }
+    }
+    let label = if state.world_space { "World" } else { "Parent" };
+    for children in &buttons {
+        let mut iter = texts.iter_many_mut(children);
+        while let Some(mut text) = iter.fetch_next() {
+            if text.0 != label {
+                text.0 = label.to_string();
+            }
+        }
+    }
+}
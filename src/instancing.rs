@@ -0,0 +1,342 @@
+use std::any::TypeId;
+
+use bevy::prelude::*;
+use jackdaw_jsn::{GltfSource, InstanceBatch, InstanceGroup};
+
+use crate::{
+    EditorEntity, EditorHidden, NonSerializable,
+    commands::{
+        CommandGroup, CommandHistory, DespawnEntity, EditorCommand, SetComponentField,
+        SpawnSnapshot,
+    },
+    selection::{Selected, Selection},
+};
+
+/// Array duplicates of a mesh or glTF prop with more than this many copies (the source
+/// included) become one instance group, see [`instance_array`].
+pub const AUTO_INSTANCE_THRESHOLD: usize = 32;
+
+pub struct InstancingPlugin;
+
+impl Plugin for InstancingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PickedInstance>()
+            .add_observer(on_instance_batch_added);
+    }
+}
+
+/// The individual instance under the cursor at the last viewport click, as
+/// `(container, index)`. Lets "De-instance" extract just the clicked copy.
+#[derive(Resource, Default)]
+pub struct PickedInstance(pub Option<(Entity, usize)>);
+
+/// Instance batches are rebuilt from their container, so keep them out of the
+/// hierarchy and out of saved scenes.
+fn on_instance_batch_added(trigger: On<Add, InstanceBatch>, mut commands: Commands) {
+    if let Ok(mut ec) = commands.get_entity(trigger.event_target()) {
+        ec.insert((EditorHidden, NonSerializable));
+    }
+}
+
+/// What a prop renders with; entities can only share a container if this matches.
+#[derive(Clone, PartialEq)]
+enum InstanceSource {
+    Mesh {
+        mesh: Handle<Mesh>,
+        material: Handle<StandardMaterial>,
+    },
+    Gltf(String, usize),
+}
+
+fn instance_source(world: &World, entity: Entity) -> Option<InstanceSource> {
+    if let Some(gltf) = world.get::<GltfSource>(entity) {
+        return Some(InstanceSource::Gltf(gltf.path.clone(), gltf.scene_index));
+    }
+    let mesh = world.get::<Mesh3d>(entity)?;
+    let material = world.get::<MeshMaterial3d<StandardMaterial>>(entity)?;
+    Some(InstanceSource::Mesh {
+        mesh: mesh.0.clone(),
+        material: material.0.clone(),
+    })
+}
+
+/// Strip a trailing numeric suffix ("Rock 12" -> "Rock").
+fn base_name(name: &str) -> &str {
+    match name.rfind(' ') {
+        Some(pos) if name[pos + 1..].parse::<u32>().is_ok() => &name[..pos],
+        _ => name,
+    }
+}
+
+/// Collapse the selected entities into a single [`InstanceGroup`] container.
+pub fn instance_selected(world: &mut World) {
+    let entities = world.resource::<Selection>().entities.clone();
+    instance_entities(world, &entities);
+}
+
+/// Replace `entities` (all sharing the same mesh/material or glTF scene) with one
+/// instanced container. Undoable as a single step. Returns the container.
+pub fn instance_entities(world: &mut World, entities: &[Entity]) -> Option<Entity> {
+    let entities: Vec<Entity> = entities
+        .iter()
+        .copied()
+        .filter(|&e| {
+            world.get_entity(e).is_ok()
+                && world.get::<EditorEntity>(e).is_none()
+                && world.get::<InstanceGroup>(e).is_none()
+        })
+        .collect();
+    if entities.len() < 2 {
+        warn!("Instancing needs at least two entities");
+        return None;
+    }
+
+    let Some(source) = instance_source(world, entities[0]) else {
+        warn!("Instancing only supports mesh and glTF entities");
+        return None;
+    };
+    if entities
+        .iter()
+        .any(|&e| instance_source(world, e).as_ref() != Some(&source))
+    {
+        warn!("Instancing requires every entity to share the same mesh and material");
+        return None;
+    }
+
+    let globals: Vec<GlobalTransform> = entities
+        .iter()
+        .map(|&e| world.get::<GlobalTransform>(e).copied().unwrap_or_default())
+        .collect();
+    Some(replace_with_instances(world, &entities, source, globals))
+}
+
+/// Replace `source` and copies of it at `transforms` (in its parent's space) with one
+/// instanced container, without spawning the copies first. Undoable as a single step.
+/// Returns `None`, changing nothing, if `source` can't be instanced.
+pub fn instance_array(
+    world: &mut World,
+    source: Entity,
+    transforms: &[Transform],
+) -> Option<Entity> {
+    if world.get::<EditorEntity>(source).is_some() || world.get::<InstanceGroup>(source).is_some() {
+        return None;
+    }
+    let kind = instance_source(world, source)?;
+    let parent_global = world
+        .get::<ChildOf>(source)
+        .and_then(|c| world.get::<GlobalTransform>(c.parent()).copied())
+        .unwrap_or_default();
+    let globals: Vec<GlobalTransform> = std::iter::once(
+        world
+            .get::<GlobalTransform>(source)
+            .copied()
+            .unwrap_or_default(),
+    )
+    .chain(transforms.iter().map(|t| parent_global.mul_transform(*t)))
+    .collect();
+    Some(replace_with_instances(world, &[source], kind, globals))
+}
+
+/// Despawn `entities` and spawn a container of `source` instances at `globals`, all as
+/// one undo step. Returns the container, which ends up selected.
+fn replace_with_instances(
+    world: &mut World,
+    entities: &[Entity],
+    source: InstanceSource,
+    globals: Vec<GlobalTransform>,
+) -> Entity {
+    let centroid = globals.iter().map(|g| g.translation()).sum::<Vec3>() / globals.len() as f32;
+    let container_global = GlobalTransform::from(Transform::from_translation(centroid));
+    let instances: Vec<Transform> = globals
+        .iter()
+        .map(|g| g.reparented_to(&container_global))
+        .collect();
+
+    let name = world
+        .get::<Name>(entities[0])
+        .map(|n| base_name(n.as_str()).to_string())
+        .unwrap_or_else(|| "Prop".to_string());
+
+    let mut cmds: Vec<Box<dyn EditorCommand>> = Vec::new();
+    for &entity in entities {
+        cmds.push(Box::new(DespawnEntity::from_world(world, entity)));
+    }
+
+    // Deselect before despawning so `On<Remove, Selected>` observers see live entities.
+    for &entity in entities {
+        if let Ok(mut ec) = world.get_entity_mut(entity) {
+            ec.remove::<Selected>();
+        }
+    }
+    world.resource_mut::<Selection>().entities.clear();
+    for cmd in &cmds {
+        cmd.execute(world);
+    }
+
+    let (mesh, material, gltf) = match source {
+        InstanceSource::Mesh { mesh, material } => (mesh, material, None),
+        InstanceSource::Gltf(path, scene_index) => (
+            Handle::default(),
+            Handle::default(),
+            Some(GltfSource { path, scene_index }),
+        ),
+    };
+    let count = instances.len();
+    let container = world
+        .spawn((
+            Name::new(format!("{name} Instances")),
+            InstanceGroup {
+                mesh,
+                material,
+                gltf,
+                instances,
+            },
+            Transform::from_translation(centroid),
+            Visibility::default(),
+        ))
+        .id();
+    cmds.push(Box::new(SpawnSnapshot::from_world(
+        world,
+        &[container],
+        "Spawn instance group",
+    )));
+
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(CommandGroup {
+        commands: cmds,
+        label: format!("Instance {count} entities"),
+    }));
+
+    world.resource_mut::<Selection>().entities = vec![container];
+    world.entity_mut(container).insert(Selected);
+    container
+}
+
+/// Turn instances back into ordinary entities. If the last viewport click hit a
+/// specific instance of a selected container, only that copy is extracted;
+/// otherwise every instance of each selected container is.
+pub fn deinstance_selected(world: &mut World) {
+    let selected = world.resource::<Selection>().entities.clone();
+    let picked = world.resource::<PickedInstance>().0;
+
+    let mut cmds: Vec<Box<dyn EditorCommand>> = Vec::new();
+    let mut new_entities = Vec::new();
+
+    for container in selected {
+        let Some(group) = world.get::<InstanceGroup>(container).cloned() else {
+            continue;
+        };
+        let indices: Vec<usize> = match picked {
+            Some((picked_container, index))
+                if picked_container == container && index < group.instances.len() =>
+            {
+                vec![index]
+            }
+            _ => (0..group.instances.len()).collect(),
+        };
+
+        let container_transform = world
+            .get::<Transform>(container)
+            .copied()
+            .unwrap_or_default();
+        let parent = world.get::<ChildOf>(container).map(|c| c.0);
+        let name = world
+            .get::<Name>(container)
+            .map(|n| {
+                n.as_str()
+                    .strip_suffix(" Instances")
+                    .unwrap_or(n.as_str())
+                    .to_string()
+            })
+            .unwrap_or_else(|| "Prop".to_string());
+
+        let mut remaining = group.clone();
+        remaining.instances = group
+            .instances
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !indices.contains(i))
+            .map(|(_, t)| *t)
+            .collect();
+
+        if remaining.instances.is_empty() {
+            let cmd = DespawnEntity::from_world(world, container);
+            world.entity_mut(container).remove::<Selected>();
+            cmd.execute(world);
+            cmds.push(Box::new(cmd));
+        } else {
+            let cmd = SetComponentField {
+                entity: container,
+                component_type_id: TypeId::of::<InstanceGroup>(),
+                field_path: String::new(),
+                old_value: Box::new(group.clone()),
+                new_value: Box::new(remaining),
+            };
+            cmd.execute(world);
+            cmds.push(Box::new(cmd));
+        }
+
+        for (n, &index) in indices.iter().enumerate() {
+            let transform = container_transform.mul_transform(group.instances[index]);
+            let mut ec = world.spawn((
+                Name::new(format!("{name} {}", n + 1)),
+                transform,
+                Visibility::default(),
+            ));
+            match &group.gltf {
+                Some(gltf) => {
+                    ec.insert(gltf.clone());
+                }
+                None => {
+                    ec.insert((
+                        Mesh3d(group.mesh.clone()),
+                        MeshMaterial3d(group.material.clone()),
+                    ));
+                }
+            }
+            if let Some(parent) = parent {
+                ec.insert(ChildOf(parent));
+            }
+            new_entities.push(ec.id());
+        }
+    }
+
+    if new_entities.is_empty() {
+        return;
+    }
+
+    // glTF entities need their SceneRoot, same as after a scene load.
+    for &entity in &new_entities {
+        let Some(gltf) = world.get::<GltfSource>(entity).cloned() else {
+            continue;
+        };
+        let scene = world
+            .resource::<AssetServer>()
+            .load(GltfAssetLabel::Scene(gltf.scene_index).from_asset(gltf.path));
+        world.entity_mut(entity).insert(SceneRoot(scene));
+    }
+
+    cmds.push(Box::new(SpawnSnapshot::from_world(
+        world,
+        &new_entities,
+        "Spawn de-instanced entities",
+    )));
+    world.resource_mut::<PickedInstance>().0 = None;
+
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(CommandGroup {
+        commands: cmds,
+        label: "De-instance".to_string(),
+    }));
+
+    let previous = std::mem::take(&mut world.resource_mut::<Selection>().entities);
+    for e in previous {
+        if let Ok(mut ec) = world.get_entity_mut(e) {
+            ec.remove::<Selected>();
+        }
+    }
+    for &entity in &new_entities {
+        world.entity_mut(entity).insert(Selected);
+    }
+    world.resource_mut::<Selection>().entities = new_entities;
+}
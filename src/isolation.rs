@@ -0,0 +1,178 @@
+use bevy::{input_focus::InputFocus, platform::collections::HashSet, prelude::*};
+
+use crate::{
+    brush::EditMode,
+    draw_brush::DrawBrushState,
+    modal_transform::ModalTransformState,
+    scene_io::{collect_editor_entities, collect_scene_entities_from_set},
+    selection::Selection,
+};
+
+pub struct IsolationPlugin;
+
+impl Plugin for IsolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<IsolationState>().add_systems(
+            Update,
+            (toggle_isolation_key, apply_isolation)
+                .chain()
+                .run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+/// Local view: everything outside the isolated entities is temporarily hidden.
+#[derive(Resource, Default)]
+pub struct IsolationState {
+    /// Entities the view is isolated to. Empty when isolation is off.
+    pub isolated: Vec<Entity>,
+    /// The hidden set needs (re)computing, e.g. after a save restored it.
+    pending: bool,
+}
+
+impl IsolationState {
+    pub fn is_active(&self) -> bool {
+        !self.isolated.is_empty()
+    }
+}
+
+/// Placed on entities hidden by isolation, holding the visibility to restore on exit.
+#[derive(Component)]
+pub struct IsolationHidden {
+    pub original: Visibility,
+}
+
+fn toggle_isolation_key(world: &mut World) {
+    if world.resource::<InputFocus>().0.is_some()
+        || world.resource::<ModalTransformState>().active.is_some()
+        || world.resource::<DrawBrushState>().active.is_some()
+        || !matches!(*world.resource::<EditMode>(), EditMode::Object)
+    {
+        return;
+    }
+
+    let keyboard = world.resource::<ButtonInput<KeyCode>>();
+    let modifier = keyboard.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::ShiftLeft,
+        KeyCode::ShiftRight,
+        KeyCode::AltLeft,
+        KeyCode::AltRight,
+    ]);
+    if modifier || !keyboard.just_pressed(KeyCode::Slash) {
+        return;
+    }
+
+    if world.resource::<IsolationState>().is_active() {
+        exit_isolation(world);
+        return;
+    }
+
+    let selected = world.resource::<Selection>().entities.clone();
+    if selected.is_empty() {
+        return;
+    }
+    let mut state = world.resource_mut::<IsolationState>();
+    state.isolated = selected;
+    state.pending = true;
+}
+
+/// Hide the scene outside the isolated entities once the hidden set is stale.
+fn apply_isolation(world: &mut World) {
+    let state = world.resource::<IsolationState>();
+    if !state.pending {
+        // Scene cleared or the isolated entities deleted: nothing left to isolate.
+        if state.is_active() && state.isolated.iter().all(|&e| world.get_entity(e).is_err()) {
+            exit_isolation(world);
+        }
+        return;
+    }
+    let isolated: Vec<Entity> = state
+        .isolated
+        .iter()
+        .copied()
+        .filter(|&e| world.get_entity(e).is_ok())
+        .collect();
+    let mut state = world.resource_mut::<IsolationState>();
+    state.pending = false;
+    if isolated.is_empty() {
+        state.isolated.clear();
+        return;
+    }
+    state.isolated = isolated.clone();
+
+    // Kept visible: the isolated entities, their descendants, and their ancestors
+    // (hiding an ancestor would hide the isolated entity through inheritance).
+    let mut keep = HashSet::new();
+    let mut stack = isolated.clone();
+    while let Some(entity) = stack.pop() {
+        if !keep.insert(entity) {
+            continue;
+        }
+        if let Some(children) = world.get::<Children>(entity) {
+            stack.extend(children.iter());
+        }
+    }
+    for &entity in &isolated {
+        let mut current = entity;
+        while let Some(child_of) = world.get::<ChildOf>(current) {
+            current = child_of.parent();
+            keep.insert(current);
+        }
+    }
+
+    // Hide the topmost scene entities outside the kept set; their subtrees follow.
+    let editor_set = collect_editor_entities(world);
+    let scene: HashSet<Entity> = collect_scene_entities_from_set(world, &editor_set)
+        .into_iter()
+        .collect();
+    let to_hide: Vec<(Entity, Visibility)> = scene
+        .iter()
+        .filter(|e| !keep.contains(*e))
+        .filter(|&&e| {
+            world
+                .get::<ChildOf>(e)
+                .is_none_or(|c| keep.contains(&c.parent()) || !scene.contains(&c.parent()))
+        })
+        .filter(|&&e| world.get::<IsolationHidden>(e).is_none())
+        .filter_map(|&e| world.get::<Visibility>(e).map(|v| (e, *v)))
+        .collect();
+
+    for (entity, original) in to_hide {
+        world
+            .entity_mut(entity)
+            .insert((IsolationHidden { original }, Visibility::Hidden));
+    }
+}
+
+/// Leave isolation, restoring every visibility it changed.
+pub fn exit_isolation(world: &mut World) {
+    restore_isolated_visibility(world);
+    let mut state = world.resource_mut::<IsolationState>();
+    state.isolated.clear();
+    state.pending = false;
+}
+
+/// Put back the visibilities isolation overrode, without leaving isolation.
+/// Called before serializing so saved scenes keep their authored visibility;
+/// the override is reapplied on the next frame.
+pub fn restore_isolated_visibility(world: &mut World) {
+    let hidden: Vec<(Entity, Visibility, bool)> = world
+        .query::<(Entity, &IsolationHidden, Option<&Visibility>)>()
+        .iter(world)
+        .map(|(e, h, current)| (e, h.original, current == Some(&Visibility::Hidden)))
+        .collect();
+    for (entity, original, untouched) in hidden {
+        let mut ec = world.entity_mut(entity);
+        ec.remove::<IsolationHidden>();
+        // Visibility changed by hand while isolated wins over the stored value.
+        if untouched {
+            ec.insert(original);
+        }
+    }
+    let mut state = world.resource_mut::<IsolationState>();
+    if state.is_active() {
+        state.pending = true;
+    }
+}
@@ -1,12 +1,15 @@
 use bevy::{
+    camera::RenderTarget,
     feathers::{
         theme::{ThemeBackgroundColor, ThemedText},
         tokens as bevy_tokens,
     },
     prelude::*,
     ui_widgets::observe,
+    window::{WindowRef, WindowResolution},
 };
 use jackdaw_feathers::{
+    combobox::{ComboBoxChangeEvent, ComboBoxSelectedIndex, combobox_with_selected},
     icons::{Icon, IconFont},
     menu_bar, panel_header, popover, separator, split_panel, status_bar,
     text_edit::{self, TextEditProps},
@@ -17,13 +20,19 @@ use jackdaw_feathers::{
 use crate::{
     EditorEntity, asset_browser,
     brush::{BrushEditMode, BrushSelection, EditMode},
+    docking::{DockArea, DockPanel, DockRoot},
     draw_brush::DrawBrushState,
     gizmos::{GizmoMode, GizmoSpace},
-    hierarchy::{HierarchyPanel, HierarchyShowAllButton, HierarchyTreeContainer},
-    inspector::Inspector,
+    hierarchy::{
+        HierarchyCollapseChainsButton, HierarchyOptionsButton, HierarchyPanel,
+        HierarchyShowAllButton, HierarchyTreeContainer,
+    },
     material_browser,
     selection::Selection,
+    texture_browser,
+    view_modes::{ViewMode, ViewModeSettings},
     viewport::SceneViewport,
+    world_settings,
 };
 
 /// Marker on the hierarchy filter text input
@@ -53,6 +62,10 @@ pub enum EditToolButton {
     Clip,
 }
 
+/// Marker for the viewport shading mode dropdown
+#[derive(Component)]
+pub struct ViewModeDropdown;
+
 /// Marker for keybind helper button
 #[derive(Component)]
 pub struct KeybindHelpButton;
@@ -63,10 +76,67 @@ pub struct KeybindHelpPopover {
     pub entity: Option<Entity>,
 }
 
+/// An OS window besides the primary one, with its own UI camera so panels can be
+/// parented under `root`.
+#[derive(Clone, Copy)]
+pub struct SecondaryWindow {
+    pub window: Entity,
+    pub camera: Entity,
+    pub root: Entity,
+}
+
+impl SecondaryWindow {
+    pub fn despawn(self, world: &mut World) {
+        for entity in [self.root, self.camera, self.window] {
+            if let Ok(entity) = world.get_entity_mut(entity) {
+                entity.despawn();
+            }
+        }
+    }
+}
+
+/// Open a secondary window. UI nodes are routed to it through `UiTargetCamera` on the
+/// root; everything else in the editor keeps targeting the primary window.
+pub fn spawn_secondary_window(world: &mut World, title: &str, size: UVec2) -> SecondaryWindow {
+    let window = world
+        .spawn(Window {
+            title: format!("{title} - Jackdaw"),
+            resolution: WindowResolution::new(size.x, size.y),
+            ..default()
+        })
+        .id();
+    let camera = world
+        .spawn((
+            Camera2d,
+            EditorEntity,
+            RenderTarget::Window(WindowRef::Entity(window)),
+        ))
+        .id();
+    let root = world
+        .spawn((
+            EditorEntity,
+            UiTargetCamera(camera),
+            ThemeBackgroundColor(bevy_tokens::WINDOW_BG),
+            Node {
+                width: percent(100),
+                height: percent(100),
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            },
+        ))
+        .id();
+    SecondaryWindow {
+        window,
+        camera,
+        root,
+    }
+}
+
 pub fn editor_layout(icon_font: &IconFont) -> impl Bundle {
     let font = icon_font.0.clone();
     (
         EditorEntity,
+        DockRoot,
         ThemeBackgroundColor(bevy_tokens::WINDOW_BG),
         Node {
             width: percent(100),
@@ -115,11 +185,74 @@ fn main_area(icon_font: Handle<Font>) -> impl Bundle {
         split_panel::panel_group(
             0.2,
             (
-                Spawn((split_panel::panel(1), entity_heiarchy(icon_font.clone()))),
+                Spawn((split_panel::panel(1), hierarchy_column(icon_font.clone()))),
+                Spawn(split_panel::panel_handle()),
+                Spawn((
+                    split_panel::panel(4),
+                    viewport_with_toolbar(icon_font.clone()),
+                )),
+                Spawn(split_panel::panel_handle()),
+                Spawn((split_panel::panel(1), inspector_column(icon_font))),
+            ),
+        ),
+    )
+}
+
+fn hierarchy_column(icon_font: Handle<Font>) -> impl Bundle {
+    (
+        EditorEntity,
+        DockArea::Left,
+        Node {
+            width: percent(100),
+            height: percent(100),
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        },
+        // Vertical split: hierarchy (top) + minimap (bottom)
+        split_panel::panel_group(
+            0.15,
+            (
+                Spawn((
+                    split_panel::panel(3),
+                    DockPanel::new("hierarchy", "Hierarchy", DockArea::Left),
+                    entity_heiarchy(icon_font),
+                )),
                 Spawn(split_panel::panel_handle()),
-                Spawn((split_panel::panel(4), viewport_with_toolbar(icon_font))),
+                Spawn((
+                    split_panel::panel(1),
+                    DockPanel::new("minimap", "Minimap", DockArea::Left),
+                    crate::minimap::minimap_panel(),
+                )),
+            ),
+        ),
+    )
+}
+
+fn inspector_column(icon_font: Handle<Font>) -> impl Bundle {
+    (
+        EditorEntity,
+        DockArea::Right,
+        Node {
+            width: percent(100),
+            height: percent(100),
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        },
+        // Vertical split: inspector (top) + world settings (bottom)
+        split_panel::panel_group(
+            0.15,
+            (
+                Spawn((
+                    split_panel::panel(3),
+                    DockPanel::new("inspector", "Inspector", DockArea::Right),
+                    crate::inspector::inspector_panel(icon_font),
+                )),
                 Spawn(split_panel::panel_handle()),
-                Spawn((split_panel::panel(1), entity_inspector())),
+                Spawn((
+                    split_panel::panel(1),
+                    DockPanel::new("world_settings", "World Settings", DockArea::Right),
+                    world_settings::world_settings_panel(),
+                )),
             ),
         ),
     )
@@ -138,6 +271,9 @@ fn viewport_with_toolbar(icon_font: Handle<Font>) -> impl Bundle {
             crate::navmesh::toolbar::navmesh_toolbar(),
             crate::terrain::toolbar::terrain_toolbar(),
             scene_view(),
+            crate::asset_audit::asset_audit_panel(),
+            crate::problems::problems_panel(),
+            crate::classname_colors::classname_legend_panel(),
         ],
     )
 }
@@ -181,6 +317,10 @@ fn toolbar(icon_font: Handle<Font>) -> impl Bundle {
                 flex_grow: 1.0,
                 ..Default::default()
             },),
+            // Shading mode
+            view_mode_dropdown(),
+            // Post-processing preview
+            crate::post_processing::post_process_button(f.clone()),
             // Keybind help button
             toolbar_help_button(f),
         ],
@@ -393,6 +533,20 @@ fn toolbar_edit_button(icon: Icon, tool: EditToolButton, font: Handle<Font>) ->
     )
 }
 
+fn view_mode_dropdown() -> impl Bundle {
+    (
+        ViewModeDropdown,
+        combobox_with_selected(ViewMode::ALL.iter().map(|m| m.label()).collect(), 0),
+        observe(
+            |event: On<ComboBoxChangeEvent>, mut settings: ResMut<ViewModeSettings>| {
+                if let Some(&mode) = ViewMode::ALL.get(event.selected) {
+                    settings.mode = mode;
+                }
+            },
+        ),
+    )
+}
+
 fn toolbar_help_button(icon_font: Handle<Font>) -> impl Bundle {
     (
         KeybindHelpButton,
@@ -466,6 +620,10 @@ fn spawn_keybind_help_content(parent: &mut ChildSpawnerCommands) {
                 ("Scroll", "Dolly forward/back"),
                 ("RMB + Scroll", "Adjust move speed"),
                 ("F", "Focus selected"),
+                ("Alt+LMB Drag", "Orbit selection (orbit navigation)"),
+                ("Alt+MMB Drag", "Pan (orbit navigation)"),
+                ("Alt+RMB Drag", "Dolly (orbit navigation)"),
+                ("`", "Walk mode (Space: jump)"),
                 ("Ctrl+1-9", "Save camera bookmark"),
                 ("1-9", "Restore bookmark"),
             ],
@@ -490,6 +648,10 @@ fn spawn_keybind_help_content(parent: &mut ChildSpawnerCommands) {
                 ("Arrows", "Nudge (grid-unit)"),
                 ("Alt+Arrows", "90° rotate"),
                 ("PgUp / PgDn", "Nudge vertical"),
+                ("G/R/S", "Modal grab/rotate/scale (Blender keys, View menu)"),
+                ("Modal: X/Y/Z", "Constrain axis, twice for local"),
+                ("Modal: Shift+X/Y/Z", "Exclude axis"),
+                ("Modal: 0-9 . -", "Type exact value"),
             ],
         ),
         (
@@ -497,8 +659,16 @@ fn spawn_keybind_help_content(parent: &mut ChildSpawnerCommands) {
             &[
                 ("Delete", "Delete"),
                 ("Ctrl+D", "Duplicate"),
+                ("Alt+D", "Duplicate linked"),
+                (
+                    "F2 / Double-click",
+                    "Rename (# in name numbers a multi-selection)",
+                ),
                 ("Ctrl+C / Ctrl+V", "Copy / Paste components"),
+                ("Ctrl+G", "Group"),
+                ("Ctrl+Shift+G", "Ungroup"),
                 ("H", "Toggle visibility"),
+                ("/", "Isolate selection (toggle)"),
                 ("Alt+G", "Reset position"),
                 ("Alt+R", "Reset rotation"),
                 ("Alt+S", "Reset scale"),
@@ -514,15 +684,21 @@ fn spawn_keybind_help_content(parent: &mut ChildSpawnerCommands) {
                 ("Shift+Click", "Multi-select"),
                 ("Click+Drag", "Move selected"),
                 ("X/Y/Z", "Constrain axis (during drag)"),
+                ("Shift+Drag", "Split edge / face (vertex mode)"),
+                ("M", "Merge selected vertices"),
+                ("K", "Split face between 2 vertices"),
                 ("Delete", "Delete selected"),
                 ("Enter", "Apply clip"),
+                ("Tab", "Clip: keep front / back / both"),
+                ("U", "Texture tool (face mode)"),
                 ("Esc", "Exit edit / Cancel drag"),
             ],
         ),
         (
             "CSG",
             &[
-                ("J", "Join (convex merge)"),
+                ("J", "Merge brushes (convex)"),
+                ("Shift+J", "Merge brushes (force)"),
                 ("Ctrl+K", "CSG Subtract"),
                 ("Ctrl+Shift+K", "CSG Intersect"),
             ],
@@ -664,6 +840,50 @@ fn entity_heiarchy(icon_font: Handle<Font>) -> impl Bundle {
                                 },
                                 children![(
                                     Text::new(String::from(Icon::Eye.unicode())),
+                                    TextFont {
+                                        font: icon_font.clone(),
+                                        font_size: 14.0,
+                                        ..Default::default()
+                                    },
+                                    TextColor(tokens::TEXT_SECONDARY),
+                                )],
+                            ),
+                            // Collapse unnamed single-child chains toggle
+                            (
+                                HierarchyCollapseChainsButton,
+                                Interaction::default(),
+                                Node {
+                                    width: px(24.0),
+                                    height: px(24.0),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    border_radius: BorderRadius::all(px(tokens::BORDER_RADIUS_SM),),
+                                    ..Default::default()
+                                },
+                                children![(
+                                    Text::new(String::from(Icon::ChevronsUpDown.unicode())),
+                                    TextFont {
+                                        font: icon_font.clone(),
+                                        font_size: 14.0,
+                                        ..Default::default()
+                                    },
+                                    TextColor(tokens::TEXT_SECONDARY),
+                                )],
+                            ),
+                            // Sort / group / show hidden options menu
+                            (
+                                HierarchyOptionsButton,
+                                Interaction::default(),
+                                Node {
+                                    width: px(24.0),
+                                    height: px(24.0),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    border_radius: BorderRadius::all(px(tokens::BORDER_RADIUS_SM),),
+                                    ..Default::default()
+                                },
+                                children![(
+                                    Text::new(String::from(Icon::Ellipsis.unicode())),
                                     TextFont {
                                         font: icon_font,
                                         font_size: 14.0,
@@ -781,6 +1001,28 @@ pub fn update_edit_tool_highlights(
     }
 }
 
+/// Keeps the shading dropdown in sync when the mode changes from the menu.
+pub fn update_view_mode_dropdown(
+    mut commands: Commands,
+    settings: Res<ViewModeSettings>,
+    dropdowns: Query<Entity, With<ViewModeDropdown>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    let index = ViewMode::ALL
+        .iter()
+        .position(|&m| m == settings.mode)
+        .unwrap_or(0);
+    for entity in &dropdowns {
+        commands.entity(entity).insert(ComboBoxSelectedIndex(index));
+    }
+}
+
+/// The split group of the bottom panels.
+#[derive(Component)]
+pub struct BottomPanels;
+
 fn bottom_panels(icon_font: Handle<Font>) -> impl Bundle {
     (
         EditorEntity,
@@ -789,46 +1031,44 @@ fn bottom_panels(icon_font: Handle<Font>) -> impl Bundle {
             height: percent(100),
             ..Default::default()
         },
-        // Horizontal split: asset browser | material browser
+        BottomPanels,
+        DockArea::Bottom,
+        // Horizontal split: asset browser | texture browser | material browser | templates |
+        // timeline,
+        // followed by panels from `EditorExtensions`
         split_panel::panel_group(
             0.15,
             (
                 Spawn((
                     split_panel::panel(2),
+                    DockPanel::new("assets", "Assets", DockArea::Bottom),
                     asset_browser::asset_browser_panel(icon_font.clone()),
                 )),
                 Spawn(split_panel::panel_handle()),
+                Spawn((
+                    split_panel::panel(2),
+                    DockPanel::new("textures", "Textures", DockArea::Bottom),
+                    texture_browser::texture_browser_panel(icon_font.clone()),
+                )),
+                Spawn(split_panel::panel_handle()),
                 Spawn((
                     split_panel::panel(1),
+                    DockPanel::new("materials", "Materials", DockArea::Bottom),
                     material_browser::material_browser_panel(icon_font),
                 )),
+                Spawn(split_panel::panel_handle()),
+                Spawn((
+                    split_panel::panel(1),
+                    DockPanel::new("templates", "Templates", DockArea::Bottom),
+                    crate::template_browser::template_browser_panel(),
+                )),
+                Spawn(split_panel::panel_handle()),
+                Spawn((
+                    split_panel::panel(2),
+                    DockPanel::new("timeline", "Timeline", DockArea::Bottom),
+                    crate::timeline::timeline_panel(),
+                )),
             ),
         ),
     )
 }
-
-fn entity_inspector() -> impl Bundle {
-    (
-        Node {
-            height: percent(100),
-            flex_direction: FlexDirection::Column,
-            ..Default::default()
-        },
-        BackgroundColor(tokens::PANEL_BG),
-        children![
-            panel_header::panel_header("Inspector"),
-            (
-                Inspector,
-                Node {
-                    flex_direction: FlexDirection::Column,
-                    row_gap: px(tokens::SPACING_SM),
-                    overflow: Overflow::scroll_y(),
-                    flex_grow: 1.0,
-                    min_height: px(0.0),
-                    padding: UiRect::all(px(tokens::SPACING_SM)),
-                    ..Default::default()
-                }
-            ),
-        ],
-    )
-}
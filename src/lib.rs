@@ -1,36 +1,87 @@
+pub mod align;
 pub mod alignment_guides;
+pub mod array_duplicate;
+pub mod asset_audit;
 pub mod asset_browser;
 pub mod asset_catalog;
+pub mod asset_root;
 pub mod brush;
+pub mod brush_bake;
+pub mod classname_colors;
+pub mod collab;
 pub mod commands;
+pub mod csg_preview;
+pub mod cursor3d;
 pub mod custom_properties;
+pub mod display_settings;
+pub mod docking;
+pub mod drag_collision;
 pub mod draw_brush;
+pub mod embedded;
+pub use embedded::EmbeddedEditorPlugin;
+pub mod entity_classes;
 pub mod entity_ops;
 pub mod entity_templates;
+pub mod extensions;
 pub mod face_grid;
 pub mod gizmos;
+pub mod gltf_reload;
+pub mod grouping;
+pub mod headless;
 pub mod hierarchy;
 pub mod inspector;
+pub mod instancing;
+pub mod isolation;
 pub use inspector::{EditorMeta, ReflectEditorMeta};
 pub mod layout;
+pub mod lightmap_bake;
+pub mod linked_duplicate;
+pub mod lod;
+pub mod macros;
 pub mod material_browser;
 pub mod material_preview;
+pub mod minimap;
 pub mod modal_transform;
 pub mod navmesh;
+pub mod particles;
+pub mod player_gauge;
+pub mod post_processing;
 pub mod prefab_picker;
+pub mod problems;
 pub mod project;
 pub mod project_select;
+pub mod project_watcher;
+pub mod scene_import;
 pub mod scene_io;
+pub mod scene_templates;
+pub mod select_similar;
 pub mod selection;
+pub mod selection_outline;
+pub mod simulation;
 pub mod snapping;
+pub mod spline;
+pub mod stable_id;
 pub mod status_bar;
+pub mod sub_scene;
+pub mod surface_types;
+pub mod template_browser;
+pub mod template_parameters;
 pub mod terrain;
 pub mod texture_browser;
+pub mod texture_reload;
+pub mod timeline;
+pub mod transform_panel;
+pub mod trigger_volume;
+pub mod unsaved_changes;
 pub mod view_modes;
 pub mod viewport;
 pub mod viewport_overlays;
 pub mod viewport_select;
 pub mod viewport_util;
+pub mod visibility_flags;
+pub mod visibility_volumes;
+pub mod walk_mode;
+pub mod world_settings;
 
 use bevy::{
     ecs::system::SystemState,
@@ -78,6 +129,7 @@ impl Plugin for EditorPlugin {
     fn build(&self, app: &mut App) {
         // Disable InputDispatchPlugin from FeathersPlugins because bevy_ui_text_input's
         // TextInputPlugin also adds it unconditionally and panics on duplicates.
+        extensions::build_extensions(app);
         app.init_state::<AppState>()
             .add_plugins((
                 FeathersPlugins.build().disable::<InputDispatchPlugin>(),
@@ -111,6 +163,56 @@ impl Plugin for EditorPlugin {
                 navmesh::NavmeshPlugin,
                 terrain::TerrainPlugin,
                 prefab_picker::PrefabPickerPlugin,
+                instancing::InstancingPlugin,
+            ))
+            .add_plugins((
+                array_duplicate::ArrayDuplicatePlugin,
+                surface_types::SurfaceTypesPlugin,
+                macros::MacrosPlugin,
+                asset_audit::AssetAuditPlugin,
+                texture_reload::TextureReloadPlugin,
+                scene_import::SceneImportPlugin,
+                sub_scene::SubScenePlugin,
+                stable_id::StableIdPlugin,
+                entity_classes::EntityClassesPlugin,
+                trigger_volume::TriggerVolumePlugin,
+                texture_browser::TextureBrowserPlugin,
+                isolation::IsolationPlugin,
+                visibility_flags::VisibilityFlagsPlugin,
+                unsaved_changes::UnsavedChangesPlugin,
+            ))
+            .add_plugins((
+                scene_templates::SceneTemplatesPlugin,
+                gltf_reload::GltfReloadPlugin,
+                world_settings::WorldSettingsPlugin,
+                post_processing::PostProcessingPlugin,
+                minimap::MinimapPlugin,
+                cursor3d::Cursor3dPlugin,
+                template_browser::TemplateBrowserPlugin,
+                template_parameters::TemplateParametersPlugin,
+                collab::CollabPlugin,
+                docking::DockingPlugin,
+                display_settings::DisplaySettingsPlugin,
+                timeline::TimelinePlugin,
+                particles::ParticlesPlugin,
+                spline::SplinePlugin,
+            ))
+            .add_plugins((
+                csg_preview::CsgPreviewPlugin,
+                grouping::GroupingPlugin,
+                drag_collision::DragCollisionPlugin,
+                simulation::SimulationPlugin,
+                select_similar::SelectSimilarPlugin,
+                selection_outline::SelectionOutlinePlugin,
+                transform_panel::TransformPanelPlugin,
+                lod::LodPlugin,
+                visibility_volumes::VisibilityVolumesPlugin,
+                problems::ProblemsPlugin,
+                classname_colors::ClassnameColorsPlugin,
+                walk_mode::WalkModePlugin,
+                player_gauge::PlayerGaugePlugin,
+                linked_duplicate::LinkedDuplicatePlugin,
+                project_watcher::ProjectWatcherPlugin,
             ))
             .insert_resource(UiTheme(create_dark_theme()))
             .init_resource::<layout::KeybindHelpPopover>()
@@ -126,6 +228,7 @@ impl Plugin for EditorPlugin {
                     layout::update_toolbar_highlights,
                     layout::update_space_toggle_label,
                     layout::update_edit_tool_highlights,
+                    layout::update_view_mode_dropdown,
                     auto_hide_internal_entities,
                 )
                     .run_if(in_state(AppState::Editor)),
@@ -187,68 +290,239 @@ fn populate_menu(world: &mut World) {
     let Some(menu_bar_entity) = menu_bar_entity else {
         return;
     };
-    jackdaw_feathers::menu_bar::populate_menu_bar(
-        world,
-        menu_bar_entity,
-        vec![
-            (
-                "File",
-                vec![
-                    ("file.new", "New"),
-                    ("file.open", "Open"),
-                    ("---", ""),
-                    ("file.save", "Save"),
-                    ("file.save_as", "Save As..."),
-                    ("---", ""),
-                    ("file.save_template", "Save Selection as Template"),
-                ],
-            ),
-            (
-                "Edit",
-                vec![
-                    ("edit.undo", "Undo"),
-                    ("edit.redo", "Redo"),
-                    ("---", ""),
-                    ("edit.delete", "Delete"),
-                    ("edit.duplicate", "Duplicate"),
-                    ("---", ""),
-                    ("edit.join", "Join (Convex Merge)"),
-                    ("edit.csg_subtract", "CSG Subtract"),
-                    ("edit.csg_intersect", "CSG Intersect"),
-                ],
-            ),
-            (
-                "View",
-                vec![
-                    ("view.wireframe", "Toggle Wireframe"),
-                    ("view.bounding_boxes", "Toggle Bounding Boxes"),
-                    ("view.bounding_box_mode", "Cycle Bounding Box Mode"),
-                    ("view.face_grid", "Toggle Face Grid"),
-                    ("view.brush_wireframe", "Toggle Brush Wireframe"),
-                    ("view.alignment_guides", "Toggle Alignment Guides"),
-                ],
-            ),
-            (
-                "Add",
-                vec![
-                    ("add.cube", "Cube"),
-                    ("add.sphere", "Sphere"),
-                    ("---", ""),
-                    ("add.point_light", "Point Light"),
-                    ("add.directional_light", "Directional Light"),
-                    ("add.spot_light", "Spot Light"),
-                    ("---", ""),
-                    ("add.camera", "Camera"),
-                    ("add.empty", "Empty"),
-                    ("---", ""),
-                    ("add.navmesh", "Navmesh Region"),
-                    ("add.terrain", "Terrain"),
-                    ("---", ""),
-                    ("add.prefab", "Prefab..."),
-                ],
-            ),
-        ],
-    );
+    let menus: Vec<(&str, Vec<(&str, &str)>)> = vec![
+        (
+            "File",
+            vec![
+                ("file.new", "New..."),
+                ("file.open", "Open"),
+                ("file.open_recent", "Open Recent"),
+                ("---", ""),
+                ("file.save", "Save"),
+                ("file.save_as", "Save As..."),
+                ("---", ""),
+                ("file.import_scene", "Import Scene..."),
+                ("file.apply_patch", "Apply Patch..."),
+                ("file.save_template", "Save Selection as Template"),
+                ("---", ""),
+                ("file.bake_brushes", "Bake Brushes to Mesh"),
+                ("file.bake_spline_meshes", "Bake Spline Meshes"),
+                ("file.bake_lightmaps", "Bake Lightmaps (Experimental)"),
+                ("file.export_lightmaps", "Export Lightmaps"),
+                ("---", ""),
+                ("file.collab_host", "Host Session (Experimental)..."),
+                ("file.collab_join", "Join Session..."),
+                ("file.collab_leave", "Leave Session"),
+            ],
+        ),
+        (
+            "Edit",
+            vec![
+                ("edit.undo", "Undo"),
+                ("edit.redo", "Redo"),
+                ("edit.selection_undo", "Toggle Selection Undo"),
+                ("---", ""),
+                ("edit.delete", "Delete"),
+                ("edit.duplicate", "Duplicate"),
+                ("edit.duplicate_linked", "Duplicate Linked"),
+                ("edit.array_duplicate", "Array Duplicate..."),
+                ("---", ""),
+                ("edit.group", "Group"),
+                ("edit.ungroup", "Ungroup"),
+                ("edit.func_group", "Group as Func Group"),
+                ("edit.enter_group", "Enter Func Group"),
+                ("edit.exit_group", "Exit Func Group"),
+                ("---", ""),
+                ("edit.instance", "Instance Selected"),
+                ("edit.deinstance", "De-instance"),
+                ("---", ""),
+                ("edit.join", "Merge Brushes"),
+                ("edit.join_force", "Merge Brushes (Force)"),
+                ("edit.csg_subtract", "CSG Subtract"),
+                ("edit.csg_intersect", "CSG Intersect"),
+                ("edit.csg_carve_mesh", "CSG Carve Mesh"),
+                ("---", ""),
+                ("edit.convert_to_trigger", "Convert to Trigger"),
+                ("edit.convert_to_brush", "Convert to World Brush"),
+                ("edit.convert_to_zone", "Convert to Visibility Zone"),
+                ("edit.convert_to_occluder", "Convert to Occluder"),
+                ("edit.convert_to_portal", "Convert to Portal"),
+                ("edit.clear_visibility_volume", "Clear Visibility Volume"),
+                ("edit.spline_points", "Edit Spline Points"),
+                ("---", ""),
+                ("edit.retag_surfaces", "Re-tag Surfaces from Rules"),
+                ("edit.auto_smooth", "Auto Smooth Brushes"),
+                ("---", ""),
+                ("edit.simulate", "Simulate Physics"),
+            ],
+        ),
+        (
+            "Select",
+            vec![
+                ("select.similar_material", "Select Similar: Material"),
+                ("select.similar_gltf", "Select Similar: glTF Source"),
+                ("select.similar_template", "Select Similar: Template"),
+                ("select.similar_classname", "Select Similar: Classname"),
+            ],
+        ),
+        (
+            "Align",
+            vec![
+                ("align.x_min", "Align X Min"),
+                ("align.x_center", "Align X Center"),
+                ("align.x_max", "Align X Max"),
+                ("---", ""),
+                ("align.y_min", "Align Y Min"),
+                ("align.y_center", "Align Y Center"),
+                ("align.y_max", "Align Y Max"),
+                ("---", ""),
+                ("align.z_min", "Align Z Min"),
+                ("align.z_center", "Align Z Center"),
+                ("align.z_max", "Align Z Max"),
+                ("---", ""),
+                ("align.active.x_min", "Align to Active: X Min"),
+                ("align.active.x_center", "Align to Active: X Center"),
+                ("align.active.x_max", "Align to Active: X Max"),
+                ("---", ""),
+                ("align.active.y_min", "Align to Active: Y Min"),
+                ("align.active.y_center", "Align to Active: Y Center"),
+                ("align.active.y_max", "Align to Active: Y Max"),
+                ("---", ""),
+                ("align.active.z_min", "Align to Active: Z Min"),
+                ("align.active.z_center", "Align to Active: Z Center"),
+                ("align.active.z_max", "Align to Active: Z Max"),
+                ("---", ""),
+                ("align.distribute_x", "Distribute X"),
+                ("align.distribute_y", "Distribute Y"),
+                ("align.distribute_z", "Distribute Z"),
+                ("---", ""),
+                ("mirror.x", "Mirror X"),
+                ("mirror.y", "Mirror Y"),
+                ("mirror.z", "Mirror Z"),
+                ("---", ""),
+                ("mirror.origin.x", "Mirror X about Origin"),
+                ("mirror.origin.y", "Mirror Y about Origin"),
+                ("mirror.origin.z", "Mirror Z about Origin"),
+                ("---", ""),
+                ("mirror.cursor.x", "Mirror X about 3D Cursor"),
+                ("mirror.cursor.y", "Mirror Y about 3D Cursor"),
+                ("mirror.cursor.z", "Mirror Z about 3D Cursor"),
+                ("cursor.to_selection", "3D Cursor to Selection"),
+                ("cursor.reset", "Reset 3D Cursor"),
+            ],
+        ),
+        (
+            "View",
+            vec![
+                ("view.mode.lit", "Shading: Lit"),
+                ("view.mode.unlit", "Shading: Unlit"),
+                ("view.mode.normals", "Shading: Normals"),
+                ("view.mode.uv_checker", "Shading: UV Checker"),
+                ("view.mode.lighting_only", "Shading: Lighting Only"),
+                ("view.mode.baked", "Shading: Baked"),
+                ("view.mode.visibility_zones", "Shading: Visibility Zones"),
+                ("---", ""),
+                ("view.wireframe", "Toggle Wireframe"),
+                ("view.bounding_boxes", "Toggle Bounding Boxes"),
+                ("view.bounding_box_mode", "Cycle Bounding Box Mode"),
+                ("view.face_grid", "Toggle Face Grid"),
+                ("view.brush_wireframe", "Toggle Brush Wireframe"),
+                ("view.alignment_guides", "Toggle Alignment Guides"),
+                ("view.surface_types", "Toggle Surface Types"),
+                ("view.stats", "Toggle Statistics"),
+                ("view.asset_audit", "Toggle Asset Audit"),
+                ("view.problems", "Toggle Problems"),
+                ("view.validate", "Validate Scene"),
+                ("view.classname_legend", "Toggle Classname Colors"),
+                ("---", ""),
+                ("view.grid.finer", "Grid: Finer ( [ )"),
+                ("view.grid.coarser", "Grid: Coarser ( ] )"),
+                ("view.grid.-3", "Grid: 0.125"),
+                ("view.grid.-2", "Grid: 0.25"),
+                ("view.grid.-1", "Grid: 0.5"),
+                ("view.grid.0", "Grid: 1"),
+                ("view.grid.1", "Grid: 2"),
+                ("view.grid.2", "Grid: 4"),
+                ("view.grid.3", "Grid: 8"),
+                ("view.grid.4", "Grid: 16"),
+                ("---", ""),
+                ("view.blender_modal_keys", "Toggle Blender G/R/S Keys"),
+                ("view.gizmo_xray", "Toggle Gizmo X-Ray"),
+                (
+                    "view.collide_while_dragging",
+                    "Toggle Collide While Dragging",
+                ),
+                ("---", ""),
+                ("view.transform_panel", "Toggle Transform Panel (N)"),
+                ("view.pinned_inspector", "Open Pinned Inspector"),
+                ("view.compare_entities", "Compare Selected Entities"),
+                ("view.walk_mode", "Toggle Walk Mode (`)"),
+                ("view.player_gauge", "Toggle Player Gauge"),
+                ("view.display_settings", "Display Settings..."),
+                ("window.assets", "Open Assets in New Window"),
+                ("window.inspector", "Open Inspector in New Window"),
+                ("layout.modeling", "Layout: Modeling"),
+                ("layout.texturing", "Layout: Texturing"),
+                ("layout.scripting", "Layout: Scripting"),
+            ],
+        ),
+        (
+            "Macro",
+            vec![
+                ("macro.record", "Start Recording"),
+                ("macro.stop", "Stop Recording..."),
+                ("---", ""),
+                ("macro.play", "Play Macro..."),
+                ("macro.repeat", "Repeat Last Macro"),
+            ],
+        ),
+        (
+            "Add",
+            vec![
+                ("add.cube", "Cube"),
+                ("add.sphere", "Sphere"),
+                ("---", ""),
+                ("add.point_light", "Point Light"),
+                ("add.directional_light", "Directional Light"),
+                ("add.spot_light", "Spot Light"),
+                ("---", ""),
+                ("add.camera", "Camera"),
+                ("add.empty", "Empty"),
+                ("---", ""),
+                ("add.navmesh", "Navmesh Region"),
+                ("add.terrain", "Terrain"),
+                ("add.particle_emitter", "Particle Emitter"),
+                ("add.spline", "Spline"),
+                ("add.road", "Road"),
+                ("---", ""),
+                ("add.prefab", "Prefab..."),
+                ("add.sub_scene", "Sub-Scene Reference..."),
+            ],
+        ),
+    ];
+
+    let mut menus: Vec<(String, Vec<(String, String)>)> = menus
+        .into_iter()
+        .map(|(title, items)| {
+            let items = items
+                .into_iter()
+                .map(|(action, label)| (action.to_string(), label.to_string()))
+                .collect();
+            (title.to_string(), items)
+        })
+        .collect();
+    extensions::extend_menus(world, &mut menus);
+    let menus = menus
+        .iter()
+        .map(|(title, items)| {
+            let items = items
+                .iter()
+                .map(|(action, label)| (action.as_str(), label.as_str()))
+                .collect();
+            (title.as_str(), items)
+        })
+        .collect();
+    jackdaw_feathers::menu_bar::populate_menu_bar(world, menu_bar_entity, menus);
 }
 
 fn handle_menu_action(event: On<MenuAction>, mut commands: Commands) {
@@ -273,6 +547,16 @@ fn handle_menu_action(event: On<MenuAction>, mut commands: Commands) {
                 scene_io::load_scene(world);
             });
         }
+        "file.import_scene" => {
+            commands.queue(|world: &mut World| {
+                scene_import::open_import_dialog(world);
+            });
+        }
+        "file.apply_patch" => {
+            commands.queue(|world: &mut World| {
+                scene_io::apply_patch_file(world);
+            });
+        }
         "file.save_template" => {
             // Use a default name based on the selected entity
             commands.queue(|world: &mut World| {
@@ -284,6 +568,27 @@ fn handle_menu_action(event: On<MenuAction>, mut commands: Commands) {
                 entity_templates::save_entity_template(world, &name);
             });
         }
+        "file.bake_brushes" => {
+            commands.queue(brush_bake::bake_selected_brushes);
+        }
+        "file.bake_spline_meshes" => {
+            commands.queue(spline::bake_selected_extrusions);
+        }
+        "file.collab_host" => {
+            commands.queue(collab::open_host_dialog);
+        }
+        "file.collab_join" => {
+            commands.queue(collab::open_join_dialog);
+        }
+        "file.collab_leave" => {
+            commands.queue(collab::leave_session);
+        }
+        "file.bake_lightmaps" => {
+            commands.queue(lightmap_bake::bake_lightmaps);
+        }
+        "file.export_lightmaps" => {
+            commands.queue(lightmap_bake::export_lightmaps);
+        }
         "edit.undo" => {
             commands.queue(|world: &mut World| {
                 world.resource_scope(|world, mut history: Mut<commands::CommandHistory>| {
@@ -298,6 +603,9 @@ fn handle_menu_action(event: On<MenuAction>, mut commands: Commands) {
                 });
             });
         }
+        "edit.selection_undo" => {
+            commands.queue(selection::toggle_selection_undo);
+        }
         "edit.delete" => {
             commands.queue(|world: &mut World| {
                 entity_ops::delete_selected(world);
@@ -308,15 +616,189 @@ fn handle_menu_action(event: On<MenuAction>, mut commands: Commands) {
                 entity_ops::duplicate_selected(world);
             });
         }
+        "edit.duplicate_linked" => {
+            commands.queue(linked_duplicate::duplicate_linked_selected);
+        }
+        "edit.array_duplicate" => {
+            commands.queue(|world: &mut World| {
+                array_duplicate::open_array_duplicate_dialog(world);
+            });
+        }
+        "edit.group" => {
+            commands.queue(|world: &mut World| {
+                grouping::group_selected(world);
+            });
+        }
+        "edit.ungroup" => {
+            commands.queue(|world: &mut World| {
+                grouping::ungroup_selected(world);
+            });
+        }
+        "edit.func_group" => {
+            commands.queue(grouping::func_group_selected);
+        }
+        "edit.enter_group" => {
+            commands.queue(grouping::enter_selected_group);
+        }
+        "edit.exit_group" => {
+            commands.queue(grouping::exit_group);
+        }
+        "edit.instance" => {
+            commands.queue(|world: &mut World| {
+                instancing::instance_selected(world);
+            });
+        }
+        "edit.deinstance" => {
+            commands.queue(|world: &mut World| {
+                instancing::deinstance_selected(world);
+            });
+        }
         "edit.join" => {
-            commands.queue(draw_brush::join_selected_brushes_impl);
+            commands.queue(|world: &mut World| {
+                csg_preview::begin_csg_preview(world, csg_preview::CsgOp::Merge { force: false });
+            });
+        }
+        "edit.join_force" => {
+            commands.queue(|world: &mut World| {
+                csg_preview::begin_csg_preview(world, csg_preview::CsgOp::Merge { force: true });
+            });
         }
         "edit.csg_subtract" => {
-            commands.queue(draw_brush::csg_subtract_selected_impl);
+            commands.queue(|world: &mut World| {
+                csg_preview::begin_csg_preview(world, csg_preview::CsgOp::Subtract);
+            });
         }
         "edit.csg_intersect" => {
             commands.queue(draw_brush::csg_intersect_selected_impl);
         }
+        "edit.csg_carve_mesh" => {
+            commands.queue(draw_brush::csg_carve_selected_meshes_impl);
+        }
+        "edit.convert_to_trigger" => {
+            commands.queue(trigger_volume::convert_selected_to_trigger);
+        }
+        "edit.convert_to_brush" => {
+            commands.queue(trigger_volume::convert_selected_to_brush);
+        }
+        "edit.convert_to_zone" => {
+            commands.queue(|world: &mut World| {
+                visibility_volumes::convert_selected(
+                    world,
+                    Some(jackdaw_jsn::VisibilityVolumeKind::Zone),
+                );
+            });
+        }
+        "edit.convert_to_occluder" => {
+            commands.queue(|world: &mut World| {
+                visibility_volumes::convert_selected(
+                    world,
+                    Some(jackdaw_jsn::VisibilityVolumeKind::Occluder),
+                );
+            });
+        }
+        "edit.convert_to_portal" => {
+            commands.queue(|world: &mut World| {
+                visibility_volumes::convert_selected(
+                    world,
+                    Some(jackdaw_jsn::VisibilityVolumeKind::Portal),
+                );
+            });
+        }
+        "edit.clear_visibility_volume" => {
+            commands.queue(|world: &mut World| visibility_volumes::convert_selected(world, None));
+        }
+        "edit.spline_points" => {
+            commands.queue(spline::toggle_spline_edit);
+        }
+        "edit.retag_surfaces" => {
+            commands.queue(surface_types::retag_surfaces_from_rules);
+        }
+        "edit.auto_smooth" => {
+            commands.queue(brush::auto_smooth_selected_brushes);
+        }
+        "select.similar_material" => {
+            commands.queue(|world: &mut World| {
+                select_similar::select_similar(world, select_similar::SimilarKind::Material);
+            });
+        }
+        "select.similar_gltf" => {
+            commands.queue(|world: &mut World| {
+                select_similar::select_similar(world, select_similar::SimilarKind::GltfSource);
+            });
+        }
+        "select.similar_template" => {
+            commands.queue(|world: &mut World| {
+                select_similar::select_similar(world, select_similar::SimilarKind::Template);
+            });
+        }
+        "select.similar_classname" => {
+            commands.queue(|world: &mut World| {
+                select_similar::select_similar(world, select_similar::SimilarKind::Classname);
+            });
+        }
+        "edit.simulate" => {
+            commands.queue(simulation::toggle_simulation);
+        }
+        "view.walk_mode" => {
+            commands.queue(walk_mode::toggle_walk_mode);
+        }
+        "view.pinned_inspector" => {
+            commands.queue(inspector::open_pinned_inspector);
+        }
+        "view.compare_entities" => {
+            commands.queue(inspector::open_compare_window);
+        }
+        "view.player_gauge" => {
+            commands.queue(player_gauge::toggle_player_gauge);
+        }
+        "view.asset_audit" => {
+            commands.queue(asset_audit::toggle_audit);
+        }
+        "view.problems" => {
+            commands.queue(problems::toggle_problems);
+        }
+        "view.validate" => {
+            commands.queue(problems::validate_and_show);
+        }
+        "view.classname_legend" => {
+            commands.queue(classname_colors::toggle_legend);
+        }
+        "macro.record" => {
+            commands.queue(macros::start_recording);
+        }
+        "macro.stop" => {
+            commands.queue(macros::stop_recording);
+        }
+        "macro.play" => {
+            commands.queue(macros::open_play_dialog);
+        }
+        "macro.repeat" => {
+            commands.queue(macros::repeat_last);
+        }
+        "view.transform_panel" => {
+            commands.queue(|world: &mut World| {
+                let mut state = world.resource_mut::<transform_panel::TransformPanelState>();
+                state.open = !state.open;
+            });
+        }
+        "view.display_settings" => {
+            commands.queue(display_settings::open_display_dialog);
+        }
+        "view.blender_modal_keys" => {
+            commands.queue(|world: &mut World| {
+                let mut settings = world.resource_mut::<modal_transform::ModalTransformSettings>();
+                settings.blender_keys = !settings.blender_keys;
+            });
+        }
+        "view.gizmo_xray" => {
+            commands.queue(gizmos::toggle_gizmo_occlusion);
+        }
+        "view.collide_while_dragging" => {
+            commands.queue(|world: &mut World| {
+                let mut settings = world.resource_mut::<drag_collision::DragCollisionSettings>();
+                settings.enabled = !settings.enabled;
+            });
+        }
         "view.wireframe" => {
             commands.queue(|world: &mut World| {
                 let mut settings = world.resource_mut::<view_modes::ViewModeSettings>();
@@ -360,6 +842,18 @@ fn handle_menu_action(event: On<MenuAction>, mut commands: Commands) {
                 settings.show_alignment_guides = !settings.show_alignment_guides;
             });
         }
+        "view.stats" => {
+            commands.queue(|world: &mut World| {
+                let mut settings = world.resource_mut::<viewport_overlays::OverlaySettings>();
+                settings.show_stats = !settings.show_stats;
+            });
+        }
+        "view.surface_types" => {
+            commands.queue(|world: &mut World| {
+                let mut settings = world.resource_mut::<viewport_overlays::OverlaySettings>();
+                settings.show_surface_types = !settings.show_surface_types;
+            });
+        }
         "add.cube" => {
             commands.queue(|world: &mut World| {
                 entity_ops::create_entity_in_world(world, entity_ops::EntityTemplate::Cube);
@@ -418,11 +912,115 @@ fn handle_menu_action(event: On<MenuAction>, mut commands: Commands) {
                 system_state.apply(world);
             });
         }
+        "add.particle_emitter" => {
+            commands.queue(|world: &mut World| {
+                let mut system_state: SystemState<(Commands, ResMut<Selection>)> =
+                    SystemState::new(world);
+                let (mut commands, mut selection) = system_state.get_mut(world);
+                let entity = particles::spawn_particle_emitter(&mut commands);
+                selection.select_single(&mut commands, entity);
+                system_state.apply(world);
+            });
+        }
+        "add.spline" => {
+            commands.queue(|world: &mut World| {
+                let mut system_state: SystemState<(Commands, ResMut<Selection>)> =
+                    SystemState::new(world);
+                let (mut commands, mut selection) = system_state.get_mut(world);
+                let entity = spline::spawn_spline_entity(&mut commands);
+                selection.select_single(&mut commands, entity);
+                system_state.apply(world);
+                spline::toggle_spline_edit(world);
+            });
+        }
+        "add.road" => {
+            commands.queue(|world: &mut World| {
+                let mut system_state: SystemState<(Commands, ResMut<Selection>)> =
+                    SystemState::new(world);
+                let (mut commands, mut selection) = system_state.get_mut(world);
+                let entity = spline::spawn_road_entity(&mut commands);
+                selection.select_single(&mut commands, entity);
+                system_state.apply(world);
+                spline::toggle_spline_edit(world);
+            });
+        }
         "add.prefab" => {
             commands.queue(|world: &mut World| {
                 crate::prefab_picker::open_prefab_picker(world);
             });
         }
+        "add.sub_scene" => {
+            commands.queue(|world: &mut World| {
+                crate::prefab_picker::open_sub_scene_picker(world);
+            });
+        }
+        "cursor.to_selection" => {
+            commands.queue(cursor3d::cursor_to_selection);
+        }
+        "cursor.reset" => {
+            commands.queue(|world: &mut World| {
+                world.resource_mut::<cursor3d::Cursor3d>().position = Vec3::ZERO;
+            });
+        }
+        action if action.starts_with("mirror.") => {
+            let action = action.to_string();
+            commands.queue(move |world: &mut World| {
+                align::handle_mirror_action(world, &action);
+            });
+        }
+        action if action.starts_with("align.") => {
+            let action = action.to_string();
+            commands.queue(move |world: &mut World| {
+                align::handle_align_action(world, &action);
+            });
+        }
+        action if action.starts_with(entity_classes::ADD_POINT_ENTITY_ACTION) => {
+            let class_name = action[entity_classes::ADD_POINT_ENTITY_ACTION.len()..].to_string();
+            commands.queue(move |world: &mut World| {
+                entity_classes::spawn_point_entity(world, &class_name);
+            });
+        }
+        action if action.starts_with(scene_io::OPEN_RECENT_ACTION) => {
+            if let Ok(index) = action[scene_io::OPEN_RECENT_ACTION.len()..].parse::<usize>() {
+                commands.queue(move |world: &mut World| {
+                    scene_io::open_recent_scene(world, index);
+                });
+            }
+        }
+        action if action.starts_with("window.") => {
+            let panel = action["window.".len()..].to_string();
+            commands.queue(move |world: &mut World| docking::open_in_window(world, &panel));
+        }
+        action if action.starts_with("layout.") => {
+            let preset = action["layout.".len()..].to_string();
+            commands.queue(move |world: &mut World| docking::apply_preset(world, &preset));
+        }
+        "view.grid.finer" => {
+            commands.queue(|world: &mut World| {
+                world.resource_mut::<snapping::SnapSettings>().step_grid(-1);
+            });
+        }
+        "view.grid.coarser" => {
+            commands.queue(|world: &mut World| {
+                world.resource_mut::<snapping::SnapSettings>().step_grid(1);
+            });
+        }
+        action if action.starts_with("view.grid.") => {
+            if let Ok(power) = action["view.grid.".len()..].parse::<i32>() {
+                commands.queue(move |world: &mut World| {
+                    world
+                        .resource_mut::<snapping::SnapSettings>()
+                        .set_grid_power(power);
+                });
+            }
+        }
+        action if action.starts_with("view.mode.") => {
+            if let Some(mode) = view_modes::ViewMode::from_id(&action["view.mode.".len()..]) {
+                commands.queue(move |world: &mut World| {
+                    world.resource_mut::<view_modes::ViewModeSettings>().mode = mode;
+                });
+            }
+        }
         _ => {}
     }
 }
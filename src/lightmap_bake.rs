@@ -0,0 +1,592 @@
+//! Experimental CPU lightmap baker for brush faces. Every face gets its own lightmap:
+//! texels are lit directly by the scene's lights, with ray-cast shadows against all
+//! brush faces, plus the scene ambient and one bounce gathered from the faces around
+//! them. Bakes are previewed with the `Baked` view mode and can be exported as Radiance
+//! `.hdr` files next to the scene.
+//!
+//! Bakes live on the face entities, so editing a brush (which respawns its faces)
+//! drops that brush's bakes until the next bake.
+
+use std::{f32::consts::PI, path::Path, time::Instant};
+
+use bevy::{
+    asset::RenderAssetUsages,
+    camera::Exposure,
+    mesh::VertexAttributeValues,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use jackdaw_geometry::{EPSILON, compute_face_tangent_axes};
+use jackdaw_jsn::{SceneEnvironment, TriggerVolume, VisibilityVolume};
+use serde_json::json;
+
+use crate::{
+    EditorEntity, brush::BrushFaceEntity, scene_io::SceneFilePath, stable_id::stable_id_of,
+    viewport::MainViewportCamera,
+};
+
+/// Lightmap texels per world unit.
+const TEXELS_PER_UNIT: f32 = 4.0;
+const MIN_LIGHTMAP_SIZE: u32 = 4;
+const MAX_LIGHTMAP_SIZE: u32 = 64;
+/// Hemisphere rays per texel for the bounce pass.
+const BOUNCE_RAYS: usize = 16;
+/// Fraction of the light reaching a surface that it bounces back.
+const BOUNCE_ALBEDO: f32 = 0.5;
+/// How far shadow and bounce rays start off the surface.
+const RAY_OFFSET: f32 = 0.01;
+
+/// Baked lighting of one brush face, mapped with the face mesh's second UV channel.
+#[derive(Component)]
+pub struct BakedLightmap {
+    /// Tonemapped copy for the viewport.
+    pub display: Handle<Image>,
+    pub width: u32,
+    pub height: u32,
+    /// Linear lighting per texel, rows top to bottom, already scaled by the camera
+    /// exposure: 1.0 shows a white surface at full brightness.
+    pub texels: Vec<Vec3>,
+}
+
+/// A brush face in world space, with the planar projection of its lightmap.
+struct LightmapFace {
+    entity: Entity,
+    mesh: Handle<Mesh>,
+    points: Vec<Vec3>,
+    normal: Vec3,
+    u_axis: Vec3,
+    v_axis: Vec3,
+    /// Lower corner and size of the face's bounds in the (u, v) plane.
+    min: Vec2,
+    size: Vec2,
+    width: u32,
+    height: u32,
+}
+
+impl LightmapFace {
+    fn texel_count(&self) -> usize {
+        (self.width * self.height) as usize
+    }
+
+    /// World position of a texel centre, pulled onto the face for texels hanging over
+    /// its edges so they don't sample from inside neighbouring geometry.
+    fn texel_position(&self, index: usize) -> Vec3 {
+        let (x, y) = (index as u32 % self.width, index as u32 / self.width);
+        let uv = Vec2::new(
+            (x as f32 + 0.5) / self.width as f32,
+            (y as f32 + 0.5) / self.height as f32,
+        );
+        let plane = self.min + uv * self.size;
+        let point = self.points[0]
+            + self.u_axis * (plane.x - self.points[0].dot(self.u_axis))
+            + self.v_axis * (plane.y - self.points[0].dot(self.v_axis));
+        clamp_to_polygon(point, &self.points, self.normal)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum BakeLight {
+    Directional {
+        /// Direction the light travels.
+        direction: Vec3,
+        color: Vec3,
+    },
+    Point {
+        position: Vec3,
+        color: Vec3,
+        range: f32,
+        /// Spot cone as (forward, cos outer angle, cos inner angle).
+        cone: Option<(Vec3, f32, f32)>,
+    },
+}
+
+/// One triangle of the occluding geometry, tagged with the face it belongs to.
+struct Triangle {
+    a: Vec3,
+    edge1: Vec3,
+    edge2: Vec3,
+    face: usize,
+}
+
+/// Bake lightmaps for every brush face in the scene and put them on the face entities.
+pub fn bake_lightmaps(world: &mut World) {
+    let started = Instant::now();
+    let faces = collect_faces(world);
+    if faces.is_empty() {
+        info!("Bake lightmaps: no brush faces to bake");
+        return;
+    }
+    let lights = collect_lights(world);
+    let exposure = world
+        .query_filtered::<&Exposure, With<MainViewportCamera>>()
+        .iter(world)
+        .next()
+        .copied()
+        .unwrap_or_default()
+        .exposure();
+    let ambient = world
+        .get_resource::<SceneEnvironment>()
+        .map(|env| {
+            let c = env.ambient_color.to_linear();
+            Vec3::new(c.red, c.green, c.blue) * env.ambient_brightness
+        })
+        .unwrap_or(Vec3::ZERO);
+
+    let triangles: Vec<Triangle> = faces
+        .iter()
+        .enumerate()
+        .flat_map(|(fi, face)| {
+            (1..face.points.len() - 1).map(move |i| Triangle {
+                a: face.points[0],
+                edge1: face.points[i] - face.points[0],
+                edge2: face.points[i + 1] - face.points[0],
+                face: fi,
+            })
+        })
+        .collect();
+
+    // Direct light, in the same units as the renderer's Lambert term
+    let direct: Vec<Vec<Vec3>> = faces
+        .iter()
+        .map(|face| {
+            (0..face.texel_count())
+                .map(|ti| {
+                    let point = face.texel_position(ti);
+                    direct_light(point, face.normal, &lights, &triangles) / PI * exposure
+                })
+                .collect()
+        })
+        .collect();
+    let face_average: Vec<Vec3> = direct
+        .iter()
+        .map(|texels| texels.iter().sum::<Vec3>() / texels.len().max(1) as f32)
+        .collect();
+
+    // Single bounce: what the surrounding faces reflect of their own direct light
+    let directions = hemisphere_directions();
+    let mut baked = Vec::with_capacity(faces.len());
+    for (fi, face) in faces.iter().enumerate() {
+        let (tangent, bitangent) = face.normal.any_orthonormal_pair();
+        let texels: Vec<Vec3> = (0..face.texel_count())
+            .map(|ti| {
+                let origin = face.texel_position(ti) + face.normal * RAY_OFFSET;
+                let bounce = directions
+                    .iter()
+                    .filter_map(|d| {
+                        let dir = tangent * d.x + bitangent * d.y + face.normal * d.z;
+                        closest_hit(origin, dir, f32::MAX, &triangles)
+                    })
+                    .map(|hit| face_average[hit] * BOUNCE_ALBEDO)
+                    .sum::<Vec3>()
+                    / directions.len() as f32;
+                direct[fi][ti] + bounce + ambient * exposure
+            })
+            .collect();
+        baked.push(texels);
+    }
+
+    let face_count = faces.len();
+    for (face, texels) in faces.into_iter().zip(baked) {
+        write_lightmap_uvs(world, &face);
+        let display = world.resource_mut::<Assets<Image>>().add(display_image(
+            face.width,
+            face.height,
+            &texels,
+        ));
+        world.entity_mut(face.entity).insert(BakedLightmap {
+            display,
+            width: face.width,
+            height: face.height,
+            texels,
+        });
+    }
+    info!(
+        "Baked lightmaps for {face_count} brush faces from {} lights in {:.1}s",
+        lights.len(),
+        started.elapsed().as_secs_f32()
+    );
+}
+
+/// Faces of every visible brush that isn't a trigger or visibility volume, with lightmap sizes from their area.
+fn collect_faces(world: &mut World) -> Vec<LightmapFace> {
+    let mut query = world.query::<(
+        Entity,
+        &BrushFaceEntity,
+        &Mesh3d,
+        &GlobalTransform,
+        &InheritedVisibility,
+    )>();
+    let candidates: Vec<_> = query
+        .iter(world)
+        .filter(|(_, face, _, _, visible)| {
+            visible.get()
+                && world.get::<TriggerVolume>(face.brush_entity).is_none()
+                && world.get::<VisibilityVolume>(face.brush_entity).is_none()
+        })
+        .map(|(entity, _, mesh, global, _)| (entity, mesh.0.clone(), *global))
+        .collect();
+
+    let meshes = world.resource::<Assets<Mesh>>();
+    candidates
+        .into_iter()
+        .filter_map(|(entity, mesh_handle, global)| {
+            let mesh = meshes.get(&mesh_handle)?;
+            let Some(VertexAttributeValues::Float32x3(positions)) =
+                mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+            else {
+                return None;
+            };
+            let points: Vec<Vec3> = positions
+                .iter()
+                .map(|p| global.transform_point(Vec3::from_array(*p)))
+                .collect();
+            if points.len() < 3 {
+                return None;
+            }
+            // Newell's method, robust to nearly collinear first vertices
+            let normal = points
+                .iter()
+                .zip(points.iter().cycle().skip(1))
+                .map(|(a, b)| a.cross(*b))
+                .sum::<Vec3>()
+                .try_normalize()?;
+            let (u_axis, v_axis) = compute_face_tangent_axes(normal);
+            let (mut min, mut max) = (Vec2::MAX, Vec2::MIN);
+            for p in &points {
+                let uv = Vec2::new(p.dot(u_axis), p.dot(v_axis));
+                min = min.min(uv);
+                max = max.max(uv);
+            }
+            let size = (max - min).max(Vec2::splat(EPSILON));
+            let texels = |extent: f32| {
+                ((extent * TEXELS_PER_UNIT).ceil() as u32)
+                    .clamp(MIN_LIGHTMAP_SIZE, MAX_LIGHTMAP_SIZE)
+            };
+            Some(LightmapFace {
+                entity,
+                mesh: mesh_handle,
+                points,
+                normal,
+                u_axis,
+                v_axis,
+                min,
+                size,
+                width: texels(size.x),
+                height: texels(size.y),
+            })
+        })
+        .collect()
+}
+
+/// Visible scene lights, with their intensity folded into the color.
+fn collect_lights(world: &mut World) -> Vec<BakeLight> {
+    let to_vec = |color: Color| {
+        let c = color.to_linear();
+        Vec3::new(c.red, c.green, c.blue)
+    };
+    let mut lights = Vec::new();
+
+    let mut directional = world.query_filtered::<
+        (&DirectionalLight, &GlobalTransform, &InheritedVisibility),
+        Without<EditorEntity>,
+    >();
+    for (light, global, visible) in directional.iter(world) {
+        if visible.get() {
+            lights.push(BakeLight::Directional {
+                direction: global.forward().as_vec3(),
+                color: to_vec(light.color) * light.illuminance,
+            });
+        }
+    }
+
+    // Point and spot lights are given in lumens; as luminous intensity that's lm / 4π.
+    let mut point = world.query_filtered::<
+        (&PointLight, &GlobalTransform, &InheritedVisibility),
+        Without<EditorEntity>,
+    >();
+    for (light, global, visible) in point.iter(world) {
+        if visible.get() {
+            lights.push(BakeLight::Point {
+                position: global.translation(),
+                color: to_vec(light.color) * light.intensity / (4.0 * PI),
+                range: light.range,
+                cone: None,
+            });
+        }
+    }
+
+    let mut spot = world.query_filtered::<
+        (&SpotLight, &GlobalTransform, &InheritedVisibility),
+        Without<EditorEntity>,
+    >();
+    for (light, global, visible) in spot.iter(world) {
+        if visible.get() {
+            lights.push(BakeLight::Point {
+                position: global.translation(),
+                color: to_vec(light.color) * light.intensity / (4.0 * PI),
+                range: light.range,
+                cone: Some((
+                    global.forward().as_vec3(),
+                    light.outer_angle.cos(),
+                    light.inner_angle.min(light.outer_angle).cos(),
+                )),
+            });
+        }
+    }
+    lights
+}
+
+/// Irradiance at `point` from all lights that reach it unobstructed.
+fn direct_light(point: Vec3, normal: Vec3, lights: &[BakeLight], triangles: &[Triangle]) -> Vec3 {
+    let origin = point + normal * RAY_OFFSET;
+    let mut total = Vec3::ZERO;
+    for light in lights {
+        match *light {
+            BakeLight::Directional { direction, color } => {
+                let n_dot_l = normal.dot(-direction);
+                if n_dot_l > 0.0 && closest_hit(origin, -direction, f32::MAX, triangles).is_none() {
+                    total += color * n_dot_l;
+                }
+            }
+            BakeLight::Point {
+                position,
+                color,
+                range,
+                cone,
+            } => {
+                let to_light = position - origin;
+                let distance = to_light.length();
+                if distance < EPSILON || distance > range {
+                    continue;
+                }
+                let dir = to_light / distance;
+                let n_dot_l = normal.dot(dir);
+                if n_dot_l <= 0.0 {
+                    continue;
+                }
+                // Same windowing as the renderer, fading light out towards its range
+                let window = (1.0 - (distance / range).powi(4)).clamp(0.0, 1.0).powi(2);
+                let spot = cone.map_or(1.0, |(forward, cos_outer, cos_inner)| {
+                    let cos = forward.dot(-dir);
+                    ((cos - cos_outer) / (cos_inner - cos_outer).max(EPSILON)).clamp(0.0, 1.0)
+                });
+                if spot <= 0.0 || closest_hit(origin, dir, distance, triangles).is_some() {
+                    continue;
+                }
+                total += color * n_dot_l * window * spot / (distance * distance);
+            }
+        }
+    }
+    total
+}
+
+/// Index of the face hit first by the ray, if any within `max_distance`.
+fn closest_hit(
+    origin: Vec3,
+    dir: Vec3,
+    max_distance: f32,
+    triangles: &[Triangle],
+) -> Option<usize> {
+    let mut best = max_distance;
+    let mut hit = None;
+    for tri in triangles {
+        // Möller–Trumbore
+        let p = dir.cross(tri.edge2);
+        let det = tri.edge1.dot(p);
+        if det.abs() < 1e-8 {
+            continue;
+        }
+        let inv_det = 1.0 / det;
+        let s = origin - tri.a;
+        let u = s.dot(p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            continue;
+        }
+        let q = s.cross(tri.edge1);
+        let v = dir.dot(q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            continue;
+        }
+        let t = tri.edge2.dot(q) * inv_det;
+        if t > EPSILON && t < best {
+            best = t;
+            hit = Some(tri.face);
+        }
+    }
+    hit
+}
+
+/// Cosine-weighted directions over the +Z hemisphere, stratified so every bake
+/// of the same scene gives the same result.
+fn hemisphere_directions() -> Vec<Vec3> {
+    let rings = (BOUNCE_RAYS as f32).sqrt().ceil() as usize;
+    let sectors = BOUNCE_RAYS.div_ceil(rings);
+    let mut directions = Vec::with_capacity(rings * sectors);
+    for ring in 0..rings {
+        for sector in 0..sectors {
+            let r = ((ring as f32 + 0.5) / rings as f32).sqrt();
+            // Offset every other ring so rays don't line up
+            let phi = 2.0 * PI * (sector as f32 + 0.5 * (ring % 2) as f32) / sectors as f32;
+            directions.push(Vec3::new(
+                r * phi.cos(),
+                r * phi.sin(),
+                (1.0 - r * r).max(0.0).sqrt(),
+            ));
+        }
+    }
+    directions
+}
+
+/// Closest point to `point` on the convex polygon, nudged slightly inwards.
+fn clamp_to_polygon(point: Vec3, polygon: &[Vec3], normal: Vec3) -> Vec3 {
+    let outside = polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .any(|(a, b)| (*b - *a).cross(point - *a).dot(normal) < 0.0);
+    if !outside {
+        return point;
+    }
+    let closest = polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| {
+            let edge = *b - *a;
+            let t = ((point - *a).dot(edge) / edge.length_squared().max(EPSILON)).clamp(0.0, 1.0);
+            *a + edge * t
+        })
+        .min_by(|a, b| {
+            a.distance_squared(point)
+                .total_cmp(&b.distance_squared(point))
+        })
+        .unwrap_or(point);
+    let centroid = polygon.iter().sum::<Vec3>() / polygon.len() as f32;
+    closest + (centroid - closest).normalize_or_zero() * RAY_OFFSET
+}
+
+/// Give the face mesh a second UV channel spanning the face's lightmap.
+fn write_lightmap_uvs(world: &mut World, face: &LightmapFace) {
+    let mut meshes = world.resource_mut::<Assets<Mesh>>();
+    let Some(mesh) = meshes.get_mut(&face.mesh) else {
+        return;
+    };
+    let uvs: Vec<[f32; 2]> = face
+        .points
+        .iter()
+        .map(|p| {
+            let uv = Vec2::new(p.dot(face.u_axis), p.dot(face.v_axis));
+            ((uv - face.min) / face.size).to_array()
+        })
+        .collect();
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_1, uvs);
+}
+
+fn display_image(width: u32, height: u32, texels: &[Vec3]) -> Image {
+    let data = texels
+        .iter()
+        .flat_map(|t| {
+            // Extended Reinhard with a white point of 4, so bright spots roll off
+            let mapped = *t * (Vec3::ONE + *t / 16.0) / (Vec3::ONE + *t);
+            Color::linear_rgb(mapped.x, mapped.y, mapped.z)
+                .to_srgba()
+                .to_u8_array()
+        })
+        .collect();
+    Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// Write every baked lightmap as a Radiance `.hdr` file into `<scene>_lightmaps/` next
+/// to the scene file, with a `lightmaps.json` mapping brush ids and face indices to files.
+pub fn export_lightmaps(world: &mut World) {
+    let Some(scene_path) = world.resource::<SceneFilePath>().path.clone() else {
+        warn!("Export lightmaps: save the scene first");
+        return;
+    };
+    let scene_path = Path::new(&scene_path);
+    let stem = scene_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("scene");
+    let dir = scene_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(format!("{stem}_lightmaps"));
+
+    let mut query = world.query::<(&BrushFaceEntity, &BakedLightmap)>();
+    let bakes: Vec<_> = query
+        .iter(world)
+        .filter_map(|(face, baked)| {
+            let id = stable_id_of(world, face.brush_entity)?;
+            Some((id, face.face_index, baked))
+        })
+        .collect();
+    if bakes.is_empty() {
+        warn!("Export lightmaps: nothing baked yet");
+        return;
+    }
+
+    if let Err(err) = std::fs::create_dir_all(&dir) {
+        warn!(
+            "Export lightmaps: failed to create '{}': {err}",
+            dir.display()
+        );
+        return;
+    }
+    let mut entries = Vec::with_capacity(bakes.len());
+    for (id, face_index, baked) in &bakes {
+        let file = format!("{id}_{face_index}.hdr");
+        let bytes = encode_hdr(baked.width, baked.height, &baked.texels);
+        if let Err(err) = std::fs::write(dir.join(&file), bytes) {
+            warn!("Export lightmaps: failed to write '{file}': {err}");
+            return;
+        }
+        entries.push(json!({
+            "brush": id.to_string(),
+            "face": face_index,
+            "file": file,
+            "width": baked.width,
+            "height": baked.height,
+        }));
+    }
+    let manifest = json!({ "uv_channel": 1, "lightmaps": entries });
+    if let Err(err) = std::fs::write(
+        dir.join("lightmaps.json"),
+        serde_json::to_string_pretty(&manifest).unwrap_or_default(),
+    ) {
+        warn!("Export lightmaps: failed to write manifest: {err}");
+        return;
+    }
+    info!("Exported {} lightmaps to '{}'", bakes.len(), dir.display());
+}
+
+/// Uncompressed Radiance RGBE image.
+fn encode_hdr(width: u32, height: u32, texels: &[Vec3]) -> Vec<u8> {
+    let mut bytes =
+        format!("#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y {height} +X {width}\n").into_bytes();
+    bytes.reserve(texels.len() * 4);
+    for texel in texels {
+        let max = texel.max_element();
+        if max < 1e-32 {
+            bytes.extend_from_slice(&[0, 0, 0, 0]);
+            continue;
+        }
+        let exponent = max.log2().floor() as i32 + 1;
+        let scale = 256.0 / 2f32.powi(exponent);
+        bytes.extend_from_slice(&[
+            (texel.x * scale).clamp(0.0, 255.0) as u8,
+            (texel.y * scale).clamp(0.0, 255.0) as u8,
+            (texel.z * scale).clamp(0.0, 255.0) as u8,
+            (exponent + 128).clamp(0, 255) as u8,
+        ]);
+    }
+    bytes
+}
@@ -0,0 +1,185 @@
+//! "Duplicate Linked": copies that share their source's mesh and material assets and,
+//! for brushes, its brush data. Editing one linked brush edits them all until a member
+//! is made unique.
+
+use std::collections::HashMap;
+
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+use jackdaw_jsn::{Brush, LinkedDuplicate};
+
+use crate::{
+    EditorEntity,
+    commands::{CommandGroup, CommandHistory, EditorCommand, SpawnSnapshot, remap_entity},
+    entity_ops::duplicate_entity,
+    selection::{Selected, Selection, select_entities},
+};
+
+pub struct LinkedDuplicatePlugin;
+
+impl Plugin for LinkedDuplicatePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            sync_linked_brushes.run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+/// Sets or clears an entity's link.
+struct SetLink {
+    entity: Entity,
+    old: Option<LinkedDuplicate>,
+    new: Option<LinkedDuplicate>,
+}
+
+impl SetLink {
+    fn apply(world: &mut World, entity: Entity, link: Option<LinkedDuplicate>) {
+        let Ok(mut ec) = world.get_entity_mut(entity) else {
+            return;
+        };
+        match link {
+            Some(link) => {
+                ec.insert(link);
+            }
+            None => {
+                ec.remove::<LinkedDuplicate>();
+            }
+        }
+    }
+}
+
+impl EditorCommand for SetLink {
+    fn execute(&self, world: &mut World) {
+        Self::apply(world, self.entity, self.new);
+    }
+
+    fn undo(&self, world: &mut World) {
+        Self::apply(world, self.entity, self.old);
+    }
+
+    fn description(&self) -> &str {
+        if self.new.is_some() {
+            "Link entity"
+        } else {
+            "Make unique"
+        }
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+    }
+}
+
+/// Duplicate the selection as linked copies and select the copies. Undoable as one step.
+pub fn duplicate_linked_selected(world: &mut World) {
+    let originals: Vec<Entity> = world
+        .resource::<Selection>()
+        .entities
+        .iter()
+        .copied()
+        .filter(|&e| world.get_entity(e).is_ok() && world.get::<EditorEntity>(e).is_none())
+        .collect();
+    if originals.is_empty() {
+        return;
+    }
+
+    // Link the originals first so their copies inherit the link
+    let mut cmds: Vec<Box<dyn EditorCommand>> = Vec::new();
+    for &original in &originals {
+        if world.get::<LinkedDuplicate>(original).is_none() {
+            let cmd = SetLink {
+                entity: original,
+                old: None,
+                new: Some(LinkedDuplicate::new()),
+            };
+            cmd.execute(world);
+            cmds.push(Box::new(cmd));
+        }
+    }
+
+    for &original in &originals {
+        world.entity_mut(original).remove::<Selected>();
+    }
+    let mut copies = Vec::new();
+    for &original in &originals {
+        let Some(copy) = duplicate_entity(world, original) else {
+            continue;
+        };
+        cmds.push(Box::new(SpawnSnapshot::from_world(
+            world,
+            &[copy],
+            "Duplicate linked",
+        )));
+        copies.push(copy);
+    }
+
+    select_entities(world, &copies);
+
+    world
+        .resource_mut::<CommandHistory>()
+        .push_executed(Box::new(CommandGroup {
+            commands: cmds,
+            label: "Duplicate linked".to_string(),
+        }));
+}
+
+/// Detach `entity` from its link, so later edits to it stay its own. Undoable.
+pub fn make_unique(world: &mut World, entity: Entity) {
+    let Some(&link) = world.get::<LinkedDuplicate>(entity) else {
+        return;
+    };
+    let cmd = SetLink {
+        entity,
+        old: Some(link),
+        new: None,
+    };
+    cmd.execute(world);
+    world
+        .resource_mut::<CommandHistory>()
+        .push_executed(Box::new(cmd));
+}
+
+/// Entities sharing `entity`'s link, including itself.
+pub fn linked_members(world: &mut World, entity: Entity) -> Vec<Entity> {
+    let Some(&link) = world.get::<LinkedDuplicate>(entity) else {
+        return Vec::new();
+    };
+    world
+        .query::<(Entity, &LinkedDuplicate)>()
+        .iter(world)
+        .filter(|(_, other)| **other == link)
+        .map(|(member, _)| member)
+        .collect()
+}
+
+/// Select every member of `entity`'s link.
+pub fn select_linked(world: &mut World, entity: Entity) {
+    let members = linked_members(world, entity);
+    if members.is_empty() {
+        return;
+    }
+    select_entities(world, &members);
+}
+
+/// Copy an edited linked brush to the rest of its link. The copies' own change isn't
+/// seen by this system next frame, so edits don't bounce back.
+fn sync_linked_brushes(mut brushes: Query<(Entity, &LinkedDuplicate, &mut Brush)>) {
+    let mut edited: HashMap<LinkedDuplicate, (Entity, Brush)> = HashMap::new();
+    for (entity, link, brush) in brushes.iter_mut() {
+        if brush.is_changed() && !brush.is_added() {
+            edited
+                .entry(*link)
+                .or_insert_with(|| (entity, brush.clone()));
+        }
+    }
+    if edited.is_empty() {
+        return;
+    }
+    for (entity, link, mut brush) in &mut brushes {
+        if let Some((source, edited_brush)) = edited.get(link)
+            && *source != entity
+        {
+            *brush = edited_brush.clone();
+        }
+    }
+}
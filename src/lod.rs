@@ -0,0 +1,443 @@
+//! Level-of-detail preview for [`LodGroup`] entities. Each level's glTF model is loaded
+//! as a hidden child of the group and shown in place of the entity's own geometry when the
+//! viewport camera is far enough away, or when the preview field forces a level.
+
+use bevy::{
+    camera::visibility::RenderLayers, ecs::entity::EntityHashMap, prelude::*,
+    scene::SceneInstanceReady,
+};
+use jackdaw_feathers::{
+    button::{ButtonProps, button},
+    text_edit::{self, TextEditCommitEvent, TextEditDragging, TextEditProps, TextEditValue},
+    tokens,
+};
+use jackdaw_jsn::{LodGroup, LodLevel};
+
+use crate::{
+    EditorEntity, EditorHidden, NonSerializable,
+    commands::{EditorCommand, remap_entity},
+    selection::Selection,
+    viewport::{MainViewportCamera, SceneViewport},
+};
+
+pub struct LodPlugin;
+
+impl Plugin for LodPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LodPreview>()
+            .add_observer(on_lod_preview_field_commit)
+            .add_systems(
+                Update,
+                (
+                    update_lod_previews,
+                    rebuild_lod_preview_overlay,
+                    apply_dragged_lod_preview_field,
+                    update_lod_preview_readout,
+                )
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+pub struct SetLodGroup {
+    pub entity: Entity,
+    pub old: LodGroup,
+    pub new: LodGroup,
+}
+
+impl EditorCommand for SetLodGroup {
+    fn execute(&self, world: &mut World) {
+        if let Some(mut group) = world.get_mut::<LodGroup>(self.entity) {
+            *group = self.new.clone();
+        }
+    }
+
+    fn undo(&self, world: &mut World) {
+        if let Some(mut group) = world.get_mut::<LodGroup>(self.entity) {
+            *group = self.old.clone();
+        }
+    }
+
+    fn description(&self) -> &str {
+        "Edit LOD levels"
+    }
+
+    fn memory_size(&self) -> usize {
+        let levels = self.old.levels.iter().chain(&self.new.levels);
+        std::mem::size_of::<Self>()
+            + (self.old.levels.capacity() + self.new.levels.capacity())
+                * std::mem::size_of::<LodLevel>()
+            + levels.map(|level| level.path.capacity()).sum::<usize>()
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+    }
+}
+
+/// Level every [`LodGroup`] shows, instead of picking it by camera distance.
+#[derive(Resource, Default)]
+pub struct LodPreview {
+    pub forced: Option<usize>,
+}
+
+/// Model of one level (1 and up) of the parent's [`LodGroup`].
+#[derive(Component)]
+struct LodPreviewModel {
+    level: usize,
+    path: String,
+}
+
+/// On level-0 meshes hidden while a coarser level shows.
+#[derive(Component)]
+struct LodHidden;
+
+#[derive(Component)]
+struct LodPreviewOverlay;
+
+#[derive(Component)]
+struct LodPreviewField;
+
+#[derive(Component)]
+struct LodPreviewReadout;
+
+/// Level `group` shows with the camera at `camera`.
+fn current_level(
+    group: &LodGroup,
+    global: &GlobalTransform,
+    camera: Option<Vec3>,
+    preview: &LodPreview,
+) -> usize {
+    match (preview.forced, camera) {
+        (Some(level), _) => level.min(group.levels.len()),
+        (None, Some(camera)) => group.level_at(camera.distance(global.translation())),
+        (None, None) => 0,
+    }
+}
+
+/// Keep each group's level models in step with its component and show the current level.
+fn update_lod_previews(world: &mut World) {
+    let camera = world
+        .query_filtered::<&GlobalTransform, With<MainViewportCamera>>()
+        .iter(world)
+        .next()
+        .map(GlobalTransform::translation);
+    let groups: Vec<(Entity, LodGroup, GlobalTransform)> = world
+        .query_filtered::<(Entity, &LodGroup, &GlobalTransform), Without<EditorEntity>>()
+        .iter(world)
+        .map(|(e, group, global)| (e, group.clone(), *global))
+        .collect();
+
+    // Drop models whose group or level is gone or now points at another file
+    let models: Vec<(Entity, Entity, usize, String)> = world
+        .query::<(Entity, &LodPreviewModel, &ChildOf)>()
+        .iter(world)
+        .map(|(e, model, child_of)| (e, child_of.parent(), model.level, model.path.clone()))
+        .collect();
+    for (model, owner, level, path) in &models {
+        let current = groups
+            .iter()
+            .find(|(e, ..)| e == owner)
+            .and_then(|(_, group, _)| group.levels.get(level - 1));
+        if current.is_none_or(|l| &l.path != path) {
+            world.entity_mut(*model).despawn();
+        }
+    }
+    let hidden: Vec<Entity> = world
+        .query_filtered::<Entity, With<LodHidden>>()
+        .iter(world)
+        .collect();
+
+    let preview = world.resource::<LodPreview>();
+    let shown: Vec<(Entity, LodGroup, usize)> = groups
+        .into_iter()
+        .map(|(entity, group, global)| {
+            let level = current_level(&group, &global, camera, preview);
+            (entity, group, level)
+        })
+        .collect();
+
+    let mut still_hidden = Vec::new();
+    for (owner, group, level) in shown {
+        for (index, lod) in group.levels.iter().enumerate() {
+            let lod_level = index + 1;
+            if lod.path.is_empty() {
+                continue;
+            }
+            let existing = world
+                .get::<Children>(owner)
+                .into_iter()
+                .flat_map(|children| children.iter())
+                .find(|&child| {
+                    world
+                        .get::<LodPreviewModel>(child)
+                        .is_some_and(|m| m.level == lod_level)
+                });
+            let model = existing.unwrap_or_else(|| spawn_lod_model(world, owner, lod_level, lod));
+            let visibility = if lod_level == level {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+            if world.get::<Visibility>(model) != Some(&visibility) {
+                world.entity_mut(model).insert(visibility);
+            }
+        }
+        // Only hide the group's own geometry when a model stands in for it
+        let has_model = group
+            .levels
+            .get(level.wrapping_sub(1))
+            .is_some_and(|l| !l.path.is_empty());
+        if level > 0 && has_model {
+            still_hidden.extend(own_meshes(world, owner));
+        }
+    }
+
+    // Meshes render on no layer to hide without touching the authored `Visibility`;
+    // `RenderLayers` is never saved.
+    for entity in hidden {
+        if !still_hidden.contains(&entity)
+            && let Ok(mut ec) = world.get_entity_mut(entity)
+        {
+            ec.remove::<(LodHidden, RenderLayers)>();
+        }
+    }
+    for entity in still_hidden {
+        if world.get::<LodHidden>(entity).is_none() && world.get::<RenderLayers>(entity).is_none() {
+            world
+                .entity_mut(entity)
+                .insert((LodHidden, RenderLayers::none()));
+        }
+    }
+}
+
+fn spawn_lod_model(
+    world: &mut World,
+    owner: Entity,
+    level: usize,
+    lod: &jackdaw_jsn::LodLevel,
+) -> Entity {
+    let scene = world
+        .resource::<AssetServer>()
+        .load(GltfAssetLabel::Scene(0).from_asset(lod.path.clone()));
+    world
+        .spawn((
+            LodPreviewModel {
+                level,
+                path: lod.path.clone(),
+            },
+            SceneRoot(scene),
+            Transform::IDENTITY,
+            Visibility::Hidden,
+            EditorHidden,
+            NonSerializable,
+            ChildOf(owner),
+        ))
+        .observe(keep_lod_model_out_of_scene)
+        .id()
+}
+
+/// The model's nodes are named, so they would otherwise show in the hierarchy and be saved.
+fn keep_lod_model_out_of_scene(
+    event: On<SceneInstanceReady>,
+    children: Query<&Children>,
+    mut commands: Commands,
+) {
+    for descendant in children.iter_descendants(event.entity) {
+        commands
+            .entity(descendant)
+            .insert((EditorHidden, NonSerializable));
+    }
+}
+
+/// The group's level-0 meshes: everything it draws outside its level models.
+fn own_meshes(world: &World, owner: Entity) -> Vec<Entity> {
+    let mut meshes = Vec::new();
+    let mut stack = vec![owner];
+    while let Some(entity) = stack.pop() {
+        if world.get::<LodPreviewModel>(entity).is_some() {
+            continue;
+        }
+        if world.get::<Mesh3d>(entity).is_some() {
+            meshes.push(entity);
+        }
+        if let Some(children) = world.get::<Children>(entity) {
+            stack.extend(children.iter());
+        }
+    }
+    meshes
+}
+
+/// Show the preview controls over the viewport while the active entity has LODs.
+fn rebuild_lod_preview_overlay(
+    world: &mut World,
+    mut shown: Local<Option<(Option<Entity>, usize)>>,
+) {
+    let levels = world
+        .resource::<Selection>()
+        .primary()
+        .and_then(|e| world.get::<LodGroup>(e))
+        .map(|group| group.levels.len());
+    let current = (
+        levels.and(world.resource::<Selection>().primary()),
+        levels.unwrap_or(0),
+    );
+    if *shown == Some(current) {
+        return;
+    }
+    *shown = Some(current);
+
+    let old: Vec<Entity> = world
+        .query_filtered::<Entity, With<LodPreviewOverlay>>()
+        .iter(world)
+        .collect();
+    for overlay in old {
+        world.entity_mut(overlay).despawn();
+    }
+    let Some(levels) = levels else {
+        return;
+    };
+    let Some(viewport) = world
+        .query_filtered::<Entity, With<SceneViewport>>()
+        .iter(world)
+        .next()
+    else {
+        return;
+    };
+
+    let overlay = world
+        .spawn((
+            LodPreviewOverlay,
+            EditorEntity,
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: px(tokens::SPACING_MD),
+                left: px(tokens::SPACING_MD),
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: px(tokens::SPACING_SM),
+                padding: UiRect::all(px(tokens::SPACING_SM)),
+                border_radius: BorderRadius::all(tokens::CORNER_RADIUS_LG),
+                ..Default::default()
+            },
+            BackgroundColor(tokens::PANEL_BG.with_alpha(0.92)),
+            ChildOf(viewport),
+        ))
+        .id();
+    world.spawn((
+        Text::new("LOD"),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_PRIMARY),
+        ChildOf(overlay),
+    ));
+    let forced = world.resource::<LodPreview>().forced.unwrap_or(0);
+    let field_slot = world
+        .spawn((
+            Node {
+                width: px(56.0),
+                ..Default::default()
+            },
+            ChildOf(overlay),
+        ))
+        .id();
+    world.spawn((
+        LodPreviewField,
+        text_edit::text_edit(
+            TextEditProps::default()
+                .numeric_i32()
+                .grow()
+                .with_min(0.0)
+                .with_max(levels as f64)
+                .with_default_value(forced.min(levels).to_string()),
+        ),
+        ChildOf(field_slot),
+    ));
+    world
+        .spawn((button(ButtonProps::new("Auto")), ChildOf(overlay)))
+        .observe(|_: On<Pointer<Click>>, mut preview: ResMut<LodPreview>| {
+            preview.forced = None;
+        });
+    world.spawn((
+        LodPreviewReadout,
+        Text::new(""),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_SECONDARY),
+        ChildOf(overlay),
+    ));
+}
+
+fn on_lod_preview_field_commit(
+    event: On<TextEditCommitEvent>,
+    fields: Query<(), With<LodPreviewField>>,
+    child_of_query: Query<&ChildOf>,
+    mut preview: ResMut<LodPreview>,
+) {
+    let mut current = event.entity;
+    for _ in 0..4 {
+        let Ok(child_of) = child_of_query.get(current) else {
+            return;
+        };
+        current = child_of.parent();
+        if fields.contains(current) {
+            if let Ok(level) = event.text.trim().parse::<usize>() {
+                preview.forced = Some(level);
+            }
+            return;
+        }
+    }
+}
+
+/// Scrubbing the field previews each level as it passes.
+fn apply_dragged_lod_preview_field(
+    fields: Query<(&TextEditValue, &Children), (With<LodPreviewField>, Changed<TextEditValue>)>,
+    dragging: Query<(), With<TextEditDragging>>,
+    mut preview: ResMut<LodPreview>,
+) {
+    for (value, children) in &fields {
+        if !children.iter().any(|child| dragging.contains(child)) {
+            continue;
+        }
+        if let Ok(level) = value.0.trim().parse::<usize>() {
+            preview.forced = Some(level);
+        }
+    }
+}
+
+/// Which level the active entity shows, at what camera distance.
+fn update_lod_preview_readout(
+    preview: Res<LodPreview>,
+    selection: Res<Selection>,
+    groups: Query<(&LodGroup, &GlobalTransform)>,
+    camera: Query<&GlobalTransform, With<MainViewportCamera>>,
+    mut readouts: Query<&mut Text, With<LodPreviewReadout>>,
+) {
+    let Some((group, global)) = selection.primary().and_then(|e| groups.get(e).ok()) else {
+        return;
+    };
+    let camera = camera.single().ok().map(GlobalTransform::translation);
+    let level = current_level(group, global, camera, &preview);
+    let mode = if preview.forced.is_some() {
+        "forced"
+    } else {
+        "auto"
+    };
+    let text = match camera {
+        Some(camera) => format!(
+            "showing {level} ({mode}) at {:.1} m",
+            camera.distance(global.translation())
+        ),
+        None => format!("showing {level} ({mode})"),
+    };
+    for mut readout in &mut readouts {
+        if readout.0 != text {
+            readout.0 = text.clone();
+        }
+    }
+}
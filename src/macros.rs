@@ -0,0 +1,366 @@
+//! Editor macros: record a sequence of menu actions, name it, and replay it later.
+//!
+//! Only actions run from the menus are recorded. Edits made directly in the viewport,
+//! with the gizmo or in the inspector are not, as they can't be replayed on another
+//! selection; the status bar says so while recording.
+//!
+//! Macros are stored per project in `.jsn/macros.json`.
+
+use bevy::prelude::*;
+use jackdaw_feathers::{
+    combobox::{ComboBoxChangeEvent, combobox, combobox_with_selected},
+    dialog::{DialogActionEvent, DialogChildrenSlot, DialogClosedEvent, DialogId, OpenDialogEvent},
+    text_edit::{self, TextEditProps, TextEditValue},
+};
+use jackdaw_widgets::menu_bar::MenuAction;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    commands::{CommandGroup, CommandHistory},
+    project::ProjectRoot,
+    selection::{Selection, select_entities},
+    status_bar::StatusHints,
+};
+
+const RECORDING_HINTS: &str = "macro_recording";
+const MACRO_DIALOG: &str = "macro";
+
+pub struct MacrosPlugin;
+
+impl Plugin for MacrosPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MacroLibrary>()
+            .init_resource::<MacroRecorder>()
+            .init_resource::<PendingMacroDialog>()
+            .add_observer(record_menu_action)
+            .add_observer(on_macro_dialog_action)
+            .add_observer(on_macro_dialog_closed)
+            .add_systems(
+                Update,
+                (load_macros_for_project, populate_macro_dialog)
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EditorMacro {
+    pub name: String,
+    /// Menu action ids, replayed in order.
+    pub actions: Vec<String>,
+}
+
+/// All macros of the open project.
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub struct MacroLibrary {
+    pub macros: Vec<EditorMacro>,
+    /// Name of the macro last saved or played, for "Repeat Last Macro".
+    #[serde(skip)]
+    pub last: Option<String>,
+}
+
+#[derive(Resource, Default)]
+pub struct MacroRecorder {
+    pub recording: bool,
+    pub actions: Vec<String>,
+}
+
+#[derive(Resource, Default)]
+enum PendingMacroDialog {
+    #[default]
+    None,
+    Save,
+    Play {
+        index: usize,
+        per_entity: bool,
+    },
+}
+
+/// Marker for the macro name input in the save dialog.
+#[derive(Component)]
+struct MacroNameInput;
+
+fn macros_path(project: &ProjectRoot) -> std::path::PathBuf {
+    project.jsn_dir().join("macros.json")
+}
+
+fn load_macros_for_project(project: Option<Res<ProjectRoot>>, mut library: ResMut<MacroLibrary>) {
+    let Some(project) = project else {
+        return;
+    };
+    if !project.is_changed() {
+        return;
+    }
+    *library = std::fs::read_to_string(macros_path(&project))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default();
+}
+
+fn save_macros(world: &World) {
+    let Some(project) = world.get_resource::<ProjectRoot>() else {
+        warn!("No project open; macro will not persist");
+        return;
+    };
+    let path = macros_path(project);
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    match serde_json::to_string_pretty(world.resource::<MacroLibrary>()) {
+        Ok(data) => {
+            if let Err(e) = std::fs::write(&path, data) {
+                warn!("Failed to write {}: {e}", path.display());
+            }
+        }
+        Err(e) => warn!("Failed to serialize macros: {e}"),
+    }
+}
+
+/// Append every menu action to the active recording. Macro actions and undo/redo
+/// are left out since replaying them would fight the macro's own undo step.
+fn record_menu_action(event: On<MenuAction>, mut recorder: ResMut<MacroRecorder>) {
+    let action = event.action.as_str();
+    if recorder.recording
+        && !action.starts_with("macro.")
+        && action != "edit.undo"
+        && action != "edit.redo"
+    {
+        recorder.actions.push(event.action.clone());
+    }
+}
+
+pub fn start_recording(world: &mut World) {
+    let mut recorder = world.resource_mut::<MacroRecorder>();
+    recorder.recording = true;
+    recorder.actions.clear();
+    world.resource_mut::<StatusHints>().set(
+        RECORDING_HINTS,
+        "Recording macro: menu actions only | Macro > Stop Recording to finish",
+    );
+    info!("Macro recording started");
+}
+
+/// Stop recording and ask for a name to save the macro under.
+pub fn stop_recording(world: &mut World) {
+    let mut recorder = world.resource_mut::<MacroRecorder>();
+    if !recorder.recording {
+        return;
+    }
+    recorder.recording = false;
+    let empty = recorder.actions.is_empty();
+    world.resource_mut::<StatusHints>().clear(RECORDING_HINTS);
+    if empty {
+        info!("Macro recording stopped (nothing recorded)");
+        return;
+    }
+    *world.resource_mut::<PendingMacroDialog>() = PendingMacroDialog::Save;
+    world.trigger(
+        OpenDialogEvent::new("Save Macro", "Save")
+            .with_id(MACRO_DIALOG)
+            .with_max_width(px(360)),
+    );
+}
+
+/// Ask which macro to play and how.
+pub fn open_play_dialog(world: &mut World) {
+    if world.resource::<MacroLibrary>().macros.is_empty() {
+        info!("No macros recorded for this project");
+        return;
+    }
+    *world.resource_mut::<PendingMacroDialog>() = PendingMacroDialog::Play {
+        index: 0,
+        per_entity: false,
+    };
+    world.trigger(
+        OpenDialogEvent::new("Play Macro", "Play")
+            .with_id(MACRO_DIALOG)
+            .with_max_width(px(360)),
+    );
+}
+
+/// Replay the most recently saved or played macro on the current selection.
+pub fn repeat_last(world: &mut World) {
+    let Some(name) = world.resource::<MacroLibrary>().last.clone() else {
+        return;
+    };
+    play_macro(world, &name, false);
+}
+
+/// Replay a macro by name. With `per_entity`, each selected entity is selected on its
+/// own and the macro runs once for it. All resulting edits undo as one step.
+pub fn play_macro(world: &mut World, name: &str, per_entity: bool) {
+    let Some(actions) = world
+        .resource::<MacroLibrary>()
+        .macros
+        .iter()
+        .find(|m| m.name == name)
+        .map(|m| m.actions.clone())
+    else {
+        return;
+    };
+    world.resource_mut::<MacroLibrary>().last = Some(name.to_string());
+
+    // Don't record the replayed steps into a recording in progress.
+    let was_recording = std::mem::take(&mut world.resource_mut::<MacroRecorder>().recording);
+    let history_len = world.resource::<CommandHistory>().undo_stack.len();
+    let selection = world.resource::<Selection>().entities.clone();
+
+    if per_entity && !selection.is_empty() {
+        for &entity in &selection {
+            if world.get_entity(entity).is_err() {
+                continue;
+            }
+            select_entities(world, &[entity]);
+            run_actions(world, &actions);
+        }
+        let survivors: Vec<Entity> = selection
+            .into_iter()
+            .filter(|&e| world.get_entity(e).is_ok())
+            .collect();
+        select_entities(world, &survivors);
+    } else {
+        run_actions(world, &actions);
+    }
+    world.resource_mut::<MacroRecorder>().recording = was_recording;
+
+    // Fold everything the macro pushed into a single undo entry.
+    let mut history = world.resource_mut::<CommandHistory>();
+    if history.undo_stack.len() > history_len + 1 {
+        let commands = history.undo_stack.split_off(history_len);
+        history.push_executed(Box::new(CommandGroup {
+            commands,
+            label: format!("Macro: {name}"),
+        }));
+    }
+}
+
+fn run_actions(world: &mut World, actions: &[String]) {
+    for action in actions {
+        world.trigger(MenuAction {
+            action: action.clone(),
+        });
+        // Menu handlers queue their work; apply it before the next step.
+        world.flush();
+    }
+}
+
+fn populate_macro_dialog(
+    mut commands: Commands,
+    pending: Res<PendingMacroDialog>,
+    library: Res<MacroLibrary>,
+    slots: Query<(Entity, &DialogId), Added<DialogChildrenSlot>>,
+) {
+    for (slot, id) in &slots {
+        if id.0 != MACRO_DIALOG {
+            continue;
+        }
+        match *pending {
+            PendingMacroDialog::None => {}
+            PendingMacroDialog::Save => {
+                let default_name = format!("Macro {}", library.macros.len() + 1);
+                commands.spawn((
+                    MacroNameInput,
+                    text_edit::text_edit(
+                        TextEditProps::default()
+                            .with_label("Name")
+                            .with_placeholder("Macro name...")
+                            .with_default_value(default_name),
+                    ),
+                    ChildOf(slot),
+                ));
+            }
+            PendingMacroDialog::Play { .. } => {
+                let names: Vec<String> = library.macros.iter().map(|m| m.name.clone()).collect();
+                let selected = library
+                    .last
+                    .as_ref()
+                    .and_then(|last| names.iter().position(|n| n == last))
+                    .unwrap_or(0);
+                commands.queue(move |world: &mut World| {
+                    if let PendingMacroDialog::Play { index, .. } =
+                        &mut *world.resource_mut::<PendingMacroDialog>()
+                    {
+                        *index = selected;
+                    }
+                });
+                commands
+                    .spawn((combobox_with_selected(names, selected), ChildOf(slot)))
+                    .observe(
+                        |event: On<ComboBoxChangeEvent>,
+                         mut pending: ResMut<PendingMacroDialog>| {
+                            if let PendingMacroDialog::Play { index, .. } = &mut *pending {
+                                *index = event.selected;
+                            }
+                        },
+                    );
+                commands
+                    .spawn((
+                        combobox(vec!["Run once", "Run on each selected entity"]),
+                        ChildOf(slot),
+                    ))
+                    .observe(
+                        |event: On<ComboBoxChangeEvent>,
+                         mut pending: ResMut<PendingMacroDialog>| {
+                            if let PendingMacroDialog::Play { per_entity, .. } = &mut *pending {
+                                *per_entity = event.selected == 1;
+                            }
+                        },
+                    );
+            }
+        }
+    }
+}
+
+/// However the dialog closes, forget the pending dialog; an action has used it by then.
+fn on_macro_dialog_closed(event: On<DialogClosedEvent>, mut pending: ResMut<PendingMacroDialog>) {
+    if event.id == Some(MACRO_DIALOG) {
+        *pending = PendingMacroDialog::None;
+    }
+}
+
+fn on_macro_dialog_action(
+    event: On<DialogActionEvent>,
+    mut commands: Commands,
+    name_inputs: Query<&TextEditValue, With<MacroNameInput>>,
+) {
+    if event.id != Some(MACRO_DIALOG) {
+        return;
+    }
+    let name = name_inputs
+        .iter()
+        .next()
+        .map(|input| input.0.trim().to_string())
+        .unwrap_or_default();
+
+    commands.queue(move |world: &mut World| {
+        let pending = std::mem::take(&mut *world.resource_mut::<PendingMacroDialog>());
+        match pending {
+            PendingMacroDialog::None => {}
+            PendingMacroDialog::Save => {
+                if name.is_empty() {
+                    return;
+                }
+                let actions = std::mem::take(&mut world.resource_mut::<MacroRecorder>().actions);
+                let mut library = world.resource_mut::<MacroLibrary>();
+                library.macros.retain(|m| m.name != name);
+                library.macros.push(EditorMacro {
+                    name: name.clone(),
+                    actions,
+                });
+                library.last = Some(name);
+                save_macros(world);
+            }
+            PendingMacroDialog::Play { index, per_entity } => {
+                let Some(name) = world
+                    .resource::<MacroLibrary>()
+                    .macros
+                    .get(index)
+                    .map(|m| m.name.clone())
+                else {
+                    return;
+                };
+                play_macro(world, &name, per_entity);
+            }
+        }
+    });
+}
@@ -1,23 +1,48 @@
 use bevy::{
     asset::{AssetPlugin, UnapprovedPathMode},
     image::{ImageAddressMode, ImagePlugin, ImageSamplerDescriptor},
-    light::GlobalAmbientLight,
     prelude::*,
+    window::ExitCondition,
 };
-use jackdaw::EditorPlugin;
+use jackdaw::{EditorPlugin, asset_root::ProjectAssetSourcePlugin};
 
 fn main() -> AppExit {
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() == Some("--headless") {
+        return match jackdaw::headless::HeadlessArgs::parse(args) {
+            Ok(args) => jackdaw::headless::run(args),
+            Err(err) => {
+                eprintln!("error: {err}\n\n{}", jackdaw::headless::USAGE);
+                AppExit::error()
+            }
+        };
+    }
+
     let project_root = jackdaw::project::read_last_project()
         .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
 
     App::new()
+        // Replaces the `AssetPlugin::file_path` source, so it has to come first.
+        .add_plugins(ProjectAssetSourcePlugin {
+            root: jackdaw::project::project_assets_dir(&project_root),
+        })
         .add_plugins(
             DefaultPlugins
                 .set(AssetPlugin {
-                    file_path: project_root.join("assets").to_string_lossy().to_string(),
                     unapproved_path_mode: UnapprovedPathMode::Allow,
                     ..default()
                 })
+                // The editor closes the window itself, after asking about unsaved changes.
+                .set(WindowPlugin {
+                    primary_window: Some(Window {
+                        title: "Jackdaw".into(),
+                        ..default()
+                    }),
+                    close_when_requested: false,
+                    // Closing the main window quits even with panel windows open
+                    exit_condition: ExitCondition::OnPrimaryClosed,
+                    ..default()
+                })
                 .set(ImagePlugin {
                     default_sampler: ImageSamplerDescriptor {
                         address_mode_u: ImageAddressMode::Repeat,
@@ -33,12 +58,6 @@ fn main() -> AppExit {
 }
 
 fn spawn_scene(mut commands: Commands) {
-    commands.insert_resource(GlobalAmbientLight {
-        color: Color::WHITE,
-        brightness: 400.0,
-        affects_lightmapped_meshes: true,
-    });
-
     commands.spawn((
         Name::new("Sun"),
         DirectionalLight {
@@ -1,3 +1,4 @@
+use std::any::TypeId;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
@@ -11,17 +12,19 @@ use bevy::{
 };
 use jackdaw_feathers::{
     icons,
+    panel_header::PanelHeader,
     text_edit::{self, TextEditCommitEvent, TextEditDragging, TextEditProps, TextEditValue},
     tokens,
 };
+use jackdaw_jsn::MaterialOverrideApplied;
 use rfd::AsyncFileDialog;
 
 use crate::{
     EditorEntity,
     asset_browser::attach_tooltip,
     brush::{Brush, BrushEditMode, BrushSelection, EditMode, SetBrush},
-    commands::CommandHistory,
-    material_preview::MaterialPreviewState,
+    commands::{CommandGroup, CommandHistory, EditorCommand, SetComponentField},
+    material_preview::{MaterialPreviewState, PreviewSphere},
     selection::Selection,
 };
 
@@ -61,6 +64,9 @@ impl Plugin for MaterialBrowserPlugin {
             .add_observer(handle_select_material_preview)
             .add_observer(on_material_param_commit)
             .add_observer(handle_create_new_material)
+            .add_observer(handle_duplicate_material)
+            .add_observer(handle_rename_material)
+            .add_observer(on_material_name_commit)
             .add_observer(handle_browse_texture_slot)
             .add_observer(handle_clear_texture_slot);
     }
@@ -85,6 +91,91 @@ impl MaterialRegistry {
     pub fn add(&mut self, name: String, handle: Handle<StandardMaterial>) {
         self.entries.push(MaterialRegistryEntry { name, handle });
     }
+
+    pub fn name_of(&self, handle: &Handle<StandardMaterial>) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.handle == *handle)
+            .map(|e| e.name.as_str())
+    }
+
+    /// `base` if no library material has that name yet, otherwise `base_2`, `base_3`, ...
+    pub fn unique_name(&self, base: &str) -> String {
+        if self.get_by_name(base).is_none() {
+            return base.to_string();
+        }
+        let mut idx = 2u32;
+        loop {
+            let candidate = format!("{base}_{idx}");
+            if self.get_by_name(&candidate).is_none() {
+                return candidate;
+            }
+            idx += 1;
+        }
+    }
+}
+
+/// Replace the contents of a material asset, snapshotting the whole material.
+pub struct SetMaterial {
+    pub handle: Handle<StandardMaterial>,
+    pub old: StandardMaterial,
+    pub new: StandardMaterial,
+}
+
+impl EditorCommand for SetMaterial {
+    fn execute(&self, world: &mut World) {
+        set_material(world, &self.handle, &self.new);
+    }
+
+    fn undo(&self, world: &mut World) {
+        set_material(world, &self.handle, &self.old);
+    }
+
+    fn description(&self) -> &str {
+        "Edit material"
+    }
+}
+
+/// Edit a material asset undoably, snapshotting it before and after.
+pub(crate) fn edit_material(
+    world: &mut World,
+    handle: Handle<StandardMaterial>,
+    edit: impl FnOnce(&mut StandardMaterial),
+) {
+    let Some(old) = world
+        .resource::<Assets<StandardMaterial>>()
+        .get(&handle)
+        .cloned()
+    else {
+        return;
+    };
+    let mut new = old.clone();
+    edit(&mut new);
+    world.resource_scope(|world, mut history: Mut<CommandHistory>| {
+        history.execute(Box::new(SetMaterial { handle, old, new }), world);
+    });
+}
+
+fn set_material(world: &mut World, handle: &Handle<StandardMaterial>, material: &StandardMaterial) {
+    if let Some(mat) = world
+        .resource_mut::<Assets<StandardMaterial>>()
+        .get_mut(handle)
+    {
+        *mat = material.clone();
+    }
+
+    // Library materials are persisted in the catalog
+    let in_library = world
+        .get_resource::<MaterialRegistry>()
+        .is_some_and(|registry| registry.entries.iter().any(|e| e.handle == *handle));
+    if in_library {
+        if let Some(mut catalog) = world.get_resource_mut::<crate::asset_catalog::AssetCatalog>() {
+            catalog.dirty = true;
+        }
+        if let Some(mut preview_state) = world.get_resource_mut::<MaterialPreviewState>() {
+            preview_state.set_changed();
+        }
+    }
 }
 
 #[derive(Resource, Default)]
@@ -143,7 +234,7 @@ enum MaterialParamInput {
 
 /// Identifies a texture slot on `StandardMaterial`.
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum TextureSlot {
+pub(crate) enum TextureSlot {
     BaseColorTexture,
     NormalMapTexture,
     MetallicRoughnessTexture,
@@ -153,7 +244,7 @@ enum TextureSlot {
 }
 
 impl TextureSlot {
-    const ALL: [TextureSlot; 6] = [
+    pub(crate) const ALL: [TextureSlot; 6] = [
         TextureSlot::BaseColorTexture,
         TextureSlot::NormalMapTexture,
         TextureSlot::MetallicRoughnessTexture,
@@ -162,7 +253,7 @@ impl TextureSlot {
         TextureSlot::DepthMap,
     ];
 
-    fn label(self) -> &'static str {
+    pub(crate) fn label(self) -> &'static str {
         match self {
             TextureSlot::BaseColorTexture => "base_color_texture",
             TextureSlot::NormalMapTexture => "normal_map_texture",
@@ -180,7 +271,19 @@ impl TextureSlot {
         )
     }
 
-    fn get_from(self, mat: &StandardMaterial) -> Option<Handle<Image>> {
+    /// Load an image for this slot; only color slots are decoded as sRGB.
+    pub(crate) fn load(self, asset_server: &AssetServer, asset_path: String) -> Handle<Image> {
+        if self.is_srgb() {
+            asset_server.load::<Image>(asset_path)
+        } else {
+            asset_server.load_with_settings::<Image, ImageLoaderSettings>(
+                asset_path,
+                |s: &mut ImageLoaderSettings| s.is_srgb = false,
+            )
+        }
+    }
+
+    pub(crate) fn get_from(self, mat: &StandardMaterial) -> Option<Handle<Image>> {
         match self {
             TextureSlot::BaseColorTexture => mat.base_color_texture.clone(),
             TextureSlot::NormalMapTexture => mat.normal_map_texture.clone(),
@@ -191,7 +294,7 @@ impl TextureSlot {
         }
     }
 
-    fn set_on(self, mat: &mut StandardMaterial, handle: Option<Handle<Image>>) {
+    pub(crate) fn set_on(self, mat: &mut StandardMaterial, handle: Option<Handle<Image>>) {
         match self {
             TextureSlot::BaseColorTexture => mat.base_color_texture = handle,
             TextureSlot::NormalMapTexture => mat.normal_map_texture = handle,
@@ -229,6 +332,21 @@ impl TextureSlot {
 #[derive(Event)]
 struct CreateNewMaterial;
 
+#[derive(Event)]
+struct DuplicateMaterial {
+    handle: Handle<StandardMaterial>,
+}
+
+#[derive(Event)]
+struct RenameMaterial {
+    handle: Handle<StandardMaterial>,
+    name: String,
+}
+
+/// Name input for the material shown in the preview area.
+#[derive(Component)]
+struct MaterialNameInput;
+
 #[derive(Event)]
 struct BrowseTextureSlot {
     slot: TextureSlot,
@@ -258,7 +376,7 @@ pub(crate) fn pbr_filename_regex() -> Option<regex::Regex> {
 ///
 /// Bevy decodes such PNGs as `R16Uint` which is incompatible with
 /// `StandardMaterial`'s float-filterable `depth_map` slot.
-fn is_16bit_png(path: &Path) -> bool {
+pub(crate) fn is_16bit_png(path: &Path) -> bool {
     if !path
         .extension()
         .is_some_and(|e| e.eq_ignore_ascii_case("png"))
@@ -624,7 +742,9 @@ fn handle_apply_material(
     edit_mode: Res<EditMode>,
     selection: Res<Selection>,
     mut brushes: Query<&mut Brush>,
+    mesh_materials: Query<(), With<MeshMaterial3d<StandardMaterial>>>,
     mut history: ResMut<CommandHistory>,
+    mut commands: Commands,
 ) {
     if *edit_mode == EditMode::BrushEdit(BrushEditMode::Face) && !brush_selection.faces.is_empty() {
         if let Some(entity) = brush_selection.entity {
@@ -641,12 +761,15 @@ fn handle_apply_material(
                     new: brush.clone(),
                     label: "Apply material".into(),
                 };
-                history.undo_stack.push(Box::new(cmd));
-                history.redo_stack.clear();
+                history.push_executed(Box::new(cmd));
             }
         }
     } else {
+        let mut mesh_entities = Vec::new();
         for &entity in &selection.entities {
+            if mesh_materials.contains(entity) && !brushes.contains(entity) {
+                mesh_entities.push(entity);
+            }
             if let Ok(mut brush) = brushes.get_mut(entity) {
                 let old = brush.clone();
                 for face in brush.faces.iter_mut() {
@@ -658,13 +781,54 @@ fn handle_apply_material(
                     new: brush.clone(),
                     label: "Apply material".into(),
                 };
-                history.undo_stack.push(Box::new(cmd));
-                history.redo_stack.clear();
+                history.push_executed(Box::new(cmd));
             }
         }
+        if !mesh_entities.is_empty() {
+            let material = event.material.clone();
+            commands.queue(move |world: &mut World| {
+                assign_material_to_meshes(world, &mesh_entities, material);
+            });
+        }
     }
 }
 
+/// Point the `MeshMaterial3d` of each entity at `material`, as one undo step.
+pub(crate) fn assign_material_to_meshes(
+    world: &mut World,
+    entities: &[Entity],
+    material: Handle<StandardMaterial>,
+) {
+    let commands: Vec<Box<dyn EditorCommand>> = entities
+        .iter()
+        .filter_map(|&entity| {
+            let old = world
+                .get::<MeshMaterial3d<StandardMaterial>>(entity)?
+                .clone();
+            let cmd: Box<dyn EditorCommand> = Box::new(SetComponentField {
+                entity,
+                component_type_id: TypeId::of::<MeshMaterial3d<StandardMaterial>>(),
+                field_path: String::new(),
+                old_value: Box::new(old),
+                new_value: Box::new(MeshMaterial3d(material.clone())),
+            });
+            Some(cmd)
+        })
+        .collect();
+    if commands.is_empty() {
+        return;
+    }
+    world.resource_scope(|world, mut history: Mut<CommandHistory>| {
+        history.execute(
+            Box::new(CommandGroup {
+                commands,
+                label: "Assign material".into(),
+            }),
+            world,
+        );
+    });
+}
+
 fn handle_select_material_preview(
     event: On<SelectMaterialPreview>,
     mut preview_state: ResMut<MaterialPreviewState>,
@@ -740,53 +904,43 @@ fn update_preview_area(
         ChildOf(container),
     ));
 
-    // Material name
+    // Material name; renaming a scene material adds it to the library
     let active_name = registry
-        .entries
-        .iter()
-        .find(|e| e.handle == *active_handle)
-        .map(|e| e.name.clone())
-        .unwrap_or_else(|| format!("{:?}", active_handle.id()));
+        .name_of(active_handle)
+        .map(str::to_string)
+        .unwrap_or_else(|| scene_material_label(active_handle));
     commands.spawn((
         PreviewAreaLabel,
-        Text::new(active_name),
-        TextFont {
-            font_size: tokens::FONT_SM,
-            ..Default::default()
-        },
-        TextColor(tokens::TEXT_PRIMARY),
+        MaterialNameInput,
+        text_edit::text_edit(
+            TextEditProps::default()
+                .with_placeholder("Material name")
+                .with_default_value(active_name),
+        ),
         Node {
-            align_self: AlignSelf::Center,
+            width: Val::Percent(100.0),
             margin: UiRect::vertical(Val::Px(tokens::SPACING_XS)),
             ..Default::default()
         },
         ChildOf(container),
     ));
 
-    // Apply button
-    let handle_for_apply = active_handle.clone();
-    let apply_btn = commands
+    let buttons = commands
         .spawn((
             Node {
-                padding: UiRect::axes(Val::Px(tokens::SPACING_MD), Val::Px(tokens::SPACING_XS)),
-                border_radius: BorderRadius::all(Val::Px(tokens::BORDER_RADIUS_SM)),
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(tokens::SPACING_XS),
                 align_self: AlignSelf::Center,
                 margin: UiRect::top(Val::Px(tokens::SPACING_XS)),
                 ..Default::default()
             },
-            BackgroundColor(tokens::INPUT_BG),
             ChildOf(container),
         ))
         .id();
-    commands.spawn((
-        Text::new("Apply"),
-        TextFont {
-            font_size: tokens::FONT_SM,
-            ..Default::default()
-        },
-        TextColor(tokens::TEXT_PRIMARY),
-        ChildOf(apply_btn),
-    ));
+
+    // Apply to the selected faces, brushes or meshes
+    let handle_for_apply = active_handle.clone();
+    let apply_btn = spawn_preview_button(&mut commands, buttons, "Apply");
     commands
         .entity(apply_btn)
         .observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
@@ -794,20 +948,16 @@ fn update_preview_area(
                 material: handle_for_apply.clone(),
             });
         });
-    commands.entity(apply_btn).observe(
-        |hover: On<Pointer<Over>>, mut bg: Query<&mut BackgroundColor>| {
-            if let Ok(mut bg) = bg.get_mut(hover.event_target()) {
-                bg.0 = tokens::HOVER_BG;
-            }
-        },
-    );
-    commands.entity(apply_btn).observe(
-        |out: On<Pointer<Out>>, mut bg: Query<&mut BackgroundColor>| {
-            if let Ok(mut bg) = bg.get_mut(out.event_target()) {
-                bg.0 = tokens::INPUT_BG;
-            }
-        },
-    );
+
+    let handle_for_duplicate = active_handle.clone();
+    let duplicate_btn = spawn_preview_button(&mut commands, buttons, "Duplicate");
+    commands
+        .entity(duplicate_btn)
+        .observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+            commands.trigger(DuplicateMaterial {
+                handle: handle_for_duplicate.clone(),
+            });
+        });
 
     // Material parameter sliders
     let Some(mat) = materials.get(active_handle) else {
@@ -1064,15 +1214,51 @@ fn update_preview_area(
     );
 }
 
+fn spawn_preview_button(commands: &mut Commands, parent: Entity, label: &str) -> Entity {
+    let btn = commands
+        .spawn((
+            Node {
+                padding: UiRect::axes(Val::Px(tokens::SPACING_MD), Val::Px(tokens::SPACING_XS)),
+                border_radius: BorderRadius::all(Val::Px(tokens::BORDER_RADIUS_SM)),
+                ..Default::default()
+            },
+            BackgroundColor(tokens::INPUT_BG),
+            ChildOf(parent),
+        ))
+        .id();
+    commands.spawn((
+        Text::new(label),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_PRIMARY),
+        ChildOf(btn),
+    ));
+    commands.entity(btn).observe(
+        |hover: On<Pointer<Over>>, mut bg: Query<&mut BackgroundColor>| {
+            if let Ok(mut bg) = bg.get_mut(hover.event_target()) {
+                bg.0 = tokens::HOVER_BG;
+            }
+        },
+    );
+    commands.entity(btn).observe(
+        |out: On<Pointer<Out>>, mut bg: Query<&mut BackgroundColor>| {
+            if let Ok(mut bg) = bg.get_mut(out.event_target()) {
+                bg.0 = tokens::INPUT_BG;
+            }
+        },
+    );
+    btn
+}
+
 /// Handle TextEditCommitEvent for material parameter inputs.
 fn on_material_param_commit(
     event: On<TextEditCommitEvent>,
     param_query: Query<&MaterialParamInput>,
     child_of_query: Query<&ChildOf>,
     preview_state: Res<MaterialPreviewState>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    registry: Res<MaterialRegistry>,
-    mut catalog: ResMut<crate::asset_catalog::AssetCatalog>,
+    mut commands: Commands,
 ) {
     // Walk up the hierarchy to find a MaterialParamInput marker
     let mut current = event.entity;
@@ -1094,28 +1280,16 @@ fn on_material_param_commit(
     };
     let value: f32 = event.text.parse().unwrap_or(0.0);
 
-    let Some(mat) = materials.get_mut(active_handle) else {
-        return;
-    };
-    match param {
-        MaterialParamInput::ParallaxDepthScale => mat.parallax_depth_scale = value,
-        MaterialParamInput::MaxParallaxLayers => mat.max_parallax_layer_count = value,
-        MaterialParamInput::PerceptualRoughness => mat.perceptual_roughness = value,
-        MaterialParamInput::Metallic => mat.metallic = value,
-        MaterialParamInput::Reflectance => mat.reflectance = value,
-    }
-
-    // Persist to catalog
-    let catalog_name = registry
-        .entries
-        .iter()
-        .find(|e| e.handle == *active_handle)
-        .map(|e| format!("@{}", e.name));
-    if let Some(name) = catalog_name {
-        if catalog.contains_name(&name) {
-            catalog.dirty = true;
-        }
-    }
+    let handle = active_handle.clone();
+    commands.queue(move |world: &mut World| {
+        edit_material(world, handle, |mat| match param {
+            MaterialParamInput::ParallaxDepthScale => mat.parallax_depth_scale = value,
+            MaterialParamInput::MaxParallaxLayers => mat.max_parallax_layer_count = value,
+            MaterialParamInput::PerceptualRoughness => mat.perceptual_roughness = value,
+            MaterialParamInput::Metallic => mat.metallic = value,
+            MaterialParamInput::Reflectance => mat.reflectance = value,
+        });
+    });
 }
 
 fn spawn_material_folder_dialog(
@@ -1182,6 +1356,92 @@ fn handle_create_new_material(
     preview_state.zoom_distance = 3.0;
 }
 
+fn handle_duplicate_material(
+    event: On<DuplicateMaterial>,
+    mut registry: ResMut<MaterialRegistry>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut catalog: ResMut<crate::asset_catalog::AssetCatalog>,
+    mut preview_state: ResMut<MaterialPreviewState>,
+) {
+    let Some(material) = materials.get(&event.handle).cloned() else {
+        return;
+    };
+    let base = registry
+        .name_of(&event.handle)
+        .map(str::to_string)
+        .unwrap_or_else(|| scene_material_label(&event.handle));
+    let name = registry.unique_name(&format!("{base}_copy"));
+
+    let handle = materials.add(material);
+    catalog.insert(format!("@{name}"), handle.clone().untyped());
+    catalog.dirty = true;
+    registry.add(name, handle.clone());
+    preview_state.active_material = Some(handle);
+}
+
+/// Rename a library material, or add a scene material to the library under `name`.
+fn handle_rename_material(
+    event: On<RenameMaterial>,
+    mut registry: ResMut<MaterialRegistry>,
+    mut catalog: ResMut<crate::asset_catalog::AssetCatalog>,
+    mut preview_state: ResMut<MaterialPreviewState>,
+) {
+    let name = event.name.trim();
+    if name.is_empty() || registry.name_of(&event.handle) == Some(name) {
+        return;
+    }
+    if registry.get_by_name(name).is_some() {
+        warn!("A material named '{name}' already exists");
+        // Rebuild the preview area to put the old name back
+        preview_state.set_changed();
+        return;
+    }
+
+    match registry
+        .entries
+        .iter()
+        .position(|e| e.handle == event.handle)
+    {
+        Some(index) => {
+            let old_catalog_name = format!("@{}", registry.entries[index].name);
+            catalog.handles.remove(&old_catalog_name);
+            for entries in catalog.assets.0.values_mut() {
+                entries.remove(&old_catalog_name);
+            }
+            registry.entries[index].name = name.to_string();
+        }
+        None => registry.add(name.to_string(), event.handle.clone()),
+    }
+    catalog.insert(format!("@{name}"), event.handle.clone().untyped());
+    catalog.dirty = true;
+}
+
+fn on_material_name_commit(
+    event: On<TextEditCommitEvent>,
+    inputs: Query<(), With<MaterialNameInput>>,
+    child_of_query: Query<&ChildOf>,
+    preview_state: Res<MaterialPreviewState>,
+    mut commands: Commands,
+) {
+    let Some(ref active_handle) = preview_state.active_material else {
+        return;
+    };
+    let mut current = event.entity;
+    for _ in 0..4 {
+        let Ok(child_of) = child_of_query.get(current) else {
+            return;
+        };
+        current = child_of.parent();
+        if inputs.contains(current) {
+            commands.trigger(RenameMaterial {
+                handle: active_handle.clone(),
+                name: event.text.clone(),
+            });
+            return;
+        }
+    }
+}
+
 fn handle_browse_texture_slot(
     event: On<BrowseTextureSlot>,
     mut commands: Commands,
@@ -1233,38 +1493,18 @@ fn poll_texture_slot_pick(world: &mut World) {
     }
 
     let asset_path = path.to_string_lossy().replace('\\', "/");
-    let asset_server = world.resource::<AssetServer>().clone();
-    let image_handle = if slot.is_srgb() {
-        asset_server.load::<Image>(asset_path)
-    } else {
-        asset_server.load_with_settings::<Image, ImageLoaderSettings>(
-            asset_path,
-            |s: &mut ImageLoaderSettings| s.is_srgb = false,
-        )
-    };
-
-    let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
-    if let Some(mat) = materials.get_mut(&material_handle) {
+    let image_handle = slot.load(world.resource::<AssetServer>(), asset_path);
+    edit_material(world, material_handle, |mat| {
         slot.set_on(mat, Some(image_handle));
-    }
-
-    world
-        .resource_mut::<crate::asset_catalog::AssetCatalog>()
-        .dirty = true;
-    world.resource_mut::<MaterialPreviewState>().set_changed();
+    });
 }
 
-fn handle_clear_texture_slot(
-    event: On<ClearTextureSlot>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    mut catalog: ResMut<crate::asset_catalog::AssetCatalog>,
-    mut preview_state: ResMut<MaterialPreviewState>,
-) {
-    if let Some(mat) = materials.get_mut(&event.material_handle) {
-        event.slot.set_on(mat, None);
-    }
-    catalog.dirty = true;
-    preview_state.set_changed();
+fn handle_clear_texture_slot(event: On<ClearTextureSlot>, mut commands: Commands) {
+    let slot = event.slot;
+    let handle = event.material_handle.clone();
+    commands.queue(move |world: &mut World| {
+        edit_material(world, handle, |mat| slot.set_on(mat, None));
+    });
 }
 
 fn update_material_browser_ui(
@@ -1274,8 +1514,26 @@ fn update_material_browser_ui(
     materials: Res<Assets<StandardMaterial>>,
     grid_query: Query<(Entity, Option<&Children>), With<MaterialBrowserGrid>>,
     mut root_label_query: Query<&mut Text, With<MaterialBrowserRootLabel>>,
+    scene_materials: Query<
+        (
+            &MeshMaterial3d<StandardMaterial>,
+            Option<&MaterialOverrideApplied>,
+        ),
+        (Without<EditorEntity>, Without<PreviewSphere>),
+    >,
+    changed_scene_materials: Query<
+        (),
+        (
+            Changed<MeshMaterial3d<StandardMaterial>>,
+            Without<EditorEntity>,
+            Without<PreviewSphere>,
+        ),
+    >,
+    mut removed_scene_materials: RemovedComponents<MeshMaterial3d<StandardMaterial>>,
 ) {
-    let needs_rebuild = registry.is_changed() || state.is_changed();
+    let scene_changed =
+        !changed_scene_materials.is_empty() || removed_scene_materials.read().count() > 0;
+    let needs_rebuild = registry.is_changed() || state.is_changed() || scene_changed;
     if !needs_rebuild {
         return;
     }
@@ -1295,114 +1553,185 @@ fn update_material_browser_ui(
     }
 
     let filter_lower = state.filter.to_lowercase();
+    let matches_filter =
+        |name: &str| filter_lower.is_empty() || name.to_lowercase().contains(&filter_lower);
 
     for entry in &registry.entries {
-        if !filter_lower.is_empty() && !entry.name.to_lowercase().contains(&filter_lower) {
+        if !matches_filter(&entry.name) {
             continue;
         }
+        spawn_material_thumbnail(
+            &mut commands,
+            grid_entity,
+            entry.name.clone(),
+            entry.handle.clone(),
+            &materials,
+        );
+    }
 
-        let name = entry.name.clone();
-        let handle = entry.handle.clone();
-
-        let thumb_entity = commands
-            .spawn((
-                Node {
-                    width: Val::Px(64.0),
-                    height: Val::Px(80.0),
-                    flex_direction: FlexDirection::Column,
-                    align_items: AlignItems::Center,
-                    padding: UiRect::all(Val::Px(2.0)),
-                    border: UiRect::all(Val::Px(1.0)),
-                    border_radius: BorderRadius::all(Val::Px(4.0)),
-                    ..Default::default()
-                },
-                BorderColor::all(Color::NONE),
-                BackgroundColor(Color::NONE),
-                ChildOf(grid_entity),
-            ))
-            .id();
+    // Materials used in the scene that aren't in the library yet
+    let mut scene_handles: Vec<Handle<StandardMaterial>> = Vec::new();
+    for (material, applied) in &scene_materials {
+        // Overridden meshes list the shared material, not their derived copy
+        let handle = match applied {
+            Some(applied) if applied.derived == material.0 => &applied.base,
+            _ => &material.0,
+        };
+        if registry.name_of(handle).is_none() && !scene_handles.contains(handle) {
+            scene_handles.push(handle.clone());
+        }
+    }
+    scene_handles.retain(|handle| matches_filter(&scene_material_label(handle)));
+    if scene_handles.is_empty() {
+        return;
+    }
+    commands.spawn((
+        Text::new("In scene"),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_SECONDARY),
+        Node {
+            width: Val::Percent(100.0),
+            margin: UiRect::top(Val::Px(tokens::SPACING_SM)),
+            ..Default::default()
+        },
+        ChildOf(grid_entity),
+    ));
+    for handle in scene_handles {
+        spawn_material_thumbnail(
+            &mut commands,
+            grid_entity,
+            scene_material_label(&handle),
+            handle,
+            &materials,
+        );
+    }
+}
 
-        // Use base_color_texture as thumbnail if available
-        let thumbnail = materials
-            .get(&handle)
-            .and_then(|m| m.base_color_texture.clone());
+/// Display name for a material that isn't in the library, from its asset path.
+fn scene_material_label(handle: &Handle<StandardMaterial>) -> String {
+    let Some(path) = handle.path() else {
+        return "Material".to_string();
+    };
+    let stem = path
+        .path()
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match path.label() {
+        Some(label) => format!("{stem}_{label}"),
+        None => stem,
+    }
+}
 
-        if let Some(img) = thumbnail {
-            commands.spawn((
-                ImageNode::new(img),
-                Node {
-                    width: Val::Px(56.0),
-                    height: Val::Px(56.0),
-                    ..Default::default()
-                },
-                ChildOf(thumb_entity),
-            ));
-        } else {
-            commands.spawn((
-                Node {
-                    width: Val::Px(56.0),
-                    height: Val::Px(56.0),
-                    ..Default::default()
-                },
-                BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
-                ChildOf(thumb_entity),
-            ));
-        }
+fn spawn_material_thumbnail(
+    commands: &mut Commands,
+    grid_entity: Entity,
+    name: String,
+    handle: Handle<StandardMaterial>,
+    materials: &Assets<StandardMaterial>,
+) {
+    let thumb_entity = commands
+        .spawn((
+            Node {
+                width: Val::Px(64.0),
+                height: Val::Px(80.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(Val::Px(2.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                border_radius: BorderRadius::all(Val::Px(4.0)),
+                ..Default::default()
+            },
+            BorderColor::all(Color::NONE),
+            BackgroundColor(Color::NONE),
+            ChildOf(grid_entity),
+        ))
+        .id();
 
-        let is_truncated = name.len() > 10;
-        let display_name = if is_truncated {
-            format!("{}...", &name[..8])
-        } else {
-            name.clone()
-        };
-        let name_entity = commands
-            .spawn((
-                Text::new(display_name),
-                TextFont {
-                    font_size: 9.0,
-                    ..Default::default()
-                },
-                TextColor(tokens::TEXT_SECONDARY),
-                Node {
-                    max_width: Val::Px(60.0),
-                    overflow: Overflow::clip(),
-                    ..Default::default()
-                },
-                ChildOf(thumb_entity),
-            ))
-            .id();
-        if is_truncated {
-            attach_tooltip(&mut commands, name_entity, name.clone());
-        }
+    // Use base_color_texture as thumbnail if available, else a base color swatch
+    let material = materials.get(&handle);
+    let thumbnail = material.and_then(|m| m.base_color_texture.clone());
 
-        // Hover
-        commands.entity(thumb_entity).observe(
-            |hover: On<Pointer<Over>>, mut borders: Query<&mut BorderColor>| {
-                if let Ok(mut border) = borders.get_mut(hover.event_target()) {
-                    *border = BorderColor::all(tokens::SELECTED_BORDER);
-                }
+    if let Some(img) = thumbnail {
+        commands.spawn((
+            ImageNode::new(img),
+            Node {
+                width: Val::Px(56.0),
+                height: Val::Px(56.0),
+                ..Default::default()
             },
-        );
-        commands.entity(thumb_entity).observe(
-            |out: On<Pointer<Out>>, mut borders: Query<&mut BorderColor>| {
-                if let Ok(mut border) = borders.get_mut(out.event_target()) {
-                    *border = BorderColor::all(Color::NONE);
-                }
+            ChildOf(thumb_entity),
+        ));
+    } else {
+        let swatch = material.map_or(Color::srgb(0.3, 0.3, 0.3), |m| m.base_color);
+        commands.spawn((
+            Node {
+                width: Val::Px(56.0),
+                height: Val::Px(56.0),
+                border_radius: BorderRadius::all(Val::Px(28.0)),
+                ..Default::default()
             },
-        );
+            BackgroundColor(swatch),
+            ChildOf(thumb_entity),
+        ));
+    }
 
-        // Single-click: select for preview
-        let handle_for_select = handle.clone();
-        commands.entity(thumb_entity).observe(
-            move |click: On<Pointer<Click>>, mut commands: Commands| {
-                if click.event().button == PointerButton::Primary {
-                    commands.trigger(SelectMaterialPreview {
-                        handle: handle_for_select.clone(),
-                    });
-                }
+    let is_truncated = name.len() > 10;
+    let display_name = if is_truncated {
+        format!("{}...", &name[..8])
+    } else {
+        name.clone()
+    };
+    let name_entity = commands
+        .spawn((
+            Text::new(display_name),
+            TextFont {
+                font_size: 9.0,
+                ..Default::default()
             },
-        );
+            TextColor(tokens::TEXT_SECONDARY),
+            Node {
+                max_width: Val::Px(60.0),
+                overflow: Overflow::clip(),
+                ..Default::default()
+            },
+            ChildOf(thumb_entity),
+        ))
+        .id();
+    if is_truncated {
+        attach_tooltip(commands, name_entity, name.clone());
     }
+
+    // Hover
+    commands.entity(thumb_entity).observe(
+        |hover: On<Pointer<Over>>, mut borders: Query<&mut BorderColor>| {
+            if let Ok(mut border) = borders.get_mut(hover.event_target()) {
+                *border = BorderColor::all(tokens::SELECTED_BORDER);
+            }
+        },
+    );
+    commands.entity(thumb_entity).observe(
+        |out: On<Pointer<Out>>, mut borders: Query<&mut BorderColor>| {
+            if let Ok(mut border) = borders.get_mut(out.event_target()) {
+                *border = BorderColor::all(Color::NONE);
+            }
+        },
+    );
+
+    // Single-click: select for preview
+    let handle_for_select = handle.clone();
+    commands.entity(thumb_entity).observe(
+        move |click: On<Pointer<Click>>, mut commands: Commands| {
+            if click.event().button == PointerButton::Primary {
+                commands.trigger(SelectMaterialPreview {
+                    handle: handle_for_select.clone(),
+                });
+            }
+        },
+    );
 }
 
 pub fn material_browser_panel(icon_font: Handle<Font>) -> impl Bundle {
@@ -1419,6 +1748,7 @@ pub fn material_browser_panel(icon_font: Handle<Font>) -> impl Bundle {
         children![
             // Header
             (
+                PanelHeader,
                 Node {
                     flex_direction: FlexDirection::Row,
                     align_items: AlignItems::Center,
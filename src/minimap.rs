@@ -0,0 +1,319 @@
+//! Top-down overview of the scene: footprints of scene meshes and lights, and the view
+//! cone of the viewport camera, drawn with gizmos seen only by an orthographic camera
+//! looking straight down. Clicking the map moves the fly camera to that spot.
+
+use bevy::{
+    camera::{RenderTarget, ScalingMode, primitives::Aabb, visibility::RenderLayers},
+    prelude::*,
+    render::render_resource::TextureFormat,
+    ui::UiGlobalTransform,
+};
+use jackdaw_feathers::{panel_header, tokens};
+
+use crate::{EditorEntity, selection::Selected, viewport::MainViewportCamera};
+
+const MINIMAP_LAYER: usize = 2;
+const MINIMAP_RESOLUTION: u32 = 256;
+/// Height the minimap camera looks down from; everything is drawn flat at y = 0.
+const MINIMAP_CAMERA_HEIGHT: f32 = 500.0;
+/// Smallest stretch of ground the map shows, so a near-empty scene isn't zoomed in on.
+const MIN_EXTENT: f32 = 20.0;
+/// Margin around the scene bounds, relative to their size.
+const BOUNDS_MARGIN: f32 = 0.1;
+/// Length of the camera's view cone, relative to the map's extent.
+const VIEW_CONE_LENGTH: f32 = 0.15;
+
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_gizmo_group::<MinimapGizmoGroup>()
+            .init_resource::<MinimapBounds>()
+            .add_systems(Startup, configure_minimap_gizmos)
+            .add_systems(
+                OnEnter(crate::AppState::Editor),
+                setup_minimap.after(crate::spawn_layout),
+            )
+            .add_systems(
+                Update,
+                (update_minimap_camera, draw_minimap)
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+/// Gizmos drawn on the minimap only.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct MinimapGizmoGroup;
+
+/// The UI node showing the minimap image.
+#[derive(Component)]
+pub struct MinimapView;
+
+#[derive(Component)]
+struct MinimapCamera;
+
+/// Square area of the ground plane (XZ) the minimap shows.
+#[derive(Resource)]
+struct MinimapBounds {
+    center: Vec2,
+    extent: f32,
+}
+
+impl Default for MinimapBounds {
+    fn default() -> Self {
+        Self {
+            center: Vec2::ZERO,
+            extent: MIN_EXTENT,
+        }
+    }
+}
+
+pub fn minimap_panel() -> impl Bundle {
+    (
+        EditorEntity,
+        Node {
+            width: percent(100),
+            height: percent(100),
+            flex_direction: FlexDirection::Column,
+            overflow: Overflow::clip(),
+            ..Default::default()
+        },
+        BackgroundColor(tokens::PANEL_BG),
+        children![
+            panel_header::panel_header("Minimap"),
+            (
+                MinimapView,
+                Node {
+                    width: percent(100),
+                    aspect_ratio: Some(1.0),
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+            )
+        ],
+    )
+}
+
+fn configure_minimap_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
+    let (config, _) = config_store.config_mut::<MinimapGizmoGroup>();
+    config.render_layers = RenderLayers::layer(MINIMAP_LAYER);
+    config.line.width = 1.5;
+}
+
+fn setup_minimap(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    view: Single<Entity, With<MinimapView>>,
+) {
+    let image = images.add(Image::new_target_texture(
+        MINIMAP_RESOLUTION,
+        MINIMAP_RESOLUTION,
+        TextureFormat::Rgba8Unorm,
+        Some(TextureFormat::Rgba8UnormSrgb),
+    ));
+
+    commands.spawn((
+        MinimapCamera,
+        EditorEntity,
+        Camera3d::default(),
+        Camera {
+            order: -2,
+            clear_color: ClearColorConfig::Custom(tokens::WINDOW_BG),
+            ..default()
+        },
+        RenderTarget::Image(image.clone().into()),
+        Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed {
+                width: MIN_EXTENT,
+                height: MIN_EXTENT,
+            },
+            far: MINIMAP_CAMERA_HEIGHT * 2.0,
+            ..OrthographicProjection::default_3d()
+        }),
+        Transform::from_xyz(0.0, MINIMAP_CAMERA_HEIGHT, 0.0).looking_to(Vec3::NEG_Y, Vec3::NEG_Z),
+        RenderLayers::layer(MINIMAP_LAYER),
+    ));
+
+    commands
+        .entity(*view)
+        .insert(ImageNode::new(image))
+        .observe(on_minimap_click);
+}
+
+/// Ground-plane bounds of a mesh entity.
+fn footprint(aabb: &Aabb, global: &GlobalTransform) -> (Vec2, Vec2) {
+    let (center, half) = (Vec3::from(aabb.center), Vec3::from(aabb.half_extents));
+    let (mut min, mut max) = (Vec2::MAX, Vec2::MIN);
+    for i in 0..8 {
+        let corner = center
+            + half
+                * Vec3::new(
+                    if i & 1 == 0 { -1.0 } else { 1.0 },
+                    if i & 2 == 0 { -1.0 } else { 1.0 },
+                    if i & 4 == 0 { -1.0 } else { 1.0 },
+                );
+        let world = global.transform_point(corner);
+        min = min.min(world.xz());
+        max = max.max(world.xz());
+    }
+    (min, max)
+}
+
+/// Fit the map to the scene and the viewport camera.
+fn update_minimap_camera(
+    mut bounds: ResMut<MinimapBounds>,
+    meshes: Query<
+        (&Aabb, &GlobalTransform),
+        (With<Mesh3d>, Without<EditorEntity>, Without<RenderLayers>),
+    >,
+    main_camera: Query<&GlobalTransform, With<MainViewportCamera>>,
+    mut minimap_camera: Query<(&mut Transform, &mut Projection), With<MinimapCamera>>,
+) {
+    let (mut min, mut max) = (Vec2::MAX, Vec2::MIN);
+    for (aabb, global) in &meshes {
+        let (lo, hi) = footprint(aabb, global);
+        min = min.min(lo);
+        max = max.max(hi);
+    }
+    if let Ok(camera) = main_camera.single() {
+        min = min.min(camera.translation().xz());
+        max = max.max(camera.translation().xz());
+    }
+    if min.x > max.x {
+        (min, max) = (Vec2::ZERO, Vec2::ZERO);
+    }
+    let center = (min + max) / 2.0;
+    let extent = ((max - min).max_element() * (1.0 + BOUNDS_MARGIN * 2.0)).max(MIN_EXTENT);
+    if bounds.center != center || bounds.extent != extent {
+        bounds.center = center;
+        bounds.extent = extent;
+    }
+
+    let Ok((mut transform, mut projection)) = minimap_camera.single_mut() else {
+        return;
+    };
+    transform.translation = Vec3::new(center.x, MINIMAP_CAMERA_HEIGHT, center.y);
+    if let Projection::Orthographic(ortho) = &mut *projection {
+        ortho.scaling_mode = ScalingMode::Fixed {
+            width: extent,
+            height: extent,
+        };
+    }
+}
+
+fn draw_minimap(
+    mut gizmos: Gizmos<MinimapGizmoGroup>,
+    bounds: Res<MinimapBounds>,
+    meshes: Query<
+        (&Aabb, &GlobalTransform, Has<Selected>, Option<&ChildOf>),
+        (With<Mesh3d>, Without<EditorEntity>, Without<RenderLayers>),
+    >,
+    selected: Query<(), With<Selected>>,
+    lights: Query<
+        (&GlobalTransform, Has<Selected>),
+        (
+            Or<(With<PointLight>, With<SpotLight>, With<DirectionalLight>)>,
+            Without<EditorEntity>,
+        ),
+    >,
+    main_camera: Query<(&GlobalTransform, &Projection, &Camera), With<MainViewportCamera>>,
+) {
+    let flat = |p: Vec2| Vec3::new(p.x, 0.0, p.y);
+
+    for (aabb, global, is_selected, parent) in &meshes {
+        // Brush faces are selected through their brush
+        let is_selected = is_selected || parent.is_some_and(|p| selected.contains(p.parent()));
+        let color = if is_selected {
+            tokens::TEXT_ACCENT
+        } else {
+            tokens::CATEGORY_MESH
+        };
+        let (min, max) = footprint(aabb, global);
+        gizmos.linestrip(
+            [
+                flat(min),
+                flat(Vec2::new(max.x, min.y)),
+                flat(max),
+                flat(Vec2::new(min.x, max.y)),
+                flat(min),
+            ],
+            color,
+        );
+    }
+
+    let marker_radius = bounds.extent * 0.01;
+    for (global, is_selected) in &lights {
+        let color = if is_selected {
+            tokens::TEXT_ACCENT
+        } else {
+            tokens::CATEGORY_LIGHT
+        };
+        gizmos.circle(
+            Isometry3d::new(
+                flat(global.translation().xz()),
+                Quat::from_rotation_x(std::f32::consts::FRAC_PI_2),
+            ),
+            marker_radius,
+            color,
+        );
+    }
+
+    let Ok((global, projection, camera)) = main_camera.single() else {
+        return;
+    };
+    let position = flat(global.translation().xz());
+    gizmos.circle(
+        Isometry3d::new(position, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+        marker_radius,
+        tokens::CATEGORY_CAMERA,
+    );
+    // Looking straight up or down leaves no direction to draw the cone in
+    let Some(forward) = (global.forward().as_vec3() * Vec3::new(1.0, 0.0, 1.0)).try_normalize()
+    else {
+        return;
+    };
+    let half_angle = match projection {
+        Projection::Perspective(perspective) => {
+            let aspect = camera
+                .logical_viewport_size()
+                .map_or(perspective.aspect_ratio, |size| size.x / size.y.max(1.0));
+            ((perspective.fov / 2.0).tan() * aspect).atan()
+        }
+        _ => 0.0,
+    };
+    let length = bounds.extent * VIEW_CONE_LENGTH;
+    let left = position + Quat::from_rotation_y(half_angle) * forward * length;
+    let right = position + Quat::from_rotation_y(-half_angle) * forward * length;
+    gizmos.linestrip([position, left, right, position], tokens::CATEGORY_CAMERA);
+}
+
+/// Move the fly camera above the clicked spot, keeping its height and orientation.
+fn on_minimap_click(
+    event: On<Pointer<Click>>,
+    nodes: Query<(&ComputedNode, &UiGlobalTransform)>,
+    bounds: Res<MinimapBounds>,
+    mut cameras: Query<&mut Transform, With<MainViewportCamera>>,
+) {
+    if event.button != PointerButton::Primary {
+        return;
+    }
+    let Ok((computed, node_transform)) = nodes.get(event.event_target()) else {
+        return;
+    };
+    // Node transforms and sizes are in physical pixels, the pointer in logical ones
+    let scale = computed.inverse_scale_factor();
+    let size = computed.size() * scale;
+    if size.x <= 0.0 || size.y <= 0.0 {
+        return;
+    }
+    let top_left = node_transform.translation * scale - size / 2.0;
+    let normalized = (event.pointer_location.position - top_left) / size;
+    // Up on the map is -Z, right is +X
+    let target = bounds.center + (normalized - Vec2::splat(0.5)) * bounds.extent;
+    for mut transform in &mut cameras {
+        transform.translation.x = target.x;
+        transform.translation.z = target.y;
+    }
+}
@@ -3,15 +3,16 @@ use bevy::{
     picking::mesh_picking::ray_cast::{MeshRayCast, MeshRayCastSettings, RayCastVisibility},
     prelude::*,
     ui::UiGlobalTransform,
-    window::{CursorGrabMode, CursorOptions},
+    window::{CursorGrabMode, CursorOptions, PrimaryWindow},
 };
 
 use crate::{
     commands::{CommandHistory, SetTransform},
+    drag_collision::DragCollision,
     gizmos::{GizmoAxis, GizmoDragState, GizmoHoverState, GizmoMode},
     selection::{Selected, Selection},
     snapping::SnapSettings,
-    viewport::{MainViewportCamera, SceneViewport},
+    viewport::{MainViewportCamera, OrbitCameras, SceneViewport, orbit_modifier_held},
     viewport_util::window_to_viewport_cursor,
 };
 
@@ -36,12 +37,90 @@ pub struct ModalTransformState {
     pub active: Option<ActiveModal>,
 }
 
+/// Blender-style keys: G/R/S start a modal grab/rotate/scale of the selection.
+/// Off by default, since R and S already switch gizmo modes and move the camera.
+#[derive(Resource, Default)]
+pub struct ModalTransformSettings {
+    pub blender_keys: bool,
+}
+
 pub struct ActiveModal {
     pub op: ModalOp,
     pub entity: Entity,
     pub start_transform: Transform,
     pub constraint: ModalConstraint,
+    /// Constrain to the entity's own axes instead of the world axes.
+    pub local: bool,
     pub start_cursor: Vec2,
+    /// Value typed on the keyboard; replaces the mouse movement while not empty.
+    pub numeric: String,
+    /// Current change from the start: offset for grab, degrees (in `x`) for rotate,
+    /// factors for scale.
+    pub delta: Vec3,
+}
+
+impl ActiveModal {
+    /// The typed value, if any. Partial input like `-` or `.` counts as zero.
+    pub fn typed_value(&self) -> Option<f32> {
+        (!self.numeric.is_empty()).then(|| self.numeric.parse().unwrap_or(0.0))
+    }
+
+    /// Direction of `axis`, following the entity's rotation for local constraints.
+    fn axis_dir(&self, axis: GizmoAxis) -> Vec3 {
+        let dir = axis_to_vec3(axis);
+        if self.local {
+            self.start_transform.rotation * dir
+        } else {
+            dir
+        }
+    }
+
+    /// The two axes spanning the plane that excludes `excluded`.
+    fn plane_dirs(&self, excluded: GizmoAxis) -> [Vec3; 2] {
+        let [a, b] = match excluded {
+            GizmoAxis::X => [GizmoAxis::Y, GizmoAxis::Z],
+            GizmoAxis::Y => [GizmoAxis::X, GizmoAxis::Z],
+            GizmoAxis::Z => [GizmoAxis::X, GizmoAxis::Y],
+        };
+        [self.axis_dir(a), self.axis_dir(b)]
+    }
+
+    /// Axis rotations turn around; free rotation turns around world Y.
+    fn rotation_axis(&self) -> Vec3 {
+        match self.constraint {
+            ModalConstraint::Free | ModalConstraint::Plane(_) => Vec3::Y,
+            ModalConstraint::Axis(axis) => self.axis_dir(axis),
+        }
+    }
+
+    /// Start scale multiplied by `factor` on the constrained axes. Scale is always
+    /// applied along the entity's own axes.
+    fn scaled(&self, factor: f32) -> Vec3 {
+        let start = self.start_transform.scale;
+        let affects = |axis: GizmoAxis| match self.constraint {
+            ModalConstraint::Free => true,
+            ModalConstraint::Axis(constrained) => axis == constrained,
+            ModalConstraint::Plane(excluded) => axis != excluded,
+        };
+        Vec3::new(
+            if affects(GizmoAxis::X) {
+                start.x * factor
+            } else {
+                start.x
+            },
+            if affects(GizmoAxis::Y) {
+                start.y * factor
+            } else {
+                start.y
+            },
+            if affects(GizmoAxis::Z) {
+                start.z * factor
+            } else {
+                start.z
+            },
+        )
+        .max(Vec3::splat(0.01))
+    }
 }
 
 #[derive(Resource, Default)]
@@ -72,11 +151,10 @@ pub struct ModalTransformPlugin;
 
 impl Plugin for ModalTransformPlugin {
     fn build(&self, app: &mut App) {
-        // ModalTransformState is kept so other systems can check `modal.active.is_some()`.
-        // Modal activate/constrain/update/confirm/cancel/draw systems are disabled
-        // (G/R/S no longer trigger modal transforms — TrenchBroom-style keybinds instead).
-        // The code is preserved in this file for a future Blender keymap option.
+        // G/R/S modal transforms only start with the Blender keys enabled (View menu);
+        // by default the TrenchBroom-style keybinds apply.
         app.init_resource::<ModalTransformState>()
+            .init_resource::<ModalTransformSettings>()
             .init_resource::<ViewportDragState>()
             .add_systems(
                 Update,
@@ -88,11 +166,25 @@ impl Plugin for ModalTransformPlugin {
                 )
                     .chain()
                     .run_if(in_state(crate::AppState::Editor)),
+            )
+            .add_systems(
+                Update,
+                (
+                    modal_activate
+                        .run_if(|settings: Res<ModalTransformSettings>| settings.blender_keys),
+                    modal_constrain,
+                    modal_numeric_input,
+                    modal_update,
+                    modal_confirm,
+                    modal_cancel,
+                    modal_draw,
+                )
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
             );
     }
 }
 
-#[allow(dead_code)]
 fn modal_activate(
     keyboard: Res<ButtonInput<KeyCode>>,
     input_focus: Res<InputFocus>,
@@ -101,8 +193,8 @@ fn modal_activate(
     gizmo_drag: Res<GizmoDragState>,
     mut modal: ResMut<ModalTransformState>,
     mut gizmo_mode: ResMut<GizmoMode>,
-    windows: Query<&Window>,
-    mut cursor_query: Query<&mut CursorOptions, With<Window>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut cursor_query: Query<&mut CursorOptions, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
     edit_mode: Res<crate::brush::EditMode>,
@@ -119,7 +211,7 @@ fn modal_activate(
 
     let ctrl = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
 
-    let op = if keyboard.just_pressed(KeyCode::KeyG) {
+    let op = if keyboard.just_pressed(KeyCode::KeyG) && !ctrl {
         Some(ModalOp::Grab)
     } else if keyboard.just_pressed(KeyCode::KeyR) && !ctrl {
         Some(ModalOp::Rotate)
@@ -148,15 +240,21 @@ fn modal_activate(
     let Ok((camera, _)) = camera_query.single() else {
         return;
     };
-    let viewport_cursor =
-        window_to_viewport_cursor(cursor_pos, camera, &viewport_query).unwrap_or(cursor_pos);
+    // Only over the viewport, so typing elsewhere never starts a transform
+    let Some(viewport_cursor) = window_to_viewport_cursor(cursor_pos, camera, &viewport_query)
+    else {
+        return;
+    };
 
     modal.active = Some(ActiveModal {
         op,
         entity: primary,
         start_transform: *transform,
         constraint: ModalConstraint::Free,
+        local: false,
         start_cursor: viewport_cursor,
+        numeric: String::new(),
+        delta: Vec3::ZERO,
     });
 
     // Confine cursor during modal transform
@@ -172,7 +270,6 @@ fn modal_activate(
     }
 }
 
-#[allow(dead_code)]
 fn modal_constrain(keyboard: Res<ButtonInput<KeyCode>>, mut modal: ResMut<ModalTransformState>) {
     let Some(ref mut active) = modal.active else {
         return;
@@ -180,43 +277,102 @@ fn modal_constrain(keyboard: Res<ButtonInput<KeyCode>>, mut modal: ResMut<ModalT
 
     let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
 
-    if keyboard.just_pressed(KeyCode::KeyX) {
-        active.constraint = if shift {
-            ModalConstraint::Plane(GizmoAxis::X)
-        } else {
-            ModalConstraint::Axis(GizmoAxis::X)
-        };
+    let axis = if keyboard.just_pressed(KeyCode::KeyX) {
+        GizmoAxis::X
     } else if keyboard.just_pressed(KeyCode::KeyY) {
-        active.constraint = if shift {
-            ModalConstraint::Plane(GizmoAxis::Y)
-        } else {
-            ModalConstraint::Axis(GizmoAxis::Y)
-        };
+        GizmoAxis::Y
     } else if keyboard.just_pressed(KeyCode::KeyZ) {
-        active.constraint = if shift {
-            ModalConstraint::Plane(GizmoAxis::Z)
-        } else {
-            ModalConstraint::Axis(GizmoAxis::Z)
+        GizmoAxis::Z
+    } else {
+        return;
+    };
+    let constraint = if shift {
+        ModalConstraint::Plane(axis)
+    } else {
+        ModalConstraint::Axis(axis)
+    };
+
+    // Same key again: world axis -> local axis -> unconstrained
+    if active.constraint != constraint {
+        active.constraint = constraint;
+        active.local = false;
+    } else if !active.local {
+        active.local = true;
+    } else {
+        active.constraint = ModalConstraint::Free;
+        active.local = false;
+    }
+}
+
+/// Typing a number during a modal transform: digits, `.`, `-` to flip the sign,
+/// Backspace to delete.
+fn modal_numeric_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut modal: ResMut<ModalTransformState>,
+) {
+    let Some(ref mut active) = modal.active else {
+        return;
+    };
+
+    for key in keyboard.get_just_pressed() {
+        let digit = match key {
+            KeyCode::Digit0 | KeyCode::Numpad0 => Some('0'),
+            KeyCode::Digit1 | KeyCode::Numpad1 => Some('1'),
+            KeyCode::Digit2 | KeyCode::Numpad2 => Some('2'),
+            KeyCode::Digit3 | KeyCode::Numpad3 => Some('3'),
+            KeyCode::Digit4 | KeyCode::Numpad4 => Some('4'),
+            KeyCode::Digit5 | KeyCode::Numpad5 => Some('5'),
+            KeyCode::Digit6 | KeyCode::Numpad6 => Some('6'),
+            KeyCode::Digit7 | KeyCode::Numpad7 => Some('7'),
+            KeyCode::Digit8 | KeyCode::Numpad8 => Some('8'),
+            KeyCode::Digit9 | KeyCode::Numpad9 => Some('9'),
+            _ => None,
         };
+        if let Some(digit) = digit {
+            active.numeric.push(digit);
+            continue;
+        }
+        match key {
+            KeyCode::Period | KeyCode::NumpadDecimal if !active.numeric.contains('.') => {
+                active.numeric.push('.');
+            }
+            KeyCode::Minus | KeyCode::NumpadSubtract => {
+                if let Some(rest) = active.numeric.strip_prefix('-') {
+                    active.numeric = rest.to_string();
+                } else {
+                    active.numeric.insert(0, '-');
+                }
+            }
+            KeyCode::Backspace => {
+                active.numeric.pop();
+            }
+            _ => {}
+        }
     }
 }
 
-#[allow(dead_code)]
 fn modal_update(
-    modal: Res<ModalTransformState>,
+    mut modal: ResMut<ModalTransformState>,
     mut transforms: Query<&mut Transform, With<Selected>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     keyboard: Res<ButtonInput<KeyCode>>,
     snap_settings: Res<SnapSettings>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
+    mut collision: DragCollision,
 ) {
-    let Some(ref active) = modal.active else {
+    let Some(ref mut active) = modal.active else {
         return;
     };
     let Ok(mut transform) = transforms.get_mut(active.entity) else {
         return;
     };
+
+    if let Some(value) = active.typed_value() {
+        apply_typed_value(active, &mut transform, value);
+        return;
+    }
+
     let Ok(window) = windows.single() else {
         return;
     };
@@ -243,59 +399,68 @@ fn modal_update(
                 &viewport_query,
                 ctrl,
             );
+            let start = active.start_transform.translation;
+            transform.translation =
+                start + collision.limit(active.entity, transform.translation - start);
         }
         ModalOp::Rotate => {
             let mouse_delta = viewport_cursor - active.start_cursor;
             let raw_angle = mouse_delta.x * 0.01;
             let angle = snap_settings.snap_rotate_if(raw_angle, ctrl);
 
-            let axis_dir = match active.constraint {
-                ModalConstraint::Free | ModalConstraint::Plane(_) => Vec3::Y,
-                ModalConstraint::Axis(axis) => axis_to_vec3(axis),
-            };
-
-            let rotation_delta = Quat::from_axis_angle(axis_dir, angle);
+            let rotation_delta = Quat::from_axis_angle(active.rotation_axis(), angle);
             transform.rotation = rotation_delta * active.start_transform.rotation;
+            active.delta = Vec3::new(angle.to_degrees(), 0.0, 0.0);
+            return;
         }
         ModalOp::Scale => {
             let mouse_delta = viewport_cursor - active.start_cursor;
             let factor = 1.0 + mouse_delta.x * 0.005;
+            transform.scale = snap_settings.snap_scale_vec3_if(active.scaled(factor), ctrl);
+        }
+    }
+    active.delta = modal_delta(active, &transform);
+}
 
-            let mut new_scale = active.start_transform.scale;
-            match active.constraint {
-                ModalConstraint::Free => {
-                    new_scale *= factor;
-                }
-                ModalConstraint::Axis(axis) => match axis {
-                    GizmoAxis::X => {
-                        new_scale.x = (active.start_transform.scale.x * factor).max(0.01)
-                    }
-                    GizmoAxis::Y => {
-                        new_scale.y = (active.start_transform.scale.y * factor).max(0.01)
-                    }
-                    GizmoAxis::Z => {
-                        new_scale.z = (active.start_transform.scale.z * factor).max(0.01)
-                    }
-                },
+/// Apply a typed value: a distance along the constraint for grab (along X when free),
+/// degrees for rotate, a factor for scale.
+fn apply_typed_value(active: &mut ActiveModal, transform: &mut Transform, value: f32) {
+    let start = active.start_transform;
+    match active.op {
+        ModalOp::Grab => {
+            let offset = match active.constraint {
+                ModalConstraint::Free => active.axis_dir(GizmoAxis::X) * value,
+                ModalConstraint::Axis(axis) => active.axis_dir(axis) * value,
                 ModalConstraint::Plane(excluded) => {
-                    if excluded != GizmoAxis::X {
-                        new_scale.x = (active.start_transform.scale.x * factor).max(0.01);
-                    }
-                    if excluded != GizmoAxis::Y {
-                        new_scale.y = (active.start_transform.scale.y * factor).max(0.01);
-                    }
-                    if excluded != GizmoAxis::Z {
-                        new_scale.z = (active.start_transform.scale.z * factor).max(0.01);
-                    }
+                    let [a, b] = active.plane_dirs(excluded);
+                    (a + b) * value
                 }
-            }
-            new_scale = new_scale.max(Vec3::splat(0.01));
-            transform.scale = snap_settings.snap_scale_vec3_if(new_scale, ctrl);
+            };
+            transform.translation = start.translation + offset;
+        }
+        ModalOp::Rotate => {
+            let rotation_delta = Quat::from_axis_angle(active.rotation_axis(), value.to_radians());
+            transform.rotation = rotation_delta * start.rotation;
+            active.delta = Vec3::new(value, 0.0, 0.0);
+            return;
+        }
+        ModalOp::Scale => {
+            transform.scale = active.scaled(value);
         }
     }
+    active.delta = modal_delta(active, transform);
+}
+
+/// Change from the start transform for grab and scale, as shown in the status bar.
+fn modal_delta(active: &ActiveModal, transform: &Transform) -> Vec3 {
+    let start = active.start_transform;
+    match active.op {
+        ModalOp::Grab => transform.translation - start.translation,
+        ModalOp::Rotate => active.delta,
+        ModalOp::Scale => transform.scale / start.scale.max(Vec3::splat(f32::EPSILON)),
+    }
 }
 
-#[allow(dead_code)]
 fn modal_grab(
     active: &ActiveModal,
     transform: &mut Transform,
@@ -326,7 +491,7 @@ fn modal_grab(
             transform.translation = start_pos + snapped_offset;
         }
         ModalConstraint::Axis(axis) => {
-            let axis_dir = axis_to_vec3(axis);
+            let axis_dir = active.axis_dir(axis);
             let gizmo_pos = active.start_transform.translation;
 
             let Ok(origin_screen) = camera.world_to_viewport(cam_tf, gizmo_pos) else {
@@ -352,11 +517,7 @@ fn modal_grab(
             let scale = cam_dist * 0.003;
             let mouse_delta = viewport_cursor - active.start_cursor;
 
-            let axes: [Vec3; 2] = match excluded_axis {
-                GizmoAxis::X => [Vec3::Y, Vec3::Z],
-                GizmoAxis::Y => [Vec3::X, Vec3::Z],
-                GizmoAxis::Z => [Vec3::X, Vec3::Y],
-            };
+            let axes = active.plane_dirs(excluded_axis);
 
             let mut offset = Vec3::ZERO;
             for dir in &axes {
@@ -377,14 +538,13 @@ fn modal_grab(
     }
 }
 
-#[allow(dead_code)]
 fn modal_confirm(
     mouse: Res<ButtonInput<MouseButton>>,
     keyboard: Res<ButtonInput<KeyCode>>,
     mut modal: ResMut<ModalTransformState>,
     transforms: Query<&Transform, With<Selected>>,
     mut history: ResMut<CommandHistory>,
-    mut cursor_query: Query<&mut CursorOptions, With<Window>>,
+    mut cursor_query: Query<&mut CursorOptions, With<PrimaryWindow>>,
 ) {
     let Some(ref active) = modal.active else {
         return;
@@ -400,8 +560,7 @@ fn modal_confirm(
             old_transform: active.start_transform,
             new_transform: *transform,
         };
-        history.undo_stack.push(Box::new(cmd));
-        history.redo_stack.clear();
+        history.push_executed(Box::new(cmd));
     }
 
     modal.active = None;
@@ -410,13 +569,12 @@ fn modal_confirm(
     }
 }
 
-#[allow(dead_code)]
 fn modal_cancel(
     mouse: Res<ButtonInput<MouseButton>>,
     keyboard: Res<ButtonInput<KeyCode>>,
     mut modal: ResMut<ModalTransformState>,
     mut transforms: Query<&mut Transform, With<Selected>>,
-    mut cursor_query: Query<&mut CursorOptions, With<Window>>,
+    mut cursor_query: Query<&mut CursorOptions, With<PrimaryWindow>>,
 ) {
     let Some(ref active) = modal.active else {
         return;
@@ -459,7 +617,7 @@ fn snap_toggle(
 fn viewport_drag_detect(
     mouse: Res<ButtonInput<MouseButton>>,
     keyboard: Res<ButtonInput<KeyCode>>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
     selection: Res<Selection>,
@@ -468,16 +626,18 @@ fn viewport_drag_detect(
     modal: Res<ModalTransformState>,
     gizmo_hover: Res<GizmoHoverState>,
     mut drag_state: ResMut<ViewportDragState>,
-    (edit_mode, draw_state, terrain_edit_mode): (
+    (edit_mode, draw_state, terrain_edit_mode, spline_edit): (
         Res<crate::brush::EditMode>,
         Res<crate::draw_brush::DrawBrushState>,
         Res<crate::terrain::TerrainEditMode>,
+        Res<crate::spline::SplineEditState>,
     ),
     mut ray_cast: MeshRayCast,
     parents: Query<&ChildOf>,
     brushes: Query<(), With<jackdaw_jsn::Brush>>,
+    orbit_cameras: OrbitCameras,
 ) {
-    if modal.active.is_some() || gizmo_drag.active || gizmo_hover.hovered_axis.is_some() {
+    if modal.active.is_some() || gizmo_drag.active || gizmo_hover.hovered.is_some() {
         return;
     }
 
@@ -509,8 +669,11 @@ fn viewport_drag_detect(
         }
     }
 
-    // Block viewport drag during brush edit mode or draw mode
-    if *edit_mode != crate::brush::EditMode::Object || draw_state.active.is_some() {
+    // Block viewport drag during brush edit mode, draw mode or spline edit mode
+    if *edit_mode != crate::brush::EditMode::Object
+        || draw_state.active.is_some()
+        || spline_edit.active.is_some()
+    {
         return;
     }
 
@@ -533,7 +696,7 @@ fn viewport_drag_detect(
         }
     }
 
-    if !mouse.just_pressed(MouseButton::Left) {
+    if !mouse.just_pressed(MouseButton::Left) || orbit_modifier_held(&keyboard, &orbit_cameras) {
         return;
     }
 
@@ -597,16 +760,17 @@ fn viewport_drag_detect(
 
 fn viewport_drag_update(
     mouse: Res<ButtonInput<MouseButton>>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
     keyboard: Res<ButtonInput<KeyCode>>,
     snap_settings: Res<SnapSettings>,
     mut drag_state: ResMut<ViewportDragState>,
     mut transforms: Query<&mut Transform>,
-    mut cursor_query: Query<&mut CursorOptions, With<Window>>,
+    mut cursor_query: Query<&mut CursorOptions, With<PrimaryWindow>>,
     edit_mode: Res<crate::brush::EditMode>,
     terrain_edit_mode: Res<crate::terrain::TerrainEditMode>,
+    mut collision: DragCollision,
 ) {
     if !mouse.pressed(MouseButton::Left) {
         drag_state.pending = None;
@@ -703,6 +867,7 @@ fn viewport_drag_update(
     };
 
     let snapped_offset = snap_settings.snap_translate_vec3_if(offset, ctrl);
+    let snapped_offset = collision.limit(active.entity, snapped_offset);
 
     if let Ok(mut transform) = transforms.get_mut(active.entity) {
         transform.translation = start_pos + snapped_offset;
@@ -715,7 +880,7 @@ fn viewport_drag_finish(
     mut drag_state: ResMut<ViewportDragState>,
     mut transforms: Query<&mut Transform>,
     mut history: ResMut<CommandHistory>,
-    mut cursor_query: Query<&mut CursorOptions, With<Window>>,
+    mut cursor_query: Query<&mut CursorOptions, With<PrimaryWindow>>,
 ) {
     // Handle duplicated grab mode (click-to-place, no button held)
     if let Some(ref active) = drag_state.active {
@@ -741,8 +906,7 @@ fn viewport_drag_finish(
                         old_transform: active.start_transform,
                         new_transform: *transform,
                     };
-                    history.undo_stack.push(Box::new(cmd));
-                    history.redo_stack.clear();
+                    history.push_executed(Box::new(cmd));
                 }
                 return;
             }
@@ -767,8 +931,7 @@ fn viewport_drag_finish(
             old_transform: active.start_transform,
             new_transform: *transform,
         };
-        history.undo_stack.push(Box::new(cmd));
-        history.redo_stack.clear();
+        history.push_executed(Box::new(cmd));
     }
 
     // Release cursor confinement
@@ -777,7 +940,6 @@ fn viewport_drag_finish(
     }
 }
 
-#[allow(dead_code)]
 fn modal_draw(
     modal: Res<ModalTransformState>,
     mut gizmos: Gizmos,
@@ -796,14 +958,14 @@ fn modal_draw(
     match active.constraint {
         ModalConstraint::Free => {}
         ModalConstraint::Axis(axis) => {
-            let dir = axis_to_vec3(axis);
+            let dir = active.axis_dir(axis);
             let color = axis_color(axis);
             gizmos.line(pos - dir * line_length, pos + dir * line_length, color);
         }
         ModalConstraint::Plane(excluded) => {
             for axis in [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z] {
                 if axis != excluded {
-                    let dir = axis_to_vec3(axis);
+                    let dir = active.axis_dir(axis);
                     let color = axis_color(axis);
                     gizmos.line(
                         pos - dir * line_length,
@@ -816,7 +978,6 @@ fn modal_draw(
     }
 }
 
-#[allow(dead_code)]
 fn axis_to_vec3(axis: GizmoAxis) -> Vec3 {
     match axis {
         GizmoAxis::X => Vec3::X,
@@ -825,7 +986,6 @@ fn axis_to_vec3(axis: GizmoAxis) -> Vec3 {
     }
 }
 
-#[allow(dead_code)]
 fn axis_color(axis: GizmoAxis) -> Color {
     match axis {
         GizmoAxis::X => Color::srgb(1.0, 0.2, 0.2),
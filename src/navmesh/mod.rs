@@ -6,8 +6,9 @@ mod visualization;
 
 use bevy::prelude::*;
 use bevy_rerecast::{TriMeshFromBevyMesh as _, prelude::*, rerecast::TriMesh};
+use jackdaw_jsn::{TriggerVolume, VisibilityVolume};
 
-use crate::{EditorEntity, EditorMeta};
+use crate::{EditorEntity, EditorMeta, brush::BrushFaceEntity};
 
 pub use toolbar::NavmeshToolbar;
 pub use visualization::NavmeshVizConfig;
@@ -39,14 +40,22 @@ impl Plugin for NavmeshPlugin {
 fn scene_mesh_backend(
     input: In<NavmeshSettings>,
     meshes: Res<Assets<Mesh>>,
-    mesh_entities: Query<(Entity, &GlobalTransform, &Mesh3d), Without<EditorEntity>>,
+    mesh_entities: Query<
+        (Entity, &GlobalTransform, &Mesh3d, Option<&BrushFaceEntity>),
+        Without<EditorEntity>,
+    >,
+    // Trigger and visibility volumes are not walkable geometry
+    volumes: Query<(), Or<(With<TriggerVolume>, With<VisibilityVolume>)>>,
     brp_obstacles: Res<NavmeshObstacles>,
 ) -> TriMesh {
     let mut result = brp_obstacles.0.clone();
-    for (entity, global_tf, mesh_handle) in mesh_entities.iter() {
+    for (entity, global_tf, mesh_handle, face) in mesh_entities.iter() {
         if input.filter.as_ref().is_some_and(|f| !f.contains(&entity)) {
             continue;
         }
+        if face.is_some_and(|face| volumes.contains(face.brush_entity)) {
+            continue;
+        }
         let Some(mesh) = meshes.get(mesh_handle) else {
             continue;
         };
@@ -74,11 +74,18 @@ fn on_save_navmesh(
     _: On<SaveNavmesh>,
     mut commands: Commands,
     raw_handle: Query<&RawHandleWrapper, With<PrimaryWindow>>,
+    project: Option<Res<crate::project::ProjectRoot>>,
 ) {
     let mut dialog = AsyncFileDialog::new()
         .add_filter("Navmesh", &["nav"])
         .set_file_name("navmesh.nav");
 
+    if let Some(project) = project {
+        let export_dir = project.export_dir();
+        let _ = std::fs::create_dir_all(&export_dir);
+        dialog = dialog.set_directory(export_dir);
+    }
+
     if let Ok(rh) = raw_handle.single() {
         // SAFETY: called on the main thread during an observer
         let handle = unsafe { rh.get_handle() };
@@ -0,0 +1,150 @@
+//! Live viewport preview of [`ParticleEmitter`]s. Particles are simulated on the CPU in
+//! world space and drawn with gizmos; nothing here is saved with the scene.
+
+use bevy::prelude::*;
+use jackdaw_jsn::ParticleEmitter;
+
+pub struct ParticlesPlugin;
+
+impl Plugin for ParticlesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (attach_particle_pools, simulate_particles, draw_particles)
+                .chain()
+                .run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+/// Spawns a new emitter entity with default settings.
+pub fn spawn_particle_emitter(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            Name::new("Particle Emitter"),
+            Transform::default(),
+            Visibility::default(),
+            ParticleEmitter::default(),
+        ))
+        .id()
+}
+
+struct Particle {
+    position: Vec3,
+    velocity: Vec3,
+    age: f32,
+}
+
+/// Preview state of one emitter.
+#[derive(Component, Default)]
+struct ParticlePool {
+    particles: Vec<Particle>,
+    /// Fractional particles owed from previous frames.
+    pending: f32,
+    seed: u32,
+}
+
+fn attach_particle_pools(
+    mut commands: Commands,
+    emitters: Query<Entity, (With<ParticleEmitter>, Without<ParticlePool>)>,
+    orphans: Query<Entity, (With<ParticlePool>, Without<ParticleEmitter>)>,
+) {
+    for entity in &emitters {
+        commands.entity(entity).insert(ParticlePool {
+            seed: entity.index_u32().wrapping_mul(0x9E37_79B9) | 1,
+            ..Default::default()
+        });
+    }
+    for entity in &orphans {
+        commands.entity(entity).remove::<ParticlePool>();
+    }
+}
+
+/// xorshift32: a uniform value in 0..1. Previews don't need a better generator.
+fn next_random(seed: &mut u32) -> f32 {
+    *seed ^= *seed << 13;
+    *seed ^= *seed >> 17;
+    *seed ^= *seed << 5;
+    (*seed >> 8) as f32 / (1u32 << 24) as f32
+}
+
+/// A direction within `half_angle` radians of `axis`, uniform over the cone's cap.
+fn cone_direction(seed: &mut u32, axis: Dir3, half_angle: f32) -> Vec3 {
+    let cos_max = half_angle.clamp(0.0, std::f32::consts::PI).cos();
+    let cos_theta = 1.0 - next_random(seed) * (1.0 - cos_max);
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    let phi = next_random(seed) * std::f32::consts::TAU;
+    let local = Vec3::new(sin_theta * phi.cos(), cos_theta, sin_theta * phi.sin());
+    Quat::from_rotation_arc(Vec3::Y, *axis) * local
+}
+
+fn simulate_particles(
+    time: Res<Time>,
+    mut emitters: Query<(
+        &ParticleEmitter,
+        &GlobalTransform,
+        &mut ParticlePool,
+        Option<&InheritedVisibility>,
+    )>,
+) {
+    let dt = time.delta_secs();
+    for (emitter, transform, mut pool, visibility) in &mut emitters {
+        let pool = &mut *pool;
+        if visibility.is_some_and(|v| !v.get()) {
+            pool.particles.clear();
+            pool.pending = 0.0;
+            continue;
+        }
+
+        let lifetime = emitter.lifetime.max(0.0);
+        let gravity = Vec3::NEG_Y * emitter.gravity;
+        pool.particles.retain_mut(|particle| {
+            particle.age += dt;
+            particle.velocity += gravity * dt;
+            particle.position += particle.velocity * dt;
+            particle.age < lifetime
+        });
+
+        pool.pending += emitter.rate.max(0.0) * dt;
+        let room = (emitter.max_particles as usize).saturating_sub(pool.particles.len());
+        let count = (pool.pending.floor() as usize).min(room);
+        pool.pending = if count < room {
+            pool.pending.fract()
+        } else {
+            0.0
+        };
+        let axis = transform.up();
+        let half_angle = emitter.cone_angle.to_radians();
+        for _ in 0..count {
+            let direction = cone_direction(&mut pool.seed, axis, half_angle);
+            pool.particles.push(Particle {
+                position: transform.translation(),
+                velocity: direction * emitter.speed,
+                // Spread spawns across the frame so low frame rates don't emit in bursts
+                age: next_random(&mut pool.seed) * dt,
+            });
+        }
+    }
+}
+
+fn draw_particles(mut gizmos: Gizmos, emitters: Query<(&ParticleEmitter, &ParticlePool)>) {
+    for (emitter, pool) in &emitters {
+        let lifetime = emitter.lifetime.max(f32::EPSILON);
+        let start = emitter.start_color.to_linear();
+        let end = emitter.end_color.to_linear();
+        for particle in &pool.particles {
+            let t = (particle.age / lifetime).clamp(0.0, 1.0);
+            let radius = emitter.size * 0.5 * emitter.size_over_lifetime.sample(t).max(0.0);
+            if radius <= 0.0 {
+                continue;
+            }
+            gizmos
+                .sphere(
+                    Isometry3d::from_translation(particle.position),
+                    radius,
+                    start.mix(&end, t),
+                )
+                .resolution(6);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Player gauge: a translucent player-sized capsule or box standing where the cursor points
+//! in the viewport, with gridlines at step and crouch height, to check doorways and stairs
+//! against the player while blocking out. Sized by the `[player]` section of `project.toml`.
+
+use bevy::{
+    color::palettes::tailwind,
+    light::{NotShadowCaster, NotShadowReceiver},
+    prelude::*,
+    ui::UiGlobalTransform,
+    window::PrimaryWindow,
+};
+
+use crate::{
+    EditorEntity,
+    project::{PlayerDimensions, PlayerShape, ProjectRoot},
+    viewport::{MainViewportCamera, SceneViewport},
+    viewport_util::window_to_viewport_cursor,
+    walk_mode::WalkMode,
+};
+
+/// Half the width of the step and crouch grids around the gauge, in meters.
+const GRID_HALF_EXTENT: f32 = 1.5;
+/// Spacing of the step and crouch gridlines, in meters.
+const GRID_SPACING: f32 = 0.5;
+
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct PlayerGaugeGizmoGroup;
+
+pub struct PlayerGaugePlugin;
+
+impl Plugin for PlayerGaugePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlayerGauge>()
+            .init_gizmo_group::<PlayerGaugeGizmoGroup>()
+            .add_systems(
+                Update,
+                (place_gauge, sync_gauge_mesh, draw_gauge_lines)
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct PlayerGauge {
+    pub enabled: bool,
+    /// Where the gauge's feet stand, when the cursor is over the scene.
+    feet: Option<Vec3>,
+}
+
+pub fn toggle_player_gauge(world: &mut World) {
+    let mut gauge = world.resource_mut::<PlayerGauge>();
+    gauge.enabled = !gauge.enabled;
+}
+
+/// The gauge mesh and the dimensions it was built for.
+#[derive(Component)]
+struct PlayerGaugeMesh(PlayerDimensions);
+
+fn place_gauge(
+    mut gauge: ResMut<PlayerGauge>,
+    walk: Res<WalkMode>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
+    viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
+    editor_entities: Query<(), With<EditorEntity>>,
+    mut ray_cast: MeshRayCast,
+) {
+    let feet = if gauge.enabled && !walk.is_active() {
+        let cursor = windows.single().ok().and_then(Window::cursor_position);
+        cursor
+            .zip(camera_query.single().ok())
+            .and_then(|(cursor, (camera, cam_tf))| {
+                let viewport_cursor = window_to_viewport_cursor(cursor, camera, &viewport_query)?;
+                camera.viewport_to_world(cam_tf, viewport_cursor).ok()
+            })
+            .and_then(|ray| {
+                let filter = |entity| !editor_entities.contains(entity);
+                let settings = MeshRayCastSettings::default().with_filter(&filter);
+                match ray_cast.cast_ray(ray, &settings).first() {
+                    Some((_, hit)) => Some(hit.point),
+                    // Nothing under the cursor: stand on the ground plane
+                    None => ray
+                        .intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y))
+                        .map(|distance| ray.get_point(distance)),
+                }
+            })
+    } else {
+        None
+    };
+    if gauge.feet != feet {
+        gauge.feet = feet;
+    }
+}
+
+fn gauge_mesh(player: &PlayerDimensions) -> Mesh {
+    let radius = player.radius.max(0.01);
+    let height = player.height.max(2.0 * radius);
+    match player.shape {
+        PlayerShape::Capsule => Capsule3d::new(radius, height - 2.0 * radius).into(),
+        PlayerShape::Box => Cuboid::new(2.0 * radius, height, 2.0 * radius).into(),
+    }
+}
+
+/// Spawn, move and resize the translucent gauge mesh; despawn it when the gauge is off.
+fn sync_gauge_mesh(
+    mut commands: Commands,
+    gauge: Res<PlayerGauge>,
+    project: Option<Res<ProjectRoot>>,
+    mut gauges: Query<(Entity, &mut PlayerGaugeMesh, &mut Mesh3d, &mut Transform)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let Some(feet) = gauge.feet else {
+        for (entity, ..) in &gauges {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+    let player = project
+        .map(|project| project.settings.player)
+        .unwrap_or_default();
+    let transform = Transform::from_translation(feet + Vec3::Y * player.height.max(0.0) / 2.0);
+
+    if let Ok((_, mut built, mut mesh, mut current)) = gauges.single_mut() {
+        if built.0 != player {
+            built.0 = player;
+            mesh.0 = meshes.add(gauge_mesh(&player));
+        }
+        if *current != transform {
+            *current = transform;
+        }
+        return;
+    }
+    commands.spawn((
+        PlayerGaugeMesh(player),
+        Mesh3d(meshes.add(gauge_mesh(&player))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::srgba(0.3, 0.7, 1.0, 0.25),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            cull_mode: None,
+            ..default()
+        })),
+        transform,
+        NotShadowCaster,
+        NotShadowReceiver,
+        Pickable::IGNORE,
+        EditorEntity,
+    ));
+}
+
+/// Gridlines around the gauge at step and crouch height, plus a height ruler with a tick
+/// every meter and a mark at eye height.
+fn draw_gauge_lines(
+    gauge: Res<PlayerGauge>,
+    project: Option<Res<ProjectRoot>>,
+    mut gizmos: Gizmos<PlayerGaugeGizmoGroup>,
+) {
+    let Some(feet) = gauge.feet else {
+        return;
+    };
+    let player = project
+        .map(|project| project.settings.player)
+        .unwrap_or_default();
+
+    for (height, color) in [
+        (player.step_height, tailwind::GREEN_400),
+        (player.crouch_height, tailwind::AMBER_400),
+    ] {
+        if height <= 0.0 {
+            continue;
+        }
+        let center = feet + Vec3::Y * height;
+        let steps = (GRID_HALF_EXTENT / GRID_SPACING).round() as i32;
+        for i in -steps..=steps {
+            let offset = i as f32 * GRID_SPACING;
+            gizmos.line(
+                center + Vec3::new(offset, 0.0, -GRID_HALF_EXTENT),
+                center + Vec3::new(offset, 0.0, GRID_HALF_EXTENT),
+                color.with_alpha(0.6),
+            );
+            gizmos.line(
+                center + Vec3::new(-GRID_HALF_EXTENT, 0.0, offset),
+                center + Vec3::new(GRID_HALF_EXTENT, 0.0, offset),
+                color.with_alpha(0.6),
+            );
+        }
+    }
+
+    // Ruler beside the gauge
+    let ruler = feet + Vec3::X * (player.radius + 0.1);
+    let top = ruler + Vec3::Y * player.height;
+    gizmos.line(ruler, top, tailwind::SKY_300);
+    let mut meter = 0.0;
+    while meter <= player.height {
+        let tick = ruler + Vec3::Y * meter;
+        gizmos.line(tick, tick + Vec3::X * 0.15, tailwind::SKY_300);
+        meter += 1.0;
+    }
+    gizmos.line(top, top + Vec3::X * 0.15, tailwind::SKY_300);
+    let eye = ruler + Vec3::Y * player.eye_height;
+    gizmos.line(
+        eye - Vec3::X * 0.05,
+        eye + Vec3::X * 0.1,
+        tailwind::ROSE_400,
+    );
+}
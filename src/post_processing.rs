@@ -0,0 +1,484 @@
+//! Post-processing preview for the editor viewport: bloom, tonemapping, and SSAO,
+//! so lighting can be judged as it will look in game. The same settings can be
+//! copied onto the scene's cameras, which saves them with the scene.
+
+use bevy::{
+    core_pipeline::tonemapping::Tonemapping,
+    ecs::entity::EntityHashMap,
+    pbr::{ScreenSpaceAmbientOcclusion, ScreenSpaceAmbientOcclusionQualityLevel},
+    post_process::bloom::Bloom,
+    prelude::*,
+    ui_widgets::observe,
+};
+use jackdaw_feathers::{
+    button::{self, ButtonProps},
+    checkbox::{CheckboxCommitEvent, CheckboxProps, checkbox},
+    combobox::{ComboBoxChangeEvent, combobox_with_selected},
+    icons::{EditorFont, Icon, IconFont},
+    popover,
+    text_edit::{self, TextEditCommitEvent, TextEditProps},
+    tokens,
+};
+
+use crate::{
+    EditorEntity,
+    commands::{CommandHistory, EditorCommand, remap_entity},
+    viewport::MainViewportCamera,
+};
+
+pub struct PostProcessingPlugin;
+
+impl Plugin for PostProcessingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PostProcessSettings>()
+            .init_resource::<PostProcessPopover>()
+            .add_observer(on_post_process_checkbox_commit)
+            .add_observer(on_bloom_intensity_commit)
+            .add_systems(
+                Update,
+                apply_post_process_settings.run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+const TONEMAPPING_OPTIONS: [(Tonemapping, &str); 7] = [
+    (Tonemapping::None, "None"),
+    (Tonemapping::Reinhard, "Reinhard"),
+    (Tonemapping::ReinhardLuminance, "Reinhard Luminance"),
+    (Tonemapping::AcesFitted, "ACES Fitted"),
+    (Tonemapping::AgX, "AgX"),
+    (
+        Tonemapping::SomewhatBoringDisplayTransform,
+        "Somewhat Boring",
+    ),
+    (Tonemapping::TonyMcMapface, "Tony McMapface"),
+];
+
+const SSAO_QUALITY_OPTIONS: [(ScreenSpaceAmbientOcclusionQualityLevel, &str); 4] = [
+    (ScreenSpaceAmbientOcclusionQualityLevel::Low, "Low"),
+    (ScreenSpaceAmbientOcclusionQualityLevel::Medium, "Medium"),
+    (ScreenSpaceAmbientOcclusionQualityLevel::High, "High"),
+    (ScreenSpaceAmbientOcclusionQualityLevel::Ultra, "Ultra"),
+];
+
+/// Post-processing on the editor viewport camera.
+#[derive(Resource, Clone, PartialEq)]
+pub struct PostProcessSettings {
+    pub bloom: bool,
+    pub bloom_intensity: f32,
+    pub tonemapping: Tonemapping,
+    pub ssao: bool,
+    pub ssao_quality: ScreenSpaceAmbientOcclusionQualityLevel,
+}
+
+impl Default for PostProcessSettings {
+    fn default() -> Self {
+        Self {
+            bloom: false,
+            bloom_intensity: Bloom::NATURAL.intensity,
+            tonemapping: Tonemapping::default(),
+            ssao: false,
+            ssao_quality: ScreenSpaceAmbientOcclusionQualityLevel::default(),
+        }
+    }
+}
+
+impl PostProcessSettings {
+    fn camera_components(&self) -> CameraPostProcess {
+        CameraPostProcess {
+            bloom: self.bloom.then_some(Bloom {
+                intensity: self.bloom_intensity,
+                ..Bloom::NATURAL
+            }),
+            tonemapping: Some(self.tonemapping),
+            ssao: self.ssao.then(|| ScreenSpaceAmbientOcclusion {
+                quality_level: self.ssao_quality,
+                ..default()
+            }),
+            // SSAO only runs without MSAA.
+            msaa: Some(if self.ssao {
+                Msaa::Off
+            } else {
+                Msaa::default()
+            }),
+        }
+    }
+}
+
+/// Resource tracking the open post-processing popover entity
+#[derive(Resource, Default)]
+pub struct PostProcessPopover {
+    pub entity: Option<Entity>,
+}
+
+/// The post-processing components of one camera.
+#[derive(Clone)]
+struct CameraPostProcess {
+    bloom: Option<Bloom>,
+    tonemapping: Option<Tonemapping>,
+    ssao: Option<ScreenSpaceAmbientOcclusion>,
+    msaa: Option<Msaa>,
+}
+
+impl CameraPostProcess {
+    fn read(entity: EntityRef) -> Self {
+        Self {
+            bloom: entity.get::<Bloom>().cloned(),
+            tonemapping: entity.get::<Tonemapping>().copied(),
+            ssao: entity.get::<ScreenSpaceAmbientOcclusion>().cloned(),
+            msaa: entity.get::<Msaa>().copied(),
+        }
+    }
+
+    fn write(&self, mut entity: EntityWorldMut) {
+        match &self.bloom {
+            Some(bloom) => entity.insert(bloom.clone()),
+            None => entity.remove::<Bloom>(),
+        };
+        match self.tonemapping {
+            Some(tonemapping) => entity.insert(tonemapping),
+            None => entity.remove::<Tonemapping>(),
+        };
+        match &self.ssao {
+            Some(ssao) => entity.insert(ssao.clone()),
+            None => entity.remove::<ScreenSpaceAmbientOcclusion>(),
+        };
+        match self.msaa {
+            Some(msaa) => entity.insert(msaa),
+            None => entity.remove::<Msaa>(),
+        };
+    }
+}
+
+fn apply_post_process_settings(
+    mut commands: Commands,
+    settings: Res<PostProcessSettings>,
+    cameras: Query<Entity, Added<MainViewportCamera>>,
+    all_cameras: Query<Entity, With<MainViewportCamera>>,
+) {
+    let targets: Vec<Entity> = if settings.is_changed() {
+        all_cameras.iter().collect()
+    } else {
+        cameras.iter().collect()
+    };
+    if targets.is_empty() {
+        return;
+    }
+    let components = settings.camera_components();
+    for camera in targets {
+        let components = components.clone();
+        commands.queue(move |world: &mut World| {
+            if let Ok(entity) = world.get_entity_mut(camera) {
+                components.write(entity);
+            }
+        });
+    }
+}
+
+/// Copies the viewport's post-processing onto cameras in the scene; one undo step.
+struct CopyPostProcessToCameras {
+    old: Vec<(Entity, CameraPostProcess)>,
+    new: CameraPostProcess,
+}
+
+impl EditorCommand for CopyPostProcessToCameras {
+    fn execute(&self, world: &mut World) {
+        for &(camera, _) in &self.old {
+            if let Ok(entity) = world.get_entity_mut(camera) {
+                self.new.write(entity);
+            }
+        }
+    }
+
+    fn undo(&self, world: &mut World) {
+        for (camera, old) in &self.old {
+            if let Ok(entity) = world.get_entity_mut(*camera) {
+                old.write(entity);
+            }
+        }
+    }
+
+    fn description(&self) -> &str {
+        "Copy post-processing to cameras"
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        for (camera, _) in &mut self.old {
+            remap_entity(camera, map);
+        }
+    }
+}
+
+fn copy_to_scene_cameras(world: &mut World) {
+    let cameras: Vec<Entity> = world
+        .query_filtered::<Entity, (With<Camera3d>, With<Name>, Without<EditorEntity>)>()
+        .iter(world)
+        .collect();
+    if cameras.is_empty() {
+        info!("No scene cameras to copy post-processing to");
+        return;
+    }
+    let old = cameras
+        .iter()
+        .map(|&camera| (camera, CameraPostProcess::read(world.entity(camera))))
+        .collect();
+    let new = world.resource::<PostProcessSettings>().camera_components();
+    let cmd = CopyPostProcessToCameras { old, new };
+    cmd.execute(world);
+    info!("Copied post-processing to {} camera(s)", cameras.len());
+    world
+        .resource_mut::<CommandHistory>()
+        .push_executed(Box::new(cmd));
+}
+
+// --- Popover ---
+
+#[derive(Component, Clone, Copy)]
+enum PostProcessToggle {
+    Bloom,
+    Ssao,
+}
+
+/// Marker on the bloom intensity field.
+#[derive(Component)]
+struct BloomIntensityField;
+
+pub fn post_process_button(icon_font: Handle<Font>) -> impl Bundle {
+    (
+        Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            padding: UiRect::axes(px(tokens::SPACING_MD), px(tokens::SPACING_XS)),
+            border_radius: BorderRadius::all(px(tokens::BORDER_RADIUS_SM)),
+            ..Default::default()
+        },
+        BackgroundColor(tokens::TOOLBAR_BUTTON_BG),
+        children![(
+            Text::new(String::from(Icon::Sparkles.unicode())),
+            TextFont {
+                font: icon_font,
+                font_size: tokens::FONT_MD,
+                ..Default::default()
+            },
+            TextColor(tokens::TEXT_SECONDARY),
+        )],
+        observe(
+            |trigger: On<Pointer<Click>>,
+             mut commands: Commands,
+             mut popover_state: ResMut<PostProcessPopover>,
+             settings: Res<PostProcessSettings>,
+             editor_font: Res<EditorFont>,
+             icon_font: Res<IconFont>| {
+                // Toggle: if the popover is still open, close it
+                if let Some(entity) = popover_state.entity.take()
+                    && let Ok(mut ec) = commands.get_entity(entity)
+                {
+                    ec.despawn();
+                    return;
+                }
+
+                let popover_entity = commands
+                    .spawn(popover::popover(
+                        popover::PopoverProps::new(trigger.event_target())
+                            .with_placement(popover::PopoverPlacement::BottomEnd)
+                            .with_padding(12.0)
+                            .with_z_index(200),
+                    ))
+                    .id();
+                spawn_post_process_content(
+                    &mut commands,
+                    popover_entity,
+                    &settings,
+                    &editor_font.0,
+                    &icon_font.0,
+                );
+                popover_state.entity = Some(popover_entity);
+            },
+        ),
+    )
+}
+
+fn spawn_post_process_content(
+    commands: &mut Commands,
+    popover: Entity,
+    settings: &PostProcessSettings,
+    editor_font: &Handle<Font>,
+    icon_font: &Handle<Font>,
+) {
+    let column = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: px(tokens::SPACING_SM),
+                width: px(240.0),
+                ..Default::default()
+            },
+            ChildOf(popover),
+        ))
+        .id();
+
+    spawn_heading(commands, column, "Post-processing");
+
+    let row = spawn_row(commands, column, "Tonemapping");
+    let selected = TONEMAPPING_OPTIONS
+        .iter()
+        .position(|(t, _)| *t == settings.tonemapping)
+        .unwrap_or(0);
+    commands
+        .spawn((
+            combobox_with_selected(
+                TONEMAPPING_OPTIONS
+                    .iter()
+                    .map(|(_, label)| *label)
+                    .collect(),
+                selected,
+            ),
+            ChildOf(row),
+        ))
+        .observe(
+            |event: On<ComboBoxChangeEvent>, mut settings: ResMut<PostProcessSettings>| {
+                if let Some((tonemapping, _)) = TONEMAPPING_OPTIONS.get(event.selected) {
+                    settings.tonemapping = *tonemapping;
+                }
+            },
+        );
+
+    commands.spawn((
+        checkbox(
+            CheckboxProps::new("Bloom").checked(settings.bloom),
+            editor_font,
+            icon_font,
+        ),
+        PostProcessToggle::Bloom,
+        ChildOf(column),
+    ));
+    let row = spawn_row(commands, column, "Intensity");
+    commands.spawn((
+        text_edit::text_edit(
+            TextEditProps::default()
+                .numeric_f32()
+                .grow()
+                .with_min(0.0)
+                .with_default_value(settings.bloom_intensity.to_string()),
+        ),
+        BloomIntensityField,
+        ChildOf(row),
+    ));
+
+    commands.spawn((
+        checkbox(
+            CheckboxProps::new("Ambient Occlusion (SSAO)").checked(settings.ssao),
+            editor_font,
+            icon_font,
+        ),
+        PostProcessToggle::Ssao,
+        ChildOf(column),
+    ));
+    let row = spawn_row(commands, column, "Quality");
+    let selected = SSAO_QUALITY_OPTIONS
+        .iter()
+        .position(|(q, _)| *q == settings.ssao_quality)
+        .unwrap_or(2);
+    commands
+        .spawn((
+            combobox_with_selected(
+                SSAO_QUALITY_OPTIONS
+                    .iter()
+                    .map(|(_, label)| *label)
+                    .collect(),
+                selected,
+            ),
+            ChildOf(row),
+        ))
+        .observe(
+            |event: On<ComboBoxChangeEvent>, mut settings: ResMut<PostProcessSettings>| {
+                if let Some((quality, _)) = SSAO_QUALITY_OPTIONS.get(event.selected) {
+                    settings.ssao_quality = *quality;
+                }
+            },
+        );
+
+    commands.spawn((
+        button::button(ButtonProps::new("Copy to Scene Cameras")),
+        ChildOf(column),
+        observe(|_: On<Pointer<Click>>, mut commands: Commands| {
+            commands.queue(copy_to_scene_cameras);
+        }),
+    ));
+}
+
+fn on_post_process_checkbox_commit(
+    event: On<CheckboxCommitEvent>,
+    toggles: Query<&PostProcessToggle>,
+    mut settings: ResMut<PostProcessSettings>,
+) {
+    let Ok(toggle) = toggles.get(event.entity) else {
+        return;
+    };
+    match toggle {
+        PostProcessToggle::Bloom => settings.bloom = event.checked,
+        PostProcessToggle::Ssao => settings.ssao = event.checked,
+    }
+}
+
+fn on_bloom_intensity_commit(
+    event: On<TextEditCommitEvent>,
+    fields: Query<(), With<BloomIntensityField>>,
+    child_of_query: Query<&ChildOf>,
+    mut settings: ResMut<PostProcessSettings>,
+) {
+    // Walk up from committed entity to find the field
+    let mut current = event.entity;
+    for _ in 0..4 {
+        let Ok(child_of) = child_of_query.get(current) else {
+            return;
+        };
+        current = child_of.parent();
+        if fields.contains(current) {
+            settings.bloom_intensity = event.text.parse().unwrap_or(0.0_f32).max(0.0);
+            return;
+        }
+    }
+}
+
+fn spawn_heading(commands: &mut Commands, parent: Entity, text: &str) {
+    commands.spawn((
+        Text::new(text),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_PRIMARY),
+        ChildOf(parent),
+    ));
+}
+
+fn spawn_row(commands: &mut Commands, parent: Entity, label: &str) -> Entity {
+    let row = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: px(tokens::SPACING_XS),
+                width: Val::Percent(100.0),
+                ..Default::default()
+            },
+            ChildOf(parent),
+        ))
+        .id();
+    commands.spawn((
+        Text::new(label),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_SECONDARY),
+        Node {
+            min_width: px(80.0),
+            flex_shrink: 0.0,
+            ..Default::default()
+        },
+        ChildOf(row),
+    ));
+    row
+}
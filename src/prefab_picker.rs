@@ -33,8 +33,26 @@ struct PrefabPickerEntry {
     display_name: String,
 }
 
+/// What picking a `.jsn` file in the picker does.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PickerMode {
+    /// Copy the file's entities into the scene.
+    Prefab,
+    /// Add a [`jackdaw_jsn::SubScene`] that stays linked to the file.
+    SubScene,
+}
+
 /// Open (or close, if already open) the prefab picker overlay.
 pub fn open_prefab_picker(world: &mut World) {
+    open_picker(world, PickerMode::Prefab);
+}
+
+/// Open (or close, if already open) the picker in sub-scene reference mode.
+pub fn open_sub_scene_picker(world: &mut World) {
+    open_picker(world, PickerMode::SubScene);
+}
+
+fn open_picker(world: &mut World, mode: PickerMode) {
     // Toggle: if picker already open, close it
     let existing: Vec<Entity> = world
         .query_filtered::<Entity, With<PrefabPicker>>()
@@ -107,7 +125,10 @@ pub fn open_prefab_picker(world: &mut World) {
         PrefabPickerSearch,
         text_edit::text_edit(
             TextEditProps::default()
-                .with_placeholder("Search prefabs...")
+                .with_placeholder(match mode {
+                    PickerMode::Prefab => "Search prefabs...",
+                    PickerMode::SubScene => "Search scenes to reference...",
+                })
                 .allow_empty(),
         ),
         ChildOf(picker),
@@ -176,8 +197,19 @@ pub fn open_prefab_picker(world: &mut World) {
                                 ec.despawn();
                             }
                         }
-                        // Instantiate
-                        crate::entity_templates::instantiate_jsn_prefab(world, &path, Vec3::ZERO);
+                        match mode {
+                            PickerMode::Prefab => {
+                                crate::entity_templates::instantiate_jsn_prefab(
+                                    world,
+                                    &path,
+                                    Vec3::ZERO,
+                                    None,
+                                );
+                            }
+                            PickerMode::SubScene => {
+                                crate::sub_scene::spawn_sub_scene(world, &path, Vec3::ZERO);
+                            }
+                        }
                     });
                 }),
                 observe(
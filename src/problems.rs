@@ -0,0 +1,624 @@
+//! Scene validation: rules check the scene on demand or on save and report problems in
+//! the Problems panel below the viewport, each with a jump to the entity and, where the
+//! rule knows one, a quick fix.
+//!
+//! Rules are pluggable: game projects add their own [`ValidationRule`]s to the
+//! [`ValidationRules`] resource after adding the editor plugin.
+
+use std::{any::TypeId, sync::Arc};
+
+use bevy::{ecs::system::SystemState, prelude::*, ui_widgets::observe};
+use jackdaw_feathers::{
+    button::{self, ButtonProps, ButtonVariant},
+    tokens,
+};
+use jackdaw_jsn::{Brush, TriggerVolume, VisibilityVolume};
+
+use crate::{
+    EditorEntity,
+    brush::{BrushFaceEntity, BrushMeshCache},
+    commands::{CommandHistory, EditorCommand, SetComponentField},
+    custom_properties::CustomProperties,
+    entity_classes::{EntityClasses, add_class_property},
+    selection::Selection,
+    view_modes::NormalsViewMaterial,
+    viewport::MainViewportCamera,
+};
+
+pub struct ProblemsPlugin;
+
+impl Plugin for ProblemsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Problems>()
+            .init_resource::<ValidationRules>()
+            .add_systems(
+                Update,
+                (toggle_problems_panel, rebuild_problem_list)
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+/// A check run over the whole scene.
+pub trait ValidationRule: Send + Sync + 'static {
+    /// Short name shown next to each problem the rule reports.
+    fn name(&self) -> &str;
+
+    /// Push a [`Problem`] for everything in the scene that breaks the rule.
+    fn check(&self, world: &mut World, problems: &mut Vec<Problem>);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProblemSeverity {
+    Warning,
+    Error,
+}
+
+/// An action that resolves a problem, recording its own undo step.
+#[derive(Clone)]
+pub struct QuickFix {
+    pub label: String,
+    pub apply: Arc<dyn Fn(&mut World) + Send + Sync>,
+}
+
+impl QuickFix {
+    pub fn new(
+        label: impl Into<String>,
+        apply: impl Fn(&mut World) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            apply: Arc::new(apply),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Problem {
+    pub entity: Entity,
+    pub severity: ProblemSeverity,
+    pub message: String,
+    /// Name of the rule that reported it; filled in by [`validate_scene`].
+    pub rule: String,
+    pub fix: Option<QuickFix>,
+}
+
+impl Problem {
+    pub fn warning(entity: Entity, message: impl Into<String>) -> Self {
+        Self {
+            entity,
+            severity: ProblemSeverity::Warning,
+            message: message.into(),
+            rule: String::new(),
+            fix: None,
+        }
+    }
+
+    pub fn error(entity: Entity, message: impl Into<String>) -> Self {
+        Self {
+            severity: ProblemSeverity::Error,
+            ..Self::warning(entity, message)
+        }
+    }
+
+    pub fn with_fix(mut self, fix: QuickFix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+/// The rules [`validate_scene`] runs, in order. Starts with the built-in rules.
+#[derive(Resource)]
+pub struct ValidationRules {
+    pub rules: Vec<Box<dyn ValidationRule>>,
+}
+
+impl Default for ValidationRules {
+    fn default() -> Self {
+        Self {
+            rules: vec![
+                Box::new(MeshWithoutMaterial),
+                Box::new(LightWithoutIntensity),
+                Box::new(OverlappingBrushes),
+                Box::new(MissingRequiredProperty),
+            ],
+        }
+    }
+}
+
+impl ValidationRules {
+    pub fn add(&mut self, rule: impl ValidationRule) -> &mut Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+}
+
+/// Problems found by the last validation.
+#[derive(Resource)]
+pub struct Problems {
+    pub open: bool,
+    pub problems: Vec<Problem>,
+    /// Validate every time the scene is saved.
+    pub validate_on_save: bool,
+}
+
+impl Default for Problems {
+    fn default() -> Self {
+        Self {
+            open: false,
+            problems: Vec::new(),
+            validate_on_save: true,
+        }
+    }
+}
+
+/// Marker for the problems panel below the viewport.
+#[derive(Component)]
+pub struct ProblemsPanel;
+
+#[derive(Component)]
+struct ProblemList;
+
+#[derive(Component)]
+struct ProblemsSummaryLabel;
+
+/// Run every rule and replace the listed problems with what they report.
+pub fn validate_scene(world: &mut World) {
+    let problems = world.resource_scope(|world, rules: Mut<ValidationRules>| {
+        let mut problems = Vec::new();
+        for rule in &rules.rules {
+            let start = problems.len();
+            rule.check(world, &mut problems);
+            for problem in &mut problems[start..] {
+                problem.rule = rule.name().to_string();
+            }
+        }
+        problems
+    });
+    // Errors first, each rule's problems kept together
+    let mut problems = problems;
+    problems.sort_by_key(|p| p.severity != ProblemSeverity::Error);
+    world.resource_mut::<Problems>().problems = problems;
+}
+
+/// Validate and open the panel.
+pub fn validate_and_show(world: &mut World) {
+    validate_scene(world);
+    world.resource_mut::<Problems>().open = true;
+}
+
+pub fn toggle_problems(world: &mut World) {
+    let open = !world.resource::<Problems>().open;
+    if open {
+        validate_scene(world);
+    }
+    world.resource_mut::<Problems>().open = open;
+}
+
+/// Called after the scene is written; opens the panel when there is something to fix.
+pub(crate) fn validate_after_save(world: &mut World) {
+    if !world.resource::<Problems>().validate_on_save {
+        return;
+    }
+    validate_scene(world);
+    let count = world.resource::<Problems>().problems.len();
+    if count > 0 {
+        warn!("Scene saved with {count} problem(s)");
+        world.resource_mut::<Problems>().open = true;
+    }
+}
+
+/// Builds the problems panel UI node. Starts hidden (`Display::None`).
+pub fn problems_panel() -> impl Bundle {
+    (
+        ProblemsPanel,
+        EditorEntity,
+        Node {
+            flex_direction: FlexDirection::Column,
+            width: percent(100),
+            height: px(160.0),
+            flex_shrink: 0.0,
+            display: Display::None,
+            ..Default::default()
+        },
+        BackgroundColor(tokens::PANEL_BG),
+        children![
+            (
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::SpaceBetween,
+                    padding: UiRect::axes(px(tokens::SPACING_MD), px(tokens::SPACING_XS)),
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+                BackgroundColor(tokens::PANEL_HEADER_BG),
+                children![
+                    (
+                        ProblemsSummaryLabel,
+                        Text::new("Problems"),
+                        TextFont {
+                            font_size: tokens::FONT_SM,
+                            ..Default::default()
+                        },
+                        TextColor(tokens::TEXT_SECONDARY),
+                    ),
+                    (
+                        button::button(
+                            ButtonProps::new("Validate").with_variant(ButtonVariant::Default),
+                        ),
+                        observe(|_: On<Pointer<Click>>, mut commands: Commands| {
+                            commands.queue(validate_scene);
+                        }),
+                    ),
+                ],
+            ),
+            (
+                ProblemList,
+                EditorEntity,
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    width: percent(100),
+                    flex_grow: 1.0,
+                    min_height: px(0.0),
+                    overflow: Overflow::scroll_y(),
+                    padding: UiRect::all(px(tokens::SPACING_XS)),
+                    ..Default::default()
+                },
+            ),
+        ],
+    )
+}
+
+fn toggle_problems_panel(
+    problems: Res<Problems>,
+    mut panels: Query<&mut Node, With<ProblemsPanel>>,
+) {
+    if !problems.is_changed() {
+        return;
+    }
+    for mut node in &mut panels {
+        node.display = if problems.open {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+fn rebuild_problem_list(
+    mut commands: Commands,
+    problems: Res<Problems>,
+    names: Query<&Name>,
+    lists: Query<(Entity, Option<&Children>), With<ProblemList>>,
+    mut summaries: Query<&mut Text, With<ProblemsSummaryLabel>>,
+) {
+    if !problems.is_changed() {
+        return;
+    }
+    let errors = problems
+        .problems
+        .iter()
+        .filter(|p| p.severity == ProblemSeverity::Error)
+        .count();
+    let warnings = problems.problems.len() - errors;
+    for mut text in &mut summaries {
+        text.0 = if problems.problems.is_empty() {
+            "Problems: none".to_string()
+        } else {
+            format!("Problems: {errors} error(s), {warnings} warning(s)")
+        };
+    }
+    for (list, children) in &lists {
+        if let Some(children) = children {
+            for child in children.iter() {
+                commands.entity(child).despawn();
+            }
+        }
+        for (index, problem) in problems.problems.iter().enumerate() {
+            let name = names
+                .get(problem.entity)
+                .map(|n| n.as_str().to_string())
+                .unwrap_or_else(|_| format!("Entity {}", problem.entity));
+            let color = match problem.severity {
+                ProblemSeverity::Error => Color::srgb(1.0, 0.35, 0.3),
+                ProblemSeverity::Warning => Color::srgb(1.0, 0.75, 0.3),
+            };
+            let row = commands
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::SpaceBetween,
+                        padding: UiRect::axes(px(tokens::SPACING_SM), px(2.0)),
+                        border_radius: BorderRadius::all(px(tokens::BORDER_RADIUS_SM)),
+                        ..Default::default()
+                    },
+                    ChildOf(list),
+                ))
+                .id();
+            commands.spawn((
+                Text::new(format!("[{}] {name}: {}", problem.rule, problem.message)),
+                TextFont {
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                TextColor(color),
+                ChildOf(row),
+                observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+                    commands.queue(move |world: &mut World| focus_problem(world, index));
+                }),
+            ));
+            if let Some(fix) = &problem.fix {
+                commands.spawn((
+                    button::button(ButtonProps::new(fix.label.clone())),
+                    ChildOf(row),
+                    observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+                        commands.queue(move |world: &mut World| apply_fix(world, index));
+                    }),
+                ));
+            }
+        }
+    }
+}
+
+/// Select the problem's entity and move the camera to look at it.
+fn focus_problem(world: &mut World, index: usize) {
+    let Some(entity) = world
+        .resource::<Problems>()
+        .problems
+        .get(index)
+        .map(|p| p.entity)
+    else {
+        return;
+    };
+    let Some(position) = world
+        .get::<GlobalTransform>(entity)
+        .map(GlobalTransform::translation)
+    else {
+        return;
+    };
+
+    let mut state = SystemState::<(Commands, ResMut<Selection>)>::new(world);
+    let (mut commands, mut selection) = state.get_mut(world);
+    selection.select_single(&mut commands, entity);
+    state.apply(world);
+
+    let mut cameras = world.query_filtered::<&mut Transform, With<MainViewportCamera>>();
+    for mut transform in cameras.iter_mut(world) {
+        let forward = transform.forward().as_vec3();
+        transform.translation = position - forward * 6.0;
+    }
+}
+
+/// Apply a problem's quick fix and validate again so the list reflects it.
+fn apply_fix(world: &mut World, index: usize) {
+    let Some(fix) = world
+        .resource::<Problems>()
+        .problems
+        .get(index)
+        .and_then(|p| p.fix.clone())
+    else {
+        return;
+    };
+    (fix.apply)(world);
+    validate_scene(world);
+}
+
+/// Scene meshes with no material, or one that is neither loaded nor loading. Brush
+/// faces always get one, and the normals view mode swaps it out on purpose.
+struct MeshWithoutMaterial;
+
+impl ValidationRule for MeshWithoutMaterial {
+    fn name(&self) -> &str {
+        "mesh-material"
+    }
+
+    fn check(&self, world: &mut World, problems: &mut Vec<Problem>) {
+        let mut meshes = world
+            .query_filtered::<(Entity, Option<&MeshMaterial3d<StandardMaterial>>), (
+                With<Mesh3d>,
+                Without<EditorEntity>,
+                Without<BrushFaceEntity>,
+                Without<MeshMaterial3d<NormalsViewMaterial>>,
+            )>();
+        let asset_server = world.resource::<AssetServer>();
+        let materials = world.resource::<Assets<StandardMaterial>>();
+        for (entity, material) in meshes.iter(world) {
+            let missing = material.is_none_or(|m| {
+                materials.get(&m.0).is_none()
+                    && !matches!(
+                        asset_server.get_load_state(&m.0),
+                        Some(bevy::asset::LoadState::Loading | bevy::asset::LoadState::Loaded)
+                    )
+            });
+            if missing {
+                problems.push(Problem::warning(entity, "mesh has no material"));
+            }
+        }
+    }
+}
+
+/// Lights that are on but give no light.
+struct LightWithoutIntensity;
+
+impl LightWithoutIntensity {
+    /// Undoably set a light's brightness field back to the default.
+    fn reset<T: Component + Reflect + Default>(
+        entity: Entity,
+        field: &'static str,
+        value: impl Fn(&T) -> f32 + Send + Sync + 'static,
+    ) -> QuickFix {
+        QuickFix::new("Reset intensity", move |world: &mut World| {
+            let Some(old) = world.get::<T>(entity).map(&value) else {
+                return;
+            };
+            let cmd = SetComponentField {
+                entity,
+                component_type_id: TypeId::of::<T>(),
+                field_path: field.to_string(),
+                old_value: Box::new(old),
+                new_value: Box::new(value(&T::default())),
+            };
+            cmd.execute(world);
+            let mut history = world.resource_mut::<CommandHistory>();
+            history.push_executed(Box::new(cmd));
+        })
+    }
+}
+
+impl ValidationRule for LightWithoutIntensity {
+    fn name(&self) -> &str {
+        "light-intensity"
+    }
+
+    fn check(&self, world: &mut World, problems: &mut Vec<Problem>) {
+        let mut lights = world.query_filtered::<(
+            Entity,
+            Option<&PointLight>,
+            Option<&SpotLight>,
+            Option<&DirectionalLight>,
+        ), Without<EditorEntity>>();
+        for (entity, point, spot, directional) in lights.iter(world) {
+            let fix = if point.is_some_and(|l| l.intensity <= 0.0) {
+                Self::reset::<PointLight>(entity, "intensity", |l| l.intensity)
+            } else if spot.is_some_and(|l| l.intensity <= 0.0) {
+                Self::reset::<SpotLight>(entity, "intensity", |l| l.intensity)
+            } else if directional.is_some_and(|l| l.illuminance <= 0.0) {
+                Self::reset::<DirectionalLight>(entity, "illuminance", |l| l.illuminance)
+            } else {
+                continue;
+            };
+            problems.push(Problem::warning(entity, "light intensity is zero").with_fix(fix));
+        }
+    }
+}
+
+/// World brushes whose solids overlap. Triggers and visibility volumes overlap geometry
+/// by design; brushes that only touch are fine.
+struct OverlappingBrushes;
+
+/// A brush's world-space bounding planes, corners and bounds.
+struct BrushHull {
+    entity: Entity,
+    planes: Vec<(Vec3, f32)>,
+    vertices: Vec<Vec3>,
+    min: Vec3,
+    max: Vec3,
+}
+
+impl BrushHull {
+    /// Whether one of our faces has all of `other` on or outside it.
+    fn separates(&self, other: &BrushHull) -> bool {
+        const TOUCH_EPSILON: f32 = 1e-3;
+        self.planes.iter().any(|(normal, distance)| {
+            other
+                .vertices
+                .iter()
+                .all(|v| normal.dot(*v) >= distance - TOUCH_EPSILON)
+        })
+    }
+}
+
+impl ValidationRule for OverlappingBrushes {
+    fn name(&self) -> &str {
+        "brush-overlap"
+    }
+
+    fn check(&self, world: &mut World, problems: &mut Vec<Problem>) {
+        let mut brushes = world
+            .query_filtered::<(Entity, &Brush, &BrushMeshCache, &GlobalTransform), (
+                Without<EditorEntity>,
+                Without<TriggerVolume>,
+                Without<VisibilityVolume>,
+            )>();
+        let hulls: Vec<BrushHull> = brushes
+            .iter(world)
+            .filter(|(_, _, cache, _)| !cache.vertices.is_empty())
+            .map(|(entity, brush, cache, global)| {
+                let affine = global.affine();
+                let normal_matrix = affine.matrix3.inverse().transpose();
+                let planes = brush
+                    .faces
+                    .iter()
+                    .map(|face| {
+                        let normal = Vec3::from(normal_matrix * Vec3A::from(face.plane.normal))
+                            .normalize_or_zero();
+                        let point =
+                            affine.transform_point3(face.plane.normal * face.plane.distance);
+                        (normal, normal.dot(point))
+                    })
+                    .collect();
+                let vertices: Vec<Vec3> = cache
+                    .vertices
+                    .iter()
+                    .map(|v| affine.transform_point3(*v))
+                    .collect();
+                let (min, max) = crate::viewport_overlays::aabb_from_points(&vertices);
+                BrushHull {
+                    entity,
+                    planes,
+                    vertices,
+                    min,
+                    max,
+                }
+            })
+            .collect();
+
+        for (i, a) in hulls.iter().enumerate() {
+            for b in &hulls[i + 1..] {
+                let bounds_overlap = a.min.cmplt(b.max).all() && b.min.cmplt(a.max).all();
+                // Face planes of either brush are enough to separate most convex pairs;
+                // edge-on-edge contacts may still be reported.
+                if !bounds_overlap || a.separates(b) || b.separates(a) {
+                    continue;
+                }
+                let other = world
+                    .get::<Name>(b.entity)
+                    .map(|n| n.as_str().to_string())
+                    .unwrap_or_else(|| format!("Entity {}", b.entity));
+                problems.push(Problem::warning(
+                    a.entity,
+                    format!("brush overlaps {other}"),
+                ));
+            }
+        }
+    }
+}
+
+/// Entities whose class requires custom properties they don't have.
+struct MissingRequiredProperty;
+
+impl ValidationRule for MissingRequiredProperty {
+    fn name(&self) -> &str {
+        "required-property"
+    }
+
+    fn check(&self, world: &mut World, problems: &mut Vec<Problem>) {
+        let mut entities =
+            world.query_filtered::<(Entity, &CustomProperties), Without<EditorEntity>>();
+        let classes = world.resource::<EntityClasses>();
+        for (entity, properties) in entities.iter(world) {
+            let Some((class_name, class)) = classes.class_of(properties) else {
+                continue;
+            };
+            for missing in class.missing_required(properties) {
+                let mut problem = Problem::error(
+                    entity,
+                    format!("{class_name} requires property '{missing}'"),
+                );
+                // Only properties with a default can be added without asking for a value
+                let has_default = class.properties[missing].default_value().is_some();
+                if has_default {
+                    let property = missing.to_string();
+                    problem = problem.with_fix(QuickFix::new(
+                        format!("Add '{missing}'"),
+                        move |world: &mut World| add_class_property(world, entity, &property),
+                    ));
+                }
+                problems.push(problem);
+            }
+        }
+    }
+}
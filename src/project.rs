@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 pub struct ProjectRoot {
     pub root: PathBuf,
     pub config: JsnProject,
+    pub settings: ProjectSettings,
 }
 
 impl ProjectRoot {
@@ -16,8 +17,130 @@ impl ProjectRoot {
         self.root.join(".jsn")
     }
     pub fn assets_dir(&self) -> PathBuf {
-        self.root.join("assets")
+        self.root.join(&self.settings.asset_root)
     }
+    /// Folders the texture browser scans; the whole asset root when none are set.
+    pub fn texture_dirs(&self) -> Vec<PathBuf> {
+        if self.settings.texture_dirs.is_empty() {
+            return vec![self.assets_dir()];
+        }
+        let assets_dir = self.assets_dir();
+        self.settings
+            .texture_dirs
+            .iter()
+            .map(|dir| assets_dir.join(dir))
+            .collect()
+    }
+    /// Entity class schema files; `.jsn/entity_classes.json` when none are set.
+    pub fn schema_files(&self) -> Vec<PathBuf> {
+        if self.settings.schema_files.is_empty() {
+            return vec![self.jsn_dir().join("entity_classes.json")];
+        }
+        self.settings
+            .schema_files
+            .iter()
+            .map(|file| self.root.join(file))
+            .collect()
+    }
+    pub fn export_dir(&self) -> PathBuf {
+        self.root.join(&self.settings.export_dir)
+    }
+}
+
+/// Project-level settings, read from `project.toml` in the project root. Paths are
+/// relative: `asset_root`, `schema_files`, and `export_dir` to the project root,
+/// `texture_dirs` to the asset root. `[player]` sizes the player gauge and walk mode.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ProjectSettings {
+    pub asset_root: PathBuf,
+    pub texture_dirs: Vec<PathBuf>,
+    pub schema_files: Vec<PathBuf>,
+    pub export_dir: PathBuf,
+    pub player: PlayerDimensions,
+}
+
+impl Default for ProjectSettings {
+    fn default() -> Self {
+        Self {
+            asset_root: PathBuf::from("assets"),
+            texture_dirs: Vec::new(),
+            schema_files: Vec::new(),
+            export_dir: PathBuf::from("export"),
+            player: PlayerDimensions::default(),
+        }
+    }
+}
+
+/// Size of the game's player, in meters.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct PlayerDimensions {
+    pub shape: PlayerShape,
+    pub height: f32,
+    pub radius: f32,
+    /// Camera height above the feet.
+    pub eye_height: f32,
+    /// Tallest ledge the player walks up without jumping.
+    pub step_height: f32,
+    /// Height while crouched.
+    pub crouch_height: f32,
+}
+
+impl Default for PlayerDimensions {
+    fn default() -> Self {
+        Self {
+            shape: PlayerShape::Capsule,
+            height: 1.8,
+            radius: 0.3,
+            eye_height: 1.65,
+            step_height: 0.35,
+            crouch_height: 1.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerShape {
+    #[default]
+    Capsule,
+    Box,
+}
+
+pub fn project_settings_path(root: &Path) -> PathBuf {
+    root.join("project.toml")
+}
+
+/// Read `project.toml`, writing the defaults if the project doesn't have one yet.
+pub fn load_project_settings(root: &Path) -> ProjectSettings {
+    let path = project_settings_path(root);
+    match std::fs::read_to_string(&path) {
+        Ok(data) => toml::from_str(&data).unwrap_or_else(|err| {
+            warn!("Failed to parse '{}': {err}", path.display());
+            ProjectSettings::default()
+        }),
+        Err(_) => {
+            let settings = ProjectSettings::default();
+            if let Ok(data) = toml::to_string_pretty(&settings) {
+                let _ = std::fs::write(&path, data);
+            }
+            settings
+        }
+    }
+}
+
+/// Read `project.toml` without creating it; the defaults when it's missing or invalid.
+pub fn read_project_settings(root: &Path) -> ProjectSettings {
+    std::fs::read_to_string(project_settings_path(root))
+        .ok()
+        .and_then(|data| toml::from_str::<ProjectSettings>(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Absolute asset root of the project at `root`, without creating `project.toml`.
+pub fn project_assets_dir(root: &Path) -> PathBuf {
+    root.join(read_project_settings(root).asset_root)
 }
 
 #[derive(Serialize, Deserialize, Default)]
@@ -32,7 +155,7 @@ pub struct RecentEntry {
     pub last_opened: String,
 }
 
-fn config_dir() -> Option<PathBuf> {
+pub(crate) fn config_dir() -> Option<PathBuf> {
     dirs::config_dir().map(|d| d.join("jackdaw"))
 }
 
@@ -62,6 +185,47 @@ pub fn save_recent_projects(projects: &RecentProjects) {
     }
 }
 
+/// Recently opened or saved scene files, newest first. Shown under File > Open Recent.
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub struct RecentScenes {
+    pub paths: Vec<PathBuf>,
+}
+
+impl RecentScenes {
+    /// Move `path` to the front of the list.
+    pub fn touch(&mut self, path: &Path) {
+        self.paths.retain(|p| p != path);
+        self.paths.insert(0, path.to_path_buf());
+        self.paths.truncate(10);
+    }
+}
+
+fn recent_scenes_file_path() -> Option<PathBuf> {
+    config_dir().map(|d| d.join("recent_scenes.json"))
+}
+
+pub fn read_recent_scenes() -> RecentScenes {
+    let Some(path) = recent_scenes_file_path() else {
+        return RecentScenes::default();
+    };
+    let Ok(data) = std::fs::read_to_string(&path) else {
+        return RecentScenes::default();
+    };
+    serde_json::from_str(&data).unwrap_or_default()
+}
+
+pub fn save_recent_scenes(scenes: &RecentScenes) {
+    let Some(path) = recent_scenes_file_path() else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(data) = serde_json::to_string_pretty(scenes) {
+        let _ = std::fs::write(&path, data);
+    }
+}
+
 pub fn read_last_project() -> Option<PathBuf> {
     let recent = read_recent_projects();
     recent.projects.first().map(|e| e.path.clone())
@@ -92,6 +256,9 @@ pub fn create_default_project(root: &Path) -> JsnProject {
             name,
             description: String::new(),
             default_scene: None,
+            surface_types: Vec::new(),
+            surface_rules: Vec::new(),
+            history_limit: None,
         },
     };
 
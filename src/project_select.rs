@@ -10,6 +10,7 @@ use rfd::AsyncFileDialog;
 
 use crate::{
     AppState,
+    asset_root::AssetRoot,
     project::{self, ProjectRoot},
 };
 
@@ -324,11 +325,20 @@ fn select_project(world: &mut World, root: PathBuf) {
     // Update recent projects
     project::touch_recent(&root, &config.project.name);
 
-    // Insert ProjectRoot resource
-    world.insert_resource(ProjectRoot {
+    let settings = project::load_project_settings(&root);
+    let project = ProjectRoot {
         root: root.clone(),
         config,
-    });
+        settings,
+    };
+
+    // Point the asset server at this project's assets
+    if let Some(asset_root) = world.get_resource::<AssetRoot>() {
+        asset_root.set(project.assets_dir());
+    }
+
+    // Insert ProjectRoot resource
+    world.insert_resource(project);
 
     // Despawn selector UI
     let mut to_despawn = Vec::new();
@@ -0,0 +1,197 @@
+//! One recursive filesystem watcher for the editor. It watches the project's asset
+//! directory, plus any other folder a panel asks for, and publishes what changed as a
+//! [`ProjectFilesChanged`] message once writes have settled.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{Mutex, mpsc},
+};
+
+use bevy::{asset::AssetPath, prelude::*};
+use notify::{EventKind, Watcher, event::ModifyKind};
+
+use crate::project::ProjectRoot;
+
+/// Wait this long after the last change event before publishing, so tools that write
+/// a file in several steps only cause one reload.
+const SETTLE_SECS: f32 = 0.25;
+
+/// Source key of the project's asset directory in [`ProjectWatcher::set_root`].
+const PROJECT_ASSETS: &str = "project_assets";
+
+pub struct ProjectWatcherPlugin;
+
+impl Plugin for ProjectWatcherPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<ProjectFilesChanged>()
+            .init_resource::<ProjectWatcher>()
+            .add_systems(
+                Update,
+                (watch_project_assets, publish_file_changes)
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+/// Files changed on disk under a watched directory, published once the writes settle.
+#[derive(Message, Clone, Debug, Default)]
+pub struct ProjectFilesChanged {
+    /// Files written or created, canonicalized where they still exist.
+    pub modified: HashSet<PathBuf>,
+    /// Whether files or folders were created, removed or renamed.
+    pub listing_changed: bool,
+}
+
+/// The editor's filesystem watcher. Directories are watched by source, like
+/// [`StatusHints`](crate::status_bar::StatusHints); one nested in another is covered
+/// by the outer watch.
+#[derive(Resource, Default)]
+pub struct ProjectWatcher {
+    watcher: Option<notify::RecommendedWatcher>,
+    receiver: Option<Mutex<mpsc::Receiver<notify::Event>>>,
+    roots: HashMap<&'static str, PathBuf>,
+    /// Directories the watcher is watching now.
+    watching: Vec<PathBuf>,
+    /// Changes waiting for the writes to settle.
+    pending: ProjectFilesChanged,
+    since_last_change: f32,
+}
+
+impl ProjectWatcher {
+    /// Watch `dir` recursively on behalf of `source`, replacing what it watched before.
+    /// `None` stops watching for that source.
+    pub fn set_root(&mut self, source: &'static str, dir: Option<PathBuf>) {
+        match dir {
+            Some(dir) => {
+                let dir = dir.canonicalize().unwrap_or(dir);
+                if self.roots.get(source) == Some(&dir) {
+                    return;
+                }
+                self.roots.insert(source, dir);
+            }
+            None => {
+                if self.roots.remove(source).is_none() {
+                    return;
+                }
+            }
+        }
+        self.sync_watches();
+    }
+
+    fn sync_watches(&mut self) {
+        if self.watcher.is_none() {
+            let (tx, rx) = mpsc::channel();
+            match notify::recommended_watcher(move |res: Result<notify::Event, notify::Error>| {
+                if let Ok(event) = res {
+                    let _ = tx.send(event);
+                }
+            }) {
+                Ok(watcher) => {
+                    self.watcher = Some(watcher);
+                    self.receiver = Some(Mutex::new(rx));
+                }
+                Err(e) => {
+                    warn!("Failed to create file watcher: {}", e);
+                    return;
+                }
+            }
+        }
+        let Some(watcher) = &mut self.watcher else {
+            return;
+        };
+
+        let roots: Vec<&PathBuf> = self.roots.values().collect();
+        let mut wanted: Vec<PathBuf> = roots
+            .iter()
+            .filter(|dir| {
+                !roots
+                    .iter()
+                    .any(|other| other != *dir && dir.starts_with(other))
+            })
+            .map(|dir| (*dir).clone())
+            .collect();
+        wanted.sort();
+        wanted.dedup();
+
+        for dir in &self.watching {
+            if !wanted.contains(dir) {
+                let _ = watcher.unwatch(dir);
+            }
+        }
+        let mut watching = Vec::with_capacity(wanted.len());
+        for dir in wanted {
+            if self.watching.contains(&dir)
+                || watcher
+                    .watch(&dir, notify::RecursiveMode::Recursive)
+                    .is_ok()
+            {
+                watching.push(dir);
+            } else {
+                warn!("Failed to watch directory: {:?}", dir);
+            }
+        }
+        self.watching = watching;
+    }
+}
+
+/// Resolve an asset path to a file on disk (relative paths live under `root`).
+pub fn resolve_asset_file(root: &Path, path: &AssetPath) -> PathBuf {
+    let path = path.path();
+    let full = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        root.join(path)
+    };
+    full.canonicalize().unwrap_or(full)
+}
+
+fn watch_project_assets(project: Option<Res<ProjectRoot>>, mut watcher: ResMut<ProjectWatcher>) {
+    let Some(project) = project else {
+        return;
+    };
+    if project.is_changed() {
+        watcher.set_root(PROJECT_ASSETS, Some(project.assets_dir()));
+    }
+}
+
+pub(crate) fn publish_file_changes(
+    mut watcher: ResMut<ProjectWatcher>,
+    time: Res<Time>,
+    mut changes: MessageWriter<ProjectFilesChanged>,
+) {
+    let watcher = &mut *watcher;
+    if let Some(Ok(rx)) = watcher.receiver.as_ref().map(Mutex::lock) {
+        for event in rx.try_iter() {
+            let content = matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_));
+            let listing = matches!(
+                event.kind,
+                EventKind::Create(_)
+                    | EventKind::Remove(_)
+                    | EventKind::Modify(ModifyKind::Name(_))
+            );
+            if !content && !listing {
+                continue;
+            }
+            if content {
+                watcher.pending.modified.extend(
+                    event
+                        .paths
+                        .into_iter()
+                        .map(|path| path.canonicalize().unwrap_or(path)),
+                );
+            }
+            watcher.pending.listing_changed |= listing;
+            watcher.since_last_change = 0.0;
+        }
+    }
+    if watcher.pending.modified.is_empty() && !watcher.pending.listing_changed {
+        return;
+    }
+    watcher.since_last_change += time.delta_secs();
+    if watcher.since_last_change < SETTLE_SECS {
+        return;
+    }
+    changes.write(std::mem::take(&mut watcher.pending));
+}
@@ -0,0 +1,298 @@
+//! File > Import Scene: merge another `.jsn` scene into the open one instead of
+//! replacing it, for assembling levels from modular kits.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task, futures_lite::future},
+};
+use jackdaw_feathers::{
+    combobox::{ComboBoxChangeEvent, combobox_with_selected},
+    dialog::{DialogActionEvent, DialogChildrenSlot, DialogClosedEvent, DialogId, OpenDialogEvent},
+    text_edit::{self, TextEditProps, TextEditValue},
+};
+use rfd::{AsyncFileDialog, FileHandle};
+
+use crate::{
+    entity_templates,
+    scene_io::{self, SceneFilePath},
+};
+
+const IMPORT_DIALOG: &str = "scene_import";
+
+pub struct SceneImportPlugin;
+
+impl Plugin for SceneImportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SceneImportSettings>()
+            .init_resource::<PendingSceneImport>()
+            .add_observer(on_import_dialog_action)
+            .add_observer(on_import_dialog_closed)
+            .add_systems(
+                Update,
+                (
+                    poll_import_file_dialog,
+                    populate_import_dialog,
+                    sync_import_fields,
+                )
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+/// Last-used import options. Kept between dialog invocations.
+#[derive(Resource, Clone, Debug)]
+pub struct SceneImportSettings {
+    /// Added to the translation of every imported root entity.
+    pub offset: Vec3,
+    /// Give imported entities whose name is already taken a numbered suffix.
+    pub rename_collisions: bool,
+}
+
+impl Default for SceneImportSettings {
+    fn default() -> Self {
+        Self {
+            offset: Vec3::ZERO,
+            rename_collisions: true,
+        }
+    }
+}
+
+/// The scene file the open Import Scene dialog applies to.
+#[derive(Resource, Default)]
+struct PendingSceneImport {
+    path: Option<PathBuf>,
+}
+
+#[derive(Resource)]
+struct ImportFileTask(Task<Option<FileHandle>>);
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum ImportField {
+    OffsetX,
+    OffsetY,
+    OffsetZ,
+}
+
+/// Pick a `.jsn` scene to merge into the open one.
+pub fn open_import_dialog(world: &mut World) {
+    if world.contains_resource::<ImportFileTask>() {
+        return;
+    }
+    let raw_handle = scene_io::get_window_handle(world);
+    let last_dir = world.resource::<SceneFilePath>().last_directory.clone();
+
+    let mut dialog = AsyncFileDialog::new().add_filter("JSN Scene", &["jsn"]);
+
+    if let Some(dir) = &last_dir {
+        dialog = dialog.set_directory(dir);
+    }
+    if let Some(ref rh) = raw_handle {
+        // SAFETY: called on the main thread during an exclusive system
+        let handle = unsafe { rh.get_handle() };
+        dialog = dialog.set_parent(&handle);
+    }
+
+    let task = AsyncComputeTaskPool::get().spawn(async move { dialog.pick_file().await });
+    world.insert_resource(ImportFileTask(task));
+}
+
+fn poll_import_file_dialog(world: &mut World) {
+    let Some(mut task) = world.remove_resource::<ImportFileTask>() else {
+        return;
+    };
+    let Some(result) = future::block_on(future::poll_once(&mut task.0)) else {
+        world.insert_resource(task); // Not ready, put it back
+        return;
+    };
+    let Some(file) = result else {
+        return;
+    };
+
+    let name = file.file_name();
+    world.resource_mut::<PendingSceneImport>().path = Some(file.path().to_path_buf());
+    world.trigger(
+        OpenDialogEvent::new(format!("Import {name}"), "Import")
+            .with_id(IMPORT_DIALOG)
+            .with_max_width(px(360)),
+    );
+}
+
+/// Fill the dialog's children slot with the import options.
+fn populate_import_dialog(
+    mut commands: Commands,
+    pending: Res<PendingSceneImport>,
+    settings: Res<SceneImportSettings>,
+    slots: Query<(Entity, &DialogId), Added<DialogChildrenSlot>>,
+) {
+    if pending.path.is_none() {
+        return;
+    }
+    for (slot, id) in &slots {
+        if id.0 != IMPORT_DIALOG {
+            continue;
+        }
+        let row = commands
+            .spawn((
+                Node {
+                    column_gap: px(6),
+                    ..default()
+                },
+                ChildOf(slot),
+            ))
+            .id();
+        for (field, label, value) in [
+            (ImportField::OffsetX, "Offset X", settings.offset.x),
+            (ImportField::OffsetY, "Y", settings.offset.y),
+            (ImportField::OffsetZ, "Z", settings.offset.z),
+        ] {
+            commands.spawn((
+                field,
+                text_edit::text_edit(
+                    TextEditProps::default()
+                        .with_label(label)
+                        .numeric_f32()
+                        .grow()
+                        .with_default_value(value.to_string()),
+                ),
+                ChildOf(row),
+            ));
+        }
+
+        commands
+            .spawn((
+                combobox_with_selected(
+                    vec!["Rename duplicate names", "Keep names as-is"],
+                    if settings.rename_collisions { 0 } else { 1 },
+                ),
+                ChildOf(slot),
+            ))
+            .observe(
+                |event: On<ComboBoxChangeEvent>, mut settings: ResMut<SceneImportSettings>| {
+                    settings.rename_collisions = event.selected == 0;
+                },
+            );
+    }
+}
+
+fn sync_import_fields(
+    pending: Res<PendingSceneImport>,
+    mut settings: ResMut<SceneImportSettings>,
+    fields: Query<(&ImportField, &TextEditValue), Changed<TextEditValue>>,
+) {
+    if pending.path.is_none() {
+        return;
+    }
+    for (field, value) in &fields {
+        let Ok(v) = value.0.trim().parse::<f32>() else {
+            continue;
+        };
+        match field {
+            ImportField::OffsetX => settings.offset.x = v,
+            ImportField::OffsetY => settings.offset.y = v,
+            ImportField::OffsetZ => settings.offset.z = v,
+        }
+    }
+}
+
+/// However the dialog closes, forget the file; the Import action has taken it by then.
+fn on_import_dialog_closed(event: On<DialogClosedEvent>, mut pending: ResMut<PendingSceneImport>) {
+    if event.id == Some(IMPORT_DIALOG) {
+        pending.path = None;
+    }
+}
+
+fn on_import_dialog_action(event: On<DialogActionEvent>, mut commands: Commands) {
+    if event.id != Some(IMPORT_DIALOG) {
+        return;
+    }
+    commands.queue(|world: &mut World| {
+        let Some(path) = world.resource_mut::<PendingSceneImport>().path.take() else {
+            return;
+        };
+        let settings = world.resource::<SceneImportSettings>().clone();
+        import_scene(world, &path, &settings);
+    });
+}
+
+/// Merge the entities of the scene at `path` into the world as one undoable step.
+/// The imported roots end up selected.
+pub fn import_scene(world: &mut World, path: &Path, settings: &SceneImportSettings) {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(err) => {
+            warn!("Failed to read scene '{}': {err}", path.display());
+            return;
+        }
+    };
+    let jsn = match jackdaw_jsn::parse_scene(&json) {
+        Ok((jsn, report)) => {
+            for warning in &report.warnings {
+                warn!("{}: {warning}", path.display());
+            }
+            jsn
+        }
+        Err(err) => {
+            warn!("Failed to parse scene '{}': {err}", path.display());
+            return;
+        }
+    };
+
+    let mut taken: HashSet<String> = if settings.rename_collisions {
+        let editor_set = scene_io::collect_editor_entities(world);
+        scene_io::collect_scene_entities_from_set(world, &editor_set)
+            .into_iter()
+            .filter_map(|e| world.get::<Name>(e).map(|n| n.as_str().to_string()))
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
+    let parent_path = path.parent().unwrap_or(Path::new(""));
+    let local_assets = scene_io::load_inline_assets(world, &jsn.assets, parent_path);
+    let (spawned, roots) = entity_templates::spawn_jsn_entities(
+        world,
+        &jsn.scene,
+        settings.offset,
+        parent_path,
+        &local_assets,
+    );
+
+    let mut renamed = 0;
+    if settings.rename_collisions {
+        for &entity in &spawned {
+            let Some(name) = world.get::<Name>(entity).map(|n| n.as_str().to_string()) else {
+                continue;
+            };
+            let unique = unique_name(&name, &taken);
+            if unique != name {
+                world.entity_mut(entity).insert(Name::new(unique.clone()));
+                renamed += 1;
+            }
+            taken.insert(unique);
+        }
+    }
+
+    entity_templates::finalize_instantiation(world, &roots);
+    info!(
+        "Imported {} entities from {} ({renamed} renamed)",
+        spawned.len(),
+        path.display()
+    );
+}
+
+/// `name`, or `name (2)`, `name (3)`, ... whichever is first not in `taken`.
+fn unique_name(name: &str, taken: &HashSet<String>) -> String {
+    if !taken.contains(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|i| format!("{name} ({i})"))
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap_or_else(|| name.to_string())
+}
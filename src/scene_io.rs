@@ -18,14 +18,25 @@ use bevy::{
     tasks::{AsyncComputeTaskPool, IoTaskPool, Task, futures_lite::future},
     window::{PrimaryWindow, RawHandleWrapper},
 };
-use jackdaw_jsn::format::{JsnAssets, JsnEntity, JsnHeader, JsnMetadata, JsnScene};
+use jackdaw_feathers::{
+    dialog::{CloseDialogEvent, DialogChildrenSlot, EditorDialog, OpenDialogEvent},
+    icons::EditorFont,
+    tokens,
+};
+use jackdaw_jsn::format::{JsnAssets, JsnEntity, JsnHeader, JsnMetadata, JsnPatch, JsnScene};
+use jackdaw_widgets::menu_bar::MenuBarItem;
 use rfd::{AsyncFileDialog, FileHandle};
 use serde::de::{DeserializeSeed, Visitor};
 use serde::{Deserializer, Serializer};
 
 use crate::brush::BrushMaterialPalette;
+use crate::commands::{
+    CommandGroup, CommandHistory, EditorCommand, SetComponentField, SetTransform,
+};
+use crate::project::{RecentScenes, read_recent_scenes, save_recent_scenes};
+use crate::unsaved_changes::{AfterPrompt, cancel_after_save, mark_saved, prompt_if_unsaved};
 use crate::{EditorEntity, EditorHidden, NonSerializable};
-use jackdaw_jsn::Brush;
+use jackdaw_jsn::{Brush, MaterialOverrideApplied, OriginalMaterial, SceneEnvironment};
 
 /// Component type path prefixes that should never be saved (runtime-only / internal).
 const SKIP_COMPONENT_PREFIXES: &[&str] = &[
@@ -41,6 +52,7 @@ const SKIP_COMPONENT_PREFIXES: &[&str] = &[
 const SKIP_COMPONENT_PATHS: &[&str] = &[
     "bevy_transform::components::transform::TransformTreeChanged",
     "bevy_light::cascade::Cascades",
+    "jackdaw_jsn::types::OriginalMaterial",
 ];
 
 pub fn should_skip_component(type_path: &str) -> bool {
@@ -55,14 +67,30 @@ pub fn should_skip_component(type_path: &str) -> bool {
     SKIP_COMPONENT_PATHS.contains(&type_path)
 }
 
+/// Menu action prefix of the File > Open Recent entries; followed by the list index.
+pub const OPEN_RECENT_ACTION: &str = "file.recent.";
+
 pub struct SceneIoPlugin;
 
 impl Plugin for SceneIoPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<SceneFilePath>().add_systems(
-            Update,
-            (handle_scene_io_keys, poll_scene_dialog).run_if(in_state(crate::AppState::Editor)),
-        );
+        app.init_resource::<SceneFilePath>()
+            .init_resource::<SceneWrites>()
+            .insert_resource(read_recent_scenes())
+            .add_systems(
+                Update,
+                (
+                    handle_scene_io_keys,
+                    poll_scene_dialog,
+                    poll_scene_load,
+                    populate_scene_load_dialog,
+                    update_scene_load_progress.run_if(resource_exists::<SceneLoad>),
+                    sync_open_recent_menu,
+                    poll_scene_writes,
+                )
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
     }
 }
 
@@ -70,6 +98,7 @@ impl Plugin for SceneIoPlugin {
 enum SceneDialogTask {
     Save(Task<Option<FileHandle>>),
     Load(Task<Option<FileHandle>>),
+    ApplyPatch(Task<Option<FileHandle>>),
 }
 
 /// Stores the currently active scene file path and metadata.
@@ -80,7 +109,7 @@ pub struct SceneFilePath {
     pub last_directory: Option<PathBuf>,
 }
 
-fn get_window_handle(world: &mut World) -> Option<RawHandleWrapper> {
+pub(crate) fn get_window_handle(world: &mut World) -> Option<RawHandleWrapper> {
     world
         .query_filtered::<&RawHandleWrapper, With<PrimaryWindow>>()
         .single(world)
@@ -130,7 +159,128 @@ fn spawn_open_dialog(world: &mut World) {
     world.insert_resource(SceneDialogTask::Load(task));
 }
 
+/// Open a file dialog to pick a `.jsn` patch exported by the embedded editor.
+pub fn apply_patch_file(world: &mut World) {
+    if world.contains_resource::<SceneDialogTask>() {
+        return;
+    }
+    let raw_handle = get_window_handle(world);
+    let last_dir = world.resource::<SceneFilePath>().last_directory.clone();
+
+    let mut dialog = AsyncFileDialog::new().add_filter("JSN Patch", &["jsn"]);
+
+    if let Some(dir) = &last_dir {
+        dialog = dialog.set_directory(dir);
+    }
+    if let Some(ref rh) = raw_handle {
+        // SAFETY: called on the main thread during an exclusive system
+        let handle = unsafe { rh.get_handle() };
+        dialog = dialog.set_parent(&handle);
+    }
+
+    let task = AsyncComputeTaskPool::get().spawn(async move { dialog.pick_file().await });
+    world.insert_resource(SceneDialogTask::ApplyPatch(task));
+}
+
+fn finish_apply_patch(world: &mut World, chosen: &Path) {
+    let json = match std::fs::read_to_string(chosen) {
+        Ok(json) => json,
+        Err(e) => {
+            warn!("Failed to read patch {}: {e}", chosen.display());
+            return;
+        }
+    };
+    match serde_json::from_str::<JsnPatch>(&json) {
+        Ok(patch) => apply_patch(world, &patch),
+        Err(e) => warn!("Failed to parse patch {}: {e}", chosen.display()),
+    }
+}
+
+/// Apply a runtime patch to the open scene as a single undoable step.
+/// Entities are matched by `StableId`, or by `Name` for entries without one. Entries
+/// with no matching entity, or whose name several entities share, are skipped.
+pub fn apply_patch(world: &mut World, patch: &JsnPatch) {
+    let editor_set = collect_editor_entities(world);
+    let scene_entities = collect_scene_entities_from_set(world, &editor_set);
+    let mut by_name: HashMap<String, Vec<Entity>> = HashMap::new();
+    for &entity in &scene_entities {
+        if let Some(name) = world.get::<Name>(entity) {
+            by_name
+                .entry(name.as_str().to_string())
+                .or_default()
+                .push(entity);
+        }
+    }
+
+    let mut cmds: Vec<Box<dyn EditorCommand>> = Vec::new();
+    let mut missing = 0;
+    let mut ambiguous = 0;
+    for entry in &patch.patches {
+        let by_id = entry
+            .stable_id
+            .and_then(|id| crate::stable_id::resolve_stable_id(world, id));
+        let entity = match (by_id, by_name.get(&entry.name).map(Vec::as_slice)) {
+            (Some(entity), _) => entity,
+            (None, Some(&[entity])) => entity,
+            (None, Some([_, _, ..])) => {
+                warn!(
+                    "Patch: skipped '{}', several entities share the name",
+                    entry.name
+                );
+                ambiguous += 1;
+                continue;
+            }
+            (None, _) => {
+                missing += 1;
+                continue;
+            }
+        };
+        if let Some(transform) = &entry.transform {
+            let old_transform = world.get::<Transform>(entity).copied().unwrap_or_default();
+            cmds.push(Box::new(SetTransform {
+                entity,
+                old_transform,
+                new_transform: transform.clone().into(),
+            }));
+        }
+        if let Some(visibility) = &entry.visibility {
+            let old_visibility = world.get::<Visibility>(entity).copied().unwrap_or_default();
+            cmds.push(Box::new(SetComponentField {
+                entity,
+                component_type_id: TypeId::of::<Visibility>(),
+                field_path: String::new(),
+                old_value: Box::new(old_visibility),
+                new_value: Box::new(Visibility::from(visibility.clone())),
+            }));
+        }
+    }
+
+    if missing > 0 {
+        warn!("Patch: {missing} entry(s) did not match any entity");
+    }
+    if ambiguous > 0 {
+        warn!("Patch: {ambiguous} entry(s) matched several entities by name and were skipped");
+    }
+    if cmds.is_empty() {
+        return;
+    }
+
+    let count = cmds.len();
+    let group = CommandGroup {
+        commands: cmds,
+        label: "Apply patch".to_string(),
+    };
+    group.execute(world);
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(group));
+    info!("Applied {count} patch change(s)");
+}
+
 pub fn save_scene(world: &mut World) {
+    if world.contains_resource::<SceneLoad>() {
+        return; // Saving now would write a partially loaded scene
+    }
+
     // If no path is set yet, delegate to Save As
     let has_path = world.resource::<SceneFilePath>().path.is_some();
     if !has_path {
@@ -142,7 +292,7 @@ pub fn save_scene(world: &mut World) {
 }
 
 pub fn save_scene_as(world: &mut World) {
-    if world.contains_resource::<SceneDialogTask>() {
+    if world.contains_resource::<SceneDialogTask>() || world.contains_resource::<SceneLoad>() {
         return; // Dialog already open
     }
     spawn_save_dialog(world);
@@ -159,47 +309,7 @@ fn save_scene_inner(world: &mut World) {
         None => Cow::Owned(env::current_dir().expect("Couldn't access the current directory")),
     };
 
-    // Pre-compute entity lists while we have &mut World
-    let editor_set = collect_editor_entities(world);
-    let scene_entities = collect_scene_entities_from_set(world, &editor_set);
-
-    // Resolve default brush face materials to the palette material so they serialize as inline assets
-    let default_faces = resolve_default_brush_materials(world, &scene_entities);
-
-    let registry = world.resource::<AppTypeRegistry>().clone();
-    let registry_guard = registry.read();
-
-    // Get catalog reverse lookup for emitting @Name references
-    let catalog_id_to_name = world
-        .get_resource::<crate::asset_catalog::AssetCatalog>()
-        .map(|c| c.id_to_name.clone())
-        .unwrap_or_default();
-
-    // --- Phase 1: Collect inline assets from all scene entity components ---
-    let (inline_assets, inline_asset_data) = collect_inline_assets(
-        world,
-        &registry_guard,
-        &parent_path,
-        &scene_entities,
-        &catalog_id_to_name,
-    );
-
-    // --- Phase 2: Build entity list and serialize ---
-    let entities = build_scene_snapshot(
-        world,
-        &registry_guard,
-        &parent_path,
-        &inline_assets,
-        &scene_entities,
-    );
-
-    // --- Phase 3: Serialize inline asset data into JsnAssets ---
-    let assets = JsnAssets(inline_asset_data);
-
-    drop(registry_guard);
-
-    // Restore Handle::default() on faces that were temporarily resolved
-    restore_default_brush_materials(world, &default_faces);
+    let (entities, assets) = serialize_scene_entities(world, &parent_path);
 
     // Build metadata
     let now = chrono_now();
@@ -214,10 +324,12 @@ fn save_scene_inner(world: &mut World) {
     }
 
     let jsn = JsnScene {
+        version: jackdaw_jsn::CURRENT_SCENE_VERSION,
         jsn: JsnHeader::default(),
         metadata: metadata.clone(),
         assets,
         editor: None,
+        environment: Some(world.resource::<SceneEnvironment>().clone()),
         scene: entities,
     };
 
@@ -240,29 +352,183 @@ fn save_scene_inner(world: &mut World) {
     // Save metadata back
     let mut scene_path = world.resource_mut::<SceneFilePath>();
     scene_path.metadata = metadata;
+    remember_recent_scene(world, Path::new(&path));
 
     // Write to disk on the IO task pool
     let path_clone = path.clone();
-    IoTaskPool::get()
-        .spawn(async move {
-            match std::fs::write(&path_clone, &json) {
-                Ok(()) => info!("Scene saved to {path_clone}"),
-                Err(err) => warn!("Failed to write scene file: {err}"),
-            }
-        })
-        .detach();
+    let write = IoTaskPool::get().spawn(async move {
+        match std::fs::write(&path_clone, &json) {
+            Ok(()) => info!("Scene saved to {path_clone}"),
+            Err(err) => warn!("Failed to write scene file: {err}"),
+        }
+    });
+    world.resource_mut::<SceneWrites>().0.push(write);
 
     // Save catalog alongside scene if dirty
     crate::asset_catalog::save_catalog(world);
+    mark_saved(world);
+
+    crate::problems::validate_after_save(world);
+}
+
+/// Serialize the scene's entities and the inline assets they use, with file paths
+/// relative to `parent_path`.
+pub(crate) fn serialize_scene_entities(
+    world: &mut World,
+    parent_path: &Path,
+) -> (Vec<JsnEntity>, JsnAssets) {
+    // Shading previews swap materials; serialize the authored ones.
+    crate::view_modes::restore_original_materials(world);
+    // Isolation hides the rest of the scene; serialize the authored visibility.
+    crate::isolation::restore_isolated_visibility(world);
+
+    // Pre-compute entity lists while we have &mut World
+    let editor_set = collect_editor_entities(world);
+    let scene_entities = collect_scene_entities_from_set(world, &editor_set);
+    serialize_entities(world, parent_path, &scene_entities)
+}
+
+/// Serialize `scene_entities` with the inline assets they use. Parent indices only
+/// point at entities within the given set.
+pub(crate) fn serialize_entities(
+    world: &mut World,
+    parent_path: &Path,
+    scene_entities: &[Entity],
+) -> (Vec<JsnEntity>, JsnAssets) {
+    // Resolve default brush face materials to the palette material so they serialize as inline assets
+    let default_faces = resolve_default_brush_materials(world, scene_entities);
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry_guard = registry.read();
+
+    // Get catalog reverse lookup for emitting @Name references
+    let catalog_id_to_name = world
+        .get_resource::<crate::asset_catalog::AssetCatalog>()
+        .map(|c| c.id_to_name.clone())
+        .unwrap_or_default();
+
+    // --- Phase 1: Collect inline assets from all scene entity components ---
+    let (inline_assets, inline_asset_data) = collect_inline_assets(
+        world,
+        &registry_guard,
+        parent_path,
+        scene_entities,
+        &catalog_id_to_name,
+    );
+
+    // --- Phase 2: Build entity list and serialize ---
+    let entities = build_scene_snapshot(
+        world,
+        &registry_guard,
+        parent_path,
+        &inline_assets,
+        scene_entities,
+    );
+
+    // --- Phase 3: Serialize inline asset data into JsnAssets ---
+    let assets = JsnAssets(inline_asset_data);
+
+    drop(registry_guard);
+
+    // Restore Handle::default() on faces that were temporarily resolved
+    restore_default_brush_materials(world, &default_faces);
+
+    (entities, assets)
+}
+
+/// Scene file writes still running on the IO pool. Kept so they aren't cancelled
+/// by being dropped, and so quitting can wait for them.
+#[derive(Resource, Default)]
+struct SceneWrites(Vec<Task<()>>);
+
+fn poll_scene_writes(mut writes: ResMut<SceneWrites>) {
+    if !writes.0.is_empty() {
+        writes.0.retain(|task| !task.is_finished());
+    }
+}
+
+/// Block until every pending scene write has reached the disk.
+pub(crate) fn flush_scene_writes(world: &mut World) {
+    for task in std::mem::take(&mut world.resource_mut::<SceneWrites>().0) {
+        future::block_on(task);
+    }
 }
 
 pub fn load_scene(world: &mut World) {
     if world.contains_resource::<SceneDialogTask>() {
         return; // Dialog already open
     }
+    if prompt_if_unsaved(world, AfterPrompt::OpenDialog) {
+        return;
+    }
+    load_scene_now(world);
+}
+
+/// [`load_scene`] without asking about unsaved changes.
+pub(crate) fn load_scene_now(world: &mut World) {
+    if world.contains_resource::<SceneDialogTask>() {
+        return;
+    }
     spawn_open_dialog(world);
 }
 
+/// Load the `index`th entry of File > Open Recent, dropping it if the file is gone.
+pub fn open_recent_scene(world: &mut World, index: usize) {
+    if world.contains_resource::<SceneDialogTask>() || world.contains_resource::<SceneLoad>() {
+        return;
+    }
+    if prompt_if_unsaved(world, AfterPrompt::OpenRecent(index)) {
+        return;
+    }
+    open_recent_scene_now(world, index);
+}
+
+pub(crate) fn open_recent_scene_now(world: &mut World, index: usize) {
+    let Some(path) = world.resource::<RecentScenes>().paths.get(index).cloned() else {
+        return;
+    };
+    if !path.is_file() {
+        warn!("Recent scene '{}' no longer exists", path.display());
+        let mut recent = world.resource_mut::<RecentScenes>();
+        recent.paths.retain(|p| *p != path);
+        save_recent_scenes(&recent);
+        return;
+    }
+    finish_load_scene(world, &path);
+}
+
+fn remember_recent_scene(world: &mut World, path: &Path) {
+    let mut recent = world.resource_mut::<RecentScenes>();
+    recent.touch(path);
+    save_recent_scenes(&recent);
+}
+
+/// Keep the File menu's Open Recent submenu in step with the recent scenes list.
+fn sync_open_recent_menu(recent: Res<RecentScenes>, mut menus: Query<&mut MenuBarItem>) {
+    for mut menu in &mut menus {
+        if menu.label != "File" || !(recent.is_changed() || menu.is_added()) {
+            continue;
+        }
+        let entries = recent
+            .paths
+            .iter()
+            .enumerate()
+            .map(|(i, path)| {
+                let file = path
+                    .file_name()
+                    .map(|f| f.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string());
+                let label = match path.parent().and_then(|p| p.file_name()) {
+                    Some(dir) => format!("{file} ({})", dir.to_string_lossy()),
+                    None => file,
+                };
+                (format!("{OPEN_RECENT_ACTION}{i}"), label)
+            })
+            .collect();
+        menu.set_submenu("file.open_recent", entries);
+    }
+}
+
 // ─────────────────────────────────── Serializer Processor ───────────────────────────────────
 
 struct JsnSerializerProcessor<'a> {
@@ -618,6 +884,7 @@ fn collect_inline_assets(
 
     for &entity in scene_entities {
         let entity_ref = world.entity(entity);
+        let shared_material = shared_material(entity_ref);
 
         for registration in registry.iter() {
             if skip_ids.contains(&registration.type_id()) {
@@ -633,6 +900,15 @@ fn collect_inline_assets(
             let Some(component) = reflect_component.reflect(entity_ref) else {
                 continue;
             };
+            let component = match &shared_material {
+                Some(material)
+                    if registration.type_id()
+                        == TypeId::of::<MeshMaterial3d<StandardMaterial>>() =>
+                {
+                    material as &dyn Reflect
+                }
+                _ => component,
+            };
 
             // Walk the reflected value looking for Handle<T> fields
             collect_handles_from_reflect(
@@ -651,6 +927,17 @@ fn collect_inline_assets(
     (id_to_name, asset_data)
 }
 
+/// The authored material of an entity whose `MeshMaterial3d` points at a `MaterialOverride`
+/// copy or a view mode stand-in, saved in its place so the file keeps referencing it.
+fn shared_material(entity_ref: EntityRef) -> Option<MeshMaterial3d<StandardMaterial>> {
+    if !entity_ref.contains::<MaterialOverrideApplied>()
+        && !entity_ref.contains::<OriginalMaterial>()
+    {
+        return None;
+    }
+    crate::view_modes::authored_material(entity_ref).map(MeshMaterial3d)
+}
+
 /// Recursively walk a reflected value looking for `Handle<T>` fields that are runtime-created.
 fn collect_handles_from_reflect(
     value: &dyn PartialReflect,
@@ -1048,6 +1335,7 @@ fn build_scene_snapshot(
 
             // Extensible components via reflection — now with processor
             let mut components = HashMap::new();
+            let shared_material = shared_material(entity_ref);
 
             for registration in registry.iter() {
                 if skip_ids.contains(&registration.type_id()) {
@@ -1066,6 +1354,15 @@ fn build_scene_snapshot(
                 let Some(component) = reflect_component.reflect(entity_ref) else {
                     continue;
                 };
+                let component = match &shared_material {
+                    Some(material)
+                        if registration.type_id()
+                            == TypeId::of::<MeshMaterial3d<StandardMaterial>>() =>
+                    {
+                        material as &dyn Reflect
+                    }
+                    _ => component,
+                };
 
                 // Serialize with processor — handles Handle<T> → path and Entity → index
                 let serializer =
@@ -1088,13 +1385,58 @@ fn build_scene_snapshot(
 
 // ─────────────────────────────────── Load ───────────────────────────────────
 
+/// Replace the open scene with the scene file at `path`.
+pub fn open_scene_file(world: &mut World, path: &Path) {
+    if prompt_if_unsaved(world, AfterPrompt::OpenFile(path.to_path_buf())) {
+        return;
+    }
+    open_scene_file_now(world, path);
+}
+
+pub(crate) fn open_scene_file_now(world: &mut World, path: &Path) {
+    finish_load_scene(world, path);
+}
+
 fn finish_load_scene(world: &mut World, chosen: &std::path::Path) {
+    if world.contains_resource::<SceneLoad>() {
+        warn!("A scene is already loading");
+        return;
+    }
+
     let path = chosen.to_string_lossy().to_string();
     let last_dir = chosen.parent().map(|p| p.to_path_buf());
 
     // Update last directory
     world.resource_mut::<SceneFilePath>().last_directory = last_dir;
 
+    if !path.ends_with(".scene.json") {
+        // JSN v2 format: read and parse off the main thread, spawn in `poll_scene_load`
+        let file_path = chosen.to_path_buf();
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            let json = std::fs::read_to_string(&file_path).map_err(|e| e.to_string())?;
+            jackdaw_jsn::parse_scene(&json)
+        });
+
+        let file_name = chosen
+            .file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.clone());
+        world.insert_resource(SceneLoad {
+            path,
+            stage: SceneLoadStage::Parsing(task),
+            dialog_seen: false,
+        });
+
+        let mut dialog = OpenDialogEvent::new("Loading Scene", "")
+            .with_close_button(false)
+            .with_close_on_click_outside(false)
+            .with_max_width(px(360));
+        dialog.action = None;
+        dialog.description = Some(file_name);
+        world.trigger(dialog);
+        return;
+    }
+
     let json = match std::fs::read_to_string(&path) {
         Ok(json) => json,
         Err(err) => {
@@ -1103,67 +1445,268 @@ fn finish_load_scene(world: &mut World, chosen: &std::path::Path) {
         }
     };
 
-    if path.ends_with(".scene.json") {
-        // Legacy format: raw DynamicScene JSON
-        let registry = world.resource::<AppTypeRegistry>().clone();
-        let registry = registry.read();
+    // Legacy format: raw DynamicScene JSON
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
 
-        use bevy::scene::serde::SceneDeserializer;
-        let scene_deserializer = SceneDeserializer {
-            type_registry: &registry,
-        };
-        let mut json_de = serde_json::Deserializer::from_str(&json);
-        let scene = match scene_deserializer.deserialize(&mut json_de) {
-            Ok(scene) => scene,
-            Err(err) => {
-                warn!("Failed to deserialize legacy scene: {err}");
-                return;
-            }
-        };
+    use bevy::scene::serde::SceneDeserializer;
+    let scene_deserializer = SceneDeserializer {
+        type_registry: &registry,
+    };
+    let mut json_de = serde_json::Deserializer::from_str(&json);
+    let scene = match scene_deserializer.deserialize(&mut json_de) {
+        Ok(scene) => scene,
+        Err(err) => {
+            warn!("Failed to deserialize legacy scene: {err}");
+            return;
+        }
+    };
 
-        drop(registry);
-        clear_scene_entities(world);
-        match scene.write_to_world(world, &mut Default::default()) {
-            Ok(_) => info!("Scene loaded from {path} (legacy format)"),
-            Err(err) => warn!("Failed to write scene to world: {err}"),
+    drop(registry);
+    clear_scene_entities(world);
+    match scene.write_to_world(world, &mut Default::default()) {
+        Ok(_) => info!("Scene loaded from {path} (legacy format)"),
+        Err(err) => warn!("Failed to write scene to world: {err}"),
+    }
+    remember_recent_scene(world, chosen);
+    mark_saved(world);
+
+    world.resource_mut::<SceneFilePath>().path = Some(path);
+}
+
+/// Number of entities whose components are deserialized per frame while loading.
+const LOAD_CHUNK_SIZE: usize = 250;
+
+/// A scene load in progress. Parsing runs on the async compute pool; entities are
+/// then spawned up front and their components inserted in chunks across frames.
+#[derive(Resource)]
+struct SceneLoad {
+    path: String,
+    stage: SceneLoadStage,
+    /// Whether the progress dialog has been observed; its disappearance means cancel.
+    dialog_seen: bool,
+}
+
+enum SceneLoadStage {
+    Parsing(Task<Result<(JsnScene, jackdaw_jsn::JsnLoadReport), String>>),
+    Spawning {
+        entities: Vec<JsnEntity>,
+        spawned: Vec<Entity>,
+        parent_path: PathBuf,
+        local_assets: HashMap<String, UntypedHandle>,
+        /// Index of the next entity whose components still need inserting.
+        next: usize,
+    },
+}
+
+impl SceneLoad {
+    /// Entities with components inserted so far, and the total. `None` while parsing.
+    fn progress(&self) -> Option<(usize, usize)> {
+        match &self.stage {
+            SceneLoadStage::Parsing(_) => None,
+            SceneLoadStage::Spawning { entities, next, .. } => Some((*next, entities.len())),
         }
-    } else {
-        // JSN v2 format
-        let jsn: JsnScene = match serde_json::from_str(&json) {
-            Ok(jsn) => jsn,
-            Err(err) => {
-                warn!("Failed to parse JSN file: {err}");
+    }
+}
+
+/// Fill of the loading dialog's progress bar.
+#[derive(Component)]
+struct SceneLoadProgressFill;
+
+/// Text under the loading dialog's progress bar.
+#[derive(Component)]
+struct SceneLoadProgressLabel;
+
+/// Advance the scene load by one step: finish parsing, or insert the next chunk.
+fn poll_scene_load(world: &mut World) {
+    let Some(mut load) = world.remove_resource::<SceneLoad>() else {
+        return;
+    };
+
+    // The dialog has no action button; Cancel or Esc closing it aborts the load.
+    let dialog_open = world
+        .query_filtered::<(), With<EditorDialog>>()
+        .iter(world)
+        .next()
+        .is_some();
+    if load.dialog_seen && !dialog_open {
+        cancel_scene_load(world, load);
+        return;
+    }
+    load.dialog_seen |= dialog_open;
+
+    match &mut load.stage {
+        SceneLoadStage::Parsing(task) => {
+            let Some(result) = future::block_on(future::poll_once(task)) else {
+                world.insert_resource(load);
                 return;
+            };
+            let (jsn, report) = match result {
+                Ok(parsed) => parsed,
+                Err(err) => {
+                    warn!("Failed to load scene '{}': {err}", load.path);
+                    world.trigger(CloseDialogEvent);
+                    return;
+                }
+            };
+
+            if report.migrated() {
+                info!(
+                    "Upgraded {} from scene format v{} to v{}; save to keep the upgrade",
+                    load.path, report.from_version, report.to_version
+                );
             }
-        };
+            for warning in &report.warnings {
+                warn!("{}: {warning}", load.path);
+            }
+
+            clear_scene_entities(world);
 
-        // Check format version
-        if jsn.jsn.format_version[0] < 2 {
-            warn!(
-                "JSN format version {:?} is not supported. Please re-save with the latest editor.",
-                jsn.jsn.format_version
+            let parent_path = Path::new(&load.path)
+                .parent()
+                .unwrap_or(Path::new("."))
+                .to_path_buf();
+
+            // Deserialize inline assets before entities
+            let local_assets = load_inline_assets(world, &jsn.assets, &parent_path);
+            let spawned = spawn_jsn_entities(world, &jsn.scene);
+
+            // Restore metadata and environment
+            world.resource_mut::<SceneFilePath>().metadata = jsn.metadata;
+            world.insert_resource(jsn.environment.unwrap_or_default());
+
+            load.stage = SceneLoadStage::Spawning {
+                entities: jsn.scene,
+                spawned,
+                parent_path,
+                local_assets,
+                next: 0,
+            };
+        }
+        SceneLoadStage::Spawning {
+            entities,
+            spawned,
+            parent_path,
+            local_assets,
+            next,
+        } => {
+            let end = (*next + LOAD_CHUNK_SIZE).min(entities.len());
+            insert_jsn_components(
+                world,
+                entities,
+                *next..end,
+                spawned,
+                parent_path,
+                local_assets,
             );
-            return;
+            *next = end;
+
+            if end == entities.len() {
+                reload_gltf_sources(world, spawned);
+                info!("Scene loaded from {}", load.path);
+                remember_recent_scene(world, Path::new(&load.path));
+                world.resource_mut::<SceneFilePath>().path = Some(load.path);
+                mark_saved(world);
+                world.trigger(CloseDialogEvent);
+                return;
+            }
         }
+    }
 
-        clear_scene_entities(world);
-
-        let parent_path = Path::new(&path).parent().unwrap_or(Path::new("."));
+    world.insert_resource(load);
+}
 
-        // Deserialize inline assets before entities
-        let local_assets = load_inline_assets(world, &jsn.assets, parent_path);
+/// Abort a load. Before spawning the open scene is untouched; afterwards the
+/// partially loaded entities are removed, leaving an empty untitled scene.
+fn cancel_scene_load(world: &mut World, load: SceneLoad) {
+    let SceneLoadStage::Spawning { spawned, .. } = load.stage else {
+        info!("Cancelled loading {}", load.path);
+        return;
+    };
 
-        // Load entities with processor
-        load_scene_from_jsn(world, &jsn.scene, parent_path, &local_assets);
+    world
+        .resource_mut::<crate::selection::Selection>()
+        .entities
+        .clear();
+    crate::hierarchy::clear_all_tree_rows(world);
+    for entity in spawned {
+        if let Ok(entity_mut) = world.get_entity_mut(entity) {
+            entity_mut.despawn();
+        }
+    }
 
-        info!("Scene loaded from {path}");
+    let mut scene_path = world.resource_mut::<SceneFilePath>();
+    scene_path.path = None;
+    scene_path.metadata = JsnMetadata::default();
+    world.insert_resource(SceneEnvironment::default());
+    info!("Cancelled loading {}", load.path);
+    mark_saved(world);
+}
 
-        // Restore metadata
-        let mut scene_path = world.resource_mut::<SceneFilePath>();
-        scene_path.metadata = jsn.metadata;
+/// Fill the loading dialog's children slot with a progress bar.
+fn populate_scene_load_dialog(
+    mut commands: Commands,
+    load: Option<Res<SceneLoad>>,
+    editor_font: Res<EditorFont>,
+    slots: Query<Entity, Added<DialogChildrenSlot>>,
+) {
+    if load.is_none() {
+        return;
     }
+    for slot in &slots {
+        commands.spawn((
+            Node {
+                width: percent(100),
+                height: px(6),
+                border_radius: BorderRadius::all(px(3)),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            BackgroundColor(tokens::INPUT_BG),
+            ChildOf(slot),
+            children![(
+                SceneLoadProgressFill,
+                Node {
+                    width: percent(0),
+                    height: percent(100),
+                    ..default()
+                },
+                BackgroundColor(tokens::PRIMARY_COLOR.into()),
+            )],
+        ));
+        commands.spawn((
+            SceneLoadProgressLabel,
+            Text::new("Reading file..."),
+            TextFont {
+                font: editor_font.0.clone(),
+                font_size: tokens::TEXT_SIZE,
+                ..default()
+            },
+            TextColor(tokens::TEXT_SECONDARY),
+            ChildOf(slot),
+        ));
+    }
+}
 
-    world.resource_mut::<SceneFilePath>().path = Some(path);
+fn update_scene_load_progress(
+    load: Res<SceneLoad>,
+    mut fills: Query<&mut Node, With<SceneLoadProgressFill>>,
+    mut labels: Query<&mut Text, With<SceneLoadProgressLabel>>,
+) {
+    let Some((done, total)) = load.progress() else {
+        return;
+    };
+    let fraction = if total == 0 {
+        1.0
+    } else {
+        done as f32 / total as f32
+    };
+    for mut node in &mut fills {
+        node.width = percent(fraction * 100.0);
+    }
+    for mut text in &mut labels {
+        text.0 = format!("Loading entities {done} / {total}");
+    }
 }
 
 /// Deserialize inline assets from the generic assets table.
@@ -1312,13 +1855,51 @@ pub fn load_scene_from_jsn(
     parent_path: &Path,
     local_assets: &HashMap<String, UntypedHandle>,
 ) {
-    let registry = world.resource::<AppTypeRegistry>().clone();
-    let asset_server = world.resource::<AssetServer>().clone();
-    let catalog_handles = world
-        .get_resource::<crate::asset_catalog::AssetCatalog>()
-        .map(|c| c.handles.clone())
-        .unwrap_or_default();
+    let spawned = spawn_jsn_entities(world, entities);
+    insert_jsn_components(
+        world,
+        entities,
+        0..entities.len(),
+        &spawned,
+        parent_path,
+        local_assets,
+    );
+    reload_gltf_sources(world, &spawned);
+}
 
+/// Overwrite existing `targets` with the matching `entities`: core fields, then
+/// components. Parents are left to the caller, as `entities` may be a partial set.
+pub(crate) fn apply_jsn_entities(
+    world: &mut World,
+    entities: &[JsnEntity],
+    targets: &[Entity],
+    parent_path: &Path,
+    local_assets: &HashMap<String, UntypedHandle>,
+) {
+    for (jsn, &target) in entities.iter().zip(targets) {
+        let mut entity = world.entity_mut(target);
+        if let Some(name) = &jsn.name {
+            entity.insert(Name::new(name.clone()));
+        }
+        if let Some(t) = &jsn.transform {
+            entity.insert(Transform::from(t.clone()));
+        }
+        let vis: Visibility = jsn.visibility.clone().into();
+        entity.insert(vis);
+    }
+    insert_jsn_components(
+        world,
+        entities,
+        0..entities.len(),
+        targets,
+        parent_path,
+        local_assets,
+    );
+}
+
+/// Spawn one entity per `JsnEntity` with its core fields and parent. Components are
+/// added afterwards by [`insert_jsn_components`].
+fn spawn_jsn_entities(world: &mut World, entities: &[JsnEntity]) -> Vec<Entity> {
     // First pass: spawn entities with core fields
     let mut spawned: Vec<Entity> = Vec::new();
     for (i, jsn) in entities.iter().enumerate() {
@@ -1343,10 +1924,29 @@ pub fn load_scene_from_jsn(
         }
     }
 
-    // Third pass: deserialize extensible components via reflection with processor
+    spawned
+}
+
+/// Deserialize the extensible components of `entities[range]` via reflection onto the
+/// matching `spawned` entities.
+fn insert_jsn_components(
+    world: &mut World,
+    entities: &[JsnEntity],
+    range: std::ops::Range<usize>,
+    spawned: &[Entity],
+    parent_path: &Path,
+    local_assets: &HashMap<String, UntypedHandle>,
+) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let asset_server = world.resource::<AssetServer>().clone();
+    let catalog_handles = world
+        .get_resource::<crate::asset_catalog::AssetCatalog>()
+        .map(|c| c.handles.clone())
+        .unwrap_or_default();
+
     let registry_guard = registry.read();
-    for (i, jsn) in entities.iter().enumerate() {
-        for (type_path, value) in &jsn.components {
+    for i in range {
+        for (type_path, value) in &entities[i].components {
             let Some(registration) = registry_guard.get_with_type_path(type_path) else {
                 warn!("Unknown type '{type_path}' — skipping");
                 continue;
@@ -1361,7 +1961,7 @@ pub fn load_scene_from_jsn(
                 parent_path,
                 local_assets,
                 catalog_assets: &catalog_handles,
-                entity_map: &spawned,
+                entity_map: spawned,
             };
             let deserializer = TypedReflectDeserializer::with_processor(
                 registration,
@@ -1385,9 +1985,10 @@ pub fn load_scene_from_jsn(
             }
         }
     }
-    drop(registry_guard);
+}
 
-    // Post-load: re-trigger GLTF loading for GltfSource entities
+/// Post-load: re-trigger GLTF loading for GltfSource entities
+pub(crate) fn reload_gltf_sources(world: &mut World, spawned: &[Entity]) {
     let gltf_entities: Vec<(Entity, String, usize)> = spawned
         .iter()
         .filter_map(|&e| {
@@ -1405,20 +2006,36 @@ pub fn load_scene_from_jsn(
 }
 
 pub fn new_scene(world: &mut World) {
+    if world.contains_resource::<SceneLoad>() {
+        return;
+    }
+    crate::scene_templates::open_new_scene_dialog(world);
+}
+
+/// [`new_scene`] without asking about unsaved changes.
+pub(crate) fn new_scene_now(world: &mut World) {
+    if world.contains_resource::<SceneLoad>() {
+        return;
+    }
     clear_scene_entities(world);
     let mut scene_path = world.resource_mut::<SceneFilePath>();
     scene_path.path = None;
     scene_path.metadata = JsnMetadata::default();
+    world.insert_resource(SceneEnvironment::default());
     info!("New scene created");
+    mark_saved(world);
 }
 
 // ─────────────────────────────────── Helpers ───────────────────────────────────
 
 /// Collect scene entities (named non-editor entities and all their descendants).
 /// Requires `&mut World` for `query_filtered`.
-fn collect_scene_entities_from_set(world: &mut World, editor_set: &HashSet<Entity>) -> Vec<Entity> {
+pub(crate) fn collect_scene_entities_from_set(
+    world: &mut World,
+    editor_set: &HashSet<Entity>,
+) -> Vec<Entity> {
     let roots: Vec<Entity> = world
-        .query_filtered::<Entity, With<Name>>()
+        .query_filtered::<Entity, (With<Name>, Without<NonSerializable>)>()
         .iter(world)
         .filter(|e| !editor_set.contains(e))
         .collect();
@@ -1445,7 +2062,7 @@ fn collect_scene_entities_from_set(world: &mut World, editor_set: &HashSet<Entit
 }
 
 /// Collect the set of all editor entities (those with `EditorEntity` and all their descendants).
-fn collect_editor_entities(world: &mut World) -> HashSet<Entity> {
+pub(crate) fn collect_editor_entities(world: &mut World) -> HashSet<Entity> {
     let roots: Vec<Entity> = world
         .query_filtered::<Entity, With<EditorEntity>>()
         .iter(world)
@@ -1465,7 +2082,7 @@ fn collect_editor_entities(world: &mut World) -> HashSet<Entity> {
 }
 
 /// Remove scene entities from the world (named non-editor entities + their descendants).
-fn clear_scene_entities(world: &mut World) {
+pub(crate) fn clear_scene_entities(world: &mut World) {
     // Clear selection first to prevent on_entity_deselected observer from
     // firing on stale/despawned tree row entities.
     world
@@ -1485,7 +2102,7 @@ fn clear_scene_entities(world: &mut World) {
 
     // Collect named non-editor entities as roots
     let roots: Vec<Entity> = world
-        .query_filtered::<Entity, With<Name>>()
+        .query_filtered::<Entity, (With<Name>, Without<NonSerializable>)>()
         .iter(world)
         .filter(|e| !editor_set.contains(e))
         .collect();
@@ -1510,7 +2127,7 @@ fn clear_scene_entities(world: &mut World) {
 }
 
 /// ISO 8601 timestamp (simplified — no chrono dependency).
-fn chrono_now() -> String {
+pub(crate) fn chrono_now() -> String {
     let since_epoch = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default();
@@ -1635,6 +2252,8 @@ fn poll_scene_dialog(world: &mut World) {
                 scene_path.last_directory = last_dir;
 
                 save_scene_inner(world);
+            } else {
+                cancel_after_save(world);
             }
         }
         SceneDialogTask::Load(t) => {
@@ -1646,6 +2265,15 @@ fn poll_scene_dialog(world: &mut World) {
                 finish_load_scene(world, file.path());
             }
         }
+        SceneDialogTask::ApplyPatch(t) => {
+            let Some(result) = future::block_on(future::poll_once(t)) else {
+                world.insert_resource(task);
+                return;
+            };
+            if let Some(file) = result {
+                finish_apply_patch(world, file.path());
+            }
+        }
     }
 }
 
@@ -0,0 +1,280 @@
+//! File > New: start a scene from a starter template instead of an empty world.
+
+use bevy::prelude::*;
+use jackdaw_feathers::{
+    combobox::{ComboBoxChangeEvent, combobox_with_selected},
+    dialog::{
+        DialogActionEvent, DialogChildrenSlot, DialogClosedEvent, DialogId, EditorDialog,
+        OpenDialogEvent,
+    },
+};
+use jackdaw_jsn::{SceneEnvironment, SceneSky};
+
+use crate::{
+    brush::Brush,
+    scene_io,
+    unsaved_changes::{AfterPrompt, prompt_if_unsaved},
+};
+
+const NEW_SCENE_DIALOG: &str = "new_scene";
+
+pub struct SceneTemplatesPlugin;
+
+impl Plugin for SceneTemplatesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NewSceneDialog>()
+            .add_observer(on_new_scene_dialog_action)
+            .add_observer(on_new_scene_dialog_closed)
+            .add_systems(
+                Update,
+                (populate_new_scene_dialog, create_chosen_scene)
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+/// Starter content for a new scene.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SceneTemplate {
+    #[default]
+    Empty,
+    /// A sun, a fill light, and a camera.
+    BasicLighting,
+    /// A closed 10x4x10 room of brushes lit from the inside.
+    GrayboxRoom,
+    /// A large ground brush under a low sun and a procedural sky.
+    OutdoorSun,
+}
+
+impl SceneTemplate {
+    pub const ALL: [SceneTemplate; 4] = [
+        SceneTemplate::Empty,
+        SceneTemplate::BasicLighting,
+        SceneTemplate::GrayboxRoom,
+        SceneTemplate::OutdoorSun,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SceneTemplate::Empty => "Empty",
+            SceneTemplate::BasicLighting => "Basic Lighting",
+            SceneTemplate::GrayboxRoom => "Graybox Room",
+            SceneTemplate::OutdoorSun => "Outdoor Sun",
+        }
+    }
+}
+
+/// State of the New Scene dialog.
+#[derive(Resource, Default)]
+struct NewSceneDialog {
+    open: bool,
+    /// Selected in the dialog; kept as the default for next time.
+    template: SceneTemplate,
+    /// Confirmed, waiting for the dialog to close before replacing the scene.
+    chosen: Option<SceneTemplate>,
+}
+
+/// Ask which template to start the new scene from.
+pub fn open_new_scene_dialog(world: &mut World) {
+    let mut dialog = world.resource_mut::<NewSceneDialog>();
+    if dialog.open || dialog.chosen.is_some() {
+        return;
+    }
+    dialog.open = true;
+
+    let mut event = OpenDialogEvent::new("New Scene", "Create")
+        .with_id(NEW_SCENE_DIALOG)
+        .with_max_width(px(360));
+    event.description = Some("Start from a template".to_string());
+    world.trigger(event);
+}
+
+fn populate_new_scene_dialog(
+    mut commands: Commands,
+    dialog: Res<NewSceneDialog>,
+    slots: Query<(Entity, &DialogId), Added<DialogChildrenSlot>>,
+) {
+    if !dialog.open {
+        return;
+    }
+    let selected = SceneTemplate::ALL
+        .iter()
+        .position(|t| *t == dialog.template)
+        .unwrap_or(0);
+    for (slot, id) in &slots {
+        if id.0 != NEW_SCENE_DIALOG {
+            continue;
+        }
+        commands
+            .spawn((
+                combobox_with_selected(
+                    SceneTemplate::ALL.iter().map(|t| t.label()).collect(),
+                    selected,
+                ),
+                ChildOf(slot),
+            ))
+            .observe(
+                |event: On<ComboBoxChangeEvent>, mut dialog: ResMut<NewSceneDialog>| {
+                    if let Some(template) = SceneTemplate::ALL.get(event.selected) {
+                        dialog.template = *template;
+                    }
+                },
+            );
+    }
+}
+
+fn on_new_scene_dialog_action(event: On<DialogActionEvent>, mut dialog: ResMut<NewSceneDialog>) {
+    if event.id == Some(NEW_SCENE_DIALOG) && dialog.open {
+        dialog.open = false;
+        dialog.chosen = Some(dialog.template);
+    }
+}
+
+/// The dialog can be dismissed via Cancel, Esc, or a backdrop click.
+fn on_new_scene_dialog_closed(event: On<DialogClosedEvent>, mut dialog: ResMut<NewSceneDialog>) {
+    if event.id == Some(NEW_SCENE_DIALOG) {
+        dialog.open = false;
+    }
+}
+
+/// Replace the scene once the template dialog is gone, so the unsaved-changes
+/// prompt can take its place.
+fn create_chosen_scene(world: &mut World) {
+    if world.resource::<NewSceneDialog>().chosen.is_none()
+        || world
+            .query_filtered::<(), With<EditorDialog>>()
+            .iter(world)
+            .next()
+            .is_some()
+    {
+        return;
+    }
+    let Some(template) = world.resource_mut::<NewSceneDialog>().chosen.take() else {
+        return;
+    };
+    if prompt_if_unsaved(world, AfterPrompt::NewScene(template)) {
+        return;
+    }
+    create_scene(world, template);
+}
+
+/// Clear the scene (editor entities stay) and spawn the template's content.
+pub fn create_scene(world: &mut World, template: SceneTemplate) {
+    scene_io::new_scene_now(world);
+    match template {
+        SceneTemplate::Empty => {}
+        SceneTemplate::BasicLighting => {
+            spawn_sun(
+                world,
+                10000.0,
+                Quat::from_euler(EulerRot::XYZ, -0.8, 0.4, 0.0),
+            );
+            world.spawn((
+                Name::new("Fill Light"),
+                PointLight {
+                    intensity: 200_000.0,
+                    ..default()
+                },
+                Transform::from_xyz(-4.0, 5.0, 4.0),
+            ));
+            spawn_camera(world, Vec3::new(0.0, 2.0, 8.0));
+        }
+        SceneTemplate::GrayboxRoom => {
+            let room = world
+                .spawn((
+                    Name::new("Room"),
+                    Transform::default(),
+                    Visibility::default(),
+                ))
+                .id();
+            // Inner size 10x4x10 with 0.25 thick shells; the floor top sits at y = 0.
+            let t = 0.125;
+            for (name, half, center) in [
+                (
+                    "Floor",
+                    Vec3::new(5.0 + 2.0 * t, t, 5.0 + 2.0 * t),
+                    Vec3::new(0.0, -t, 0.0),
+                ),
+                (
+                    "Ceiling",
+                    Vec3::new(5.0 + 2.0 * t, t, 5.0 + 2.0 * t),
+                    Vec3::new(0.0, 4.0 + t, 0.0),
+                ),
+                (
+                    "Wall North",
+                    Vec3::new(5.0 + 2.0 * t, 2.0, t),
+                    Vec3::new(0.0, 2.0, -5.0 - t),
+                ),
+                (
+                    "Wall South",
+                    Vec3::new(5.0 + 2.0 * t, 2.0, t),
+                    Vec3::new(0.0, 2.0, 5.0 + t),
+                ),
+                (
+                    "Wall East",
+                    Vec3::new(t, 2.0, 5.0),
+                    Vec3::new(5.0 + t, 2.0, 0.0),
+                ),
+                (
+                    "Wall West",
+                    Vec3::new(t, 2.0, 5.0),
+                    Vec3::new(-5.0 - t, 2.0, 0.0),
+                ),
+            ] {
+                world.spawn((
+                    Name::new(name),
+                    Brush::cuboid(half.x, half.y, half.z),
+                    Transform::from_translation(center),
+                    Visibility::default(),
+                    ChildOf(room),
+                ));
+            }
+            world.spawn((
+                Name::new("Room Light"),
+                PointLight {
+                    shadows_enabled: true,
+                    ..default()
+                },
+                Transform::from_xyz(0.0, 3.5, 0.0),
+                ChildOf(room),
+            ));
+            spawn_camera(world, Vec3::new(0.0, 1.7, 4.0));
+        }
+        SceneTemplate::OutdoorSun => {
+            world.spawn((
+                Name::new("Ground"),
+                Brush::cuboid(50.0, 0.25, 50.0),
+                Transform::from_xyz(0.0, -0.25, 0.0),
+                Visibility::default(),
+            ));
+            spawn_sun(
+                world,
+                100_000.0,
+                Quat::from_euler(EulerRot::YXZ, 0.6, -0.45, 0.0),
+            );
+            spawn_camera(world, Vec3::new(0.0, 2.0, 12.0));
+            world.resource_mut::<SceneEnvironment>().sky = SceneSky::Procedural;
+        }
+    }
+}
+
+fn spawn_sun(world: &mut World, illuminance: f32, rotation: Quat) {
+    world.spawn((
+        Name::new("Sun"),
+        DirectionalLight {
+            shadows_enabled: true,
+            illuminance,
+            ..default()
+        },
+        Transform::from_xyz(10.0, 20.0, 10.0).with_rotation(rotation),
+    ));
+}
+
+fn spawn_camera(world: &mut World, position: Vec3) {
+    world.spawn((
+        Name::new("Camera"),
+        Camera3d::default(),
+        Transform::from_translation(position).looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+    ));
+}
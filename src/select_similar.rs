@@ -0,0 +1,233 @@
+//! Select ▸ Similar: entities sharing a material, glTF source, prefab template or
+//! classname with the active object. An index of those relationships is kept up to date
+//! as components change, so the lookup doesn't scan the scene.
+
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+use jackdaw_jsn::{
+    Brush, CustomProperties, GltfSource, JsnPrefab, PropertyValue, SplineExtrusionMesh,
+};
+
+use crate::{
+    EditorEntity,
+    brush::BrushFaceEntity,
+    entity_classes::CLASSNAME_PROPERTY,
+    selection::{Selection, select_entities},
+    status_bar::StatusHints,
+};
+
+pub struct SelectSimilarPlugin;
+
+impl Plugin for SelectSimilarPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimilarityIndex>()
+            .add_systems(PostUpdate, update_similarity_index);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SimilarKind {
+    Material,
+    GltfSource,
+    Template,
+    Classname,
+}
+
+impl SimilarKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Material => "material",
+            Self::GltfSource => "glTF source",
+            Self::Template => "template",
+            Self::Classname => "classname",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+enum SimilarKey {
+    Material(AssetId<StandardMaterial>),
+    GltfSource(String),
+    Template(String),
+    Classname(String),
+}
+
+impl SimilarKey {
+    fn kind(&self) -> SimilarKind {
+        match self {
+            Self::Material(_) => SimilarKind::Material,
+            Self::GltfSource(_) => SimilarKind::GltfSource,
+            Self::Template(_) => SimilarKind::Template,
+            Self::Classname(_) => SimilarKind::Classname,
+        }
+    }
+}
+
+/// Entities by what they use, and the reverse, for the entities that can be selected.
+#[derive(Resource, Default)]
+pub struct SimilarityIndex {
+    entities: HashMap<SimilarKey, HashSet<Entity>>,
+    keys: HashMap<Entity, Vec<SimilarKey>>,
+}
+
+impl SimilarityIndex {
+    fn set(&mut self, entity: Entity, keys: Vec<SimilarKey>) {
+        self.remove(entity);
+        if keys.is_empty() {
+            return;
+        }
+        for key in &keys {
+            self.entities.entry(key.clone()).or_default().insert(entity);
+        }
+        self.keys.insert(entity, keys);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        for key in self.keys.remove(&entity).into_iter().flatten() {
+            if let Some(set) = self.entities.get_mut(&key) {
+                set.remove(&entity);
+                if set.is_empty() {
+                    self.entities.remove(&key);
+                }
+            }
+        }
+    }
+
+    /// Entities sharing any `kind` key with `entity`, itself included.
+    fn similar_to(&self, entity: Entity, kind: SimilarKind) -> HashSet<Entity> {
+        self.keys
+            .get(&entity)
+            .into_iter()
+            .flatten()
+            .filter(|key| key.kind() == kind)
+            .filter_map(|key| self.entities.get(key))
+            .flatten()
+            .copied()
+            .collect()
+    }
+}
+
+/// Replace the selection with the entities sharing `kind` with the active object.
+pub fn select_similar(world: &mut World, kind: SimilarKind) {
+    let Some(primary) = world.resource::<Selection>().primary() else {
+        return;
+    };
+    let mut similar: Vec<Entity> = world
+        .resource::<SimilarityIndex>()
+        .similar_to(primary, kind)
+        .into_iter()
+        .filter(|&e| e != primary)
+        .collect();
+    if similar.is_empty() {
+        world
+            .resource_mut::<StatusHints>()
+            .prompt(format!("No other entities with the same {}", kind.label()));
+        return;
+    }
+    similar.sort();
+    // Keep the active object primary
+    similar.insert(0, primary);
+    select_entities(world, &similar);
+}
+
+/// Re-index the owners of anything that changed. Meshes count towards their glTF root, so
+/// imported models match by the materials of their parts.
+fn update_similarity_index(
+    mut index: ResMut<SimilarityIndex>,
+    changed: Query<
+        Entity,
+        Or<(
+            Changed<Brush>,
+            Changed<CustomProperties>,
+            Changed<GltfSource>,
+            Changed<JsnPrefab>,
+        )>,
+    >,
+    changed_materials: Query<Entity, Changed<MeshMaterial3d<StandardMaterial>>>,
+    mut removed_brushes: RemovedComponents<Brush>,
+    mut removed_properties: RemovedComponents<CustomProperties>,
+    mut removed_gltf: RemovedComponents<GltfSource>,
+    mut removed_prefabs: RemovedComponents<JsnPrefab>,
+    mut removed_materials: RemovedComponents<MeshMaterial3d<StandardMaterial>>,
+    entities: Query<(
+        Option<&Brush>,
+        Option<&CustomProperties>,
+        Option<&GltfSource>,
+        Option<&JsnPrefab>,
+    )>,
+    materials: Query<
+        &MeshMaterial3d<StandardMaterial>,
+        (Without<BrushFaceEntity>, Without<SplineExtrusionMesh>),
+    >,
+    parents: Query<&ChildOf>,
+    children: Query<&Children>,
+    gltf_roots: Query<(), With<GltfSource>>,
+    editor_entities: Query<(), With<EditorEntity>>,
+) {
+    let owner = |entity: Entity| {
+        parents
+            .iter_ancestors(entity)
+            .find(|&a| gltf_roots.contains(a))
+            .unwrap_or(entity)
+    };
+    let mut dirty: HashSet<Entity> = changed.iter().collect();
+    dirty.extend(
+        changed_materials
+            .iter()
+            .chain(removed_materials.read())
+            .map(owner),
+    );
+    dirty.extend(
+        removed_brushes
+            .read()
+            .chain(removed_properties.read())
+            .chain(removed_gltf.read())
+            .chain(removed_prefabs.read()),
+    );
+
+    for entity in dirty {
+        let Ok((brush, properties, gltf, prefab)) = entities.get(entity) else {
+            index.remove(entity);
+            continue;
+        };
+        if editor_entities.contains(entity) {
+            continue;
+        }
+        let mut keys = Vec::new();
+        if let Some(brush) = brush {
+            keys.extend(
+                brush
+                    .faces
+                    .iter()
+                    .filter(|face| face.material != Handle::default())
+                    .map(|face| SimilarKey::Material(face.material.id())),
+            );
+        }
+        let mesh_parts = std::iter::once(entity).chain(
+            gltf.is_some()
+                .then(|| children.iter_descendants(entity))
+                .into_iter()
+                .flatten(),
+        );
+        keys.extend(
+            mesh_parts
+                .filter_map(|e| materials.get(e).ok())
+                .map(|material| SimilarKey::Material(material.id())),
+        );
+        if let Some(gltf) = gltf {
+            keys.push(SimilarKey::GltfSource(gltf.path.clone()));
+        }
+        if let Some(prefab) = prefab {
+            keys.push(SimilarKey::Template(prefab.path.clone()));
+        }
+        if let Some(PropertyValue::String(classname)) =
+            properties.and_then(|p| p.properties.get(CLASSNAME_PROPERTY))
+        {
+            keys.push(SimilarKey::Classname(classname.clone()));
+        }
+        let mut seen = HashSet::new();
+        keys.retain(|key| seen.insert(key.clone()));
+        index.set(entity, keys);
+    }
+}
@@ -1,11 +1,23 @@
-use bevy::prelude::*;
+use bevy::{asset::uuid::Uuid, ecs::entity::EntityHashMap, prelude::*};
+
+use crate::commands::{CommandHistory, EditorCommand, remap_entity};
+use crate::display_settings::{read_editor_settings, save_editor_settings};
+use crate::stable_id::{resolve_stable_id, stable_id_of};
 
 pub struct SelectionPlugin;
 
 impl Plugin for SelectionPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(Selection::default())
-            .add_observer(on_selected_removed);
+            .insert_resource(SelectionUndo {
+                enabled: read_editor_settings().history.undo_selection,
+                ..Default::default()
+            })
+            .add_observer(on_selected_removed)
+            .add_systems(
+                Last,
+                record_selection_changes.run_if(in_state(crate::AppState::Editor)),
+            );
     }
 }
 
@@ -95,9 +107,184 @@ impl Selection {
     }
 }
 
+/// Replace the selection with `entities` from exclusive world access.
+pub fn select_entities(world: &mut World, entities: &[Entity]) {
+    let previous = std::mem::take(&mut world.resource_mut::<Selection>().entities);
+    for e in previous {
+        if let Ok(mut ec) = world.get_entity_mut(e) {
+            ec.remove::<Selected>();
+        }
+    }
+    // Set before inserting `Selected` so its observers see the new primary.
+    world.resource_mut::<Selection>().entities = entities.to_vec();
+    for &e in entities {
+        if let Ok(mut ec) = world.get_entity_mut(e) {
+            ec.insert(Selected);
+        }
+    }
+}
+
 /// Clean up the Selection resource when a Selected component is removed
 /// (e.g., entity despawned).
 fn on_selected_removed(trigger: On<Remove, Selected>, mut selection: ResMut<Selection>) {
     let entity = trigger.event_target();
     selection.entities.retain(|&e| e != entity);
 }
+
+/// Opt-in undo for selection changes, toggled from Edit > Toggle Selection Undo and kept
+/// in the editor settings.
+#[derive(Resource, Default)]
+pub struct SelectionUndo {
+    pub enabled: bool,
+    /// The selection as of the end of the last frame.
+    recorded: Vec<SelectedEntity>,
+    /// History push and replay counts at the end of the last frame, to tell new edits
+    /// from undo and redo.
+    pushes: u64,
+    replays: u64,
+}
+
+/// Flip [`SelectionUndo`] and save the choice.
+pub fn toggle_selection_undo(world: &mut World) {
+    let mut undo = world.resource_mut::<SelectionUndo>();
+    undo.enabled = !undo.enabled;
+    let enabled = undo.enabled;
+    let mut settings = read_editor_settings();
+    settings.history.undo_selection = enabled;
+    save_editor_settings(&settings);
+}
+
+/// A selected entity, with its stable id so it resolves again after being respawned.
+#[derive(Clone, Copy)]
+struct SelectedEntity {
+    entity: Entity,
+    id: Option<Uuid>,
+}
+
+fn resolve_selection(world: &World, selection: &[SelectedEntity]) -> Vec<Entity> {
+    selection
+        .iter()
+        .filter_map(|selected| match selected.id {
+            Some(id) => resolve_stable_id(world, id),
+            None => world
+                .get_entity(selected.entity)
+                .is_ok()
+                .then_some(selected.entity),
+        })
+        .collect()
+}
+
+/// Replace the selection.
+pub struct SetSelection {
+    old: Vec<SelectedEntity>,
+    new: Vec<SelectedEntity>,
+}
+
+impl EditorCommand for SetSelection {
+    fn execute(&self, world: &mut World) {
+        let entities = resolve_selection(world, &self.new);
+        select_entities(world, &entities);
+    }
+
+    fn undo(&self, world: &mut World) {
+        let entities = resolve_selection(world, &self.old);
+        select_entities(world, &entities);
+    }
+
+    fn description(&self) -> &str {
+        "Change selection"
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        for selected in self.old.iter_mut().chain(&mut self.new) {
+            remap_entity(&mut selected.entity, map);
+        }
+    }
+}
+
+/// An edit that also changed the selection. The selection is applied after the edit
+/// both ways, so undoing a delete reselects the restored entities.
+struct WithSelection {
+    command: Box<dyn EditorCommand>,
+    selection: SetSelection,
+}
+
+impl EditorCommand for WithSelection {
+    fn execute(&self, world: &mut World) {
+        self.command.execute(world);
+        self.selection.execute(world);
+    }
+
+    fn undo(&self, world: &mut World) {
+        self.command.undo(world);
+        self.selection.undo(world);
+    }
+
+    fn description(&self) -> &str {
+        self.command.description()
+    }
+
+    fn can_merge(&self, next: &dyn EditorCommand) -> bool {
+        self.command.can_merge(next)
+    }
+
+    fn merge(&mut self, next: &dyn EditorCommand) {
+        self.command.merge(next);
+    }
+
+    fn memory_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.command.memory_size()
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        self.command.remap_entities(map);
+        self.selection.remap_entities(map);
+    }
+}
+
+/// With [`SelectionUndo`] on, record each frame's selection change: folded into the edit
+/// pushed the same frame if there is one, otherwise as its own undo step.
+fn record_selection_changes(world: &mut World) {
+    let current: Vec<SelectedEntity> = world
+        .resource::<Selection>()
+        .entities
+        .iter()
+        .map(|&entity| SelectedEntity {
+            entity,
+            id: stable_id_of(world, entity),
+        })
+        .collect();
+    let history = world.resource::<CommandHistory>();
+    let (pushes, replays) = (history.pushes(), history.replays());
+
+    let mut undo = world.resource_mut::<SelectionUndo>();
+    let replayed = replays != undo.replays;
+    let new_edit = !replayed && pushes != undo.pushes;
+    undo.pushes = pushes;
+    undo.replays = replays;
+    let unchanged = current.len() == undo.recorded.len()
+        && current
+            .iter()
+            .zip(&undo.recorded)
+            .all(|(a, b)| a.entity == b.entity);
+    if unchanged {
+        return;
+    }
+    let old = std::mem::replace(&mut undo.recorded, current.clone());
+    // Undo and redo set the selection themselves
+    if !undo.enabled || replayed {
+        return;
+    }
+
+    let selection = SetSelection { old, new: current };
+    let mut history = world.resource_mut::<CommandHistory>();
+    if new_edit && let Some(command) = history.undo_stack.pop() {
+        history
+            .undo_stack
+            .push(Box::new(WithSelection { command, selection }));
+    } else {
+        history.push_executed(Box::new(selection));
+    }
+    let pushes = history.pushes();
+    world.resource_mut::<SelectionUndo>().pushes = pushes;
+}
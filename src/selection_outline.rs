@@ -0,0 +1,294 @@
+//! Viewport outlines: a strong outline around the selection and a subtle one around the
+//! entity under the cursor. Both are drawn as an overlay pass on top of the scene: brush
+//! edges for brushes, feature edges (silhouette creases and open borders) for meshes.
+//! Colors come from the theme in the display settings. Entities of a colored classname
+//! get a thin outline of their class color.
+
+use std::collections::{HashMap, hash_map::Entry};
+
+use bevy::{
+    picking::mesh_picking::ray_cast::{MeshRayCast, MeshRayCastSettings, RayCastVisibility},
+    prelude::*,
+    ui::UiGlobalTransform,
+    window::PrimaryWindow,
+};
+
+use crate::{
+    EditorEntity,
+    brush::{BrushFaceEntity, BrushMeshCache},
+    classname_colors::ClassnameColors,
+    custom_properties::CustomProperties,
+    display_settings::read_editor_settings,
+    gizmos::{GizmoDragState, GizmoHoverState},
+    modal_transform::{ModalTransformState, ViewportDragState},
+    selection::Selected,
+    viewport::{MainViewportCamera, SceneViewport},
+    viewport_select::find_selectable_ancestor,
+    viewport_util::window_to_viewport_cursor,
+};
+
+/// Mesh edges between faces bent more than this count as feature edges.
+const FEATURE_EDGE_ANGLE: f32 = 30.0;
+
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct SelectionOutlineGizmoGroup;
+
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct HoverOutlineGizmoGroup;
+
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct ClassnameOutlineGizmoGroup;
+
+pub struct SelectionOutlinePlugin;
+
+impl Plugin for SelectionOutlinePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(read_editor_settings().theme.outline_colors())
+            .init_resource::<HoveredEntity>()
+            .init_resource::<FeatureEdgeCache>()
+            .init_gizmo_group::<SelectionOutlineGizmoGroup>()
+            .init_gizmo_group::<HoverOutlineGizmoGroup>()
+            .init_gizmo_group::<ClassnameOutlineGizmoGroup>()
+            .add_systems(Startup, configure_outline_gizmos)
+            .add_systems(
+                Update,
+                update_hovered_entity.run_if(in_state(crate::AppState::Editor)),
+            )
+            .add_systems(
+                PostUpdate,
+                (invalidate_feature_edges, draw_outlines)
+                    .chain()
+                    .after(bevy::transform::TransformSystems::Propagate)
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+#[derive(Resource, Clone, Copy, PartialEq, Debug)]
+pub struct OutlineColors {
+    pub selection: Color,
+    pub hover: Color,
+}
+
+impl Default for OutlineColors {
+    fn default() -> Self {
+        Self {
+            selection: Color::srgba(1.0, 0.6, 0.1, 1.0),
+            hover: Color::srgba(1.0, 1.0, 1.0, 0.45),
+        }
+    }
+}
+
+/// The selectable entity under the cursor, if any.
+#[derive(Resource, Default)]
+pub struct HoveredEntity(pub Option<Entity>);
+
+/// World-independent feature edges of each mesh, in mesh space.
+#[derive(Resource, Default)]
+struct FeatureEdgeCache(HashMap<AssetId<Mesh>, Vec<(Vec3, Vec3)>>);
+
+fn configure_outline_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
+    // Drawn over the geometry so the outline stays visible behind other objects
+    let (config, _) = config_store.config_mut::<SelectionOutlineGizmoGroup>();
+    config.depth_bias = -1.0;
+    config.line.width = 3.0;
+    let (config, _) = config_store.config_mut::<HoverOutlineGizmoGroup>();
+    config.depth_bias = -0.001;
+    config.line.width = 1.5;
+    let (config, _) = config_store.config_mut::<ClassnameOutlineGizmoGroup>();
+    config.depth_bias = -0.001;
+    config.line.width = 2.0;
+}
+
+fn update_hovered_entity(
+    mut hovered: ResMut<HoveredEntity>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
+    viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
+    scene_entities: Query<(Entity, &GlobalTransform), (Without<EditorEntity>, With<Transform>)>,
+    parents: Query<&ChildOf>,
+    (gizmo_drag, gizmo_hover, modal, viewport_drag): (
+        Res<GizmoDragState>,
+        Res<GizmoHoverState>,
+        Res<ModalTransformState>,
+        Res<ViewportDragState>,
+    ),
+    (edit_mode, entered_group): (
+        Res<crate::brush::EditMode>,
+        Res<crate::grouping::EnteredGroup>,
+    ),
+    mut ray_cast: MeshRayCast,
+    mut last_cursor: Local<Option<Vec2>>,
+) {
+    let busy = gizmo_drag.active
+        || gizmo_hover.hovered.is_some()
+        || modal.active.is_some()
+        || viewport_drag.active.is_some()
+        || *edit_mode != crate::brush::EditMode::Object;
+    let cursor = windows.single().ok().and_then(Window::cursor_position);
+    let (Some(cursor), Ok((camera, cam_tf)), false) = (cursor, camera_query.single(), busy) else {
+        *last_cursor = None;
+        hovered.0 = None;
+        return;
+    };
+    // The scene rarely changes under a still cursor
+    if *last_cursor == Some(cursor) {
+        return;
+    }
+    *last_cursor = Some(cursor);
+
+    let hit = window_to_viewport_cursor(cursor, camera, &viewport_query)
+        .and_then(|viewport_cursor| camera.viewport_to_world(cam_tf, viewport_cursor).ok())
+        .and_then(|ray| {
+            let settings = MeshRayCastSettings::default().with_visibility(RayCastVisibility::Any);
+            ray_cast
+                .cast_ray(ray, &settings)
+                .iter()
+                .find_map(|(hit, _)| {
+                    find_selectable_ancestor(*hit, &scene_entities, &parents, entered_group.0)
+                })
+        });
+    if hovered.0 != hit {
+        hovered.0 = hit;
+    }
+}
+
+fn invalidate_feature_edges(
+    mut events: MessageReader<AssetEvent<Mesh>>,
+    mut cache: ResMut<FeatureEdgeCache>,
+) {
+    for event in events.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            cache.0.remove(id);
+        }
+    }
+}
+
+fn draw_outlines(
+    mut selection_gizmos: Gizmos<SelectionOutlineGizmoGroup>,
+    mut hover_gizmos: Gizmos<HoverOutlineGizmoGroup>,
+    mut classname_gizmos: Gizmos<ClassnameOutlineGizmoGroup>,
+    colors: Res<OutlineColors>,
+    classname_colors: Res<ClassnameColors>,
+    hovered: Res<HoveredEntity>,
+    selected: Query<Entity, With<Selected>>,
+    classed: Query<(Entity, &CustomProperties, &GlobalTransform), Without<EditorEntity>>,
+    brushes: Query<(&BrushMeshCache, &GlobalTransform)>,
+    mesh_query: Query<(&Mesh3d, &GlobalTransform), Without<BrushFaceEntity>>,
+    children_query: Query<&Children>,
+    meshes: Res<Assets<Mesh>>,
+    mut cache: ResMut<FeatureEdgeCache>,
+) {
+    let mut outline = |entity: Entity, draw: &mut dyn FnMut(Vec3, Vec3)| {
+        for e in std::iter::once(entity).chain(children_query.iter_descendants(entity)) {
+            if let Ok((brush, global_tf)) = brushes.get(e) {
+                for polygon in &brush.face_polygons {
+                    for (i, &a) in polygon.iter().enumerate() {
+                        let b = polygon[(i + 1) % polygon.len()];
+                        // Each edge is shared by two faces; draw it once
+                        if a < b {
+                            draw(
+                                global_tf.transform_point(brush.vertices[a]),
+                                global_tf.transform_point(brush.vertices[b]),
+                            );
+                        }
+                    }
+                }
+            } else if let Ok((mesh3d, global_tf)) = mesh_query.get(e) {
+                let id = mesh3d.0.id();
+                if let Entry::Vacant(entry) = cache.0.entry(id) {
+                    let Some(mesh) = meshes.get(id) else {
+                        continue;
+                    };
+                    entry.insert(feature_edges(mesh));
+                }
+                for &(a, b) in &cache.0[&id] {
+                    draw(global_tf.transform_point(a), global_tf.transform_point(b));
+                }
+            }
+        }
+    };
+
+    for entity in &selected {
+        outline(entity, &mut |a, b| {
+            selection_gizmos.line(a, b, colors.selection);
+        });
+    }
+    if let Some(entity) = hovered.0.filter(|&e| !selected.contains(e)) {
+        outline(entity, &mut |a, b| hover_gizmos.line(a, b, colors.hover));
+    }
+    if classname_colors.colors.is_empty() {
+        return;
+    }
+    for (entity, properties, global_tf) in &classed {
+        if selected.contains(entity) || hovered.0 == Some(entity) {
+            continue;
+        }
+        let Some(color) = classname_colors.color_of(properties) else {
+            continue;
+        };
+        let mut drawn = false;
+        outline(entity, &mut |a, b| {
+            classname_gizmos.line(a, b, color);
+            drawn = true;
+        });
+        // Point entities have no geometry of their own
+        if !drawn {
+            classname_gizmos.sphere(
+                Isometry3d::from_translation(global_tf.translation()),
+                0.35,
+                color,
+            );
+        }
+    }
+}
+
+/// Open borders and creases sharper than [`FEATURE_EDGE_ANGLE`]; smooth interior edges
+/// are left out so curved meshes outline by their shape rather than their triangles.
+fn feature_edges(mesh: &Mesh) -> Vec<(Vec3, Vec3)> {
+    let Some(positions) = mesh
+        .attribute(Mesh::ATTRIBUTE_POSITION)
+        .and_then(|attr| attr.as_float3())
+    else {
+        return Vec::new();
+    };
+    let indices: Vec<usize> = match mesh.indices() {
+        Some(indices) => indices.iter().collect(),
+        None => (0..positions.len()).collect(),
+    };
+    // Weld split vertices (UV seams, hard normals) so their edges pair up
+    let key = |i: usize| (Vec3::from(positions[i]) * 1e4).round().as_ivec3();
+    let mut edges: HashMap<(IVec3, IVec3), (Vec3, Vec3, Vec<Vec3>)> = HashMap::new();
+    for triangle in indices.chunks_exact(3) {
+        let points = [triangle[0], triangle[1], triangle[2]].map(|i| Vec3::from(positions[i]));
+        let normal = (points[1] - points[0])
+            .cross(points[2] - points[0])
+            .normalize_or_zero();
+        if normal == Vec3::ZERO {
+            continue;
+        }
+        for k in 0..3 {
+            let (i, j) = (triangle[k], triangle[(k + 1) % 3]);
+            let (ka, kb) = (key(i), key(j));
+            let edge_key = if ka.to_array() < kb.to_array() {
+                (ka, kb)
+            } else {
+                (kb, ka)
+            };
+            edges
+                .entry(edge_key)
+                .or_insert_with(|| (points[k], points[(k + 1) % 3], Vec::new()))
+                .2
+                .push(normal);
+        }
+    }
+    let min_cos = FEATURE_EDGE_ANGLE.to_radians().cos();
+    edges
+        .into_values()
+        .filter(|(_, _, normals)| match normals.as_slice() {
+            [a, b] => a.dot(*b) < min_cos,
+            _ => true,
+        })
+        .map(|(a, b, _)| (a, b))
+        .collect()
+}
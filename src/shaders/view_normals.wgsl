@@ -0,0 +1,8 @@
+#import bevy_pbr::forward_io::VertexOutput
+
+// World-space normal mapped from [-1, 1] to [0, 1].
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let n = normalize(in.world_normal);
+    return vec4<f32>(n * 0.5 + 0.5, 1.0);
+}
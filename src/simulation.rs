@@ -0,0 +1,311 @@
+//! Physics simulation for placement. Simulate drops every [`DynamicBody`] onto the rest of
+//! the scene with avian3d, e.g. to scatter crates realistically. The bodies run on proxy
+//! entities so the scene itself never gets physics components; their poses are copied back
+//! each frame. Enter keeps the settled transforms as one undo step, Esc reverts them.
+
+use avian3d::prelude::*;
+use bevy::{
+    input_focus::InputFocus,
+    mesh::{Indices, PrimitiveTopology},
+    prelude::*,
+};
+use jackdaw_jsn::{DynamicBody, TriggerVolume, VisibilityVolume};
+
+use crate::{
+    EditorEntity,
+    brush::{BrushFaceEntity, BrushMeshCache},
+    commands::{CommandGroup, CommandHistory, EditorCommand, SetTransform},
+    status_bar::StatusHints,
+};
+
+const SIMULATION_HINTS: &str = "simulation";
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PhysicsPlugins::default())
+            .init_resource::<Simulation>()
+            .add_systems(
+                Update,
+                (handle_simulation_keys, sync_simulated_bodies)
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+struct SimulatedBody {
+    entity: Entity,
+    proxy: Entity,
+    start: Transform,
+}
+
+/// The running simulation, if any.
+#[derive(Resource, Default)]
+pub struct Simulation {
+    bodies: Vec<SimulatedBody>,
+    /// Fixed colliders standing in for the rest of the scene.
+    statics: Vec<Entity>,
+}
+
+impl Simulation {
+    pub fn is_running(&self) -> bool {
+        !self.bodies.is_empty()
+    }
+}
+
+/// Start the simulation, or keep its result when it is already running.
+pub fn toggle_simulation(world: &mut World) {
+    if world.resource::<Simulation>().is_running() {
+        finish_simulation(world, true);
+    } else {
+        start_simulation(world);
+    }
+}
+
+pub fn start_simulation(world: &mut World) {
+    if world.resource::<Simulation>().is_running() {
+        return;
+    }
+    let dynamic: Vec<Entity> = world
+        .query_filtered::<Entity, With<DynamicBody>>()
+        .iter(world)
+        .filter(|&e| !has_ancestor(world, e, |w, a| w.get::<EditorEntity>(a).is_some()))
+        .collect();
+
+    let mut bodies = Vec::new();
+    for entity in dynamic.iter().copied() {
+        let (Some(&start), Some(&global)) = (
+            world.get::<Transform>(entity),
+            world.get::<GlobalTransform>(entity),
+        ) else {
+            continue;
+        };
+        // Hull of everything the entity draws, in its own space; avian applies the scale
+        let to_local = global.affine().inverse();
+        let mut points: Vec<Vec3> = Vec::new();
+        collect_world_points(world, entity, &mut points);
+        let points: Vec<Vec3> = points
+            .into_iter()
+            .map(|p| to_local.transform_point3(p))
+            .collect();
+        let Some(collider) = Collider::convex_hull(points) else {
+            continue;
+        };
+        let proxy = world
+            .spawn((
+                RigidBody::Dynamic,
+                collider,
+                global.compute_transform(),
+                EditorEntity,
+            ))
+            .id();
+        bodies.push(SimulatedBody {
+            entity,
+            proxy,
+            start,
+        });
+    }
+    if bodies.is_empty() {
+        world
+            .resource_mut::<StatusHints>()
+            .prompt("Nothing to simulate: add a DynamicBody component to the props to drop");
+        return;
+    }
+
+    let statics = spawn_static_colliders(world, &dynamic);
+    world.resource_mut::<StatusHints>().set(
+        SIMULATION_HINTS,
+        "Simulating | Enter: keep result | Esc: revert",
+    );
+    *world.resource_mut::<Simulation>() = Simulation { bodies, statics };
+}
+
+/// Stop the simulation, keeping the settled transforms as one undo step or putting the
+/// bodies back where they started.
+pub fn finish_simulation(world: &mut World, keep: bool) {
+    let Simulation { bodies, statics } = std::mem::take(&mut *world.resource_mut::<Simulation>());
+    world.resource_mut::<StatusHints>().clear(SIMULATION_HINTS);
+    for proxy in bodies.iter().map(|b| b.proxy).chain(statics) {
+        if let Ok(proxy) = world.get_entity_mut(proxy) {
+            proxy.despawn();
+        }
+    }
+
+    let mut cmds: Vec<Box<dyn EditorCommand>> = Vec::new();
+    for body in bodies {
+        let Some(mut transform) = world.get_mut::<Transform>(body.entity) else {
+            continue;
+        };
+        let settled = *transform;
+        if !keep {
+            *transform = body.start;
+        } else if settled != body.start {
+            cmds.push(Box::new(SetTransform {
+                entity: body.entity,
+                old_transform: body.start,
+                new_transform: settled,
+            }));
+        }
+    }
+    if cmds.is_empty() {
+        return;
+    }
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(CommandGroup {
+        commands: cmds,
+        label: "Simulate physics".to_string(),
+    }));
+}
+
+/// Fixed colliders for every visible solid brush and mesh outside the dynamic bodies.
+pub(crate) fn spawn_static_colliders(world: &mut World, dynamic: &[Entity]) -> Vec<Entity> {
+    let skip = |world: &World, entity: Entity| {
+        has_ancestor(world, entity, |w, a| {
+            dynamic.contains(&a) || w.get::<EditorEntity>(a).is_some()
+        }) || world
+            .get::<InheritedVisibility>(entity)
+            .is_some_and(|v| !v.get())
+    };
+
+    let mut colliders: Vec<(Collider, Transform)> = Vec::new();
+    // Triggers and visibility volumes aren't solid
+    let brushes: Vec<(Entity, GlobalTransform, Vec<Vec3>)> = world
+        .query_filtered::<(Entity, &GlobalTransform, &BrushMeshCache), (
+            Without<TriggerVolume>,
+            Without<VisibilityVolume>,
+        )>()
+        .iter(world)
+        .map(|(e, global, cache)| (e, *global, cache.vertices.clone()))
+        .collect();
+    for (entity, global, vertices) in brushes {
+        if skip(world, entity) {
+            continue;
+        }
+        if let Some(collider) = Collider::convex_hull(vertices) {
+            colliders.push((collider, global.compute_transform()));
+        }
+    }
+
+    let meshes: Vec<(Entity, GlobalTransform, Handle<Mesh>)> = world
+        .query_filtered::<(Entity, &GlobalTransform, &Mesh3d), Without<BrushFaceEntity>>()
+        .iter(world)
+        .map(|(e, global, mesh)| (e, *global, mesh.0.clone()))
+        .collect();
+    for (entity, global, handle) in meshes {
+        if skip(world, entity) {
+            continue;
+        }
+        let Some(mesh) = world.resource::<Assets<Mesh>>().get(&handle) else {
+            continue;
+        };
+        if let Some(collider) = trimesh_collider(mesh) {
+            colliders.push((collider, global.compute_transform()));
+        }
+    }
+
+    colliders
+        .into_iter()
+        .map(|(collider, transform)| {
+            world
+                .spawn((RigidBody::Static, collider, transform, EditorEntity))
+                .id()
+        })
+        .collect()
+}
+
+fn trimesh_collider(mesh: &Mesh) -> Option<Collider> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return None;
+    }
+    let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?.as_float3()?;
+    let vertices: Vec<Vec3> = positions.iter().map(|p| Vec3::from(*p)).collect();
+    let indices: Vec<u32> = match mesh.indices() {
+        Some(Indices::U16(indices)) => indices.iter().map(|&i| i as u32).collect(),
+        Some(Indices::U32(indices)) => indices.clone(),
+        None => (0..vertices.len() as u32).collect(),
+    };
+    let triangles: Vec<[u32; 3]> = indices
+        .chunks_exact(3)
+        .map(|t| [t[0], t[1], t[2]])
+        .collect();
+    (!triangles.is_empty()).then(|| Collider::trimesh(vertices, triangles))
+}
+
+/// World-space points of the brush and meshes drawn by `entity` and its descendants.
+fn collect_world_points(world: &World, entity: Entity, out: &mut Vec<Vec3>) {
+    let global = world.get::<GlobalTransform>(entity);
+    if let (Some(cache), Some(global)) = (world.get::<BrushMeshCache>(entity), global) {
+        out.extend(cache.vertices.iter().map(|v| global.transform_point(*v)));
+    } else if let (Some(mesh3d), Some(global)) = (world.get::<Mesh3d>(entity), global)
+        && world.get::<BrushFaceEntity>(entity).is_none()
+        && let Some(positions) = world
+            .resource::<Assets<Mesh>>()
+            .get(&mesh3d.0)
+            .and_then(|mesh| mesh.attribute(Mesh::ATTRIBUTE_POSITION))
+            .and_then(|attr| attr.as_float3())
+    {
+        out.extend(
+            positions
+                .iter()
+                .map(|p| global.transform_point(Vec3::from(*p))),
+        );
+    }
+    if let Some(children) = world.get::<Children>(entity) {
+        for child in children.iter() {
+            collect_world_points(world, child, out);
+        }
+    }
+}
+
+/// Whether `entity` or one of its ancestors matches `test`.
+fn has_ancestor(world: &World, entity: Entity, test: impl Fn(&World, Entity) -> bool) -> bool {
+    let mut current = Some(entity);
+    while let Some(e) = current {
+        if test(world, e) {
+            return true;
+        }
+        current = world.get::<ChildOf>(e).map(ChildOf::parent);
+    }
+    false
+}
+
+fn handle_simulation_keys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input_focus: Res<InputFocus>,
+    simulation: Res<Simulation>,
+    mut commands: Commands,
+) {
+    if !simulation.is_running() || input_focus.0.is_some() {
+        return;
+    }
+    if keyboard.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) {
+        commands.queue(|world: &mut World| finish_simulation(world, true));
+    } else if keyboard.just_pressed(KeyCode::Escape) {
+        commands.queue(|world: &mut World| finish_simulation(world, false));
+    }
+}
+
+/// Copy the proxies' poses onto the simulated entities, keeping their own scale.
+fn sync_simulated_bodies(
+    simulation: Res<Simulation>,
+    proxies: Query<&Transform, Without<DynamicBody>>,
+    mut bodies: Query<(&mut Transform, Option<&ChildOf>), With<DynamicBody>>,
+    globals: Query<&GlobalTransform>,
+) {
+    for body in &simulation.bodies {
+        let Ok(&pose) = proxies.get(body.proxy) else {
+            continue;
+        };
+        let Ok((mut transform, child_of)) = bodies.get_mut(body.entity) else {
+            continue;
+        };
+        let local = match child_of.and_then(|c| globals.get(c.parent()).ok()) {
+            Some(parent) => GlobalTransform::from(pose).reparented_to(parent),
+            None => pose,
+        };
+        transform.translation = local.translation;
+        transform.rotation = local.rotation;
+    }
+}
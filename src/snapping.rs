@@ -4,6 +4,7 @@ use bevy::{
     prelude::*,
 };
 use bevy_infinite_grid::{InfiniteGrid, InfiniteGridSettings};
+use jackdaw_feathers::status_bar::StatusBarGrid;
 
 pub struct SnappingPlugin;
 
@@ -11,9 +12,14 @@ impl Plugin for SnappingPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SnapSettings>()
             .init_resource::<GridSettings>()
+            .add_observer(on_grid_size_widget_click)
             .add_systems(
                 Update,
-                (handle_grid_size_keys, sync_grid_settings)
+                (
+                    handle_grid_size_keys,
+                    update_grid_size_widget,
+                    sync_grid_settings,
+                )
                     .chain()
                     .run_if(in_state(crate::AppState::Editor)),
             );
@@ -67,8 +73,9 @@ fn sync_grid_settings(
     }
 }
 
+/// Grid size presets run from 2^-3 = 0.125 to 2^4 = 16.
 pub const GRID_POWER_MIN: i32 = -3;
-pub const GRID_POWER_MAX: i32 = 8;
+pub const GRID_POWER_MAX: i32 = 4;
 
 #[derive(Resource)]
 pub struct SnapSettings {
@@ -103,6 +110,17 @@ impl SnapSettings {
         2.0_f32.powi(self.grid_power)
     }
 
+    /// Switch to the `power` preset; translation snapping follows the grid.
+    pub fn set_grid_power(&mut self, power: i32) {
+        self.grid_power = power.clamp(GRID_POWER_MIN, GRID_POWER_MAX);
+        self.translate_increment = self.grid_size();
+    }
+
+    /// Step to the next finer (`-1`) or coarser (`+1`) preset.
+    pub fn step_grid(&mut self, steps: i32) {
+        self.set_grid_power(self.grid_power + steps);
+    }
+
     /// Snap a translation value to the nearest increment.
     pub fn snap_translate(&self, value: f32) -> f32 {
         if self.translate_snap && self.translate_increment > 0.0 {
@@ -223,7 +241,7 @@ fn handle_grid_size_keys(
             crate::terrain::TerrainEditMode::Sculpt(_)
         );
 
-    let mut changed = false;
+    let mut steps = 0;
 
     // Ctrl+Alt+Scroll or Shift+Scroll (non-sculpt): change grid size
     if (ctrl && alt) || shift_grid {
@@ -233,25 +251,51 @@ fn handle_grid_size_keys(
                 MouseScrollUnit::Pixel => event.y * 0.01,
             };
             if delta > 0.0 {
-                snap.grid_power = (snap.grid_power + 1).min(GRID_POWER_MAX);
-                changed = true;
+                steps += 1;
             } else if delta < 0.0 {
-                snap.grid_power = (snap.grid_power - 1).max(GRID_POWER_MIN);
-                changed = true;
+                steps -= 1;
             }
         }
     }
 
-    // Bracket keys: alternative grid size control
+    // Bracket keys: one preset finer or coarser
     if keyboard.just_pressed(KeyCode::BracketLeft) {
-        snap.grid_power = (snap.grid_power - 1).max(GRID_POWER_MIN);
-        changed = true;
+        steps -= 1;
     }
     if keyboard.just_pressed(KeyCode::BracketRight) {
-        snap.grid_power = (snap.grid_power + 1).min(GRID_POWER_MAX);
-        changed = true;
+        steps += 1;
+    }
+    if steps != 0 {
+        snap.step_grid(steps);
+    }
+}
+
+/// Show the grid size in the status bar.
+fn update_grid_size_widget(
+    snap: Res<SnapSettings>,
+    mut text: Query<&mut Text, With<StatusBarGrid>>,
+    added: Query<(), Added<StatusBarGrid>>,
+) {
+    if !snap.is_changed() && added.is_empty() {
+        return;
+    }
+    for mut text in &mut text {
+        text.0 = format!("Grid: {}", snap.grid_size());
+    }
+}
+
+/// Clicking the status bar grid size steps it coarser; right-click steps it finer.
+fn on_grid_size_widget_click(
+    click: On<Pointer<Click>>,
+    widgets: Query<(), With<StatusBarGrid>>,
+    mut snap: ResMut<SnapSettings>,
+) {
+    if !widgets.contains(click.event_target()) {
+        return;
     }
-    if changed {
-        snap.translate_increment = snap.grid_size();
+    match click.button {
+        PointerButton::Primary => snap.step_grid(1),
+        PointerButton::Secondary => snap.step_grid(-1),
+        PointerButton::Middle => {}
     }
 }
@@ -0,0 +1,547 @@
+//! Spline editing: draws every [`Spline`] in the viewport and, in spline edit mode, lets
+//! the control points and their tangent handles be dragged, added and removed.
+//! Extrusions along splines can be baked to glTF meshes for export.
+
+use std::{any::TypeId, path::Path};
+
+use bevy::{
+    ecs::entity::EntityHashMap, input_focus::InputFocus, mesh::VertexAttributeValues, prelude::*,
+    ui::UiGlobalTransform, window::PrimaryWindow,
+};
+use jackdaw_jsn::{GltfSource, Spline, SplineExtrusion, SplinePoint, spline_mesh::extrude_spline};
+
+use crate::{
+    brush_bake::{BAKE_DIR, BakePrimitive, build_gltf},
+    commands::{
+        CommandGroup, CommandHistory, EditorCommand, RemoveComponent, SpawnSnapshot, remap_entity,
+    },
+    project::ProjectRoot,
+    selection::{Selection, select_entities},
+    status_bar::StatusHints,
+    viewport::{MainViewportCamera, SceneViewport},
+    viewport_util::window_to_viewport_cursor,
+};
+
+/// Samples per segment when drawing.
+const DRAW_SUBDIVISIONS: usize = 16;
+/// How close, in viewport pixels, the cursor must be to grab a handle.
+const HANDLE_PICK_RADIUS: f32 = 10.0;
+/// Handle size as a fraction of the distance to the camera.
+const HANDLE_SCALE: f32 = 0.012;
+const SPLINE_COLOR: Color = Color::srgb(0.95, 0.65, 0.2);
+const HANDLE_COLOR: Color = Color::srgb(0.6, 0.8, 1.0);
+const SELECTED_POINT_COLOR: Color = Color::WHITE;
+const SPLINE_HINTS: &str = "spline";
+
+pub struct SplinePlugin;
+
+impl Plugin for SplinePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SplineEditState>().add_systems(
+            Update,
+            (
+                exit_spline_edit_on_deselect,
+                handle_spline_keys,
+                handle_spline_mouse,
+                update_spline_hints,
+                draw_splines,
+            )
+                .chain()
+                .run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SplineEditState {
+    /// The spline whose points are being edited; viewport selection and dragging are
+    /// suspended meanwhile.
+    pub active: Option<Entity>,
+    /// Point last clicked, removed with Delete.
+    selected_point: Option<usize>,
+    drag: Option<SplineDrag>,
+}
+
+struct SplineDrag {
+    handle: SplineHandle,
+    /// The spline before the drag, for undo.
+    start: Spline,
+    /// World position of the handle when grabbed; it moves in the camera-facing plane
+    /// through this point.
+    origin: Vec3,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SplineHandle {
+    Point(usize),
+    In(usize),
+    Out(usize),
+}
+
+impl SplineHandle {
+    fn point(self) -> usize {
+        match self {
+            Self::Point(i) | Self::In(i) | Self::Out(i) => i,
+        }
+    }
+
+    /// Local position of the handle on `spline`.
+    fn position(self, spline: &Spline) -> Vec3 {
+        let point = &spline.points[self.point()];
+        match self {
+            Self::Point(_) => point.position,
+            Self::In(_) => point.position + point.in_tangent,
+            Self::Out(_) => point.position + point.out_tangent,
+        }
+    }
+
+    /// Move the handle to local position `to`. Tangent handles stay mirrored so the curve
+    /// passes smoothly through the point.
+    fn set_position(self, spline: &mut Spline, to: Vec3) {
+        let point = &mut spline.points[self.point()];
+        let mirror = |moved: Vec3, other: Vec3| {
+            let length = other.length();
+            -moved.normalize_or_zero() * if length > 0.0 { length } else { moved.length() }
+        };
+        match self {
+            Self::Point(_) => point.position = to,
+            Self::In(_) => {
+                point.in_tangent = to - point.position;
+                point.out_tangent = mirror(point.in_tangent, point.out_tangent);
+            }
+            Self::Out(_) => {
+                point.out_tangent = to - point.position;
+                point.in_tangent = mirror(point.out_tangent, point.in_tangent);
+            }
+        }
+    }
+}
+
+/// Replace an entity's spline.
+pub struct SetSpline {
+    pub entity: Entity,
+    pub old: Spline,
+    pub new: Spline,
+}
+
+impl EditorCommand for SetSpline {
+    fn execute(&self, world: &mut World) {
+        if let Some(mut spline) = world.get_mut::<Spline>(self.entity) {
+            *spline = self.new.clone();
+        }
+    }
+
+    fn undo(&self, world: &mut World) {
+        if let Some(mut spline) = world.get_mut::<Spline>(self.entity) {
+            *spline = self.old.clone();
+        }
+    }
+
+    fn description(&self) -> &str {
+        "Edit spline"
+    }
+
+    fn memory_size(&self) -> usize {
+        let points = self.old.points.capacity() + self.new.points.capacity();
+        std::mem::size_of::<Self>() + points * std::mem::size_of::<SplinePoint>()
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+    }
+}
+
+/// Spawns a two-point spline.
+pub fn spawn_spline_entity(commands: &mut Commands) -> Entity {
+    let mut spline = Spline::default();
+    spline.push_point(Vec3::ZERO);
+    spline.push_point(Vec3::new(4.0, 0.0, 0.0));
+    commands
+        .spawn((
+            Name::new("Spline"),
+            Transform::default(),
+            Visibility::default(),
+            spline,
+        ))
+        .id()
+}
+
+/// Spawns a spline with a road extruded along it.
+pub fn spawn_road_entity(commands: &mut Commands) -> Entity {
+    let entity = spawn_spline_entity(commands);
+    commands
+        .entity(entity)
+        .insert((Name::new("Road"), SplineExtrusion::default()));
+    entity
+}
+
+/// Bake the extrusions of the selected splines into glTF meshes. Each extrusion is
+/// replaced by an entity referencing its mesh; the splines stay for further editing.
+pub fn bake_selected_extrusions(world: &mut World) {
+    let Some(assets_dir) = world
+        .get_resource::<ProjectRoot>()
+        .map(|project| project.assets_dir())
+    else {
+        warn!("Bake spline meshes: no project open");
+        return;
+    };
+    let selected: Vec<Entity> = world.resource::<Selection>().entities.clone();
+    let Some(component_id) = world.components().component_id::<SplineExtrusion>() else {
+        return;
+    };
+
+    let mut undo_commands: Vec<Box<dyn EditorCommand>> = Vec::new();
+    let mut baked = Vec::new();
+    let mut written = Vec::new();
+    for entity in selected {
+        let (Some(spline), Some(extrusion), Some(global)) = (
+            world.get::<Spline>(entity),
+            world.get::<SplineExtrusion>(entity),
+            world.get::<GlobalTransform>(entity),
+        ) else {
+            continue;
+        };
+        let Some(primitive) = extrude_spline(spline, extrusion).and_then(|m| bake_primitive(&m))
+        else {
+            continue;
+        };
+        let extrusion = extrusion.clone();
+        let transform = global.compute_transform();
+        let name = world
+            .get::<Name>(entity)
+            .map_or_else(|| "Spline".to_string(), |n| n.to_string());
+
+        let file_name = (0..)
+            .map(|n| format!("spline_{n}.gltf"))
+            .find(|name| !assets_dir.join(BAKE_DIR).join(name).exists())
+            .unwrap_or_default();
+        let asset_path = format!("{BAKE_DIR}/{file_name}");
+        let gltf = build_gltf(
+            world,
+            &name,
+            std::slice::from_ref(&extrusion.material),
+            &[primitive],
+            Path::new(".."),
+        );
+        let result = std::fs::create_dir_all(assets_dir.join(BAKE_DIR)).and_then(|()| {
+            std::fs::write(
+                assets_dir.join(&asset_path),
+                serde_json::to_string_pretty(&gltf).unwrap_or_default(),
+            )
+        });
+        if let Err(err) = result {
+            warn!("Bake spline meshes: failed to write '{asset_path}': {err}");
+            continue;
+        }
+
+        world.entity_mut(entity).remove::<SplineExtrusion>();
+        undo_commands.push(Box::new(RemoveComponent {
+            entity,
+            type_id: TypeId::of::<SplineExtrusion>(),
+            component_id,
+            snapshot: Box::new(extrusion),
+        }));
+
+        let scene = world
+            .resource::<AssetServer>()
+            .load(GltfAssetLabel::Scene(0).from_asset(asset_path.clone()));
+        baked.push(
+            world
+                .spawn((
+                    Name::new(format!("{name} Mesh")),
+                    GltfSource {
+                        path: asset_path.clone(),
+                        scene_index: 0,
+                    },
+                    SceneRoot(scene),
+                    transform,
+                    Visibility::default(),
+                ))
+                .id(),
+        );
+        written.push(asset_path);
+    }
+    if baked.is_empty() {
+        world
+            .resource_mut::<StatusHints>()
+            .prompt("Select a spline with an extrusion to bake");
+        return;
+    }
+
+    undo_commands.push(Box::new(SpawnSnapshot::from_world(
+        world,
+        &baked,
+        "Bake spline meshes",
+    )));
+    world
+        .resource_mut::<CommandHistory>()
+        .push_executed(Box::new(CommandGroup {
+            commands: undo_commands,
+            label: "Bake spline meshes".to_string(),
+        }));
+
+    select_entities(world, &baked);
+    info!(
+        "Baked {} spline meshes: {}",
+        baked.len(),
+        written.join(", ")
+    );
+    world.resource_mut::<StatusHints>().prompt(format!(
+        "Baked {} spline meshes into '{BAKE_DIR}/'",
+        baked.len()
+    ));
+}
+
+/// Vertex buffers of an extruded mesh. The extrusion's UVs double as lightmap UVs.
+fn bake_primitive(mesh: &Mesh) -> Option<BakePrimitive> {
+    let positions = mesh
+        .attribute(Mesh::ATTRIBUTE_POSITION)?
+        .as_float3()?
+        .to_vec();
+    let normals = mesh
+        .attribute(Mesh::ATTRIBUTE_NORMAL)?
+        .as_float3()?
+        .to_vec();
+    let VertexAttributeValues::Float32x2(uvs) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)? else {
+        return None;
+    };
+    let indices = mesh.indices()?.iter().map(|i| i as u32).collect();
+    Some(BakePrimitive {
+        positions,
+        normals,
+        uv0: uvs.clone(),
+        uv1: uvs.clone(),
+        indices,
+        ..Default::default()
+    })
+}
+
+/// Enter spline edit mode for the primary selection, or leave it.
+pub fn toggle_spline_edit(world: &mut World) {
+    if world.resource::<SplineEditState>().active.is_some() {
+        *world.resource_mut::<SplineEditState>() = SplineEditState::default();
+        return;
+    }
+    let primary = world.resource::<Selection>().primary();
+    let Some(entity) = primary.filter(|&e| world.get::<Spline>(e).is_some()) else {
+        world
+            .resource_mut::<StatusHints>()
+            .prompt("Select a spline to edit its points");
+        return;
+    };
+    world.resource_mut::<SplineEditState>().active = Some(entity);
+}
+
+fn exit_spline_edit_on_deselect(selection: Res<Selection>, mut state: ResMut<SplineEditState>) {
+    if state.active.is_some() && state.active != selection.primary() {
+        *state = SplineEditState::default();
+    }
+}
+
+fn handle_spline_keys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input_focus: Res<InputFocus>,
+    mut state: ResMut<SplineEditState>,
+    mut splines: Query<&mut Spline>,
+    mut history: ResMut<CommandHistory>,
+) {
+    let Some(entity) = state.active else {
+        return;
+    };
+    if input_focus.0.is_some() || state.drag.is_some() {
+        return;
+    }
+    if keyboard.any_just_pressed([KeyCode::Escape, KeyCode::Enter]) {
+        *state = SplineEditState::default();
+        return;
+    }
+    if keyboard.any_just_pressed([KeyCode::Delete, KeyCode::Backspace])
+        && let Some(index) = state.selected_point.take()
+        && let Ok(mut spline) = splines.get_mut(entity)
+        // A spline keeps at least two points
+        && spline.points.len() > 2
+        && index < spline.points.len()
+    {
+        let old = spline.clone();
+        spline.points.remove(index);
+        history.push_executed(Box::new(SetSpline {
+            entity,
+            old,
+            new: spline.clone(),
+        }));
+    }
+}
+
+fn handle_spline_mouse(
+    mouse: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
+    viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
+    mut state: ResMut<SplineEditState>,
+    mut splines: Query<(&GlobalTransform, &mut Spline)>,
+    mut history: ResMut<CommandHistory>,
+) {
+    let Some(entity) = state.active else {
+        return;
+    };
+    let Ok((global, mut spline)) = splines.get_mut(entity) else {
+        *state = SplineEditState::default();
+        return;
+    };
+
+    if mouse.just_released(MouseButton::Left)
+        && let Some(drag) = state.drag.take()
+    {
+        if *spline != drag.start {
+            history.push_executed(Box::new(SetSpline {
+                entity,
+                old: drag.start,
+                new: spline.clone(),
+            }));
+        }
+        return;
+    }
+
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let Ok((camera, cam_tf)) = camera_query.single() else {
+        return;
+    };
+    let Some(cursor) = window
+        .cursor_position()
+        .and_then(|pos| window_to_viewport_cursor(pos, camera, &viewport_query))
+    else {
+        return;
+    };
+    let Ok(ray) = camera.viewport_to_world(cam_tf, cursor) else {
+        return;
+    };
+    let to_local = global.affine().inverse();
+
+    if let Some(drag) = &state.drag {
+        if mouse.pressed(MouseButton::Left)
+            && let Some(distance) =
+                ray.intersect_plane(drag.origin, InfinitePlane3d::new(cam_tf.forward()))
+        {
+            let local = to_local.transform_point3(ray.get_point(distance));
+            let handle = drag.handle;
+            if handle.position(&spline) != local {
+                handle.set_position(&mut spline, local);
+            }
+        }
+        return;
+    }
+
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let grabbed = spline_handles(&spline)
+        .filter_map(|handle| {
+            let world = global.transform_point(handle.position(&spline));
+            let screen = camera.world_to_viewport(cam_tf, world).ok()?;
+            let distance = screen.distance(cursor);
+            (distance <= HANDLE_PICK_RADIUS).then_some((handle, world, distance))
+        })
+        .min_by(|a, b| a.2.total_cmp(&b.2));
+    if let Some((handle, origin, _)) = grabbed {
+        state.selected_point = Some(handle.point());
+        state.drag = Some(SplineDrag {
+            handle,
+            start: spline.clone(),
+            origin,
+        });
+        return;
+    }
+
+    // Ctrl+click appends a point at the height of the last one
+    if keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        let height = spline
+            .points
+            .last()
+            .map_or(global.translation(), |p| global.transform_point(p.position));
+        let Some(distance) = ray.intersect_plane(height, InfinitePlane3d::new(Vec3::Y)) else {
+            return;
+        };
+        let old = spline.clone();
+        spline.push_point(to_local.transform_point3(ray.get_point(distance)));
+        state.selected_point = Some(spline.points.len() - 1);
+        history.push_executed(Box::new(SetSpline {
+            entity,
+            old,
+            new: spline.clone(),
+        }));
+    }
+}
+
+/// Every handle of `spline`, tangents first so they win over a point they overlap.
+fn spline_handles(spline: &Spline) -> impl Iterator<Item = SplineHandle> {
+    let count = spline.points.len();
+    (0..count)
+        .flat_map(|i| [SplineHandle::In(i), SplineHandle::Out(i)])
+        .chain((0..count).map(SplineHandle::Point))
+}
+
+fn update_spline_hints(state: Res<SplineEditState>, mut hints: ResMut<StatusHints>) {
+    if !state.is_changed() {
+        return;
+    }
+    if state.active.is_some() {
+        if hints.get(SPLINE_HINTS).is_none() {
+            hints.set(
+                SPLINE_HINTS,
+                "Drag: move point or handle | Ctrl+Click: add point | Del: remove point | Esc: done",
+            );
+        }
+    } else if hints.get(SPLINE_HINTS).is_some() {
+        hints.clear(SPLINE_HINTS);
+    }
+}
+
+fn draw_splines(
+    mut gizmos: Gizmos,
+    state: Res<SplineEditState>,
+    splines: Query<(Entity, &GlobalTransform, &Spline)>,
+    camera_query: Query<&GlobalTransform, With<MainViewportCamera>>,
+) {
+    for (entity, global, spline) in &splines {
+        gizmos.linestrip(
+            spline
+                .tessellate(DRAW_SUBDIVISIONS)
+                .into_iter()
+                .map(|(position, _)| global.transform_point(position)),
+            SPLINE_COLOR,
+        );
+
+        if state.active != Some(entity) {
+            continue;
+        }
+        let camera = camera_query
+            .single()
+            .map_or(Vec3::ZERO, |tf| tf.translation());
+        let size = |at: Vec3| at.distance(camera) * HANDLE_SCALE;
+        for (index, point) in spline.points.iter().enumerate() {
+            let position = global.transform_point(point.position);
+            for tangent in [point.in_tangent, point.out_tangent] {
+                let handle = global.transform_point(point.position + tangent);
+                gizmos.line(position, handle, HANDLE_COLOR);
+                gizmos.sphere(
+                    Isometry3d::from_translation(handle),
+                    size(handle),
+                    HANDLE_COLOR,
+                );
+            }
+            let color = if state.selected_point == Some(index) {
+                SELECTED_POINT_COLOR
+            } else {
+                SPLINE_COLOR
+            };
+            gizmos.cube(
+                Transform::from_translation(position).with_scale(Vec3::splat(size(position) * 2.0)),
+                color,
+            );
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Gives every scene entity a [`StableId`] and keeps an index from id to live entity,
+//! so references stored by id resolve again after save/load, undo, and redo.
+
+use std::collections::HashMap;
+
+use bevy::{asset::uuid::Uuid, prelude::*};
+use jackdaw_jsn::StableId;
+
+use crate::{EditorEntity, NonSerializable};
+
+pub struct StableIdPlugin;
+
+impl Plugin for StableIdPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StableIds>()
+            .add_observer(assign_stable_id)
+            .add_observer(index_stable_id)
+            .add_observer(unindex_stable_id);
+    }
+}
+
+/// Maps stable ids to the entities currently carrying them.
+#[derive(Resource, Default)]
+pub struct StableIds {
+    entities: HashMap<Uuid, Entity>,
+}
+
+impl StableIds {
+    /// The live entity with the given id, if any.
+    pub fn entity(&self, id: Uuid) -> Option<Entity> {
+        self.entities.get(&id).copied()
+    }
+}
+
+/// The stable id of `entity`, if it has one.
+pub fn stable_id_of(world: &World, entity: Entity) -> Option<Uuid> {
+    world.get::<StableId>(entity).map(|id| id.0)
+}
+
+/// Resolve a stable id to the live entity carrying it.
+pub fn resolve_stable_id(world: &World, id: Uuid) -> Option<Entity> {
+    world.resource::<StableIds>().entity(id)
+}
+
+/// New scene entities, and scenes saved before ids existed, get a fresh id as they
+/// spawn, so snapshots taken right after already carry it. An id written afterwards,
+/// as when a scene or snapshot is loaded, is kept.
+fn assign_stable_id(
+    event: On<Add, Transform>,
+    missing: Query<
+        (),
+        (
+            Without<StableId>,
+            Without<EditorEntity>,
+            Without<NonSerializable>,
+        ),
+    >,
+    mut commands: Commands,
+) {
+    if missing.contains(event.entity) {
+        commands
+            .entity(event.entity)
+            .try_insert_if_new(StableId::new());
+    }
+}
+
+/// Index an inserted id. A copy of a live entity's id (duplicate, paste, importing
+/// a scene twice) gets a fresh one so ids stay unique.
+fn index_stable_id(
+    event: On<Insert, StableId>,
+    mut index: ResMut<StableIds>,
+    ids: Query<&StableId>,
+    mut commands: Commands,
+) {
+    let entity = event.entity;
+    let Ok(&StableId(id)) = ids.get(entity) else {
+        return;
+    };
+    if let Some(owner) = index.entity(id)
+        && owner != entity
+        && ids.get(owner).is_ok_and(|other| other.0 == id)
+    {
+        commands.entity(entity).insert(StableId::new());
+        return;
+    }
+    index.entities.insert(id, entity);
+}
+
+fn unindex_stable_id(
+    event: On<Replace, StableId>,
+    mut index: ResMut<StableIds>,
+    ids: Query<&StableId>,
+) {
+    let entity = event.entity;
+    let Ok(&StableId(id)) = ids.get(entity) else {
+        return;
+    };
+    if index.entity(id) == Some(entity) {
+        index.entities.remove(&id);
+    }
+}
@@ -3,31 +3,108 @@ use jackdaw_feathers::status_bar::{StatusBarCenter, StatusBarLeft, StatusBarRigh
 
 use crate::{
     EditorEntity,
-    brush::{BrushEditMode, ClipState, EditMode, VertexDragConstraint, VertexDragState},
+    brush::{
+        BrushEditMode, BrushRebuildQueue, ClipMode, ClipState, EditMode, UvToolState,
+        VertexDragConstraint, VertexDragState,
+    },
+    commands::CommandHistory,
     draw_brush::{DrawBrushState, DrawMode, DrawPhase},
     gizmos::{GizmoMode, GizmoSpace},
+    isolation::IsolationState,
     modal_transform::{ModalConstraint, ModalOp, ModalTransformState},
     scene_io::SceneFilePath,
     selection::{Selected, Selection},
     snapping::SnapSettings,
+    unsaved_changes::UnsavedChanges,
 };
 
 pub struct StatusBarPlugin;
 
 impl Plugin for StatusBarPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
+        app.init_resource::<StatusHints>().add_systems(
             Update,
             (
+                tick_status_prompt,
+                update_mode_hints,
                 update_status_left,
                 update_status_center,
                 update_status_right,
             )
+                .chain()
                 .run_if(in_state(crate::AppState::Editor)),
         );
     }
 }
 
+/// How long a prompt stays up by default, in seconds.
+const PROMPT_SECONDS: f32 = 4.0;
+
+/// Source of the hints for the built-in modes (draw brush, brush edit, modal transform).
+const MODE_HINTS: &str = "mode";
+
+/// Key hints and prompts shown in the status bar.
+///
+/// A tool that stays active sets a hint under its own name for as long as it runs and
+/// clears it when done; the most recently set hint replaces the right side of the bar.
+/// One-off messages go through [`prompt`](Self::prompt) and take over the center for a
+/// few seconds.
+#[derive(Resource, Default)]
+pub struct StatusHints {
+    hints: Vec<(&'static str, String)>,
+    prompt: Option<(String, Timer)>,
+}
+
+impl StatusHints {
+    pub fn set(&mut self, source: &'static str, hint: impl Into<String>) {
+        self.hints.retain(|(s, _)| *s != source);
+        self.hints.push((source, hint.into()));
+    }
+
+    pub fn clear(&mut self, source: &'static str) {
+        self.hints.retain(|(s, _)| *s != source);
+    }
+
+    /// The hint `source` currently shows, if any.
+    pub fn get(&self, source: &'static str) -> Option<&str> {
+        self.hints
+            .iter()
+            .find(|(s, _)| *s == source)
+            .map(|(_, hint)| hint.as_str())
+    }
+
+    /// The hint on display.
+    pub fn hint(&self) -> Option<&str> {
+        self.hints.last().map(|(_, hint)| hint.as_str())
+    }
+
+    /// Show `text` for a few seconds, replacing any earlier prompt.
+    pub fn prompt(&mut self, text: impl Into<String>) {
+        self.prompt_for(text, PROMPT_SECONDS);
+    }
+
+    pub fn prompt_for(&mut self, text: impl Into<String>, seconds: f32) {
+        self.prompt = Some((text.into(), Timer::from_seconds(seconds, TimerMode::Once)));
+    }
+
+    /// The prompt on display.
+    pub fn current_prompt(&self) -> Option<&str> {
+        self.prompt.as_ref().map(|(text, _)| text.as_str())
+    }
+}
+
+fn tick_status_prompt(time: Res<Time>, mut hints: ResMut<StatusHints>) {
+    // Ticking alone isn't a change worth redrawing the bar for
+    let expired = hints
+        .bypass_change_detection()
+        .prompt
+        .as_mut()
+        .is_some_and(|(_, timer)| timer.tick(time.delta()).is_finished());
+    if expired {
+        hints.prompt = None;
+    }
+}
+
 fn update_status_left(
     selection: Res<Selection>,
     selected: Query<Option<&Name>, With<Selected>>,
@@ -76,12 +153,54 @@ fn update_status_center(
     spot_lights: Query<(), (With<SpotLight>, Without<EditorEntity>)>,
     cameras: Query<(), (With<Camera3d>, Without<EditorEntity>)>,
     navmesh_state: Res<crate::navmesh::NavmeshState>,
+    recorder: Res<crate::macros::MacroRecorder>,
+    entity_pick: Option<Res<crate::custom_properties::PendingEntityPick>>,
+    history: Res<CommandHistory>,
+    rebuild_queue: Res<BrushRebuildQueue>,
+    hints: Res<StatusHints>,
+    time: Res<Time>,
+    mut history_label: Local<String>,
     mut text_query: Query<&mut Text, With<StatusBarCenter>>,
 ) {
+    if history.is_changed() || history_label.is_empty() {
+        *history_label = format!(
+            "Undo: {}/{} ({:.1} MB)",
+            history.undo_stack.len(),
+            history.max_depth,
+            history.memory_usage() as f64 / (1024.0 * 1024.0)
+        );
+    }
+
     let Ok(mut text) = text_query.single_mut() else {
         return;
     };
 
+    if let Some(pick) = entity_pick {
+        let status_str = format!(
+            "Pick the entity for '{}' in the viewport or hierarchy (Esc to cancel)",
+            pick.property_name
+        );
+        if text.0 != status_str {
+            text.0 = status_str;
+        }
+        return;
+    }
+
+    if let Some(prompt) = hints.current_prompt() {
+        if text.0 != prompt {
+            text.0 = prompt.to_string();
+        }
+        return;
+    }
+
+    if recorder.recording {
+        let status_str = format!("Recording macro ({} steps)", recorder.actions.len());
+        if text.0 != status_str {
+            text.0 = status_str;
+        }
+        return;
+    }
+
     if !matches!(navmesh_state.status, crate::navmesh::NavmeshStatus::Idle) {
         let status_str = format!("{}", navmesh_state.status);
         if text.0 != status_str {
@@ -90,6 +209,16 @@ fn update_status_center(
         return;
     }
 
+    if !rebuild_queue.is_empty() {
+        const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+        let frame = SPINNER[(time.elapsed_secs() * 10.0) as usize % SPINNER.len()];
+        let status_str = format!("{frame} Rebuilding {} brushes", rebuild_queue.len());
+        if text.0 != status_str {
+            text.0 = status_str;
+        }
+        return;
+    }
+
     let total = scene_entities.iter().count();
     let mesh_count = meshes.iter().count();
     let light_count =
@@ -97,39 +226,34 @@ fn update_status_center(
     let camera_count = cameras.iter().count();
 
     let new_text = format!(
-        "Entities: {total}  |  Meshes: {mesh_count}  |  Lights: {light_count}  |  Cameras: {camera_count}"
+        "Entities: {total}  |  Meshes: {mesh_count}  |  Lights: {light_count}  |  Cameras: {camera_count}  |  {}",
+        *history_label
     );
     if text.0 != new_text {
         text.0 = new_text;
     }
 }
 
-fn update_status_right(
-    mode: Res<GizmoMode>,
-    space: Res<GizmoSpace>,
-    scene_path: Res<SceneFilePath>,
-    snap_settings: Res<SnapSettings>,
+/// Key hints for the active mode: draw brush steps, brush edit keys (with clip tool
+/// steps), and modal transform axes.
+fn update_mode_hints(
     modal: Res<ModalTransformState>,
     edit_mode: Res<EditMode>,
     vertex_drag: Res<VertexDragState>,
     clip_state: Res<ClipState>,
     draw_state: Res<DrawBrushState>,
-    mut text_query: Query<&mut Text, With<StatusBarRight>>,
+    uv_tool: Res<UvToolState>,
+    mut hints: ResMut<StatusHints>,
 ) {
-    if !mode.is_changed()
-        && !space.is_changed()
-        && !snap_settings.is_changed()
-        && !modal.is_changed()
+    if !modal.is_changed()
         && !edit_mode.is_changed()
         && !vertex_drag.is_changed()
         && !clip_state.is_changed()
         && !draw_state.is_changed()
+        && !uv_tool.is_changed()
     {
         return;
     }
-    let Ok(mut text) = text_query.single_mut() else {
-        return;
-    };
 
     // Show draw brush mode status
     if let Some(ref active) = draw_state.active {
@@ -138,7 +262,7 @@ fn update_status_right(
             (DrawMode::Add, false) => "ADD",
             (DrawMode::Cut, _) => "CUT",
         };
-        text.0 = match active.phase {
+        let hint = match active.phase {
             DrawPhase::PlacingFirstCorner => {
                 format!(
                     "DRAW BRUSH ({mode_label}): Click to place first corner (Ctrl lock plane, Tab toggle mode) | Esc cancel"
@@ -168,6 +292,7 @@ fn update_status_right(
                 )
             }
         };
+        set_mode_hint(&mut hints, hint);
         return;
     }
 
@@ -187,23 +312,37 @@ fn update_status_right(
                 VertexDragConstraint::AxisZ => "Z",
             };
             format!(" | Dragging ({c}) X/Y/Z constrain")
+        } else if let Some(kind) = uv_tool.dragging() {
+            format!(" | Texture {} | RMB/Esc cancel", kind.label())
+        } else if uv_tool.active {
+            " | TEXTURE TOOL: Drag shift  Ctrl+Drag rotate  Shift+Drag scale | U/Esc exit"
+                .to_string()
         } else if sub_mode == BrushEditMode::Clip {
             let n = clip_state.points.len();
+            let keep = match clip_state.mode {
+                ClipMode::KeepFront => "Keep front",
+                ClipMode::KeepBack => "Keep back",
+                ClipMode::Split => "Keep both",
+            };
             if n < 2 {
                 format!(" | Click {}-3 points, Enter apply, Esc cancel", n + 1)
             } else {
-                " | Enter apply, Esc cancel".to_string()
+                format!(" | {keep} (Tab cycle), Enter apply, Esc cancel")
             }
         } else {
             String::new()
         };
         let base_hint = if sub_mode == BrushEditMode::Vertex {
-            "Drag move  Shift+Drag split edge  Del remove"
+            "Drag move  Shift+Drag split edge  M merge  K split face  Del remove"
+        } else if sub_mode == BrushEditMode::Face {
+            "Drag to move  Del remove  U texture tool"
         } else {
             "Drag to move  Del remove"
         };
-        text.0 =
-            format!("EDIT MODE: {sub_str} | 1 Vert  2 Edge  3 Face  4 Clip | {base_hint}{extra}");
+        set_mode_hint(
+            &mut hints,
+            format!("EDIT MODE: {sub_str} | 1 Vert  2 Edge  3 Face  4 Clip | {base_hint}{extra}"),
+        );
         return;
     }
 
@@ -214,12 +353,70 @@ fn update_status_right(
             ModalOp::Rotate => "Rotate",
             ModalOp::Scale => "Scale",
         };
+        let space_str = if active.local { "local " } else { "" };
         let constraint_str = match active.constraint {
             ModalConstraint::Free => "Free".to_string(),
-            ModalConstraint::Axis(axis) => format!("{axis:?} axis"),
-            ModalConstraint::Plane(excluded) => format!("{excluded:?} plane"),
+            ModalConstraint::Axis(axis) => format!("{space_str}{axis:?} axis"),
+            ModalConstraint::Plane(excluded) => format!("{space_str}{excluded:?} plane"),
+        };
+        let d = active.delta;
+        let delta_str = match active.op {
+            ModalOp::Grab => format!("D: ({:.3}, {:.3}, {:.3})", d.x, d.y, d.z),
+            ModalOp::Rotate => format!("{:.2}°", d.x),
+            ModalOp::Scale => format!("({:.3}, {:.3}, {:.3})", d.x, d.y, d.z),
+        };
+        let typed_str = if active.numeric.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", active.numeric)
         };
-        text.0 = format!("{op_str}: {constraint_str} | LMB confirm, RMB/Esc cancel");
+        set_mode_hint(
+            &mut hints,
+            format!(
+                "{op_str}: {constraint_str}{typed_str} | {delta_str} | Type value, X/Y/Z axis (twice local), Shift+X/Y/Z exclude | LMB/Enter confirm, RMB/Esc cancel"
+            ),
+        );
+        return;
+    }
+
+    if hints.get(MODE_HINTS).is_some() {
+        hints.clear(MODE_HINTS);
+    }
+}
+
+/// Only touch the hints when the text differs, so the bar isn't redrawn every frame.
+fn set_mode_hint(hints: &mut ResMut<StatusHints>, hint: String) {
+    if hints.get(MODE_HINTS) != Some(hint.as_str()) {
+        hints.set(MODE_HINTS, hint);
+    }
+}
+
+fn update_status_right(
+    mode: Res<GizmoMode>,
+    space: Res<GizmoSpace>,
+    scene_path: Res<SceneFilePath>,
+    snap_settings: Res<SnapSettings>,
+    hints: Res<StatusHints>,
+    isolation: Res<IsolationState>,
+    unsaved: Res<UnsavedChanges>,
+    mut text_query: Query<&mut Text, With<StatusBarRight>>,
+) {
+    if !mode.is_changed()
+        && !space.is_changed()
+        && !snap_settings.is_changed()
+        && !hints.is_changed()
+        && !isolation.is_changed()
+        && !unsaved.is_changed()
+        && !scene_path.is_changed()
+    {
+        return;
+    }
+    let Ok(mut text) = text_query.single_mut() else {
+        return;
+    };
+
+    if let Some(hint) = hints.hint() {
+        text.0 = hint.to_string();
         return;
     }
 
@@ -260,11 +457,18 @@ fn update_status_right(
         }
     };
 
-    let path_str = scene_path
-        .path
-        .as_deref()
-        .map(|p| format!(" | {p}"))
-        .unwrap_or_default();
+    let star = if unsaved.dirty { "*" } else { "" };
+    let path_str = match scene_path.path.as_deref() {
+        Some(p) => format!(" | {p}{star}"),
+        None if unsaved.dirty => " | Untitled*".to_string(),
+        None => String::new(),
+    };
+
+    let isolation_str = if isolation.is_active() {
+        "ISOLATED (/ to exit) | "
+    } else {
+        ""
+    };
 
-    text.0 = format!("{mode_str} ({space_str}) | {snap_str}{path_str}");
+    text.0 = format!("{isolation_str}{mode_str} ({space_str}) | {snap_str}{path_str}");
 }
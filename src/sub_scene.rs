@@ -0,0 +1,125 @@
+//! Editor side of [`SubScene`] references: placing them, keeping their contents out
+//! of the saved scene and the hierarchy, and reloading them when the source changes.
+
+use std::{collections::HashSet, path::Path};
+
+use bevy::{asset::AssetPath, prelude::*};
+use jackdaw_jsn::SubScene;
+
+use crate::{
+    EditorHidden, NonSerializable,
+    entity_ops::to_asset_path,
+    project::ProjectRoot,
+    project_watcher::{ProjectFilesChanged, resolve_asset_file},
+};
+
+pub struct SubScenePlugin;
+
+impl Plugin for SubScenePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                mark_sub_scene_contents,
+                reload_changed_sub_scenes.after(crate::project_watcher::publish_file_changes),
+            )
+                .run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+/// Spawn a sub-scene referencing the `.jsn` file at `path`, selected and undoable.
+pub fn spawn_sub_scene(world: &mut World, path: &str, position: Vec3) {
+    let canonical = |p: &str| Path::new(p).canonicalize().ok();
+    let current_scene = world
+        .resource::<crate::scene_io::SceneFilePath>()
+        .path
+        .clone();
+    if current_scene
+        .and_then(|current| canonical(&current))
+        .is_some_and(|current| Some(current) == canonical(path))
+    {
+        warn!("A scene can't contain a reference to itself");
+        return;
+    }
+
+    let name = Path::new(path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Sub-Scene".to_string());
+    let entity = world
+        .spawn((
+            Name::new(name),
+            SubScene {
+                path: to_asset_path(path),
+            },
+            Transform::from_translation(position),
+            Visibility::default(),
+        ))
+        .id();
+    crate::entity_templates::finalize_instantiation(world, &[entity]);
+}
+
+/// Reload a sub-scene's source file now.
+pub fn reload_sub_scene(world: &mut World, entity: Entity) {
+    let Some(path) = world.get::<SubScene>(entity).map(|s| s.path.clone()) else {
+        return;
+    };
+    world.resource::<AssetServer>().reload(path);
+}
+
+/// Entities spawned from a sub-scene belong to its file: keep them out of the saved
+/// scene and the hierarchy. Clicking them in the viewport selects the sub-scene.
+fn mark_sub_scene_contents(
+    mut commands: Commands,
+    new_children: Query<Entity, (Added<ChildOf>, Without<NonSerializable>)>,
+    parents: Query<&ChildOf>,
+    sub_scenes: Query<(), With<SubScene>>,
+) {
+    for entity in &new_children {
+        if parents
+            .iter_ancestors(entity)
+            .any(|ancestor| sub_scenes.contains(ancestor))
+        {
+            commands
+                .entity(entity)
+                .insert((NonSerializable, EditorHidden));
+        }
+    }
+}
+
+/// Reload the sub-scenes whose source files changed on disk. The scene spawner then
+/// respawns every instance of the reloaded scene.
+fn reload_changed_sub_scenes(
+    mut changes: MessageReader<ProjectFilesChanged>,
+    sub_scenes: Query<&SubScene>,
+    project: Option<Res<ProjectRoot>>,
+    asset_server: Res<AssetServer>,
+) {
+    let changed: HashSet<&Path> = changes
+        .read()
+        .flat_map(|change| &change.modified)
+        .filter(|path| path.extension().is_some_and(|e| e == "jsn"))
+        .map(|path| path.as_path())
+        .collect();
+    let Some(project) = project else {
+        return;
+    };
+    if changed.is_empty() {
+        return;
+    }
+
+    let assets_dir = project.assets_dir();
+    let mut reloaded = HashSet::new();
+    for sub_scene in &sub_scenes {
+        if sub_scene.path.is_empty() || reloaded.contains(&sub_scene.path) {
+            continue;
+        }
+        let file = resolve_asset_file(&assets_dir, &AssetPath::parse(&sub_scene.path));
+        if changed.contains(file.as_path()) {
+            info!("Sub-scene source changed, reloading {}", sub_scene.path);
+            asset_server.reload(sub_scene.path.clone());
+            reloaded.insert(sub_scene.path.clone());
+        }
+    }
+}
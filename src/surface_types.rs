@@ -0,0 +1,169 @@
+use bevy::prelude::*;
+
+use crate::{
+    brush::{Brush, BrushMeshCache, SetBrush},
+    commands::{CommandGroup, CommandHistory, EditorCommand},
+    draw_brush::CutPreviewHidden,
+    face_grid::FaceGridGizmoGroup,
+    project::ProjectRoot,
+    selection::Selection,
+    viewport_overlays::OverlaySettings,
+};
+
+/// Offered when the project doesn't define its own `surface_types`.
+pub const DEFAULT_SURFACE_TYPES: &[&str] = &[
+    "concrete", "dirt", "glass", "grass", "metal", "stone", "water", "wood",
+];
+
+pub struct SurfaceTypesPlugin;
+
+impl Plugin for SurfaceTypesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            draw_surface_overlay
+                .after(bevy::transform::TransformSystems::Propagate)
+                .run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+/// Surface types available in the current project.
+pub fn project_surface_types(project: Option<&ProjectRoot>) -> Vec<String> {
+    match project {
+        Some(project) if !project.config.project.surface_types.is_empty() => {
+            project.config.project.surface_types.clone()
+        }
+        _ => DEFAULT_SURFACE_TYPES
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+    }
+}
+
+/// Stable overlay color for a surface type name.
+pub fn surface_color(surface: &str) -> Color {
+    // FNV-1a so colors don't change between runs.
+    let hash = surface.bytes().fold(0x811c9dc5u32, |h, b| {
+        (h ^ b as u32).wrapping_mul(0x01000193)
+    });
+    Color::hsl((hash % 360) as f32, 0.75, 0.55)
+}
+
+/// Tint every tagged brush face with its surface color (outline plus a fan to the centroid).
+fn draw_surface_overlay(
+    mut gizmos: Gizmos<FaceGridGizmoGroup>,
+    settings: Res<OverlaySettings>,
+    brushes: Query<(&Brush, &BrushMeshCache, &GlobalTransform), Without<CutPreviewHidden>>,
+) {
+    if !settings.show_surface_types {
+        return;
+    }
+
+    for (brush, cache, global_tf) in &brushes {
+        for (face, polygon) in brush.faces.iter().zip(&cache.face_polygons) {
+            if face.surface.is_empty() || polygon.len() < 3 {
+                continue;
+            }
+            let color = surface_color(&face.surface);
+            let points: Vec<Vec3> = polygon
+                .iter()
+                .map(|&i| global_tf.transform_point(cache.vertices[i]))
+                .collect();
+            let centroid = points.iter().sum::<Vec3>() / points.len() as f32;
+            for (i, &a) in points.iter().enumerate() {
+                let b = points[(i + 1) % points.len()];
+                gizmos.line(a, b, color);
+                gizmos.line(centroid, a.lerp(centroid, 0.1), color.with_alpha(0.5));
+            }
+        }
+    }
+}
+
+/// The texture (or, failing that, material) path a face's surface rules match against.
+fn face_texture_path(
+    materials: &Assets<StandardMaterial>,
+    material: &Handle<StandardMaterial>,
+) -> Option<String> {
+    materials
+        .get(material)
+        .and_then(|m| m.base_color_texture.as_ref())
+        .and_then(|t| t.path())
+        .or_else(|| material.path())
+        .map(|p| p.to_string())
+}
+
+/// Re-tag brush faces from the project's texture path rules. Applies to the
+/// selected brushes, or every brush if none are selected. One undo step.
+pub fn retag_surfaces_from_rules(world: &mut World) {
+    let Some(rules) = world
+        .get_resource::<ProjectRoot>()
+        .map(|p| p.config.project.surface_rules.clone())
+    else {
+        return;
+    };
+    if rules.is_empty() {
+        warn!("No surface_rules defined in project.jsn");
+        return;
+    }
+
+    let selected: Vec<Entity> = world
+        .resource::<Selection>()
+        .entities
+        .iter()
+        .copied()
+        .filter(|&e| world.get::<Brush>(e).is_some())
+        .collect();
+    let targets: Vec<Entity> = if selected.is_empty() {
+        world
+            .query_filtered::<Entity, With<Brush>>()
+            .iter(world)
+            .collect()
+    } else {
+        selected
+    };
+
+    let mut cmds: Vec<Box<dyn EditorCommand>> = Vec::new();
+    let mut tagged = 0;
+    for entity in targets {
+        let Some(old) = world.get::<Brush>(entity).cloned() else {
+            continue;
+        };
+        let mut new = old.clone();
+        let mut changed = false;
+        let materials = world.resource::<Assets<StandardMaterial>>();
+        for face in &mut new.faces {
+            let Some(path) = face_texture_path(materials, &face.material) else {
+                continue;
+            };
+            if let Some(rule) = rules.iter().find(|r| r.matches(&path)) {
+                if face.surface != rule.surface {
+                    face.surface = rule.surface.clone();
+                    tagged += 1;
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            continue;
+        }
+        let cmd = SetBrush {
+            entity,
+            old,
+            new,
+            label: "Re-tag surfaces".to_string(),
+        };
+        cmd.execute(world);
+        cmds.push(Box::new(cmd));
+    }
+
+    info!("Re-tagged {tagged} faces from surface rules");
+    if cmds.is_empty() {
+        return;
+    }
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(CommandGroup {
+        commands: cmds,
+        label: "Re-tag surfaces".to_string(),
+    }));
+}
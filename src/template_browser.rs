@@ -0,0 +1,472 @@
+//! Templates panel: every entity template saved under `assets/templates`, grouped by
+//! category folder, with the viewport thumbnail captured when it was saved. Drag a
+//! template onto the viewport to instantiate it; right-click to rename or delete.
+
+use std::path::{Path, PathBuf};
+
+use bevy::{feathers::theme::ThemedText, prelude::*};
+use jackdaw_feathers::{
+    context_menu::spawn_context_menu,
+    dialog::{
+        DialogActionEvent, DialogChildrenSlot, DialogClosedEvent, DialogId,
+        OpenConfirmationDialogEvent, OpenDialogEvent,
+    },
+    icons::{self, Icon, IconFont},
+    panel_header,
+    text_edit::{self, TextEditProps, TextEditValue},
+    tokens,
+};
+use jackdaw_widgets::{
+    context_menu::{ContextMenuAction, ContextMenuState},
+    file_browser::FileBrowserItem,
+};
+
+use crate::{
+    EditorEntity,
+    asset_browser::attach_tooltip,
+    entity_templates::{self, TEMPLATES_DIR},
+};
+
+const TEMPLATE_DIALOG: &str = "template_browser";
+
+pub struct TemplateBrowserPlugin;
+
+impl Plugin for TemplateBrowserPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TemplateBrowserState>()
+            .init_resource::<PendingTemplateDialog>()
+            .add_observer(on_template_context_action)
+            .add_observer(on_template_dialog_action)
+            .add_observer(on_template_dialog_closed)
+            .add_systems(
+                Update,
+                (refresh_template_browser, populate_template_dialog)
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+#[derive(Resource)]
+pub struct TemplateBrowserState {
+    pub needs_refresh: bool,
+    pub entries: Vec<TemplateEntry>,
+}
+
+impl Default for TemplateBrowserState {
+    fn default() -> Self {
+        Self {
+            needs_refresh: true,
+            entries: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TemplateEntry {
+    pub path: PathBuf,
+    pub name: String,
+    /// Folder below the templates directory; empty for uncategorized templates.
+    pub category: String,
+    pub thumbnail: Option<PathBuf>,
+}
+
+#[derive(Resource, Default)]
+enum PendingTemplateDialog {
+    #[default]
+    None,
+    Rename(PathBuf),
+    Delete(PathBuf),
+}
+
+#[derive(Component)]
+pub struct TemplateBrowserContent;
+
+/// Marker for the name input in the rename dialog.
+#[derive(Component)]
+struct TemplateRenameInput;
+
+fn scan_templates(dir: &Path, entries: &mut Vec<TemplateEntry>) {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            scan_templates(&path, entries);
+            continue;
+        }
+        if !entity_templates::is_template_file(&path) {
+            continue;
+        }
+        let full_name = entity_templates::template_name(&path);
+        let (category, name) = match full_name.rsplit_once('/') {
+            Some((category, name)) => (category.to_string(), name.to_string()),
+            None => (String::new(), full_name),
+        };
+        let thumbnail = entity_templates::thumbnail_path(&path);
+        entries.push(TemplateEntry {
+            thumbnail: thumbnail.exists().then_some(thumbnail),
+            path,
+            name,
+            category,
+        });
+    }
+}
+
+fn refresh_template_browser(
+    mut state: ResMut<TemplateBrowserState>,
+    mut commands: Commands,
+    icon_font: Res<IconFont>,
+    asset_server: Res<AssetServer>,
+    content: Query<(Entity, Option<&Children>), With<TemplateBrowserContent>>,
+) {
+    if !state.needs_refresh {
+        return;
+    }
+    let Ok((content, children)) = content.single() else {
+        return;
+    };
+    state.needs_refresh = false;
+
+    state.entries.clear();
+    scan_templates(Path::new(TEMPLATES_DIR), &mut state.entries);
+    // Uncategorized first, then categories alphabetically
+    state.entries.sort_by(|a, b| {
+        a.category
+            .to_lowercase()
+            .cmp(&b.category.to_lowercase())
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+
+    if let Some(children) = children {
+        for child in children.iter() {
+            commands.entity(child).despawn();
+        }
+    }
+
+    if state.entries.is_empty() {
+        commands.spawn((
+            Text::new("No templates yet. Right-click an entity in the hierarchy to save one."),
+            TextFont {
+                font_size: tokens::FONT_SM,
+                ..Default::default()
+            },
+            TextColor(tokens::TEXT_SECONDARY),
+            ChildOf(content),
+        ));
+        return;
+    }
+
+    let mut grid = None;
+    let mut category = None;
+    for entry in &state.entries {
+        if category != Some(&entry.category) {
+            category = Some(&entry.category);
+            let label = if entry.category.is_empty() {
+                "Uncategorized"
+            } else {
+                &entry.category
+            };
+            commands.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                ThemedText,
+                Node {
+                    margin: UiRect::top(Val::Px(tokens::SPACING_SM)),
+                    ..Default::default()
+                },
+                ChildOf(content),
+            ));
+            grid = Some(
+                commands
+                    .spawn((
+                        Node {
+                            flex_direction: FlexDirection::Row,
+                            flex_wrap: FlexWrap::Wrap,
+                            row_gap: Val::Px(tokens::SPACING_XS),
+                            column_gap: Val::Px(tokens::SPACING_XS),
+                            ..Default::default()
+                        },
+                        ChildOf(content),
+                    ))
+                    .id(),
+            );
+        }
+        let Some(grid) = grid else {
+            continue;
+        };
+        spawn_template_tile(&mut commands, grid, entry, &icon_font.0, &asset_server);
+    }
+}
+
+fn spawn_template_tile(
+    commands: &mut Commands,
+    grid: Entity,
+    entry: &TemplateEntry,
+    icon_font: &Handle<Font>,
+    asset_server: &AssetServer,
+) {
+    let path = entry.path.to_string_lossy().to_string();
+    // The viewport instantiates dropped template files
+    let tile = commands
+        .spawn((
+            FileBrowserItem {
+                path,
+                is_directory: false,
+                file_name: entry.name.clone(),
+            },
+            Node {
+                width: Val::Px(64.0),
+                height: Val::Px(80.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(Val::Px(2.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                border_radius: BorderRadius::all(Val::Px(4.0)),
+                ..Default::default()
+            },
+            BorderColor::all(Color::NONE),
+            BackgroundColor(Color::NONE),
+            ChildOf(grid),
+        ))
+        .id();
+
+    let thumbnail = entry
+        .thumbnail
+        .as_ref()
+        .and_then(|p| std::path::absolute(p).ok());
+    if let Some(thumbnail) = thumbnail {
+        let abs = thumbnail.to_string_lossy().replace('\\', "/");
+        commands.spawn((
+            ImageNode::new(asset_server.load(abs)),
+            Node {
+                width: Val::Px(56.0),
+                height: Val::Px(56.0),
+                ..Default::default()
+            },
+            ChildOf(tile),
+        ));
+    } else {
+        commands.spawn((
+            Node {
+                width: Val::Px(56.0),
+                height: Val::Px(56.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            ChildOf(tile),
+            children![icons::icon_colored(
+                Icon::LayoutTemplate,
+                tokens::FONT_LG,
+                icon_font.clone(),
+                tokens::TEXT_SECONDARY,
+            )],
+        ));
+    }
+
+    let is_truncated = entry.name.len() > 10;
+    let display_name = if is_truncated {
+        format!("{}...", entry.name.chars().take(8).collect::<String>())
+    } else {
+        entry.name.clone()
+    };
+    let name_entity = commands
+        .spawn((
+            Text::new(display_name),
+            TextFont {
+                font_size: 9.0,
+                ..Default::default()
+            },
+            TextColor(tokens::TEXT_SECONDARY),
+            Node {
+                max_width: Val::Px(60.0),
+                overflow: Overflow::clip(),
+                ..Default::default()
+            },
+            ChildOf(tile),
+        ))
+        .id();
+    if is_truncated {
+        attach_tooltip(commands, name_entity, entry.name.clone());
+    }
+
+    commands.entity(tile).observe(
+        |hover: On<Pointer<Over>>, mut borders: Query<&mut BorderColor>| {
+            if let Ok(mut border) = borders.get_mut(hover.event_target()) {
+                *border = BorderColor::all(tokens::SELECTED_BORDER);
+            }
+        },
+    );
+    commands.entity(tile).observe(
+        |out: On<Pointer<Out>>, mut borders: Query<&mut BorderColor>| {
+            if let Ok(mut border) = borders.get_mut(out.event_target()) {
+                *border = BorderColor::all(Color::NONE);
+            }
+        },
+    );
+    commands.entity(tile).observe(open_template_context_menu);
+}
+
+fn open_template_context_menu(
+    event: On<Pointer<Click>>,
+    mut commands: Commands,
+    mut state: ResMut<ContextMenuState>,
+) {
+    if event.button != PointerButton::Secondary {
+        return;
+    }
+    if let Some(menu) = state.menu_entity.take() {
+        if let Ok(mut ec) = commands.get_entity(menu) {
+            ec.despawn();
+        }
+    }
+    let tile = event.event_target();
+    let items = [
+        ("template.rename", "Rename / Move..."),
+        ("template.delete", "Delete"),
+    ];
+    let menu = spawn_context_menu(
+        &mut commands,
+        event.pointer_location.position,
+        Some(tile),
+        &items,
+    );
+    state.menu_entity = Some(menu);
+    state.target_entity = Some(tile);
+}
+
+fn on_template_context_action(
+    event: On<ContextMenuAction>,
+    items: Query<&FileBrowserItem>,
+    mut pending: ResMut<PendingTemplateDialog>,
+    mut commands: Commands,
+) {
+    let Some(item) = event.target_entity.and_then(|e| items.get(e).ok()) else {
+        return;
+    };
+    let path = PathBuf::from(&item.path);
+    match event.action.as_str() {
+        "template.rename" => {
+            *pending = PendingTemplateDialog::Rename(path);
+            commands.trigger(
+                OpenDialogEvent::new("Rename Template", "Rename")
+                    .with_id(TEMPLATE_DIALOG)
+                    .with_max_width(px(360)),
+            );
+        }
+        "template.delete" => {
+            *pending = PendingTemplateDialog::Delete(path);
+            commands.trigger(
+                OpenConfirmationDialogEvent::new("Delete Template", "Delete")
+                    .with_id(TEMPLATE_DIALOG)
+                    .with_description(format!(
+                        "Delete the template \"{}\"? This cannot be undone.",
+                        item.file_name
+                    )),
+            );
+        }
+        _ => {}
+    }
+}
+
+fn populate_template_dialog(
+    mut commands: Commands,
+    pending: Res<PendingTemplateDialog>,
+    slots: Query<(Entity, &DialogId), Added<DialogChildrenSlot>>,
+) {
+    let PendingTemplateDialog::Rename(path) = &*pending else {
+        return;
+    };
+    for (slot, id) in &slots {
+        if id.0 != TEMPLATE_DIALOG {
+            continue;
+        }
+        commands.spawn((
+            TemplateRenameInput,
+            text_edit::text_edit(
+                TextEditProps::default()
+                    .with_label("Name (Category/Name to categorize)")
+                    .with_placeholder("Template name...")
+                    .with_default_value(entity_templates::template_name(path)),
+            ),
+            ChildOf(slot),
+        ));
+    }
+}
+
+/// However the dialog closes, forget the pending dialog; an action has used it by then.
+fn on_template_dialog_closed(
+    event: On<DialogClosedEvent>,
+    mut pending: ResMut<PendingTemplateDialog>,
+) {
+    if event.id == Some(TEMPLATE_DIALOG) {
+        *pending = PendingTemplateDialog::None;
+    }
+}
+
+fn on_template_dialog_action(
+    event: On<DialogActionEvent>,
+    mut pending: ResMut<PendingTemplateDialog>,
+    mut state: ResMut<TemplateBrowserState>,
+    name_inputs: Query<&TextEditValue, With<TemplateRenameInput>>,
+) {
+    if event.id != Some(TEMPLATE_DIALOG) {
+        return;
+    }
+    match std::mem::take(&mut *pending) {
+        PendingTemplateDialog::None => return,
+        PendingTemplateDialog::Rename(path) => {
+            let name = name_inputs
+                .iter()
+                .next()
+                .map(|input| input.0.trim().to_string())
+                .unwrap_or_default();
+            if name.is_empty() {
+                return;
+            }
+            if let Err(err) = entity_templates::rename_template(&path, &name) {
+                warn!("Failed to rename template '{}': {err}", path.display());
+            }
+        }
+        PendingTemplateDialog::Delete(path) => {
+            if let Err(err) = entity_templates::delete_template(&path) {
+                warn!("Failed to delete template '{}': {err}", path.display());
+            }
+        }
+    }
+    state.needs_refresh = true;
+}
+
+pub fn template_browser_panel() -> impl Bundle {
+    (
+        EditorEntity,
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        },
+        BackgroundColor(tokens::PANEL_BG),
+        children![
+            panel_header::panel_header("Templates"),
+            (
+                TemplateBrowserContent,
+                EditorEntity,
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    flex_grow: 1.0,
+                    min_height: Val::Px(0.0),
+                    overflow: Overflow::scroll_y(),
+                    padding: UiRect::all(Val::Px(tokens::SPACING_SM)),
+                    row_gap: Val::Px(tokens::SPACING_XS),
+                    ..Default::default()
+                },
+            ),
+        ],
+    )
+}
@@ -0,0 +1,292 @@
+//! Dialog for overriding a template's exposed parameters as it's instantiated. The
+//! template is spawned before the dialog opens so it previews in place; confirming
+//! applies the typed values, "Use Defaults" keeps the template as saved, and
+//! dismissing the dialog removes it again.
+
+use bevy::prelude::*;
+use jackdaw_feathers::{
+    combobox::{ComboBoxChangeEvent, combobox_with_selected},
+    dialog::{
+        DialogActionEvent, DialogChildrenSlot, DialogClosedEvent, DialogId,
+        DialogSecondaryActionEvent, OpenDialogEvent,
+    },
+    text_edit::{self, TextEditProps, TextEditValue},
+};
+use jackdaw_jsn::{Brush, CustomProperties, PropertyValue};
+
+use crate::{
+    entity_templates::{TemplateParameter, TemplateParameterTarget, finalize_instantiation},
+    material_browser::MaterialRegistry,
+};
+
+const PARAMETERS_DIALOG: &str = "template_parameters";
+
+pub struct TemplateParametersPlugin;
+
+impl Plugin for TemplateParametersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingTemplateParameters>()
+            .add_observer(on_parameters_confirm)
+            .add_observer(on_parameters_use_defaults)
+            .add_observer(on_parameters_dialog_closed)
+            .add_systems(
+                Update,
+                populate_parameters_dialog.run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+#[derive(Resource, Default)]
+struct PendingTemplateParameters(Option<PendingInstance>);
+
+struct PendingInstance {
+    parameters: Vec<TemplateParameter>,
+    spawned: Vec<Entity>,
+    roots: Vec<Entity>,
+    /// Chosen material per parameter: an index into the material registry, or
+    /// `None` to keep the template's own.
+    materials: Vec<Option<usize>>,
+}
+
+/// Text input for the parameter at this index.
+#[derive(Component)]
+struct TemplateParameterInput(usize);
+
+/// Ask for parameter overrides of freshly spawned template entities before their
+/// instantiation is finalized.
+pub fn open_parameters_dialog(
+    world: &mut World,
+    parameters: Vec<TemplateParameter>,
+    spawned: Vec<Entity>,
+    roots: Vec<Entity>,
+) {
+    // An instance still waiting on the dialog is dropped, like a dismissed dialog
+    if let Some(previous) = world.resource_mut::<PendingTemplateParameters>().0.take() {
+        despawn_instance(world, &previous);
+    }
+    let materials = vec![None; parameters.len()];
+    world.resource_mut::<PendingTemplateParameters>().0 = Some(PendingInstance {
+        parameters,
+        spawned,
+        roots,
+        materials,
+    });
+    world.trigger(
+        OpenDialogEvent::new("Template Parameters", "Create")
+            .with_id(PARAMETERS_DIALOG)
+            .with_secondary("Use Defaults")
+            .with_max_width(px(360)),
+    );
+}
+
+fn property_text(value: &PropertyValue) -> Option<String> {
+    match value {
+        PropertyValue::Bool(v) => Some(v.to_string()),
+        PropertyValue::Int(v) => Some(v.to_string()),
+        PropertyValue::Float(v) => Some(v.to_string()),
+        PropertyValue::String(v) => Some(v.clone()),
+        _ => None,
+    }
+}
+
+/// Parse typed text as the same kind of value the property already holds.
+fn parse_property(text: &str, like: &PropertyValue) -> Option<PropertyValue> {
+    match like {
+        PropertyValue::Bool(_) => text.parse().ok().map(PropertyValue::Bool),
+        PropertyValue::Int(_) => text.parse().ok().map(PropertyValue::Int),
+        PropertyValue::Float(_) => text.parse().ok().map(PropertyValue::Float),
+        PropertyValue::String(_) => Some(PropertyValue::String(text.to_string())),
+        _ => None,
+    }
+}
+
+fn populate_parameters_dialog(
+    mut commands: Commands,
+    pending: Res<PendingTemplateParameters>,
+    registry: Res<MaterialRegistry>,
+    names: Query<&Name>,
+    properties: Query<&CustomProperties>,
+    slots: Query<(Entity, &DialogId), Added<DialogChildrenSlot>>,
+) {
+    let Some(instance) = &pending.0 else {
+        return;
+    };
+    for (slot, id) in &slots {
+        if id.0 != PARAMETERS_DIALOG {
+            continue;
+        }
+        for (index, parameter) in instance.parameters.iter().enumerate() {
+            let Some(&entity) = instance.spawned.get(parameter.entity) else {
+                continue;
+            };
+            match &parameter.target {
+                TemplateParameterTarget::Name => {
+                    let current = names.get(entity).map(|n| n.to_string()).unwrap_or_default();
+                    commands.spawn((
+                        TemplateParameterInput(index),
+                        text_edit::text_edit(
+                            TextEditProps::default()
+                                .with_label(parameter.label.clone())
+                                .with_default_value(current),
+                        ),
+                        ChildOf(slot),
+                    ));
+                }
+                TemplateParameterTarget::Property { key } => {
+                    let Some(current) = properties
+                        .get(entity)
+                        .ok()
+                        .and_then(|p| p.properties.get(key))
+                        .and_then(property_text)
+                    else {
+                        continue;
+                    };
+                    commands.spawn((
+                        TemplateParameterInput(index),
+                        text_edit::text_edit(
+                            TextEditProps::default()
+                                .with_label(parameter.label.clone())
+                                .with_default_value(current)
+                                .allow_empty(),
+                        ),
+                        ChildOf(slot),
+                    ));
+                }
+                TemplateParameterTarget::Material => {
+                    let options: Vec<String> = std::iter::once("(Template default)".to_string())
+                        .chain(registry.entries.iter().map(|e| e.name.clone()))
+                        .collect();
+                    commands
+                        .spawn((combobox_with_selected(options, 0), ChildOf(slot)))
+                        .observe(
+                            move |event: On<ComboBoxChangeEvent>,
+                                  mut pending: ResMut<PendingTemplateParameters>| {
+                                if let Some(instance) = &mut pending.0 {
+                                    instance.materials[index] = event.selected.checked_sub(1);
+                                }
+                            },
+                        );
+                }
+            }
+        }
+    }
+}
+
+fn on_parameters_confirm(
+    event: On<DialogActionEvent>,
+    mut commands: Commands,
+    mut pending: ResMut<PendingTemplateParameters>,
+    inputs: Query<(&TemplateParameterInput, &TextEditValue)>,
+) {
+    if event.id != Some(PARAMETERS_DIALOG) {
+        return;
+    }
+    let Some(instance) = pending.0.take() else {
+        return;
+    };
+    let values: Vec<(usize, String)> = inputs
+        .iter()
+        .map(|(input, value)| (input.0, value.0.trim().to_string()))
+        .collect();
+    commands.queue(move |world: &mut World| {
+        apply_parameters(world, &instance, &values);
+        finalize_instantiation(world, &instance.roots);
+    });
+}
+
+fn on_parameters_use_defaults(
+    event: On<DialogSecondaryActionEvent>,
+    mut commands: Commands,
+    mut pending: ResMut<PendingTemplateParameters>,
+) {
+    if event.id != Some(PARAMETERS_DIALOG) {
+        return;
+    }
+    let Some(instance) = pending.0.take() else {
+        return;
+    };
+    commands.queue(move |world: &mut World| {
+        finalize_instantiation(world, &instance.roots);
+    });
+}
+
+/// Esc, Cancel, or a backdrop click dismisses without an action: take the previewed
+/// template back out of the scene.
+fn on_parameters_dialog_closed(
+    event: On<DialogClosedEvent>,
+    mut commands: Commands,
+    mut pending: ResMut<PendingTemplateParameters>,
+) {
+    if event.id != Some(PARAMETERS_DIALOG) {
+        return;
+    }
+    if let Some(instance) = pending.0.take() {
+        commands.queue(move |world: &mut World| despawn_instance(world, &instance));
+    }
+}
+
+fn despawn_instance(world: &mut World, instance: &PendingInstance) {
+    for &root in &instance.roots {
+        if let Ok(entity) = world.get_entity_mut(root) {
+            entity.despawn();
+        }
+    }
+}
+
+fn apply_parameters(world: &mut World, instance: &PendingInstance, values: &[(usize, String)]) {
+    for &(index, ref text) in values {
+        let Some(parameter) = instance.parameters.get(index) else {
+            continue;
+        };
+        let Some(&entity) = instance.spawned.get(parameter.entity) else {
+            continue;
+        };
+        match &parameter.target {
+            TemplateParameterTarget::Name => {
+                if !text.is_empty() {
+                    world.entity_mut(entity).insert(Name::new(text.clone()));
+                }
+            }
+            TemplateParameterTarget::Property { key } => {
+                let Some(mut properties) = world.get_mut::<CustomProperties>(entity) else {
+                    continue;
+                };
+                let Some(current) = properties.properties.get(key) else {
+                    continue;
+                };
+                match parse_property(text, current) {
+                    Some(value) => {
+                        properties.properties.insert(key.clone(), value);
+                    }
+                    None => warn!("'{text}' is not a valid value for '{}'", parameter.label),
+                }
+            }
+            TemplateParameterTarget::Material => {}
+        }
+    }
+
+    for (parameter, choice) in instance.parameters.iter().zip(&instance.materials) {
+        let Some(choice) = *choice else {
+            continue;
+        };
+        let Some(&entity) = instance.spawned.get(parameter.entity) else {
+            continue;
+        };
+        let Some(material) = world
+            .resource::<MaterialRegistry>()
+            .entries
+            .get(choice)
+            .map(|e| e.handle.clone())
+        else {
+            continue;
+        };
+        if let Some(mut brush) = world.get_mut::<Brush>(entity) {
+            for face in &mut brush.faces {
+                face.material = material.clone();
+            }
+        }
+        if let Some(mut mesh_material) = world.get_mut::<MeshMaterial3d<StandardMaterial>>(entity) {
+            mesh_material.0 = material;
+        }
+    }
+}
@@ -1,13 +1,15 @@
 use bevy::{
+    ecs::entity::EntityHashMap,
     input::mouse::{MouseScrollUnit, MouseWheel},
     prelude::*,
     ui::UiGlobalTransform,
+    window::PrimaryWindow,
 };
 
 use super::{
     CHUNK_SIZE, TerrainBrushSettings, TerrainDirtyChunks, TerrainEditMode, TerrainSculptState,
 };
-use crate::commands::{CommandHistory, EditorCommand};
+use crate::commands::{CommandHistory, EditorCommand, remap_entity};
 use crate::selection::Selection;
 use crate::viewport::{MainViewportCamera, SceneViewport};
 
@@ -53,11 +55,21 @@ impl EditorCommand for SetTerrainHeights {
     fn description(&self) -> &str {
         &self.label
     }
+
+    fn memory_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + (self.old_heights.len() + self.new_heights.len()) * std::mem::size_of::<f32>()
+            + self.label.len()
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+    }
 }
 
 fn terrain_sculpt_interaction(
     mouse: Res<ButtonInput<MouseButton>>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
     mut terrain_query: Query<(
@@ -217,8 +229,7 @@ fn terrain_sculpt_interaction(
             new_heights: terrain.heights.clone(),
             label: format!("Terrain {:?}", tool),
         };
-        history.undo_stack.push(Box::new(cmd));
-        history.redo_stack.clear();
+        history.push_executed(Box::new(cmd));
     }
 }
 
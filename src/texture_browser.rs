@@ -1,2 +1,1060 @@
+//! Texture browser panel: every image under the project's assets directory, with a
+//! folder tree, search, tag filter, favorites and a recently used strip. Clicking a
+//! texture applies it to the selected brushes, or to the selected faces in face mode.
+
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+use bevy::{feathers::theme::ThemedText, prelude::*, ui_widgets::observe};
+use jackdaw_feathers::{
+    combobox::{ComboBoxChangeEvent, combobox_with_selected},
+    icons::{self, Icon, IconFont},
+    panel_header::PanelHeader,
+    text_edit::{self, TextEditCommitEvent, TextEditProps, TextEditValue},
+    tokens,
+};
+use jackdaw_jsn::JsnTextureLibrary;
+
 // Re-exports for backwards compatibility — these items now live in asset_browser.
 pub use crate::asset_browser::{ApplyTextureToFaces, ClearTextureFromFaces};
+
+use crate::{
+    EditorEntity,
+    asset_browser::{attach_tooltip, is_image_file_path, is_ktx2_non_2d},
+    project::ProjectRoot,
+};
+
+/// How many recently used textures are remembered.
+const MAX_RECENT: usize = 16;
+
+pub struct TextureBrowserPlugin;
+
+impl Plugin for TextureBrowserPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TextureBrowserState>()
+            .init_resource::<TextureLibrary>()
+            .add_systems(OnEnter(crate::AppState::Editor), load_texture_library)
+            .add_systems(
+                Update,
+                (
+                    scan_textures,
+                    apply_texture_search,
+                    update_folder_tree,
+                    update_tag_filter,
+                    update_recent_strip,
+                    update_texture_grid,
+                    update_texture_details,
+                    update_favorites_toggle,
+                    save_texture_library,
+                )
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            )
+            .add_observer(record_recent_texture)
+            .add_observer(on_texture_tags_commit);
+    }
+}
+
+// ── Library ─────────────────────────────────────────────────────────────────
+
+/// Favorites, recently used textures and tags of the open project, saved to
+/// `.jsn/texture_library.json`.
+#[derive(Resource, Default)]
+pub struct TextureLibrary {
+    pub data: JsnTextureLibrary,
+    dirty: bool,
+}
+
+impl TextureLibrary {
+    pub fn is_favorite(&self, relative: &str) -> bool {
+        self.data.favorites.iter().any(|f| f == relative)
+    }
+
+    pub fn toggle_favorite(&mut self, relative: &str) {
+        if let Some(index) = self.data.favorites.iter().position(|f| f == relative) {
+            self.data.favorites.remove(index);
+        } else {
+            self.data.favorites.push(relative.to_string());
+        }
+        self.dirty = true;
+    }
+
+    /// Move a texture to the front of the recently used list.
+    pub fn push_recent(&mut self, relative: &str) {
+        self.data.recent.retain(|r| r != relative);
+        self.data.recent.insert(0, relative.to_string());
+        self.data.recent.truncate(MAX_RECENT);
+        self.dirty = true;
+    }
+
+    /// User tags of a texture.
+    pub fn tags(&self, relative: &str) -> &[String] {
+        self.data
+            .tags
+            .get(relative)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn set_tags(&mut self, relative: &str, tags: Vec<String>) {
+        if tags.is_empty() {
+            self.data.tags.remove(relative);
+        } else {
+            self.data.tags.insert(relative.to_string(), tags);
+        }
+        self.dirty = true;
+    }
+}
+
+pub fn texture_library_path(project: &ProjectRoot) -> PathBuf {
+    project.jsn_dir().join("texture_library.json")
+}
+
+fn load_texture_library(
+    project: Option<Res<ProjectRoot>>,
+    mut library: ResMut<TextureLibrary>,
+    mut state: ResMut<TextureBrowserState>,
+) {
+    *library = TextureLibrary::default();
+    state.needs_rescan = true;
+    let Some(project) = project else {
+        return;
+    };
+    let path = texture_library_path(&project);
+    let Ok(json) = std::fs::read_to_string(&path) else {
+        return;
+    };
+    match serde_json::from_str(&json) {
+        Ok(data) => library.data = data,
+        Err(err) => warn!("Failed to parse '{}': {err}", path.display()),
+    }
+}
+
+fn save_texture_library(project: Option<Res<ProjectRoot>>, mut library: ResMut<TextureLibrary>) {
+    if !library.dirty {
+        return;
+    }
+    library.dirty = false;
+    let Some(project) = project else {
+        return;
+    };
+    let path = texture_library_path(&project);
+    let result = serde_json::to_string_pretty(&library.data)
+        .map_err(|err| err.to_string())
+        .and_then(|json| {
+            std::fs::create_dir_all(project.jsn_dir())
+                .and_then(|()| std::fs::write(&path, json))
+                .map_err(|err| err.to_string())
+        });
+    if let Err(err) = result {
+        warn!("Failed to save '{}': {err}", path.display());
+    }
+}
+
+/// Path of `path` relative to the assets directory, with `/` separators.
+fn relative_texture_path(project: &ProjectRoot, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(project.assets_dir()).ok()?;
+    Some(relative.to_string_lossy().replace('\\', "/"))
+}
+
+fn record_recent_texture(
+    event: On<ApplyTextureToFaces>,
+    project: Option<Res<ProjectRoot>>,
+    mut library: ResMut<TextureLibrary>,
+    mut state: ResMut<TextureBrowserState>,
+) {
+    let Some(relative) = project
+        .as_deref()
+        .and_then(|project| relative_texture_path(project, Path::new(&event.path)))
+    else {
+        return;
+    };
+    library.push_recent(&relative);
+    state.focused = Some(relative);
+}
+
+// ── Browser state ───────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug)]
+pub struct TextureEntry {
+    /// Path relative to the assets directory, with `/` separators.
+    pub relative: String,
+    pub path: PathBuf,
+    pub image: Handle<Image>,
+}
+
+impl TextureEntry {
+    /// Relative folder the texture is in, empty at the assets root.
+    pub fn folder(&self) -> &str {
+        self.relative
+            .rsplit_once('/')
+            .map(|(folder, _)| folder)
+            .unwrap_or("")
+    }
+
+    pub fn file_name(&self) -> &str {
+        self.relative
+            .rsplit_once('/')
+            .map(|(_, name)| name)
+            .unwrap_or(&self.relative)
+    }
+}
+
+#[derive(Resource)]
+pub struct TextureBrowserState {
+    pub textures: Vec<TextureEntry>,
+    /// Every folder containing textures, relative and sorted.
+    pub folders: Vec<String>,
+    /// Folder shown (with its subfolders), or all textures.
+    pub folder: Option<String>,
+    pub search: String,
+    pub tag: Option<String>,
+    pub favorites_only: bool,
+    /// Texture shown in the details row, by relative path.
+    pub focused: Option<String>,
+    pub needs_rescan: bool,
+}
+
+impl Default for TextureBrowserState {
+    fn default() -> Self {
+        Self {
+            textures: Vec::new(),
+            folders: Vec::new(),
+            folder: None,
+            search: String::new(),
+            tag: None,
+            favorites_only: false,
+            focused: None,
+            needs_rescan: true,
+        }
+    }
+}
+
+impl TextureBrowserState {
+    fn matches(&self, entry: &TextureEntry, library: &TextureLibrary) -> bool {
+        if let Some(folder) = &self.folder {
+            let in_folder = entry.folder() == folder
+                || entry
+                    .folder()
+                    .strip_prefix(folder.as_str())
+                    .is_some_and(|rest| rest.starts_with('/'));
+            if !in_folder {
+                return false;
+            }
+        }
+        if self.favorites_only && !library.is_favorite(&entry.relative) {
+            return false;
+        }
+        if !self.search.is_empty()
+            && !entry
+                .relative
+                .to_lowercase()
+                .contains(&self.search.to_lowercase())
+        {
+            return false;
+        }
+        if let Some(tag) = &self.tag
+            && !texture_tags(entry, library).contains(tag)
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// User tags of a texture plus the names of the folders it's in.
+fn texture_tags(entry: &TextureEntry, library: &TextureLibrary) -> BTreeSet<String> {
+    let mut tags: BTreeSet<String> = entry
+        .folder()
+        .split('/')
+        .filter(|part| !part.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    tags.extend(library.tags(&entry.relative).iter().cloned());
+    tags
+}
+
+// ── Components ──────────────────────────────────────────────────────────────
+
+#[derive(Component)]
+pub struct TextureBrowserPanel;
+
+#[derive(Component)]
+struct TextureBrowserSearch;
+
+#[derive(Component)]
+struct TextureFolderTree;
+
+#[derive(Component)]
+struct TextureTagSlot;
+
+#[derive(Component)]
+struct TextureRecentStrip;
+
+#[derive(Component)]
+struct TextureBrowserGrid;
+
+#[derive(Component)]
+struct TextureDetails;
+
+#[derive(Component)]
+struct FavoritesOnlyToggle;
+
+/// Tags input of the details row, for the texture at this relative path.
+#[derive(Component)]
+struct TextureTagsInput(String);
+
+// ── Systems ─────────────────────────────────────────────────────────────────
+
+fn scan_textures(
+    mut state: ResMut<TextureBrowserState>,
+    project: Option<Res<ProjectRoot>>,
+    asset_server: Res<AssetServer>,
+) {
+    if !state.needs_rescan {
+        return;
+    }
+    state.needs_rescan = false;
+    let Some(project) = project else {
+        return;
+    };
+
+    let mut paths = Vec::new();
+    for dir in project.texture_dirs() {
+        collect_images(&dir, &mut paths);
+    }
+    // Configured texture folders may nest.
+    paths.sort();
+    paths.dedup();
+    let mut textures: Vec<TextureEntry> = paths
+        .into_iter()
+        .filter_map(|path| {
+            let relative = relative_texture_path(&project, &path)?;
+            let image = asset_server.load(path.to_string_lossy().replace('\\', "/"));
+            Some(TextureEntry {
+                relative,
+                path,
+                image,
+            })
+        })
+        .collect();
+    textures.sort_by_key(|entry| entry.relative.to_lowercase());
+
+    let mut folders = BTreeSet::new();
+    for entry in &textures {
+        let mut folder = entry.folder();
+        while !folder.is_empty() {
+            folders.insert(folder.to_string());
+            folder = folder
+                .rsplit_once('/')
+                .map(|(parent, _)| parent)
+                .unwrap_or("");
+        }
+    }
+
+    state.textures = textures;
+    state.folders = folders.into_iter().collect();
+    if state
+        .folder
+        .as_ref()
+        .is_some_and(|folder| !state.folders.contains(folder))
+    {
+        state.folder = None;
+    }
+}
+
+/// Plain 2D images under `dir`, skipping hidden files and folders.
+fn collect_images(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in read_dir.flatten() {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        if path.is_dir() {
+            collect_images(&path, out);
+        } else if is_image_file_path(&path)
+            && !(path
+                .extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case("ktx2"))
+                && is_ktx2_non_2d(&path))
+        {
+            out.push(path);
+        }
+    }
+}
+
+fn apply_texture_search(
+    search_input: Query<&TextEditValue, (With<TextureBrowserSearch>, Changed<TextEditValue>)>,
+    mut state: ResMut<TextureBrowserState>,
+) {
+    for input in &search_input {
+        if state.search != input.0 {
+            state.search = input.0.clone();
+        }
+    }
+}
+
+fn update_folder_tree(
+    mut commands: Commands,
+    state: Res<TextureBrowserState>,
+    tree: Query<(Entity, Option<&Children>), With<TextureFolderTree>>,
+    mut shown: Local<Option<(Vec<String>, Option<String>)>>,
+) {
+    let current = (state.folders.clone(), state.folder.clone());
+    if shown.as_ref() == Some(&current) {
+        return;
+    }
+    let Ok((tree_entity, tree_children)) = tree.single() else {
+        return;
+    };
+    *shown = Some(current);
+    if let Some(children) = tree_children {
+        for child in children.iter() {
+            commands.entity(child).despawn();
+        }
+    }
+
+    let rows = std::iter::once((None, "All textures".to_string(), 0)).chain(
+        state.folders.iter().map(|folder| {
+            let depth = folder.matches('/').count() + 1;
+            let name = folder.rsplit('/').next().unwrap_or(folder).to_string();
+            (Some(folder.clone()), name, depth)
+        }),
+    );
+    for (folder, name, depth) in rows {
+        let selected = folder == state.folder;
+        commands.spawn((
+            Node {
+                padding: UiRect::new(
+                    Val::Px(tokens::SPACING_SM + depth as f32 * tokens::SPACING_MD),
+                    Val::Px(tokens::SPACING_SM),
+                    Val::Px(2.0),
+                    Val::Px(2.0),
+                ),
+                flex_shrink: 0.0,
+                ..Default::default()
+            },
+            BackgroundColor(if selected {
+                tokens::SELECTED_BG
+            } else {
+                Color::NONE
+            }),
+            ChildOf(tree_entity),
+            children![(
+                Text::new(name),
+                TextFont {
+                    font_size: tokens::FONT_SM,
+                    ..Default::default()
+                },
+                ThemedText,
+            )],
+            observe(
+                move |_: On<Pointer<Click>>, mut state: ResMut<TextureBrowserState>| {
+                    state.folder = folder.clone();
+                },
+            ),
+        ));
+    }
+}
+
+fn update_tag_filter(
+    mut commands: Commands,
+    state: Res<TextureBrowserState>,
+    library: Res<TextureLibrary>,
+    slot: Query<(Entity, Option<&Children>), With<TextureTagSlot>>,
+    mut shown: Local<Option<(Vec<String>, Option<String>)>>,
+) {
+    if !state.is_changed() && !library.is_changed() && shown.is_some() {
+        return;
+    }
+    let mut all_tags = BTreeSet::new();
+    for entry in &state.textures {
+        all_tags.extend(texture_tags(entry, &library));
+    }
+    let all_tags: Vec<String> = all_tags.into_iter().collect();
+    let current = (all_tags, state.tag.clone());
+    if shown.as_ref() == Some(&current) {
+        return;
+    }
+    let Ok((slot_entity, slot_children)) = slot.single() else {
+        return;
+    };
+    if let Some(children) = slot_children {
+        for child in children.iter() {
+            commands.entity(child).despawn();
+        }
+    }
+
+    let (tags, selected_tag) = &current;
+    let selected = selected_tag
+        .as_ref()
+        .and_then(|tag| tags.iter().position(|t| t == tag))
+        .map_or(0, |i| i + 1);
+    let items: Vec<String> = std::iter::once("All tags".to_string())
+        .chain(tags.iter().cloned())
+        .collect();
+    let options = tags.clone();
+    commands
+        .spawn((
+            combobox_with_selected(items, selected),
+            ChildOf(slot_entity),
+        ))
+        .observe(
+            move |event: On<ComboBoxChangeEvent>, mut state: ResMut<TextureBrowserState>| {
+                state.tag = event
+                    .selected
+                    .checked_sub(1)
+                    .and_then(|i| options.get(i))
+                    .cloned();
+            },
+        );
+    *shown = Some(current);
+}
+
+fn update_recent_strip(
+    mut commands: Commands,
+    library: Res<TextureLibrary>,
+    project: Option<Res<ProjectRoot>>,
+    asset_server: Res<AssetServer>,
+    strip: Query<(Entity, Option<&Children>), With<TextureRecentStrip>>,
+    mut shown: Local<Option<Vec<String>>>,
+) {
+    if shown.as_ref() == Some(&library.data.recent) {
+        return;
+    }
+    let Some(project) = project else {
+        return;
+    };
+    let Ok((strip_entity, strip_children)) = strip.single() else {
+        return;
+    };
+    *shown = Some(library.data.recent.clone());
+    if let Some(children) = strip_children {
+        for child in children.iter() {
+            commands.entity(child).despawn();
+        }
+    }
+
+    if library.data.recent.is_empty() {
+        commands.spawn((
+            Text::new("Recently used textures appear here"),
+            TextFont {
+                font_size: tokens::FONT_SM,
+                ..Default::default()
+            },
+            TextColor(tokens::TEXT_SECONDARY),
+            ChildOf(strip_entity),
+        ));
+        return;
+    }
+    for relative in &library.data.recent {
+        let path = project.assets_dir().join(relative);
+        let path = path.to_string_lossy().replace('\\', "/");
+        let image: Handle<Image> = asset_server.load(path.clone());
+        let tile = commands
+            .spawn((
+                ImageNode::new(image),
+                Node {
+                    width: Val::Px(32.0),
+                    height: Val::Px(32.0),
+                    flex_shrink: 0.0,
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..Default::default()
+                },
+                BorderColor::all(Color::NONE),
+                ChildOf(strip_entity),
+            ))
+            .id();
+        attach_tooltip(&mut commands, tile, relative.clone());
+        add_tile_hover(&mut commands, tile);
+        commands
+            .entity(tile)
+            .observe(move |click: On<Pointer<Click>>, mut commands: Commands| {
+                if click.event().button == PointerButton::Primary {
+                    commands.trigger(ApplyTextureToFaces { path: path.clone() });
+                }
+            });
+    }
+}
+
+fn update_texture_grid(
+    mut commands: Commands,
+    state: Res<TextureBrowserState>,
+    library: Res<TextureLibrary>,
+    icon_font: Res<IconFont>,
+    grid: Query<(Entity, Option<&Children>), With<TextureBrowserGrid>>,
+) {
+    if !state.is_changed() && !library.is_changed() {
+        return;
+    }
+    let Ok((grid_entity, grid_children)) = grid.single() else {
+        return;
+    };
+    if let Some(children) = grid_children {
+        for child in children.iter() {
+            commands.entity(child).despawn();
+        }
+    }
+
+    for entry in state
+        .textures
+        .iter()
+        .filter(|entry| state.matches(entry, &library))
+    {
+        let focused = state.focused.as_ref() == Some(&entry.relative);
+        let favorite = library.is_favorite(&entry.relative);
+        let tile = commands
+            .spawn((
+                Node {
+                    width: Val::Px(64.0),
+                    height: Val::Px(80.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(2.0)),
+                    border: UiRect::all(Val::Px(1.0)),
+                    border_radius: BorderRadius::all(Val::Px(4.0)),
+                    ..Default::default()
+                },
+                BorderColor::all(Color::NONE),
+                BackgroundColor(if focused {
+                    tokens::SELECTED_BG
+                } else {
+                    Color::NONE
+                }),
+                ChildOf(grid_entity),
+            ))
+            .id();
+
+        commands.spawn((
+            ImageNode::new(entry.image.clone()),
+            Node {
+                width: Val::Px(56.0),
+                height: Val::Px(56.0),
+                ..Default::default()
+            },
+            ChildOf(tile),
+        ));
+
+        // Favorite star in the thumbnail's corner
+        let relative = entry.relative.clone();
+        commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(2.0),
+                right: Val::Px(2.0),
+                ..Default::default()
+            },
+            icons::icon_colored(
+                Icon::Star,
+                tokens::FONT_SM,
+                icon_font.0.clone(),
+                if favorite {
+                    tokens::TEXT_ACCENT
+                } else {
+                    tokens::TEXT_SECONDARY.with_alpha(0.35)
+                },
+            ),
+            ChildOf(tile),
+            observe(
+                move |mut click: On<Pointer<Click>>, mut library: ResMut<TextureLibrary>| {
+                    click.propagate(false);
+                    library.toggle_favorite(&relative);
+                },
+            ),
+        ));
+
+        let file_name = entry.file_name();
+        let is_truncated = file_name.len() > 10;
+        let display_name = if is_truncated {
+            format!("{}...", &file_name[..8])
+        } else {
+            file_name.to_string()
+        };
+        let name_entity = commands
+            .spawn((
+                Text::new(display_name),
+                TextFont {
+                    font_size: 9.0,
+                    ..Default::default()
+                },
+                TextColor(tokens::TEXT_SECONDARY),
+                Node {
+                    max_width: Val::Px(60.0),
+                    overflow: Overflow::clip(),
+                    ..Default::default()
+                },
+                ChildOf(tile),
+            ))
+            .id();
+        attach_tooltip(&mut commands, name_entity, entry.relative.clone());
+
+        add_tile_hover(&mut commands, tile);
+
+        // Left click applies, right click only shows the texture's details
+        let relative = entry.relative.clone();
+        let path = entry.path.to_string_lossy().replace('\\', "/");
+        commands.entity(tile).observe(
+            move |click: On<Pointer<Click>>,
+                  mut commands: Commands,
+                  mut state: ResMut<TextureBrowserState>| {
+                match click.event().button {
+                    PointerButton::Primary => {
+                        commands.trigger(ApplyTextureToFaces { path: path.clone() });
+                    }
+                    PointerButton::Secondary => {
+                        state.focused = Some(relative.clone());
+                    }
+                    PointerButton::Middle => {}
+                }
+            },
+        );
+    }
+}
+
+fn add_tile_hover(commands: &mut Commands, tile: Entity) {
+    commands.entity(tile).observe(
+        |hover: On<Pointer<Over>>, mut borders: Query<&mut BorderColor>| {
+            if let Ok(mut border) = borders.get_mut(hover.event_target()) {
+                *border = BorderColor::all(tokens::SELECTED_BORDER);
+            }
+        },
+    );
+    commands.entity(tile).observe(
+        |out: On<Pointer<Out>>, mut borders: Query<&mut BorderColor>| {
+            if let Ok(mut border) = borders.get_mut(out.event_target()) {
+                *border = BorderColor::all(Color::NONE);
+            }
+        },
+    );
+}
+
+/// Name, folder tags and editable user tags of the focused texture.
+fn update_texture_details(
+    mut commands: Commands,
+    state: Res<TextureBrowserState>,
+    library: Res<TextureLibrary>,
+    details: Query<(Entity, Option<&Children>), With<TextureDetails>>,
+    mut shown: Local<Option<(Option<String>, Vec<String>)>>,
+) {
+    let current = (
+        state.focused.clone(),
+        state
+            .focused
+            .as_ref()
+            .map(|relative| library.tags(relative).to_vec())
+            .unwrap_or_default(),
+    );
+    if shown.as_ref() == Some(&current) {
+        return;
+    }
+    let Ok((details_entity, details_children)) = details.single() else {
+        return;
+    };
+    if let Some(children) = details_children {
+        for child in children.iter() {
+            commands.entity(child).despawn();
+        }
+    }
+
+    let (focused, tags) = &current;
+    let Some(relative) = focused else {
+        commands.spawn((
+            Text::new("Right-click a texture to edit its tags"),
+            TextFont {
+                font_size: tokens::FONT_SM,
+                ..Default::default()
+            },
+            TextColor(tokens::TEXT_SECONDARY),
+            ChildOf(details_entity),
+        ));
+        *shown = Some(current);
+        return;
+    };
+
+    commands.spawn((
+        Text::new(relative.clone()),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        ThemedText,
+        Node {
+            flex_shrink: 1.0,
+            overflow: Overflow::clip(),
+            ..Default::default()
+        },
+        ChildOf(details_entity),
+    ));
+    commands.spawn((
+        TextureTagsInput(relative.clone()),
+        Node {
+            flex_grow: 1.0,
+            ..Default::default()
+        },
+        ChildOf(details_entity),
+        children![text_edit::text_edit(
+            TextEditProps::default()
+                .with_placeholder("Tags, comma separated")
+                .with_default_value(tags.join(", "))
+                .allow_empty()
+        )],
+    ));
+    *shown = Some(current);
+}
+
+fn on_texture_tags_commit(
+    event: On<TextEditCommitEvent>,
+    inputs: Query<&TextureTagsInput>,
+    parents: Query<&ChildOf>,
+    mut library: ResMut<TextureLibrary>,
+) {
+    let Some(relative) = std::iter::once(event.entity)
+        .chain(parents.iter_ancestors(event.entity))
+        .find_map(|entity| inputs.get(entity).ok())
+        .map(|input| input.0.clone())
+    else {
+        return;
+    };
+    let mut tags: Vec<String> = event
+        .text
+        .split(',')
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    tags.dedup();
+    if library.tags(&relative) != tags.as_slice() {
+        library.set_tags(&relative, tags);
+    }
+}
+
+fn update_favorites_toggle(
+    state: Res<TextureBrowserState>,
+    mut toggles: Query<&mut TextColor, With<FavoritesOnlyToggle>>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+    for mut color in &mut toggles {
+        color.0 = if state.favorites_only {
+            tokens::TEXT_ACCENT
+        } else {
+            tokens::TEXT_SECONDARY
+        };
+    }
+}
+
+// ── Panel ───────────────────────────────────────────────────────────────────
+
+pub fn texture_browser_panel(icon_font: Handle<Font>) -> impl Bundle {
+    (
+        TextureBrowserPanel,
+        EditorEntity,
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        },
+        BackgroundColor(tokens::PANEL_BG),
+        children![
+            // Header
+            (
+                PanelHeader,
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::SpaceBetween,
+                    width: Val::Percent(100.0),
+                    height: Val::Px(tokens::ROW_HEIGHT),
+                    padding: UiRect::horizontal(Val::Px(tokens::SPACING_MD)),
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+                BackgroundColor(tokens::PANEL_HEADER_BG),
+                children![
+                    (
+                        Text::new("Textures"),
+                        TextFont {
+                            font_size: tokens::FONT_MD,
+                            ..Default::default()
+                        },
+                        ThemedText,
+                    ),
+                    (
+                        Node {
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(tokens::SPACING_XS),
+                            ..Default::default()
+                        },
+                        children![
+                            favorites_only_button(icon_font.clone()),
+                            rescan_button(icon_font),
+                        ],
+                    ),
+                ],
+            ),
+            // Search + tag filter
+            (
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(tokens::SPACING_SM),
+                    padding: UiRect::axes(Val::Px(tokens::SPACING_SM), Val::Px(tokens::SPACING_XS)),
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+                children![
+                    (
+                        Node {
+                            flex_grow: 1.0,
+                            ..Default::default()
+                        },
+                        children![(
+                            TextureBrowserSearch,
+                            text_edit::text_edit(
+                                TextEditProps::default()
+                                    .with_placeholder("Search textures")
+                                    .allow_empty()
+                            )
+                        )],
+                    ),
+                    (
+                        TextureTagSlot,
+                        EditorEntity,
+                        Node {
+                            width: Val::Px(120.0),
+                            ..Default::default()
+                        },
+                    ),
+                ],
+            ),
+            // Recently used strip
+            (
+                TextureRecentStrip,
+                EditorEntity,
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(tokens::SPACING_XS),
+                    width: Val::Percent(100.0),
+                    min_height: Val::Px(40.0),
+                    padding: UiRect::axes(Val::Px(tokens::SPACING_SM), Val::Px(tokens::SPACING_XS)),
+                    overflow: Overflow::scroll_x(),
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+                BackgroundColor(tokens::TOOLBAR_BG),
+            ),
+            // Main row: folder tree + grid
+            (
+                EditorEntity,
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    width: Val::Percent(100.0),
+                    flex_grow: 1.0,
+                    min_height: Val::Px(0.0),
+                    ..Default::default()
+                },
+                children![
+                    (
+                        TextureFolderTree,
+                        EditorEntity,
+                        Node {
+                            flex_direction: FlexDirection::Column,
+                            width: Val::Px(140.0),
+                            flex_shrink: 0.0,
+                            padding: UiRect::vertical(Val::Px(tokens::SPACING_XS)),
+                            border: UiRect::right(Val::Px(1.0)),
+                            overflow: Overflow::scroll_y(),
+                            ..Default::default()
+                        },
+                        BorderColor::all(tokens::PANEL_HEADER_BG),
+                    ),
+                    (
+                        TextureBrowserGrid,
+                        EditorEntity,
+                        Node {
+                            flex_direction: FlexDirection::Row,
+                            flex_wrap: FlexWrap::Wrap,
+                            align_content: AlignContent::FlexStart,
+                            flex_grow: 1.0,
+                            min_width: Val::Px(0.0),
+                            min_height: Val::Px(0.0),
+                            overflow: Overflow::scroll_y(),
+                            padding: UiRect::all(Val::Px(tokens::SPACING_SM)),
+                            row_gap: Val::Px(tokens::SPACING_XS),
+                            column_gap: Val::Px(tokens::SPACING_XS),
+                            ..Default::default()
+                        },
+                    ),
+                ],
+            ),
+            // Details of the focused texture
+            (
+                TextureDetails,
+                EditorEntity,
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(tokens::SPACING_SM),
+                    width: Val::Percent(100.0),
+                    height: Val::Px(tokens::ROW_HEIGHT),
+                    padding: UiRect::horizontal(Val::Px(tokens::SPACING_SM)),
+                    border: UiRect::top(Val::Px(1.0)),
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+                BorderColor::all(tokens::PANEL_HEADER_BG),
+            ),
+        ],
+    )
+}
+
+fn favorites_only_button(icon_font: Handle<Font>) -> impl Bundle {
+    (
+        FavoritesOnlyToggle,
+        Node {
+            padding: UiRect::all(Val::Px(tokens::SPACING_XS)),
+            border_radius: BorderRadius::all(Val::Px(tokens::BORDER_RADIUS_SM)),
+            ..Default::default()
+        },
+        icons::icon_colored(
+            Icon::Star,
+            tokens::FONT_MD,
+            icon_font,
+            tokens::TEXT_SECONDARY,
+        ),
+        observe(
+            |_: On<Pointer<Click>>, mut state: ResMut<TextureBrowserState>| {
+                state.favorites_only = !state.favorites_only;
+            },
+        ),
+    )
+}
+
+fn rescan_button(icon_font: Handle<Font>) -> impl Bundle {
+    (
+        Node {
+            padding: UiRect::all(Val::Px(tokens::SPACING_XS)),
+            border_radius: BorderRadius::all(Val::Px(tokens::BORDER_RADIUS_SM)),
+            ..Default::default()
+        },
+        icons::icon_colored(
+            Icon::RefreshCw,
+            tokens::FONT_MD,
+            icon_font,
+            tokens::TEXT_SECONDARY,
+        ),
+        observe(
+            |_: On<Pointer<Click>>, mut state: ResMut<TextureBrowserState>| {
+                state.needs_rescan = true;
+            },
+        ),
+    )
+}
@@ -0,0 +1,135 @@
+//! Reloads brush textures when their image files change on disk, and refreshes
+//! every material that samples them so faces update without being restamped.
+
+use std::{collections::HashSet, path::PathBuf, time::Duration};
+
+use bevy::prelude::*;
+use jackdaw_feathers::{
+    icons::{EditorFont, IconFont},
+    toast::{ToastVariant, toast},
+};
+
+use crate::{
+    EditorEntity,
+    asset_browser::is_image_file_path,
+    project::ProjectRoot,
+    project_watcher::{ProjectFilesChanged, resolve_asset_file},
+};
+
+pub struct TextureReloadPlugin;
+
+impl Plugin for TextureReloadPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingTextureReloads>().add_systems(
+            Update,
+            (
+                reload_changed_textures,
+                refresh_materials_for_reloaded_images,
+            )
+                .chain()
+                .after(crate::project_watcher::publish_file_changes)
+                .run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+#[derive(Resource, Default)]
+struct PendingTextureReloads {
+    /// Images asked to reload, waiting for the new data to arrive.
+    reloading: HashSet<AssetId<Image>>,
+}
+
+fn reload_changed_textures(
+    mut changes: MessageReader<ProjectFilesChanged>,
+    project: Option<Res<ProjectRoot>>,
+    mut pending: ResMut<PendingTextureReloads>,
+    asset_server: Res<AssetServer>,
+    images: Res<Assets<Image>>,
+) {
+    let changed: HashSet<&PathBuf> = changes
+        .read()
+        .flat_map(|change| &change.modified)
+        .filter(|path| is_image_file_path(path))
+        .collect();
+    let Some(project) = project else {
+        return;
+    };
+    if changed.is_empty() {
+        return;
+    }
+
+    let root = project.assets_dir();
+    for id in images.ids() {
+        let Some(path) = asset_server.get_path(id) else {
+            continue;
+        };
+        if changed.contains(&resolve_asset_file(&root, &path)) {
+            asset_server.reload(path.into_owned());
+            pending.reloading.insert(id);
+        }
+    }
+}
+
+/// Touch every material sampling a reloaded image so its bind group is rebuilt,
+/// then report what was refreshed.
+fn refresh_materials_for_reloaded_images(
+    mut commands: Commands,
+    mut events: MessageReader<AssetEvent<Image>>,
+    mut pending: ResMut<PendingTextureReloads>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+    editor_font: Res<EditorFont>,
+    icon_font: Res<IconFont>,
+) {
+    for event in events.read() {
+        let (AssetEvent::Modified { id } | AssetEvent::LoadedWithDependencies { id }) = *event
+        else {
+            continue;
+        };
+        if !pending.reloading.remove(&id) {
+            continue;
+        }
+
+        let uses_image = |m: &StandardMaterial| {
+            [
+                &m.base_color_texture,
+                &m.normal_map_texture,
+                &m.metallic_roughness_texture,
+                &m.emissive_texture,
+                &m.occlusion_texture,
+            ]
+            .into_iter()
+            .flatten()
+            .any(|t| t.id() == id)
+        };
+        let affected: Vec<AssetId<StandardMaterial>> = materials
+            .iter()
+            .filter(|(_, m)| uses_image(m))
+            .map(|(material_id, _)| material_id)
+            .collect();
+        for &material_id in &affected {
+            // Mutable access marks the material modified.
+            let _ = materials.get_mut(material_id);
+        }
+
+        let name = asset_server
+            .get_path(id)
+            .and_then(|p| {
+                p.path()
+                    .file_name()
+                    .map(|f| f.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| "texture".to_string());
+        info!("Reloaded {name} ({} materials)", affected.len());
+        commands.spawn((
+            EditorEntity,
+            toast(
+                ToastVariant::Info,
+                format!("Reloaded {name}"),
+                Duration::from_secs(2),
+                &editor_font.0,
+                &icon_font.0,
+            ),
+        ));
+    }
+}
@@ -0,0 +1,469 @@
+//! Timeline panel: keyframe the transform of selected entities over time and scrub or
+//! play the result in the viewport. Keys are stored in a [`TransformAnimation`] on the
+//! entity, so clips are saved with the scene.
+
+use bevy::{
+    ecs::entity::EntityHashMap,
+    picking::events::{Drag, Press},
+    prelude::*,
+    ui::UiGlobalTransform,
+    ui_widgets::observe,
+};
+use jackdaw_feathers::{
+    button::{self, ButtonProps},
+    panel_header,
+    text_edit::{self, TextEditCommitEvent, TextEditProps},
+    tokens,
+};
+use jackdaw_jsn::{TransformAnimation, TransformKeyframe};
+
+use crate::{
+    EditorEntity,
+    commands::{CommandGroup, CommandHistory, EditorCommand, remap_entity},
+    selection::Selection,
+};
+
+const TRACK_HEIGHT: f32 = 28.0;
+const KEY_MARKER_SIZE: f32 = 8.0;
+/// Keys closer than this to the playhead count as being at it, in seconds.
+const KEY_TOLERANCE: f32 = 1.0 / 120.0;
+
+pub struct TimelinePlugin;
+
+impl Plugin for TimelinePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Timeline>()
+            .add_observer(on_length_commit)
+            .add_systems(
+                Update,
+                (
+                    advance_playback,
+                    apply_animation_pose,
+                    update_playhead,
+                    update_key_markers,
+                )
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+/// Playhead state shared by every animated entity in the scene.
+#[derive(Resource)]
+pub struct Timeline {
+    /// Playhead position in seconds.
+    pub time: f32,
+    /// Length of the visible range, and of clips created by keying.
+    pub length: f32,
+    pub playing: bool,
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self {
+            time: 0.0,
+            length: TransformAnimation::default().duration,
+            playing: false,
+        }
+    }
+}
+
+/// Adds, replaces or removes an entity's [`TransformAnimation`].
+pub struct SetTransformAnimation {
+    pub entity: Entity,
+    pub old: Option<TransformAnimation>,
+    pub new: Option<TransformAnimation>,
+}
+
+impl SetTransformAnimation {
+    fn apply(&self, world: &mut World, value: &Option<TransformAnimation>) {
+        let Ok(mut entity) = world.get_entity_mut(self.entity) else {
+            return;
+        };
+        match value {
+            Some(animation) => {
+                entity.insert(animation.clone());
+            }
+            None => {
+                entity.remove::<TransformAnimation>();
+            }
+        }
+    }
+}
+
+impl EditorCommand for SetTransformAnimation {
+    fn execute(&self, world: &mut World) {
+        self.apply(world, &self.new);
+    }
+
+    fn undo(&self, world: &mut World) {
+        self.apply(world, &self.old);
+    }
+
+    fn description(&self) -> &str {
+        "Edit keyframes"
+    }
+
+    fn memory_size(&self) -> usize {
+        let keys: usize = [&self.old, &self.new]
+            .into_iter()
+            .flatten()
+            .map(|animation| animation.keys.capacity())
+            .sum();
+        std::mem::size_of::<Self>() + keys * std::mem::size_of::<TransformKeyframe>()
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+    }
+}
+
+#[derive(Component)]
+struct TimelineTrack;
+
+#[derive(Component)]
+struct TimelinePlayhead;
+
+#[derive(Component)]
+struct TimelineKeyMarker;
+
+#[derive(Component)]
+struct TimelineReadout;
+
+#[derive(Component)]
+struct TimelineLengthField;
+
+pub fn timeline_panel() -> impl Bundle {
+    (
+        EditorEntity,
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        },
+        BackgroundColor(tokens::PANEL_BG),
+        children![
+            panel_header::panel_header("Timeline"),
+            (
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(tokens::SPACING_SM)),
+                    row_gap: Val::Px(tokens::SPACING_SM),
+                    ..Default::default()
+                },
+                children![
+                    (
+                        Node {
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(tokens::SPACING_XS),
+                            ..Default::default()
+                        },
+                        children![
+                            timeline_button("Play", |world| {
+                                let mut timeline = world.resource_mut::<Timeline>();
+                                timeline.playing = !timeline.playing;
+                            }),
+                            timeline_button("Stop", |world| {
+                                let mut timeline = world.resource_mut::<Timeline>();
+                                timeline.playing = false;
+                                timeline.time = 0.0;
+                            }),
+                            timeline_button("Key", key_selected),
+                            timeline_button("Delete Key", delete_selected_keys),
+                            (
+                                TimelineReadout,
+                                Text::new(""),
+                                TextFont {
+                                    font_size: tokens::FONT_SM,
+                                    ..Default::default()
+                                },
+                                TextColor(tokens::TEXT_SECONDARY),
+                                Node {
+                                    flex_grow: 1.0,
+                                    ..Default::default()
+                                },
+                            ),
+                            (
+                                Node {
+                                    width: Val::Px(110.0),
+                                    ..Default::default()
+                                },
+                                children![(
+                                    TimelineLengthField,
+                                    text_edit::text_edit(
+                                        TextEditProps::default()
+                                            .with_label("Length")
+                                            .numeric_f32()
+                                            .with_min(0.1)
+                                            .with_default_value(
+                                                Timeline::default().length.to_string()
+                                            ),
+                                    ),
+                                )],
+                            ),
+                        ],
+                    ),
+                    (
+                        TimelineTrack,
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Px(TRACK_HEIGHT),
+                            border: UiRect::all(Val::Px(1.0)),
+                            border_radius: BorderRadius::all(Val::Px(tokens::BORDER_RADIUS_SM)),
+                            ..Default::default()
+                        },
+                        BackgroundColor(tokens::INPUT_BG),
+                        BorderColor::all(tokens::BORDER_SUBTLE),
+                        observe(on_track_press),
+                        observe(on_track_drag),
+                        children![(
+                            TimelinePlayhead,
+                            Node {
+                                position_type: PositionType::Absolute,
+                                width: Val::Px(2.0),
+                                height: Val::Percent(100.0),
+                                margin: UiRect::left(Val::Px(-1.0)),
+                                ..Default::default()
+                            },
+                            BackgroundColor(tokens::TEXT_ACCENT),
+                            ZIndex(1),
+                            Pickable::IGNORE,
+                        )],
+                    ),
+                ],
+            ),
+        ],
+    )
+}
+
+fn timeline_button(label: &str, action: fn(&mut World)) -> impl Bundle {
+    (
+        button::button(ButtonProps::new(label)),
+        observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
+            commands.queue(action);
+        }),
+    )
+}
+
+fn seek_to_pointer(
+    track: Entity,
+    position: Vec2,
+    tracks: &Query<(&ComputedNode, &UiGlobalTransform), With<TimelineTrack>>,
+    timeline: &mut Timeline,
+) {
+    let Ok((computed, transform)) = tracks.get(track) else {
+        return;
+    };
+    let Some(normalized) =
+        computed.normalize_point(*transform, position / computed.inverse_scale_factor)
+    else {
+        return;
+    };
+    timeline.time = ((normalized.x + 0.5) * timeline.length).clamp(0.0, timeline.length);
+    timeline.playing = false;
+}
+
+fn on_track_press(
+    event: On<Pointer<Press>>,
+    tracks: Query<(&ComputedNode, &UiGlobalTransform), With<TimelineTrack>>,
+    mut timeline: ResMut<Timeline>,
+) {
+    seek_to_pointer(
+        event.event_target(),
+        event.pointer_location.position,
+        &tracks,
+        &mut timeline,
+    );
+}
+
+fn on_track_drag(
+    mut event: On<Pointer<Drag>>,
+    tracks: Query<(&ComputedNode, &UiGlobalTransform), With<TimelineTrack>>,
+    mut timeline: ResMut<Timeline>,
+) {
+    event.propagate(false);
+    seek_to_pointer(
+        event.event_target(),
+        event.pointer_location.position,
+        &tracks,
+        &mut timeline,
+    );
+}
+
+fn on_length_commit(
+    event: On<TextEditCommitEvent>,
+    fields: Query<(), With<TimelineLengthField>>,
+    child_of_query: Query<&ChildOf>,
+    mut timeline: ResMut<Timeline>,
+) {
+    // Walk up from committed entity to find the field
+    let mut current = event.entity;
+    for _ in 0..4 {
+        let Ok(child_of) = child_of_query.get(current) else {
+            return;
+        };
+        current = child_of.parent();
+        if fields.contains(current) {
+            if let Ok(length) = event.text.trim().parse::<f32>() {
+                timeline.length = length.max(0.1);
+                timeline.time = timeline.time.min(timeline.length);
+            }
+            return;
+        }
+    }
+}
+
+fn advance_playback(time: Res<Time>, mut timeline: ResMut<Timeline>) {
+    if !timeline.playing {
+        return;
+    }
+    timeline.time = (timeline.time + time.delta_secs()).rem_euclid(timeline.length);
+}
+
+/// Pose every animated entity at the playhead whenever it moves.
+fn apply_animation_pose(
+    timeline: Res<Timeline>,
+    mut animated: Query<(&TransformAnimation, &mut Transform)>,
+) {
+    if !timeline.is_changed() || timeline.is_added() {
+        return;
+    }
+    for (animation, mut transform) in &mut animated {
+        if let Some(pose) = animation.sample(timeline.time)
+            && *transform != pose
+        {
+            *transform = pose;
+        }
+    }
+}
+
+fn update_playhead(
+    timeline: Res<Timeline>,
+    mut playheads: Query<&mut Node, With<TimelinePlayhead>>,
+    mut readouts: Query<&mut Text, With<TimelineReadout>>,
+) {
+    if !timeline.is_changed() {
+        return;
+    }
+    let fraction = (timeline.time / timeline.length).clamp(0.0, 1.0);
+    for mut node in &mut playheads {
+        node.left = Val::Percent(fraction * 100.0);
+    }
+    for mut text in &mut readouts {
+        text.0 = format!("{:.2}s / {:.2}s", timeline.time, timeline.length);
+    }
+}
+
+/// Diamond markers on the track for the keys of the primary selection.
+fn update_key_markers(
+    mut commands: Commands,
+    timeline: Res<Timeline>,
+    selection: Res<Selection>,
+    animations: Query<&TransformAnimation>,
+    tracks: Query<Entity, With<TimelineTrack>>,
+    markers: Query<Entity, With<TimelineKeyMarker>>,
+    mut shown: Local<Option<(Vec<f32>, f32)>>,
+) {
+    let times: Vec<f32> = selection
+        .primary()
+        .and_then(|entity| animations.get(entity).ok())
+        .map(|animation| animation.keys.iter().map(|k| k.time).collect())
+        .unwrap_or_default();
+    let state = (times, timeline.length);
+    if shown.as_ref() == Some(&state) {
+        return;
+    }
+    let Ok(track) = tracks.single() else {
+        return;
+    };
+    for marker in &markers {
+        commands.entity(marker).despawn();
+    }
+    for &time in &state.0 {
+        commands.spawn((
+            TimelineKeyMarker,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent((time / timeline.length).clamp(0.0, 1.0) * 100.0),
+                top: Val::Percent(50.0),
+                width: Val::Px(KEY_MARKER_SIZE),
+                height: Val::Px(KEY_MARKER_SIZE),
+                margin: UiRect::all(Val::Px(-KEY_MARKER_SIZE / 2.0)),
+                ..Default::default()
+            },
+            UiTransform::from_rotation(Rot2::degrees(45.0)),
+            BackgroundColor(tokens::TEXT_PRIMARY),
+            Pickable::IGNORE,
+            ChildOf(track),
+        ));
+    }
+    *shown = Some(state);
+}
+
+/// Record a key at the playhead for every selected entity's current transform.
+pub fn key_selected(world: &mut World) {
+    let (time, length) = {
+        let timeline = world.resource::<Timeline>();
+        (timeline.time, timeline.length)
+    };
+    edit_selected_animations(world, |entity, animation| {
+        let transform = *entity.get::<Transform>()?;
+        let mut animation = animation.cloned().unwrap_or_default();
+        animation.duration = length;
+        animation.set_key(time, transform);
+        Some(Some(animation))
+    });
+}
+
+/// Remove the selected entities' keys at the playhead. An animation losing its last key
+/// is removed from the entity.
+pub fn delete_selected_keys(world: &mut World) {
+    let time = world.resource::<Timeline>().time;
+    edit_selected_animations(world, |_, animation| {
+        let mut animation = animation?.clone();
+        let count = animation.keys.len();
+        animation
+            .keys
+            .retain(|k| (k.time - time).abs() > KEY_TOLERANCE);
+        if animation.keys.len() == count {
+            return None;
+        }
+        Some((!animation.keys.is_empty()).then_some(animation))
+    });
+}
+
+/// Apply `edit` to each selected entity's animation as one undo step. `edit` returns
+/// `None` to leave the entity alone, or the animation it should end up with.
+fn edit_selected_animations(
+    world: &mut World,
+    edit: impl Fn(EntityRef, Option<&TransformAnimation>) -> Option<Option<TransformAnimation>>,
+) {
+    let selected = world.resource::<Selection>().entities.clone();
+    let mut commands: Vec<Box<dyn EditorCommand>> = Vec::new();
+    for entity in selected {
+        let Ok(entity_ref) = world.get_entity(entity) else {
+            continue;
+        };
+        let old = entity_ref.get::<TransformAnimation>().cloned();
+        let Some(new) = edit(entity_ref, old.as_ref()) else {
+            continue;
+        };
+        commands.push(Box::new(SetTransformAnimation { entity, old, new }));
+    }
+    if commands.is_empty() {
+        return;
+    }
+    let command: Box<dyn EditorCommand> = if commands.len() == 1 {
+        commands.pop().unwrap()
+    } else {
+        Box::new(CommandGroup {
+            commands,
+            label: "Edit keyframes".to_string(),
+        })
+    };
+    world.resource_scope(|world, mut history: Mut<CommandHistory>| {
+        history.execute(command, world);
+    });
+}
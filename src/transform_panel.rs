@@ -0,0 +1,473 @@
+//! N-key transform panel over the viewport: exact location, rotation and scale of the
+//! active entity in local or world space, plus its dimensions, which scale the object to
+//! an exact size.
+
+use bevy::{input_focus::InputFocus, prelude::*};
+use jackdaw_feathers::{
+    button::{ButtonProps, button},
+    text_edit::{
+        self, TextEditCommitEvent, TextEditDragging, TextEditPrefix, TextEditProps, TextEditValue,
+        TextEditVariant, TextInputQueue, set_text_input_value,
+    },
+    tokens,
+};
+
+use crate::{
+    EditorEntity,
+    brush::{BrushFaceEntity, BrushMeshCache},
+    commands::{CommandHistory, SetTransform},
+    inspector::find_text_edit_entities,
+    selection::Selection,
+    viewport::SceneViewport,
+    viewport_overlays,
+};
+
+const PANEL_WIDTH: f32 = 240.0;
+
+pub struct TransformPanelPlugin;
+
+impl Plugin for TransformPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TransformPanelState>()
+            .add_observer(on_transform_field_commit)
+            .add_systems(
+                Update,
+                (
+                    toggle_transform_panel,
+                    rebuild_transform_panel,
+                    refresh_transform_fields,
+                )
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct TransformPanelState {
+    pub open: bool,
+    /// Show and edit location, rotation and scale in world space instead of the parent's.
+    pub world_space: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum TransformField {
+    Location(usize),
+    /// Euler XYZ, in degrees.
+    Rotation(usize),
+    Scale(usize),
+    /// Size of the object's bounds along its own axes.
+    Dimension(usize),
+}
+
+#[derive(Component)]
+struct TransformPanel;
+
+/// A numeric field editing one component of an entity's transform. The inspector's
+/// world-space Transform fields use it too.
+#[derive(Component)]
+pub(crate) struct TransformFieldBinding {
+    pub(crate) entity: Entity,
+    pub(crate) field: TransformField,
+    pub(crate) world_space: bool,
+}
+
+fn toggle_transform_panel(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input_focus: Res<InputFocus>,
+    mut state: ResMut<TransformPanelState>,
+) {
+    let modifier = keyboard.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::AltLeft,
+        KeyCode::AltRight,
+        KeyCode::ShiftLeft,
+        KeyCode::ShiftRight,
+    ]);
+    if input_focus.0.is_none() && !modifier && keyboard.just_pressed(KeyCode::KeyN) {
+        state.open = !state.open;
+    }
+}
+
+/// The entity's transform relative to the world, from its parent's current transform.
+pub(crate) fn world_transform(world: &World, entity: Entity) -> Option<Transform> {
+    let local = *world.get::<Transform>(entity)?;
+    Some(match parent_global(world, entity) {
+        Some(parent) => parent.mul_transform(local).compute_transform(),
+        None => local,
+    })
+}
+
+/// The local transform putting `entity` at `world_transform`.
+pub(crate) fn local_from_world(
+    world: &World,
+    entity: Entity,
+    world_transform: Transform,
+) -> Transform {
+    match parent_global(world, entity) {
+        Some(parent) => GlobalTransform::from(world_transform).reparented_to(&parent),
+        None => world_transform,
+    }
+}
+
+fn parent_global(world: &World, entity: Entity) -> Option<GlobalTransform> {
+    let parent = world.get::<ChildOf>(entity)?.parent();
+    world.get::<GlobalTransform>(parent).copied()
+}
+
+/// Size of what the entity draws, in its own unscaled space.
+fn local_size(world: &World, entity: Entity) -> Option<Vec3> {
+    let to_local = world.get::<GlobalTransform>(entity)?.affine().inverse();
+    let mut points = Vec::new();
+    if let Some(cache) = world.get::<BrushMeshCache>(entity) {
+        points.extend(cache.vertices.iter().copied());
+    } else {
+        collect_local_mesh_points(world, entity, to_local, &mut points);
+    }
+    if points.is_empty() {
+        return None;
+    }
+    let (min, max) = viewport_overlays::aabb_from_points(&points);
+    Some(max - min)
+}
+
+fn collect_local_mesh_points(
+    world: &World,
+    entity: Entity,
+    to_local: bevy::math::Affine3A,
+    out: &mut Vec<Vec3>,
+) {
+    if world.get::<BrushFaceEntity>(entity).is_none()
+        && let (Some(mesh3d), Some(global)) = (
+            world.get::<Mesh3d>(entity),
+            world.get::<GlobalTransform>(entity),
+        )
+        && let Some(positions) = world
+            .resource::<Assets<Mesh>>()
+            .get(&mesh3d.0)
+            .and_then(|mesh| mesh.attribute(Mesh::ATTRIBUTE_POSITION))
+            .and_then(|attr| attr.as_float3())
+    {
+        out.extend(
+            positions
+                .iter()
+                .map(|p| to_local.transform_point3(global.transform_point(Vec3::from(*p)))),
+        );
+    }
+    if let Some(children) = world.get::<Children>(entity) {
+        for child in children.iter() {
+            collect_local_mesh_points(world, child, to_local, out);
+        }
+    }
+}
+
+fn field_value(
+    world: &World,
+    entity: Entity,
+    field: TransformField,
+    world_space: bool,
+) -> Option<f32> {
+    let transform = if world_space {
+        world_transform(world, entity)?
+    } else {
+        *world.get::<Transform>(entity)?
+    };
+    Some(match field {
+        TransformField::Location(axis) => transform.translation[axis],
+        TransformField::Rotation(axis) => {
+            let (x, y, z) = transform.rotation.to_euler(EulerRot::XYZ);
+            [x, y, z][axis].to_degrees()
+        }
+        TransformField::Scale(axis) => transform.scale[axis],
+        TransformField::Dimension(axis) => {
+            let scale = world.get::<Transform>(entity)?.scale;
+            local_size(world, entity)?[axis] * scale[axis].abs()
+        }
+    })
+}
+
+fn set_field(
+    world: &mut World,
+    entity: Entity,
+    field: TransformField,
+    world_space: bool,
+    value: f32,
+) {
+    let Some(&old_transform) = world.get::<Transform>(entity) else {
+        return;
+    };
+    let mut edited = if world_space {
+        world_transform(world, entity).unwrap_or(old_transform)
+    } else {
+        old_transform
+    };
+    match field {
+        TransformField::Location(axis) => edited.translation[axis] = value,
+        TransformField::Rotation(axis) => {
+            let (x, y, z) = edited.rotation.to_euler(EulerRot::XYZ);
+            let mut angles = [x, y, z];
+            angles[axis] = value.to_radians();
+            edited.rotation = Quat::from_euler(EulerRot::XYZ, angles[0], angles[1], angles[2]);
+        }
+        TransformField::Scale(axis) => edited.scale[axis] = value,
+        TransformField::Dimension(axis) => {
+            // Dimensions always scale along the object's own axes
+            let Some(size) = local_size(world, entity) else {
+                return;
+            };
+            if size[axis] <= f32::EPSILON || value <= 0.0 {
+                return;
+            }
+            let mut new_transform = old_transform;
+            new_transform.scale[axis] = value / size[axis] * old_transform.scale[axis].signum();
+            commit_transform(world, entity, old_transform, new_transform);
+            return;
+        }
+    }
+    let new_transform = if world_space {
+        local_from_world(world, entity, edited)
+    } else {
+        edited
+    };
+    commit_transform(world, entity, old_transform, new_transform);
+}
+
+fn commit_transform(
+    world: &mut World,
+    entity: Entity,
+    old_transform: Transform,
+    new_transform: Transform,
+) {
+    if old_transform == new_transform {
+        return;
+    }
+    world.resource_scope(|world, mut history: Mut<CommandHistory>| {
+        history.execute(
+            Box::new(SetTransform {
+                entity,
+                old_transform,
+                new_transform,
+            }),
+            world,
+        );
+    });
+}
+
+/// Rebuild the panel when it opens or closes, the active entity changes or the space flips.
+fn rebuild_transform_panel(
+    world: &mut World,
+    mut shown: Local<Option<(bool, Option<Entity>, bool)>>,
+) {
+    let state = world.resource::<TransformPanelState>();
+    let current = (
+        state.open,
+        world.resource::<Selection>().primary(),
+        state.world_space,
+    );
+    if *shown == Some(current) {
+        return;
+    }
+    *shown = Some(current);
+
+    let old: Vec<Entity> = world
+        .query_filtered::<Entity, With<TransformPanel>>()
+        .iter(world)
+        .collect();
+    for panel in old {
+        world.entity_mut(panel).despawn();
+    }
+    let (open, primary, world_space) = current;
+    if !open {
+        return;
+    }
+    let Some(viewport) = world
+        .query_filtered::<Entity, With<SceneViewport>>()
+        .iter(world)
+        .next()
+    else {
+        return;
+    };
+
+    let panel = world
+        .spawn((
+            TransformPanel,
+            EditorEntity,
+            Node {
+                position_type: PositionType::Absolute,
+                top: px(tokens::SPACING_MD),
+                right: px(tokens::SPACING_MD),
+                width: px(PANEL_WIDTH),
+                flex_direction: FlexDirection::Column,
+                row_gap: px(tokens::SPACING_XS),
+                padding: UiRect::all(px(tokens::SPACING_MD)),
+                border_radius: BorderRadius::all(tokens::CORNER_RADIUS_LG),
+                ..Default::default()
+            },
+            BackgroundColor(tokens::PANEL_BG.with_alpha(0.92)),
+            ChildOf(viewport),
+        ))
+        .id();
+
+    let header = world
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                width: percent(100),
+                ..Default::default()
+            },
+            ChildOf(panel),
+        ))
+        .id();
+    world.spawn((
+        Text::new("Transform"),
+        TextFont {
+            font_size: tokens::FONT_MD,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_PRIMARY),
+        ChildOf(header),
+    ));
+    let space_label = if world_space { "World" } else { "Local" };
+    world
+        .spawn((button(ButtonProps::new(space_label)), ChildOf(header)))
+        .observe(
+            |_: On<Pointer<Click>>, mut state: ResMut<TransformPanelState>| {
+                state.world_space = !state.world_space;
+            },
+        );
+
+    let Some(entity) = primary.filter(|&e| world.get::<Transform>(e).is_some()) else {
+        world.spawn((
+            Text::new("Nothing selected"),
+            TextFont {
+                font_size: tokens::FONT_SM,
+                ..Default::default()
+            },
+            TextColor(tokens::TEXT_SECONDARY),
+            ChildOf(panel),
+        ));
+        return;
+    };
+
+    let has_size = local_size(world, entity).is_some();
+    let mut sections: Vec<(&str, fn(usize) -> TransformField)> = vec![
+        ("Location", TransformField::Location),
+        ("Rotation", TransformField::Rotation),
+        ("Scale", TransformField::Scale),
+    ];
+    // Empties and lights have no bounds to size
+    if has_size {
+        sections.push(("Dimensions", TransformField::Dimension));
+    }
+    for (label, field) in sections {
+        world.spawn((
+            Text::new(label),
+            TextFont {
+                font_size: tokens::FONT_SM,
+                ..Default::default()
+            },
+            TextColor(tokens::TEXT_SECONDARY),
+            ChildOf(panel),
+        ));
+        let row = world
+            .spawn((
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: px(tokens::SPACING_XS),
+                    width: percent(100),
+                    ..Default::default()
+                },
+                ChildOf(panel),
+            ))
+            .id();
+        for (axis, axis_label) in ["X", "Y", "Z"].into_iter().enumerate() {
+            let value = field_value(world, entity, field(axis), world_space).unwrap_or(0.0);
+            let mut props = TextEditProps::default()
+                .numeric_f32()
+                .grow()
+                .with_prefix(TextEditPrefix::Label {
+                    label: axis_label.to_string(),
+                    size: tokens::TEXT_SIZE,
+                })
+                .with_default_value(value.to_string());
+            if matches!(field(axis), TransformField::Dimension(_)) {
+                props = props.with_min(0.0);
+            }
+            world.spawn((
+                text_edit::text_edit(props),
+                TransformFieldBinding {
+                    entity,
+                    field: field(axis),
+                    world_space,
+                },
+                ChildOf(row),
+            ));
+        }
+    }
+}
+
+fn on_transform_field_commit(
+    event: On<TextEditCommitEvent>,
+    bindings: Query<&TransformFieldBinding>,
+    child_of_query: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    // Walk up from committed entity to find a field binding
+    let mut current = event.entity;
+    for _ in 0..4 {
+        let Ok(child_of) = child_of_query.get(current) else {
+            return;
+        };
+        current = child_of.parent();
+        let Ok(binding) = bindings.get(current) else {
+            continue;
+        };
+        let Ok(value) = event.text.trim().parse::<f32>() else {
+            return;
+        };
+        let (entity, field, world_space) = (binding.entity, binding.field, binding.world_space);
+        commands.queue(move |world: &mut World| {
+            set_field(world, entity, field, world_space, value);
+        });
+        return;
+    }
+}
+
+/// Keep the fields in step with the entity (gizmo drags, undo).
+fn refresh_transform_fields(world: &mut World) {
+    let mut updates: Vec<(Entity, String)> = Vec::new();
+    let mut query = world.query::<(Entity, &TransformFieldBinding, &TextEditValue)>();
+    for (entity, binding, value) in query.iter(world) {
+        let Some(target) = field_value(world, binding.entity, binding.field, binding.world_space)
+        else {
+            continue;
+        };
+        let formatted = text_edit::format_numeric_value(target as f64, TextEditVariant::NumericF32);
+        if value.0 != formatted {
+            updates.push((entity, formatted));
+        }
+    }
+    if updates.is_empty() {
+        return;
+    }
+
+    let input_focus = world.resource::<InputFocus>().0;
+    for (outer_entity, formatted) in updates {
+        let Some((wrapper_entity, inner_entity)) = find_text_edit_entities(world, outer_entity)
+        else {
+            continue;
+        };
+        // Skip if the field is being drag-adjusted or the user is typing in it
+        if world.get::<TextEditDragging>(wrapper_entity).is_some()
+            || input_focus == Some(inner_entity)
+        {
+            continue;
+        }
+        if let Some(mut queue) = world.get_mut::<TextInputQueue>(inner_entity) {
+            set_text_input_value(&mut queue, formatted);
+        }
+    }
+}
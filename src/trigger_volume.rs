@@ -0,0 +1,157 @@
+//! Trigger volumes: brushes the game uses as invisible regions. Converting a brush
+//! tags it with [`TriggerVolume`] and gives it a `classname` so the game knows what
+//! the volume does.
+
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+use jackdaw_jsn::TriggerVolume;
+
+use crate::{
+    brush::Brush,
+    commands::{CommandGroup, CommandHistory, EditorCommand, remap_entity},
+    custom_properties::CustomProperties,
+    entity_classes::{CLASSNAME_PROPERTY, EntityClasses},
+    selection::Selection,
+};
+
+/// Classname given to converted brushes that don't have one yet.
+pub const DEFAULT_TRIGGER_CLASSNAME: &str = "trigger";
+
+pub struct TriggerVolumePlugin;
+
+impl Plugin for TriggerVolumePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            refresh_trigger_meshes
+                .before(crate::brush::mesh::regenerate_brush_meshes)
+                .run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+/// Turn a brush into a trigger volume or back into world geometry, along with the
+/// custom properties change that goes with it.
+pub struct SetTriggerVolume {
+    pub entity: Entity,
+    pub trigger: bool,
+    pub old_properties: Option<CustomProperties>,
+    pub new_properties: Option<CustomProperties>,
+}
+
+impl SetTriggerVolume {
+    fn apply(
+        world: &mut World,
+        entity: Entity,
+        trigger: bool,
+        properties: &Option<CustomProperties>,
+    ) {
+        let Ok(mut entity) = world.get_entity_mut(entity) else {
+            return;
+        };
+        if trigger {
+            entity.insert(TriggerVolume);
+        } else {
+            entity.remove::<TriggerVolume>();
+        }
+        match properties {
+            Some(properties) => {
+                entity.insert(properties.clone());
+            }
+            None => {
+                entity.remove::<CustomProperties>();
+            }
+        }
+    }
+}
+
+impl EditorCommand for SetTriggerVolume {
+    fn execute(&self, world: &mut World) {
+        Self::apply(world, self.entity, self.trigger, &self.new_properties);
+    }
+
+    fn undo(&self, world: &mut World) {
+        Self::apply(world, self.entity, !self.trigger, &self.old_properties);
+    }
+
+    fn description(&self) -> &str {
+        if self.trigger {
+            "Convert to trigger"
+        } else {
+            "Convert to brush"
+        }
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+    }
+}
+
+/// Make every selected brush a trigger volume. Brushes without a classname get
+/// [`DEFAULT_TRIGGER_CLASSNAME`] and that class's default properties.
+pub fn convert_selected_to_trigger(world: &mut World) {
+    set_selected_trigger(world, true);
+}
+
+/// Turn selected trigger volumes back into rendered brushes. Their custom properties
+/// are kept.
+pub fn convert_selected_to_brush(world: &mut World) {
+    set_selected_trigger(world, false);
+}
+
+fn set_selected_trigger(world: &mut World, trigger: bool) {
+    let selected = world.resource::<Selection>().entities.clone();
+    let mut cmds: Vec<Box<dyn EditorCommand>> = Vec::new();
+    for entity in selected {
+        if world.get::<Brush>(entity).is_none()
+            || world.get::<TriggerVolume>(entity).is_some() == trigger
+        {
+            continue;
+        }
+        let old_properties = world.get::<CustomProperties>(entity).cloned();
+        let mut new_properties = old_properties.clone();
+        if trigger {
+            let properties = new_properties.get_or_insert_default();
+            if !properties.properties.contains_key(CLASSNAME_PROPERTY) {
+                world
+                    .resource::<EntityClasses>()
+                    .fill_class(properties, DEFAULT_TRIGGER_CLASSNAME);
+            }
+        }
+        let cmd = SetTriggerVolume {
+            entity,
+            trigger,
+            old_properties,
+            new_properties,
+        };
+        cmd.execute(world);
+        cmds.push(Box::new(cmd));
+    }
+    if cmds.is_empty() {
+        return;
+    }
+
+    let label = if trigger {
+        "Convert to trigger"
+    } else {
+        "Convert to brush"
+    };
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(CommandGroup {
+        commands: cmds,
+        label: label.to_string(),
+    }));
+}
+
+/// Rebuild face meshes when a brush becomes or stops being a trigger so the
+/// translucent material is swapped in or out.
+fn refresh_trigger_meshes(
+    added: Query<Entity, Added<TriggerVolume>>,
+    mut removed: RemovedComponents<TriggerVolume>,
+    mut brushes: Query<&mut Brush>,
+) {
+    for entity in added.iter().chain(removed.read()) {
+        if let Ok(mut brush) = brushes.get_mut(entity) {
+            brush.set_changed();
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::path::PathBuf;
+
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowCloseRequested},
+};
+use jackdaw_feathers::dialog::{
+    DialogActionEvent, DialogClosedEvent, DialogSecondaryActionEvent, OpenDialogEvent,
+};
+
+use crate::{
+    commands::CommandHistory,
+    scene_io::{self, SceneFilePath},
+    scene_templates::{self, SceneTemplate},
+};
+
+const PROMPT_DIALOG: &str = "unsaved_changes";
+
+pub struct UnsavedChangesPlugin;
+
+impl Plugin for UnsavedChangesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UnsavedChanges>()
+            .add_observer(on_unsaved_prompt_save)
+            .add_observer(on_unsaved_prompt_discard)
+            .add_observer(on_unsaved_prompt_closed)
+            // Close requests arrive in every state, e.g. on the project picker
+            .add_systems(Update, handle_window_close_requests)
+            .add_systems(
+                Update,
+                (track_unsaved_changes, update_window_title)
+                    .chain()
+                    .after(handle_window_close_requests)
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+/// Whether the open scene has edits that haven't been written to disk.
+#[derive(Resource, Default)]
+pub struct UnsavedChanges {
+    pub dirty: bool,
+    /// Undo/redo stack shape when last looked at, to skip no-op history access.
+    seen: HistoryShape,
+    /// Action waiting on the Save / Discard / Cancel prompt.
+    prompt: Option<AfterPrompt>,
+    /// Action to run once the prompt's Save has finished (it may need a file dialog).
+    after_save: Option<AfterPrompt>,
+}
+
+#[derive(Default, PartialEq, Eq, Clone, Copy)]
+struct HistoryShape {
+    undo: usize,
+    redo: usize,
+    /// Steps recorded so far, so an undo followed by a new edit still counts.
+    pushes: u64,
+}
+
+impl HistoryShape {
+    fn of(history: &CommandHistory) -> Self {
+        Self {
+            undo: history.undo_stack.len(),
+            redo: history.redo_stack.len(),
+            pushes: history.pushes(),
+        }
+    }
+}
+
+/// What to do once unsaved changes have been saved or discarded.
+#[derive(Clone)]
+pub enum AfterPrompt {
+    NewScene(SceneTemplate),
+    OpenDialog,
+    OpenRecent(usize),
+    OpenFile(PathBuf),
+    CloseWindow(Entity),
+}
+
+/// Ask to Save / Discard / Cancel before `action` replaces an unsaved scene.
+/// Returns `false` (and does nothing) when there is nothing to lose, so the caller
+/// goes ahead; otherwise the action runs later from the prompt.
+pub fn prompt_if_unsaved(world: &mut World, action: AfterPrompt) -> bool {
+    let unsaved = world.resource::<UnsavedChanges>();
+    if !unsaved.dirty {
+        return false;
+    }
+    if unsaved.prompt.is_some() || unsaved.after_save.is_some() {
+        return true;
+    }
+
+    let name = scene_display_name(world.resource::<SceneFilePath>());
+    let mut unsaved = world.resource_mut::<UnsavedChanges>();
+    unsaved.prompt = Some(action);
+
+    let mut dialog = OpenDialogEvent::new("Unsaved Changes", "Save")
+        .with_id(PROMPT_DIALOG)
+        .with_secondary("Discard")
+        .with_close_on_click_outside(false)
+        .with_max_width(px(400));
+    dialog.description = Some(format!("Save changes to {name} before closing it?"));
+    world.trigger(dialog);
+    true
+}
+
+/// The scene on disk now matches the editor: after a save, load, or new scene.
+/// Runs the action a prompt's Save was waiting on.
+pub fn mark_saved(world: &mut World) {
+    let shape = HistoryShape::of(world.resource::<CommandHistory>());
+    let mut unsaved = world.resource_mut::<UnsavedChanges>();
+    unsaved.dirty = false;
+    unsaved.seen = shape;
+    if let Some(action) = unsaved.after_save.take() {
+        run_after_prompt(world, action);
+    }
+}
+
+/// The Save As dialog opened from the prompt was cancelled; so is the pending action.
+pub(crate) fn cancel_after_save(world: &mut World) {
+    world.resource_mut::<UnsavedChanges>().after_save = None;
+}
+
+fn run_after_prompt(world: &mut World, action: AfterPrompt) {
+    match action {
+        AfterPrompt::NewScene(template) => scene_templates::create_scene(world, template),
+        AfterPrompt::OpenDialog => scene_io::load_scene_now(world),
+        AfterPrompt::OpenRecent(index) => scene_io::open_recent_scene_now(world, index),
+        AfterPrompt::OpenFile(path) => scene_io::open_scene_file_now(world, &path),
+        AfterPrompt::CloseWindow(window) => {
+            // Let the scene finish writing before the app goes away.
+            scene_io::flush_scene_writes(world);
+            if let Ok(window) = world.get_entity_mut(window) {
+                window.despawn();
+            }
+        }
+    }
+}
+
+fn on_unsaved_prompt_save(event: On<DialogActionEvent>, mut commands: Commands) {
+    if event.id != Some(PROMPT_DIALOG) {
+        return;
+    }
+    commands.queue(|world: &mut World| {
+        let mut unsaved = world.resource_mut::<UnsavedChanges>();
+        let Some(action) = unsaved.prompt.take() else {
+            return;
+        };
+        unsaved.after_save = Some(action);
+        scene_io::save_scene(world);
+    });
+}
+
+fn on_unsaved_prompt_discard(event: On<DialogSecondaryActionEvent>, mut commands: Commands) {
+    if event.id != Some(PROMPT_DIALOG) {
+        return;
+    }
+    commands.queue(|world: &mut World| {
+        let Some(action) = world.resource_mut::<UnsavedChanges>().prompt.take() else {
+            return;
+        };
+        world.resource_mut::<UnsavedChanges>().dirty = false;
+        run_after_prompt(world, action);
+    });
+}
+
+/// Cancel, Esc, or the close button dismiss the prompt without an action. After Save or
+/// Discard the prompt has already been taken.
+fn on_unsaved_prompt_closed(event: On<DialogClosedEvent>, mut unsaved: ResMut<UnsavedChanges>) {
+    if event.id == Some(PROMPT_DIALOG) {
+        unsaved.prompt = None;
+    }
+}
+
+/// Any new, undone, or redone command makes the scene differ from the saved file.
+fn track_unsaved_changes(history: Res<CommandHistory>, mut unsaved: ResMut<UnsavedChanges>) {
+    if !history.is_changed() {
+        return;
+    }
+    let shape = HistoryShape::of(&history);
+    if shape != unsaved.seen {
+        unsaved.seen = shape;
+        unsaved.dirty = true;
+    }
+}
+
+/// Closing the window with unsaved changes asks first. Needs
+/// `WindowPlugin::close_when_requested` off, otherwise Bevy closes the window itself.
+/// Outside the editor there is no scene to lose, so the window just closes.
+fn handle_window_close_requests(
+    mut requests: MessageReader<WindowCloseRequested>,
+    unsaved: Res<UnsavedChanges>,
+    state: Res<State<crate::AppState>>,
+    primary: Query<(), With<PrimaryWindow>>,
+    mut commands: Commands,
+) {
+    for request in requests.read() {
+        let window = request.window;
+        // Secondary panel windows close on their own
+        if !primary.contains(window) {
+            continue;
+        }
+        if unsaved.dirty && *state.get() == crate::AppState::Editor {
+            commands.queue(move |world: &mut World| {
+                if !prompt_if_unsaved(world, AfterPrompt::CloseWindow(window)) {
+                    run_after_prompt(world, AfterPrompt::CloseWindow(window));
+                }
+            });
+        } else {
+            commands.entity(window).try_despawn();
+        }
+    }
+}
+
+/// Show the scene name in the window title, with `*` while it has unsaved changes.
+fn update_window_title(
+    unsaved: Res<UnsavedChanges>,
+    scene_path: Res<SceneFilePath>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut app_title: Local<Option<String>>,
+) {
+    if !unsaved.is_changed() && !scene_path.is_changed() {
+        return;
+    }
+    let Ok(mut window) = windows.single_mut() else {
+        return;
+    };
+    let app_title = app_title.get_or_insert_with(|| window.title.clone());
+    let star = if unsaved.dirty { "*" } else { "" };
+    let title = format!("{}{star} - {app_title}", scene_display_name(&scene_path));
+    if window.title != title {
+        window.title = title;
+    }
+}
+
+fn scene_display_name(scene_path: &SceneFilePath) -> String {
+    scene_path
+        .path
+        .as_deref()
+        .and_then(|p| std::path::Path::new(p).file_name())
+        .map(|f| f.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Untitled".to_string())
+}
@@ -1,19 +1,132 @@
-use bevy::prelude::*;
+use bevy::{
+    asset::{RenderAssetUsages, embedded_asset},
+    camera::visibility::RenderLayers,
+    pbr::UvChannel,
+    platform::collections::HashMap,
+    prelude::*,
+    render::render_resource::{AsBindGroup, Extent3d, TextureDimension, TextureFormat},
+    shader::ShaderRef,
+};
+use jackdaw_jsn::{MaterialOverrideApplied, OriginalMaterial};
+
+use crate::{EditorEntity, lightmap_bake::BakedLightmap, visibility_volumes::VisibilityZoneTint};
+
+const SHADER_VIEW_NORMALS_PATH: &str = "embedded://jackdaw/shaders/view_normals.wgsl";
 
 pub struct ViewModesPlugin;
 
 impl Plugin for ViewModesPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<ViewModeSettings>().add_systems(
-            Update,
-            toggle_wireframe_key.run_if(in_state(crate::AppState::Editor)),
-        );
+        embedded_asset!(app, "shaders/view_normals.wgsl");
+
+        app.add_plugins(MaterialPlugin::<NormalsViewMaterial>::default())
+            .init_resource::<ViewModeSettings>()
+            .init_resource::<ViewModeMaterials>()
+            .register_type::<ViewModeOverride>()
+            .register_type::<OriginalMaterial>()
+            .add_systems(
+                Update,
+                (toggle_wireframe_key, apply_view_mode)
+                    .chain()
+                    .run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+/// How scene meshes are shaded in the viewport.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum ViewMode {
+    /// Materials as authored.
+    #[default]
+    Lit,
+    /// Base color and textures without lighting.
+    Unlit,
+    /// World-space normals as RGB.
+    Normals,
+    /// A checker texture in place of every base color texture, for checking UVs.
+    UvChecker,
+    /// White surfaces, so only the lighting is visible.
+    LightingOnly,
+    /// Lightmaps from the last bake, without realtime lighting. Meshes without a
+    /// bake look as in `LightingOnly`.
+    Baked,
+    /// Flat colors by the visibility zone each mesh is in.
+    VisibilityZones,
+}
+
+impl ViewMode {
+    pub const ALL: [ViewMode; 7] = [
+        ViewMode::Lit,
+        ViewMode::Unlit,
+        ViewMode::Normals,
+        ViewMode::UvChecker,
+        ViewMode::LightingOnly,
+        ViewMode::Baked,
+        ViewMode::VisibilityZones,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ViewMode::Lit => "Lit",
+            ViewMode::Unlit => "Unlit",
+            ViewMode::Normals => "Normals",
+            ViewMode::UvChecker => "UV Checker",
+            ViewMode::LightingOnly => "Lighting Only",
+            ViewMode::Baked => "Baked",
+            ViewMode::VisibilityZones => "Visibility Zones",
+        }
+    }
+
+    /// Suffix of the `view.mode.*` menu action.
+    pub fn id(self) -> &'static str {
+        match self {
+            ViewMode::Lit => "lit",
+            ViewMode::Unlit => "unlit",
+            ViewMode::Normals => "normals",
+            ViewMode::UvChecker => "uv_checker",
+            ViewMode::LightingOnly => "lighting_only",
+            ViewMode::Baked => "baked",
+            ViewMode::VisibilityZones => "visibility_zones",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.id() == id)
     }
 }
 
 #[derive(Resource, Default)]
 pub struct ViewModeSettings {
     pub wireframe: bool,
+    pub mode: ViewMode,
+}
+
+#[derive(Asset, TypePath, AsBindGroup, Clone, Default)]
+pub struct NormalsViewMaterial {}
+
+impl Material for NormalsViewMaterial {
+    fn fragment_shader() -> ShaderRef {
+        SHADER_VIEW_NORMALS_PATH.into()
+    }
+}
+
+/// Materials shared by every overridden mesh, plus per-material variants.
+#[derive(Resource, Default)]
+pub(crate) struct ViewModeMaterials {
+    normals: Option<Handle<NormalsViewMaterial>>,
+    checker: Option<Handle<Image>>,
+    variants: HashMap<(AssetId<StandardMaterial>, ViewMode), Handle<StandardMaterial>>,
+    zone_tints: HashMap<[u8; 4], Handle<StandardMaterial>>,
+}
+
+/// A mesh whose material a view mode replaced; the authored one is kept in its
+/// [`OriginalMaterial`]. Reflected so duplicates and undo snapshots carry it along.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub(crate) struct ViewModeOverride {
+    mode: ViewMode,
+    /// The standard material put in its place (none for `Normals`).
+    applied: Option<Handle<StandardMaterial>>,
 }
 
 fn toggle_wireframe_key(
@@ -33,3 +146,299 @@ fn toggle_wireframe_key(
         }
     }
 }
+
+fn checker_image() -> Image {
+    const SIZE: u32 = 256;
+    const CELL: u32 = 32;
+    let mut data = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let (cx, cy) = (x / CELL, y / CELL);
+            let rgb = if (cx + cy) % 2 == 0 {
+                [220, 220, 220]
+            } else {
+                // Tint the dark cells by quadrant so flipped or rotated UVs stand out.
+                match (cx < SIZE / CELL / 2, cy < SIZE / CELL / 2) {
+                    (true, true) => [170, 60, 60],
+                    (false, true) => [60, 150, 60],
+                    (true, false) => [60, 80, 170],
+                    (false, false) => [170, 150, 50],
+                }
+            };
+            data.extend_from_slice(&[rgb[0], rgb[1], rgb[2], 255]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// The material `entity` was authored with: its [`OriginalMaterial`] while a view mode
+/// shows a stand-in, else its `MeshMaterial3d`, and the shared material under a
+/// `MaterialOverride` copy.
+pub fn authored_material(entity: EntityRef) -> Option<Handle<StandardMaterial>> {
+    let handle = match entity.get::<OriginalMaterial>() {
+        Some(original) => original.0.clone(),
+        None => entity.get::<MeshMaterial3d<StandardMaterial>>()?.0.clone(),
+    };
+    match entity.get::<MaterialOverrideApplied>() {
+        Some(applied) if applied.derived == handle => Some(applied.base.clone()),
+        _ => Some(handle),
+    }
+}
+
+/// Swap scene mesh materials to match the current view mode, and back again for `Lit`.
+pub(crate) fn apply_view_mode(
+    mut commands: Commands,
+    settings: Res<ViewModeSettings>,
+    mut cache: ResMut<ViewModeMaterials>,
+    mut material_events: MessageReader<AssetEvent<StandardMaterial>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut normal_materials: ResMut<Assets<NormalsViewMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    // Editor-only meshes and the material preview (its own render layer) keep their look.
+    mut meshes: Query<
+        (
+            Entity,
+            Option<&MeshMaterial3d<StandardMaterial>>,
+            Option<&mut ViewModeOverride>,
+            Option<&mut OriginalMaterial>,
+            Option<Ref<BakedLightmap>>,
+            Option<Ref<VisibilityZoneTint>>,
+        ),
+        (With<Mesh3d>, Without<EditorEntity>, Without<RenderLayers>),
+    >,
+) {
+    let mode = settings.mode;
+    if settings.is_changed() {
+        // Authored materials may have been edited since the variants were made.
+        cache.variants.clear();
+    }
+    // Authored materials edited in the inspector: rebuild their variants
+    let edited_ids: Vec<AssetId<StandardMaterial>> = material_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    if !edited_ids.is_empty() {
+        cache.variants.retain(|(id, _), _| !edited_ids.contains(id));
+    }
+
+    for (entity, current, existing, original, lightmap, tint) in &mut meshes {
+        // A fresh bake or zone change needs a fresh variant even though the mode is unchanged.
+        let rebaked = mode == ViewMode::Baked && lightmap.as_ref().is_some_and(Ref::is_changed);
+        let rezoned =
+            mode == ViewMode::VisibilityZones && tint.as_ref().is_some_and(Ref::is_changed);
+        let lightmap = lightmap.as_deref();
+        let tint = tint.map(|t| t.0);
+        let current = current.map(|m| m.0.clone());
+
+        let Some(mut existing) = existing else {
+            if mode == ViewMode::Lit {
+                continue;
+            }
+            let Some(original) = current else {
+                continue;
+            };
+            let applied = apply_to_entity(
+                &mut commands,
+                entity,
+                &original,
+                mode,
+                lightmap,
+                tint,
+                &mut cache,
+                &mut materials,
+                &mut normal_materials,
+                &mut images,
+            );
+            commands.entity(entity).insert((
+                OriginalMaterial(original),
+                ViewModeOverride { mode, applied },
+            ));
+            continue;
+        };
+        let Some(mut original) = original else {
+            commands.entity(entity).remove::<ViewModeOverride>();
+            continue;
+        };
+
+        // The authored material was reassigned or edited through `OriginalMaterial`
+        // (e.g. by a material override or the inspector).
+        let reassigned = original.is_changed() && !original.is_added();
+        let edited = edited_ids.contains(&original.0.id());
+
+        // Something else assigned a material while overridden (e.g. a texture was
+        // applied); treat it as the new authored material.
+        let replaced = current.is_some() && current != existing.applied;
+        if replaced {
+            original.0 = current.clone().unwrap_or_default();
+        }
+
+        if mode == ViewMode::Lit {
+            let mut ec = commands.entity(entity);
+            ec.remove::<(
+                ViewModeOverride,
+                OriginalMaterial,
+                MeshMaterial3d<NormalsViewMaterial>,
+            )>();
+            if !replaced {
+                ec.insert(MeshMaterial3d(original.0.clone()));
+            }
+            continue;
+        }
+
+        if existing.mode == mode && !replaced && !reassigned && !edited && !rebaked && !rezoned {
+            continue;
+        }
+        existing.applied = apply_to_entity(
+            &mut commands,
+            entity,
+            &original.0,
+            mode,
+            lightmap,
+            tint,
+            &mut cache,
+            &mut materials,
+            &mut normal_materials,
+            &mut images,
+        );
+        existing.mode = mode;
+    }
+}
+
+/// Give `entity` the `mode` look for `original`. Returns the standard material used, if any.
+fn apply_to_entity(
+    commands: &mut Commands,
+    entity: Entity,
+    original: &Handle<StandardMaterial>,
+    mode: ViewMode,
+    lightmap: Option<&BakedLightmap>,
+    tint: Option<Color>,
+    cache: &mut ViewModeMaterials,
+    materials: &mut Assets<StandardMaterial>,
+    normal_materials: &mut Assets<NormalsViewMaterial>,
+    images: &mut Assets<Image>,
+) -> Option<Handle<StandardMaterial>> {
+    let mut ec = commands.entity(entity);
+    if mode == ViewMode::Normals {
+        let normals = cache
+            .normals
+            .get_or_insert_with(|| normal_materials.add(NormalsViewMaterial::default()))
+            .clone();
+        ec.remove::<MeshMaterial3d<StandardMaterial>>()
+            .insert(MeshMaterial3d(normals));
+        return None;
+    }
+
+    // Every lightmap is its own texture, so baked variants are per entity and not cached.
+    if let (ViewMode::Baked, Some(lightmap)) = (mode, lightmap) {
+        let mut material = materials.get(original).cloned().unwrap_or_default();
+        material.base_color_texture = Some(lightmap.display.clone());
+        material.base_color_channel = UvChannel::Uv1;
+        material.unlit = true;
+        let variant = materials.add(material);
+        ec.remove::<MeshMaterial3d<NormalsViewMaterial>>()
+            .insert(MeshMaterial3d(variant.clone()));
+        return Some(variant);
+    }
+
+    // Zone colors replace the material outright, so meshes of a zone share one
+    if mode == ViewMode::VisibilityZones {
+        let tint = tint.unwrap_or(Color::srgb(0.35, 0.35, 0.35));
+        let variant = cache
+            .zone_tints
+            .entry(tint.to_srgba().to_u8_array())
+            .or_insert_with(|| {
+                let translucent = tint.alpha() < 1.0;
+                materials.add(StandardMaterial {
+                    base_color: tint,
+                    alpha_mode: if translucent {
+                        AlphaMode::Blend
+                    } else {
+                        AlphaMode::Opaque
+                    },
+                    unlit: true,
+                    ..default()
+                })
+            })
+            .clone();
+        ec.remove::<MeshMaterial3d<NormalsViewMaterial>>()
+            .insert(MeshMaterial3d(variant.clone()));
+        return Some(variant);
+    }
+
+    let key = (original.id(), mode);
+    let variant = match cache.variants.get(&key) {
+        Some(handle) => handle.clone(),
+        None => {
+            let mut material = materials.get(original).cloned().unwrap_or_default();
+            match mode {
+                ViewMode::Unlit => {
+                    material.unlit = true;
+                }
+                ViewMode::UvChecker => {
+                    let checker = cache
+                        .checker
+                        .get_or_insert_with(|| images.add(checker_image()))
+                        .clone();
+                    material.base_color = Color::WHITE;
+                    material.base_color_texture = Some(checker);
+                }
+                ViewMode::LightingOnly | ViewMode::Baked => {
+                    material.base_color = Color::WHITE;
+                    material.base_color_texture = None;
+                    material.emissive = LinearRgba::BLACK;
+                    material.emissive_texture = None;
+                    material.unlit = false;
+                }
+                ViewMode::Lit | ViewMode::Normals | ViewMode::VisibilityZones => {}
+            }
+            let handle = materials.add(material);
+            cache.variants.insert(key, handle.clone());
+            handle
+        }
+    };
+    ec.remove::<MeshMaterial3d<NormalsViewMaterial>>()
+        .insert(MeshMaterial3d(variant.clone()));
+    Some(variant)
+}
+
+/// Put every authored material back. Called before serializing so view mode
+/// variants never end up in a saved scene; `apply_view_mode` re-applies next frame.
+pub fn restore_original_materials(world: &mut World) {
+    let overridden: Vec<_> = world
+        .query::<(
+            Entity,
+            &ViewModeOverride,
+            &OriginalMaterial,
+            Option<&MeshMaterial3d<StandardMaterial>>,
+        )>()
+        .iter(world)
+        .map(|(e, o, original, current)| {
+            let untouched = current.is_none_or(|m| Some(&m.0) == o.applied.as_ref());
+            (e, original.0.clone(), untouched)
+        })
+        .collect();
+    for (entity, original, untouched) in overridden {
+        let mut ec = world.entity_mut(entity);
+        ec.remove::<(
+            ViewModeOverride,
+            OriginalMaterial,
+            MeshMaterial3d<NormalsViewMaterial>,
+        )>();
+        if untouched {
+            ec.insert(MeshMaterial3d(original));
+        }
+    }
+}
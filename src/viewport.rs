@@ -4,6 +4,7 @@ use bevy::{
     prelude::*,
     render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
     ui::{UiGlobalTransform, widget::ViewportNode},
+    window::PrimaryWindow,
 };
 use bevy_infinite_grid::InfiniteGridPlugin;
 use jackdaw_camera::{JackdawCameraPlugin, JackdawCameraSettings};
@@ -34,7 +35,12 @@ impl Plugin for ViewportPlugin {
             )
             .add_systems(
                 Update,
-                (update_camera_enabled, handle_camera_keys)
+                (
+                    update_camera_enabled,
+                    follow_selection_pivot,
+                    handle_camera_keys,
+                )
+                    .chain()
                     .run_if(in_state(crate::AppState::Editor)),
             );
     }
@@ -75,7 +81,12 @@ fn setup_viewport(
             },
             RenderTarget::Image(image_handle.into()),
             Transform::from_xyz(0.0, 4.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
-            JackdawCameraSettings::default(),
+            JackdawCameraSettings {
+                orbit: crate::display_settings::read_editor_settings()
+                    .navigation
+                    .orbit,
+                ..default()
+            },
         ))
         .id();
 
@@ -97,7 +108,7 @@ fn handle_viewport_drop(
     event: On<Pointer<DragDrop>>,
     file_items: Query<&FileBrowserItem>,
     parents: Query<&ChildOf>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
     snap_settings: Res<crate::snapping::SnapSettings>,
@@ -138,15 +149,15 @@ fn handle_viewport_drop(
     let path = item.path.clone();
     if is_jsn {
         commands.queue(move |world: &mut World| {
-            crate::entity_templates::instantiate_jsn_prefab(world, &path, snapped_pos);
+            crate::entity_templates::instantiate_jsn_prefab(world, &path, snapped_pos, None);
         });
     } else if is_template {
         commands.queue(move |world: &mut World| {
-            crate::entity_templates::instantiate_template(world, &path, snapped_pos);
+            crate::entity_templates::instantiate_template(world, &path, snapped_pos, None);
         });
     } else {
         commands.queue(move |world: &mut World| {
-            crate::entity_ops::spawn_gltf_in_world(world, &path, snapped_pos);
+            crate::entity_ops::spawn_gltf_in_world(world, &path, snapped_pos, None);
         });
     }
 }
@@ -186,7 +197,7 @@ pub(crate) fn cursor_to_ground_plane(
 }
 
 /// Walk up the entity hierarchy to find a component.
-fn find_ancestor_component<'a, C: Component>(
+pub(crate) fn find_ancestor_component<'a, C: Component>(
     mut entity: Entity,
     query: &'a Query<&C>,
     parents: &Query<&ChildOf>,
@@ -205,12 +216,14 @@ fn find_ancestor_component<'a, C: Component>(
 
 /// Enable/disable camera controls based on viewport hover, modal state, etc.
 fn update_camera_enabled(
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     viewport_node: Single<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
     mut camera_query: Query<&mut JackdawCameraSettings>,
     modal: Res<crate::modal_transform::ModalTransformState>,
     input_focus: Res<bevy::input_focus::InputFocus>,
     blockers: Query<(), With<crate::BlocksCameraInput>>,
+    walk: Res<crate::walk_mode::WalkMode>,
+    gizmo_drag: Res<crate::gizmos::GizmoDragState>,
 ) {
     let Ok(window) = windows.single() else {
         return;
@@ -238,13 +251,56 @@ fn update_camera_enabled(
     let modal_active = modal.active.is_some();
     let text_focused = input_focus.0.is_some();
     let overlay_blocking = !blockers.is_empty();
-    let should_enable = hovered && !modal_active && !text_focused && !overlay_blocking;
+    // Walk mode drives the camera itself; an Alt-drag on the gizmo mustn't orbit
+    let should_enable = hovered
+        && !modal_active
+        && !text_focused
+        && !overlay_blocking
+        && !walk.is_active()
+        && !gizmo_drag.active;
 
     for mut settings in &mut camera_query {
         settings.enabled = should_enable;
     }
 }
 
+/// The viewport camera's settings, for checking [`orbit_modifier_held`].
+pub(crate) type OrbitCameras<'w, 's> =
+    Query<'w, 's, &'static JackdawCameraSettings, With<MainViewportCamera>>;
+
+/// Whether Alt is held for orbit navigation, so viewport clicks navigate instead of
+/// selecting or dragging.
+pub(crate) fn orbit_modifier_held(keyboard: &ButtonInput<KeyCode>, cameras: &OrbitCameras) -> bool {
+    cameras
+        .iter()
+        .any(|settings| settings.orbit_modifier_held(keyboard))
+}
+
+/// Orbit around the selection's center when it changes; otherwise around the last
+/// focus point.
+fn follow_selection_pivot(
+    selection: Res<Selection>,
+    selected_transforms: Query<&GlobalTransform, With<Selected>>,
+    mut cameras: Query<&mut JackdawCameraSettings>,
+) {
+    if !selection.is_changed() {
+        return;
+    }
+    let points: Vec<Vec3> = selection
+        .entities
+        .iter()
+        .filter_map(|&e| selected_transforms.get(e).ok())
+        .map(GlobalTransform::translation)
+        .collect();
+    if points.is_empty() {
+        return;
+    }
+    let center = points.iter().sum::<Vec3>() / points.len() as f32;
+    for mut settings in &mut cameras {
+        settings.pivot = center;
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct CameraBookmarks {
     pub slots: [Option<CameraBookmark>; 9],
@@ -259,7 +315,7 @@ fn handle_camera_keys(
     keyboard: Res<ButtonInput<KeyCode>>,
     selection: Res<Selection>,
     selected_transforms: Query<&GlobalTransform, With<Selected>>,
-    mut camera_query: Query<&mut Transform, With<JackdawCameraSettings>>,
+    mut camera_query: Query<(&mut Transform, &mut JackdawCameraSettings)>,
     mut bookmarks: ResMut<CameraBookmarks>,
     modal: Res<crate::modal_transform::ModalTransformState>,
     edit_mode: Res<crate::brush::EditMode>,
@@ -279,11 +335,12 @@ fn handle_camera_keys(
                 let scale = global_tf.compute_transform().scale;
                 let dist = (scale.length() * 3.0).max(5.0);
 
-                for mut transform in &mut camera_query {
+                for (mut transform, mut settings) in &mut camera_query {
                     // Move camera to look at target from current viewing direction
                     let forward = transform.forward().as_vec3();
                     transform.translation = target - forward * dist;
                     *transform = transform.looking_at(target, Vec3::Y);
+                    settings.pivot = target;
                 }
             }
         }
@@ -306,7 +363,7 @@ fn handle_camera_keys(
         if keyboard.just_pressed(key) {
             if ctrl {
                 // Save bookmark (always works)
-                for transform in &camera_query {
+                for (transform, _) in &camera_query {
                     bookmarks.slots[index] = Some(CameraBookmark {
                         transform: *transform,
                     });
@@ -314,7 +371,7 @@ fn handle_camera_keys(
             } else if *edit_mode == crate::brush::EditMode::Object {
                 // Restore bookmark (only in Object mode — number keys are edit modes in brush edit)
                 if let Some(bookmark) = bookmarks.slots[index] {
-                    for mut transform in &mut camera_query {
+                    for (mut transform, _) in &mut camera_query {
                         *transform = bookmark.transform;
                     }
                 }
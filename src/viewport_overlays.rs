@@ -2,16 +2,32 @@ use std::f32::consts::FRAC_PI_2;
 
 use avian3d::parry::math::Point as ParryPoint;
 use avian3d::parry::transformation::convex_hull;
-use bevy::prelude::*;
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    platform::{collections::HashSet, time::Instant},
+    prelude::*,
+};
+use jackdaw_feathers::tokens;
 
+use crate::EditorEntity;
 use crate::brush::{self, BrushMeshCache};
 use crate::selection::Selected;
+use crate::viewport::SceneViewport;
+
+/// Seconds between stats HUD text refreshes, so the numbers stay readable.
+const STATS_REFRESH_INTERVAL: f32 = 0.25;
 
 pub struct ViewportOverlaysPlugin;
 
 impl Plugin for ViewportOverlaysPlugin {
     fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+        }
         app.init_resource::<OverlaySettings>()
+            .init_resource::<FrameStats>()
+            .add_systems(First, begin_frame_stats)
+            .add_systems(Last, end_frame_stats)
             .add_systems(
                 PostUpdate,
                 draw_selection_bounding_boxes
@@ -31,7 +47,11 @@ impl Plugin for ViewportOverlaysPlugin {
             )
             .add_systems(
                 Update,
-                (draw_coordinate_indicator, draw_navmesh_region_bounds)
+                (
+                    draw_coordinate_indicator,
+                    draw_navmesh_region_bounds,
+                    update_stats_hud,
+                )
                     .run_if(in_state(crate::AppState::Editor)),
             );
     }
@@ -54,6 +74,8 @@ pub struct OverlaySettings {
     pub show_face_grid: bool,
     pub show_brush_wireframe: bool,
     pub show_alignment_guides: bool,
+    pub show_surface_types: bool,
+    pub show_stats: bool,
 }
 
 impl Default for OverlaySettings {
@@ -65,6 +87,8 @@ impl Default for OverlaySettings {
             show_face_grid: true,
             show_brush_wireframe: true,
             show_alignment_guides: true,
+            show_surface_types: false,
+            show_stats: false,
         }
     }
 }
@@ -406,3 +430,127 @@ fn draw_navmesh_region_bounds(
         gizmos.cube(transform, color);
     }
 }
+
+/// CPU time of the main schedule, measured from `First` to `Last`.
+#[derive(Resource, Default)]
+struct FrameStats {
+    frame_start: Option<Instant>,
+    update_ms: f32,
+}
+
+fn begin_frame_stats(mut stats: ResMut<FrameStats>) {
+    stats.frame_start = Some(Instant::now());
+}
+
+fn end_frame_stats(mut stats: ResMut<FrameStats>) {
+    if let Some(start) = stats.frame_start.take() {
+        stats.update_ms = start.elapsed().as_secs_f32() * 1000.0;
+    }
+}
+
+/// Root of the statistics overlay in the top-right corner of the viewport.
+#[derive(Component)]
+struct StatsHud;
+
+/// Show FPS, frame time, what is in view and brush rebuild work while
+/// [`OverlaySettings::show_stats`] is on.
+fn update_stats_hud(
+    mut commands: Commands,
+    settings: Res<OverlaySettings>,
+    time: Res<Time>,
+    mut since_refresh: Local<f32>,
+    mut rebuilds_since_refresh: Local<usize>,
+    diagnostics: Res<DiagnosticsStore>,
+    frame_stats: Res<FrameStats>,
+    rebuild_queue: Res<brush::BrushRebuildQueue>,
+    meshes: Query<
+        (
+            &ViewVisibility,
+            &Mesh3d,
+            Option<&MeshMaterial3d<StandardMaterial>>,
+        ),
+        Without<EditorEntity>,
+    >,
+    viewport: Query<Entity, With<SceneViewport>>,
+    mut hud: Query<(Entity, &mut Text), With<StatsHud>>,
+) {
+    if !settings.show_stats {
+        for (entity, _) in &hud {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    *since_refresh += time.delta_secs();
+    *rebuilds_since_refresh += rebuild_queue.rebuilt_last_frame();
+    let existing = hud.single_mut().ok();
+    if existing.is_some() && *since_refresh < STATS_REFRESH_INTERVAL {
+        return;
+    }
+    let rebuilds_per_sec = *rebuilds_since_refresh as f32 / since_refresh.max(f32::EPSILON);
+    *since_refresh = 0.0;
+    *rebuilds_since_refresh = 0;
+
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|d| d.smoothed())
+        .unwrap_or_default();
+    let frame_ms = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .and_then(|d| d.smoothed())
+        .unwrap_or_default() as f32;
+    let update_ms = frame_stats.update_ms;
+
+    // Instances of one mesh with one material are drawn together, so distinct pairs
+    // approximate the draw calls
+    let mut total = 0;
+    let mut visible = 0;
+    let mut batches = HashSet::new();
+    for (view_visibility, mesh, material) in &meshes {
+        total += 1;
+        if view_visibility.get() {
+            visible += 1;
+            batches.insert((mesh.id(), material.map(|m| m.id())));
+        }
+    }
+
+    let label = format!(
+        "{fps:.0} FPS  {frame_ms:.1} ms\n\
+         Update {update_ms:.1} ms  Render/wait {:.1} ms\n\
+         Meshes in view {visible} / {total}\n\
+         Draw calls (est.) {}\n\
+         Brush rebuilds {rebuilds_per_sec:.0}/s ({} queued)",
+        (frame_ms - update_ms).max(0.0),
+        batches.len(),
+        rebuild_queue.len(),
+    );
+
+    if let Some((_, mut text)) = existing {
+        text.0 = label;
+        return;
+    }
+    let Ok(viewport) = viewport.single() else {
+        return;
+    };
+    commands.spawn((
+        StatsHud,
+        EditorEntity,
+        Text::new(label),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_PRIMARY),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(tokens::SPACING_MD),
+            right: px(tokens::SPACING_MD),
+            padding: UiRect::all(px(tokens::SPACING_SM)),
+            border_radius: BorderRadius::all(tokens::CORNER_RADIUS_LG),
+            ..Default::default()
+        },
+        BackgroundColor(tokens::PANEL_BG.with_alpha(0.92)),
+        Pickable::IGNORE,
+        ChildOf(viewport),
+    ));
+}
@@ -1,15 +1,19 @@
 use crate::{
     EditorEntity,
-    gizmos::GizmoDragState,
+    gizmos::{GizmoDragState, GizmoHoverState},
+    instancing::PickedInstance,
     modal_transform::{ModalTransformState, ViewportDragState},
     selection::Selection,
-    viewport::{MainViewportCamera, SceneViewport},
+    viewport::{MainViewportCamera, OrbitCameras, SceneViewport, orbit_modifier_held},
 };
 use bevy::input_focus::InputFocus;
 use bevy::{
-    picking::mesh_picking::ray_cast::{MeshRayCast, MeshRayCastSettings, RayCastVisibility},
+    picking::mesh_picking::ray_cast::{
+        MeshRayCast, MeshRayCastSettings, RayCastVisibility, RayMeshHit,
+    },
     prelude::*,
     ui::UiGlobalTransform,
+    window::PrimaryWindow,
 };
 
 pub struct ViewportSelectPlugin;
@@ -33,35 +37,47 @@ pub struct BoxSelectState {
 fn handle_viewport_click(
     mouse: Res<ButtonInput<MouseButton>>,
     keyboard: Res<ButtonInput<KeyCode>>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
     scene_entities: Query<(Entity, &GlobalTransform), (Without<EditorEntity>, With<Transform>)>,
     parents: Query<&ChildOf>,
-    gizmo_drag: Res<GizmoDragState>,
+    (gizmo_drag, gizmo_hover): (Res<GizmoDragState>, Res<GizmoHoverState>),
     modal: Res<ModalTransformState>,
     vp_drag: Res<ViewportDragState>,
     mut selection: ResMut<Selection>,
     mut input_focus: ResMut<InputFocus>,
     mut commands: Commands,
-    (edit_mode, draw_state): (
+    (edit_mode, draw_state, terrain_edit_mode, spline_edit, orbit_cameras): (
         Res<crate::brush::EditMode>,
         Res<crate::draw_brush::DrawBrushState>,
+        Res<crate::terrain::TerrainEditMode>,
+        Res<crate::spline::SplineEditState>,
+        OrbitCameras,
+    ),
+    (mut picked_instance, instance_batches, entered_group, sub_scenes): (
+        ResMut<PickedInstance>,
+        Query<&jackdaw_jsn::InstanceBatch>,
+        Res<crate::grouping::EnteredGroup>,
+        Query<(), With<jackdaw_jsn::SubScene>>,
     ),
-    terrain_edit_mode: Res<crate::terrain::TerrainEditMode>,
     mut ray_cast: MeshRayCast,
 ) {
     let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
 
-    // Don't select during gizmo drag, modal ops, viewport drag, brush edit mode, draw mode,
-    // terrain sculpt mode, or shift+click (which starts box select)
+    // Don't select during gizmo drag or over a gizmo handle, modal ops, viewport drag, brush edit mode, draw mode,
+    // terrain sculpt mode, spline edit mode, shift+click (which starts box select) or
+    // Alt+click with orbit navigation on
     if !mouse.just_pressed(MouseButton::Left)
         || shift
+        || orbit_modifier_held(&keyboard, &orbit_cameras)
         || gizmo_drag.active
+        || gizmo_hover.hovered.is_some()
         || modal.active.is_some()
         || vp_drag.active.is_some()
         || *edit_mode != crate::brush::EditMode::Object
         || draw_state.active.is_some()
+        || spline_edit.active.is_some()
         || matches!(
             *terrain_edit_mode,
             crate::terrain::TerrainEditMode::Sculpt(_)
@@ -113,14 +129,20 @@ fn handle_viewport_click(
         let hits = ray_cast.cast_ray(ray, &settings);
 
         // Find the first hit that resolves to a scene entity (skip editor entities)
-        for (hit_entity, _) in hits {
-            if let Some(ancestor) = find_selectable_ancestor(*hit_entity, &scene_entities, &parents)
+        for (hit_entity, hit) in hits {
+            if let Some(ancestor) =
+                find_selectable_ancestor(*hit_entity, &scene_entities, &parents, entered_group.0)
             {
                 best_entity = Some(ancestor);
+                picked_instance.0 =
+                    find_picked_instance(*hit_entity, hit, &instance_batches, &parents);
                 break;
             }
         }
     }
+    if best_entity.is_none() {
+        picked_instance.0 = None;
+    }
 
     // Fall back to screen-space proximity for non-mesh entities (lights, empties)
     if best_entity.is_none() {
@@ -137,6 +159,15 @@ fn handle_viewport_click(
         }
     }
 
+    // A sub-scene's contents belong to its file; clicking them selects the sub-scene
+    let best_entity = best_entity.map(|entity| {
+        parents
+            .iter_ancestors(entity)
+            .filter(|&ancestor| sub_scenes.contains(ancestor))
+            .last()
+            .unwrap_or(entity)
+    });
+
     if let Some(entity) = best_entity {
         let ctrl = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
         if ctrl {
@@ -156,15 +187,17 @@ fn handle_viewport_click(
 fn handle_box_select(
     mouse: Res<ButtonInput<MouseButton>>,
     keyboard: Res<ButtonInput<KeyCode>>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     mut box_state: ResMut<BoxSelectState>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainViewportCamera>>,
     viewport_query: Query<(&ComputedNode, &UiGlobalTransform), With<SceneViewport>>,
     gizmo_drag: Res<GizmoDragState>,
+    gizmo_hover: Res<GizmoHoverState>,
     edit_mode: Res<crate::brush::EditMode>,
     scene_entities: Query<(Entity, &GlobalTransform), (Without<EditorEntity>, With<Transform>)>,
     mut selection: ResMut<Selection>,
     mut commands: Commands,
+    orbit_cameras: OrbitCameras,
 ) {
     // Don't box-select during gizmo drag or brush edit mode
     if gizmo_drag.active || *edit_mode != crate::brush::EditMode::Object {
@@ -181,8 +214,13 @@ fn handle_box_select(
 
     let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
 
-    // Start box select on Shift+LMB drag
-    if shift && mouse.just_pressed(MouseButton::Left) && !box_state.active {
+    // Start box select on Shift+LMB drag, unless Shift-dragging a gizmo handle
+    if shift
+        && mouse.just_pressed(MouseButton::Left)
+        && !box_state.active
+        && gizmo_hover.hovered.is_none()
+        && !orbit_modifier_held(&keyboard, &orbit_cameras)
+    {
         box_state.active = true;
         box_state.start = cursor_pos;
         box_state.current = cursor_pos;
@@ -240,11 +278,13 @@ fn handle_box_select(
 
 /// Walk up the `ChildOf` hierarchy from a raycast hit entity to find the
 /// top-level scene entity (one that appears in `scene_entities`).
-/// Handles GLTF child meshes and brush face children.
-fn find_selectable_ancestor(
+/// Handles GLTF child meshes and brush face children. Inside the `entered` func group
+/// the walk stops at the group's members.
+pub(crate) fn find_selectable_ancestor(
     mut entity: Entity,
     scene_entities: &Query<(Entity, &GlobalTransform), (Without<EditorEntity>, With<Transform>)>,
     parents: &Query<&ChildOf>,
+    entered: Option<Entity>,
 ) -> Option<Entity> {
     // Walk up until we find a scene entity (one that has Transform and is not EditorEntity)
     // Start with the hit entity itself — it may already be a scene entity
@@ -254,7 +294,7 @@ fn find_selectable_ancestor(
             // if so, prefer the parent (handles GLTF sub-meshes).
             if let Ok(child_of) = parents.get(entity) {
                 let parent = child_of.0;
-                if scene_entities.contains(parent) {
+                if scene_entities.contains(parent) && Some(parent) != entered {
                     // Keep walking up — the parent is also selectable
                     entity = parent;
                     continue;
@@ -269,3 +309,16 @@ fn find_selectable_ancestor(
         }
     }
 }
+
+/// If the hit entity is an instance batch, return the `(container, index)` of the
+/// instance whose triangle was hit.
+fn find_picked_instance(
+    entity: Entity,
+    hit: &RayMeshHit,
+    batches: &Query<&jackdaw_jsn::InstanceBatch>,
+    parents: &Query<&ChildOf>,
+) -> Option<(Entity, usize)> {
+    let batch = batches.get(entity).ok()?;
+    let container = parents.get(entity).ok()?.parent();
+    Some((container, batch.instance_of(hit.triangle_index?)))
+}
@@ -0,0 +1,50 @@
+use bevy::{camera::visibility::VisibilitySystems, prelude::*};
+
+pub use jackdaw_jsn::HiddenInGame;
+
+pub struct VisibilityFlagsPlugin;
+
+impl Plugin for VisibilityFlagsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<HiddenInEditor>()
+            .add_observer(on_hidden_in_editor_removed)
+            .add_systems(
+                PostUpdate,
+                hide_in_editor_subtrees
+                    .after(VisibilitySystems::VisibilityPropagate)
+                    .before(VisibilitySystems::CheckVisibility),
+            );
+    }
+}
+
+/// Hides the entity and its children in the editor viewport only. Unlike toggling
+/// `Visibility`, it leaves the authored value alone and is never saved with the scene;
+/// the exported-game counterpart is [`HiddenInGame`].
+#[derive(Component, Reflect, Default, Clone, Copy, Debug)]
+#[reflect(Component, Default)]
+pub struct HiddenInEditor;
+
+/// Override the propagated visibility of flagged subtrees before culling reads it.
+fn hide_in_editor_subtrees(
+    flagged: Query<Entity, With<HiddenInEditor>>,
+    children: Query<&Children>,
+    mut inherited: Query<&mut InheritedVisibility>,
+) {
+    for root in &flagged {
+        for entity in std::iter::once(root).chain(children.iter_descendants(root)) {
+            if let Ok(mut visibility) = inherited.get_mut(entity) {
+                visibility.set_if_neq(InheritedVisibility::HIDDEN);
+            }
+        }
+    }
+}
+
+/// Propagation only revisits changed `Visibility`, so poke it to undo the override.
+fn on_hidden_in_editor_removed(
+    trigger: On<Remove, HiddenInEditor>,
+    mut visibility: Query<&mut Visibility>,
+) {
+    if let Ok(mut visibility) = visibility.get_mut(trigger.event_target()) {
+        visibility.set_changed();
+    }
+}
@@ -0,0 +1,212 @@
+//! Visibility volumes: brushes exported for the game's culling instead of rendered. Zones
+//! partition the level, occluders hide what is behind them and portals join zones. The
+//! Visibility Zones view mode colors every mesh by the zone it is in.
+
+use bevy::{
+    camera::{primitives::Aabb, visibility::RenderLayers},
+    ecs::entity::EntityHashMap,
+    platform::collections::HashMap,
+    prelude::*,
+};
+use jackdaw_geometry::point_inside_all_planes;
+use jackdaw_jsn::{VisibilityVolume, VisibilityVolumeKind};
+
+use crate::{
+    EditorEntity,
+    brush::{Brush, BrushFaceEntity, BrushMeshCache},
+    commands::{CommandGroup, CommandHistory, EditorCommand, remap_entity},
+    selection::Selection,
+    view_modes::{ViewMode, ViewModeSettings},
+};
+
+pub struct VisibilityVolumesPlugin;
+
+impl Plugin for VisibilityVolumesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                refresh_volume_meshes.before(crate::brush::mesh::regenerate_brush_meshes),
+                update_zone_tints.before(crate::view_modes::apply_view_mode),
+            )
+                .run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+/// Color a mesh shows in the Visibility Zones view mode.
+#[derive(Component, Clone, Copy, PartialEq)]
+pub struct VisibilityZoneTint(pub Color);
+
+/// Turn a brush into a visibility volume of some kind, or back into world geometry.
+pub struct SetVisibilityVolume {
+    pub entity: Entity,
+    pub old: Option<VisibilityVolume>,
+    pub new: Option<VisibilityVolume>,
+}
+
+impl SetVisibilityVolume {
+    fn apply(world: &mut World, entity: Entity, volume: Option<VisibilityVolume>) {
+        let Ok(mut entity) = world.get_entity_mut(entity) else {
+            return;
+        };
+        match volume {
+            Some(volume) => {
+                entity.insert(volume);
+            }
+            None => {
+                entity.remove::<VisibilityVolume>();
+            }
+        }
+    }
+}
+
+impl EditorCommand for SetVisibilityVolume {
+    fn execute(&self, world: &mut World) {
+        Self::apply(world, self.entity, self.new);
+    }
+
+    fn undo(&self, world: &mut World) {
+        Self::apply(world, self.entity, self.old);
+    }
+
+    fn description(&self) -> &str {
+        "Set visibility volume"
+    }
+
+    fn remap_entities(&mut self, map: &EntityHashMap<Entity>) {
+        remap_entity(&mut self.entity, map);
+    }
+}
+
+fn kind_label(kind: Option<VisibilityVolumeKind>) -> &'static str {
+    match kind {
+        Some(VisibilityVolumeKind::Zone) => "Convert to zone",
+        Some(VisibilityVolumeKind::Occluder) => "Convert to occluder",
+        Some(VisibilityVolumeKind::Portal) => "Convert to portal",
+        None => "Convert to brush",
+    }
+}
+
+/// Make every selected brush a visibility volume of `kind`, or with `None` turn selected
+/// volumes back into rendered brushes.
+pub fn convert_selected(world: &mut World, kind: Option<VisibilityVolumeKind>) {
+    let selected = world.resource::<Selection>().entities.clone();
+    let new = kind.map(|kind| VisibilityVolume { kind });
+    let mut cmds: Vec<Box<dyn EditorCommand>> = Vec::new();
+    for entity in selected {
+        if world.get::<Brush>(entity).is_none() {
+            continue;
+        }
+        let old = world.get::<VisibilityVolume>(entity).copied();
+        if old == new {
+            continue;
+        }
+        let cmd = SetVisibilityVolume { entity, old, new };
+        cmd.execute(world);
+        cmds.push(Box::new(cmd));
+    }
+    if cmds.is_empty() {
+        return;
+    }
+
+    let mut history = world.resource_mut::<CommandHistory>();
+    history.push_executed(Box::new(CommandGroup {
+        commands: cmds,
+        label: kind_label(kind).to_string(),
+    }));
+}
+
+/// Rebuild face meshes when a brush becomes, changes or stops being a volume so its
+/// material follows.
+fn refresh_volume_meshes(
+    changed: Query<Entity, Changed<VisibilityVolume>>,
+    mut removed: RemovedComponents<VisibilityVolume>,
+    mut brushes: Query<&mut Brush>,
+) {
+    for entity in changed.iter().chain(removed.read()) {
+        if let Ok(mut brush) = brushes.get_mut(entity) {
+            brush.set_changed();
+        }
+    }
+}
+
+/// Distinct, stable-per-order color for the `index`th zone.
+fn zone_color(index: usize) -> Color {
+    Color::hsl((index as f32 * 137.5) % 360.0, 0.65, 0.55)
+}
+
+/// While the Visibility Zones view mode is on, tint every mesh by the smallest zone
+/// containing its center. Volume faces keep a translucent tint of their own.
+fn update_zone_tints(
+    mut commands: Commands,
+    settings: Res<ViewModeSettings>,
+    zones: Query<(
+        Entity,
+        &VisibilityVolume,
+        &Brush,
+        &BrushMeshCache,
+        &GlobalTransform,
+    )>,
+    meshes: Query<
+        (
+            Entity,
+            &Aabb,
+            &GlobalTransform,
+            Option<&BrushFaceEntity>,
+            Option<&VisibilityZoneTint>,
+        ),
+        (With<Mesh3d>, Without<EditorEntity>, Without<RenderLayers>),
+    >,
+    volumes: Query<&VisibilityVolume>,
+) {
+    if settings.mode != ViewMode::VisibilityZones {
+        return;
+    }
+    let mut zones: Vec<_> = zones
+        .iter()
+        .filter(|(_, volume, ..)| volume.kind == VisibilityVolumeKind::Zone)
+        .map(|(entity, _, brush, cache, global)| {
+            let (min, max) = crate::viewport_overlays::aabb_from_points(&cache.vertices);
+            let size = (max - min) * global.compute_transform().scale.abs();
+            (
+                entity,
+                brush,
+                global.affine().inverse(),
+                size.x * size.y * size.z,
+            )
+        })
+        .collect();
+    zones.sort_by_key(|(entity, ..)| *entity);
+    let colors: HashMap<Entity, Color> = zones
+        .iter()
+        .enumerate()
+        .map(|(index, (entity, ..))| (*entity, zone_color(index)))
+        .collect();
+
+    for (entity, aabb, global, face, current) in &meshes {
+        let volume = face.and_then(|f| volumes.get(f.brush_entity).ok());
+        let tint = match volume {
+            Some(volume) => match volume.kind {
+                VisibilityVolumeKind::Zone => colors
+                    .get(&face.unwrap().brush_entity)
+                    .map_or(Color::NONE, |c| c.with_alpha(0.15)),
+                VisibilityVolumeKind::Occluder => Color::srgba(0.9, 0.2, 0.3, 0.35),
+                VisibilityVolumeKind::Portal => Color::srgba(1.0, 1.0, 1.0, 0.35),
+            },
+            None => {
+                let center = global.transform_point(Vec3::from(aabb.center));
+                zones
+                    .iter()
+                    .filter(|(_, brush, to_local, _)| {
+                        point_inside_all_planes(to_local.transform_point3(center), &brush.faces)
+                    })
+                    .min_by(|a, b| a.3.total_cmp(&b.3))
+                    .map_or(Color::srgb(0.35, 0.35, 0.35), |(zone, ..)| colors[zone])
+            }
+        };
+        if current.map(|c| c.0) != Some(tint) {
+            commands.entity(entity).insert(VisibilityZoneTint(tint));
+        }
+    }
+}
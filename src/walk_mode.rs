@@ -0,0 +1,283 @@
+//! Walk mode: the viewport camera becomes a player-sized capsule with gravity, walking on
+//! the scene at eye height to preview scale and sightlines. The scene is stood in for by
+//! the same fixed colliders the physics simulation uses, taken when walk mode starts. The
+//! player's size comes from the `[player]` section of `project.toml`.
+
+use avian3d::prelude::*;
+use bevy::{input::mouse::MouseMotion, input_focus::InputFocus, prelude::*, window::PrimaryWindow};
+
+use crate::{
+    project::ProjectRoot, simulation::spawn_static_colliders, status_bar::StatusHints,
+    viewport::MainViewportCamera,
+};
+
+const WALK_HINTS: &str = "walk_mode";
+
+const WALK_SPEED: f32 = 4.0;
+const RUN_MULTIPLIER: f32 = 2.0;
+const JUMP_SPEED: f32 = 5.0;
+const GRAVITY: f32 = 9.81;
+/// Gap kept between the capsule and what it touches.
+const SKIN: f32 = 0.01;
+/// Surfaces flatter than this (normal Y) count as ground.
+const MIN_GROUND_NORMAL_Y: f32 = 0.7;
+/// Seconds to wait after starting for the colliders to reach the physics world.
+const SETTLE_SECONDS: f32 = 0.2;
+
+pub struct WalkModePlugin;
+
+impl Plugin for WalkModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WalkMode>().add_systems(
+            Update,
+            (handle_walk_keys, walk_camera)
+                .chain()
+                .run_if(in_state(crate::AppState::Editor)),
+        );
+    }
+}
+
+/// The running walk, if any.
+#[derive(Resource, Default)]
+pub struct WalkMode {
+    active: bool,
+    /// Fixed colliders standing in for the scene.
+    statics: Vec<Entity>,
+    vertical_speed: f32,
+    grounded: bool,
+    since_start: f32,
+}
+
+impl WalkMode {
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+}
+
+pub fn toggle_walk_mode(world: &mut World) {
+    if world.resource::<WalkMode>().active {
+        stop_walk_mode(world);
+    } else {
+        start_walk_mode(world);
+    }
+}
+
+pub fn start_walk_mode(world: &mut World) {
+    if world.resource::<WalkMode>().active {
+        return;
+    }
+    let statics = spawn_static_colliders(world, &[]);
+    world.resource_mut::<StatusHints>().set(
+        WALK_HINTS,
+        "Walk mode | WASD: walk | Shift: run | Space: jump | RMB: look | `/Esc: exit",
+    );
+    *world.resource_mut::<WalkMode>() = WalkMode {
+        active: true,
+        statics,
+        ..Default::default()
+    };
+}
+
+pub fn stop_walk_mode(world: &mut World) {
+    let WalkMode { statics, .. } = std::mem::take(&mut *world.resource_mut::<WalkMode>());
+    world.resource_mut::<StatusHints>().clear(WALK_HINTS);
+    for collider in statics {
+        if let Ok(collider) = world.get_entity_mut(collider) {
+            collider.despawn();
+        }
+    }
+}
+
+fn handle_walk_keys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input_focus: Res<InputFocus>,
+    walk: Res<WalkMode>,
+    mut commands: Commands,
+) {
+    if input_focus.0.is_some() {
+        return;
+    }
+    if keyboard.just_pressed(KeyCode::Backquote)
+        || (walk.active && keyboard.just_pressed(KeyCode::Escape))
+    {
+        commands.queue(toggle_walk_mode);
+    }
+}
+
+/// Shape cast of the capsule centered at `center` along `motion`: how far it gets and
+/// the normal of what stops it.
+fn cast_capsule(
+    spatial: &SpatialQuery,
+    capsule: &Collider,
+    center: Vec3,
+    motion: Vec3,
+) -> Option<(f32, Vec3)> {
+    let (direction, distance) = Dir3::new_and_length(motion).ok()?;
+    spatial
+        .cast_shape(
+            capsule,
+            center,
+            Quat::IDENTITY,
+            direction,
+            &ShapeCastConfig::from_max_distance(distance),
+            &SpatialQueryFilter::default(),
+        )
+        .map(|hit| (hit.distance, hit.normal1))
+}
+
+/// Move the capsule by `motion`, sliding along what it hits. Returns the new center and
+/// whether it ended up standing on ground.
+fn move_and_slide(
+    spatial: &SpatialQuery,
+    capsule: &Collider,
+    mut center: Vec3,
+    mut motion: Vec3,
+) -> (Vec3, bool) {
+    let mut grounded = false;
+    for _ in 0..4 {
+        let Some((distance, normal)) = cast_capsule(spatial, capsule, center, motion) else {
+            center += motion;
+            break;
+        };
+        let length = motion.length();
+        let travel = (distance - SKIN).max(0.0);
+        center += motion / length * travel;
+        if normal.y >= MIN_GROUND_NORMAL_Y {
+            grounded = true;
+        }
+        // Keep only the part of the rest that runs along the surface
+        let rest = motion / length * (length - travel);
+        motion = rest - normal * rest.dot(normal).min(0.0);
+        if motion.length_squared() < 1e-8 {
+            break;
+        }
+    }
+    (center, grounded)
+}
+
+fn walk_camera(
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut mouse_motion: MessageReader<MouseMotion>,
+    input_focus: Res<InputFocus>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut walk: ResMut<WalkMode>,
+    mut cameras: Query<
+        (&mut Transform, &jackdaw_camera::JackdawCameraSettings),
+        With<MainViewportCamera>,
+    >,
+    spatial: SpatialQuery,
+    project: Option<Res<ProjectRoot>>,
+) {
+    if !walk.active {
+        mouse_motion.read().count();
+        return;
+    }
+    let Ok((mut transform, settings)) = cameras.single_mut() else {
+        return;
+    };
+    let dt = time.delta_secs();
+    let typing = input_focus.0.is_some();
+    let focused = windows.single().is_ok_and(|w| w.focused);
+
+    // Look around with the right button held, as in fly mode
+    let mouse_delta: Vec2 = mouse_motion.read().map(|motion| motion.delta).sum();
+    if mouse.pressed(MouseButton::Right) && mouse_delta != Vec2::ZERO {
+        let (mut yaw, mut pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+        yaw -= mouse_delta.x * settings.sensitivity;
+        pitch = (pitch - mouse_delta.y * settings.sensitivity).clamp(
+            -std::f32::consts::FRAC_PI_2 + 0.01,
+            std::f32::consts::FRAC_PI_2 - 0.01,
+        );
+        transform.rotation = Quat::from_euler(EulerRot::YXZ, yaw, pitch, 0.0);
+    }
+
+    walk.since_start += dt;
+    if walk.since_start < SETTLE_SECONDS {
+        return;
+    }
+
+    let mut input = Vec3::ZERO;
+    if focused && !typing {
+        let forward = transform
+            .forward()
+            .as_vec3()
+            .with_y(0.0)
+            .normalize_or_zero();
+        let right = transform.right().as_vec3().with_y(0.0).normalize_or_zero();
+        for (key, direction) in [
+            (KeyCode::KeyW, forward),
+            (KeyCode::KeyS, -forward),
+            (KeyCode::KeyD, right),
+            (KeyCode::KeyA, -right),
+        ] {
+            if keyboard.pressed(key) {
+                input += direction;
+            }
+        }
+    }
+    let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let speed = if shift {
+        WALK_SPEED * RUN_MULTIPLIER
+    } else {
+        WALK_SPEED
+    };
+    let horizontal = input.normalize_or_zero() * speed * dt;
+
+    if walk.grounded && !typing && keyboard.just_pressed(KeyCode::Space) {
+        walk.vertical_speed = JUMP_SPEED;
+        walk.grounded = false;
+    }
+    walk.vertical_speed = (walk.vertical_speed - GRAVITY * dt).max(-50.0);
+
+    let player = project
+        .map(|project| project.settings.player)
+        .unwrap_or_default();
+    let radius = player.radius.max(0.01);
+    let height = player.height.max(2.0 * radius);
+    // Ledges up to step height are stepped onto, and the feet snap down as far to stay on
+    // slopes and stairs going down
+    let step_height = player.step_height.max(0.0);
+    let capsule = Collider::capsule(radius, height - 2.0 * radius);
+    let center_offset = player.eye_height.min(height) - height / 2.0;
+    let mut center = transform.translation - Vec3::Y * center_offset;
+
+    // Horizontal first, trying a step up when blocked
+    let (walked, _) = move_and_slide(&spatial, &capsule, center, horizontal);
+    if walk.grounded && walked.distance(center + horizontal) > 1e-3 {
+        let up = cast_capsule(&spatial, &capsule, center, Vec3::Y * step_height)
+            .map_or(step_height, |(distance, _)| (distance - SKIN).max(0.0));
+        let raised = center + Vec3::Y * up;
+        let (stepped, _) = move_and_slide(&spatial, &capsule, raised, horizontal);
+        let progress = |p: Vec3| (p - center).with_y(0.0).length();
+        center = if progress(stepped) > progress(walked) + 1e-3 {
+            stepped
+        } else {
+            walked
+        };
+    } else {
+        center = walked;
+    }
+
+    // Then gravity, snapping down over small drops while walking
+    let fall = Vec3::Y * walk.vertical_speed * dt;
+    let was_grounded = walk.grounded;
+    let (fallen, grounded) = move_and_slide(&spatial, &capsule, center, fall);
+    center = fallen;
+    walk.grounded = grounded;
+    if !grounded && was_grounded && walk.vertical_speed <= 0.0 {
+        let snap = Vec3::NEG_Y * step_height;
+        if let Some((distance, normal)) = cast_capsule(&spatial, &capsule, center, snap)
+            && normal.y >= MIN_GROUND_NORMAL_Y
+        {
+            center.y -= (distance - SKIN).max(0.0);
+            walk.grounded = true;
+        }
+    }
+    if walk.grounded {
+        walk.vertical_speed = walk.vertical_speed.max(0.0);
+    }
+
+    transform.translation = center + Vec3::Y * center_offset;
+}
@@ -0,0 +1,491 @@
+//! World Settings panel: the scene's sky, ambient light, fog, and reflections.
+//! These live on [`SceneEnvironment`] rather than on an entity, so they can be
+//! edited without selecting anything.
+
+use bevy::prelude::*;
+use jackdaw_feathers::{
+    checkbox::{CheckboxCommitEvent, CheckboxProps, checkbox},
+    color_picker::{ColorPickerCommitEvent, ColorPickerProps, color_picker},
+    combobox::{ComboBoxChangeEvent, combobox_with_selected},
+    icons::{EditorFont, IconFont},
+    panel_header,
+    text_edit::{self, TextEditCommitEvent, TextEditProps},
+    tokens,
+};
+use jackdaw_jsn::{
+    IgnoreSceneEnvironment, SceneEnvironment, SceneEnvironmentMap, SceneFog, SceneSky,
+};
+
+use crate::{
+    commands::{CommandHistory, EditorCommand},
+    viewport::MainViewportCamera,
+};
+
+pub struct WorldSettingsPlugin;
+
+impl Plugin for WorldSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SceneEnvironment>()
+            .add_observer(keep_environment_off_other_cameras)
+            .add_observer(on_world_text_commit)
+            .add_observer(on_world_checkbox_commit)
+            .add_systems(
+                Update,
+                update_world_settings_panel.run_if(in_state(crate::AppState::Editor)),
+            );
+    }
+}
+
+/// Marker for the scrollable body of the World Settings panel.
+#[derive(Component)]
+pub struct WorldSettingsBody;
+
+const SKY_LABELS: [&str; 3] = ["None", "Procedural", "Cubemap"];
+
+// --- Field binding tags ---
+
+#[derive(Component, Clone, Copy)]
+enum WorldField {
+    SkyImage,
+    SkyBrightness,
+    AmbientBrightness,
+    FogStart,
+    FogEnd,
+    EnvironmentDiffuse,
+    EnvironmentSpecular,
+    EnvironmentIntensity,
+}
+
+#[derive(Component, Clone, Copy)]
+enum WorldToggle {
+    Fog,
+    Reflections,
+}
+
+pub fn world_settings_panel() -> impl Bundle {
+    (
+        Node {
+            height: percent(100),
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        },
+        BackgroundColor(tokens::PANEL_BG),
+        children![
+            panel_header::panel_header("World Settings"),
+            (
+                WorldSettingsBody,
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: px(tokens::SPACING_SM),
+                    overflow: Overflow::scroll_y(),
+                    flex_grow: 1.0,
+                    min_height: px(0.0),
+                    padding: UiRect::all(px(tokens::SPACING_SM)),
+                    ..Default::default()
+                }
+            ),
+        ],
+    )
+}
+
+/// Only the editor viewport previews the scene environment. Other cameras (scene
+/// cameras, previews) keep their own settings, so nothing leaks into saved entities.
+fn keep_environment_off_other_cameras(
+    trigger: On<Add, Camera3d>,
+    mut commands: Commands,
+    main_cameras: Query<(), With<MainViewportCamera>>,
+) {
+    let camera = trigger.event_target();
+    if !main_cameras.contains(camera) {
+        commands.entity(camera).insert(IgnoreSceneEnvironment);
+    }
+}
+
+/// Replaces the whole [`SceneEnvironment`]; each edit in the panel is one step.
+struct SetSceneEnvironment {
+    old: SceneEnvironment,
+    new: SceneEnvironment,
+}
+
+impl EditorCommand for SetSceneEnvironment {
+    fn execute(&self, world: &mut World) {
+        world.insert_resource(self.new.clone());
+    }
+
+    fn undo(&self, world: &mut World) {
+        world.insert_resource(self.old.clone());
+    }
+
+    fn description(&self) -> &str {
+        "Edit world settings"
+    }
+}
+
+/// Apply `edit` to the scene environment as an undoable step.
+fn edit_environment(world: &mut World, edit: impl FnOnce(&mut SceneEnvironment)) {
+    let old = world.resource::<SceneEnvironment>().clone();
+    let mut new = old.clone();
+    edit(&mut new);
+    if new == old {
+        return;
+    }
+    let cmd = SetSceneEnvironment { old, new };
+    cmd.execute(world);
+    world
+        .resource_mut::<CommandHistory>()
+        .push_executed(Box::new(cmd));
+}
+
+fn update_world_settings_panel(
+    mut commands: Commands,
+    environment: Res<SceneEnvironment>,
+    body: Query<(Entity, Option<&Children>), With<WorldSettingsBody>>,
+    editor_font: Res<EditorFont>,
+    icon_font: Res<IconFont>,
+    mut shown: Local<Option<SceneEnvironment>>,
+) {
+    let Ok((body, children)) = body.single() else {
+        return;
+    };
+    if shown.as_ref() == Some(&*environment) {
+        return;
+    }
+    *shown = Some(environment.clone());
+
+    if let Some(children) = children {
+        for child in children.iter() {
+            commands.entity(child).despawn();
+        }
+    }
+
+    // --- Sky ---
+    let (_section, sky_body) = jackdaw_feathers::collapsible::collapsible_section(
+        &mut commands,
+        "Sky",
+        &icon_font.0,
+        body,
+    );
+    let sky_index = match environment.sky {
+        SceneSky::None => 0,
+        SceneSky::Procedural => 1,
+        SceneSky::Cubemap { .. } => 2,
+    };
+    let row = spawn_row(&mut commands, sky_body, "Type");
+    commands
+        .spawn((
+            combobox_with_selected(SKY_LABELS.to_vec(), sky_index),
+            ChildOf(row),
+        ))
+        .observe(|event: On<ComboBoxChangeEvent>, mut commands: Commands| {
+            let selected = event.selected;
+            commands.queue(move |world: &mut World| {
+                edit_environment(world, |env| {
+                    env.sky = match selected {
+                        1 => SceneSky::Procedural,
+                        2 => match &env.sky {
+                            SceneSky::Cubemap { .. } => env.sky.clone(),
+                            _ => SceneSky::Cubemap {
+                                image: String::new(),
+                                brightness: 1000.0,
+                            },
+                        },
+                        _ => SceneSky::None,
+                    };
+                });
+            });
+        });
+    if let SceneSky::Cubemap { image, brightness } = &environment.sky {
+        spawn_path_field(
+            &mut commands,
+            sky_body,
+            "Image",
+            image,
+            WorldField::SkyImage,
+        );
+        spawn_number_field(
+            &mut commands,
+            sky_body,
+            "Brightness",
+            *brightness,
+            WorldField::SkyBrightness,
+        );
+    }
+
+    // --- Ambient light ---
+    let (_section, ambient_body) = jackdaw_feathers::collapsible::collapsible_section(
+        &mut commands,
+        "Ambient Light",
+        &icon_font.0,
+        body,
+    );
+    let row = spawn_row(&mut commands, ambient_body, "Color");
+    commands
+        .spawn((
+            color_picker(ColorPickerProps::new().with_color(to_rgba(environment.ambient_color))),
+            ChildOf(row),
+        ))
+        .observe(
+            |event: On<ColorPickerCommitEvent>, mut commands: Commands| {
+                let color = from_rgba(event.color);
+                commands.queue(move |world: &mut World| {
+                    edit_environment(world, |env| env.ambient_color = color);
+                });
+            },
+        );
+    spawn_number_field(
+        &mut commands,
+        ambient_body,
+        "Brightness",
+        environment.ambient_brightness,
+        WorldField::AmbientBrightness,
+    );
+
+    // --- Fog ---
+    let (_section, fog_body) = jackdaw_feathers::collapsible::collapsible_section(
+        &mut commands,
+        "Fog",
+        &icon_font.0,
+        body,
+    );
+    commands.spawn((
+        checkbox(
+            CheckboxProps::new("Enabled").checked(environment.fog.is_some()),
+            &editor_font.0,
+            &icon_font.0,
+        ),
+        WorldToggle::Fog,
+        ChildOf(fog_body),
+    ));
+    if let Some(fog) = &environment.fog {
+        let row = spawn_row(&mut commands, fog_body, "Color");
+        commands
+            .spawn((
+                color_picker(ColorPickerProps::new().with_color(to_rgba(fog.color))),
+                ChildOf(row),
+            ))
+            .observe(
+                |event: On<ColorPickerCommitEvent>, mut commands: Commands| {
+                    let color = from_rgba(event.color);
+                    commands.queue(move |world: &mut World| {
+                        edit_environment(world, |env| {
+                            if let Some(fog) = &mut env.fog {
+                                fog.color = color;
+                            }
+                        });
+                    });
+                },
+            );
+        spawn_number_field(
+            &mut commands,
+            fog_body,
+            "Start",
+            fog.start,
+            WorldField::FogStart,
+        );
+        spawn_number_field(&mut commands, fog_body, "End", fog.end, WorldField::FogEnd);
+    }
+
+    // --- Reflections ---
+    let (_section, env_body) = jackdaw_feathers::collapsible::collapsible_section(
+        &mut commands,
+        "Reflections",
+        &icon_font.0,
+        body,
+    );
+    commands.spawn((
+        checkbox(
+            CheckboxProps::new("Environment Map").checked(environment.environment_map.is_some()),
+            &editor_font.0,
+            &icon_font.0,
+        ),
+        WorldToggle::Reflections,
+        ChildOf(env_body),
+    ));
+    if let Some(map) = &environment.environment_map {
+        spawn_path_field(
+            &mut commands,
+            env_body,
+            "Diffuse",
+            &map.diffuse,
+            WorldField::EnvironmentDiffuse,
+        );
+        spawn_path_field(
+            &mut commands,
+            env_body,
+            "Specular",
+            &map.specular,
+            WorldField::EnvironmentSpecular,
+        );
+        spawn_number_field(
+            &mut commands,
+            env_body,
+            "Intensity",
+            map.intensity,
+            WorldField::EnvironmentIntensity,
+        );
+    }
+}
+
+fn on_world_text_commit(
+    event: On<TextEditCommitEvent>,
+    bindings: Query<&WorldField>,
+    child_of_query: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    // Walk up from committed entity to find a field binding
+    let mut current = event.entity;
+    for _ in 0..4 {
+        let Ok(child_of) = child_of_query.get(current) else {
+            return;
+        };
+        let parent = child_of.parent();
+        if let Ok(&field) = bindings.get(parent) {
+            let text = event.text.trim().to_string();
+            let value: f32 = text.parse().unwrap_or(0.0);
+            commands.queue(move |world: &mut World| {
+                edit_environment(world, |env| match field {
+                    WorldField::SkyImage => {
+                        if let SceneSky::Cubemap { image, .. } = &mut env.sky {
+                            *image = text;
+                        }
+                    }
+                    WorldField::SkyBrightness => {
+                        if let SceneSky::Cubemap { brightness, .. } = &mut env.sky {
+                            *brightness = value;
+                        }
+                    }
+                    WorldField::AmbientBrightness => env.ambient_brightness = value,
+                    WorldField::FogStart => {
+                        if let Some(fog) = &mut env.fog {
+                            fog.start = value;
+                        }
+                    }
+                    WorldField::FogEnd => {
+                        if let Some(fog) = &mut env.fog {
+                            fog.end = value;
+                        }
+                    }
+                    WorldField::EnvironmentDiffuse => {
+                        if let Some(map) = &mut env.environment_map {
+                            map.diffuse = text;
+                        }
+                    }
+                    WorldField::EnvironmentSpecular => {
+                        if let Some(map) = &mut env.environment_map {
+                            map.specular = text;
+                        }
+                    }
+                    WorldField::EnvironmentIntensity => {
+                        if let Some(map) = &mut env.environment_map {
+                            map.intensity = value;
+                        }
+                    }
+                });
+            });
+            return;
+        }
+        current = parent;
+    }
+}
+
+fn on_world_checkbox_commit(
+    event: On<CheckboxCommitEvent>,
+    toggles: Query<&WorldToggle>,
+    mut commands: Commands,
+) {
+    let Ok(&toggle) = toggles.get(event.entity) else {
+        return;
+    };
+    let checked = event.checked;
+    commands.queue(move |world: &mut World| {
+        edit_environment(world, |env| match toggle {
+            WorldToggle::Fog => env.fog = checked.then(SceneFog::default),
+            WorldToggle::Reflections => {
+                env.environment_map = checked.then(SceneEnvironmentMap::default)
+            }
+        });
+    });
+}
+
+// --- Spawn helpers ---
+
+fn to_rgba(color: Color) -> [f32; 4] {
+    let srgba = color.to_srgba();
+    [srgba.red, srgba.green, srgba.blue, srgba.alpha]
+}
+
+fn from_rgba(rgba: [f32; 4]) -> Color {
+    Color::srgba(rgba[0], rgba[1], rgba[2], rgba[3])
+}
+
+/// A labeled row; the caller adds the value widget to it.
+fn spawn_row(commands: &mut Commands, parent: Entity, label: &str) -> Entity {
+    let row = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: px(tokens::SPACING_XS),
+                width: Val::Percent(100.0),
+                ..Default::default()
+            },
+            ChildOf(parent),
+        ))
+        .id();
+    commands.spawn((
+        Text::new(label),
+        TextFont {
+            font_size: tokens::FONT_SM,
+            ..Default::default()
+        },
+        TextColor(tokens::TEXT_SECONDARY),
+        Node {
+            min_width: px(80.0),
+            flex_shrink: 0.0,
+            ..Default::default()
+        },
+        ChildOf(row),
+    ));
+    row
+}
+
+fn spawn_number_field(
+    commands: &mut Commands,
+    parent: Entity,
+    label: &str,
+    value: f32,
+    field: WorldField,
+) {
+    let row = spawn_row(commands, parent, label);
+    commands.spawn((
+        text_edit::text_edit(
+            TextEditProps::default()
+                .numeric_f32()
+                .grow()
+                .with_default_value(value.to_string()),
+        ),
+        field,
+        ChildOf(row),
+    ));
+}
+
+fn spawn_path_field(
+    commands: &mut Commands,
+    parent: Entity,
+    label: &str,
+    path: &str,
+    field: WorldField,
+) {
+    let row = spawn_row(commands, parent, label);
+    commands.spawn((
+        text_edit::text_edit(
+            TextEditProps::default()
+                .with_placeholder("path/to/cubemap.ktx2")
+                .allow_empty()
+                .grow()
+                .with_default_value(path),
+        ),
+        field,
+        ChildOf(row),
+    ));
+}